@@ -36,9 +36,12 @@ function assertIncludes(filePath, snippet) {
 function assertNoEndpointLeak(filePath) {
   const relPath = path.relative(root, filePath);
   const text = fs.readFileSync(filePath, 'utf8');
-  const leaks = ['/v1/health', '/v1/db/schema-version', '/v1/query/ask'].filter((needle) =>
-    text.includes(needle)
-  );
+  const leaks = [
+    '/v1/health',
+    '/v1/db/schema-version',
+    '/v1/query/ask',
+    '/v1/query/recall',
+  ].filter((needle) => text.includes(needle));
   if (leaks.length > 0) {
     fail(`${relPath} contains MemoryKernel endpoint strings outside adapter: ${leaks.join(', ')}`);
   }
@@ -53,6 +56,7 @@ function main() {
   assertIncludes(adapterPath, '/v1/health');
   assertIncludes(adapterPath, '/v1/db/schema-version');
   assertIncludes(adapterPath, 'memory_kernel_query_ask');
+  assertIncludes(adapterPath, 'memory_kernel_policy_gate');
 
   // Disallow service endpoint leaks outside adapter boundary in Rust sources.
   const rustFiles = walkFiles(rustRoot, '.rs');
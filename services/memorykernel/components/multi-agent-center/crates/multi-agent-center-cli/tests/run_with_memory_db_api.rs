@@ -71,6 +71,7 @@ fn seed_memory_kernel_db(memory_db: &Path) {
             resource: "repo".to_string(),
             effect: ConstraintEffect::Allow,
             note: None,
+            expires_at: None,
             memory_id: None,
             version: 1,
             writer: "test".to_string(),
@@ -85,12 +86,16 @@ fn seed_memory_kernel_db(memory_db: &Path) {
             effective_at: None,
             supersedes: Vec::new(),
             contradicts: Vec::new(),
+            tags: Vec::new(),
         })
         .is_ok());
     assert!(api
         .add_summary(AddSummaryRequest {
             record_type: RecordType::Decision,
             summary: "repo policy decision".to_string(),
+            task_status: None,
+            risk_severity: None,
+            assumption_status: None,
             memory_id: None,
             version: 1,
             writer: "test".to_string(),
@@ -105,6 +110,7 @@ fn seed_memory_kernel_db(memory_db: &Path) {
             effective_at: None,
             supersedes: Vec::new(),
             contradicts: Vec::new(),
+            tags: Vec::new(),
         })
         .is_ok());
 }
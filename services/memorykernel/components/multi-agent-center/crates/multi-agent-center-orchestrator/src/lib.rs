@@ -7,7 +7,7 @@ use anyhow::{anyhow, Context, Result};
 use memory_kernel_api::{AskRequest, MemoryKernelApi, RecallRequest};
 use memory_kernel_core::{
     build_context_package, build_recall_context_package, default_recall_record_types, MemoryRecord,
-    QueryRequest, RecordType,
+    PolicyRuleset, QueryRequest, RecordType,
 };
 use memory_kernel_outcome_core::{
     apply_as_of_decay, gate_memory, parse_rfc3339_utc, GateDecision as OutcomeGateDecision,
@@ -256,12 +256,15 @@ impl ContextPackageSource for ApiMemoryKernelContextSource {
                     action: request.action,
                     resource: request.resource,
                     as_of: Some(request.as_of),
+                    ruleset: PolicyRuleset::default(),
+                    conflict_resolution: None,
                 })?,
                 StepContextQuery::Recall { text, record_types } => {
                     self.api.query_recall(RecallRequest {
                         text,
                         record_types,
                         as_of: Some(as_of),
+                        tags: Vec::new(),
                     })?
                 }
             };
@@ -1704,10 +1707,10 @@ mod tests {
     };
     use memory_kernel_api::{AddConstraintRequest, AddSummaryRequest, MemoryKernelApi};
     use memory_kernel_core::{
-        default_recall_record_types, Answer, AnswerResult, Authority, ConstraintEffect,
-        ConstraintPayload, ConstraintScope, ContextItem, ContextPackage, DecisionPayload,
-        DeterminismMetadata, MemoryId, MemoryPayload, MemoryRecord, MemoryVersionId, QueryRequest,
-        RecordType, TruthStatus, Why,
+        default_recall_record_types, Answer, AnswerResult, Authority, ConflictResolution,
+        ConstraintEffect, ConstraintPayload, ConstraintScope, ContextItem, ContextPackage,
+        DecisionPayload, DeterminismMetadata, MemoryId, MemoryPayload, MemoryRecord,
+        MemoryVersionId, QueryRequest, RecordType, TruthStatus, Why,
     };
     use multi_agent_center_domain::{ContextPackageEnvelope, StepId, StepStatus};
     use multi_agent_center_trace_core::TraceStore;
@@ -1744,6 +1747,7 @@ mod tests {
             },
             supersedes: Vec::new(),
             contradicts: Vec::new(),
+            tags: Vec::new(),
             payload: MemoryPayload::Constraint(ConstraintPayload {
                 scope: ConstraintScope {
                     actor: "*".to_string(),
@@ -1752,6 +1756,7 @@ mod tests {
                 },
                 effect: ConstraintEffect::Allow,
                 note: None,
+                expires_at: None,
             }),
         }
     }
@@ -1773,7 +1778,7 @@ mod tests {
             RecordType::Outcome => MemoryPayload::Outcome(memory_kernel_core::OutcomePayload {
                 summary: summary.to_string(),
             }),
-            RecordType::Constraint => panic!("fixture_summary_record does not support constraint"),
+            other => panic!("fixture_summary_record does not support {}", other.as_str()),
         };
 
         MemoryRecord {
@@ -1794,6 +1799,7 @@ mod tests {
             },
             supersedes: Vec::new(),
             contradicts: Vec::new(),
+            tags: Vec::new(),
             payload,
         }
     }
@@ -1846,6 +1852,7 @@ mod tests {
                 ruleset_version: "mk.v1".to_string(),
                 snapshot_id: "snapshot".to_string(),
                 tie_breakers: vec!["fixture".to_string()],
+                conflict_resolution: ConflictResolution::default(),
             },
             answer: Answer {
                 result: AnswerResult::Allow,
@@ -2449,6 +2456,7 @@ defaults:
                 resource: "repo".to_string(),
                 effect: ConstraintEffect::Allow,
                 note: None,
+                expires_at: None,
                 memory_id: None,
                 version: 1,
                 writer: "test".to_string(),
@@ -2463,12 +2471,16 @@ defaults:
                 effective_at: None,
                 supersedes: Vec::new(),
                 contradicts: Vec::new(),
+                tags: Vec::new(),
             })
             .is_ok());
         assert!(api
             .add_summary(AddSummaryRequest {
                 record_type: RecordType::Decision,
                 summary: "repo policy decision".to_string(),
+                task_status: None,
+                risk_severity: None,
+                assumption_status: None,
                 memory_id: None,
                 version: 1,
                 writer: "test".to_string(),
@@ -2483,6 +2495,7 @@ defaults:
                 effective_at: None,
                 supersedes: Vec::new(),
                 contradicts: Vec::new(),
+                tags: Vec::new(),
             })
             .is_ok());
 
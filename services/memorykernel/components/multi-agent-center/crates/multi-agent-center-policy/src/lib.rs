@@ -117,8 +117,8 @@ pub fn apply_context_permissions(
 mod tests {
     use super::apply_context_permissions;
     use memory_kernel_core::{
-        Answer, AnswerResult, Authority, ContextItem, ContextPackage, DeterminismMetadata,
-        MemoryId, MemoryVersionId, QueryRequest, RecordType, TruthStatus, Why,
+        Answer, AnswerResult, Authority, ConflictResolution, ContextItem, ContextPackage,
+        DeterminismMetadata, MemoryId, MemoryVersionId, QueryRequest, RecordType, TruthStatus, Why,
     };
     use multi_agent_center_domain::{ContextPackageEnvelope, EffectivePermissions};
 
@@ -186,6 +186,7 @@ mod tests {
                 ruleset_version: "memory_kernel.v1".to_string(),
                 snapshot_id: "snapshot".to_string(),
                 tie_breakers: vec!["fixture".to_string()],
+                conflict_resolution: ConflictResolution::default(),
             },
             answer: Answer {
                 result: AnswerResult::Allow,
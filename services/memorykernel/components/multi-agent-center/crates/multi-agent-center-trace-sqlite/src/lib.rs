@@ -1231,8 +1231,8 @@ fn parse_rfc3339(value: &str) -> Result<OffsetDateTime> {
 mod tests {
     use super::SqliteTraceStore;
     use memory_kernel_core::{
-        Answer, AnswerResult, Authority, ContextItem, ContextPackage, DeterminismMetadata,
-        MemoryId, MemoryVersionId, QueryRequest, RecordType, TruthStatus, Why,
+        Answer, AnswerResult, Authority, ConflictResolution, ContextItem, ContextPackage,
+        DeterminismMetadata, MemoryId, MemoryVersionId, QueryRequest, RecordType, TruthStatus, Why,
     };
     use multi_agent_center_domain::{
         ContextPackageEnvelope, GateDecision, GateDecisionRecord, GateKind, RunId, RunRecord,
@@ -1323,6 +1323,7 @@ mod tests {
                 ruleset_version: "mk.v1".to_string(),
                 snapshot_id: "snap".to_string(),
                 tie_breakers: vec!["x".to_string()],
+                conflict_resolution: ConflictResolution::default(),
             },
             answer: Answer {
                 result: AnswerResult::Allow,
@@ -2020,10 +2020,7 @@ mod tests {
 
         let mut fresh = fixture_store();
         seed_memory_row(&fresh);
-        for item in baseline_events
-            .into_iter()
-            .chain(incremental_events.into_iter())
-        {
+        for item in baseline_events.into_iter().chain(incremental_events) {
             let mut input = fixture_event_input(item.event_type);
             input.event_id = Some(item.event_id);
             input.occurred_at = item.occurred_at;
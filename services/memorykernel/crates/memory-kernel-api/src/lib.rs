@@ -1,13 +1,26 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
 
 use anyhow::{anyhow, Result};
 use memory_kernel_core::{
-    build_context_package, build_recall_context_package, default_recall_record_types, Authority,
-    ConstraintEffect, ConstraintPayload, ConstraintScope, ContextPackage, DecisionPayload,
-    EventPayload, LinkType, MemoryId, MemoryPayload, MemoryRecord, MemoryVersionId,
-    PreferencePayload, QueryRequest, RecordType, TruthStatus,
+    build_context_package_with_ruleset, build_recall_context_package_with_decay,
+    build_semantic_recall_context_package, default_recall_record_types, explain_record,
+    record_recall_text, retain_tagged, simulate_constraint, AssumptionPayload, AssumptionStatus,
+    Authority, ConfidenceDecay, ConflictResolution, ConstraintEffect, ConstraintPayload,
+    ConstraintScope, ContextPackage, DecisionPayload, Embedder, EventPayload, LinkType, MemoryId,
+    MemoryPayload, MemoryRecord, MemoryVersionId, PolicyRuleset, PreferencePayload, QueryRequest,
+    RecordExplanation, RecordType, RiskPayload, RiskSeverity, SemanticRecall, SimulationOutcome,
+    TaskPayload, TaskStatus, TruthStatus, DEFAULT_MIN_SEMANTIC_SIMILARITY,
 };
-use memory_kernel_store_sqlite::{SchemaStatus, SqliteStore};
+use memory_kernel_store_api::{
+    summarize_query_metrics, ContextPackageFilter, ContextPackagePage, ContradictionReport,
+    ExportManifest, FollowingOutcome, ImportSummary, MaintenanceReport, MemoryStore,
+    PackageOutcome, QueryMetric, QueryMode, QueryModeStats, RecordFilter, RecordPage, Redaction,
+    SchemaStatus, DEFAULT_NAMESPACE, DEFAULT_RECALL_CANDIDATE_LIMIT,
+};
+use memory_kernel_store_postgres::PostgresStore;
+use memory_kernel_store_sqlite::{SqliteStore, StoreKey};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use time::OffsetDateTime;
@@ -32,6 +45,8 @@ pub struct AddConstraintRequest {
     pub resource: String,
     pub effect: ConstraintEffect,
     pub note: Option<String>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
     pub memory_id: Option<MemoryId>,
     pub version: u32,
     pub writer: String,
@@ -48,12 +63,23 @@ pub struct AddConstraintRequest {
     pub effective_at: Option<OffsetDateTime>,
     pub supersedes: Vec<MemoryVersionId>,
     pub contradicts: Vec<MemoryVersionId>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AddSummaryRequest {
     pub record_type: RecordType,
     pub summary: String,
+    /// Task status; defaults to `open` for task records.
+    #[serde(default)]
+    pub task_status: Option<TaskStatus>,
+    /// Risk severity; required for risk records.
+    #[serde(default)]
+    pub risk_severity: Option<RiskSeverity>,
+    /// Assumption status; defaults to `unverified` for assumption records.
+    #[serde(default)]
+    pub assumption_status: Option<AssumptionStatus>,
     pub memory_id: Option<MemoryId>,
     pub version: u32,
     pub writer: String,
@@ -70,6 +96,8 @@ pub struct AddSummaryRequest {
     pub effective_at: Option<OffsetDateTime>,
     pub supersedes: Vec<MemoryVersionId>,
     pub contradicts: Vec<MemoryVersionId>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -90,6 +118,61 @@ pub struct AddLinkResult {
     pub justification: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RedactRequest {
+    pub memory_version_id: MemoryVersionId,
+    pub writer: String,
+    pub justification: String,
+}
+
+/// Outcome record to attach to the context package that informed a decision, for
+/// [`MemoryKernelApi::link_outcome`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LinkOutcomeRequest {
+    pub context_package_id: String,
+    pub outcome_memory_version_id: MemoryVersionId,
+    pub writer: String,
+    pub justification: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OutcomesFollowingRequest {
+    pub memory_version_id: MemoryVersionId,
+}
+
+/// Outcomes linked to context packages that selected `memory_version_id`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutcomesFollowingResult {
+    pub memory_version_id: MemoryVersionId,
+    pub outcomes: Vec<FollowingOutcome>,
+}
+
+/// Records derived from one source document, for [`MemoryKernelApi::records_by_source`]. When
+/// both selectors are set, records must match both.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SourceLookupRequest {
+    /// Match records whose `provenance.source_uri` starts with this text.
+    pub source_uri_prefix: Option<String>,
+    /// Match records whose `provenance.source_hash` equals this digest.
+    pub source_hash: Option<String>,
+    /// Resume after this record, as returned in [`RecordPage::next_after`].
+    pub after: Option<MemoryVersionId>,
+    /// Maximum number of records to return; all matching records when unset.
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AddBatchRequest {
+    pub records: Vec<MemoryRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AddBatchResult {
+    pub written: usize,
+    pub memory_version_ids: Vec<MemoryVersionId>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AskRequest {
     pub text: String,
@@ -98,6 +181,33 @@ pub struct AskRequest {
     pub resource: String,
     #[serde(with = "time::serde::rfc3339::option")]
     pub as_of: Option<OffsetDateTime>,
+    /// Precedence rules used to order candidates and derive the answer.
+    #[serde(default)]
+    pub ruleset: PolicyRuleset,
+    /// How allow/deny conflicts the ruleset cannot order are answered; defaults to the
+    /// handle's [`MemoryKernelApi::with_conflict_resolution`] setting.
+    #[serde(default)]
+    pub conflict_resolution: Option<ConflictResolution>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SimulateRequest {
+    pub constraint: AddConstraintRequest,
+    pub queries: Vec<AskRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SimulateResult {
+    /// The proposed constraint record as it would be written; it is not persisted.
+    pub proposed: MemoryRecord,
+    pub outcomes: Vec<SimulationOutcome>,
+    pub changed: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExplainRequest {
+    pub memory_version_id: MemoryVersionId,
+    pub query: AskRequest,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -106,21 +216,186 @@ pub struct RecallRequest {
     pub record_types: Vec<RecordType>,
     #[serde(with = "time::serde::rfc3339::option")]
     pub as_of: Option<OffsetDateTime>,
+    /// Only recall records carrying every one of these tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Handle to one memory kernel store: a `SQLite` file, a Postgres database, or any other
+/// [`MemoryStore`] such as an in-memory test store.
+///
+/// Clones share a single lazily opened connection, serialized by a mutex, and pending
+/// migrations are applied at most once per handle, before the first call that needs the
+/// current schema. Every call is scoped to the handle's namespace.
+pub struct MemoryKernelApi<S: MemoryStore = Box<dyn MemoryStore>> {
+    opener: Arc<StoreOpener<S>>,
+    store: Arc<Mutex<StoreHandle<S>>>,
+    namespace: String,
+    confidence_decay: Option<ConfidenceDecay>,
+    conflict_resolution: ConflictResolution,
+    store_key: Option<StoreKey>,
+}
+
+/// Opens the store on first use, given the handle's store key.
+type StoreOpener<S> = dyn Fn(Option<&StoreKey>) -> Result<S> + Send + Sync;
+
+impl<S: MemoryStore> Clone for MemoryKernelApi<S> {
+    fn clone(&self) -> Self {
+        Self {
+            opener: Arc::clone(&self.opener),
+            store: Arc::clone(&self.store),
+            namespace: self.namespace.clone(),
+            confidence_decay: self.confidence_decay.clone(),
+            conflict_resolution: self.conflict_resolution,
+            store_key: self.store_key.clone(),
+        }
+    }
+}
+
+impl<S: MemoryStore> std::fmt::Debug for MemoryKernelApi<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryKernelApi")
+            .field("store", &self.store)
+            .field("namespace", &self.namespace)
+            .field("confidence_decay", &self.confidence_decay)
+            .field("conflict_resolution", &self.conflict_resolution)
+            .field("store_key", &self.store_key)
+            .finish_non_exhaustive()
+    }
 }
 
-#[derive(Debug, Clone)]
-pub struct MemoryKernelApi {
-    db_path: PathBuf,
+#[derive(Debug)]
+struct StoreHandle<S> {
+    store: Option<S>,
+    migrated: bool,
 }
 
 impl MemoryKernelApi {
+    /// Create a handle without touching the database; it is opened on first use.
     #[must_use]
     pub fn new(db_path: PathBuf) -> Self {
-        Self { db_path }
+        Self::with_opener(move |key| {
+            Ok(Box::new(SqliteStore::open_with_key(&db_path, key)?) as Box<dyn MemoryStore>)
+        })
+    }
+
+    /// Create a handle on the Postgres database named by a `postgres://` URL without
+    /// connecting; several service instances may share the database.
+    #[must_use]
+    pub fn new_postgres(url: &str) -> Self {
+        let url = url.to_string();
+        Self::with_opener(move |key| {
+            if key.is_some() {
+                return Err(anyhow!("store keys apply only to SQLite stores"));
+            }
+            Ok(Box::new(PostgresStore::connect(&url)?) as Box<dyn MemoryStore>)
+        })
+    }
+
+    /// Open the database as an encrypted store unlocked with `key`. Must be set before the
+    /// first call, since the connection is opened lazily and then reused. Only `SQLite` stores
+    /// can be encrypted.
+    #[must_use]
+    pub fn with_store_key(mut self, key: StoreKey) -> Self {
+        self.store_key = Some(key);
+        self
+    }
+
+    /// Open the database and apply pending migrations once, up front.
+    ///
+    /// # Errors
+    /// Returns an error when the `SQLite` database cannot be opened or migrated.
+    pub fn open(db_path: PathBuf) -> Result<Self> {
+        let api = Self::new(db_path);
+        api.with_migrated_store(|_| Ok(()))?;
+        Ok(api)
+    }
+}
+
+impl<S: MemoryStore> MemoryKernelApi<S> {
+    /// Create a handle over an already opened `store`, e.g. an in-memory test store.
+    #[must_use]
+    pub fn from_store(store: S) -> Self {
+        let api = Self::with_opener(|_| Err(anyhow!("store is already open")));
+        api.store.lock().unwrap_or_else(PoisonError::into_inner).store = Some(store);
+        api
+    }
+
+    fn with_opener(
+        opener: impl Fn(Option<&StoreKey>) -> Result<S> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            opener: Arc::new(opener),
+            store: Arc::new(Mutex::new(StoreHandle { store: None, migrated: false })),
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            confidence_decay: None,
+            conflict_resolution: ConflictResolution::default(),
+            store_key: None,
+        }
     }
 
-    fn open_store(&self) -> Result<SqliteStore> {
-        SqliteStore::open(&self.db_path)
+    /// Scope records and context packages to `namespace`; clones of the returned handle still
+    /// share the connection. An invalid namespace is reported by the first call that uses it.
+    #[must_use]
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        namespace.clone_into(&mut self.namespace);
+        self
+    }
+
+    /// Namespace every call on this handle is scoped to.
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Snapshot extra for a non-default namespace, so equal queries over empty namespaces do not
+    /// share context package ids; `None` keeps default-namespace snapshot ids unchanged.
+    fn namespace_extra(&self) -> Option<String> {
+        (self.namespace != DEFAULT_NAMESPACE).then(|| format!("namespace={}", self.namespace))
+    }
+
+    /// Decay candidate confidence by truth status in policy, recall, and simulation queries.
+    #[must_use]
+    pub fn with_confidence_decay(mut self, decay: ConfidenceDecay) -> Self {
+        self.confidence_decay = Some(decay);
+        self
+    }
+
+    /// Answer allow/deny conflicts with `resolution` in policy and simulation queries that do
+    /// not choose their own.
+    #[must_use]
+    pub fn with_conflict_resolution(mut self, resolution: ConflictResolution) -> Self {
+        self.conflict_resolution = resolution;
+        self
+    }
+
+    fn confidence_decay_extra(&self) -> Option<String> {
+        self.confidence_decay.as_ref().and_then(ConfidenceDecay::snapshot_extra)
+    }
+
+    fn with_store<T>(&self, op: impl FnOnce(&mut S) -> Result<T>) -> Result<T> {
+        self.lock_store(false, op)
+    }
+
+    fn with_migrated_store<T>(&self, op: impl FnOnce(&mut S) -> Result<T>) -> Result<T> {
+        self.lock_store(true, op)
+    }
+
+    fn lock_store<T>(&self, migrate: bool, op: impl FnOnce(&mut S) -> Result<T>) -> Result<T> {
+        // A panic mid-call leaves nothing half-applied: open transactions roll back on drop.
+        let mut guard = self.store.lock().unwrap_or_else(PoisonError::into_inner);
+        let handle = &mut *guard;
+        let store = match handle.store.take() {
+            Some(store) => store,
+            None => (self.opener)(self.store_key.as_ref())?,
+        };
+        let store = handle.store.insert(store);
+        store.set_namespace(&self.namespace)?;
+        if migrate && !handle.migrated {
+            store.migrate()?;
+            handle.migrated = true;
+        }
+        op(store)
     }
 
     /// Inspect schema status without mutating data.
@@ -128,8 +403,15 @@ impl MemoryKernelApi {
     /// # Errors
     /// Returns an error when the `SQLite` database cannot be opened or queried.
     pub fn schema_status(&self) -> Result<SchemaStatus> {
-        let store = self.open_store()?;
-        store.schema_status()
+        self.with_store(|store| store.schema_status())
+    }
+
+    /// Checkpoint the WAL and optimize, optionally vacuuming into a new file.
+    ///
+    /// # Errors
+    /// Returns an error when the database cannot be opened or maintenance fails.
+    pub fn maintenance(&self, vacuum_into: Option<&Path>) -> Result<MaintenanceReport> {
+        self.with_store(|store| store.maintenance(vacuum_into))
     }
 
     /// Apply pending migrations, or return planned versions for dry-run mode.
@@ -137,7 +419,10 @@ impl MemoryKernelApi {
     /// # Errors
     /// Returns an error when migration planning or execution fails.
     pub fn migrate(&self, dry_run: bool) -> Result<MigrateResult> {
-        let mut store = self.open_store()?;
+        self.with_store(|store| Self::migrate_store(store, dry_run))
+    }
+
+    fn migrate_store(store: &mut S, dry_run: bool) -> Result<MigrateResult> {
         let before = store.schema_status()?;
         if dry_run {
             return Ok(MigrateResult {
@@ -170,23 +455,121 @@ impl MemoryKernelApi {
     /// # Errors
     /// Returns an error when record validation or persistence fails.
     pub fn add_constraint(&self, input: AddConstraintRequest) -> Result<MemoryRecord> {
-        let mut store = self.open_store()?;
-        store.migrate()?;
-        let record = build_constraint_record(input);
-        store.write_record(&record)?;
-        Ok(record)
+        self.with_migrated_store(|store| {
+            let record = build_constraint_record(input);
+            store.write_record(&record)?;
+            Ok(record)
+        })
     }
 
-    /// Add one summary-backed memory record (`decision`, `preference`, `event`, or `outcome`).
+    /// Add one summary-backed memory record (any record type other than `constraint`).
     ///
     /// # Errors
     /// Returns an error when an unsupported record type is provided, or persistence fails.
     pub fn add_summary(&self, input: AddSummaryRequest) -> Result<MemoryRecord> {
-        let mut store = self.open_store()?;
-        store.migrate()?;
-        let record = build_summary_record(input)?;
-        store.write_record(&record)?;
-        Ok(record)
+        self.with_migrated_store(|store| {
+            let record = build_summary_record(input)?;
+            store.write_record(&record)?;
+            Ok(record)
+        })
+    }
+
+    /// Add prebuilt memory records atomically, in request order.
+    ///
+    /// # Errors
+    /// Returns an error when the batch is empty, any record fails validation, or persistence
+    /// fails. Nothing is written on error.
+    pub fn add_batch(&self, input: &AddBatchRequest) -> Result<AddBatchResult> {
+        if input.records.is_empty() {
+            return Err(anyhow!("records MUST contain at least one record"));
+        }
+        self.with_migrated_store(|store| {
+            store.write_records_batch(&input.records)?;
+            Ok(AddBatchResult {
+                written: input.records.len(),
+                memory_version_ids: input
+                    .records
+                    .iter()
+                    .map(|record| record.memory_version_id)
+                    .collect(),
+            })
+        })
+    }
+
+    /// Retract `memory_version_id` by appending a `retracted` copy as the memory's next version.
+    ///
+    /// The new version keeps the payload, provenance, authority, confidence, and tags, supersedes
+    /// the retracted version, and takes effect now.
+    ///
+    /// # Errors
+    /// Returns an error when the version does not exist, is already superseded, or persistence
+    /// fails.
+    pub fn retract(
+        &self,
+        memory_version_id: MemoryVersionId,
+        writer: &str,
+        justification: &str,
+    ) -> Result<MemoryRecord> {
+        self.with_migrated_store(|store| {
+            store.write_successor(
+                memory_version_id,
+                Box::new(|previous, version| {
+                    let mut payload = previous.payload.clone();
+                    // A retraction applies indefinitely, and a stale expiry would fail validation.
+                    if let MemoryPayload::Constraint(constraint) = &mut payload {
+                        constraint.expires_at = None;
+                    }
+                    Ok(successor_record(
+                        previous,
+                        version,
+                        payload,
+                        TruthStatus::Retracted,
+                        writer,
+                        justification,
+                    ))
+                }),
+            )
+        })
+    }
+
+    /// Replace `old_version_id` with `payload` as the memory's next version.
+    ///
+    /// The new version keeps the previous truth status, provenance, authority, confidence, and
+    /// tags, supersedes the old version, and takes effect now.
+    ///
+    /// # Errors
+    /// Returns an error when the payload changes record type, the old version does not exist or
+    /// is already superseded, or persistence fails.
+    pub fn supersede(
+        &self,
+        old_version_id: MemoryVersionId,
+        payload: MemoryPayload,
+        writer: &str,
+        justification: &str,
+    ) -> Result<MemoryRecord> {
+        self.with_migrated_store(|store| {
+            store.write_successor(
+                old_version_id,
+                Box::new(|previous, version| {
+                    let (from, to) = (previous.payload.record_type(), payload.record_type());
+                    if from != to {
+                        return Err(anyhow!(
+                            "supersede cannot change record_type from {} to {}",
+                            from.as_str(),
+                            to.as_str()
+                        ));
+                    }
+                    Ok(successor_record(
+                        previous,
+                        version,
+                        payload,
+                        previous.truth_status,
+                        writer,
+                        justification,
+                    ))
+                }),
+            )
+        })
     }
 
     /// Add one lineage link between memory versions.
@@ -194,60 +577,262 @@ impl MemoryKernelApi {
     /// # Errors
     /// Returns an error when link persistence fails.
     pub fn add_link(&self, input: AddLinkRequest) -> Result<AddLinkResult> {
-        let mut store = self.open_store()?;
-        store.migrate()?;
-        store.add_link(
-            input.from,
-            input.to,
-            input.relation,
-            &input.writer,
-            &input.justification,
-        )?;
+        self.with_migrated_store(|store| {
+            store.add_link(
+                input.from,
+                input.to,
+                input.relation,
+                &input.writer,
+                &input.justification,
+            )?;
+
+            Ok(AddLinkResult {
+                from_memory_version_id: input.from,
+                to_memory_version_id: input.to,
+                relation: input.relation,
+                writer: input.writer,
+                justification: input.justification,
+            })
+        })
+    }
+
+    /// Scrub the payload text of one memory version, keeping its lineage and provenance.
+    ///
+    /// # Errors
+    /// Returns an error when accountability fields are empty, the version does not exist or is
+    /// already redacted, or persistence fails.
+    pub fn redact(&self, input: &RedactRequest) -> Result<Redaction> {
+        self.with_migrated_store(|store| {
+            store.redact(input.memory_version_id, &input.writer, &input.justification)
+        })
+    }
+
+    /// Attach an Outcome record to the context package that informed the decision it reports
+    /// on.
+    ///
+    /// # Errors
+    /// Returns an error when `context_package_id` or accountability fields are empty, the
+    /// package or record does not exist, the record is not an Outcome, the pair is already
+    /// linked, or persistence fails.
+    pub fn link_outcome(&self, input: &LinkOutcomeRequest) -> Result<PackageOutcome> {
+        if input.context_package_id.trim().is_empty() {
+            return Err(anyhow!("context_package_id cannot be empty"));
+        }
+        self.with_migrated_store(|store| {
+            store.link_package_outcome(
+                &input.context_package_id,
+                input.outcome_memory_version_id,
+                &input.writer,
+                &input.justification,
+            )
+        })
+    }
+
+    /// List the outcomes that followed context packages selecting `memory_version_id`, oldest
+    /// package first.
+    ///
+    /// # Errors
+    /// Returns an error when links, packages, or outcome records cannot be read.
+    pub fn outcomes_following(
+        &self,
+        input: &OutcomesFollowingRequest,
+    ) -> Result<OutcomesFollowingResult> {
+        self.with_migrated_store(|store| {
+            Ok(OutcomesFollowingResult {
+                memory_version_id: input.memory_version_id,
+                outcomes: store.outcomes_following(input.memory_version_id)?,
+            })
+        })
+    }
+
+    /// List one page of memory records matching `filter`.
+    ///
+    /// # Errors
+    /// Returns an error when the filter is invalid or records cannot be read.
+    pub fn list_records(&self, filter: &RecordFilter) -> Result<RecordPage> {
+        self.with_migrated_store(|store| store.list_records_filtered(filter))
+    }
 
-        Ok(AddLinkResult {
-            from_memory_version_id: input.from,
-            to_memory_version_id: input.to,
-            relation: input.relation,
-            writer: input.writer,
-            justification: input.justification,
+    /// List one page of record versions whose provenance points at a source document, so
+    /// records derived from a revised document can be found and superseded.
+    ///
+    /// # Errors
+    /// Returns an error when neither `source_uri_prefix` nor `source_hash` is set, either is
+    /// blank, the page is invalid, or records cannot be read.
+    pub fn records_by_source(&self, input: &SourceLookupRequest) -> Result<RecordPage> {
+        if input.source_uri_prefix.is_none() && input.source_hash.is_none() {
+            return Err(anyhow!("source_uri_prefix or source_hash must be provided"));
+        }
+        for (field, value) in
+            [("source_uri_prefix", &input.source_uri_prefix), ("source_hash", &input.source_hash)]
+        {
+            if value.as_deref().is_some_and(|value| value.trim().is_empty()) {
+                return Err(anyhow!("{field} cannot be empty"));
+            }
+        }
+        self.list_records(&RecordFilter {
+            source_uri_prefix: input.source_uri_prefix.clone(),
+            source_hash: input.source_hash.clone(),
+            after: input.after,
+            limit: input.limit,
+            ..RecordFilter::default()
         })
     }
 
+    /// Report unresolved contradictions between active record versions.
+    ///
+    /// # Errors
+    /// Returns an error when links or records cannot be read.
+    pub fn list_contradictions(&self) -> Result<ContradictionReport> {
+        self.with_migrated_store(|store| store.list_contradictions())
+    }
+
     /// Execute a policy query and persist the generated context package.
     ///
     /// # Errors
     /// Returns an error when retrieval or persistence fails.
     pub fn query_ask(&self, input: AskRequest) -> Result<ContextPackage> {
-        let mut store = self.open_store()?;
-        store.migrate()?;
+        self.with_migrated_store(|store| {
+            let started = Instant::now();
+            let as_of = input.as_of.unwrap_or_else(OffsetDateTime::now_utc);
 
-        let as_of = input.as_of.unwrap_or_else(OffsetDateTime::now_utc);
-        let records = store.list_records()?;
-        let snapshot_id = compute_snapshot_id(
-            &records,
-            as_of,
-            &input.text,
-            &[
+            // Policy resolution only reads constraints and supersedes links, so stream the store
+            // and keep just those records rather than materializing the whole table.
+            let mut snapshot_keys = Vec::new();
+            let mut records = Vec::new();
+            for record in store.iter_records()? {
+                let record = record?;
+                record.validate()?;
+                snapshot_keys.push(snapshot_key(record.memory_id, record.memory_version_id));
+                if matches!(record.payload, MemoryPayload::Constraint(_))
+                    || !record.supersedes.is_empty()
+                {
+                    records.push(record);
+                }
+            }
+
+            let ruleset = input.ruleset.strategy();
+            let resolution = input.conflict_resolution.unwrap_or(self.conflict_resolution);
+            let mut extras = vec![
                 "query_mode=policy".to_string(),
                 format!("actor={}", input.actor),
                 format!("action={}", input.action),
                 format!("resource={}", input.resource),
-            ],
-        );
+            ];
+            // Keep snapshot ids of default-ruleset packages unchanged.
+            if input.ruleset != PolicyRuleset::default() {
+                extras.push(format!("ruleset={}", ruleset.version()));
+            }
+            extras.extend(resolution.snapshot_extra());
+            extras.extend(self.namespace_extra());
+            extras.extend(self.confidence_decay_extra());
+            let snapshot_id = compute_snapshot_id(snapshot_keys, as_of, &input.text, &extras);
 
-        let package = build_context_package(
-            &records,
-            QueryRequest {
-                text: input.text,
-                actor: input.actor,
-                action: input.action,
-                resource: input.resource,
-                as_of,
-            },
-            &snapshot_id,
-        )?;
-        store.save_context_package(&package)?;
-        Ok(package)
+            let package = build_context_package_with_ruleset(
+                &records,
+                QueryRequest {
+                    text: input.text,
+                    actor: input.actor,
+                    action: input.action,
+                    resource: input.resource,
+                    as_of,
+                },
+                &snapshot_id,
+                ruleset,
+                self.confidence_decay.as_ref(),
+                resolution,
+            )?;
+            let metric = QueryMetric::for_package(
+                QueryMode::Policy,
+                &package,
+                started.elapsed(),
+                records.len(),
+            );
+            store.save_context_package(&package)?;
+            store.record_query_metric(&metric)?;
+            Ok(package)
+        })
+    }
+
+    /// Evaluate a proposed constraint against sample policy queries without persisting it.
+    ///
+    /// # Errors
+    /// Returns an error when no queries are provided, the proposed constraint is invalid, or
+    /// retrieval fails.
+    pub fn simulate(&self, input: SimulateRequest) -> Result<SimulateResult> {
+        if input.queries.is_empty() {
+            return Err(anyhow!("queries MUST contain at least one query"));
+        }
+        // Default `as_of` after building the proposal so it is already effective.
+        let proposed = build_constraint_record(input.constraint);
+        let now = OffsetDateTime::now_utc();
+        self.with_migrated_store(|store| {
+            let mut records = Vec::new();
+            for record in store.iter_records()? {
+                let record = record?;
+                if matches!(record.payload, MemoryPayload::Constraint(_))
+                    || !record.supersedes.is_empty()
+                {
+                    records.push(record);
+                }
+            }
+            let mut outcomes = Vec::with_capacity(input.queries.len());
+            for query in input.queries {
+                let ruleset = query.ruleset.strategy();
+                let resolution = query.conflict_resolution.unwrap_or(self.conflict_resolution);
+                let query = QueryRequest {
+                    text: query.text,
+                    actor: query.actor,
+                    action: query.action,
+                    resource: query.resource,
+                    as_of: query.as_of.unwrap_or(now),
+                };
+                outcomes.extend(simulate_constraint(
+                    &records,
+                    &proposed,
+                    &[query],
+                    ruleset,
+                    self.confidence_decay.as_ref(),
+                    resolution,
+                )?);
+            }
+            let changed = outcomes.iter().filter(|outcome| outcome.changed).count();
+            Ok(SimulateResult { proposed, outcomes, changed })
+        })
+    }
+
+    /// Explain how one record version would fare in a policy query without building or
+    /// persisting a context package.
+    ///
+    /// # Errors
+    /// Returns an error when the record version is not found in the namespace or retrieval fails.
+    pub fn explain(&self, input: ExplainRequest) -> Result<RecordExplanation> {
+        self.with_migrated_store(|store| {
+            let mut records = Vec::new();
+            for record in store.iter_records()? {
+                let record = record?;
+                if matches!(record.payload, MemoryPayload::Constraint(_))
+                    || !record.supersedes.is_empty()
+                    || record.memory_version_id == input.memory_version_id
+                {
+                    records.push(record);
+                }
+            }
+            let query = input.query;
+            Ok(explain_record(
+                &records,
+                input.memory_version_id,
+                QueryRequest {
+                    text: query.text,
+                    actor: query.actor,
+                    action: query.action,
+                    resource: query.resource,
+                    as_of: query.as_of.unwrap_or_else(OffsetDateTime::now_utc),
+                },
+                query.ruleset.strategy(),
+                self.confidence_decay.as_ref(),
+            )?)
+        })
     }
 
     /// Execute deterministic recall retrieval across selected record types.
@@ -255,47 +840,217 @@ impl MemoryKernelApi {
     /// # Errors
     /// Returns an error when retrieval or persistence fails.
     pub fn query_recall(&self, input: RecallRequest) -> Result<ContextPackage> {
-        let mut store = self.open_store()?;
-        store.migrate()?;
+        self.with_migrated_store(|store| {
+            let started = Instant::now();
+            let as_of = input.as_of.unwrap_or_else(OffsetDateTime::now_utc);
+            let selected_record_types = if input.record_types.is_empty() {
+                default_recall_record_types()
+            } else {
+                input.record_types
+            };
+            let record_keys = store.list_record_keys()?;
+            let records = retain_tagged(
+                store.recall_candidates_tagged(
+                    &input.text,
+                    &selected_record_types,
+                    &input.tags,
+                    DEFAULT_RECALL_CANDIDATE_LIMIT,
+                )?,
+                &input.tags,
+            );
 
-        let as_of = input.as_of.unwrap_or_else(OffsetDateTime::now_utc);
-        let selected_record_types = if input.record_types.is_empty() {
-            default_recall_record_types()
-        } else {
-            input.record_types
-        };
-        let records = store.list_records()?;
+            let mut record_type_names = selected_record_types
+                .iter()
+                .map(|record_type| record_type.as_str())
+                .collect::<Vec<_>>();
+            record_type_names.sort_unstable();
 
-        let mut record_type_names = selected_record_types
-            .iter()
-            .map(|record_type| record_type.as_str())
-            .collect::<Vec<_>>();
-        record_type_names.sort_unstable();
-
-        let snapshot_id = compute_snapshot_id(
-            &records,
-            as_of,
-            &input.text,
-            &[
+            let mut extras = vec![
                 "query_mode=recall".to_string(),
                 format!("record_types={}", record_type_names.join(",")),
-            ],
-        );
-
-        let package = build_recall_context_package(
-            &records,
-            QueryRequest {
-                text: input.text,
-                actor: "*".to_string(),
-                action: "*".to_string(),
-                resource: "*".to_string(),
+            ];
+            extras.extend(tags_extra(&input.tags));
+            extras.extend(self.namespace_extra());
+            extras.extend(self.confidence_decay_extra());
+            let snapshot_id = compute_snapshot_id(
+                record_keys
+                    .into_iter()
+                    .map(|(memory_id, memory_version_id)| {
+                        snapshot_key(memory_id, memory_version_id)
+                    })
+                    .collect(),
                 as_of,
-            },
-            &snapshot_id,
-            &selected_record_types,
-        )?;
-        store.save_context_package(&package)?;
-        Ok(package)
+                &input.text,
+                &extras,
+            );
+
+            let no_decay = ConfidenceDecay::default();
+            let package = build_recall_context_package_with_decay(
+                &records,
+                QueryRequest {
+                    text: input.text,
+                    actor: "*".to_string(),
+                    action: "*".to_string(),
+                    resource: "*".to_string(),
+                    as_of,
+                },
+                &snapshot_id,
+                &selected_record_types,
+                self.confidence_decay.as_ref().unwrap_or(&no_decay),
+            )?;
+            let metric = QueryMetric::for_package(
+                QueryMode::Recall,
+                &package,
+                started.elapsed(),
+                records.len(),
+            );
+            store.save_context_package(&package)?;
+            store.record_query_metric(&metric)?;
+            Ok(package)
+        })
+    }
+
+    /// Execute recall that also matches records by `embedder` similarity and persist the
+    /// generated context package.
+    ///
+    /// Vectors missing for in-scope, non-retracted records are embedded and stored under the
+    /// embedder's model before ranking, so later queries reuse them.
+    ///
+    /// # Errors
+    /// Returns an error when embedding, retrieval, or persistence fails.
+    pub fn query_recall_semantic(
+        &self,
+        input: RecallRequest,
+        embedder: &dyn Embedder,
+    ) -> Result<ContextPackage> {
+        self.with_migrated_store(|store| {
+            let started = Instant::now();
+            let as_of = input.as_of.unwrap_or_else(OffsetDateTime::now_utc);
+            let selected_record_types = if input.record_types.is_empty() {
+                default_recall_record_types()
+            } else {
+                input.record_types
+            };
+
+            // Paraphrases share no terms, so every in-scope record is a candidate; keep
+            // superseding records too so superseded matches are still excluded.
+            let mut snapshot_keys = Vec::new();
+            let mut records = Vec::new();
+            for record in store.iter_records()? {
+                let record = record?;
+                snapshot_keys.push(snapshot_key(record.memory_id, record.memory_version_id));
+                if selected_record_types.contains(&record.payload.record_type())
+                    || !record.supersedes.is_empty()
+                {
+                    records.push(record);
+                }
+            }
+            let records = retain_tagged(records, &input.tags);
+
+            let model = embedder.model().to_string();
+            let mut record_vectors = store.record_embeddings(&model)?;
+            let missing = records
+                .iter()
+                .filter(|record| {
+                    selected_record_types.contains(&record.payload.record_type())
+                        && record.truth_status != TruthStatus::Retracted
+                        && !record_vectors.contains_key(&record.memory_version_id)
+                })
+                .map(|record| {
+                    Ok((record.memory_version_id, embedder.embed(&record_recall_text(record))?))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if !missing.is_empty() {
+                store.put_record_embeddings(&model, &missing)?;
+                record_vectors.extend(missing);
+            }
+            let query_vector = embedder.embed(&input.text)?;
+
+            let mut record_type_names = selected_record_types
+                .iter()
+                .map(|record_type| record_type.as_str())
+                .collect::<Vec<_>>();
+            record_type_names.sort_unstable();
+            let mut extras = vec![
+                "query_mode=semantic_recall".to_string(),
+                format!("record_types={}", record_type_names.join(",")),
+                format!("embedding_model={model}"),
+            ];
+            extras.extend(tags_extra(&input.tags));
+            extras.extend(self.namespace_extra());
+            extras.extend(self.confidence_decay_extra());
+            let snapshot_id = compute_snapshot_id(snapshot_keys, as_of, &input.text, &extras);
+
+            let package = build_semantic_recall_context_package(
+                &records,
+                QueryRequest {
+                    text: input.text,
+                    actor: "*".to_string(),
+                    action: "*".to_string(),
+                    resource: "*".to_string(),
+                    as_of,
+                },
+                &snapshot_id,
+                &selected_record_types,
+                &SemanticRecall {
+                    model: &model,
+                    query_vector: &query_vector,
+                    record_vectors: &record_vectors,
+                    min_similarity: DEFAULT_MIN_SEMANTIC_SIMILARITY,
+                },
+                self.confidence_decay.as_ref(),
+            )?;
+            let metric = QueryMetric::for_package(
+                QueryMode::Recall,
+                &package,
+                started.elapsed(),
+                records.len(),
+            );
+            store.save_context_package(&package)?;
+            store.record_query_metric(&metric)?;
+            Ok(package)
+        })
+    }
+
+    /// Summarize the latency of ask and recall queries recorded at or after `since` (every
+    /// recorded query when unset), per query mode.
+    ///
+    /// # Errors
+    /// Returns an error when metrics cannot be read.
+    pub fn query_stats(&self, since: Option<OffsetDateTime>) -> Result<Vec<QueryModeStats>> {
+        self.with_migrated_store(|store| {
+            Ok(summarize_query_metrics(&store.list_query_metrics(since)?))
+        })
+    }
+
+    /// List one page of persisted context packages matching `filter`.
+    ///
+    /// # Errors
+    /// Returns an error when the filter is invalid or packages cannot be read.
+    pub fn list_context_packages(
+        &self,
+        filter: &ContextPackageFilter,
+    ) -> Result<ContextPackagePage> {
+        self.with_migrated_store(|store| store.list_context_packages(filter))
+    }
+
+    /// Export this namespace's records, context packages, and redactions as a snapshot
+    /// directory.
+    ///
+    /// # Errors
+    /// Returns an error when the store cannot export snapshots or export files cannot be written.
+    pub fn export_snapshot(&self, out_dir: &Path) -> Result<ExportManifest> {
+        self.with_migrated_store(|store| store.export_snapshot(out_dir))
+    }
+
+    /// Import a snapshot directory into this namespace, skipping existing rows when
+    /// `skip_existing` is set.
+    ///
+    /// # Errors
+    /// Returns an error when the store cannot import snapshots, the snapshot is invalid, or
+    /// writes fail.
+    pub fn import_snapshot(&self, in_dir: &Path, skip_existing: bool) -> Result<ImportSummary> {
+        self.with_migrated_store(|store| store.import_snapshot(in_dir, skip_existing))
     }
 
     /// Fetch a previously persisted context package.
@@ -303,12 +1058,12 @@ impl MemoryKernelApi {
     /// # Errors
     /// Returns an error when lookup fails or package does not exist.
     pub fn context_show(&self, context_package_id: &str) -> Result<ContextPackage> {
-        let mut store = self.open_store()?;
-        store.migrate()?;
-        let package = store
-            .get_context_package(context_package_id)?
-            .ok_or_else(|| anyhow!("context package not found: {context_package_id}"))?;
-        Ok(package)
+        self.with_migrated_store(|store| {
+            let package = store
+                .get_context_package(context_package_id)?
+                .ok_or_else(|| anyhow!("context package not found: {context_package_id}"))?;
+            Ok(package)
+        })
     }
 }
 
@@ -334,6 +1089,7 @@ fn build_constraint_record(input: AddConstraintRequest) -> MemoryRecord {
         },
         supersedes: input.supersedes,
         contradicts: input.contradicts,
+        tags: input.tags,
         payload: MemoryPayload::Constraint(ConstraintPayload {
             scope: ConstraintScope {
                 actor: input.actor,
@@ -342,6 +1098,7 @@ fn build_constraint_record(input: AddConstraintRequest) -> MemoryRecord {
             },
             effect: input.effect,
             note: input.note,
+            expires_at: input.expires_at,
         }),
     }
 }
@@ -350,6 +1107,16 @@ fn build_summary_record(input: AddSummaryRequest) -> Result<MemoryRecord> {
     let created_at = input.created_at.unwrap_or_else(OffsetDateTime::now_utc);
     let effective_at = input.effective_at.unwrap_or(created_at);
 
+    if input.task_status.is_some() && input.record_type != RecordType::Task {
+        return Err(anyhow!("task_status is only valid for record_type=task"));
+    }
+    if input.risk_severity.is_some() && input.record_type != RecordType::Risk {
+        return Err(anyhow!("risk_severity is only valid for record_type=risk"));
+    }
+    if input.assumption_status.is_some() && input.record_type != RecordType::Assumption {
+        return Err(anyhow!("assumption_status is only valid for record_type=assumption"));
+    }
+
     let payload = match input.record_type {
         RecordType::Decision => MemoryPayload::Decision(DecisionPayload { summary: input.summary }),
         RecordType::Preference => {
@@ -359,6 +1126,20 @@ fn build_summary_record(input: AddSummaryRequest) -> Result<MemoryRecord> {
         RecordType::Outcome => {
             MemoryPayload::Outcome(memory_kernel_core::OutcomePayload { summary: input.summary })
         }
+        RecordType::Task => MemoryPayload::Task(TaskPayload {
+            summary: input.summary,
+            status: input.task_status.unwrap_or(TaskStatus::Open),
+        }),
+        RecordType::Risk => MemoryPayload::Risk(RiskPayload {
+            summary: input.summary,
+            severity: input
+                .risk_severity
+                .ok_or_else(|| anyhow!("risk_severity MUST be provided for record_type=risk"))?,
+        }),
+        RecordType::Assumption => MemoryPayload::Assumption(AssumptionPayload {
+            summary: input.summary,
+            status: input.assumption_status.unwrap_or(AssumptionStatus::Unverified),
+        }),
         RecordType::Constraint => {
             return Err(anyhow!("add_summary does not support record_type=constraint"));
         }
@@ -382,12 +1163,54 @@ fn build_summary_record(input: AddSummaryRequest) -> Result<MemoryRecord> {
         },
         supersedes: input.supersedes,
         contradicts: input.contradicts,
+        tags: input.tags,
         payload,
     })
 }
 
+fn successor_record(
+    previous: &MemoryRecord,
+    version: u32,
+    payload: MemoryPayload,
+    truth_status: TruthStatus,
+    writer: &str,
+    justification: &str,
+) -> MemoryRecord {
+    let now = OffsetDateTime::now_utc();
+    MemoryRecord {
+        memory_version_id: MemoryVersionId::new(),
+        memory_id: previous.memory_id,
+        version,
+        created_at: now,
+        effective_at: now,
+        truth_status,
+        authority: previous.authority,
+        confidence: previous.confidence,
+        writer: writer.to_string(),
+        justification: justification.to_string(),
+        provenance: previous.provenance.clone(),
+        supersedes: vec![previous.memory_version_id],
+        contradicts: Vec::new(),
+        tags: previous.tags.clone(),
+        payload,
+    }
+}
+
+/// Snapshot extra for a tag filter; `None` keeps snapshot ids of unfiltered queries unchanged.
+fn tags_extra(tags: &[String]) -> Option<String> {
+    if tags.is_empty() {
+        return None;
+    }
+    let tags = tags.iter().map(String::as_str).collect::<std::collections::BTreeSet<_>>();
+    Some(format!("tags={}", tags.into_iter().collect::<Vec<_>>().join(",")))
+}
+
+fn snapshot_key(memory_id: MemoryId, memory_version_id: MemoryVersionId) -> String {
+    format!("{memory_id}:{memory_version_id}")
+}
+
 fn compute_snapshot_id(
-    records: &[MemoryRecord],
+    mut record_keys: Vec<String>,
     as_of: OffsetDateTime,
     text: &str,
     scope_parts: &[String],
@@ -399,13 +1222,9 @@ fn compute_snapshot_id(
         hasher.update(part.as_bytes());
     }
 
-    let mut sorted_ids = records
-        .iter()
-        .map(|record| format!("{}:{}", record.memory_id, record.memory_version_id))
-        .collect::<Vec<_>>();
-    sorted_ids.sort_unstable();
+    record_keys.sort_unstable();
 
-    for value in sorted_ids {
+    for value in record_keys {
         hasher.update(value.as_bytes());
     }
 
@@ -417,6 +1236,7 @@ fn compute_snapshot_id(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use memory_kernel_core::AnswerResult;
 
     fn unique_temp_db_path() -> PathBuf {
         std::env::temp_dir().join(format!("memorykernel-api-{}.sqlite3", ulid::Ulid::new()))
@@ -434,6 +1254,7 @@ mod tests {
             resource: "usb_drive".to_string(),
             effect: ConstraintEffect::Deny,
             note: None,
+            expires_at: None,
             memory_id: None,
             version: 1,
             writer: "tester".to_string(),
@@ -448,6 +1269,7 @@ mod tests {
             effective_at: None,
             supersedes: Vec::new(),
             contradicts: Vec::new(),
+            tags: Vec::new(),
         })?;
 
         let package = api.query_ask(AskRequest {
@@ -456,6 +1278,8 @@ mod tests {
             action: "use".to_string(),
             resource: "usb_drive".to_string(),
             as_of: None,
+            ruleset: PolicyRuleset::default(),
+            conflict_resolution: None,
         })?;
 
         let loaded = api.context_show(&package.context_package_id)?;
@@ -474,6 +1298,9 @@ mod tests {
         let _decision = api.add_summary(AddSummaryRequest {
             record_type: RecordType::Decision,
             summary: "Decision: USB media access must be approved".to_string(),
+            task_status: None,
+            risk_severity: None,
+            assumption_status: None,
             memory_id: None,
             version: 1,
             writer: "tester".to_string(),
@@ -488,11 +1315,15 @@ mod tests {
             effective_at: None,
             supersedes: Vec::new(),
             contradicts: Vec::new(),
+            tags: Vec::new(),
         })?;
 
         let _outcome = api.add_summary(AddSummaryRequest {
             record_type: RecordType::Outcome,
             summary: "Outcome: USB compliance improved after controls".to_string(),
+            task_status: None,
+            risk_severity: None,
+            assumption_status: None,
             memory_id: None,
             version: 1,
             writer: "tester".to_string(),
@@ -507,12 +1338,14 @@ mod tests {
             effective_at: None,
             supersedes: Vec::new(),
             contradicts: Vec::new(),
+            tags: Vec::new(),
         })?;
 
         let package = api.query_recall(RecallRequest {
             text: "usb compliance".to_string(),
             record_types: vec![RecordType::Decision, RecordType::Outcome],
             as_of: None,
+            tags: Vec::new(),
         })?;
 
         assert_eq!(package.determinism.ruleset_version, "recall-ordering.v1");
@@ -534,6 +1367,7 @@ mod tests {
             resource: "usb_drive".to_string(),
             effect: ConstraintEffect::Deny,
             note: Some("constraint should not be in default recall scope".to_string()),
+            expires_at: None,
             memory_id: None,
             version: 1,
             writer: "tester".to_string(),
@@ -548,11 +1382,15 @@ mod tests {
             effective_at: None,
             supersedes: Vec::new(),
             contradicts: Vec::new(),
+            tags: Vec::new(),
         })?;
 
         let _decision = api.add_summary(AddSummaryRequest {
             record_type: RecordType::Decision,
             summary: "Decision: USB usage requires manager approval".to_string(),
+            task_status: None,
+            risk_severity: None,
+            assumption_status: None,
             memory_id: None,
             version: 1,
             writer: "tester".to_string(),
@@ -567,12 +1405,14 @@ mod tests {
             effective_at: None,
             supersedes: Vec::new(),
             contradicts: Vec::new(),
+            tags: Vec::new(),
         })?;
 
         let package = api.query_recall(RecallRequest {
             text: "usb usage".to_string(),
             record_types: Vec::new(),
             as_of: None,
+            tags: Vec::new(),
         })?;
 
         assert_eq!(package.determinism.ruleset_version, "recall-ordering.v1");
@@ -585,4 +1425,759 @@ mod tests {
         let _ = std::fs::remove_file(&db_path);
         Ok(())
     }
+
+    // Test IDs: TAPI-004
+    #[test]
+    fn api_add_summary_supports_task_risk_and_assumption_records() -> Result<()> {
+        let db_path = unique_temp_db_path();
+        let api = MemoryKernelApi::new(db_path.clone());
+
+        let request = |record_type: RecordType, summary: &str| AddSummaryRequest {
+            record_type,
+            summary: summary.to_string(),
+            task_status: None,
+            risk_severity: None,
+            assumption_status: None,
+            memory_id: None,
+            version: 1,
+            writer: "tester".to_string(),
+            justification: "api record type fixture".to_string(),
+            source_uri: "file:///planning.md".to_string(),
+            source_hash: None,
+            evidence: Vec::new(),
+            confidence: Some(0.8),
+            truth_status: TruthStatus::Observed,
+            authority: Authority::Authoritative,
+            created_at: None,
+            effective_at: None,
+            supersedes: Vec::new(),
+            contradicts: Vec::new(),
+            tags: Vec::new(),
+        };
+
+        let task = api.add_summary(request(RecordType::Task, "Rotate USB encryption keys"))?;
+        assert_eq!(
+            task.payload,
+            MemoryPayload::Task(TaskPayload {
+                summary: "Rotate USB encryption keys".to_string(),
+                status: TaskStatus::Open,
+            })
+        );
+
+        let missing_severity = api.add_summary(request(RecordType::Risk, "USB data exfiltration"));
+        assert!(missing_severity.is_err());
+        let risk = api.add_summary(AddSummaryRequest {
+            risk_severity: Some(RiskSeverity::Critical),
+            ..request(RecordType::Risk, "USB data exfiltration")
+        })?;
+
+        let assumption = api.add_summary(AddSummaryRequest {
+            assumption_status: Some(AssumptionStatus::Validated),
+            ..request(RecordType::Assumption, "Contractors never need USB access")
+        })?;
+        assert_eq!(
+            assumption.payload,
+            MemoryPayload::Assumption(AssumptionPayload {
+                summary: "Contractors never need USB access".to_string(),
+                status: AssumptionStatus::Validated,
+            })
+        );
+
+        let mismatched = api.add_summary(AddSummaryRequest {
+            task_status: Some(TaskStatus::Done),
+            ..request(RecordType::Decision, "USB policy adopted")
+        });
+        assert!(mismatched.is_err());
+
+        let package = api.query_recall(RecallRequest {
+            text: "usb".to_string(),
+            record_types: Vec::new(),
+            as_of: None,
+            tags: Vec::new(),
+        })?;
+        let selected = package
+            .selected_items
+            .iter()
+            .map(|item| item.memory_version_id)
+            .collect::<std::collections::BTreeSet<_>>();
+        assert!(selected.contains(&task.memory_version_id));
+        assert!(selected.contains(&risk.memory_version_id));
+        assert!(selected.contains(&assumption.memory_version_id));
+
+        let _ = std::fs::remove_file(&db_path);
+        Ok(())
+    }
+
+    // Test IDs: TAPI-005
+    #[test]
+    fn api_supersede_and_retract_append_linked_versions() -> Result<()> {
+        let db_path = unique_temp_db_path();
+        let api = MemoryKernelApi::new(db_path.clone());
+
+        let original = api.add_constraint(AddConstraintRequest {
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: "usb_drive".to_string(),
+            effect: ConstraintEffect::Allow,
+            note: None,
+            expires_at: None,
+            memory_id: None,
+            version: 1,
+            writer: "tester".to_string(),
+            justification: "initial policy".to_string(),
+            source_uri: "file:///policy.md".to_string(),
+            source_hash: Some("sha256:abc123".to_string()),
+            evidence: vec!["policy.md#usb".to_string()],
+            confidence: Some(0.9),
+            truth_status: TruthStatus::Asserted,
+            authority: Authority::Authoritative,
+            created_at: None,
+            effective_at: None,
+            supersedes: Vec::new(),
+            contradicts: Vec::new(),
+            tags: Vec::new(),
+        })?;
+
+        let MemoryPayload::Constraint(mut payload) = original.payload.clone() else {
+            return Err(anyhow!("expected constraint payload"));
+        };
+        payload.effect = ConstraintEffect::Deny;
+        let replacement = api.supersede(
+            original.memory_version_id,
+            MemoryPayload::Constraint(payload),
+            "reviewer",
+            "policy tightened",
+        )?;
+        assert_eq!(replacement.memory_id, original.memory_id);
+        assert_eq!(replacement.version, 2);
+        assert_eq!(replacement.supersedes, vec![original.memory_version_id]);
+        assert_eq!(replacement.provenance, original.provenance);
+        assert_eq!(replacement.writer, "reviewer");
+
+        let ask = || {
+            api.query_ask(AskRequest {
+                text: "Am I allowed to use a USB drive?".to_string(),
+                actor: "user".to_string(),
+                action: "use".to_string(),
+                resource: "usb_drive".to_string(),
+                as_of: None,
+                ruleset: PolicyRuleset::default(),
+                conflict_resolution: None,
+            })
+        };
+        assert_eq!(ask()?.answer.result, memory_kernel_core::AnswerResult::Deny);
+
+        let wrong_type = api.supersede(
+            replacement.memory_version_id,
+            MemoryPayload::Decision(DecisionPayload { summary: "not a constraint".to_string() }),
+            "reviewer",
+            "wrong type",
+        );
+        assert!(wrong_type.is_err());
+        assert!(api.retract(original.memory_version_id, "reviewer", "stale").is_err());
+
+        let retraction =
+            api.retract(replacement.memory_version_id, "reviewer", "policy withdrawn")?;
+        assert_eq!(retraction.version, 3);
+        assert_eq!(retraction.truth_status, TruthStatus::Retracted);
+        assert_eq!(retraction.supersedes, vec![replacement.memory_version_id]);
+        assert_eq!(ask()?.answer.result, memory_kernel_core::AnswerResult::Inconclusive);
+
+        let page = api.list_records(&RecordFilter::default())?;
+        assert_eq!(page.records.len(), 3);
+
+        let _ = std::fs::remove_file(&db_path);
+        Ok(())
+    }
+
+    // Test IDs: TAPI-006
+    #[test]
+    fn api_handle_reuses_one_connection_and_migrates_once() -> Result<()> {
+        let db_path = unique_temp_db_path();
+        let lazy = MemoryKernelApi::new(db_path.clone());
+        assert!(!db_path.exists(), "new() must not open the database");
+
+        let api = MemoryKernelApi::open(db_path.clone())?;
+        assert!(api.schema_status()?.pending_versions.is_empty());
+        let shared = api.clone();
+        assert!(Arc::ptr_eq(&api.store, &shared.store));
+
+        let page = shared.list_records(&RecordFilter::default())?;
+        assert!(page.records.is_empty());
+        {
+            let handle = api.store.lock().unwrap_or_else(PoisonError::into_inner);
+            assert!(handle.store.is_some());
+            assert!(handle.migrated);
+        }
+        let handle = lazy.store.lock().unwrap_or_else(PoisonError::into_inner);
+        assert!(handle.store.is_none());
+        drop(handle);
+
+        let _ = std::fs::remove_file(&db_path);
+        Ok(())
+    }
+
+    struct KeywordEmbedder {
+        calls: std::cell::Cell<usize>,
+    }
+
+    impl Embedder for KeywordEmbedder {
+        fn model(&self) -> &'static str {
+            "keyword-fixture"
+        }
+
+        fn embed(&self, text: &str) -> Result<Vec<f32>, memory_kernel_core::KernelError> {
+            self.calls.set(self.calls.get() + 1);
+            let text = text.to_ascii_lowercase();
+            if text.contains("usb") || text.contains("removable") {
+                Ok(vec![1.0, 0.0])
+            } else {
+                Ok(vec![0.0, 1.0])
+            }
+        }
+    }
+
+    fn summary_fixture(record_type: RecordType, summary: &str) -> AddSummaryRequest {
+        AddSummaryRequest {
+            record_type,
+            summary: summary.to_string(),
+            task_status: None,
+            risk_severity: None,
+            assumption_status: None,
+            memory_id: None,
+            version: 1,
+            writer: "tester".to_string(),
+            justification: "api semantic recall fixture".to_string(),
+            source_uri: "file:///semantic.md".to_string(),
+            source_hash: Some("sha256:abc123".to_string()),
+            evidence: Vec::new(),
+            confidence: Some(0.8),
+            truth_status: TruthStatus::Observed,
+            authority: Authority::Authoritative,
+            created_at: None,
+            effective_at: None,
+            supersedes: Vec::new(),
+            contradicts: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    // Test IDs: TAPI-007
+    #[test]
+    fn api_semantic_recall_matches_paraphrases_and_reuses_stored_vectors() -> Result<()> {
+        let db_path = unique_temp_db_path();
+        let api = MemoryKernelApi::new(db_path.clone());
+        let usb = api.add_summary(summary_fixture(
+            RecordType::Decision,
+            "Decision: USB media access must be approved",
+        ))?;
+        let _printer = api.add_summary(summary_fixture(
+            RecordType::Outcome,
+            "Outcome: printer toner restocked",
+        ))?;
+
+        let embedder = KeywordEmbedder { calls: std::cell::Cell::new(0) };
+        let request = RecallRequest {
+            text: "removable storage policy".to_string(),
+            record_types: vec![RecordType::Decision, RecordType::Outcome],
+            as_of: None,
+            tags: Vec::new(),
+        };
+        let package = api.query_recall_semantic(request.clone(), &embedder)?;
+
+        assert_eq!(package.determinism.ruleset_version, "semantic-recall-ordering.v1");
+        assert_eq!(package.selected_items.len(), 1);
+        assert_eq!(package.selected_items[0].memory_version_id, usb.memory_version_id);
+        assert_eq!(package.excluded_items.len(), 1);
+        assert_eq!(embedder.calls.get(), 3, "two records plus the query are embedded");
+
+        let repeat = api.query_recall_semantic(request, &embedder)?;
+        assert_eq!(repeat.selected_items.len(), 1);
+        assert_eq!(embedder.calls.get(), 4, "stored record vectors are reused");
+
+        let _ = std::fs::remove_file(&db_path);
+        Ok(())
+    }
+
+    // Test IDs: TAPI-008
+    #[test]
+    fn api_simulate_reports_flips_without_persisting_constraint() -> Result<()> {
+        let db_path = unique_temp_db_path();
+        let api = MemoryKernelApi::new(db_path.clone());
+        let constraint =
+            |effect: ConstraintEffect, supersedes: Vec<MemoryVersionId>| AddConstraintRequest {
+                actor: "user".to_string(),
+                action: "use".to_string(),
+                resource: "usb_drive".to_string(),
+                effect,
+                note: None,
+                expires_at: None,
+                memory_id: None,
+                version: 1,
+                writer: "tester".to_string(),
+                justification: "api simulate fixture".to_string(),
+                source_uri: "file:///policy.md".to_string(),
+                source_hash: Some("sha256:abc123".to_string()),
+                evidence: Vec::new(),
+                confidence: Some(0.9),
+                truth_status: TruthStatus::Observed,
+                authority: Authority::Authoritative,
+                created_at: None,
+                effective_at: None,
+                supersedes,
+                contradicts: Vec::new(),
+                tags: Vec::new(),
+            };
+        let allow = api.add_constraint(constraint(ConstraintEffect::Allow, Vec::new()))?;
+        let ask = |resource: &str| AskRequest {
+            text: "Can I use it?".to_string(),
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: resource.to_string(),
+            as_of: None,
+            ruleset: PolicyRuleset::default(),
+            conflict_resolution: None,
+        };
+
+        let result = api.simulate(SimulateRequest {
+            constraint: constraint(ConstraintEffect::Deny, vec![allow.memory_version_id]),
+            queries: vec![ask("usb_drive"), ask("printer")],
+        })?;
+
+        assert_eq!(result.changed, 1);
+        assert_eq!(result.outcomes[0].baseline, AnswerResult::Allow);
+        assert_eq!(result.outcomes[0].simulated, AnswerResult::Deny);
+        assert!(!result.outcomes[1].changed);
+        assert_eq!(api.list_records(&RecordFilter::default())?.records.len(), 1);
+
+        let empty = api.simulate(SimulateRequest {
+            constraint: constraint(ConstraintEffect::Deny, Vec::new()),
+            queries: Vec::new(),
+        });
+        assert!(empty.is_err());
+
+        let _ = std::fs::remove_file(&db_path);
+        Ok(())
+    }
+
+    // Test IDs: TAPI-009
+    #[test]
+    fn api_confidence_decay_applies_to_recall_scores() -> Result<()> {
+        let db_path = unique_temp_db_path();
+        let api = MemoryKernelApi::new(db_path.clone());
+        let mut stale = summary_fixture(RecordType::Decision, "Decision: rotate usb keys");
+        stale.truth_status = TruthStatus::Speculative;
+        stale.effective_at = Some(OffsetDateTime::now_utc() - time::Duration::days(14));
+        let _ = api.add_summary(stale)?;
+        let request = RecallRequest {
+            text: "usb keys".to_string(),
+            record_types: vec![RecordType::Decision],
+            as_of: None,
+            tags: Vec::new(),
+        };
+
+        let plain = api.query_recall(request.clone())?;
+        let decayed = api
+            .clone()
+            .with_confidence_decay(
+                ConfidenceDecay::default().with_half_life(TruthStatus::Speculative, 7.0),
+            )
+            .query_recall(request)?;
+
+        let confidence = |package: &ContextPackage| {
+            package.selected_items[0].why.rule_scores.as_ref().map(|scores| scores.confidence)
+        };
+        assert_eq!(confidence(&plain), Some(0.8));
+        assert!(confidence(&decayed).is_some_and(|confidence| confidence < 0.21));
+        assert_ne!(plain.determinism.snapshot_id, decayed.determinism.snapshot_id);
+
+        let _ = std::fs::remove_file(&db_path);
+        Ok(())
+    }
+
+    // Test IDs: TAPI-010
+    #[test]
+    fn api_recall_filters_by_tags_of_latest_versions() -> Result<()> {
+        let db_path = unique_temp_db_path();
+        let api = MemoryKernelApi::new(db_path.clone());
+        let mut payments = summary_fixture(RecordType::Decision, "Decision: usb keys for payments");
+        payments.tags = vec!["team-payments".to_string()];
+        let payments = api.add_summary(payments)?;
+        let mut retagged = summary_fixture(RecordType::Decision, "Decision: usb keys for billing");
+        retagged.tags = vec!["team-payments".to_string()];
+        let retagged = api.add_summary(retagged)?;
+        let _ = api.add_summary(summary_fixture(RecordType::Decision, "Decision: usb keys"))?;
+        // The latest version of `retagged` drops the tag, so it no longer matches.
+        let mut moved = summary_fixture(RecordType::Decision, "Decision: usb keys for billing");
+        moved.memory_id = Some(retagged.memory_id);
+        moved.version = 2;
+        moved.supersedes = vec![retagged.memory_version_id];
+        moved.tags = vec!["team-billing".to_string()];
+        let _ = api.add_summary(moved)?;
+
+        let request = RecallRequest {
+            text: "usb keys".to_string(),
+            record_types: vec![RecordType::Decision],
+            as_of: None,
+            tags: vec!["team-payments".to_string()],
+        };
+        let tagged = api.query_recall(request.clone())?;
+        let selected =
+            tagged.selected_items.iter().map(|item| item.memory_version_id).collect::<Vec<_>>();
+        assert_eq!(selected, vec![payments.memory_version_id]);
+
+        let untagged = api.query_recall(RecallRequest { tags: Vec::new(), ..request })?;
+        assert_eq!(untagged.selected_items.len(), 3);
+        assert_ne!(tagged.determinism.snapshot_id, untagged.determinism.snapshot_id);
+
+        let _ = std::fs::remove_file(&db_path);
+        Ok(())
+    }
+
+    // Test IDs: TAPI-011
+    #[test]
+    fn api_namespaces_share_a_database_without_sharing_data() -> Result<()> {
+        let db_path = unique_temp_db_path();
+        let default_api = MemoryKernelApi::new(db_path.clone());
+        let team_a = default_api.clone().with_namespace("team-a");
+        let team_b = default_api.clone().with_namespace("team-b");
+        assert_eq!(default_api.namespace(), "default");
+
+        let record =
+            team_a.add_summary(summary_fixture(RecordType::Decision, "Decision: usb keys"))?;
+        let request = RecallRequest {
+            text: "usb keys".to_string(),
+            record_types: vec![RecordType::Decision],
+            as_of: None,
+            tags: Vec::new(),
+        };
+        let from_a = team_a.query_recall(request.clone())?;
+        assert_eq!(from_a.selected_items.len(), 1);
+        assert_eq!(from_a.selected_items[0].memory_version_id, record.memory_version_id);
+
+        let from_b = team_b.query_recall(request.clone())?;
+        let from_default = default_api.query_recall(request)?;
+        assert!(from_b.selected_items.is_empty());
+        assert!(from_default.selected_items.is_empty());
+        assert_ne!(from_b.context_package_id, from_default.context_package_id);
+
+        assert!(team_a.context_show(&from_a.context_package_id).is_ok());
+        assert!(team_b.context_show(&from_a.context_package_id).is_err());
+        assert!(team_b.retract(record.memory_version_id, "tester", "wrong namespace").is_err());
+        assert!(team_b.list_records(&RecordFilter::default())?.records.is_empty());
+
+        let invalid = default_api.clone().with_namespace("team a");
+        assert!(invalid.list_records(&RecordFilter::default()).is_err());
+
+        let _ = std::fs::remove_file(&db_path);
+        Ok(())
+    }
+
+    // Test IDs: TAPI-012
+    #[test]
+    fn api_explain_reports_one_record_without_persisting_a_package() -> Result<()> {
+        let db_path = unique_temp_db_path();
+        let api = MemoryKernelApi::new(db_path.clone());
+        let constraint = api.add_constraint(AddConstraintRequest {
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: "usb_drive".to_string(),
+            effect: ConstraintEffect::Deny,
+            note: None,
+            expires_at: None,
+            memory_id: None,
+            version: 1,
+            writer: "tester".to_string(),
+            justification: "api explain fixture".to_string(),
+            source_uri: "file:///policy.md".to_string(),
+            source_hash: Some("sha256:abc123".to_string()),
+            evidence: Vec::new(),
+            confidence: Some(0.9),
+            truth_status: TruthStatus::Observed,
+            authority: Authority::Authoritative,
+            created_at: None,
+            effective_at: None,
+            supersedes: Vec::new(),
+            contradicts: Vec::new(),
+            tags: Vec::new(),
+        })?;
+        let decision = api.add_summary(summary_fixture(RecordType::Decision, "Decision: usb"))?;
+        let explain = |memory_version_id: MemoryVersionId, resource: &str| {
+            api.explain(ExplainRequest {
+                memory_version_id,
+                query: AskRequest {
+                    text: "Can I use it?".to_string(),
+                    actor: "user".to_string(),
+                    action: "use".to_string(),
+                    resource: resource.to_string(),
+                    as_of: None,
+                    ruleset: PolicyRuleset::DenyOverrides,
+                    conflict_resolution: None,
+                },
+            })
+        };
+
+        let explained = explain(constraint.memory_version_id, "usb_drive")?;
+        assert_eq!(explained.ruleset_version, "deny-overrides.v1");
+        assert_eq!(explained.candidates, 1);
+        assert_eq!(explained.item.rank, 1);
+        assert!(explained.item.why.rule_scores.is_some());
+
+        let out_of_scope = explain(constraint.memory_version_id, "printer")?;
+        assert!(!out_of_scope.item.why.included);
+        assert_eq!(out_of_scope.candidates, 0);
+
+        let not_policy = explain(decision.memory_version_id, "usb_drive")?;
+        assert!(not_policy.item.why.reasons[0].starts_with("record_type=decision"));
+
+        assert!(explain(MemoryVersionId::new(), "usb_drive").is_err());
+        assert!(api.list_context_packages(&ContextPackageFilter::default())?.packages.is_empty());
+
+        let _ = std::fs::remove_file(&db_path);
+        Ok(())
+    }
+
+    // Test IDs: TAPI-013
+    #[test]
+    fn api_runs_over_any_memory_store_including_in_memory_stores() -> Result<()> {
+        let in_memory = || -> Result<MemoryKernelApi<SqliteStore>> {
+            Ok(MemoryKernelApi::from_store(SqliteStore::open(Path::new(":memory:"))?))
+        };
+        let api = in_memory()?;
+        let decision = api.add_summary(summary_fixture(RecordType::Decision, "Decision: usb"))?;
+        let package = api.query_recall(RecallRequest {
+            text: "usb".to_string(),
+            record_types: Vec::new(),
+            as_of: None,
+            tags: Vec::new(),
+        })?;
+        assert_eq!(package.selected_items.len(), 1);
+        assert_eq!(api.clone().context_show(&package.context_package_id)?, package);
+
+        let snapshot_dir =
+            std::env::temp_dir().join(format!("memorykernel-api-{}", ulid::Ulid::new()));
+        let manifest = api.export_snapshot(&snapshot_dir)?;
+        assert_eq!(manifest.files.first().map(|file| file.records), Some(1));
+        let copy = in_memory()?;
+        let imported = copy.import_snapshot(&snapshot_dir, false)?;
+        assert_eq!((imported.imported_records, imported.imported_context_packages), (1, 1));
+        let records = copy.list_records(&RecordFilter::default())?.records;
+        assert_eq!(
+            records.first().map(|record| record.memory_version_id),
+            Some(decision.memory_version_id)
+        );
+
+        let _ = std::fs::remove_dir_all(&snapshot_dir);
+        Ok(())
+    }
+
+    // Test IDs: TAPI-014
+    #[test]
+    fn api_conflict_resolution_defaults_per_handle_and_overrides_per_query() -> Result<()> {
+        let api = MemoryKernelApi::from_store(SqliteStore::open(Path::new(":memory:"))?)
+            .with_conflict_resolution(ConflictResolution::DenyOverrides);
+        for effect in [ConstraintEffect::Allow, ConstraintEffect::Deny] {
+            let _ = api.add_constraint(AddConstraintRequest {
+                actor: "user".to_string(),
+                action: "use".to_string(),
+                resource: "usb_drive".to_string(),
+                effect,
+                note: None,
+                expires_at: None,
+                memory_id: None,
+                version: 1,
+                writer: "tester".to_string(),
+                justification: "api fixture".to_string(),
+                source_uri: "file:///policy.md".to_string(),
+                source_hash: None,
+                evidence: Vec::new(),
+                confidence: Some(0.9),
+                truth_status: TruthStatus::Asserted,
+                authority: Authority::Authoritative,
+                created_at: None,
+                effective_at: None,
+                supersedes: Vec::new(),
+                contradicts: Vec::new(),
+                tags: Vec::new(),
+            })?;
+        }
+        let ask = |conflict_resolution: Option<ConflictResolution>| AskRequest {
+            text: "Am I allowed to use a USB drive?".to_string(),
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: "usb_drive".to_string(),
+            as_of: None,
+            ruleset: PolicyRuleset::default(),
+            conflict_resolution,
+        };
+
+        let denied = api.query_ask(ask(None))?;
+        assert_eq!(denied.answer.result, AnswerResult::Deny);
+        assert_eq!(denied.determinism.conflict_resolution, ConflictResolution::DenyOverrides);
+
+        let allowed = api.query_ask(ask(Some(ConflictResolution::AllowOverrides)))?;
+        assert_eq!(allowed.answer.result, AnswerResult::Allow);
+
+        let inconclusive = api.query_ask(ask(Some(ConflictResolution::Inconclusive)))?;
+        assert_eq!(inconclusive.answer.result, AnswerResult::Inconclusive);
+        assert_ne!(inconclusive.determinism.snapshot_id, denied.determinism.snapshot_id);
+        Ok(())
+    }
+
+    // Test IDs: TAPI-015
+    #[test]
+    fn api_records_by_source_matches_uri_prefix_or_hash() -> Result<()> {
+        let api = MemoryKernelApi::from_store(SqliteStore::open(Path::new(":memory:"))?);
+        for (source_uri, source_hash) in [
+            ("https://wiki.example/runbooks/vpn", "sha256:vpn1"),
+            ("https://wiki.example/runbooks/vpn#mfa", "sha256:vpn1"),
+            ("https://wiki.example/faq", "sha256:faq1"),
+        ] {
+            let mut request = summary_fixture(RecordType::Decision, "Decision: VPN requires MFA");
+            request.source_uri = source_uri.to_string();
+            request.source_hash = Some(source_hash.to_string());
+            let _ = api.add_summary(request)?;
+        }
+
+        let by_prefix = api.records_by_source(&SourceLookupRequest {
+            source_uri_prefix: Some("https://wiki.example/runbooks/".to_string()),
+            ..SourceLookupRequest::default()
+        })?;
+        assert_eq!(by_prefix.records.len(), 2);
+
+        let by_hash = api.records_by_source(&SourceLookupRequest {
+            source_hash: Some("sha256:faq1".to_string()),
+            limit: Some(1),
+            ..SourceLookupRequest::default()
+        })?;
+        assert_eq!(by_hash.records.len(), 1);
+        assert_eq!(by_hash.records[0].provenance.source_uri, "https://wiki.example/faq");
+        assert_eq!(by_hash.next_after, None);
+
+        assert!(api.records_by_source(&SourceLookupRequest::default()).is_err());
+        assert!(api
+            .records_by_source(&SourceLookupRequest {
+                source_hash: Some(" ".to_string()),
+                ..SourceLookupRequest::default()
+            })
+            .is_err());
+        Ok(())
+    }
+
+    // Test IDs: TAPI-016
+    #[test]
+    fn api_queries_record_latency_metrics() -> Result<()> {
+        let api = MemoryKernelApi::from_store(SqliteStore::open(Path::new(":memory:"))?);
+        let _ =
+            api.add_summary(summary_fixture(RecordType::Decision, "Decision: rotate usb keys"))?;
+        let recall = || RecallRequest {
+            text: "usb keys".to_string(),
+            record_types: vec![RecordType::Decision],
+            as_of: None,
+            tags: Vec::new(),
+        };
+        let started = OffsetDateTime::now_utc();
+        let first = api.query_recall(recall())?;
+        let _ = api.query_recall(recall())?;
+        let _ = api.query_ask(AskRequest {
+            text: "Am I allowed to use a USB drive?".to_string(),
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: "usb_drive".to_string(),
+            as_of: None,
+            ruleset: PolicyRuleset::default(),
+            conflict_resolution: None,
+        })?;
+
+        let stats = api.query_stats(None)?;
+        let modes = stats.iter().map(|mode| (mode.query_mode, mode.queries)).collect::<Vec<_>>();
+        assert_eq!(modes, vec![(QueryMode::Policy, 1), (QueryMode::Recall, 2)]);
+        let recall_stats = &stats[1];
+        assert_eq!(recall_stats.mean_candidate_records, 1);
+        assert_eq!(recall_stats.mean_selected_items, u64::try_from(first.selected_items.len())?);
+        assert!(recall_stats.p50_micros <= recall_stats.p95_micros);
+        assert!(recall_stats.p95_micros <= recall_stats.max_micros);
+        assert!(recall_stats.first_recorded_at >= started);
+
+        let later = api.query_stats(Some(OffsetDateTime::now_utc() + time::Duration::hours(1)))?;
+        assert!(later.is_empty());
+        Ok(())
+    }
+
+    // Test IDs: TAPI-017
+    #[test]
+    fn api_links_outcomes_to_packages_and_follows_selected_records() -> Result<()> {
+        let api = MemoryKernelApi::from_store(SqliteStore::open(Path::new(":memory:"))?);
+        let constraint = api.add_constraint(AddConstraintRequest {
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: "usb_drive".to_string(),
+            effect: ConstraintEffect::Deny,
+            note: None,
+            expires_at: None,
+            memory_id: None,
+            version: 1,
+            writer: "tester".to_string(),
+            justification: "usb lockdown".to_string(),
+            source_uri: "file:///policy.md".to_string(),
+            source_hash: None,
+            evidence: Vec::new(),
+            confidence: Some(0.9),
+            truth_status: TruthStatus::Asserted,
+            authority: Authority::Authoritative,
+            created_at: None,
+            effective_at: None,
+            supersedes: Vec::new(),
+            contradicts: Vec::new(),
+            tags: Vec::new(),
+        })?;
+        let package = api.query_ask(AskRequest {
+            text: "Am I allowed to use a USB drive?".to_string(),
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: "usb_drive".to_string(),
+            as_of: None,
+            ruleset: PolicyRuleset::default(),
+            conflict_resolution: None,
+        })?;
+        let outcome =
+            api.add_summary(summary_fixture(RecordType::Outcome, "Outcome: USB incidents fell"))?;
+
+        let link = api.link_outcome(&LinkOutcomeRequest {
+            context_package_id: package.context_package_id.clone(),
+            outcome_memory_version_id: outcome.memory_version_id,
+            writer: "analyst".to_string(),
+            justification: "quarterly incident review".to_string(),
+        })?;
+        assert_eq!(link.context_package_id, package.context_package_id);
+
+        let followed = api.outcomes_following(&OutcomesFollowingRequest {
+            memory_version_id: constraint.memory_version_id,
+        })?;
+        assert_eq!(followed.outcomes.len(), 1);
+        assert_eq!(followed.outcomes[0].link, link);
+        assert_eq!(followed.outcomes[0].outcome, outcome);
+        assert_eq!(followed.outcomes[0].generated_at, package.generated_at);
+
+        let blank = api.link_outcome(&LinkOutcomeRequest {
+            context_package_id: " ".to_string(),
+            ..link_request(&link)
+        });
+        assert!(blank.is_err());
+        let not_outcome = api.link_outcome(&LinkOutcomeRequest {
+            outcome_memory_version_id: constraint.memory_version_id,
+            ..link_request(&link)
+        });
+        assert!(not_outcome.is_err());
+        Ok(())
+    }
+
+    fn link_request(link: &PackageOutcome) -> LinkOutcomeRequest {
+        LinkOutcomeRequest {
+            context_package_id: link.context_package_id.clone(),
+            outcome_memory_version_id: link.outcome_memory_version_id,
+            writer: link.writer.clone(),
+            justification: link.justification.clone(),
+        }
+    }
 }
@@ -0,0 +1,124 @@
+//! Optional per-user defaults for `mk`, read from `config.toml`.
+//!
+//! The file lives at `$MK_CONFIG`, else `$XDG_CONFIG_HOME/mk/config.toml`, else
+//! `~/.config/mk/config.toml`. Its values become clap defaults, so flags on the command line
+//! still win and `--help` shows the configured value.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::output::OutputFormat;
+use crate::ConflictResolutionArg;
+
+/// Environment variable naming the config file; when set, the file MUST exist.
+const CONFIG_ENV: &str = "MK_CONFIG";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct CliConfig {
+    /// Default for the global `--db`.
+    db: Option<PathBuf>,
+    /// Default for every `--writer`.
+    writer: Option<String>,
+    /// Default for the global `--output`.
+    output: Option<String>,
+    /// Default for every `--conflict-resolution`.
+    conflict_resolution: Option<String>,
+}
+
+impl CliConfig {
+    /// Read the config file, or return empty defaults when the default location has none.
+    ///
+    /// # Errors
+    /// Returns an error when `$MK_CONFIG` names a missing file, or the file cannot be read or
+    /// parsed.
+    pub(crate) fn load() -> Result<Self> {
+        if let Some(path) = std::env::var_os(CONFIG_ENV) {
+            return Self::read(Path::new(&path));
+        }
+        match default_config_path() {
+            Some(path) if path.is_file() => Self::read(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    fn read(path: &Path) -> Result<Self> {
+        let body = fs::read_to_string(path)
+            .with_context(|| format!("failed to read mk config {}", path.display()))?;
+        Self::parse(&body).with_context(|| format!("invalid mk config {}", path.display()))
+    }
+
+    fn parse(body: &str) -> Result<Self> {
+        let config: Self = toml::from_str(body)?;
+        if let Some(output) = config.output.as_deref() {
+            OutputFormat::from_str(output, false)
+                .map_err(|_| anyhow!("unknown output format `{output}`"))?;
+        }
+        if config.writer.as_deref().is_some_and(|writer| writer.trim().is_empty()) {
+            return Err(anyhow!("writer must not be empty"));
+        }
+        if let Some(resolution) = config.conflict_resolution.as_deref() {
+            ConflictResolutionArg::from_str(resolution, false)
+                .map_err(|_| anyhow!("unknown conflict resolution `{resolution}`"))?;
+        }
+        Ok(config)
+    }
+
+    /// Install the configured values as defaults on `command` and all of its subcommands.
+    pub(crate) fn apply(&self, mut command: clap::Command) -> clap::Command {
+        if let Some(db) = self.db.as_ref() {
+            command = command.mut_arg("db", |arg| arg.default_value(db.as_os_str().to_owned()));
+        }
+        if let Some(output) = self.output.as_ref() {
+            command = command.mut_arg("output", |arg| arg.default_value(output.clone()));
+        }
+        if let Some(resolution) = self.conflict_resolution.as_ref() {
+            command = with_default(command, "conflict_resolution", resolution);
+        }
+        match self.writer.as_ref() {
+            Some(writer) => with_default(command, "writer", writer),
+            None => command,
+        }
+    }
+}
+
+fn default_config_path() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("mk").join("config.toml"))
+}
+
+/// Default every argument `id` in the tree, which also makes it optional on the command line.
+fn with_default(mut command: clap::Command, id: &str, value: &str) -> clap::Command {
+    if command.get_arguments().any(|arg| arg.get_id() == id) {
+        command = command.mut_arg(id, |arg| arg.default_value(value.to_string()).required(false));
+    }
+    let names = command.get_subcommands().map(|sub| sub.get_name().to_string()).collect::<Vec<_>>();
+    names.iter().fold(command, |command, name| {
+        command.mut_subcommand(name, |sub| with_default(sub, id, value))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CliConfig;
+
+    #[test]
+    fn parse_accepts_known_keys_and_rejects_the_rest() {
+        let config = CliConfig::parse(
+            "db = \"/tmp/mk.sqlite3\"\nwriter = \"ops\"\noutput = \"table\"\n\
+             conflict_resolution = \"deny-overrides\"\n",
+        );
+        assert!(config.is_ok());
+        assert!(CliConfig::parse("conflict_resolution = \"deny\"\n").is_err());
+        assert!(CliConfig::parse("output = \"yaml\"\n").is_err());
+        assert!(CliConfig::parse("writer = \" \"\n").is_err());
+        assert!(CliConfig::parse("namespace = \"team\"\n").is_err());
+    }
+}
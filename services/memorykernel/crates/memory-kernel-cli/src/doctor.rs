@@ -0,0 +1,284 @@
+//! `mk doctor`: one report over the store's readability, schema, WAL, referential health, and
+//! clock, with a suggested fix for every problem found.
+//!
+//! Doctor never migrates or repairs anything, and it does not create `--db` when it is missing.
+
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use memory_kernel_store_sqlite::{IntegrityReport, SqliteStore, DEFAULT_NAMESPACE};
+use serde::Serialize;
+use time::{Duration, OffsetDateTime};
+
+use crate::output::{Column, TableView};
+use crate::{emit_table, open_store};
+
+/// WAL size above which doctor suggests `mk db maintain`.
+const WAL_WARN_BYTES: u64 = 64 * 1024 * 1024;
+
+/// How far a record's `created_at` may run ahead of the local clock before doctor warns.
+const CLOCK_SKEW_TOLERANCE: Duration = Duration::minutes(5);
+
+const DOCTOR_COLUMNS: &[Column] = &[
+    Column { header: "check", pointer: "/check" },
+    Column { header: "status", pointer: "/status" },
+    Column { header: "detail", pointer: "/detail" },
+    Column { header: "fix", pointer: "/fix" },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+    /// Not run because an earlier check failed or the schema is behind.
+    Skip,
+}
+
+#[derive(Debug, Serialize)]
+struct DoctorCheck {
+    check: &'static str,
+    status: CheckStatus,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<String>,
+}
+
+impl DoctorCheck {
+    fn ok(check: &'static str, detail: impl Into<String>) -> Self {
+        Self { check, status: CheckStatus::Ok, detail: detail.into(), fix: None }
+    }
+
+    fn problem(
+        check: &'static str,
+        status: CheckStatus,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self { check, status, detail: detail.into(), fix: Some(fix.into()) }
+    }
+
+    fn skip(check: &'static str, reason: &str) -> Self {
+        Self { check, status: CheckStatus::Skip, detail: reason.to_string(), fix: None }
+    }
+}
+
+/// Print the doctor report and fail when any check has status `fail`.
+pub(crate) fn run(db: &Path, key_file: Option<&Path>) -> Result<()> {
+    let checks = diagnose(db, key_file, OffsetDateTime::now_utc());
+    let count = |status| checks.iter().filter(|check| check.status == status).count();
+    let (failed, warnings) = (count(CheckStatus::Fail), count(CheckStatus::Warn));
+    let items = checks.iter().map(serde_json::to_value).collect::<Result<Vec<_>, _>>()?;
+    emit_table(
+        serde_json::json!({
+            "db": db,
+            "healthy": failed == 0,
+            "failed": failed,
+            "warnings": warnings,
+            "checks": items,
+        }),
+        TableView {
+            columns: DOCTOR_COLUMNS,
+            items,
+            notes: vec![("summary", format!("{failed} failed, {warnings} warning(s)"))],
+        },
+    )?;
+    if failed > 0 {
+        return Err(anyhow!("doctor found {failed} failing check(s) in {}", db.display()));
+    }
+    Ok(())
+}
+
+fn diagnose(db: &Path, key_file: Option<&Path>, now: OffsetDateTime) -> Vec<DoctorCheck> {
+    const DATA_CHECKS: [&str; 5] =
+        ["foreign_keys", "wal_size", "orphaned_rows", "dangling_links", "clock_skew"];
+
+    if !db.is_file() {
+        return vec![DoctorCheck::problem(
+            "db_readable",
+            CheckStatus::Fail,
+            format!("{} does not exist", db.display()),
+            "check `--db` (or `db` in the mk config file); `mk db migrate` creates a new store",
+        )];
+    }
+    let store = match open_store(db, DEFAULT_NAMESPACE, key_file) {
+        Ok(store) => store,
+        Err(err) => {
+            return vec![DoctorCheck::problem(
+                "db_readable",
+                CheckStatus::Fail,
+                format!("cannot open store: {err:#}"),
+                "pass `--db-key-file` (or set MEMORY_KERNEL_DB_KEY) for an encrypted store, or \
+                 restore a backup with `mk db restore`",
+            )];
+        }
+    };
+    let report = match store.integrity_check() {
+        Ok(report) => report,
+        Err(err) => {
+            return vec![DoctorCheck::problem(
+                "db_readable",
+                CheckStatus::Fail,
+                format!("cannot read store: {err:#}"),
+                "the file may be encrypted or not a memory kernel store; check `--db` and \
+                 `--db-key-file`",
+            )];
+        }
+    };
+
+    let mut checks = vec![if report.quick_check_ok {
+        DoctorCheck::ok("db_readable", "quick_check ok")
+    } else {
+        DoctorCheck::problem(
+            "db_readable",
+            CheckStatus::Fail,
+            format!("quick_check: {}", report.quick_check_message),
+            "restore the latest backup with `mk db restore --in <backup>`",
+        )
+    }];
+
+    let schema = &report.schema_status;
+    if !schema.pending_versions.is_empty() {
+        checks.push(DoctorCheck::problem(
+            "migrations",
+            CheckStatus::Warn,
+            format!(
+                "schema version {} of {}; pending {:?}",
+                schema.current_version, schema.target_version, schema.pending_versions
+            ),
+            "run `mk db migrate` (preview with `--dry-run`)",
+        ));
+        checks.extend(
+            DATA_CHECKS
+                .into_iter()
+                .map(|check| DoctorCheck::skip(check, "schema is not up to date")),
+        );
+        return checks;
+    }
+    checks.push(DoctorCheck::ok(
+        "migrations",
+        format!("schema version {} is current", schema.current_version),
+    ));
+    checks.extend(data_checks(&store, &report, now));
+    checks
+}
+
+/// Checks that need a migrated schema, in `DATA_CHECKS` order.
+fn data_checks(
+    store: &SqliteStore,
+    report: &IntegrityReport,
+    now: OffsetDateTime,
+) -> Vec<DoctorCheck> {
+    let mut checks = vec![if report.foreign_key_violations.is_empty() {
+        DoctorCheck::ok("foreign_keys", "no violations")
+    } else {
+        let tables = report
+            .foreign_key_violations
+            .iter()
+            .map(|violation| violation.table.as_str())
+            .collect::<std::collections::BTreeSet<_>>();
+        DoctorCheck::problem(
+            "foreign_keys",
+            CheckStatus::Fail,
+            format!(
+                "{} violation(s) in {}",
+                report.foreign_key_violations.len(),
+                tables.into_iter().collect::<Vec<_>>().join(", ")
+            ),
+            "inspect with `mk db integrity-check`; restore a backup if parent rows were lost",
+        )
+    }];
+
+    let wal_bytes = store.wal_bytes();
+    checks.push(if wal_bytes > WAL_WARN_BYTES {
+        DoctorCheck::problem(
+            "wal_size",
+            CheckStatus::Warn,
+            format!("write-ahead log is {wal_bytes} bytes"),
+            "run `mk db maintain` to checkpoint and truncate the WAL",
+        )
+    } else {
+        DoctorCheck::ok("wal_size", format!("write-ahead log is {wal_bytes} bytes"))
+    });
+
+    checks.push(match store.orphaned_rows() {
+        Ok(orphans) if orphans.is_empty() => DoctorCheck::ok("orphaned_rows", "none"),
+        Ok(orphans) => {
+            let tables = orphans
+                .iter()
+                .map(|orphan| orphan.table.as_str())
+                .collect::<std::collections::BTreeSet<_>>();
+            DoctorCheck::problem(
+                "orphaned_rows",
+                CheckStatus::Warn,
+                format!(
+                    "{} row(s) without a record version in {}",
+                    orphans.len(),
+                    tables.into_iter().collect::<Vec<_>>().join(", ")
+                ),
+                "the rows are unreachable; preview with `mk db integrity-check --repair --dry-run`, \
+                 then delete them with `--repair`",
+            )
+        }
+        Err(err) => probe_failed("orphaned_rows", &err),
+    });
+
+    checks.push(match store.dangling_links() {
+        Ok(links) if links.is_empty() => DoctorCheck::ok("dangling_links", "none"),
+        Ok(links) => DoctorCheck::problem(
+            "dangling_links",
+            CheckStatus::Warn,
+            format!(
+                "{} link(s) point at missing versions (first: link {})",
+                links.len(),
+                links[0].link_id
+            ),
+            "restore the missing versions from a backup, or drop the links with \
+             `mk db integrity-check --repair`",
+        ),
+        Err(err) => probe_failed("dangling_links", &err),
+    });
+
+    checks.push(match store.latest_record_created_at() {
+        Ok(None) => DoctorCheck::ok("clock_skew", "no records"),
+        Ok(Some(latest)) if latest - now > CLOCK_SKEW_TOLERANCE => DoctorCheck::problem(
+            "clock_skew",
+            CheckStatus::Warn,
+            format!("latest record created_at is {} ahead of the local clock", latest - now),
+            "check this host's clock and the clocks of the writers; `as_of` queries before that \
+             time will not see those records",
+        ),
+        Ok(Some(_)) => DoctorCheck::ok("clock_skew", "record timestamps are not in the future"),
+        Err(err) => probe_failed("clock_skew", &err),
+    });
+
+    checks
+}
+
+fn probe_failed(check: &'static str, err: &anyhow::Error) -> DoctorCheck {
+    DoctorCheck::problem(
+        check,
+        CheckStatus::Fail,
+        format!("probe failed: {err:#}"),
+        "run `mk db integrity-check` and restore a backup if the schema is damaged",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use time::OffsetDateTime;
+
+    use super::{diagnose, CheckStatus};
+
+    #[test]
+    fn missing_database_fails_without_creating_it() {
+        let path = std::env::temp_dir().join(format!("mk-doctor-{}.sqlite3", ulid::Ulid::new()));
+        let checks = diagnose(&path, None, OffsetDateTime::now_utc());
+        assert_eq!(checks.len(), 1);
+        assert_eq!((checks[0].check, checks[0].status), ("db_readable", CheckStatus::Fail));
+        assert!(!Path::new(&path).exists());
+    }
+}
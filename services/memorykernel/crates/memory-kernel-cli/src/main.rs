@@ -1,32 +1,69 @@
-use std::fs;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, IsTerminal, Read, Write};
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 use anyhow::{anyhow, Context, Result};
+use chacha20poly1305::aead::stream::{DecryptorBE32, EncryptorBE32};
 use chacha20poly1305::aead::{Aead, KeyInit};
 use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
-use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use hkdf::Hkdf;
 use hmac::{Hmac, Mac};
 use memory_kernel_core::{
-    build_context_package, build_recall_context_package, default_recall_record_types, Authority,
-    ConstraintEffect, ConstraintPayload, ConstraintScope, LinkType, MemoryId, MemoryPayload,
-    MemoryRecord, MemoryVersionId, QueryRequest, RecordType, TruthStatus,
+    build_context_package_with_ruleset, build_recall_context_package_with_decay,
+    default_recall_record_types, explain_record, retain_tagged, simulate_constraint, AnswerResult,
+    AssumptionStatus, Authority, ConfidenceDecay, ConflictResolution, ConstraintEffect,
+    ConstraintPayload, ConstraintScope, ContextPackage, LinkType, MemoryId, MemoryPayload,
+    MemoryRecord, MemoryVersionId, PolicyRuleset, QueryRequest, RecordType, RiskSeverity,
+    TaskStatus, TruthStatus,
 };
 use memory_kernel_outcome_cli::OutcomeCommand as OutcomeCliCommand;
-use memory_kernel_store_sqlite::{ExportManifest, SqliteStore};
+use memory_kernel_store_sqlite::{
+    summarize_query_metrics, ContextPackageFilter, ExportCheckpoint, ExportFilter, ExportManifest,
+    ImportLimits, IntegrityReport, QueryMetric, QueryMode, RecordFilter, RepairReport,
+    SnapshotExport, SnapshotStream, SqliteStore, StoreKey, DEFAULT_NAMESPACE,
+    DEFAULT_RECALL_CANDIDATE_LIMIT, STORE_KEY_ENV,
+};
 use rand::RngCore;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 use time::OffsetDateTime;
 use ulid::Ulid;
+use x25519_dalek::{PublicKey, SharedSecret, StaticSecret};
+
+use crate::config::CliConfig;
+use crate::output::{Column, OutputFormat, TableView};
+
+mod config;
+mod doctor;
+mod output;
+mod tui;
+mod watch;
 
 const CLI_CONTRACT_VERSION: &str = "cli.v1";
 const MANIFEST_FILE: &str = "manifest.json";
+const SNAPSHOT_RECORDS_FILE: &str = "memory_records.ndjson";
+const SNAPSHOT_CONTEXT_PACKAGES_FILE: &str = "context_packages.ndjson";
+/// `--out`/`--in` value that streams a snapshot archive over stdout/stdin.
+const STDIO_PATH: &str = "-";
 const MANIFEST_SIG_FILE: &str = "manifest.sig";
 const MANIFEST_SECURITY_FILE: &str = "manifest.security.json";
 const ENCRYPTION_MAGIC: &[u8] = b"MKENC1";
 const ENCRYPTION_ALGORITHM: &str = "xchacha20poly1305";
 const SIGNATURE_ALGORITHM: &str = "hmac-sha256";
+const ARCHIVE_ENCRYPTION_MAGIC: &[u8] = b"MKAENC1";
+const ARCHIVE_RECIPIENTS_MAGIC: &[u8] = b"MKAREC1";
+const ARCHIVE_NONCE_LEN: usize = 19;
+const ARCHIVE_CHUNK_LEN: usize = 64 * 1024;
+const ARCHIVE_TAG_LEN: usize = 16;
+/// Largest accepted archive recipients header; read before decryption, so it is untrusted.
+const MAX_RECIPIENTS_HEADER_BYTES: u64 = 1024 * 1024;
+const KEY_WRAP_ALGORITHM: &str = "x25519-hkdf-sha256-xchacha20poly1305";
+const KEY_WRAP_INFO: &[u8] = b"memorykernel/snapshot-recipient/v1";
 
 type HmacSha256 = Hmac<Sha256>;
 
@@ -37,6 +74,18 @@ struct Cli {
     #[arg(long, default_value = "./memory_kernel.sqlite3")]
     db: PathBuf,
 
+    /// Namespace that scopes memory, query, context, and export/import commands.
+    #[arg(long, global = true, default_value = DEFAULT_NAMESPACE)]
+    namespace: String,
+
+    /// Hex key file for an encrypted store; falls back to `MEMORY_KERNEL_DB_KEY`.
+    #[arg(long, global = true)]
+    db_key_file: Option<PathBuf>,
+
+    /// Output format; `table` and `csv` apply to commands with a tabular view.
+    #[arg(long, global = true, value_enum, default_value = "json")]
+    output: OutputFormat,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -47,6 +96,10 @@ enum Command {
         #[command(subcommand)]
         command: Box<DbCommand>,
     },
+    Keys {
+        #[command(subcommand)]
+        command: Box<KeysCommand>,
+    },
     Memory {
         #[command(subcommand)]
         command: Box<MemoryCommand>,
@@ -63,6 +116,18 @@ enum Command {
         #[command(subcommand)]
         command: Box<OutcomeCliCommand>,
     },
+    /// Print a shell completion script to stdout.
+    Completions(CompletionsArgs),
+    /// Browse records, lineage, and ask/recall results interactively.
+    Tui,
+    /// Check store health and suggest a fix for each problem found.
+    Doctor,
+}
+
+#[derive(Debug, Args)]
+struct CompletionsArgs {
+    #[arg(value_enum)]
+    shell: clap_complete::Shell,
 }
 
 #[derive(Debug, Subcommand)]
@@ -73,7 +138,28 @@ enum DbCommand {
     Import(DbImportArgs),
     Backup(DbBackupArgs),
     Restore(DbRestoreArgs),
-    IntegrityCheck,
+    IntegrityCheck(DbIntegrityCheckArgs),
+    Encrypt(DbEncryptArgs),
+    Maintain(DbMaintainArgs),
+    Stats(DbStatsArgs),
+    Diff(DbDiffArgs),
+}
+
+#[derive(Debug, Args)]
+struct DbStatsArgs {
+    /// Only summarize queries recorded at or after this RFC3339 time.
+    #[arg(long)]
+    since: Option<String>,
+}
+
+#[derive(Debug, Args)]
+struct DbDiffArgs {
+    /// Baseline snapshot directory.
+    #[arg(long)]
+    a: PathBuf,
+    /// Snapshot directory compared against `--a`.
+    #[arg(long)]
+    b: PathBuf,
 }
 
 #[derive(Debug, Args)]
@@ -84,24 +170,144 @@ struct DbMigrateArgs {
 
 #[derive(Debug, Args)]
 struct DbExportArgs {
+    /// Snapshot directory or archive file; `-` streams a `tar-zstd` archive to stdout.
     #[arg(long)]
     out: PathBuf,
+    /// Only export records of this type (repeatable).
+    #[arg(long = "record-type", value_enum)]
+    record_types: Vec<RecordTypeArg>,
+    /// Only export records created at or after this RFC3339 time.
+    #[arg(long)]
+    created_from: Option<String>,
+    /// Only export records created before this RFC3339 time.
+    #[arg(long)]
+    created_to: Option<String>,
+    /// Only export records written by this writer (repeatable).
+    #[arg(long = "writer")]
+    writers: Vec<String>,
+    /// Previous export's `manifest.json`; only rows written after its checkpoint are exported.
+    #[arg(
+        long,
+        conflicts_with_all = ["record_types", "created_from", "created_to", "writers"]
+    )]
+    since_manifest: Option<PathBuf>,
+    /// `dir` writes a snapshot directory; `tar-zstd` writes a single `.mkarchive` file.
+    #[arg(long, value_enum, default_value = "dir")]
+    format: SnapshotFormatArg,
     #[arg(long)]
     signing_key_file: Option<PathBuf>,
     #[arg(long)]
     encrypt_key_file: Option<PathBuf>,
+    /// Hex X25519 public key to encrypt the snapshot to (repeatable); each recipient decrypts
+    /// with their own `--identity-file`.
+    #[arg(long = "encrypt-to", conflicts_with = "encrypt_key_file")]
+    encrypt_to: Vec<String>,
 }
 
 #[derive(Debug, Args)]
 struct DbImportArgs {
+    /// Snapshot directory or archive file; `-` reads a `tar-zstd` archive from stdin.
     #[arg(long = "in")]
     input: PathBuf,
+    /// Layout of `--in`: a snapshot directory or a `tar-zstd` `.mkarchive` file.
+    #[arg(long, value_enum, default_value = "dir")]
+    format: SnapshotFormatArg,
     #[arg(long, default_value_t = true)]
     skip_existing: bool,
     #[arg(long)]
     verify_key_file: Option<PathBuf>,
     #[arg(long)]
     decrypt_key_file: Option<PathBuf>,
+    /// Hex X25519 private key file for snapshots exported with `--encrypt-to`.
+    #[arg(long)]
+    identity_file: Option<PathBuf>,
+    #[arg(long, default_value_t = false)]
+    allow_unsigned: bool,
+    /// Reject snapshot NDJSON lines longer than this many bytes (default 8 MiB).
+    #[arg(long)]
+    max_line_bytes: Option<usize>,
+    /// Reject snapshot files with more than this many rows (default 1000000).
+    #[arg(long)]
+    max_rows: Option<usize>,
+    /// Reject rows whose JSON objects and arrays nest deeper than this (default 64).
+    #[arg(long)]
+    max_depth: Option<usize>,
+}
+
+#[derive(Debug, Args)]
+struct DbIntegrityCheckArgs {
+    /// Delete dangling links, orphaned per-version rows, and context packages naming missing
+    /// record versions, in one transaction.
+    #[arg(long, default_value_t = false)]
+    repair: bool,
+    /// With `--repair`, report what would be deleted without changing the store.
+    #[arg(long, default_value_t = false, requires = "repair")]
+    dry_run: bool,
+}
+
+#[derive(Debug, Args)]
+struct DbMaintainArgs {
+    /// Also write a compacted copy of the database here; MUST NOT already exist.
+    #[arg(long)]
+    vacuum_into: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct DbEncryptArgs {
+    /// Path for the encrypted copy; MUST NOT already exist.
+    #[arg(long)]
+    out: PathBuf,
+    /// Hex key file for the encrypted copy; falls back to `MEMORY_KERNEL_DB_KEY`.
+    #[arg(long)]
+    key_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum KeysCommand {
+    Generate(KeysGenerateArgs),
+    Fingerprint(KeysFingerprintArgs),
+    Rotate(KeysRotateArgs),
+    Resign(KeysResignArgs),
+}
+
+#[derive(Debug, Args)]
+struct KeysGenerateArgs {
+    #[arg(long, value_enum)]
+    kind: KeyKindArg,
+    /// Path for the new hex key file; MUST NOT already exist.
+    #[arg(long)]
+    out: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct KeysFingerprintArgs {
+    #[arg(long, value_enum)]
+    kind: KeyKindArg,
+    #[arg(long)]
+    key_file: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct KeysRotateArgs {
+    #[arg(long, value_enum)]
+    kind: KeyKindArg,
+    #[arg(long)]
+    key_file: PathBuf,
+    /// Where to move the current key; defaults to `<key-file>.previous` and MUST NOT exist.
+    #[arg(long)]
+    retired_out: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct KeysResignArgs {
+    /// Snapshot directory whose `manifest.json` is re-signed.
+    #[arg(long = "in")]
+    input: PathBuf,
+    #[arg(long)]
+    signing_key_file: PathBuf,
+    /// Key that verifies the current `manifest.sig` before it is replaced.
+    #[arg(long)]
+    verify_key_file: Option<PathBuf>,
     #[arg(long, default_value_t = false)]
     allow_unsigned: bool,
 }
@@ -124,8 +330,11 @@ enum MemoryCommand {
         #[command(subcommand)]
         command: Box<AddCommand>,
     },
+    AddBatch(AddBatchArgs),
     Link(LinkArgs),
-    List,
+    Redact(RedactArgs),
+    List(ListArgs),
+    Contradictions,
 }
 
 #[derive(Debug, Subcommand)]
@@ -135,6 +344,9 @@ enum AddCommand {
     Preference(AddSummaryArgs),
     Event(AddSummaryArgs),
     Outcome(AddSummaryArgs),
+    Task(AddTaskArgs),
+    Risk(AddRiskArgs),
+    Assumption(AddAssumptionArgs),
 }
 
 #[derive(Debug, Args)]
@@ -149,6 +361,8 @@ struct AddConstraintArgs {
     effect: EffectArg,
     #[arg(long)]
     note: Option<String>,
+    #[arg(long)]
+    expires_at: Option<String>,
     #[command(flatten)]
     write: WriteArgs,
 }
@@ -161,6 +375,36 @@ struct AddSummaryArgs {
     write: WriteArgs,
 }
 
+#[derive(Debug, Args)]
+struct AddTaskArgs {
+    #[arg(long)]
+    summary: String,
+    #[arg(long, value_enum, default_value_t = TaskStatusArg::Open)]
+    status: TaskStatusArg,
+    #[command(flatten)]
+    write: WriteArgs,
+}
+
+#[derive(Debug, Args)]
+struct AddRiskArgs {
+    #[arg(long)]
+    summary: String,
+    #[arg(long, value_enum)]
+    severity: RiskSeverityArg,
+    #[command(flatten)]
+    write: WriteArgs,
+}
+
+#[derive(Debug, Args)]
+struct AddAssumptionArgs {
+    #[arg(long)]
+    summary: String,
+    #[arg(long, value_enum, default_value_t = AssumptionStatusArg::Unverified)]
+    status: AssumptionStatusArg,
+    #[command(flatten)]
+    write: WriteArgs,
+}
+
 #[derive(Debug, Args)]
 struct WriteArgs {
     #[arg(long)]
@@ -191,6 +435,15 @@ struct WriteArgs {
     supersedes: Vec<String>,
     #[arg(long = "contradicts")]
     contradicts: Vec<String>,
+    /// Tag to attach to the record, e.g. a team or project (repeatable).
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+struct AddBatchArgs {
+    #[arg(long)]
+    file: PathBuf,
 }
 
 #[derive(Debug, Args)]
@@ -207,13 +460,64 @@ struct LinkArgs {
     justification: String,
 }
 
+#[derive(Debug, Args)]
+struct RedactArgs {
+    #[arg(long)]
+    memory_version_id: String,
+    #[arg(long)]
+    writer: String,
+    #[arg(long)]
+    justification: String,
+}
+
+#[derive(Debug, Args)]
+struct ListArgs {
+    #[arg(long)]
+    record_type: Option<RecordTypeArg>,
+    #[arg(long)]
+    writer: Option<String>,
+    #[arg(long)]
+    truth_status: Option<TruthStatusArg>,
+    #[arg(long)]
+    effective_from: Option<String>,
+    #[arg(long)]
+    effective_to: Option<String>,
+    /// Only list records carrying this tag (repeatable; all must match).
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+    /// Only list records whose provenance `source_uri` starts with this text.
+    #[arg(long)]
+    source_uri_prefix: Option<String>,
+    /// Only list records whose provenance `source_hash` equals this digest.
+    #[arg(long)]
+    source_hash: Option<String>,
+    #[arg(long)]
+    after: Option<String>,
+    #[arg(long)]
+    limit: Option<usize>,
+}
+
 #[derive(Debug, Subcommand)]
 enum QueryCommand {
-    Ask(QueryAskArgs),
+    Ask(QueryAskCommandArgs),
     Recall(QueryRecallArgs),
+    Simulate(Box<QuerySimulateArgs>),
+    Explain(QueryExplainArgs),
 }
 
 #[derive(Debug, Args)]
+struct QueryAskCommandArgs {
+    #[command(flatten)]
+    query: QueryAskArgs,
+    /// Keep re-running the query and print what changes in the answer and selected items.
+    #[arg(long, default_value_t = false)]
+    watch: bool,
+    /// With `--watch`, re-run every this many seconds instead of when `--db` changes.
+    #[arg(long, requires = "watch")]
+    watch_interval: Option<u64>,
+}
+
+#[derive(Debug, Clone, Args)]
 struct QueryAskArgs {
     #[arg(long)]
     text: String,
@@ -225,6 +529,14 @@ struct QueryAskArgs {
     resource: String,
     #[arg(long)]
     as_of: Option<String>,
+    #[arg(long, value_enum, default_value_t = RulesetArg::Precedence)]
+    ruleset: RulesetArg,
+    /// How an allow/deny conflict the ruleset cannot settle is answered.
+    #[arg(long, value_enum, default_value_t = ConflictResolutionArg::Inconclusive)]
+    conflict_resolution: ConflictResolutionArg,
+    /// Confidence half-life as `<truth_status>=<days>` (repeatable).
+    #[arg(long = "half-life")]
+    half_lives: Vec<String>,
 }
 
 #[derive(Debug, Args)]
@@ -235,11 +547,74 @@ struct QueryRecallArgs {
     record_types: Vec<RecordTypeArg>,
     #[arg(long)]
     as_of: Option<String>,
+    /// Only recall records carrying this tag (repeatable; all must match).
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+    /// Confidence half-life as `<truth_status>=<days>` (repeatable).
+    #[arg(long = "half-life")]
+    half_lives: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+struct QuerySimulateArgs {
+    #[command(flatten)]
+    constraint: AddConstraintArgs,
+    /// NDJSON file of sample queries (`text`, `actor`, `action`, `resource`, optional `as_of`).
+    #[arg(long)]
+    queries: PathBuf,
+    /// Confidence half-life as `<truth_status>=<days>` (repeatable).
+    #[arg(long = "half-life")]
+    half_lives: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+struct QueryExplainArgs {
+    #[arg(long)]
+    memory_version_id: String,
+    #[command(flatten)]
+    query: QueryAskArgs,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimulationQueryRow {
+    text: String,
+    actor: String,
+    action: String,
+    resource: String,
+    #[serde(default)]
+    as_of: Option<String>,
+    #[serde(default)]
+    ruleset: PolicyRuleset,
+    #[serde(default)]
+    conflict_resolution: ConflictResolution,
 }
 
 #[derive(Debug, Subcommand)]
 enum ContextCommand {
     Show(ContextShowArgs),
+    List(ContextListArgs),
+    LinkOutcome(ContextLinkOutcomeArgs),
+    Outcomes(ContextOutcomesArgs),
+}
+
+#[derive(Debug, Args)]
+struct ContextLinkOutcomeArgs {
+    #[arg(long)]
+    context_package_id: String,
+    /// `memory_version_id` of the outcome record that followed the package's decision.
+    #[arg(long)]
+    outcome_memory_version_id: String,
+    #[arg(long)]
+    writer: String,
+    #[arg(long)]
+    justification: String,
+}
+
+#[derive(Debug, Args)]
+struct ContextOutcomesArgs {
+    /// List outcomes of packages whose selected items include this version.
+    #[arg(long)]
+    memory_version_id: String,
 }
 
 #[derive(Debug, Args)]
@@ -248,6 +623,110 @@ struct ContextShowArgs {
     context_package_id: String,
 }
 
+#[derive(Debug, Args)]
+struct ContextListArgs {
+    #[arg(long)]
+    generated_from: Option<String>,
+    #[arg(long)]
+    generated_to: Option<String>,
+    #[arg(long)]
+    actor: Option<String>,
+    #[arg(long)]
+    resource: Option<String>,
+    #[arg(long)]
+    answer_result: Option<AnswerResultArg>,
+    #[arg(long)]
+    after: Option<String>,
+    #[arg(long)]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum KeyKindArg {
+    /// HMAC-SHA256 manifest signing key.
+    Signing,
+    /// XChaCha20-Poly1305 snapshot encryption key.
+    Encryption,
+    /// X25519 private key for `--encrypt-to` recipients.
+    Identity,
+}
+
+impl KeyKindArg {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Signing => "signing",
+            Self::Encryption => "encryption",
+            Self::Identity => "identity",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SnapshotFormatArg {
+    Dir,
+    TarZstd,
+}
+
+impl SnapshotFormatArg {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Dir => "dir",
+            Self::TarZstd => "tar-zstd",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum AnswerResultArg {
+    Allow,
+    Deny,
+    Inconclusive,
+}
+
+impl AnswerResultArg {
+    fn into_answer_result(self) -> AnswerResult {
+        match self {
+            Self::Allow => AnswerResult::Allow,
+            Self::Deny => AnswerResult::Deny,
+            Self::Inconclusive => AnswerResult::Inconclusive,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum RulesetArg {
+    Precedence,
+    DenyOverrides,
+    MostRecentWins,
+}
+
+impl RulesetArg {
+    fn into_policy_ruleset(self) -> PolicyRuleset {
+        match self {
+            Self::Precedence => PolicyRuleset::Precedence,
+            Self::DenyOverrides => PolicyRuleset::DenyOverrides,
+            Self::MostRecentWins => PolicyRuleset::MostRecentWins,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ConflictResolutionArg {
+    Inconclusive,
+    DenyOverrides,
+    AllowOverrides,
+}
+
+impl ConflictResolutionArg {
+    fn into_conflict_resolution(self) -> ConflictResolution {
+        match self {
+            Self::Inconclusive => ConflictResolution::Inconclusive,
+            Self::DenyOverrides => ConflictResolution::DenyOverrides,
+            Self::AllowOverrides => ConflictResolution::AllowOverrides,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum TruthStatusArg {
     Asserted,
@@ -270,6 +749,30 @@ enum EffectArg {
     Deny,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum TaskStatusArg {
+    Open,
+    #[value(name = "in_progress")]
+    InProgress,
+    Done,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum RiskSeverityArg {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum AssumptionStatusArg {
+    Unverified,
+    Validated,
+    Invalidated,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum RelationArg {
     Supersedes,
@@ -283,6 +786,9 @@ enum RecordTypeArg {
     Preference,
     Event,
     Outcome,
+    Task,
+    Risk,
+    Assumption,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -291,6 +797,25 @@ struct SnapshotSecurityMetadata {
     encryption_algorithm: Option<String>,
     signature_file: Option<String>,
     signature_algorithm: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key_wrap_algorithm: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    recipients: Vec<SnapshotRecipient>,
+}
+
+/// The snapshot file key wrapped for one X25519 recipient.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotRecipient {
+    recipient: String,
+    ephemeral_public_key: String,
+    wrapped_file_key: String,
+}
+
+/// Header of a recipient-encrypted archive, written after `MKAREC1` as length-prefixed JSON.
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchiveRecipientsHeader {
+    key_wrap_algorithm: String,
+    recipients: Vec<SnapshotRecipient>,
 }
 
 fn with_contract_version(value: Value) -> Value {
@@ -310,35 +835,144 @@ fn with_contract_version(value: Value) -> Value {
 }
 
 fn emit_json(value: Value) -> Result<()> {
-    println!("{}", serde_json::to_string_pretty(&with_contract_version(value))?);
-    Ok(())
+    output::emit(&with_contract_version(value), None)
+}
+
+fn emit_table(value: Value, view: TableView) -> Result<()> {
+    output::emit(&with_contract_version(value), Some(view))
+}
+
+const RECORD_COLUMNS: &[Column] = &[
+    Column { header: "memory_version_id", pointer: "/memory_version_id" },
+    Column { header: "type", pointer: "/payload/record_type" },
+    Column { header: "version", pointer: "/version" },
+    Column { header: "truth_status", pointer: "/truth_status" },
+    Column { header: "authority", pointer: "/authority" },
+    Column { header: "confidence", pointer: "/confidence" },
+    Column { header: "writer", pointer: "/writer" },
+    Column { header: "effective_at", pointer: "/effective_at" },
+    Column { header: "tags", pointer: "/tags" },
+    Column { header: "summary", pointer: "/payload/payload/summary" },
+];
+
+const CONTEXT_ITEM_COLUMNS: &[Column] = &[
+    Column { header: "rank", pointer: "/rank" },
+    Column { header: "included", pointer: "/why/included" },
+    Column { header: "memory_version_id", pointer: "/memory_version_id" },
+    Column { header: "type", pointer: "/record_type" },
+    Column { header: "truth_status", pointer: "/truth_status" },
+    Column { header: "authority", pointer: "/authority" },
+    Column { header: "confidence", pointer: "/confidence" },
+    Column { header: "reasons", pointer: "/why/reasons" },
+];
+
+const INTEGRITY_COLUMNS: &[Column] = &[
+    Column { header: "check", pointer: "/check" },
+    Column { header: "ok", pointer: "/ok" },
+    Column { header: "detail", pointer: "/detail" },
+];
+
+const DIFF_COLUMNS: &[Column] = &[
+    Column { header: "kind", pointer: "/kind" },
+    Column { header: "change", pointer: "/change" },
+    Column { header: "id", pointer: "/id" },
+    Column { header: "a_sha256", pointer: "/a_sha256" },
+    Column { header: "b_sha256", pointer: "/b_sha256" },
+];
+
+/// One row per integrity check, then one per foreign key violation.
+fn integrity_rows(report: &IntegrityReport) -> Vec<Value> {
+    let schema = &report.schema_status;
+    let mut rows = vec![
+        serde_json::json!({
+            "check": "quick_check",
+            "ok": report.quick_check_ok,
+            "detail": report.quick_check_message,
+        }),
+        serde_json::json!({
+            "check": "foreign_keys",
+            "ok": report.foreign_key_violations.is_empty(),
+            "detail": format!("{} violation(s)", report.foreign_key_violations.len()),
+        }),
+        serde_json::json!({
+            "check": "schema",
+            "ok": schema.pending_versions.is_empty(),
+            "detail": format!(
+                "version {} of {}",
+                schema.current_version, schema.target_version
+            ),
+        }),
+    ];
+    rows.extend(report.foreign_key_violations.iter().map(|violation| {
+        serde_json::json!({
+            "check": "foreign_key_violation",
+            "ok": false,
+            "detail": format!(
+                "{} rowid {} -> {} (index {})",
+                violation.table, violation.rowid, violation.parent, violation.fk_index
+            ),
+        })
+    }));
+    rows
 }
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let config = CliConfig::load()?;
+    let matches = config.apply(Cli::command()).get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    output::set_format(cli.output);
+    let open_store = || open_store(&cli.db, &cli.namespace, cli.db_key_file.as_deref());
     match cli.command {
-        Command::Db { command } => {
-            let mut store = SqliteStore::open(&cli.db)?;
-            run_db(*command, &mut store)
-        }
+        Command::Db { command } => match *command {
+            DbCommand::Diff(args) => run_db_diff(&args),
+            command => {
+                let mut store = open_store()?;
+                run_db(command, &mut store)
+            }
+        },
+        Command::Keys { command } => run_keys(*command),
         Command::Memory { command } => {
-            let mut store = SqliteStore::open(&cli.db)?;
+            let mut store = open_store()?;
             run_memory(*command, &mut store)
         }
         Command::Query { command } => {
-            let mut store = SqliteStore::open(&cli.db)?;
-            run_query(*command, &mut store)
+            let mut store = open_store()?;
+            run_query(*command, &mut store, &cli.db)
         }
         Command::Context { command } => {
-            let mut store = SqliteStore::open(&cli.db)?;
+            let mut store = open_store()?;
             run_context(*command, &mut store)
         }
         Command::Outcome { command } => {
+            if StoreKey::resolve(cli.db_key_file.as_deref())?.is_some() {
+                return Err(anyhow!("outcome commands do not support encrypted stores"));
+            }
             memory_kernel_outcome_cli::run_outcome_with_db(&cli.db, *command)
         }
+        Command::Doctor => doctor::run(&cli.db, cli.db_key_file.as_deref()),
+        Command::Tui => {
+            let mut store = open_store()?;
+            tui::run(&mut store)
+        }
+        Command::Completions(args) => {
+            clap_complete::generate(args.shell, &mut Cli::command(), "mk", &mut std::io::stdout());
+            Ok(())
+        }
     }
 }
 
+fn open_store(db: &Path, namespace: &str, key_file: Option<&Path>) -> Result<SqliteStore> {
+    let key = StoreKey::resolve(key_file)?;
+    let mut store = SqliteStore::open_with_key(db, key.as_ref())?;
+    store.set_namespace(namespace)?;
+    Ok(store)
+}
+
+/// Snapshot extra for a non-default namespace; mirrors `MemoryKernelApi` so ids agree.
+fn namespace_extra(store: &SqliteStore) -> Option<String> {
+    (store.namespace() != DEFAULT_NAMESPACE).then(|| format!("namespace={}", store.namespace()))
+}
+
 fn run_db(command: DbCommand, store: &mut SqliteStore) -> Result<()> {
     match command {
         DbCommand::SchemaVersion => run_db_schema_version(store),
@@ -347,7 +981,11 @@ fn run_db(command: DbCommand, store: &mut SqliteStore) -> Result<()> {
         DbCommand::Import(args) => run_db_import(&args, store),
         DbCommand::Backup(args) => run_db_backup(&args, store),
         DbCommand::Restore(args) => run_db_restore(&args, store),
-        DbCommand::IntegrityCheck => run_db_integrity_check(store),
+        DbCommand::IntegrityCheck(args) => run_db_integrity_check(&args, store),
+        DbCommand::Encrypt(args) => run_db_encrypt(&args, store),
+        DbCommand::Maintain(args) => run_db_maintain(&args, store),
+        DbCommand::Stats(args) => run_db_stats(&args, store),
+        DbCommand::Diff(args) => run_db_diff(&args),
     }
 }
 
@@ -362,6 +1000,183 @@ fn run_db_schema_version(store: &SqliteStore) -> Result<()> {
     }))
 }
 
+fn run_db_stats(args: &DbStatsArgs, store: &mut SqliteStore) -> Result<()> {
+    store.migrate()?;
+    let since = args.since.as_deref().map(parse_rfc3339).transpose()?;
+    let metrics = store.list_query_metrics(since)?;
+    emit_json(serde_json::json!({
+        "since": args.since,
+        "queries": metrics.len(),
+        "modes": summarize_query_metrics(&metrics)
+    }))
+}
+
+/// Compare two snapshot directories row by row. Records are keyed by `memory_version_id` and
+/// context packages by `context_package_id`; a row present in both whose NDJSON line hashes
+/// differently, such as a record redacted between exports, is reported as changed.
+fn run_db_diff(args: &DbDiffArgs) -> Result<()> {
+    let a = SnapshotRowDigests::read(&args.a)?;
+    let b = SnapshotRowDigests::read(&args.b)?;
+    let mut changes = Vec::new();
+    let records = diff_row_digests("record", &a.records, &b.records, &mut changes);
+    let packages =
+        diff_row_digests("context_package", &a.context_packages, &b.context_packages, &mut changes);
+    let summary = format!("records: {records}; context packages: {packages}");
+
+    let items = changes
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()
+        .context("failed to serialize snapshot diff")?;
+    emit_table(
+        serde_json::json!({
+            "a": args.a,
+            "b": args.b,
+            "identical": changes.is_empty(),
+            "records": records,
+            "context_packages": packages,
+            "changes": items,
+            "summary": summary,
+        }),
+        TableView { columns: DIFF_COLUMNS, items, notes: vec![("summary", summary)] },
+    )
+}
+
+/// SHA-256 of each exported row's NDJSON line, keyed by row id.
+struct SnapshotRowDigests {
+    records: BTreeMap<String, String>,
+    context_packages: BTreeMap<String, String>,
+}
+
+impl SnapshotRowDigests {
+    fn read(dir: &Path) -> Result<Self> {
+        let manifest_path = dir.join(MANIFEST_FILE);
+        let manifest_bytes = fs::read(&manifest_path)
+            .with_context(|| format!("failed to read manifest {}", manifest_path.display()))?;
+        let manifest: ExportManifest = serde_json::from_slice(&manifest_bytes)
+            .with_context(|| format!("failed to parse manifest {}", manifest_path.display()))?;
+        if read_security_metadata(dir)?.is_some_and(|security| !security.encrypted_files.is_empty())
+        {
+            return Err(anyhow!(
+                "snapshot {} is encrypted; diff MUST compare plaintext exports",
+                dir.display()
+            ));
+        }
+        Ok(Self {
+            records: read_row_digests(
+                dir,
+                &manifest,
+                SNAPSHOT_RECORDS_FILE,
+                |record: MemoryRecord| record.memory_version_id.to_string(),
+            )?,
+            context_packages: read_row_digests(
+                dir,
+                &manifest,
+                SNAPSHOT_CONTEXT_PACKAGES_FILE,
+                |package: ContextPackage| package.context_package_id,
+            )?,
+        })
+    }
+}
+
+/// Digest every row of the snapshot file `name`; a file the manifest does not list has no rows.
+fn read_row_digests<T: DeserializeOwned>(
+    dir: &Path,
+    manifest: &ExportManifest,
+    name: &str,
+    row_id: impl Fn(T) -> String,
+) -> Result<BTreeMap<String, String>> {
+    let mut digests = BTreeMap::new();
+    if !manifest.files.iter().any(|file| file.path == name) {
+        return Ok(digests);
+    }
+    let path = dir.join(name);
+    let body = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read snapshot file {}", path.display()))?;
+    for (index, line) in body.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let row = serde_json::from_str(trimmed).with_context(|| {
+            format!("failed to parse NDJSON row {} from {}", index + 1, path.display())
+        })?;
+        let id = row_id(row);
+        if digests.insert(id.clone(), sha256_hex(trimmed.as_bytes())).is_some() {
+            return Err(anyhow!("duplicate row {id} in {}", path.display()));
+        }
+    }
+    Ok(digests)
+}
+
+/// Added, removed, changed, and unchanged row counts for one kind of snapshot row.
+#[derive(Debug, Default, Serialize)]
+struct RowDiffCounts {
+    added: usize,
+    removed: usize,
+    changed: usize,
+    unchanged: usize,
+}
+
+impl std::fmt::Display for RowDiffCounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} added, {} removed, {} changed, {} unchanged",
+            self.added, self.removed, self.changed, self.unchanged
+        )
+    }
+}
+
+/// One row that differs between `--a` and `--b`; the digest is absent on the side without it.
+#[derive(Debug, Serialize)]
+struct RowDiff {
+    kind: &'static str,
+    change: &'static str,
+    id: String,
+    a_sha256: Option<String>,
+    b_sha256: Option<String>,
+}
+
+fn diff_row_digests(
+    kind: &'static str,
+    a: &BTreeMap<String, String>,
+    b: &BTreeMap<String, String>,
+    changes: &mut Vec<RowDiff>,
+) -> RowDiffCounts {
+    let mut counts = RowDiffCounts::default();
+    let ids = a.keys().chain(b.keys()).collect::<BTreeSet<_>>();
+    for id in ids {
+        let (a_sha256, b_sha256) = (a.get(id), b.get(id));
+        let change = match (a_sha256, b_sha256) {
+            (None, _) => {
+                counts.added += 1;
+                "added"
+            }
+            (Some(_), None) => {
+                counts.removed += 1;
+                "removed"
+            }
+            (Some(before), Some(after)) if before != after => {
+                counts.changed += 1;
+                "changed"
+            }
+            (Some(_), Some(_)) => {
+                counts.unchanged += 1;
+                continue;
+            }
+        };
+        changes.push(RowDiff {
+            kind,
+            change,
+            id: id.clone(),
+            a_sha256: a_sha256.cloned(),
+            b_sha256: b_sha256.cloned(),
+        });
+    }
+    counts
+}
+
 fn run_db_migrate(args: &DbMigrateArgs, store: &mut SqliteStore) -> Result<()> {
     let before = store.schema_status()?;
     if args.dry_run {
@@ -389,14 +1204,36 @@ fn run_db_migrate(args: &DbMigrateArgs, store: &mut SqliteStore) -> Result<()> {
 
 fn run_db_export(args: &DbExportArgs, store: &mut SqliteStore) -> Result<()> {
     store.migrate()?;
-    let mut manifest = store.export_snapshot(&args.out)?;
+    let filter = ExportFilter {
+        record_types: args
+            .record_types
+            .iter()
+            .copied()
+            .map(RecordTypeArg::into_record_type)
+            .collect(),
+        created_from: args.created_from.as_deref().map(parse_rfc3339).transpose()?,
+        created_to: args.created_to.as_deref().map(parse_rfc3339).transpose()?,
+        writers: args.writers.clone(),
+    };
+    let since = args.since_manifest.as_deref().map(read_manifest_checkpoint).transpose()?;
+    if args.format == SnapshotFormatArg::TarZstd || is_stdio_path(&args.out) {
+        return run_db_export_archive(args, store, &filter, since);
+    }
+
+    let mut manifest = match since {
+        Some(checkpoint) => store.export_snapshot_since(&args.out, checkpoint)?,
+        None => store.export_snapshot_filtered(&args.out, &filter)?,
+    };
     let mut security = SnapshotSecurityMetadata::default();
 
-    if let Some(key_path) = args.encrypt_key_file.as_ref() {
-        let encryption_key = read_hex_key_file(key_path)?;
+    if let Some((encryption_key, recipients)) = snapshot_encryption_key(args)? {
         encrypt_snapshot_files(&args.out, &mut manifest, &encryption_key)?;
         security.encrypted_files = manifest.files.iter().map(|file| file.path.clone()).collect();
         security.encryption_algorithm = Some(ENCRYPTION_ALGORITHM.to_string());
+        if !recipients.is_empty() {
+            security.key_wrap_algorithm = Some(KEY_WRAP_ALGORITHM.to_string());
+            security.recipients = recipients;
+        }
     }
 
     write_manifest(&args.out, &manifest)?;
@@ -420,23 +1257,100 @@ fn run_db_export(args: &DbExportArgs, store: &mut SqliteStore) -> Result<()> {
     }
 
     emit_json(serde_json::json!({
+        "format": args.format.as_str(),
         "out_dir": args.out,
         "manifest": manifest
     }))
 }
 
+/// Write the snapshot through tar, zstd, and optional encryption straight into `--out`. Each
+/// NDJSON file is serialized directly into its tar entry rather than buffered, so neither memory
+/// nor disk holds an uncompressed copy of the files; the selected rows themselves are still
+/// loaded once by the export.
+fn run_db_export_archive(
+    args: &DbExportArgs,
+    store: &SqliteStore,
+    filter: &ExportFilter,
+    since: Option<ExportCheckpoint>,
+) -> Result<()> {
+    let snapshot = store.export_snapshot_stream(filter, since)?;
+    let manifest = snapshot.manifest();
+    let manifest_bytes =
+        serde_json::to_vec_pretty(manifest).context("failed to serialize export manifest")?;
+    let signature = args
+        .signing_key_file
+        .as_ref()
+        .map(|path| manifest_signature_hex(&manifest_bytes, &read_hex_key_file(path)?))
+        .transpose()?;
+    let encryption = snapshot_encryption_key(args)?;
+
+    let mut entries = vec![(MANIFEST_FILE, manifest_bytes.as_slice())];
+    if let Some(signature) = signature.as_ref() {
+        entries.push((MANIFEST_SIG_FILE, signature.as_bytes()));
+    }
+
+    let sink: Box<dyn Write> = if is_stdio_path(&args.out) {
+        if std::io::stdout().is_terminal() {
+            return Err(anyhow!(
+                "refusing to write an archive to a terminal; pipe or redirect stdout"
+            ));
+        }
+        Box::new(std::io::stdout().lock())
+    } else {
+        Box::new(
+            File::create(&args.out)
+                .with_context(|| format!("failed to create archive {}", args.out.display()))?,
+        )
+    };
+    let mut writer = match encryption {
+        Some((key, recipients)) => {
+            let encryptor = ArchiveEncryptor::new(BufWriter::new(sink), &key, &recipients)?;
+            write_tar_zstd(encryptor, &entries, &snapshot)?.finish()?
+        }
+        None => write_tar_zstd(BufWriter::new(sink), &entries, &snapshot)?,
+    };
+    writer.flush().with_context(|| format!("failed to flush archive {}", args.out.display()))?;
+
+    let summary = serde_json::json!({
+        "format": SnapshotFormatArg::TarZstd.as_str(),
+        "out_file": args.out,
+        "manifest": manifest
+    });
+    if is_stdio_path(&args.out) {
+        // Stdout carries the archive, so the summary goes to stderr.
+        return output::emit_stderr(&with_contract_version(summary));
+    }
+    emit_json(summary)
+}
+
+/// Whether a snapshot `--out`/`--in` path is `-`, meaning stdout/stdin.
+fn is_stdio_path(path: &Path) -> bool {
+    path.as_os_str() == STDIO_PATH
+}
+
 fn run_db_import(args: &DbImportArgs, store: &mut SqliteStore) -> Result<()> {
+    let defaults = ImportLimits::default();
+    store.set_import_limits(ImportLimits {
+        max_line_bytes: args.max_line_bytes.unwrap_or(defaults.max_line_bytes),
+        max_rows: args.max_rows.unwrap_or(defaults.max_rows),
+        max_depth: args.max_depth.unwrap_or(defaults.max_depth),
+    });
     let verify_key =
         args.verify_key_file.as_ref().map(|path| read_hex_key_file(path)).transpose()?;
-    let decrypt_key =
-        args.decrypt_key_file.as_ref().map(|path| read_hex_key_file(path)).transpose()?;
-
-    let prepared = prepare_import_input(
-        &args.input,
-        verify_key.as_ref(),
-        decrypt_key.as_ref(),
-        args.allow_unsigned,
-    )?;
+    let keys = SnapshotDecryptKeys {
+        decrypt_key: args
+            .decrypt_key_file
+            .as_ref()
+            .map(|path| read_hex_key_file(path))
+            .transpose()?,
+        identity: args.identity_file.as_ref().map(|path| read_hex_key_file(path)).transpose()?,
+    };
+    if args.format == SnapshotFormatArg::TarZstd || is_stdio_path(&args.input) {
+        return run_db_import_archive(args, store, verify_key.as_ref(), &keys);
+    }
+
+    let prepared =
+        prepare_import_input(&args.input, verify_key.as_ref(), &keys, args.allow_unsigned)?;
     let summary = store.import_snapshot(&prepared, args.skip_existing)?;
     if prepared != args.input {
         fs::remove_dir_all(&prepared).with_context(|| {
@@ -444,12 +1358,43 @@ fn run_db_import(args: &DbImportArgs, store: &mut SqliteStore) -> Result<()> {
         })?;
     }
     emit_json(serde_json::json!({
+        "format": args.format.as_str(),
         "in_dir": args.input,
         "skip_existing": args.skip_existing,
         "summary": summary
     }))
 }
 
+fn run_db_import_archive(
+    args: &DbImportArgs,
+    store: &mut SqliteStore,
+    verify_key: Option<&[u8; 32]>,
+    keys: &SnapshotDecryptKeys,
+) -> Result<()> {
+    let source: Box<dyn Read> = if is_stdio_path(&args.input) {
+        if std::io::stdin().is_terminal() {
+            return Err(anyhow!("refusing to read an archive from a terminal; pipe it into stdin"));
+        }
+        Box::new(std::io::stdin().lock())
+    } else {
+        Box::new(
+            File::open(&args.input)
+                .with_context(|| format!("failed to open archive {}", args.input.display()))?,
+        )
+    };
+    let limits = store.import_limits();
+    let snapshot = read_tar_zstd(source, &args.input, keys, |reader| {
+        read_snapshot_archive(reader, args, verify_key, limits)
+    })?;
+    let summary = store.import_snapshot_stream(snapshot, args.skip_existing)?;
+    emit_json(serde_json::json!({
+        "format": SnapshotFormatArg::TarZstd.as_str(),
+        "in_file": args.input,
+        "skip_existing": args.skip_existing,
+        "summary": summary
+    }))
+}
+
 fn run_db_backup(args: &DbBackupArgs, store: &mut SqliteStore) -> Result<()> {
     store.migrate()?;
     store.backup_database(&args.out)?;
@@ -459,71 +1404,264 @@ fn run_db_backup(args: &DbBackupArgs, store: &mut SqliteStore) -> Result<()> {
     }))
 }
 
-fn run_db_restore(args: &DbRestoreArgs, store: &mut SqliteStore) -> Result<()> {
-    store.restore_database(&args.input)?;
-    let status = store.schema_status()?;
-    emit_json(serde_json::json!({
-        "restored_from": args.input,
-        "current_version": status.current_version,
-        "target_version": status.target_version,
-        "pending_versions": status.pending_versions
-    }))
+fn run_db_restore(args: &DbRestoreArgs, store: &mut SqliteStore) -> Result<()> {
+    store.restore_database(&args.input)?;
+    let status = store.schema_status()?;
+    emit_json(serde_json::json!({
+        "restored_from": args.input,
+        "current_version": status.current_version,
+        "target_version": status.target_version,
+        "pending_versions": status.pending_versions
+    }))
+}
+
+fn run_db_integrity_check(args: &DbIntegrityCheckArgs, store: &mut SqliteStore) -> Result<()> {
+    let report = store.integrity_check()?;
+    let mut value =
+        serde_json::to_value(&report).context("failed to serialize integrity report")?;
+    let mut rows = integrity_rows(&report);
+    if args.repair {
+        if !report.schema_status.pending_versions.is_empty() {
+            return Err(anyhow!("schema is not up to date; run `mk db migrate` before --repair"));
+        }
+        let repair = store.repair_integrity(args.dry_run)?;
+        rows.extend(repair_rows(&repair));
+        value["repair"] =
+            serde_json::to_value(&repair).context("failed to serialize repair report")?;
+    }
+    emit_table(value, TableView { columns: INTEGRITY_COLUMNS, items: rows, notes: Vec::new() })
+}
+
+/// One row per repair category: what was found and, outside a dry run, removed.
+fn repair_rows(repair: &RepairReport) -> Vec<Value> {
+    [
+        ("repair_dangling_links", repair.dangling_links.len(), repair.removed_links),
+        ("repair_orphaned_rows", repair.orphaned_rows.len(), repair.removed_rows),
+        (
+            "repair_stale_context_packages",
+            repair.stale_context_packages.len(),
+            repair.removed_context_packages,
+        ),
+    ]
+    .into_iter()
+    .map(|(check, found, removed)| {
+        serde_json::json!({
+            "check": check,
+            "ok": found == 0 || !repair.dry_run,
+            "detail": format!("{found} found, {removed} removed"),
+        })
+    })
+    .collect()
+}
+
+fn run_db_maintain(args: &DbMaintainArgs, store: &SqliteStore) -> Result<()> {
+    let report = store.maintenance(args.vacuum_into.as_deref())?;
+    emit_json(serde_json::to_value(&report).context("failed to serialize maintenance report")?)
+}
+
+fn run_db_encrypt(args: &DbEncryptArgs, store: &mut SqliteStore) -> Result<()> {
+    let key = StoreKey::resolve(args.key_file.as_deref())?.ok_or_else(|| {
+        anyhow!("provide --key-file or set {STORE_KEY_ENV} for the encrypted copy")
+    })?;
+    store.migrate()?;
+    store.encrypt_to(&args.out, &key)?;
+    emit_json(serde_json::json!({
+        "encrypted_path": args.out,
+        "status": "ok"
+    }))
+}
+
+fn run_keys(command: KeysCommand) -> Result<()> {
+    match command {
+        KeysCommand::Generate(args) => run_keys_generate(&args),
+        KeysCommand::Fingerprint(args) => run_keys_fingerprint(&args),
+        KeysCommand::Rotate(args) => run_keys_rotate(&args),
+        KeysCommand::Resign(args) => run_keys_resign(&args),
+    }
+}
+
+fn run_keys_generate(args: &KeysGenerateArgs) -> Result<()> {
+    let key = write_new_key_file(&args.out)?;
+    let mut output = key_summary(args.kind, &key);
+    output.insert("key_file".to_string(), serde_json::json!(args.out));
+    emit_json(Value::Object(output))
+}
+
+fn run_keys_fingerprint(args: &KeysFingerprintArgs) -> Result<()> {
+    let key = read_hex_key_file(&args.key_file)?;
+    let mut output = key_summary(args.kind, &key);
+    output.insert("key_file".to_string(), serde_json::json!(args.key_file));
+    emit_json(Value::Object(output))
+}
+
+/// Move the current key aside and write a fresh one in its place. Existing exports keep their
+/// old signatures and encryption; `mk keys resign` moves signed snapshots to the new key.
+fn run_keys_rotate(args: &KeysRotateArgs) -> Result<()> {
+    let retired = read_hex_key_file(&args.key_file)?;
+    let retired_out = args.retired_out.clone().unwrap_or_else(|| {
+        let mut path = args.key_file.clone().into_os_string();
+        path.push(".previous");
+        PathBuf::from(path)
+    });
+    if retired_out.exists() {
+        return Err(anyhow!("retired key file already exists: {}", retired_out.display()));
+    }
+    fs::rename(&args.key_file, &retired_out).with_context(|| {
+        format!("failed to move {} to {}", args.key_file.display(), retired_out.display())
+    })?;
+    let key = write_new_key_file(&args.key_file)?;
+
+    let mut output = key_summary(args.kind, &key);
+    output.insert("key_file".to_string(), serde_json::json!(args.key_file));
+    output.insert("retired_key_file".to_string(), serde_json::json!(retired_out));
+    output.insert("retired_fingerprint".to_string(), serde_json::json!(key_fingerprint(&retired)));
+    emit_json(Value::Object(output))
+}
+
+fn run_keys_resign(args: &KeysResignArgs) -> Result<()> {
+    let manifest_path = args.input.join(MANIFEST_FILE);
+    let manifest_bytes = fs::read(&manifest_path)
+        .with_context(|| format!("failed to read manifest {}", manifest_path.display()))?;
+    if args.input.join(MANIFEST_SIG_FILE).exists() {
+        let verify_path = args.verify_key_file.as_ref().ok_or_else(|| {
+            anyhow!("snapshot is signed; provide --verify-key-file to check the current signature")
+        })?;
+        verify_manifest_signature(&args.input, &manifest_bytes, &read_hex_key_file(verify_path)?)?;
+    } else if !args.allow_unsigned {
+        return Err(anyhow!(
+            "snapshot is unsigned; rerun with --allow-unsigned to sign it for the first time"
+        ));
+    }
+
+    let signing_key = read_hex_key_file(&args.signing_key_file)?;
+    write_manifest_signature(&args.input, &manifest_bytes, &signing_key)?;
+    let mut security = read_security_metadata(&args.input)?.unwrap_or_default();
+    security.signature_file = Some(MANIFEST_SIG_FILE.to_string());
+    security.signature_algorithm = Some(SIGNATURE_ALGORITHM.to_string());
+    write_security_metadata(&args.input, &security)?;
+
+    emit_json(serde_json::json!({
+        "in_dir": args.input,
+        "signature_file": MANIFEST_SIG_FILE,
+        "fingerprint": key_fingerprint(&signing_key)
+    }))
+}
+
+/// `kind` and `fingerprint` for a key; identities also report their public key, and their
+/// fingerprint covers the public key so it can be shared with exporters.
+fn key_summary(kind: KeyKindArg, key: &[u8; 32]) -> serde_json::Map<String, Value> {
+    let mut output = serde_json::Map::new();
+    output.insert("kind".to_string(), Value::String(kind.as_str().to_string()));
+    if kind == KeyKindArg::Identity {
+        let public_key = identity_public_key(key);
+        output.insert("fingerprint".to_string(), Value::String(key_fingerprint(&public_key)));
+        output.insert("public_key".to_string(), Value::String(hex::encode(public_key)));
+    } else {
+        output.insert("fingerprint".to_string(), Value::String(key_fingerprint(key)));
+    }
+    output
 }
 
-fn run_db_integrity_check(store: &SqliteStore) -> Result<()> {
-    let report = store.integrity_check()?;
-    emit_json(serde_json::to_value(&report).context("failed to serialize integrity report")?)
+fn key_fingerprint(key: &[u8; 32]) -> String {
+    format!("sha256:{}", &sha256_hex(key)[..32])
+}
+
+/// Write a random 32-byte hex key to a new file, readable only by the owner on Unix.
+fn write_new_key_file(path: &Path) -> Result<[u8; 32]> {
+    let mut key = [0_u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut key);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("failed to create key file {}", path.display()))?;
+    file.write_all(hex::encode(key).as_bytes())
+        .with_context(|| format!("failed to write key file {}", path.display()))?;
+    Ok(key)
+}
+
+fn build_add_record(command: AddCommand) -> Result<MemoryRecord> {
+    Ok(match command {
+        AddCommand::Constraint(args) => build_record(
+            MemoryPayload::Constraint(ConstraintPayload {
+                scope: ConstraintScope {
+                    actor: args.actor,
+                    action: args.action,
+                    resource: args.resource,
+                },
+                effect: match args.effect {
+                    EffectArg::Allow => ConstraintEffect::Allow,
+                    EffectArg::Deny => ConstraintEffect::Deny,
+                },
+                note: args.note,
+                expires_at: args.expires_at.as_deref().map(parse_rfc3339).transpose()?,
+            }),
+            args.write,
+        )?,
+        AddCommand::Decision(args) => build_record(
+            MemoryPayload::Decision(memory_kernel_core::DecisionPayload { summary: args.summary }),
+            args.write,
+        )?,
+        AddCommand::Preference(args) => build_record(
+            MemoryPayload::Preference(memory_kernel_core::PreferencePayload {
+                summary: args.summary,
+            }),
+            args.write,
+        )?,
+        AddCommand::Event(args) => build_record(
+            MemoryPayload::Event(memory_kernel_core::EventPayload { summary: args.summary }),
+            args.write,
+        )?,
+        AddCommand::Outcome(args) => build_record(
+            MemoryPayload::Outcome(memory_kernel_core::OutcomePayload { summary: args.summary }),
+            args.write,
+        )?,
+        AddCommand::Task(args) => build_record(
+            MemoryPayload::Task(memory_kernel_core::TaskPayload {
+                summary: args.summary,
+                status: args.status.into_task_status(),
+            }),
+            args.write,
+        )?,
+        AddCommand::Risk(args) => build_record(
+            MemoryPayload::Risk(memory_kernel_core::RiskPayload {
+                summary: args.summary,
+                severity: args.severity.into_risk_severity(),
+            }),
+            args.write,
+        )?,
+        AddCommand::Assumption(args) => build_record(
+            MemoryPayload::Assumption(memory_kernel_core::AssumptionPayload {
+                summary: args.summary,
+                status: args.status.into_assumption_status(),
+            }),
+            args.write,
+        )?,
+    })
 }
 
 fn run_memory(command: MemoryCommand, store: &mut SqliteStore) -> Result<()> {
     store.migrate()?;
     match command {
         MemoryCommand::Add { command } => {
-            let record = match *command {
-                AddCommand::Constraint(args) => build_record(
-                    MemoryPayload::Constraint(ConstraintPayload {
-                        scope: ConstraintScope {
-                            actor: args.actor,
-                            action: args.action,
-                            resource: args.resource,
-                        },
-                        effect: match args.effect {
-                            EffectArg::Allow => ConstraintEffect::Allow,
-                            EffectArg::Deny => ConstraintEffect::Deny,
-                        },
-                        note: args.note,
-                    }),
-                    args.write,
-                )?,
-                AddCommand::Decision(args) => build_record(
-                    MemoryPayload::Decision(memory_kernel_core::DecisionPayload {
-                        summary: args.summary,
-                    }),
-                    args.write,
-                )?,
-                AddCommand::Preference(args) => build_record(
-                    MemoryPayload::Preference(memory_kernel_core::PreferencePayload {
-                        summary: args.summary,
-                    }),
-                    args.write,
-                )?,
-                AddCommand::Event(args) => build_record(
-                    MemoryPayload::Event(memory_kernel_core::EventPayload {
-                        summary: args.summary,
-                    }),
-                    args.write,
-                )?,
-                AddCommand::Outcome(args) => build_record(
-                    MemoryPayload::Outcome(memory_kernel_core::OutcomePayload {
-                        summary: args.summary,
-                    }),
-                    args.write,
-                )?,
-            };
+            let record = build_add_record(*command)?;
 
             store.write_record(&record)?;
             emit_json(serde_json::to_value(&record).context("failed to serialize memory record")?)
         }
+        MemoryCommand::AddBatch(args) => {
+            let records: Vec<MemoryRecord> = read_ndjson_rows(&args.file, "batch")?;
+            store.write_records_batch(&records)?;
+            emit_json(serde_json::json!({
+                "written": records.len(),
+                "memory_version_ids": records
+                    .iter()
+                    .map(|record| record.memory_version_id.to_string())
+                    .collect::<Vec<_>>(),
+            }))
+        }
         MemoryCommand::Link(args) => {
             let from = parse_memory_version_id(&args.from)?;
             let to = parse_memory_version_id(&args.to)?;
@@ -541,92 +1679,253 @@ fn run_memory(command: MemoryCommand, store: &mut SqliteStore) -> Result<()> {
                 "justification": args.justification,
             }))
         }
-        MemoryCommand::List => {
-            let records = store.list_records()?;
-            emit_json(serde_json::json!({ "records": records }))
+        MemoryCommand::Redact(args) => {
+            let memory_version_id = parse_memory_version_id(&args.memory_version_id)?;
+            let redaction = store.redact(memory_version_id, &args.writer, &args.justification)?;
+            emit_json(serde_json::json!({ "redaction": redaction }))
+        }
+        MemoryCommand::List(args) => {
+            let filter = RecordFilter {
+                record_type: args.record_type.map(RecordTypeArg::into_record_type),
+                writer: args.writer,
+                truth_status: args.truth_status.map(TruthStatusArg::into_truth_status),
+                effective_from: args.effective_from.as_deref().map(parse_rfc3339).transpose()?,
+                effective_to: args.effective_to.as_deref().map(parse_rfc3339).transpose()?,
+                tags: args.tags,
+                source_uri_prefix: args.source_uri_prefix,
+                source_hash: args.source_hash,
+                after: args.after.as_deref().map(parse_memory_version_id).transpose()?,
+                limit: args.limit,
+            };
+            let page = store.list_records_filtered(&filter)?;
+            let items = page
+                .records
+                .iter()
+                .map(serde_json::to_value)
+                .collect::<Result<Vec<_>, _>>()
+                .context("failed to serialize memory records")?;
+            let notes = page
+                .next_after
+                .map(|next_after| ("next_after", next_after.to_string()))
+                .into_iter()
+                .collect();
+            emit_table(
+                serde_json::json!({
+                    "records": items,
+                    "next_after": page.next_after,
+                }),
+                TableView { columns: RECORD_COLUMNS, items, notes },
+            )
         }
+        MemoryCommand::Contradictions => emit_json(
+            serde_json::to_value(store.list_contradictions()?)
+                .context("failed to serialize contradiction report")?,
+        ),
     }
 }
 
-fn run_query(command: QueryCommand, store: &mut SqliteStore) -> Result<()> {
+fn run_query(command: QueryCommand, store: &mut SqliteStore, db: &Path) -> Result<()> {
     store.migrate()?;
     match command {
+        QueryCommand::Ask(args) if args.watch => {
+            let interval = watch::parse_interval(args.watch_interval)?;
+            watch::run(&args.query, store, db, interval)
+        }
         QueryCommand::Ask(args) => {
-            let as_of = parse_optional_rfc3339(args.as_of.as_deref())?;
-            let records = store.list_records()?;
-            let snapshot_id = compute_snapshot_id(
-                &records,
-                as_of,
-                &args.text,
-                &[
-                    "query_mode=policy".to_string(),
-                    format!("actor={}", args.actor),
-                    format!("action={}", args.action),
-                    format!("resource={}", args.resource),
-                ],
-            );
-
-            let package = build_context_package(
-                &records,
-                QueryRequest {
-                    text: args.text,
-                    actor: args.actor,
-                    action: args.action,
-                    resource: args.resource,
-                    as_of,
-                },
-                &snapshot_id,
-            )?;
-
-            store.save_context_package(&package)?;
+            let package = ask_package(args.query, store)?;
             emit_json(
                 serde_json::to_value(&package).context("failed to serialize context package")?,
             )
         }
         QueryCommand::Recall(args) => {
-            let as_of = parse_optional_rfc3339(args.as_of.as_deref())?;
-            let records = store.list_records()?;
-            let selected_record_types = if args.record_types.is_empty() {
-                default_recall_record_types()
-            } else {
-                args.record_types.iter().copied().map(RecordTypeArg::into_record_type).collect()
-            };
-
-            let mut type_names = selected_record_types
-                .iter()
-                .map(|record_type| record_type.as_str())
-                .collect::<Vec<_>>();
-            type_names.sort_unstable();
-
-            let snapshot_id = compute_snapshot_id(
-                &records,
-                as_of,
-                &args.text,
-                &[
-                    "query_mode=recall".to_string(),
-                    format!("record_types={}", type_names.join(",")),
-                ],
-            );
-
-            let package = build_recall_context_package(
-                &records,
-                QueryRequest {
-                    text: args.text,
-                    actor: "*".to_string(),
-                    action: "*".to_string(),
-                    resource: "*".to_string(),
-                    as_of,
-                },
-                &snapshot_id,
-                &selected_record_types,
-            )?;
-
-            store.save_context_package(&package)?;
+            let package = recall_package(args, store)?;
             emit_json(
                 serde_json::to_value(&package).context("failed to serialize context package")?,
             )
         }
+        QueryCommand::Simulate(args) => run_query_simulate(*args, store),
+        QueryCommand::Explain(args) => run_query_explain(args, store),
+    }
+}
+
+/// Resolve a policy question and persist the resulting context package.
+fn ask_package(args: QueryAskArgs, store: &mut SqliteStore) -> Result<ContextPackage> {
+    let started = Instant::now();
+    let (package, candidate_records) = resolve_ask_package(args, store)?;
+    let metric =
+        QueryMetric::for_package(QueryMode::Policy, &package, started.elapsed(), candidate_records);
+    store.save_context_package(&package)?;
+    store.record_query_metric(&metric)?;
+    Ok(package)
+}
+
+/// Resolve a policy question against the current records without saving the package.
+fn build_ask_package(args: QueryAskArgs, store: &SqliteStore) -> Result<ContextPackage> {
+    resolve_ask_package(args, store).map(|(package, _)| package)
+}
+
+/// Resolve a policy question, returning the package and the number of candidate records.
+fn resolve_ask_package(args: QueryAskArgs, store: &SqliteStore) -> Result<(ContextPackage, usize)> {
+    let as_of = parse_optional_rfc3339(args.as_of.as_deref())?;
+    let records = store.list_records()?;
+    let ruleset = args.ruleset.into_policy_ruleset();
+    let resolution = args.conflict_resolution.into_conflict_resolution();
+    let mut extras = vec![
+        "query_mode=policy".to_string(),
+        format!("actor={}", args.actor),
+        format!("action={}", args.action),
+        format!("resource={}", args.resource),
+    ];
+    if ruleset != PolicyRuleset::default() {
+        extras.push(format!("ruleset={}", ruleset.strategy().version()));
+    }
+    extras.extend(resolution.snapshot_extra());
+    extras.extend(namespace_extra(store));
+    let decay = parse_confidence_decay(&args.half_lives)?;
+    extras.extend(decay.as_ref().and_then(ConfidenceDecay::snapshot_extra));
+    let snapshot_id = compute_snapshot_id(
+        records
+            .iter()
+            .map(|record| format!("{}:{}", record.memory_id, record.memory_version_id))
+            .collect(),
+        as_of,
+        &args.text,
+        &extras,
+    );
+
+    let package = build_context_package_with_ruleset(
+        &records,
+        QueryRequest {
+            text: args.text,
+            actor: args.actor,
+            action: args.action,
+            resource: args.resource,
+            as_of,
+        },
+        &snapshot_id,
+        ruleset.strategy(),
+        decay.as_ref(),
+        resolution,
+    )?;
+    Ok((package, records.len()))
+}
+
+/// Recall summary records for free text and persist the resulting context package.
+fn recall_package(args: QueryRecallArgs, store: &mut SqliteStore) -> Result<ContextPackage> {
+    let started = Instant::now();
+    let as_of = parse_optional_rfc3339(args.as_of.as_deref())?;
+    let selected_record_types = if args.record_types.is_empty() {
+        default_recall_record_types()
+    } else {
+        args.record_types.iter().copied().map(RecordTypeArg::into_record_type).collect()
+    };
+    let record_keys = store.list_record_keys()?;
+    let records = retain_tagged(
+        store.recall_candidates_tagged(
+            &args.text,
+            &selected_record_types,
+            &args.tags,
+            DEFAULT_RECALL_CANDIDATE_LIMIT,
+        )?,
+        &args.tags,
+    );
+
+    let mut type_names =
+        selected_record_types.iter().map(|record_type| record_type.as_str()).collect::<Vec<_>>();
+    type_names.sort_unstable();
+
+    let decay = parse_confidence_decay(&args.half_lives)?.unwrap_or_default();
+    let mut extras =
+        vec!["query_mode=recall".to_string(), format!("record_types={}", type_names.join(","))];
+    if !args.tags.is_empty() {
+        let tags = args.tags.iter().map(String::as_str).collect::<BTreeSet<_>>();
+        extras.push(format!("tags={}", tags.into_iter().collect::<Vec<_>>().join(",")));
+    }
+    extras.extend(namespace_extra(store));
+    extras.extend(decay.snapshot_extra());
+    let snapshot_id = compute_snapshot_id(
+        record_keys
+            .into_iter()
+            .map(|(memory_id, memory_version_id)| format!("{memory_id}:{memory_version_id}"))
+            .collect(),
+        as_of,
+        &args.text,
+        &extras,
+    );
+
+    let package = build_recall_context_package_with_decay(
+        &records,
+        QueryRequest {
+            text: args.text,
+            actor: "*".to_string(),
+            action: "*".to_string(),
+            resource: "*".to_string(),
+            as_of,
+        },
+        &snapshot_id,
+        &selected_record_types,
+        &decay,
+    )?;
+
+    let metric =
+        QueryMetric::for_package(QueryMode::Recall, &package, started.elapsed(), records.len());
+    store.save_context_package(&package)?;
+    store.record_query_metric(&metric)?;
+    Ok(package)
+}
+
+fn run_query_simulate(args: QuerySimulateArgs, store: &SqliteStore) -> Result<()> {
+    let rows: Vec<SimulationQueryRow> = read_ndjson_rows(&args.queries, "queries")?;
+    let decay = parse_confidence_decay(&args.half_lives)?;
+    // Default `as_of` after building the proposal so it is already effective.
+    let proposed = build_add_record(AddCommand::Constraint(args.constraint))?;
+    let now = OffsetDateTime::now_utc();
+    let records = store.list_records()?;
+
+    let mut outcomes = Vec::with_capacity(rows.len());
+    for row in rows {
+        let query = QueryRequest {
+            text: row.text,
+            actor: row.actor,
+            action: row.action,
+            resource: row.resource,
+            as_of: row.as_of.as_deref().map(parse_rfc3339).transpose()?.unwrap_or(now),
+        };
+        outcomes.extend(simulate_constraint(
+            &records,
+            &proposed,
+            &[query],
+            row.ruleset.strategy(),
+            decay.as_ref(),
+            row.conflict_resolution,
+        )?);
     }
+    let changed = outcomes.iter().filter(|outcome| outcome.changed).count();
+    emit_json(serde_json::json!({
+        "proposed": proposed,
+        "outcomes": outcomes,
+        "changed": changed,
+    }))
+}
+
+fn run_query_explain(args: QueryExplainArgs, store: &SqliteStore) -> Result<()> {
+    let memory_version_id = parse_memory_version_id(&args.memory_version_id)?;
+    let query = args.query;
+    let decay = parse_confidence_decay(&query.half_lives)?;
+    let explanation = explain_record(
+        &store.list_records()?,
+        memory_version_id,
+        QueryRequest {
+            text: query.text,
+            actor: query.actor,
+            action: query.action,
+            resource: query.resource,
+            as_of: parse_optional_rfc3339(query.as_of.as_deref())?,
+        },
+        query.ruleset.into_policy_ruleset().strategy(),
+        decay.as_ref(),
+    )?;
+    emit_json(serde_json::to_value(&explanation).context("failed to serialize explanation")?)
 }
 
 fn run_context(command: ContextCommand, store: &mut SqliteStore) -> Result<()> {
@@ -637,10 +1936,57 @@ fn run_context(command: ContextCommand, store: &mut SqliteStore) -> Result<()> {
                 return Err(anyhow!("context package not found: {}", args.context_package_id));
             };
 
-            emit_json(
+            let items = package
+                .selected_items
+                .iter()
+                .chain(&package.excluded_items)
+                .map(serde_json::to_value)
+                .collect::<Result<Vec<_>, _>>()
+                .context("failed to serialize context items")?;
+            let notes = vec![
+                ("context_package_id", package.context_package_id.clone()),
+                ("answer", package.answer.result.as_str().to_string()),
+                ("why", package.answer.why.clone()),
+            ];
+            emit_table(
                 serde_json::to_value(&package).context("failed to serialize context package")?,
+                TableView { columns: CONTEXT_ITEM_COLUMNS, items, notes },
             )
         }
+        ContextCommand::List(args) => {
+            let filter = ContextPackageFilter {
+                generated_from: args.generated_from.as_deref().map(parse_rfc3339).transpose()?,
+                generated_to: args.generated_to.as_deref().map(parse_rfc3339).transpose()?,
+                actor: args.actor,
+                resource: args.resource,
+                answer_result: args.answer_result.map(AnswerResultArg::into_answer_result),
+                after: args.after,
+                limit: args.limit,
+            };
+            let page = store.list_context_packages(&filter)?;
+            emit_json(serde_json::json!({
+                "packages": page.packages,
+                "next_after": page.next_after,
+            }))
+        }
+        ContextCommand::LinkOutcome(args) => {
+            let outcome = parse_memory_version_id(&args.outcome_memory_version_id)?;
+            let link = store.link_package_outcome(
+                &args.context_package_id,
+                outcome,
+                &args.writer,
+                &args.justification,
+            )?;
+            emit_json(serde_json::json!({ "outcome_link": link }))
+        }
+        ContextCommand::Outcomes(args) => {
+            let memory_version_id = parse_memory_version_id(&args.memory_version_id)?;
+            let outcomes = store.outcomes_following(memory_version_id)?;
+            emit_json(serde_json::json!({
+                "memory_version_id": memory_version_id,
+                "outcomes": outcomes,
+            }))
+        }
     }
 }
 
@@ -672,13 +2018,7 @@ fn build_record(payload: MemoryPayload, write: WriteArgs) -> Result<MemoryRecord
         version: write.version,
         created_at,
         effective_at,
-        truth_status: match write.truth_status {
-            TruthStatusArg::Asserted => TruthStatus::Asserted,
-            TruthStatusArg::Observed => TruthStatus::Observed,
-            TruthStatusArg::Inferred => TruthStatus::Inferred,
-            TruthStatusArg::Speculative => TruthStatus::Speculative,
-            TruthStatusArg::Retracted => TruthStatus::Retracted,
-        },
+        truth_status: write.truth_status.into_truth_status(),
         authority: match write.authority {
             AuthorityArg::Authoritative => Authority::Authoritative,
             AuthorityArg::Derived => Authority::Derived,
@@ -694,12 +2034,13 @@ fn build_record(payload: MemoryPayload, write: WriteArgs) -> Result<MemoryRecord
         },
         supersedes,
         contradicts,
+        tags: write.tags,
         payload,
     })
 }
 
 fn compute_snapshot_id(
-    records: &[MemoryRecord],
+    mut record_keys: Vec<String>,
     as_of: OffsetDateTime,
     text: &str,
     scope_parts: &[String],
@@ -711,13 +2052,9 @@ fn compute_snapshot_id(
         hasher.update(part.as_bytes());
     }
 
-    let mut sorted_ids = records
-        .iter()
-        .map(|record| format!("{}:{}", record.memory_id, record.memory_version_id))
-        .collect::<Vec<_>>();
-    sorted_ids.sort();
+    record_keys.sort();
 
-    for memory_id in sorted_ids {
+    for memory_id in record_keys {
         hasher.update(memory_id.as_bytes());
     }
 
@@ -784,6 +2121,16 @@ fn decrypt_payload_bytes(key: &[u8; 32], encrypted: &[u8]) -> Result<Vec<u8>> {
         .map_err(|err| anyhow!("failed to decrypt payload bytes: {err}"))
 }
 
+fn read_manifest_checkpoint(path: &Path) -> Result<ExportCheckpoint> {
+    let bytes =
+        fs::read(path).with_context(|| format!("failed to read manifest {}", path.display()))?;
+    let manifest: ExportManifest = serde_json::from_slice(&bytes)
+        .with_context(|| format!("failed to parse manifest {}", path.display()))?;
+    manifest.checkpoint.ok_or_else(|| {
+        anyhow!("manifest {} has no checkpoint; take a full export first", path.display())
+    })
+}
+
 fn write_manifest(out_dir: &Path, manifest: &ExportManifest) -> Result<()> {
     let manifest_path = out_dir.join(MANIFEST_FILE);
     let body = serde_json::to_vec_pretty(manifest)
@@ -792,11 +2139,15 @@ fn write_manifest(out_dir: &Path, manifest: &ExportManifest) -> Result<()> {
         .with_context(|| format!("failed to write manifest file {}", manifest_path.display()))
 }
 
-fn write_manifest_signature(out_dir: &Path, manifest_bytes: &[u8], key: &[u8; 32]) -> Result<()> {
+fn manifest_signature_hex(manifest_bytes: &[u8], key: &[u8; 32]) -> Result<String> {
     let mut mac = <HmacSha256 as Mac>::new_from_slice(key)
         .map_err(|err| anyhow!("failed to initialize signature key: {err}"))?;
     mac.update(manifest_bytes);
-    let signature_hex = hex::encode(mac.finalize().into_bytes());
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+fn write_manifest_signature(out_dir: &Path, manifest_bytes: &[u8], key: &[u8; 32]) -> Result<()> {
+    let signature_hex = manifest_signature_hex(manifest_bytes, key)?;
     let signature_path = out_dir.join(MANIFEST_SIG_FILE);
     fs::write(&signature_path, signature_hex)
         .with_context(|| format!("failed to write manifest signature {}", signature_path.display()))
@@ -807,16 +2158,23 @@ fn verify_manifest_signature(in_dir: &Path, manifest_bytes: &[u8], key: &[u8; 32
     let signature_body = fs::read_to_string(&signature_path).with_context(|| {
         format!("failed to read manifest signature file {}", signature_path.display())
     })?;
-    let signature = hex::decode(signature_body.trim()).with_context(|| {
-        format!("manifest signature file is not valid hex: {}", signature_path.display())
-    })?;
+    verify_manifest_signature_hex(manifest_bytes, &signature_body, key, &signature_path)
+}
+
+fn verify_manifest_signature_hex(
+    manifest_bytes: &[u8],
+    signature_hex: &str,
+    key: &[u8; 32],
+    source: &Path,
+) -> Result<()> {
+    let signature = hex::decode(signature_hex.trim())
+        .with_context(|| format!("manifest signature is not valid hex: {}", source.display()))?;
 
     let mut mac = <HmacSha256 as Mac>::new_from_slice(key)
         .map_err(|err| anyhow!("failed to initialize signature verification key: {err}"))?;
     mac.update(manifest_bytes);
-    mac.verify_slice(&signature).map_err(|_| {
-        anyhow!("manifest signature verification failed for {}", signature_path.display())
-    })
+    mac.verify_slice(&signature)
+        .map_err(|_| anyhow!("manifest signature verification failed for {}", source.display()))
 }
 
 fn write_security_metadata(out_dir: &Path, metadata: &SnapshotSecurityMetadata) -> Result<()> {
@@ -865,6 +2223,547 @@ fn encrypt_snapshot_files(
     Ok(())
 }
 
+/// Write `entries` and then the files of `snapshot` as a ustar stream compressed with zstd,
+/// returning `writer` once the zstd frame is finished. Snapshot files are serialized straight
+/// into their entries, sized by the lengths the export counted, instead of being buffered.
+fn write_tar_zstd<W: Write>(
+    writer: W,
+    entries: &[(&str, &[u8])],
+    snapshot: &SnapshotExport,
+) -> Result<W> {
+    let encoder = zstd::Encoder::new(writer, 0).context("failed to start zstd encoder")?;
+    let mtime = u64::try_from(OffsetDateTime::now_utc().unix_timestamp()).unwrap_or_default();
+    let entry_header = |size: u64| {
+        let mut header = tar::Header::new_ustar();
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_size(size);
+        header
+    };
+    let mut builder = tar::Builder::new(encoder);
+    for (name, body) in entries {
+        let mut header =
+            entry_header(u64::try_from(body.len()).context("archive entry size overflows u64")?);
+        builder
+            .append_data(&mut header, name, *body)
+            .with_context(|| format!("failed to write archive entry {name}"))?;
+    }
+    for file in &snapshot.manifest().files {
+        let name = file.path.as_str();
+        let size = snapshot
+            .file_len(name)
+            .ok_or_else(|| anyhow!("snapshot file {name} has no recorded length"))?;
+        let mut header = entry_header(size);
+        header.set_path(name).with_context(|| format!("invalid archive entry name {name}"))?;
+        header.set_cksum();
+        // Lay the entry out as `tar::Builder::append` does: header block, body, zero padding.
+        let out = builder.get_mut();
+        out.write_all(header.as_bytes())
+            .with_context(|| format!("failed to write archive entry {name}"))?;
+        snapshot.write_file(name, out)?;
+        let padding = usize::try_from((512 - size % 512) % 512)
+            .context("archive entry padding overflows usize")?;
+        out.write_all(&[0_u8; 512][..padding])
+            .with_context(|| format!("failed to write archive entry {name}"))?;
+    }
+    let encoder = builder.into_inner().context("failed to write archive trailer")?;
+    encoder.finish().context("failed to finish zstd archive stream")
+}
+
+/// Open the tar stream of an archive from `source`, decrypting it first when it carries the
+/// archive encryption header, and hand it to `read`; `path` only names it in errors.
+fn read_tar_zstd<T>(
+    source: impl Read,
+    path: &Path,
+    keys: &SnapshotDecryptKeys,
+    read: impl FnOnce(&mut dyn Read) -> Result<T>,
+) -> Result<T> {
+    let mut reader = BufReader::new(source);
+    let prefix =
+        reader.fill_buf().with_context(|| format!("failed to read archive {}", path.display()))?;
+    let recipients = if prefix.starts_with(ARCHIVE_ENCRYPTION_MAGIC) {
+        reader.consume(ARCHIVE_ENCRYPTION_MAGIC.len());
+        Some(Vec::new())
+    } else if prefix.starts_with(ARCHIVE_RECIPIENTS_MAGIC) {
+        reader.consume(ARCHIVE_RECIPIENTS_MAGIC.len());
+        Some(read_archive_recipients(&mut reader, path)?)
+    } else {
+        None
+    };
+
+    let value = if let Some(recipients) = recipients {
+        let key = keys.file_key(&recipients, path)?;
+        let decryptor = ArchiveDecryptor::new(reader, &key)?;
+        read_to_end_of_stream(
+            zstd::Decoder::new(decryptor).context("failed to start zstd decoder")?,
+            read,
+        )
+    } else {
+        read_to_end_of_stream(
+            zstd::Decoder::with_buffer(reader).context("failed to start zstd decoder")?,
+            read,
+        )
+    };
+    value.with_context(|| format!("failed to read archive {}", path.display()))
+}
+
+fn read_to_end_of_stream<T>(
+    mut reader: impl Read,
+    read: impl FnOnce(&mut dyn Read) -> Result<T>,
+) -> Result<T> {
+    let value = read(&mut reader)?;
+    // Drain the stream so a truncated or tampered encrypted tail is still detected.
+    std::io::copy(&mut reader, &mut std::io::sink()).context("archive stream is corrupt")?;
+    Ok(value)
+}
+
+/// Stream the entries of a snapshot archive. The manifest and its signature, which exports write
+/// first, are checked before any data file is read; each data file is then parsed against
+/// `limits` as it streams past, so no file is held in memory whole.
+fn read_snapshot_archive(
+    reader: &mut dyn Read,
+    args: &DbImportArgs,
+    verify_key: Option<&[u8; 32]>,
+    limits: ImportLimits,
+) -> Result<SnapshotStream> {
+    let mut archive = tar::Archive::new(reader);
+    let mut manifest_bytes = None;
+    let mut signature = None;
+    let mut snapshot = None;
+    for entry in archive.entries().context("failed to read archive entries")? {
+        let mut entry = entry.context("failed to read archive entry")?;
+        if !entry.header().entry_type().is_file() {
+            return Err(anyhow!("archive contains an unsupported entry type"));
+        }
+        let name = entry
+            .path()
+            .context("archive entry name is invalid")?
+            .to_str()
+            .ok_or_else(|| anyhow!("archive entry name is not UTF-8"))?
+            .to_string();
+
+        if name == MANIFEST_FILE || name == MANIFEST_SIG_FILE {
+            if snapshot.is_some() {
+                return Err(anyhow!("archive lists {name} after its data files"));
+            }
+            let body = read_archive_entry(&mut entry, &name, limits.max_line_bytes)?;
+            let slot = if name == MANIFEST_FILE { &mut manifest_bytes } else { &mut signature };
+            if slot.replace(body).is_some() {
+                return Err(anyhow!("archive contains duplicate entry {name}"));
+            }
+            continue;
+        }
+
+        if snapshot.is_none() {
+            let manifest = verified_archive_manifest(
+                args,
+                verify_key,
+                manifest_bytes.as_deref(),
+                signature.as_deref(),
+            )?;
+            snapshot = Some(SnapshotStream::new(manifest, limits)?);
+        }
+        if let Some(snapshot) = snapshot.as_mut() {
+            snapshot.read_file(&name, BufReader::new(&mut entry))?;
+        }
+    }
+
+    match snapshot {
+        Some(snapshot) => Ok(snapshot),
+        None => SnapshotStream::new(
+            verified_archive_manifest(
+                args,
+                verify_key,
+                manifest_bytes.as_deref(),
+                signature.as_deref(),
+            )?,
+            limits,
+        ),
+    }
+}
+
+/// Read a small archive entry such as the manifest, rejecting one over `max_bytes`.
+fn read_archive_entry(entry: &mut impl Read, name: &str, max_bytes: usize) -> Result<Vec<u8>> {
+    // One byte past the limit tells an oversized entry from one that fits exactly.
+    let read_cap = u64::try_from(max_bytes).unwrap_or(u64::MAX).saturating_add(1);
+    let mut body = Vec::new();
+    entry
+        .by_ref()
+        .take(read_cap)
+        .read_to_end(&mut body)
+        .with_context(|| format!("archive entry {name} is truncated"))?;
+    if body.len() > max_bytes {
+        return Err(anyhow!("archive entry {name} exceeds the {max_bytes} byte limit"));
+    }
+    Ok(body)
+}
+
+/// Check an archive's manifest signature, or that unsigned snapshots are allowed, and parse the
+/// manifest.
+fn verified_archive_manifest(
+    args: &DbImportArgs,
+    verify_key: Option<&[u8; 32]>,
+    manifest_bytes: Option<&[u8]>,
+    signature: Option<&[u8]>,
+) -> Result<ExportManifest> {
+    let manifest_bytes = manifest_bytes.ok_or_else(|| {
+        anyhow!("archive {} has no {MANIFEST_FILE} before its data files", args.input.display())
+    })?;
+
+    if let Some(signature) = signature {
+        let key = verify_key.ok_or_else(|| {
+            anyhow!(
+                "archive is signed; provide --verify-key-file to verify {}",
+                args.input.display()
+            )
+        })?;
+        let signature = std::str::from_utf8(signature).with_context(|| {
+            format!("archive manifest signature is not valid hex: {}", args.input.display())
+        })?;
+        verify_manifest_signature_hex(manifest_bytes, signature, key, &args.input)?;
+    } else if !args.allow_unsigned {
+        return Err(anyhow!(
+            "snapshot is unsigned; rerun with --allow-unsigned for explicit override"
+        ));
+    }
+
+    serde_json::from_slice(manifest_bytes)
+        .with_context(|| format!("failed to parse manifest in archive {}", args.input.display()))
+}
+
+fn read_archive_recipients(reader: &mut impl Read, path: &Path) -> Result<Vec<SnapshotRecipient>> {
+    let mut len = [0_u8; 4];
+    reader
+        .read_exact(&mut len)
+        .with_context(|| format!("archive recipients header is truncated: {}", path.display()))?;
+    let len = u64::from(u32::from_le_bytes(len));
+    if len > MAX_RECIPIENTS_HEADER_BYTES {
+        return Err(anyhow!(
+            "archive recipients header of {len} bytes exceeds the {MAX_RECIPIENTS_HEADER_BYTES} \
+             byte limit: {}",
+            path.display()
+        ));
+    }
+    let mut body = Vec::new();
+    reader
+        .by_ref()
+        .take(len)
+        .read_to_end(&mut body)
+        .with_context(|| format!("failed to read archive recipients header {}", path.display()))?;
+    if u64::try_from(body.len())? != len {
+        return Err(anyhow!("archive recipients header is truncated: {}", path.display()));
+    }
+    let header: ArchiveRecipientsHeader = serde_json::from_slice(&body)
+        .with_context(|| format!("failed to parse archive recipients header {}", path.display()))?;
+    if header.key_wrap_algorithm != KEY_WRAP_ALGORITHM {
+        return Err(anyhow!("unsupported key wrap algorithm in archive {}", path.display()));
+    }
+    Ok(header.recipients)
+}
+
+/// Encrypts an archive stream as `MKAENC1 || nonce` (or `MKAREC1 || recipients header || nonce`)
+/// followed by frames of `[last flag: u8][ciphertext length: u32 LE][ciphertext]`, one frame per
+/// 64 KiB chunk.
+struct ArchiveEncryptor<W: Write> {
+    inner: W,
+    encryptor: EncryptorBE32<XChaCha20Poly1305>,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> ArchiveEncryptor<W> {
+    fn new(mut inner: W, key: &[u8; 32], recipients: &[SnapshotRecipient]) -> Result<Self> {
+        if recipients.is_empty() {
+            inner.write_all(ARCHIVE_ENCRYPTION_MAGIC).context("failed to write archive header")?;
+        } else {
+            let header = serde_json::to_vec(&ArchiveRecipientsHeader {
+                key_wrap_algorithm: KEY_WRAP_ALGORITHM.to_string(),
+                recipients: recipients.to_vec(),
+            })
+            .context("failed to serialize archive recipients header")?;
+            let len =
+                u32::try_from(header.len()).context("archive recipients header is too large")?;
+            inner.write_all(ARCHIVE_RECIPIENTS_MAGIC).context("failed to write archive header")?;
+            inner.write_all(&len.to_le_bytes()).context("failed to write archive header")?;
+            inner.write_all(&header).context("failed to write archive header")?;
+        }
+        let mut nonce = [0_u8; ARCHIVE_NONCE_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+        inner.write_all(&nonce).context("failed to write archive header")?;
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(key));
+        Ok(Self {
+            inner,
+            encryptor: EncryptorBE32::from_aead(cipher, nonce.as_slice().into()),
+            buffer: Vec::with_capacity(2 * ARCHIVE_CHUNK_LEN),
+        })
+    }
+
+    /// Seal the buffered tail as the final frame and return the underlying writer.
+    fn finish(mut self) -> Result<W> {
+        let ciphertext = self
+            .encryptor
+            .encrypt_last(self.buffer.as_slice())
+            .map_err(|err| anyhow!("failed to encrypt archive chunk: {err}"))?;
+        write_archive_frame(&mut self.inner, true, &ciphertext)?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for ArchiveEncryptor<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        // Hold back the latest chunk so `finish` always has a tail to seal as the final frame.
+        while self.buffer.len() > ARCHIVE_CHUNK_LEN {
+            let chunk = self.buffer.drain(..ARCHIVE_CHUNK_LEN).collect::<Vec<_>>();
+            let ciphertext = self.encryptor.encrypt_next(chunk.as_slice()).map_err(|err| {
+                std::io::Error::other(format!("failed to encrypt archive: {err}"))
+            })?;
+            write_archive_frame(&mut self.inner, false, &ciphertext)
+                .map_err(std::io::Error::other)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn write_archive_frame(writer: &mut impl Write, last: bool, ciphertext: &[u8]) -> Result<()> {
+    let len = u32::try_from(ciphertext.len()).context("archive frame is too large")?;
+    writer.write_all(&[u8::from(last)]).context("failed to write archive frame")?;
+    writer.write_all(&len.to_le_bytes()).context("failed to write archive frame")?;
+    writer.write_all(ciphertext).context("failed to write archive frame")
+}
+
+/// Reverses [`ArchiveEncryptor`]; a stream that ends before its final frame is an error.
+struct ArchiveDecryptor<R: Read> {
+    inner: R,
+    decryptor: Option<DecryptorBE32<XChaCha20Poly1305>>,
+    plaintext: Vec<u8>,
+    position: usize,
+}
+
+impl<R: Read> ArchiveDecryptor<R> {
+    fn new(mut inner: R, key: &[u8; 32]) -> Result<Self> {
+        let mut nonce = [0_u8; ARCHIVE_NONCE_LEN];
+        inner.read_exact(&mut nonce).context("archive encryption header is truncated")?;
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(key));
+        Ok(Self {
+            inner,
+            decryptor: Some(DecryptorBE32::from_aead(cipher, nonce.as_slice().into())),
+            plaintext: Vec::new(),
+            position: 0,
+        })
+    }
+
+    fn next_frame(&mut self) -> std::io::Result<()> {
+        let mut header = [0_u8; 5];
+        self.inner.read_exact(&mut header).map_err(|err| {
+            std::io::Error::new(err.kind(), "encrypted archive ended before its final frame")
+        })?;
+        let len = usize::try_from(u32::from_le_bytes([header[1], header[2], header[3], header[4]]))
+            .map_err(std::io::Error::other)?;
+        if len > ARCHIVE_CHUNK_LEN + ARCHIVE_TAG_LEN {
+            return Err(std::io::Error::other("encrypted archive frame is too large"));
+        }
+        let mut ciphertext = vec![0_u8; len];
+        self.inner.read_exact(&mut ciphertext)?;
+
+        let decrypt_error =
+            |_| std::io::Error::other("failed to decrypt archive; wrong key or corrupt archive");
+        self.plaintext = match (header[0], self.decryptor.take()) {
+            (0, Some(mut decryptor)) => {
+                let plaintext =
+                    decryptor.decrypt_next(ciphertext.as_slice()).map_err(decrypt_error)?;
+                self.decryptor = Some(decryptor);
+                plaintext
+            }
+            (1, Some(decryptor)) => {
+                decryptor.decrypt_last(ciphertext.as_slice()).map_err(decrypt_error)?
+            }
+            _ => return Err(std::io::Error::other("encrypted archive frame is invalid")),
+        };
+        self.position = 0;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for ArchiveDecryptor<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.position == self.plaintext.len() {
+            if self.decryptor.is_none() {
+                return Ok(0);
+            }
+            self.next_frame()?;
+        }
+        let len = buf.len().min(self.plaintext.len() - self.position);
+        buf[..len].copy_from_slice(&self.plaintext[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+/// Resolve the snapshot file key for export: a fresh random key wrapped for each `--encrypt-to`
+/// recipient, or the shared `--encrypt-key-file` key with no recipients.
+fn snapshot_encryption_key(
+    args: &DbExportArgs,
+) -> Result<Option<([u8; 32], Vec<SnapshotRecipient>)>> {
+    if !args.encrypt_to.is_empty() {
+        let mut file_key = [0_u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut file_key);
+        let recipients = args
+            .encrypt_to
+            .iter()
+            .map(|recipient| wrap_file_key(&file_key, recipient))
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(Some((file_key, recipients)));
+    }
+    args.encrypt_key_file
+        .as_ref()
+        .map(|path| Ok((read_hex_key_file(path)?, Vec::new())))
+        .transpose()
+}
+
+/// Keys supplied to `mk db import` for encrypted snapshots.
+struct SnapshotDecryptKeys {
+    decrypt_key: Option<[u8; 32]>,
+    identity: Option<[u8; 32]>,
+}
+
+impl SnapshotDecryptKeys {
+    /// Return the file key: the shared key when the snapshot has no recipients, otherwise the
+    /// key unwrapped with the identity's recipient entry.
+    fn file_key(&self, recipients: &[SnapshotRecipient], source: &Path) -> Result<[u8; 32]> {
+        if recipients.is_empty() {
+            return self.decrypt_key.ok_or_else(|| {
+                anyhow!(
+                    "snapshot files are encrypted; provide --decrypt-key-file to import {}",
+                    source.display()
+                )
+            });
+        }
+        let identity = self.identity.ok_or_else(|| {
+            anyhow!(
+                "snapshot is encrypted to recipients; provide --identity-file to import {}",
+                source.display()
+            )
+        })?;
+        unwrap_file_key(recipients, &identity)
+            .with_context(|| format!("failed to unwrap snapshot key for {}", source.display()))
+    }
+}
+
+fn wrap_file_key(file_key: &[u8; 32], recipient: &str) -> Result<SnapshotRecipient> {
+    let recipient_key = parse_hex_key(recipient)
+        .with_context(|| format!("invalid --encrypt-to public key: {recipient}"))?;
+    let ephemeral = StaticSecret::random_from_rng(rand::rngs::OsRng);
+    let ephemeral_public = PublicKey::from(&ephemeral).to_bytes();
+    let shared = ephemeral.diffie_hellman(&PublicKey::from(recipient_key));
+    let wrap_key = recipient_wrap_key(&shared, &ephemeral_public, &recipient_key)?;
+    let wrapped = XChaCha20Poly1305::new(Key::from_slice(&wrap_key))
+        .encrypt(&XNonce::default(), file_key.as_slice())
+        .map_err(|err| anyhow!("failed to wrap snapshot key: {err}"))?;
+    Ok(SnapshotRecipient {
+        recipient: hex::encode(recipient_key),
+        ephemeral_public_key: hex::encode(ephemeral_public),
+        wrapped_file_key: hex::encode(wrapped),
+    })
+}
+
+fn unwrap_file_key(recipients: &[SnapshotRecipient], identity: &[u8; 32]) -> Result<[u8; 32]> {
+    let secret = StaticSecret::from(*identity);
+    let public = PublicKey::from(&secret).to_bytes();
+    let public_hex = hex::encode(public);
+    let entry =
+        recipients
+            .iter()
+            .find(|entry| entry.recipient.eq_ignore_ascii_case(&public_hex))
+            .ok_or_else(|| anyhow!("identity {public_hex} is not a recipient of this snapshot"))?;
+    let ephemeral_public = parse_hex_key(&entry.ephemeral_public_key)
+        .context("recipient entry has an invalid ephemeral public key")?;
+    let wrapped = hex::decode(&entry.wrapped_file_key)
+        .context("recipient entry has an invalid wrapped key")?;
+    let shared = secret.diffie_hellman(&PublicKey::from(ephemeral_public));
+    let wrap_key = recipient_wrap_key(&shared, &ephemeral_public, &public)?;
+    let file_key = XChaCha20Poly1305::new(Key::from_slice(&wrap_key))
+        .decrypt(&XNonce::default(), wrapped.as_slice())
+        .map_err(|_| anyhow!("recipient entry for {public_hex} failed to decrypt"))?;
+    file_key.try_into().map_err(|_| anyhow!("unwrapped snapshot key must be 32 bytes"))
+}
+
+/// HKDF-SHA256 over the X25519 shared secret, salted with both public keys. The wrap key is
+/// unique per ephemeral key, so wrapping uses a fixed zero nonce.
+fn recipient_wrap_key(
+    shared: &SharedSecret,
+    ephemeral_public: &[u8; 32],
+    recipient: &[u8; 32],
+) -> Result<[u8; 32]> {
+    if !shared.was_contributory() {
+        return Err(anyhow!("recipient public key is a low-order point"));
+    }
+    let salt = [ephemeral_public.as_slice(), recipient].concat();
+    let mut wrap_key = [0_u8; 32];
+    Hkdf::<Sha256>::new(Some(&salt), shared.as_bytes())
+        .expand(KEY_WRAP_INFO, &mut wrap_key)
+        .map_err(|err| anyhow!("failed to derive recipient wrap key: {err}"))?;
+    Ok(wrap_key)
+}
+
+/// X25519 public key for an identity's private key.
+fn identity_public_key(identity: &[u8; 32]) -> [u8; 32] {
+    PublicKey::from(&StaticSecret::from(*identity)).to_bytes()
+}
+
+fn parse_hex_key(value: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(value.trim()).context("key must be hex")?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow!("key must decode to exactly 32 bytes (got {})", bytes.len())
+    })
+}
+
+/// Parse repeated `--half-life <truth_status>=<days>` values into a decay configuration.
+fn parse_confidence_decay(values: &[String]) -> Result<Option<ConfidenceDecay>> {
+    if values.is_empty() {
+        return Ok(None);
+    }
+    let mut decay = ConfidenceDecay::default();
+    for value in values {
+        let (truth_status, days) = value
+            .split_once('=')
+            .ok_or_else(|| anyhow!("--half-life MUST be <truth_status>=<days>, got {value}"))?;
+        let truth_status = TruthStatus::parse(truth_status.trim())
+            .ok_or_else(|| anyhow!("unknown truth_status: {truth_status}"))?;
+        let days = days
+            .trim()
+            .parse::<f32>()
+            .with_context(|| format!("invalid half-life days for {}", truth_status.as_str()))?;
+        decay = decay.with_half_life(truth_status, days);
+    }
+    decay.validate()?;
+    Ok(Some(decay))
+}
+
+fn read_ndjson_rows<T: DeserializeOwned>(path: &Path, kind: &str) -> Result<Vec<T>> {
+    let body = fs::read_to_string(path)
+        .with_context(|| format!("failed to read {kind} file {}", path.display()))?;
+    let mut records = Vec::new();
+    for (index, line) in body.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(trimmed).with_context(|| {
+            format!("failed to parse NDJSON row {} from {}", index + 1, path.display())
+        })?;
+        records.push(record);
+    }
+    if records.is_empty() {
+        return Err(anyhow!("{kind} file {} contains no rows", path.display()));
+    }
+    Ok(records)
+}
+
 fn count_ndjson_records_bytes(bytes: &[u8]) -> usize {
     let body = String::from_utf8_lossy(bytes);
     body.lines().filter(|line| !line.trim().is_empty()).count()
@@ -873,7 +2772,7 @@ fn count_ndjson_records_bytes(bytes: &[u8]) -> usize {
 fn prepare_import_input(
     input_dir: &Path,
     verify_key: Option<&[u8; 32]>,
-    decrypt_key: Option<&[u8; 32]>,
+    keys: &SnapshotDecryptKeys,
     allow_unsigned: bool,
 ) -> Result<PathBuf> {
     let manifest_path = input_dir.join(MANIFEST_FILE);
@@ -902,18 +2801,21 @@ fn prepare_import_input(
         return Ok(input_dir.to_path_buf());
     }
 
-    let key = decrypt_key.ok_or_else(|| {
-        anyhow!(
-            "snapshot files are encrypted; provide --decrypt-key-file to import {}",
-            input_dir.display()
-        )
-    })?;
     if security.encryption_algorithm.as_deref() != Some(ENCRYPTION_ALGORITHM) {
         return Err(anyhow!(
             "unsupported encryption algorithm in security metadata for {}",
             input_dir.display()
         ));
     }
+    if !security.recipients.is_empty()
+        && security.key_wrap_algorithm.as_deref() != Some(KEY_WRAP_ALGORITHM)
+    {
+        return Err(anyhow!(
+            "unsupported key wrap algorithm in security metadata for {}",
+            input_dir.display()
+        ));
+    }
+    let key = keys.file_key(&security.recipients, input_dir)?;
 
     let mut manifest: ExportManifest = serde_json::from_slice(&manifest_bytes)
         .with_context(|| format!("failed to parse manifest {}", manifest_path.display()))?;
@@ -927,7 +2829,7 @@ fn prepare_import_input(
         let encrypted_bytes = fs::read(&encrypted_path).with_context(|| {
             format!("failed to read encrypted snapshot file {}", encrypted_path.display())
         })?;
-        let decrypted_bytes = decrypt_payload_bytes(key, &encrypted_bytes)?;
+        let decrypted_bytes = decrypt_payload_bytes(&key, &encrypted_bytes)?;
         let output_path = tmp_dir.join(&file.path);
         if let Some(parent) = output_path.parent() {
             fs::create_dir_all(parent).with_context(|| {
@@ -973,6 +2875,18 @@ fn parse_memory_version_id(value: &str) -> Result<MemoryVersionId> {
     Ok(MemoryVersionId(parsed))
 }
 
+impl TruthStatusArg {
+    fn into_truth_status(self) -> TruthStatus {
+        match self {
+            Self::Asserted => TruthStatus::Asserted,
+            Self::Observed => TruthStatus::Observed,
+            Self::Inferred => TruthStatus::Inferred,
+            Self::Speculative => TruthStatus::Speculative,
+            Self::Retracted => TruthStatus::Retracted,
+        }
+    }
+}
+
 impl RecordTypeArg {
     fn into_record_type(self) -> RecordType {
         match self {
@@ -981,6 +2895,96 @@ impl RecordTypeArg {
             Self::Preference => RecordType::Preference,
             Self::Event => RecordType::Event,
             Self::Outcome => RecordType::Outcome,
+            Self::Task => RecordType::Task,
+            Self::Risk => RecordType::Risk,
+            Self::Assumption => RecordType::Assumption,
+        }
+    }
+}
+
+impl TaskStatusArg {
+    fn into_task_status(self) -> TaskStatus {
+        match self {
+            Self::Open => TaskStatus::Open,
+            Self::InProgress => TaskStatus::InProgress,
+            Self::Done => TaskStatus::Done,
+            Self::Cancelled => TaskStatus::Cancelled,
+        }
+    }
+}
+
+impl RiskSeverityArg {
+    fn into_risk_severity(self) -> RiskSeverity {
+        match self {
+            Self::Low => RiskSeverity::Low,
+            Self::Medium => RiskSeverity::Medium,
+            Self::High => RiskSeverity::High,
+            Self::Critical => RiskSeverity::Critical,
+        }
+    }
+}
+
+impl AssumptionStatusArg {
+    fn into_assumption_status(self) -> AssumptionStatus {
+        match self {
+            Self::Unverified => AssumptionStatus::Unverified,
+            Self::Validated => AssumptionStatus::Validated,
+            Self::Invalidated => AssumptionStatus::Invalidated,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex32(value: &str) -> [u8; 32] {
+        let mut out = [0_u8; 32];
+        hex::decode_to_slice(value, &mut out)
+            .unwrap_or_else(|err| panic!("invalid hex fixture {value}: {err}"));
+        out
+    }
+
+    // Test IDs: TSEC-003
+    #[test]
+    fn identity_keys_match_rfc7748_vectors() {
+        let alice = hex32("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let bob = hex32("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+        let alice_public = identity_public_key(&alice);
+        let bob_public = identity_public_key(&bob);
+        assert_eq!(
+            hex::encode(alice_public),
+            "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"
+        );
+        assert_eq!(
+            hex::encode(bob_public),
+            "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"
+        );
+        let shared = StaticSecret::from(alice).diffie_hellman(&PublicKey::from(bob_public));
+        assert_eq!(
+            hex::encode(shared.as_bytes()),
+            "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742"
+        );
+    }
+
+    // Test IDs: TSEC-005
+    #[test]
+    fn recipients_header_over_limit_is_rejected_before_reading() {
+        let path = Path::new("oversized.mkarchive");
+        let oversized = u32::try_from(MAX_RECIPIENTS_HEADER_BYTES + 1)
+            .unwrap_or_else(|err| panic!("limit fixture overflows u32: {err}"));
+        let mut input = oversized.to_le_bytes().to_vec();
+        input.extend_from_slice(b"{}");
+        let Err(err) = read_archive_recipients(&mut input.as_slice(), path) else {
+            panic!("expected an oversized recipients header to be rejected");
+        };
+        assert!(err.to_string().contains("exceeds the 1048576 byte limit"), "{err}");
+
+        let mut truncated = 16_u32.to_le_bytes().to_vec();
+        truncated.extend_from_slice(b"{}");
+        let Err(err) = read_archive_recipients(&mut truncated.as_slice(), path) else {
+            panic!("expected a truncated recipients header to be rejected");
+        };
+        assert!(err.to_string().contains("is truncated"), "{err}");
+    }
+}
@@ -0,0 +1,224 @@
+//! Rendering of command results in the format selected by the global `--output` flag.
+//!
+//! Every command result is a JSON value carrying `contract_version`. Commands with a tabular
+//! view also describe it as a [`TableView`]; the other commands print JSON for `table` and
+//! `csv`, so one `--output` setting works across the whole command tree.
+
+use std::io::Write;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde_json::Value;
+
+/// Widest cell printed in `table` output before it is truncated with `…`.
+const MAX_TABLE_CELL_CHARS: usize = 48;
+
+static OUTPUT_FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// Aligned columns for terminals; commands without a tabular view print JSON.
+    Table,
+    /// Pretty-printed JSON document (the `cli.v1` contract).
+    #[default]
+    Json,
+    /// One compact JSON object per line: one per row for tabular commands.
+    Ndjson,
+    /// CSV with a header row and RFC 4180 quoting; commands without a tabular view print JSON.
+    Csv,
+}
+
+/// Select the format for every later [`emit`]; only the first call takes effect.
+pub(crate) fn set_format(format: OutputFormat) {
+    let _ = OUTPUT_FORMAT.set(format);
+}
+
+fn format() -> OutputFormat {
+    OUTPUT_FORMAT.get().copied().unwrap_or_default()
+}
+
+/// One column of a [`TableView`]: a header and a JSON pointer into each row item.
+pub(crate) struct Column {
+    pub(crate) header: &'static str,
+    pub(crate) pointer: &'static str,
+}
+
+/// Rows of a command result for `table`, `csv`, and `ndjson` output.
+pub(crate) struct TableView {
+    pub(crate) columns: &'static [Column],
+    /// Row items; `ndjson` prints each one whole, `table` and `csv` print the `columns`.
+    pub(crate) items: Vec<Value>,
+    /// `key: value` lines printed under the table, such as a pagination cursor.
+    pub(crate) notes: Vec<(&'static str, String)>,
+}
+
+/// Print `document` (already carrying `contract_version`), or `view` when the selected
+/// format is tabular and the command has one.
+pub(crate) fn emit(document: &Value, view: Option<TableView>) -> Result<()> {
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    match (format(), view) {
+        (OutputFormat::Table, Some(view)) => write_table(&mut out, &view),
+        (OutputFormat::Csv, Some(view)) => write_csv(&mut out, &view),
+        (OutputFormat::Ndjson, Some(view)) => {
+            for item in &view.items {
+                writeln!(out, "{}", serde_json::to_string(item)?)?;
+            }
+            Ok(())
+        }
+        (OutputFormat::Ndjson, None) => {
+            writeln!(out, "{}", serde_json::to_string(document)?)?;
+            Ok(())
+        }
+        (OutputFormat::Json | OutputFormat::Table | OutputFormat::Csv, _) => {
+            writeln!(out, "{}", serde_json::to_string_pretty(document)?)?;
+            Ok(())
+        }
+    }
+    .context("failed to write command output")
+}
+
+/// Print `document` to stderr for commands whose stdout carries other data, such as an
+/// archive stream; `ndjson` prints it compact and every other format pretty.
+pub(crate) fn emit_stderr(document: &Value) -> Result<()> {
+    let body = if format() == OutputFormat::Ndjson {
+        serde_json::to_string(document)?
+    } else {
+        serde_json::to_string_pretty(document)?
+    };
+    writeln!(std::io::stderr().lock(), "{body}").context("failed to write command output")
+}
+
+fn write_table(out: &mut impl Write, view: &TableView) -> Result<()> {
+    let rows = view
+        .items
+        .iter()
+        .map(|item| {
+            view.columns
+                .iter()
+                .map(|column| truncate(&cell(item, column.pointer), MAX_TABLE_CELL_CHARS))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let widths = view
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            rows.iter()
+                .map(|row| row[index].chars().count())
+                .chain(std::iter::once(column.header.len()))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let headers = view.columns.iter().map(|column| column.header.to_string()).collect::<Vec<_>>();
+    write_table_row(out, &headers, &widths)?;
+    let rules = widths.iter().map(|width| "-".repeat(*width)).collect::<Vec<_>>();
+    write_table_row(out, &rules, &widths)?;
+    for row in &rows {
+        write_table_row(out, row, &widths)?;
+    }
+    if rows.is_empty() {
+        writeln!(out, "(no rows)")?;
+    }
+    if !view.notes.is_empty() {
+        writeln!(out)?;
+        for (key, value) in &view.notes {
+            writeln!(out, "{key}: {value}")?;
+        }
+    }
+    Ok(())
+}
+
+fn write_table_row(out: &mut impl Write, cells: &[String], widths: &[usize]) -> Result<()> {
+    let line = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{cell:<width$}"))
+        .collect::<Vec<_>>()
+        .join("  ");
+    writeln!(out, "{}", line.trim_end())?;
+    Ok(())
+}
+
+fn write_csv(out: &mut impl Write, view: &TableView) -> Result<()> {
+    let headers = view.columns.iter().map(|column| csv_field(column.header)).collect::<Vec<_>>();
+    writeln!(out, "{}", headers.join(","))?;
+    for item in &view.items {
+        let fields = view
+            .columns
+            .iter()
+            .map(|column| csv_field(&cell(item, column.pointer)))
+            .collect::<Vec<_>>();
+        writeln!(out, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+/// Render the value at `pointer` as one cell: strings unquoted, arrays joined with `,`,
+/// missing values and `null` empty.
+fn cell(item: &Value, pointer: &str) -> String {
+    match item.pointer(pointer) {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|value| match value {
+                Value::String(text) => text.clone(),
+                other => other.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    let single_line = text.replace(['\r', '\n'], " ");
+    if single_line.chars().count() <= max_chars {
+        return single_line;
+    }
+    let mut shortened = single_line.chars().take(max_chars - 1).collect::<String>();
+    shortened.push('…');
+    shortened
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cell, csv_field, truncate};
+
+    #[test]
+    fn cells_flatten_arrays_and_blank_nulls() {
+        let item =
+            serde_json::json!({ "tags": ["a", "b"], "confidence": null, "nested": { "n": 2 } });
+        assert_eq!(cell(&item, "/tags"), "a,b");
+        assert_eq!(cell(&item, "/confidence"), "");
+        assert_eq!(cell(&item, "/missing"), "");
+        assert_eq!(cell(&item, "/nested/n"), "2");
+    }
+
+    #[test]
+    fn csv_fields_quote_separators_and_double_quotes() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn table_cells_truncate_to_one_line() {
+        assert_eq!(truncate("short", 8), "short");
+        assert_eq!(truncate("two\nlines", 16), "two lines");
+        assert_eq!(truncate("abcdefghij", 5), "abcd…");
+    }
+}
@@ -0,0 +1,508 @@
+//! `mk tui`: a terminal browser over the records of one namespace.
+//!
+//! The records view lists every version with its detail and lineage (versions of the same
+//! memory, `supersedes` and `contradicts` links in both directions). `a` and `r` run `query ask`
+//! and `query recall` from a prompt; the results view lists selected and excluded items with
+//! their Why explanation. Query packages are persisted exactly as the `query` commands do.
+
+use anyhow::{anyhow, Result};
+use memory_kernel_core::{ContextItem, ContextPackage, MemoryPayload, MemoryRecord};
+use memory_kernel_store_sqlite::SqliteStore;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+
+use crate::{
+    ask_package, recall_package, ConflictResolutionArg, QueryAskArgs, QueryRecallArgs, RulesetArg,
+};
+
+const HELP: &str = "↑/↓ move  Tab switch view  a ask  r recall  q quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Records,
+    Results,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QueryKind {
+    Ask,
+    Recall,
+}
+
+impl QueryKind {
+    fn prompt(self) -> &'static str {
+        match self {
+            Self::Ask => "ask (actor action resource question)> ",
+            Self::Recall => "recall (text)> ",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Prompt {
+    kind: QueryKind,
+    input: String,
+}
+
+/// What the event loop must do after a key press.
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    None,
+    Quit,
+    Query(QueryKind, String),
+}
+
+struct App {
+    namespace: String,
+    records: Vec<MemoryRecord>,
+    record_list: ListState,
+    package: Option<ContextPackage>,
+    item_list: ListState,
+    view: View,
+    prompt: Option<Prompt>,
+    status: String,
+}
+
+/// Run the TUI until the user quits, restoring the terminal on every exit path.
+pub(crate) fn run(store: &mut SqliteStore) -> Result<()> {
+    store.migrate()?;
+    let mut app = App::new(store.namespace().to_string(), store.list_records()?);
+    let mut terminal = ratatui::init();
+    let result = app.event_loop(&mut terminal, store);
+    ratatui::restore();
+    result
+}
+
+impl App {
+    fn new(namespace: String, mut records: Vec<MemoryRecord>) -> Self {
+        records.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then_with(|| a.memory_id.cmp(&b.memory_id))
+                .then_with(|| a.memory_version_id.cmp(&b.memory_version_id))
+        });
+        let mut record_list = ListState::default();
+        record_list.select((!records.is_empty()).then_some(0));
+        Self {
+            namespace,
+            records,
+            record_list,
+            package: None,
+            item_list: ListState::default(),
+            view: View::Records,
+            prompt: None,
+            status: HELP.to_string(),
+        }
+    }
+
+    fn event_loop(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        store: &mut SqliteStore,
+    ) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match self.handle_key(key.code) {
+                Action::None => {}
+                Action::Quit => return Ok(()),
+                Action::Query(kind, input) => match run_query(kind, &input, store) {
+                    Ok(package) => self.show_package(package),
+                    Err(err) => self.status = format!("error: {err:#}"),
+                },
+            }
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> Action {
+        if let Some(prompt) = self.prompt.as_mut() {
+            match code {
+                KeyCode::Esc => self.prompt = None,
+                KeyCode::Enter => {
+                    let prompt = self.prompt.take();
+                    return prompt.map_or(Action::None, |prompt| {
+                        Action::Query(prompt.kind, prompt.input.trim().to_string())
+                    });
+                }
+                KeyCode::Backspace => {
+                    prompt.input.pop();
+                }
+                KeyCode::Char(ch) => prompt.input.push(ch),
+                _ => {}
+            }
+            return Action::None;
+        }
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('a') => {
+                self.prompt = Some(Prompt { kind: QueryKind::Ask, input: String::new() });
+            }
+            KeyCode::Char('r') => {
+                self.prompt = Some(Prompt { kind: QueryKind::Recall, input: String::new() });
+            }
+            KeyCode::Tab => {
+                self.view = match self.view {
+                    View::Records if self.package.is_some() => View::Results,
+                    _ => View::Records,
+                };
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let (state, len) = match self.view {
+            View::Records => (&mut self.record_list, self.records.len()),
+            View::Results => (&mut self.item_list, self.package.as_ref().map_or(0, item_count)),
+        };
+        if len == 0 {
+            return;
+        }
+        let current = state.selected().unwrap_or(0);
+        state.select(Some(current.saturating_add_signed(delta).min(len - 1)));
+    }
+
+    fn show_package(&mut self, package: ContextPackage) {
+        self.status = format!(
+            "{} — {} ({} selected, {} excluded)",
+            package.context_package_id,
+            package.answer.result.as_str(),
+            package.selected_items.len(),
+            package.excluded_items.len()
+        );
+        self.item_list.select((item_count(&package) > 0).then_some(0));
+        self.package = Some(package);
+        self.view = View::Results;
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tabs_area, body_area, status_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0), Constraint::Length(1)])
+                .areas(frame.area());
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)])
+                .areas(body_area);
+
+        let selected_tab = usize::from(self.view == View::Results);
+        frame.render_widget(
+            Tabs::new(vec![
+                format!("Records ({})", self.records.len()),
+                "Query results".to_string(),
+            ])
+            .select(selected_tab)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .divider(" | "),
+            tabs_area,
+        );
+
+        match self.view {
+            View::Records => self.draw_records(frame, list_area, detail_area),
+            View::Results => self.draw_results(frame, list_area, detail_area),
+        }
+
+        let status = match self.prompt.as_ref() {
+            Some(prompt) => format!("{}{}", prompt.kind.prompt(), prompt.input),
+            None => format!("[{}] {}", self.namespace, self.status),
+        };
+        frame.render_widget(Paragraph::new(status), status_area);
+    }
+
+    fn draw_records(&mut self, frame: &mut Frame, list_area: Rect, detail_area: Rect) {
+        let items = self.records.iter().map(|record| ListItem::new(record_line(record)));
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(Block::new().borders(Borders::ALL).title("records"))
+                .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+            list_area,
+            &mut self.record_list,
+        );
+
+        let detail = self
+            .record_list
+            .selected()
+            .and_then(|index| self.records.get(index))
+            .map(|record| {
+                let mut lines = record_detail(record);
+                lines.push(String::new());
+                lines.push("lineage:".to_string());
+                lines.extend(
+                    lineage(&self.records, record).into_iter().map(|line| format!("  {line}")),
+                );
+                lines
+            })
+            .unwrap_or_else(|| vec!["no records in this namespace".to_string()]);
+        render_lines(frame, detail_area, "detail", detail);
+    }
+
+    fn draw_results(&mut self, frame: &mut Frame, list_area: Rect, detail_area: Rect) {
+        let Some(package) = self.package.as_ref() else {
+            render_lines(
+                frame,
+                detail_area,
+                "why",
+                vec!["press a or r to run a query".to_string()],
+            );
+            return;
+        };
+        let items = context_items(package).map(|item| ListItem::new(context_item_line(item)));
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(Block::new().borders(Borders::ALL).title(format!(
+                    "{}: {}",
+                    package.answer.result.as_str(),
+                    package.query.text
+                )))
+                .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+            list_area,
+            &mut self.item_list,
+        );
+
+        let mut lines = vec![format!("answer: {}", package.answer.why), String::new()];
+        if let Some(item) =
+            self.item_list.selected().and_then(|index| context_items(package).nth(index))
+        {
+            lines.extend(why_lines(item));
+        }
+        render_lines(frame, detail_area, "why", lines);
+    }
+}
+
+fn render_lines(frame: &mut Frame, area: Rect, title: &str, lines: Vec<String>) {
+    frame.render_widget(
+        Paragraph::new(lines.into_iter().map(Line::from).collect::<Vec<_>>())
+            .block(Block::new().borders(Borders::ALL).title(title.to_string()))
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn run_query(kind: QueryKind, input: &str, store: &mut SqliteStore) -> Result<ContextPackage> {
+    match kind {
+        QueryKind::Ask => {
+            let (actor, action, resource, text) = parse_ask_input(input)?;
+            ask_package(
+                QueryAskArgs {
+                    text,
+                    actor,
+                    action,
+                    resource,
+                    as_of: None,
+                    ruleset: RulesetArg::Precedence,
+                    conflict_resolution: ConflictResolutionArg::Inconclusive,
+                    half_lives: Vec::new(),
+                },
+                store,
+            )
+        }
+        QueryKind::Recall => {
+            if input.is_empty() {
+                return Err(anyhow!("recall needs query text"));
+            }
+            recall_package(
+                QueryRecallArgs {
+                    text: input.to_string(),
+                    record_types: Vec::new(),
+                    as_of: None,
+                    tags: Vec::new(),
+                    half_lives: Vec::new(),
+                },
+                store,
+            )
+        }
+    }
+}
+
+/// Split `actor action resource question...`; the question defaults to the scope itself.
+fn parse_ask_input(input: &str) -> Result<(String, String, String, String)> {
+    let mut words = input.split_whitespace();
+    let (Some(actor), Some(action), Some(resource)) = (words.next(), words.next(), words.next())
+    else {
+        return Err(anyhow!("ask needs `actor action resource [question]`"));
+    };
+    let question = words.collect::<Vec<_>>().join(" ");
+    let text =
+        if question.is_empty() { format!("May {actor} {action} {resource}?") } else { question };
+    Ok((actor.to_string(), action.to_string(), resource.to_string(), text))
+}
+
+fn item_count(package: &ContextPackage) -> usize {
+    package.selected_items.len() + package.excluded_items.len()
+}
+
+fn context_items(package: &ContextPackage) -> impl Iterator<Item = &ContextItem> {
+    package.selected_items.iter().chain(&package.excluded_items)
+}
+
+/// Lowercase wire name of a serde enum, such as a truth status or authority.
+fn label<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(text)) => text,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+fn payload_summary(payload: &MemoryPayload) -> String {
+    match payload {
+        MemoryPayload::Constraint(constraint) => format!(
+            "{} {} {} {}",
+            constraint.effect.as_str(),
+            constraint.scope.actor,
+            constraint.scope.action,
+            constraint.scope.resource
+        ),
+        other => other.summary().unwrap_or_default().to_string(),
+    }
+}
+
+fn record_line(record: &MemoryRecord) -> String {
+    format!(
+        "{:<10} v{:<2} {:<11} {}",
+        record.payload.record_type().as_str(),
+        record.version,
+        label(&record.truth_status),
+        payload_summary(&record.payload)
+    )
+}
+
+fn record_detail(record: &MemoryRecord) -> Vec<String> {
+    let mut lines = vec![
+        format!("memory_version_id: {}", record.memory_version_id),
+        format!("memory_id:         {} (v{})", record.memory_id, record.version),
+        format!("type:              {}", record.payload.record_type().as_str()),
+        format!("summary:           {}", payload_summary(&record.payload)),
+        format!("truth_status:      {}", label(&record.truth_status)),
+        format!("authority:         {}", label(&record.authority)),
+        format!(
+            "confidence:        {}",
+            record.confidence.map_or_else(|| "-".to_string(), |value| value.to_string())
+        ),
+        format!("writer:            {}", record.writer),
+        format!("justification:     {}", record.justification),
+        format!("effective_at:      {}", record.effective_at.format(&Rfc3339).unwrap_or_default()),
+        format!("source_uri:        {}", record.provenance.source_uri),
+    ];
+    if !record.tags.is_empty() {
+        lines.push(format!("tags:              {}", record.tags.join(", ")));
+    }
+    lines
+}
+
+/// Versions of the same memory plus `supersedes`/`contradicts` links in both directions.
+fn lineage(records: &[MemoryRecord], record: &MemoryRecord) -> Vec<String> {
+    let mut versions =
+        records.iter().filter(|other| other.memory_id == record.memory_id).collect::<Vec<_>>();
+    versions.sort_by_key(|other| other.version);
+    let mut lines = versions
+        .into_iter()
+        .map(|other| {
+            let marker =
+                if other.memory_version_id == record.memory_version_id { '*' } else { ' ' };
+            format!(
+                "{marker} v{} {} {}",
+                other.version,
+                other.memory_version_id,
+                label(&other.truth_status)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    lines.extend(record.supersedes.iter().map(|id| format!("supersedes {id}")));
+    lines.extend(
+        records
+            .iter()
+            .filter(|other| other.supersedes.contains(&record.memory_version_id))
+            .map(|other| format!("superseded by {}", other.memory_version_id)),
+    );
+    lines.extend(record.contradicts.iter().map(|id| format!("contradicts {id}")));
+    lines.extend(
+        records
+            .iter()
+            .filter(|other| other.contradicts.contains(&record.memory_version_id))
+            .map(|other| format!("contradicted by {}", other.memory_version_id)),
+    );
+    lines
+}
+
+fn context_item_line(item: &ContextItem) -> String {
+    let marker = if item.why.included { '+' } else { '-' };
+    format!(
+        "{marker} #{:<3} {:<10} {} {}",
+        item.rank,
+        item.record_type.as_str(),
+        label(&item.truth_status),
+        item.memory_version_id
+    )
+}
+
+fn why_lines(item: &ContextItem) -> Vec<String> {
+    let mut lines = vec![
+        format!("memory_version_id: {}", item.memory_version_id),
+        format!("included:          {}", item.why.included),
+        format!("authority:         {}", label(&item.authority)),
+    ];
+    if let Some(scores) = item.why.rule_scores.as_ref() {
+        lines.push(format!(
+            "rule_scores:       scope {:.2}, authority {}, truth {}, confidence {:.2}",
+            scores.scope_match, scores.authority_rank, scores.truth_status_rank, scores.confidence
+        ));
+    }
+    lines.push("reasons:".to_string());
+    lines.extend(item.why.reasons.iter().map(|reason| format!("  - {reason}")));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyCode;
+
+    use super::{parse_ask_input, Action, App, QueryKind, View};
+
+    #[test]
+    fn ask_input_splits_scope_and_defaults_question() {
+        let (actor, action, resource, text) =
+            parse_ask_input("user use usb_drive can I plug this in?").unwrap_or_default();
+        assert_eq!(
+            (actor.as_str(), action.as_str(), resource.as_str(), text.as_str()),
+            ("user", "use", "usb_drive", "can I plug this in?")
+        );
+        let defaulted = parse_ask_input("user use usb_drive").map(|parsed| parsed.3);
+        assert_eq!(defaulted.ok().as_deref(), Some("May user use usb_drive?"));
+        assert!(parse_ask_input("user use").is_err());
+    }
+
+    #[test]
+    fn prompt_keys_collect_input_and_submit_a_query() {
+        let mut app = App::new("default".to_string(), Vec::new());
+        assert_eq!(app.handle_key(KeyCode::Char('r')), Action::None);
+        for ch in "usb x".chars() {
+            assert_eq!(app.handle_key(KeyCode::Char(ch)), Action::None);
+        }
+        assert_eq!(app.handle_key(KeyCode::Backspace), Action::None);
+        assert_eq!(
+            app.handle_key(KeyCode::Enter),
+            Action::Query(QueryKind::Recall, "usb".to_string())
+        );
+        assert!(app.prompt.is_none());
+
+        // Without results, Tab stays on the records view; q quits outside a prompt.
+        assert_eq!(app.handle_key(KeyCode::Tab), Action::None);
+        assert_eq!(app.view, View::Records);
+        assert_eq!(app.handle_key(KeyCode::Char('q')), Action::Quit);
+    }
+}
@@ -0,0 +1,173 @@
+//! `mk query ask --watch`: re-evaluate one policy question as the store changes and print what
+//! moved in the answer and the selected items.
+//!
+//! Watched evaluations are not saved as context packages; saving would itself change `--db` and
+//! wake the watcher again.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use memory_kernel_core::{Answer, ContextItem, ContextPackage};
+use memory_kernel_store_sqlite::SqliteStore;
+use serde_json::Value;
+
+use crate::{build_ask_package, emit_json, QueryAskArgs};
+
+/// How often `--db` is checked for changes when no `--watch-interval` is given.
+const CHANGE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Size and modification time of the database and its WAL; writers change at least one.
+type DbFingerprint = [(u64, Option<SystemTime>); 2];
+
+/// Print an `initial` event, then a `changed` event each time a re-run differs from the last.
+///
+/// With `interval`, the query re-runs on that period; otherwise it re-runs when `db` changes.
+/// Runs until interrupted.
+pub(crate) fn run(
+    args: &QueryAskArgs,
+    store: &SqliteStore,
+    db: &Path,
+    interval: Option<Duration>,
+) -> Result<()> {
+    let mut fingerprint = db_fingerprint(db);
+    let mut previous = build_ask_package(args.clone(), store)?;
+    let mut run = 1_u64;
+    emit_json(watch_event(run, None, &previous))?;
+    loop {
+        match interval {
+            Some(interval) => std::thread::sleep(interval),
+            None => loop {
+                std::thread::sleep(CHANGE_POLL_INTERVAL);
+                let current = db_fingerprint(db);
+                if current != fingerprint {
+                    fingerprint = current;
+                    break;
+                }
+            },
+        }
+        let package = build_ask_package(args.clone(), store)?;
+        run += 1;
+        if package_changed(&previous, &package) {
+            emit_json(watch_event(run, Some(&previous), &package))?;
+        }
+        previous = package;
+    }
+}
+
+fn db_fingerprint(db: &Path) -> DbFingerprint {
+    let mut wal = db.as_os_str().to_owned();
+    wal.push("-wal");
+    [db, Path::new(&wal)].map(|path| {
+        std::fs::metadata(path)
+            .map_or((0, None), |metadata| (metadata.len(), metadata.modified().ok()))
+    })
+}
+
+fn package_changed(previous: &ContextPackage, current: &ContextPackage) -> bool {
+    previous.answer != current.answer || item_ranks(previous) != item_ranks(current)
+}
+
+fn item_ranks(package: &ContextPackage) -> BTreeMap<String, usize> {
+    package
+        .selected_items
+        .iter()
+        .map(|item| (item.memory_version_id.to_string(), item.rank))
+        .collect()
+}
+
+/// One watch event: the current answer and how the selected items differ from `previous`
+/// (every item counts as added on the first run).
+fn watch_event(run: u64, previous: Option<&ContextPackage>, current: &ContextPackage) -> Value {
+    let before = previous.map(item_ranks).unwrap_or_default();
+    let after = item_ranks(current);
+    let added = current
+        .selected_items
+        .iter()
+        .filter(|item| !before.contains_key(&item.memory_version_id.to_string()))
+        .map(item_summary)
+        .collect::<Vec<_>>();
+    let removed = previous
+        .map(|package| {
+            package
+                .selected_items
+                .iter()
+                .filter(|item| !after.contains_key(&item.memory_version_id.to_string()))
+                .map(item_summary)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let reranked = after
+        .iter()
+        .filter_map(|(id, rank)| {
+            before.get(id).filter(|previous_rank| *previous_rank != rank).map(|previous_rank| {
+                serde_json::json!({
+                    "memory_version_id": id,
+                    "previous_rank": previous_rank,
+                    "rank": rank,
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::json!({
+        "event": if previous.is_some() { "changed" } else { "initial" },
+        "run": run,
+        "snapshot_id": current.determinism.snapshot_id,
+        "answer": answer_value(&current.answer),
+        "previous_answer": previous.map(|package| answer_value(&package.answer)),
+        "added_items": added,
+        "removed_items": removed,
+        "reranked_items": reranked,
+    })
+}
+
+fn answer_value(answer: &Answer) -> Value {
+    serde_json::json!({ "result": answer.result.as_str(), "why": answer.why })
+}
+
+fn item_summary(item: &ContextItem) -> Value {
+    serde_json::json!({
+        "memory_version_id": item.memory_version_id.to_string(),
+        "rank": item.rank,
+        "record_type": item.record_type.as_str(),
+        "truth_status": item.truth_status.as_str(),
+    })
+}
+
+/// `--watch-interval` seconds as a poll period.
+pub(crate) fn parse_interval(seconds: Option<u64>) -> Result<Option<Duration>> {
+    seconds
+        .map(|seconds| {
+            (seconds > 0)
+                .then_some(Duration::from_secs(seconds))
+                .context("--watch-interval must be at least 1 second")
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{db_fingerprint, parse_interval};
+
+    #[test]
+    fn interval_must_be_positive() {
+        assert_eq!(parse_interval(None).ok(), Some(None));
+        assert_eq!(parse_interval(Some(3)).ok(), Some(Some(Duration::from_secs(3))));
+        assert!(parse_interval(Some(0)).is_err());
+    }
+
+    #[test]
+    fn fingerprint_tracks_the_wal_file() {
+        let db = std::env::temp_dir().join(format!("mk-watch-{}.sqlite3", ulid::Ulid::new()));
+        let mut wal = db.as_os_str().to_owned();
+        wal.push("-wal");
+        let before = db_fingerprint(&db);
+        assert!(std::fs::write(&wal, b"frame").is_ok());
+        assert_ne!(db_fingerprint(&db), before);
+        let _ = std::fs::remove_file(&wal);
+    }
+}
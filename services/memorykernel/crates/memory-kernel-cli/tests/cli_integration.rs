@@ -1,8 +1,9 @@
 use std::ffi::OsStr;
 use std::fs;
+use std::io::{BufRead, BufReader, Write};
 use std::iter::FromIterator;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Output};
+use std::process::{Command, Output, Stdio};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use jsonschema::JSONSchema;
@@ -179,14 +180,14 @@ fn db_commands_cover_migrate_integrity_backup_restore_export_import() {
             .and_then(Value::as_array)
             .map(std::vec::Vec::len)
             .unwrap_or_default(),
-        2
+        14
     );
 
     let schema_after_dry_run = run_json(["--db", path_str(&db_a), "db", "schema-version"]);
     assert_eq!(as_i64(&schema_after_dry_run, "current_version"), 0);
 
     let migrate = run_json(["--db", path_str(&db_a), "db", "migrate"]);
-    assert_eq!(as_i64(&migrate, "after_version"), 2);
+    assert_eq!(as_i64(&migrate, "after_version"), 14);
 
     let _record = run_json([
         "--db",
@@ -249,7 +250,7 @@ fn db_commands_cover_migrate_integrity_backup_restore_export_import() {
         .get("files")
         .and_then(Value::as_array)
         .unwrap_or_else(|| panic!("manifest.files should be an array: {manifest}"));
-    assert_eq!(files.len(), 2);
+    assert_eq!(files.len(), 3);
     assert!(export_dir.join("manifest.json").exists());
 
     let import = run_json([
@@ -278,7 +279,7 @@ fn db_commands_cover_migrate_integrity_backup_restore_export_import() {
 
     let restore =
         run_json(["--db", path_str(&db_b), "db", "restore", "--in", path_str(&backup_file)]);
-    assert_eq!(as_i64(&restore, "current_version"), 2);
+    assert_eq!(as_i64(&restore, "current_version"), 14);
 
     let _ = fs::remove_dir_all(&sandbox);
 }
@@ -435,6 +436,96 @@ fn encrypted_snapshot_round_trip_requires_explicit_decrypt_key() {
     let _ = fs::remove_dir_all(&sandbox);
 }
 
+// Test IDs: TSEC-004
+#[test]
+fn recipient_encrypted_snapshots_decrypt_with_each_recipient_identity() {
+    let sandbox = unique_temp_dir("memorykernel-cli-recipients");
+    let db_source = sandbox.join("source.sqlite3");
+    let export_dir = sandbox.join("export");
+    let archive = sandbox.join("snapshot.mkarchive");
+    let keygen = |name: &str| {
+        let identity = sandbox.join(name);
+        let generated =
+            run_json(["keys", "generate", "--kind", "identity", "--out", path_str(&identity)]);
+        validate_schema("keys-generate.response.schema.json", &generated);
+        (identity, as_str(&generated, "public_key").to_string())
+    };
+    let (alice, alice_public) = keygen("alice.key");
+    let (bob, bob_public) = keygen("bob.key");
+    let (mallory, _) = keygen("mallory.key");
+    assert!(!run_mk(["keys", "generate", "--kind", "identity", "--out", path_str(&alice)])
+        .status
+        .success());
+
+    let _record = run_json([
+        "--db",
+        path_str(&db_source),
+        "memory",
+        "add",
+        "decision",
+        "--summary",
+        "Decision: USB media use requires approval",
+        "--writer",
+        "tester",
+        "--justification",
+        "recipient export fixture",
+        "--source-uri",
+        "file:///decision.md",
+        "--truth-status",
+        "observed",
+        "--authority",
+        "authoritative",
+    ]);
+    for (format, out) in [("dir", &export_dir), ("tar-zstd", &archive)] {
+        let _export = run_json([
+            "--db",
+            path_str(&db_source),
+            "db",
+            "export",
+            "--format",
+            format,
+            "--out",
+            path_str(out),
+            "--encrypt-to",
+            &alice_public,
+            "--encrypt-to",
+            &bob_public,
+        ]);
+    }
+    let security = read_json_file(&export_dir.join("manifest.security.json"));
+    let recipients = security["recipients"].as_array().cloned().unwrap_or_default();
+    assert_eq!(recipients.len(), 2);
+    assert_eq!(recipients[0]["recipient"], alice_public.as_str());
+    assert_eq!(recipients[1]["recipient"], bob_public.as_str());
+
+    let import = |db: &str, format: &str, input: &Path, identity: Option<&Path>| {
+        let mut args = vec!["--db", db, "db", "import", "--format", format, "--in"];
+        args.extend([path_str(input), "--allow-unsigned"]);
+        if let Some(identity) = identity {
+            args.extend(["--identity-file", path_str(identity)]);
+        }
+        run_mk(args)
+    };
+    let db_alice = sandbox.join("alice.sqlite3");
+    let db_bob = sandbox.join("bob.sqlite3");
+    let missing = import(path_str(&db_alice), "dir", &export_dir, None);
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("--identity-file"));
+    assert!(!import(path_str(&db_alice), "dir", &export_dir, Some(&mallory)).status.success());
+    assert!(!import(path_str(&db_bob), "tar-zstd", &archive, Some(&mallory)).status.success());
+
+    for (db, format, input, identity) in
+        [(&db_alice, "dir", &export_dir, &alice), (&db_bob, "tar-zstd", &archive, &bob)]
+    {
+        let output = import(path_str(db), format, input, Some(identity));
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let imported: Value = serde_json::from_slice(&output.stdout)
+            .unwrap_or_else(|err| panic!("import output is not JSON: {err}"));
+        assert_eq!(imported["summary"]["imported_records"], 1);
+    }
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
 // Test IDs: TCLI-001, TCLI-002, TCLI-003
 #[test]
 fn memory_add_query_and_context_show_flow_is_consistent() {
@@ -630,158 +721,629 @@ fn query_recall_returns_persisted_mixed_record_context_package() {
     let _ = fs::remove_dir_all(&sandbox);
 }
 
-// Test IDs: TCLI-004
+// Test IDs: TCLI-009
 #[test]
-fn memory_link_rejects_non_ulid_version_ids() {
-    let sandbox = unique_temp_dir("memorykernel-cli-step8-link-validation");
+fn memory_add_supports_task_risk_and_assumption_records() {
+    let sandbox = unique_temp_dir("memorykernel-cli-record-types");
     let db = sandbox.join("kernel.sqlite3");
-
-    let output = run_mk([
+    let add = |record_type: &str, summary: &str, extra: &[&str]| {
+        let mut args =
+            vec!["--db", path_str(&db), "memory", "add", record_type, "--summary", summary];
+        args.extend_from_slice(extra);
+        args.extend_from_slice(&[
+            "--writer",
+            "tester",
+            "--justification",
+            "record type fixture",
+            "--source-uri",
+            "file:///planning.md",
+            "--truth-status",
+            "observed",
+            "--authority",
+            "authoritative",
+        ]);
+        run_json(args)
+    };
+
+    let task = add("task", "Rotate USB keys", &[]);
+    assert_eq!(task["payload"]["record_type"], "task");
+    assert_eq!(task["payload"]["payload"]["status"], "open");
+
+    let risk = add("risk", "USB exfiltration", &["--severity", "critical"]);
+    assert_eq!(risk["payload"]["payload"]["severity"], "critical");
+
+    let assumption = add("assumption", "USB ports disabled", &["--status", "validated"]);
+    assert_eq!(assumption["payload"]["payload"]["status"], "validated");
+
+    let missing_severity = run_mk([
         "--db",
         path_str(&db),
         "memory",
-        "link",
-        "--from",
-        "not-a-ulid",
-        "--to",
-        "also-not-a-ulid",
-        "--relation",
-        "supersedes",
+        "add",
+        "risk",
+        "--summary",
+        "No severity",
         "--writer",
         "tester",
         "--justification",
-        "invalid input test",
+        "record type fixture",
+        "--source-uri",
+        "file:///planning.md",
+        "--truth-status",
+        "observed",
+        "--authority",
+        "authoritative",
     ]);
+    assert!(!missing_severity.status.success());
 
-    assert!(!output.status.success());
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("invalid ULID"), "unexpected stderr: {stderr}");
+    let listed = run_json(["--db", path_str(&db), "memory", "list", "--record-type", "risk"]);
+    let records = listed.get("records").and_then(Value::as_array).map(Vec::len);
+    assert_eq!(records, Some(1));
 
     let _ = fs::remove_dir_all(&sandbox);
 }
 
-// Test IDs: TDOC-001
+// Test IDs: TCLI-010
 #[test]
-fn spec_docs_restrict_ambiguous_terms_to_mkr_027_exception_line() {
-    let repo_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
-    let spec_dir = repo_root.join("docs/spec");
-    let mut violations = Vec::new();
+fn memory_contradictions_reports_only_active_conflicts() {
+    let sandbox = unique_temp_dir("memorykernel-cli-contradictions");
+    let db = sandbox.join("kernel.sqlite3");
+    let add = |summary: &str, extra: &[&str]| {
+        let mut args =
+            vec!["--db", path_str(&db), "memory", "add", "decision", "--summary", summary];
+        args.extend_from_slice(extra);
+        args.extend_from_slice(&[
+            "--writer",
+            "tester",
+            "--justification",
+            "contradiction fixture",
+            "--source-uri",
+            "file:///decision.md",
+            "--truth-status",
+            "observed",
+            "--authority",
+            "authoritative",
+        ]);
+        run_json(args)
+    };
+
+    let original = add("USB drives are allowed", &[]);
+    let original_id = as_str(&original, "memory_version_id").to_string();
+    let conflicting = add("USB drives are forbidden", &["--contradicts", &original_id]);
+
+    let report = run_json(["--db", path_str(&db), "memory", "contradictions"]);
+    validate_schema("memory-contradictions.response.schema.json", &report);
+    assert_eq!(as_i64(&report, "contradiction_count"), 1);
+    assert_eq!(report["groups"][0]["memory_id"], conflicting["memory_id"]);
+    assert_eq!(report["groups"][0]["contradictions"][0]["to"]["memory_version_id"], original_id);
+
+    let _replacement = add("USB drives need approval", &["--supersedes", &original_id]);
+    let resolved = run_json(["--db", path_str(&db), "memory", "contradictions"]);
+    assert_eq!(as_i64(&resolved, "contradiction_count"), 0);
+    assert_eq!(resolved.get("groups").and_then(Value::as_array).map(Vec::len), Some(0));
 
-    let entries = fs::read_dir(&spec_dir)
-        .unwrap_or_else(|err| panic!("failed to read spec dir {}: {err}", spec_dir.display()));
-    for entry in entries {
-        let entry = entry.unwrap_or_else(|err| panic!("failed to read dir entry: {err}"));
-        let path = entry.path();
-        if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
-            continue;
-        }
+    let _ = fs::remove_dir_all(&sandbox);
+}
 
-        let body = fs::read_to_string(&path)
-            .unwrap_or_else(|err| panic!("failed to read spec file {}: {err}", path.display()));
-        for (line_no, line) in body.lines().enumerate() {
-            let lower = line.to_ascii_lowercase();
-            let has_ambiguous_term = lower.contains("usually") || lower.contains("etc.");
-            let is_allowed_exception = line.contains("MKR-027");
-            if has_ambiguous_term && !is_allowed_exception {
-                violations.push(format!("{}:{}: {}", path.display(), line_no + 1, line.trim()));
-            }
-        }
+// Test IDs: TCLI-011
+#[test]
+fn memory_add_batch_writes_ndjson_records_atomically() {
+    let sandbox = unique_temp_dir("memorykernel-cli-add-batch");
+    let db_source = sandbox.join("source.sqlite3");
+    let db_target = sandbox.join("target.sqlite3");
+    for resource in ["usb_drive", "printer"] {
+        let _ = run_json([
+            "--db",
+            path_str(&db_source),
+            "memory",
+            "add",
+            "constraint",
+            "--actor",
+            "user",
+            "--action",
+            "use",
+            "--resource",
+            resource,
+            "--effect",
+            "deny",
+            "--writer",
+            "tester",
+            "--justification",
+            "batch fixture",
+            "--source-uri",
+            "file:///policy.md",
+            "--truth-status",
+            "asserted",
+            "--authority",
+            "authoritative",
+        ]);
     }
+    let listed = run_json(["--db", path_str(&db_source), "memory", "list"]);
+    let records = listed
+        .get("records")
+        .and_then(Value::as_array)
+        .unwrap_or_else(|| panic!("missing records in list payload: {listed}"));
+    let ndjson = records.iter().map(Value::to_string).collect::<Vec<_>>().join("\n\n");
+    let batch_path = sandbox.join("records.ndjson");
+    fs::write(&batch_path, &ndjson)
+        .unwrap_or_else(|err| panic!("failed to write batch file: {err}"));
 
-    assert!(
-        violations.is_empty(),
-        "found ambiguous term violations outside MKR-027:\n{}",
-        violations.join("\n")
+    let added = run_json([
+        "--db",
+        path_str(&db_target),
+        "memory",
+        "add-batch",
+        "--file",
+        path_str(&batch_path),
+    ]);
+    validate_schema("memory-add-batch.response.schema.json", &added);
+    assert_eq!(as_i64(&added, "written"), 2);
+    assert_eq!(added["memory_version_ids"][0], records[0]["memory_version_id"]);
+
+    let output = run_mk([
+        "--db",
+        path_str(&db_target),
+        "memory",
+        "add-batch",
+        "--file",
+        path_str(&batch_path),
+    ]);
+    assert!(!output.status.success());
+    let target = run_json(["--db", path_str(&db_target), "memory", "list"]);
+    assert_eq!(target.get("records").and_then(Value::as_array).map(Vec::len), Some(2));
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-012
+#[test]
+fn context_list_filters_and_pages_persisted_packages() {
+    let sandbox = unique_temp_dir("memorykernel-cli-context-list");
+    let db = sandbox.join("kernel.sqlite3");
+    let mut package_ids = Vec::new();
+    for (actor, resource) in [("user", "usb_drive"), ("user", "printer"), ("contractor", "printer")]
+    {
+        let package = run_json([
+            "--db",
+            path_str(&db),
+            "query",
+            "ask",
+            "--text",
+            "May I use it?",
+            "--actor",
+            actor,
+            "--action",
+            "use",
+            "--resource",
+            resource,
+        ]);
+        package_ids.push(as_str(&package, "context_package_id").to_string());
+    }
+
+    let first =
+        run_json(["--db", path_str(&db), "context", "list", "--actor", "user", "--limit", "1"]);
+    validate_schema("context-list.response.schema.json", &first);
+    assert_eq!(first.get("packages").and_then(Value::as_array).map(Vec::len), Some(1));
+    let next_after = as_str(&first, "next_after").to_string();
+
+    let second = run_json([
+        "--db",
+        path_str(&db),
+        "context",
+        "list",
+        "--actor",
+        "user",
+        "--limit",
+        "1",
+        "--after",
+        &next_after,
+    ]);
+    assert_eq!(second.get("packages").and_then(Value::as_array).map(Vec::len), Some(1));
+    assert_eq!(second.get("next_after"), Some(&Value::Null));
+    let mut paged = vec![
+        first["packages"][0]["context_package_id"].clone(),
+        second["packages"][0]["context_package_id"].clone(),
+    ];
+    paged.sort_by(|left, right| left.as_str().cmp(&right.as_str()));
+    let mut expected =
+        vec![Value::from(package_ids[0].as_str()), Value::from(package_ids[1].as_str())];
+    expected.sort_by(|left, right| left.as_str().cmp(&right.as_str()));
+    assert_eq!(paged, expected);
+
+    let printers = run_json([
+        "--db",
+        path_str(&db),
+        "context",
+        "list",
+        "--resource",
+        "printer",
+        "--answer-result",
+        "inconclusive",
+        "--generated-from",
+        "2000-01-01T00:00:00Z",
+    ]);
+    assert_eq!(printers.get("packages").and_then(Value::as_array).map(Vec::len), Some(2));
+    let denied = run_json(["--db", path_str(&db), "context", "list", "--answer-result", "deny"]);
+    assert_eq!(denied.get("packages").and_then(Value::as_array).map(Vec::len), Some(0));
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-013
+#[test]
+fn query_simulate_reports_flips_without_persisting_constraint() {
+    let sandbox = unique_temp_dir("memorykernel-cli-query-simulate");
+    let db = sandbox.join("kernel.sqlite3");
+    let constraint_args = |effect: &'static str| {
+        [
+            "--actor",
+            "user",
+            "--action",
+            "use",
+            "--resource",
+            "usb_drive",
+            "--effect",
+            effect,
+            "--writer",
+            "tester",
+            "--justification",
+            "simulate fixture",
+            "--source-uri",
+            "file:///policy.md",
+            "--truth-status",
+            "asserted",
+            "--authority",
+            "authoritative",
+        ]
+    };
+    let mut add = vec!["--db", path_str(&db), "memory", "add", "constraint"];
+    add.extend(constraint_args("allow"));
+    let allow = run_json(add);
+    let allow_id = allow
+        .get("memory_version_id")
+        .and_then(Value::as_str)
+        .unwrap_or_else(|| panic!("missing memory_version_id: {allow}"))
+        .to_string();
+
+    let queries_path = sandbox.join("queries.ndjson");
+    let queries = [
+        r#"{"text":"usb?","actor":"user","action":"use","resource":"usb_drive"}"#,
+        r#"{"text":"printer?","actor":"user","action":"use","resource":"printer"}"#,
+    ]
+    .join("\n");
+    fs::write(&queries_path, queries)
+        .unwrap_or_else(|err| panic!("failed to write queries file: {err}"));
+
+    let mut simulate = vec!["--db", path_str(&db), "query", "simulate"];
+    simulate.extend(constraint_args("deny"));
+    simulate.extend(["--supersedes", allow_id.as_str(), "--queries", path_str(&queries_path)]);
+    let result = run_json(simulate);
+    validate_schema("query-simulate.response.schema.json", &result);
+    assert_eq!(as_i64(&result, "changed"), 1);
+    assert_eq!(result["outcomes"][0]["baseline"], "allow");
+    assert_eq!(result["outcomes"][0]["simulated"], "deny");
+    assert_eq!(result["outcomes"][1]["changed"], false);
+
+    let listed = run_json(["--db", path_str(&db), "memory", "list"]);
+    assert_eq!(listed.get("records").and_then(Value::as_array).map(Vec::len), Some(1));
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-014
+#[test]
+fn query_ask_ruleset_selects_precedence_rules_and_records_version() {
+    let sandbox = unique_temp_dir("memorykernel-cli-query-ruleset");
+    let db = sandbox.join("kernel.sqlite3");
+    for (effect, authority) in [("allow", "authoritative"), ("deny", "derived")] {
+        let _ = run_json([
+            "--db",
+            path_str(&db),
+            "memory",
+            "add",
+            "constraint",
+            "--actor",
+            "user",
+            "--action",
+            "use",
+            "--resource",
+            "usb_drive",
+            "--effect",
+            effect,
+            "--writer",
+            "tester",
+            "--justification",
+            "ruleset fixture",
+            "--source-uri",
+            "file:///policy.md",
+            "--truth-status",
+            "asserted",
+            "--authority",
+            authority,
+        ]);
+    }
+    let ask = |ruleset: &str| {
+        run_json([
+            "--db",
+            path_str(&db),
+            "query",
+            "ask",
+            "--text",
+            "Can I use a USB drive?",
+            "--actor",
+            "user",
+            "--action",
+            "use",
+            "--resource",
+            "usb_drive",
+            "--ruleset",
+            ruleset,
+        ])
+    };
+
+    let precedence = ask("precedence");
+    validate_schema("context-package.response.schema.json", &precedence);
+    assert_eq!(precedence["answer"]["result"], "allow");
+    assert_eq!(precedence["determinism"]["ruleset_version"], "ordering.v1");
+
+    let deny_overrides = ask("deny-overrides");
+    validate_schema("context-package.response.schema.json", &deny_overrides);
+    assert_eq!(deny_overrides["answer"]["result"], "deny");
+    assert_eq!(deny_overrides["determinism"]["ruleset_version"], "deny-overrides.v1");
+    assert_ne!(
+        precedence["determinism"]["snapshot_id"],
+        deny_overrides["determinism"]["snapshot_id"]
     );
+
+    let _ = fs::remove_dir_all(&sandbox);
 }
 
-// Test IDs: TCON-001, TCON-003
+// Test IDs: TCLI-015
 #[test]
-fn cli_outputs_validate_against_versioned_schemas() {
-    let sandbox = unique_temp_dir("memorykernel-contract-schemas");
-    let db_path = sandbox.join("schema.sqlite3");
+fn query_ask_half_life_decays_stale_inferred_constraints() {
+    let sandbox = unique_temp_dir("memorykernel-cli-confidence-decay");
+    let db = sandbox.join("kernel.sqlite3");
+    for (effect, confidence, effective_at) in
+        [("allow", "0.9", "2026-01-01T00:00:00Z"), ("deny", "0.6", "2026-03-01T00:00:00Z")]
+    {
+        let _ = run_json([
+            "--db",
+            path_str(&db),
+            "memory",
+            "add",
+            "constraint",
+            "--actor",
+            "user",
+            "--action",
+            "use",
+            "--resource",
+            "usb_drive",
+            "--effect",
+            effect,
+            "--writer",
+            "tester",
+            "--justification",
+            "decay fixture",
+            "--source-uri",
+            "file:///policy.md",
+            "--truth-status",
+            "inferred",
+            "--authority",
+            "authoritative",
+            "--confidence",
+            confidence,
+            "--effective-at",
+            effective_at,
+        ]);
+    }
+    let ask = |half_lives: &[&'static str]| {
+        let mut args = vec![
+            "--db",
+            path_str(&db),
+            "query",
+            "ask",
+            "--text",
+            "Can I use a USB drive?",
+            "--actor",
+            "user",
+            "--action",
+            "use",
+            "--resource",
+            "usb_drive",
+            "--as-of",
+            "2026-03-01T00:00:00Z",
+        ];
+        for half_life in half_lives {
+            args.extend(["--half-life", half_life]);
+        }
+        args
+    };
 
-    let schema_version = run_json(["--db", path_str(&db_path), "db", "schema-version"]);
-    validate_schema("db-schema-version.response.schema.json", &schema_version);
+    let undecayed = run_json(ask(&[]));
+    assert_eq!(undecayed["answer"]["result"], "allow");
 
-    let dry_run = run_json(["--db", path_str(&db_path), "db", "migrate", "--dry-run"]);
-    validate_schema("db-migrate.response.schema.json", &dry_run);
+    let decayed = run_json(ask(&["inferred=30"]));
+    validate_schema("context-package.response.schema.json", &decayed);
+    assert_eq!(decayed["answer"]["result"], "deny");
+    assert_ne!(undecayed["determinism"]["snapshot_id"], decayed["determinism"]["snapshot_id"]);
+    let stale_reasons = decayed["selected_items"][1]["why"]["reasons"].to_string();
+    assert!(stale_reasons.contains("confidence decayed from 0.900000"), "{stale_reasons}");
+    assert!(decayed["ordering_trace"].to_string().contains("inferred=30d"));
 
-    let migrate = run_json(["--db", path_str(&db_path), "db", "migrate"]);
-    validate_schema("db-migrate.response.schema.json", &migrate);
+    let invalid = run_mk(ask(&["inferred=0"]));
+    assert!(!invalid.status.success());
 
-    let added = run_json([
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-016
+#[test]
+fn memory_tags_filter_list_and_recall() {
+    let sandbox = unique_temp_dir("memorykernel-cli-tags");
+    let db = sandbox.join("kernel.sqlite3");
+    let add = |summary: &'static str, tags: &[&'static str]| {
+        let mut args = vec![
+            "--db",
+            path_str(&db),
+            "memory",
+            "add",
+            "decision",
+            "--summary",
+            summary,
+            "--writer",
+            "tester",
+            "--justification",
+            "tag fixture",
+            "--source-uri",
+            "file:///decision.md",
+            "--truth-status",
+            "observed",
+            "--authority",
+            "authoritative",
+            "--confidence",
+            "0.8",
+        ];
+        for tag in tags {
+            args.extend(["--tag", tag]);
+        }
+        run_json(args)
+    };
+    let payments = add("Decision: USB keys for payments", &["team-payments", "project-usb"]);
+    validate_schema("memory-add.response.schema.json", &payments);
+    assert_eq!(payments["tags"], serde_json::json!(["team-payments", "project-usb"]));
+    let _ = add("Decision: USB keys for billing", &["team-billing"]);
+
+    let listed = run_json(["--db", path_str(&db), "memory", "list", "--tag", "team-payments"]);
+    validate_schema("memory-list.response.schema.json", &listed);
+    let records = listed["records"].as_array().cloned().unwrap_or_default();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["memory_version_id"], payments["memory_version_id"]);
+
+    let recall = |tags: &[&'static str]| {
+        let mut args = vec!["--db", path_str(&db), "query", "recall", "--text", "usb keys"];
+        for tag in tags {
+            args.extend(["--tag", tag]);
+        }
+        run_json(args)
+    };
+    let all = recall(&[]);
+    assert_eq!(all["selected_items"].as_array().map(Vec::len), Some(2));
+    let tagged = recall(&["team-payments", "project-usb"]);
+    validate_schema("context-package.response.schema.json", &tagged);
+    assert_eq!(tagged["selected_items"].as_array().map(Vec::len), Some(1));
+    assert_eq!(tagged["selected_items"][0]["memory_version_id"], payments["memory_version_id"]);
+    assert_ne!(all["determinism"]["snapshot_id"], tagged["determinism"]["snapshot_id"]);
+
+    let duplicate = run_mk([
         "--db",
-        path_str(&db_path),
+        path_str(&db),
         "memory",
         "add",
-        "constraint",
-        "--actor",
-        "user",
-        "--action",
-        "use",
-        "--resource",
-        "usb_drive",
-        "--effect",
-        "deny",
+        "decision",
+        "--summary",
+        "Decision: duplicate tags",
         "--writer",
         "tester",
         "--justification",
-        "contract schema",
+        "tag fixture",
         "--source-uri",
-        "file:///policy.md",
+        "file:///decision.md",
         "--truth-status",
-        "asserted",
+        "observed",
         "--authority",
         "authoritative",
-        "--confidence",
-        "0.9",
+        "--tag",
+        "team-payments",
+        "--tag",
+        "team-payments",
     ]);
-    validate_schema("memory-add.response.schema.json", &added);
+    assert!(!duplicate.status.success());
 
-    let asked = run_json([
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-017
+#[test]
+fn namespace_flag_scopes_memory_query_and_context_commands() {
+    let sandbox = unique_temp_dir("memorykernel-cli-namespaces");
+    let db = sandbox.join("kernel.sqlite3");
+    let added = run_json([
         "--db",
-        path_str(&db_path),
-        "query",
-        "ask",
-        "--text",
-        "Am I allowed to use a USB drive?",
-        "--actor",
-        "user",
-        "--action",
-        "use",
-        "--resource",
-        "usb_drive",
+        path_str(&db),
+        "--namespace",
+        "team-a",
+        "memory",
+        "add",
+        "decision",
+        "--summary",
+        "Decision: USB keys need approval",
+        "--writer",
+        "tester",
+        "--justification",
+        "namespace fixture",
+        "--source-uri",
+        "file:///decision.md",
+        "--truth-status",
+        "observed",
+        "--authority",
+        "authoritative",
+        "--confidence",
+        "0.8",
     ]);
-    validate_schema("context-package.response.schema.json", &asked);
+    validate_schema("memory-add.response.schema.json", &added);
 
-    let context_package_id = as_str(&asked, "context_package_id").to_string();
-    let shown = run_json([
+    let list = |namespace: &'static str| {
+        let listed = run_json(["--db", path_str(&db), "--namespace", namespace, "memory", "list"]);
+        listed["records"].as_array().map(Vec::len).unwrap_or_default()
+    };
+    assert_eq!(list("team-a"), 1);
+    assert_eq!(list("team-b"), 0);
+    assert_eq!(list("default"), 0);
+
+    let recall = |namespace: &'static str| {
+        run_json([
+            "--db",
+            path_str(&db),
+            "--namespace",
+            namespace,
+            "query",
+            "recall",
+            "--text",
+            "usb keys",
+        ])
+    };
+    let team_a = recall("team-a");
+    assert_eq!(team_a["selected_items"].as_array().map(Vec::len), Some(1));
+    let team_b = recall("team-b");
+    assert_eq!(team_b["selected_items"].as_array().map(Vec::len), Some(0));
+
+    let package_id = team_a["context_package_id"].as_str().unwrap_or_default();
+    let shown = run_mk([
         "--db",
-        path_str(&db_path),
+        path_str(&db),
+        "--namespace",
+        "team-b",
         "context",
         "show",
         "--context-package-id",
-        &context_package_id,
+        package_id,
     ]);
-    validate_schema("context-package.response.schema.json", &shown);
+    assert!(!shown.status.success());
+
+    let invalid = run_mk(["--db", path_str(&db), "--namespace", "team a", "memory", "list"]);
+    assert!(!invalid.status.success());
 
     let _ = fs::remove_dir_all(&sandbox);
 }
 
-// Test IDs: TCON-002
+// Test IDs: TCLI-018
 #[test]
-fn key_outputs_match_golden_fixtures_after_normalization() {
-    let sandbox = unique_temp_dir("memorykernel-contract-golden");
-    let db_path = sandbox.join("golden.sqlite3");
-
-    let schema_version = run_json(["--db", path_str(&db_path), "db", "schema-version"]);
-    assert_golden_matches("db-schema-version.golden.json", schema_version);
-
-    let _ = run_json(["--db", path_str(&db_path), "db", "migrate"]);
-    let _ = run_json([
+fn query_explain_reports_one_record_without_persisting_a_package() {
+    let sandbox = unique_temp_dir("memorykernel-cli-query-explain");
+    let db = sandbox.join("kernel.sqlite3");
+    let added = run_json([
         "--db",
-        path_str(&db_path),
+        path_str(&db),
         "memory",
         "add",
         "constraint",
@@ -796,183 +1358,776 @@ fn key_outputs_match_golden_fixtures_after_normalization() {
         "--writer",
         "tester",
         "--justification",
-        "golden",
+        "explain fixture",
         "--source-uri",
         "file:///policy.md",
         "--truth-status",
         "asserted",
         "--authority",
         "authoritative",
-        "--confidence",
-        "0.9",
     ]);
+    let explain = |resource: &'static str| {
+        run_json([
+            "--db",
+            path_str(&db),
+            "query",
+            "explain",
+            "--memory-version-id",
+            as_str(&added, "memory_version_id"),
+            "--text",
+            "Can I use a USB drive?",
+            "--actor",
+            "user",
+            "--action",
+            "use",
+            "--resource",
+            resource,
+            "--ruleset",
+            "deny-overrides",
+        ])
+    };
+
+    let explained = explain("usb_drive");
+    validate_schema("query-explain.response.schema.json", &explained);
+    assert_eq!(explained["ruleset_version"], "deny-overrides.v1");
+    assert_eq!(explained["item"]["rank"], 1);
+    assert_eq!(explained["item"]["why"]["included"], true);
+
+    let out_of_scope = explain("printer");
+    validate_schema("query-explain.response.schema.json", &out_of_scope);
+    assert_eq!(out_of_scope["candidates"], 0);
+    assert_eq!(
+        out_of_scope["item"]["why"]["reasons"],
+        serde_json::json!(["constraint scope does not match actor/action/resource"])
+    );
 
-    let asked = run_json([
-        "--db",
-        path_str(&db_path),
-        "query",
-        "ask",
-        "--text",
-        "Am I allowed to use a USB drive?",
-        "--actor",
-        "user",
-        "--action",
-        "use",
-        "--resource",
-        "usb_drive",
-    ]);
-    assert_golden_matches("query-ask.golden.json", asked.clone());
+    let packages = run_json(["--db", path_str(&db), "context", "list"]);
+    assert_eq!(packages["packages"].as_array().map(Vec::len), Some(0));
 
-    let shown = run_json([
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-019
+#[test]
+fn db_export_filters_record_types_and_writers_into_manifest() {
+    let sandbox = unique_temp_dir("memorykernel-cli-export-filter");
+    let db = sandbox.join("kernel.sqlite3");
+    let export_dir = sandbox.join("export");
+    let add_decision = |writer: &'static str| {
+        run_json([
+            "--db",
+            path_str(&db),
+            "memory",
+            "add",
+            "decision",
+            "--summary",
+            "Decision: USB keys need approval",
+            "--writer",
+            writer,
+            "--justification",
+            "export filter fixture",
+            "--source-uri",
+            "file:///decision.md",
+            "--truth-status",
+            "observed",
+            "--authority",
+            "authoritative",
+        ])
+    };
+    let shared = add_decision("alice");
+    let _ = add_decision("bob");
+
+    let exported = run_json([
         "--db",
-        path_str(&db_path),
-        "context",
-        "show",
-        "--context-package-id",
-        as_str(&asked, "context_package_id"),
+        path_str(&db),
+        "db",
+        "export",
+        "--out",
+        path_str(&export_dir),
+        "--record-type",
+        "decision",
+        "--writer",
+        "alice",
     ]);
-    assert_golden_matches("context-show.golden.json", shown);
+    validate_schema("db-export.response.schema.json", &exported);
+    assert_eq!(
+        exported["manifest"]["filter"],
+        serde_json::json!({ "record_types": ["decision"], "writers": ["alice"] })
+    );
+    assert_eq!(exported["manifest"]["files"][0]["records"], 1);
+
+    let records = fs::read_to_string(export_dir.join("memory_records.ndjson")).unwrap_or_default();
+    assert_eq!(records.lines().count(), 1);
+    assert!(records.contains(as_str(&shared, "memory_version_id")));
 
     let _ = fs::remove_dir_all(&sandbox);
 }
 
-// Test ID: TCLI-008
+// Test IDs: TCLI-020
 #[test]
-fn host_cli_exposes_outcome_command_tree() {
-    let sandbox = unique_temp_dir("memorykernel-cli-outcome-host");
-    let db_path = sandbox.join("memory.sqlite3");
+fn db_export_since_manifest_writes_importable_delta() {
+    let sandbox = unique_temp_dir("memorykernel-cli-export-delta");
+    let db_a = sandbox.join("a.sqlite3");
+    let db_b = sandbox.join("b.sqlite3");
+    let full_dir = sandbox.join("full");
+    let delta_dir = sandbox.join("delta");
+    let add_decision = |summary: &'static str| {
+        run_json([
+            "--db",
+            path_str(&db_a),
+            "memory",
+            "add",
+            "decision",
+            "--summary",
+            summary,
+            "--writer",
+            "tester",
+            "--justification",
+            "delta export fixture",
+            "--source-uri",
+            "file:///decision.md",
+            "--truth-status",
+            "observed",
+            "--authority",
+            "authoritative",
+        ])
+    };
+    let import = |dir: &Path| {
+        run_json([
+            "--db",
+            path_str(&db_b),
+            "db",
+            "import",
+            "--in",
+            path_str(dir),
+            "--allow-unsigned",
+        ])
+    };
+
+    let _ = add_decision("Decision: USB keys need approval");
+    let full = run_json(["--db", path_str(&db_a), "db", "export", "--out", path_str(&full_dir)]);
+    validate_schema("db-export.response.schema.json", &full);
+    assert_eq!(full["manifest"]["checkpoint"]["records_write_seq"], 1);
+    assert_eq!(import(&full_dir)["summary"]["imported_records"], 1);
+
+    let _ = add_decision("Decision: USB keys must be encrypted");
+    let full_manifest = full_dir.join("manifest.json");
+    let delta = run_json([
+        "--db",
+        path_str(&db_a),
+        "db",
+        "export",
+        "--out",
+        path_str(&delta_dir),
+        "--since-manifest",
+        path_str(&full_manifest),
+    ]);
+    validate_schema("db-export.response.schema.json", &delta);
+    assert_eq!(delta["manifest"]["since"], full["manifest"]["checkpoint"]);
+    assert_eq!(delta["manifest"]["checkpoint"]["records_write_seq"], 2);
+    assert_eq!(delta["manifest"]["files"][0]["records"], 1);
+
+    let imported = import(&delta_dir);
+    validate_schema("db-import.response.schema.json", &imported);
+    assert_eq!(imported["summary"]["imported_records"], 1);
+    assert_eq!(imported["summary"]["checkpoint"], delta["manifest"]["checkpoint"]);
+    let listed = run_json(["--db", path_str(&db_b), "memory", "list"]);
+    assert_eq!(listed["records"].as_array().map(Vec::len), Some(2));
 
-    let _ = run_json(["--db", path_str(&db_path), "db", "migrate"]);
-    let decision = run_json([
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-021
+#[test]
+fn db_tar_zstd_archive_round_trips_encrypted_and_signed_snapshot() {
+    let sandbox = unique_temp_dir("memorykernel-cli-archive");
+    let db_source = sandbox.join("source.sqlite3");
+    let db_target = sandbox.join("target.sqlite3");
+    let archive = sandbox.join("snapshot.mkarchive");
+    let key_path = sandbox.join("archive.key");
+    fs::write(&key_path, "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff")
+        .unwrap_or_else(|err| panic!("failed to write key file {}: {err}", key_path.display()));
+    let key = path_str(&key_path);
+
+    let _record = run_json([
         "--db",
-        path_str(&db_path),
+        path_str(&db_source),
         "memory",
         "add",
         "decision",
         "--summary",
-        "USB policy decision seed",
+        "Decision: USB media use requires approval",
         "--writer",
         "tester",
         "--justification",
-        "host outcome integration test seed",
+        "archive export fixture",
         "--source-uri",
         "file:///decision.md",
         "--truth-status",
         "observed",
         "--authority",
-        "derived",
+        "authoritative",
     ]);
-    let memory_id = as_str(&decision, "memory_id");
+    let exported = run_json([
+        "--db",
+        path_str(&db_source),
+        "db",
+        "export",
+        "--format",
+        "tar-zstd",
+        "--out",
+        path_str(&archive),
+        "--encrypt-key-file",
+        key,
+        "--signing-key-file",
+        key,
+    ]);
+    validate_schema("db-export.response.schema.json", &exported);
+    assert_eq!(as_str(&exported, "format"), "tar-zstd");
+    assert_eq!(exported["manifest"]["files"][0]["records"], 1);
+    let bytes = fs::read(&archive)
+        .unwrap_or_else(|err| panic!("failed to read archive {}: {err}", archive.display()));
+    assert!(bytes.starts_with(b"MKAENC1"));
+
+    let import_args = |input: &Path, extra: &[&str]| {
+        let mut args = vec![
+            "--db",
+            path_str(&db_target),
+            "db",
+            "import",
+            "--format",
+            "tar-zstd",
+            "--in",
+            path_str(input),
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+        args.extend(extra.iter().copied().map(String::from));
+        args
+    };
+    let without_key = run_mk(import_args(&archive, &["--allow-unsigned"]));
+    assert!(!without_key.status.success());
+    assert!(String::from_utf8_lossy(&without_key.stderr).contains("--decrypt-key-file"));
+
+    let keys = ["--decrypt-key-file", key, "--verify-key-file", key];
+    let imported = run_json(import_args(&archive, &keys));
+    validate_schema("db-import.response.schema.json", &imported);
+    assert_eq!(imported["summary"]["imported_records"], 1);
+    assert_eq!(as_str(&imported, "in_file"), path_str(&archive));
+
+    let truncated = sandbox.join("truncated.mkarchive");
+    fs::write(&truncated, &bytes[..bytes.len() - 8])
+        .unwrap_or_else(|err| panic!("failed to write archive {}: {err}", truncated.display()));
+    assert!(!run_mk(import_args(&truncated, &keys)).status.success());
 
-    let projector_status =
-        run_json(["--db", path_str(&db_path), "outcome", "projector", "status", "--json"]);
-    assert_eq!(as_str(&projector_status, "contract_version"), "projector_status.v1");
+    let _ = fs::remove_dir_all(&sandbox);
+}
 
-    let gate_preview = run_json([
+// Test IDs: TCLI-022
+#[test]
+fn keys_commands_generate_rotate_and_resign_snapshot_signatures() {
+    let sandbox = unique_temp_dir("memorykernel-cli-keys");
+    let db_source = sandbox.join("source.sqlite3");
+    let db_target = sandbox.join("target.sqlite3");
+    let export_dir = sandbox.join("export");
+    let key_path = sandbox.join("signing.key");
+    let retired_path = sandbox.join("signing.key.previous");
+
+    let generated =
+        run_json(["keys", "generate", "--kind", "signing", "--out", path_str(&key_path)]);
+    validate_schema("keys-generate.response.schema.json", &generated);
+    assert!(generated.get("public_key").is_none());
+    let fingerprint =
+        run_json(["keys", "fingerprint", "--kind", "signing", "--key-file", path_str(&key_path)]);
+    validate_schema("keys-fingerprint.response.schema.json", &fingerprint);
+    assert_eq!(fingerprint["fingerprint"], generated["fingerprint"]);
+
+    let _export = run_json([
         "--db",
-        path_str(&db_path),
-        "outcome",
-        "gate",
-        "preview",
-        "--mode",
-        "safe",
-        "--as-of",
-        "2026-02-07T12:00:00Z",
-        "--candidate",
-        &format!("{memory_id}:1"),
-        "--json",
+        path_str(&db_source),
+        "db",
+        "export",
+        "--out",
+        path_str(&export_dir),
+        "--signing-key-file",
+        path_str(&key_path),
     ]);
-    assert_eq!(as_str(&gate_preview, "contract_version"), "gate_preview.v1");
-    assert_eq!(as_str(&gate_preview, "mode"), "safe");
+    let rotated =
+        run_json(["keys", "rotate", "--kind", "signing", "--key-file", path_str(&key_path)]);
+    validate_schema("keys-rotate.response.schema.json", &rotated);
+    assert_eq!(rotated["retired_fingerprint"], generated["fingerprint"]);
+    assert_ne!(rotated["fingerprint"], generated["fingerprint"]);
+    assert_eq!(as_str(&rotated, "retired_key_file"), path_str(&retired_path));
+
+    let resign = |verify: &Path| {
+        run_mk([
+            "keys",
+            "resign",
+            "--in",
+            path_str(&export_dir),
+            "--signing-key-file",
+            path_str(&key_path),
+            "--verify-key-file",
+            path_str(verify),
+        ])
+    };
+    assert!(!resign(&key_path).status.success());
+    let resigned = resign(&retired_path);
+    assert!(resigned.status.success(), "{}", String::from_utf8_lossy(&resigned.stderr));
+    let resigned: Value = serde_json::from_slice(&resigned.stdout)
+        .unwrap_or_else(|err| panic!("resign output is not JSON: {err}"));
+    validate_schema("keys-resign.response.schema.json", &resigned);
+    assert_eq!(resigned["fingerprint"], rotated["fingerprint"]);
+
+    let import = |verify: &Path| {
+        run_mk([
+            "--db",
+            path_str(&db_target),
+            "db",
+            "import",
+            "--in",
+            path_str(&export_dir),
+            "--verify-key-file",
+            path_str(verify),
+        ])
+    };
+    assert!(!import(&retired_path).status.success());
+    assert!(import(&key_path).status.success());
 
-    let _ = run_json(["--db", path_str(&db_path), "outcome", "replay"]);
     let _ = fs::remove_dir_all(&sandbox);
 }
 
+// Test IDs: TCLI-023
+#[cfg(not(feature = "sqlcipher"))]
 #[test]
-fn integration_contract_schemas_validate_fixtures() {
-    let repo = repo_root();
-    let schema_dir = repo.join("contracts/integration/v1/schemas");
-    let fixture_dir = repo.join("contracts/integration/v1/fixtures");
+fn db_key_file_requires_sqlcipher_build() {
+    let sandbox = unique_temp_dir("memorykernel-cli-store-key");
+    let key_path = sandbox.join("store.key");
+    let _ = run_json(["keys", "generate", "--kind", "encryption", "--out", path_str(&key_path)]);
+    let db = sandbox.join("kernel.sqlite3");
 
-    let envelope = read_json_file(&fixture_dir.join("context-package-envelope.sample.json"));
-    validate_schema_in(&schema_dir, "context-package-envelope.schema.json", &envelope);
+    let output =
+        run_mk(["--db", path_str(&db), "--db-key-file", path_str(&key_path), "memory", "list"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("sqlcipher"));
 
-    let trust = read_json_file(&fixture_dir.join("trust-gate-attachment.sample.json"));
-    validate_schema_in(&schema_dir, "trust-gate-attachment.schema.json", &trust);
+    let _ = fs::remove_dir_all(&sandbox);
+}
 
-    let proposed = read_json_file(&fixture_dir.join("proposed-memory-write.sample.json"));
-    validate_schema_in(&schema_dir, "proposed-memory-write.schema.json", &proposed);
+// Test IDs: TCLI-023
+#[cfg(feature = "sqlcipher")]
+#[test]
+fn db_encrypt_writes_store_readable_only_with_db_key_file() {
+    let sandbox = unique_temp_dir("memorykernel-cli-store-key");
+    let key_path = sandbox.join("store.key");
+    let _ = run_json(["keys", "generate", "--kind", "encryption", "--out", path_str(&key_path)]);
+    let db = sandbox.join("plain.sqlite3");
+    let encrypted = sandbox.join("encrypted.sqlite3");
+    let _record = run_json([
+        "--db",
+        path_str(&db),
+        "memory",
+        "add",
+        "decision",
+        "--summary",
+        "Decision: USB media use requires approval",
+        "--writer",
+        "tester",
+        "--justification",
+        "encrypted store fixture",
+        "--source-uri",
+        "file:///decision.md",
+        "--truth-status",
+        "observed",
+        "--authority",
+        "authoritative",
+    ]);
+
+    let output = run_json([
+        "--db",
+        path_str(&db),
+        "db",
+        "encrypt",
+        "--out",
+        path_str(&encrypted),
+        "--key-file",
+        path_str(&key_path),
+    ]);
+    validate_schema("db-encrypt.response.schema.json", &output);
+    let listed = run_json([
+        "--db",
+        path_str(&encrypted),
+        "--db-key-file",
+        path_str(&key_path),
+        "memory",
+        "list",
+    ]);
+    assert_eq!(listed["records"].as_array().map(Vec::len), Some(1));
+    assert!(!run_mk(["--db", path_str(&encrypted), "memory", "list"]).status.success());
+
+    let _ = fs::remove_dir_all(&sandbox);
 }
 
-// Test IDs: TTRI-001
+// Test IDs: TCLI-004
 #[test]
-fn trilogy_contract_parity_checker_matches_sibling_repos_when_available() {
-    let repo = repo_root();
-    let script = repo.join("scripts/verify_contract_parity.sh");
-    assert!(script.exists(), "contract parity checker script should exist: {}", script.display());
+fn memory_link_rejects_non_ulid_version_ids() {
+    let sandbox = unique_temp_dir("memorykernel-cli-step8-link-validation");
+    let db = sandbox.join("kernel.sqlite3");
 
-    let output = Command::new(&script)
-        .arg("--canonical-root")
-        .arg(&repo)
-        .arg("--allow-missing")
-        .output()
-        .unwrap_or_else(|err| panic!("failed to execute {}: {err}", script.display()));
+    let output = run_mk([
+        "--db",
+        path_str(&db),
+        "memory",
+        "link",
+        "--from",
+        "not-a-ulid",
+        "--to",
+        "also-not-a-ulid",
+        "--relation",
+        "supersedes",
+        "--writer",
+        "tester",
+        "--justification",
+        "invalid input test",
+    ]);
 
-    if !output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        panic!(
-            "contract parity checker failed (status={}):\nstdout:\n{}\nstderr:\n{}",
-            output.status, stdout, stderr
-        );
-    }
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid ULID"), "unexpected stderr: {stderr}");
+
+    let _ = fs::remove_dir_all(&sandbox);
 }
 
-// Test IDs: TTRI-002
+// Test IDs: TDOC-001
 #[test]
-fn trilogy_compatibility_artifacts_validate_against_memorykernel_consumer_requirements() {
-    let repo = repo_root();
-    let script = repo.join("scripts/verify_trilogy_compatibility_artifacts.sh");
-    assert!(
-        script.exists(),
-        "trilogy compatibility artifact checker script should exist: {}",
-        script.display()
-    );
+fn spec_docs_restrict_ambiguous_terms_to_mkr_027_exception_line() {
+    let repo_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let spec_dir = repo_root.join("docs/spec");
+    let mut violations = Vec::new();
 
-    let output = Command::new(&script)
-        .arg("--memorykernel-root")
-        .arg(&repo)
-        .arg("--allow-missing")
-        .output()
-        .unwrap_or_else(|err| panic!("failed to execute {}: {err}", script.display()));
+    let entries = fs::read_dir(&spec_dir)
+        .unwrap_or_else(|err| panic!("failed to read spec dir {}: {err}", spec_dir.display()));
+    for entry in entries {
+        let entry = entry.unwrap_or_else(|err| panic!("failed to read dir entry: {err}"));
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+            continue;
+        }
 
-    if !output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        panic!(
-            "trilogy compatibility artifact checker failed (status={}):\nstdout:\n{}\nstderr:\n{}",
-            output.status, stdout, stderr
-        );
+        let body = fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("failed to read spec file {}: {err}", path.display()));
+        for (line_no, line) in body.lines().enumerate() {
+            let lower = line.to_ascii_lowercase();
+            let has_ambiguous_term = lower.contains("usually") || lower.contains("etc.");
+            let is_allowed_exception = line.contains("MKR-027");
+            if has_ambiguous_term && !is_allowed_exception {
+                violations.push(format!("{}:{}: {}", path.display(), line_no + 1, line.trim()));
+            }
+        }
     }
+
+    assert!(
+        violations.is_empty(),
+        "found ambiguous term violations outside MKR-027:\n{}",
+        violations.join("\n")
+    );
 }
 
-// Test IDs: TDOC-002, TDOC-003, TDOC-004, TDOC-005, TDOC-007, TDOC-008
-#[allow(clippy::too_many_lines)]
+// Test IDs: TCON-001, TCON-003
 #[test]
-fn governance_docs_exist_for_phase_checklists_and_policies() {
-    let repo = repo_root();
-    let required_paths = [
-        "CHANGELOG.md",
-        "docs/spec/versioning.md",
-        "docs/implementation/adoption-gate.md",
-        "docs/implementation/phase-1-contract-freeze.md",
-        "docs/implementation/phase-2-integration-surface.md",
-        "docs/implementation/phase-3-retrieval-expansion.md",
-        "docs/implementation/phase-4-hardening.md",
-        "docs/implementation/phase-5-security-and-trust.md",
-        "docs/implementation/phase-6-release-and-adoption.md",
-        "docs/implementation/phase-7-trilogy-convergence.md",
+fn cli_outputs_validate_against_versioned_schemas() {
+    let sandbox = unique_temp_dir("memorykernel-contract-schemas");
+    let db_path = sandbox.join("schema.sqlite3");
+
+    let schema_version = run_json(["--db", path_str(&db_path), "db", "schema-version"]);
+    validate_schema("db-schema-version.response.schema.json", &schema_version);
+
+    let dry_run = run_json(["--db", path_str(&db_path), "db", "migrate", "--dry-run"]);
+    validate_schema("db-migrate.response.schema.json", &dry_run);
+
+    let migrate = run_json(["--db", path_str(&db_path), "db", "migrate"]);
+    validate_schema("db-migrate.response.schema.json", &migrate);
+
+    let added = run_json([
+        "--db",
+        path_str(&db_path),
+        "memory",
+        "add",
+        "constraint",
+        "--actor",
+        "user",
+        "--action",
+        "use",
+        "--resource",
+        "usb_drive",
+        "--effect",
+        "deny",
+        "--writer",
+        "tester",
+        "--justification",
+        "contract schema",
+        "--source-uri",
+        "file:///policy.md",
+        "--truth-status",
+        "asserted",
+        "--authority",
+        "authoritative",
+        "--confidence",
+        "0.9",
+    ]);
+    validate_schema("memory-add.response.schema.json", &added);
+
+    let listed = run_json([
+        "--db",
+        path_str(&db_path),
+        "memory",
+        "list",
+        "--record-type",
+        "constraint",
+        "--writer",
+        "tester",
+        "--limit",
+        "10",
+    ]);
+    validate_schema("memory-list.response.schema.json", &listed);
+    assert_eq!(listed.get("records").and_then(Value::as_array).map(Vec::len), Some(1));
+    assert_eq!(listed.get("next_after"), Some(&Value::Null));
+
+    let asked = run_json([
+        "--db",
+        path_str(&db_path),
+        "query",
+        "ask",
+        "--text",
+        "Am I allowed to use a USB drive?",
+        "--actor",
+        "user",
+        "--action",
+        "use",
+        "--resource",
+        "usb_drive",
+    ]);
+    validate_schema("context-package.response.schema.json", &asked);
+
+    let context_package_id = as_str(&asked, "context_package_id").to_string();
+    let shown = run_json([
+        "--db",
+        path_str(&db_path),
+        "context",
+        "show",
+        "--context-package-id",
+        &context_package_id,
+    ]);
+    validate_schema("context-package.response.schema.json", &shown);
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCON-002
+#[test]
+fn key_outputs_match_golden_fixtures_after_normalization() {
+    let sandbox = unique_temp_dir("memorykernel-contract-golden");
+    let db_path = sandbox.join("golden.sqlite3");
+
+    let schema_version = run_json(["--db", path_str(&db_path), "db", "schema-version"]);
+    assert_golden_matches("db-schema-version.golden.json", schema_version);
+
+    let _ = run_json(["--db", path_str(&db_path), "db", "migrate"]);
+    let _ = run_json([
+        "--db",
+        path_str(&db_path),
+        "memory",
+        "add",
+        "constraint",
+        "--actor",
+        "user",
+        "--action",
+        "use",
+        "--resource",
+        "usb_drive",
+        "--effect",
+        "deny",
+        "--writer",
+        "tester",
+        "--justification",
+        "golden",
+        "--source-uri",
+        "file:///policy.md",
+        "--truth-status",
+        "asserted",
+        "--authority",
+        "authoritative",
+        "--confidence",
+        "0.9",
+    ]);
+
+    let asked = run_json([
+        "--db",
+        path_str(&db_path),
+        "query",
+        "ask",
+        "--text",
+        "Am I allowed to use a USB drive?",
+        "--actor",
+        "user",
+        "--action",
+        "use",
+        "--resource",
+        "usb_drive",
+    ]);
+    assert_golden_matches("query-ask.golden.json", asked.clone());
+
+    let shown = run_json([
+        "--db",
+        path_str(&db_path),
+        "context",
+        "show",
+        "--context-package-id",
+        as_str(&asked, "context_package_id"),
+    ]);
+    assert_golden_matches("context-show.golden.json", shown);
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test ID: TCLI-008
+#[test]
+fn host_cli_exposes_outcome_command_tree() {
+    let sandbox = unique_temp_dir("memorykernel-cli-outcome-host");
+    let db_path = sandbox.join("memory.sqlite3");
+
+    let _ = run_json(["--db", path_str(&db_path), "db", "migrate"]);
+    let decision = run_json([
+        "--db",
+        path_str(&db_path),
+        "memory",
+        "add",
+        "decision",
+        "--summary",
+        "USB policy decision seed",
+        "--writer",
+        "tester",
+        "--justification",
+        "host outcome integration test seed",
+        "--source-uri",
+        "file:///decision.md",
+        "--truth-status",
+        "observed",
+        "--authority",
+        "derived",
+    ]);
+    let memory_id = as_str(&decision, "memory_id");
+
+    let projector_status =
+        run_json(["--db", path_str(&db_path), "outcome", "projector", "status", "--json"]);
+    assert_eq!(as_str(&projector_status, "contract_version"), "projector_status.v1");
+
+    let gate_preview = run_json([
+        "--db",
+        path_str(&db_path),
+        "outcome",
+        "gate",
+        "preview",
+        "--mode",
+        "safe",
+        "--as-of",
+        "2026-02-07T12:00:00Z",
+        "--candidate",
+        &format!("{memory_id}:1"),
+        "--json",
+    ]);
+    assert_eq!(as_str(&gate_preview, "contract_version"), "gate_preview.v1");
+    assert_eq!(as_str(&gate_preview, "mode"), "safe");
+
+    let _ = run_json(["--db", path_str(&db_path), "outcome", "replay"]);
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+#[test]
+fn integration_contract_schemas_validate_fixtures() {
+    let repo = repo_root();
+    let schema_dir = repo.join("contracts/integration/v1/schemas");
+    let fixture_dir = repo.join("contracts/integration/v1/fixtures");
+
+    let envelope = read_json_file(&fixture_dir.join("context-package-envelope.sample.json"));
+    validate_schema_in(&schema_dir, "context-package-envelope.schema.json", &envelope);
+
+    let trust = read_json_file(&fixture_dir.join("trust-gate-attachment.sample.json"));
+    validate_schema_in(&schema_dir, "trust-gate-attachment.schema.json", &trust);
+
+    let proposed = read_json_file(&fixture_dir.join("proposed-memory-write.sample.json"));
+    validate_schema_in(&schema_dir, "proposed-memory-write.schema.json", &proposed);
+}
+
+// Test IDs: TTRI-001
+#[test]
+fn trilogy_contract_parity_checker_matches_sibling_repos_when_available() {
+    let repo = repo_root();
+    let script = repo.join("scripts/verify_contract_parity.sh");
+    assert!(script.exists(), "contract parity checker script should exist: {}", script.display());
+
+    let output = Command::new(&script)
+        .arg("--canonical-root")
+        .arg(&repo)
+        .arg("--allow-missing")
+        .output()
+        .unwrap_or_else(|err| panic!("failed to execute {}: {err}", script.display()));
+
+    if !output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        panic!(
+            "contract parity checker failed (status={}):\nstdout:\n{}\nstderr:\n{}",
+            output.status, stdout, stderr
+        );
+    }
+}
+
+// Test IDs: TTRI-002
+#[test]
+fn trilogy_compatibility_artifacts_validate_against_memorykernel_consumer_requirements() {
+    let repo = repo_root();
+    let script = repo.join("scripts/verify_trilogy_compatibility_artifacts.sh");
+    assert!(
+        script.exists(),
+        "trilogy compatibility artifact checker script should exist: {}",
+        script.display()
+    );
+
+    let output = Command::new(&script)
+        .arg("--memorykernel-root")
+        .arg(&repo)
+        .arg("--allow-missing")
+        .output()
+        .unwrap_or_else(|err| panic!("failed to execute {}: {err}", script.display()));
+
+    if !output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        panic!(
+            "trilogy compatibility artifact checker failed (status={}):\nstdout:\n{}\nstderr:\n{}",
+            output.status, stdout, stderr
+        );
+    }
+}
+
+// Test IDs: TDOC-002, TDOC-003, TDOC-004, TDOC-005, TDOC-007, TDOC-008
+#[allow(clippy::too_many_lines)]
+#[test]
+fn governance_docs_exist_for_phase_checklists_and_policies() {
+    let repo = repo_root();
+    let required_paths = [
+        "CHANGELOG.md",
+        "docs/spec/versioning.md",
+        "docs/implementation/adoption-gate.md",
+        "docs/implementation/phase-1-contract-freeze.md",
+        "docs/implementation/phase-2-integration-surface.md",
+        "docs/implementation/phase-3-retrieval-expansion.md",
+        "docs/implementation/phase-4-hardening.md",
+        "docs/implementation/phase-5-security-and-trust.md",
+        "docs/implementation/phase-6-release-and-adoption.md",
+        "docs/implementation/phase-7-trilogy-convergence.md",
         "docs/implementation/phase-8-hosted-ci-convergence.md",
         "docs/implementation/phase-9-rc-orchestration.md",
         "docs/implementation/phase-10-soak-and-ops-readiness.md",
@@ -1159,3 +2314,900 @@ fn governance_docs_exist_for_phase_checklists_and_policies() {
         }
     }
 }
+
+// Test IDs: TCLI-024
+#[test]
+fn db_maintain_checkpoints_wal_and_vacuums_into_new_file() {
+    let sandbox = unique_temp_dir("memorykernel-cli-maintain");
+    let db = sandbox.join("kernel.sqlite3");
+    let compacted = sandbox.join("compacted.sqlite3");
+    let _ = run_json(["--db", path_str(&db), "db", "migrate"]);
+
+    let report = run_json(["--db", path_str(&db), "db", "maintain"]);
+    validate_schema("db-maintain.response.schema.json", &report);
+    assert_eq!(as_i64(&report, "wal_bytes_after"), 0);
+    assert!(report.get("vacuum_into").is_none());
+
+    let vacuumed =
+        run_json(["--db", path_str(&db), "db", "maintain", "--vacuum-into", path_str(&compacted)]);
+    validate_schema("db-maintain.response.schema.json", &vacuumed);
+    assert!(compacted.exists());
+    assert!(as_i64(&vacuumed, "vacuum_bytes") > 0);
+
+    let repeat =
+        run_mk(["--db", path_str(&db), "db", "maintain", "--vacuum-into", path_str(&compacted)]);
+    assert!(!repeat.status.success());
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-025
+#[test]
+fn memory_redact_scrubs_payload_in_list_and_export() {
+    let sandbox = unique_temp_dir("memorykernel-cli-redact");
+    let db = sandbox.join("kernel.sqlite3");
+    let export_dir = sandbox.join("export");
+    let record = run_json([
+        "--db",
+        path_str(&db),
+        "memory",
+        "add",
+        "event",
+        "--summary",
+        "alice@example.com reported a lost laptop",
+        "--writer",
+        "tester",
+        "--justification",
+        "redaction fixture",
+        "--source-uri",
+        "file:///event.md",
+        "--truth-status",
+        "observed",
+        "--authority",
+        "authoritative",
+    ]);
+    let id = record["memory_version_id"].as_str().unwrap_or_default().to_string();
+
+    let args = ["--writer", "privacy", "--justification", "erasure request"];
+    let redact = |id: &str| {
+        let mut command = vec!["--db", path_str(&db), "memory", "redact", "--memory-version-id"];
+        command.push(id);
+        command.extend(args);
+        command.into_iter().map(str::to_string).collect::<Vec<_>>()
+    };
+    let redacted = run_json(redact(&id));
+    validate_schema("memory-redact.response.schema.json", &redacted);
+    assert_eq!(redacted["redaction"]["memory_version_id"], id.as_str());
+    assert!(!run_mk(redact(&id)).status.success());
+
+    let listed = run_json(["--db", path_str(&db), "memory", "list"]);
+    assert_eq!(listed["records"][0]["payload"]["payload"]["summary"], "[REDACTED]");
+
+    let export = run_json(["--db", path_str(&db), "db", "export", "--out", path_str(&export_dir)]);
+    assert_eq!(export["manifest"]["files"][2]["path"], "redactions.ndjson");
+    assert_eq!(export["manifest"]["files"][2]["records"], 1);
+    let records = fs::read_to_string(export_dir.join("memory_records.ndjson")).unwrap_or_default();
+    assert!(!records.contains("alice@example.com"));
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-026
+#[test]
+fn output_flag_renders_table_csv_and_ndjson_views() {
+    let sandbox = unique_temp_dir("memorykernel-cli-output");
+    let db = sandbox.join("kernel.sqlite3");
+    let add = |summary: &'static str| {
+        run_json([
+            "--db",
+            path_str(&db),
+            "memory",
+            "add",
+            "decision",
+            "--summary",
+            summary,
+            "--writer",
+            "tester",
+            "--justification",
+            "output fixture",
+            "--source-uri",
+            "file:///decision.md",
+            "--truth-status",
+            "observed",
+            "--authority",
+            "authoritative",
+            "--tag",
+            "ops",
+        ])
+    };
+    let first = add("Decision: rotate keys, quarterly");
+    let _ = add("Decision: keep \"audit\" logs");
+    let run_text = |args: &[&str]| {
+        let output = run_mk(args);
+        assert!(output.status.success(), "mk {args:?} failed: {output:?}");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let table = run_text(&["--db", path_str(&db), "--output", "table", "memory", "list"]);
+    let lines = table.lines().collect::<Vec<_>>();
+    assert!(lines[0].starts_with("memory_version_id  type"));
+    assert!(lines[1].starts_with("---"));
+    assert_eq!(lines.len(), 4);
+    assert!(table.contains("Decision: rotate keys, quarterly"));
+
+    let csv = run_text(&["--db", path_str(&db), "memory", "list", "--output", "csv"]);
+    let csv_lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(
+        csv_lines[0],
+        "memory_version_id,type,version,truth_status,authority,confidence,writer,effective_at,\
+         tags,summary"
+    );
+    assert!(csv.contains(",\"Decision: rotate keys, quarterly\""));
+    assert!(csv.contains(",\"Decision: keep \"\"audit\"\" logs\""));
+
+    let ndjson = run_text(&["--db", path_str(&db), "--output", "ndjson", "memory", "list"]);
+    let rows = ndjson
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap_or(Value::Null))
+        .collect::<Vec<_>>();
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().any(|row| row["memory_version_id"] == first["memory_version_id"]));
+
+    let package = run_json(["--db", path_str(&db), "query", "recall", "--text", "audit logs"]);
+    let package_id = as_str(&package, "context_package_id");
+    let shown = run_text(&[
+        "--db",
+        path_str(&db),
+        "--output",
+        "table",
+        "context",
+        "show",
+        "--context-package-id",
+        package_id,
+    ]);
+    assert!(shown.lines().next().unwrap_or_default().starts_with("rank  included"));
+    assert!(shown.contains(&format!("context_package_id: {package_id}")));
+
+    let integrity = run_text(&["--db", path_str(&db), "--output", "csv", "db", "integrity-check"]);
+    assert!(integrity.starts_with("check,ok,detail\nquick_check,true,ok\n"));
+    assert!(integrity.contains("foreign_keys,true,0 violation(s)\n"));
+
+    // Commands without a tabular view keep the JSON contract under `table`.
+    let schema = run_json(["--db", path_str(&db), "--output", "table", "db", "schema-version"]);
+    assert_eq!(as_str(&schema, "contract_version"), "cli.v1");
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-027
+#[test]
+fn config_file_supplies_db_writer_and_output_defaults() {
+    let sandbox = unique_temp_dir("memorykernel-cli-config");
+    let db = sandbox.join("configured.sqlite3");
+    let config = sandbox.join("config.toml");
+    fs::write(
+        &config,
+        format!("db = {:?}\nwriter = \"configured-writer\"\noutput = \"ndjson\"\n", path_str(&db)),
+    )
+    .unwrap_or_else(|err| panic!("failed to write config {}: {err}", config.display()));
+    let run_configured = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_mk"))
+            .args(args)
+            .env("MK_CONFIG", &config)
+            .output()
+            .unwrap_or_else(|err| panic!("failed to execute mk binary: {err}"));
+        assert!(output.status.success(), "mk {args:?} failed: {output:?}");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let added = run_configured(&[
+        "memory",
+        "add",
+        "event",
+        "--summary",
+        "Configured defaults apply",
+        "--justification",
+        "config fixture",
+        "--source-uri",
+        "file:///event.md",
+        "--truth-status",
+        "observed",
+        "--authority",
+        "authoritative",
+    ]);
+    let added: Value = serde_json::from_str(added.trim())
+        .unwrap_or_else(|err| panic!("stdout is not one JSON line: {err}\n{added}"));
+    assert_eq!(as_str(&added, "writer"), "configured-writer");
+    assert!(db.exists());
+
+    let listed = run_configured(&["memory", "list"]);
+    assert_eq!(listed.lines().count(), 1);
+    let table = run_configured(&["--output", "table", "memory", "list"]);
+    assert!(table.starts_with("memory_version_id"));
+    let overridden = run_configured(&[
+        "memory",
+        "redact",
+        "--memory-version-id",
+        as_str(&added, "memory_version_id"),
+        "--writer",
+        "flag-writer",
+        "--justification",
+        "config fixture",
+    ]);
+    assert!(overridden.contains("\"writer\":\"flag-writer\""), "{overridden}");
+
+    fs::write(&config, "output = \"yaml\"\n")
+        .unwrap_or_else(|err| panic!("failed to write config {}: {err}", config.display()));
+    let invalid = Command::new(env!("CARGO_BIN_EXE_mk"))
+        .args(["memory", "list"])
+        .env("MK_CONFIG", &config)
+        .output()
+        .unwrap_or_else(|err| panic!("failed to execute mk binary: {err}"));
+    assert!(!invalid.status.success());
+    assert!(String::from_utf8_lossy(&invalid.stderr).contains("unknown output format `yaml`"));
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-028
+#[test]
+fn completions_print_scripts_for_each_shell() {
+    for (shell, marker) in [("bash", "_mk()"), ("zsh", "#compdef mk"), ("fish", "complete -c mk")] {
+        let output = run_mk(["completions", shell]);
+        assert!(output.status.success(), "completions {shell} failed: {output:?}");
+        let script = String::from_utf8_lossy(&output.stdout);
+        assert!(script.contains(marker), "{shell} script lacks `{marker}`");
+        assert!(script.contains("integrity-check"));
+    }
+    assert!(!run_mk(["completions", "tcsh"]).status.success());
+}
+
+// Test IDs: TCLI-029
+#[test]
+fn doctor_reports_store_health_with_fixes() {
+    let sandbox = unique_temp_dir("memorykernel-cli-doctor");
+    let db = sandbox.join("kernel.sqlite3");
+
+    let missing = run_mk(["--db", path_str(&db), "doctor"]);
+    assert!(!missing.status.success());
+    assert!(!db.exists(), "doctor must not create a missing store");
+    let report: Value = serde_json::from_slice(&missing.stdout)
+        .unwrap_or_else(|err| panic!("doctor stdout is not JSON: {err}"));
+    validate_schema("doctor.response.schema.json", &report);
+    assert_eq!(report["checks"][0]["status"], "fail");
+
+    let _ = run_json(["--db", path_str(&db), "db", "schema-version"]);
+    let pending = run_json(["--db", path_str(&db), "doctor"]);
+    validate_schema("doctor.response.schema.json", &pending);
+    assert_eq!(pending["healthy"], true);
+    assert_eq!(pending["checks"][1]["check"], "migrations");
+    assert_eq!(pending["checks"][1]["status"], "warn");
+    assert_eq!(pending["checks"][1]["fix"], "run `mk db migrate` (preview with `--dry-run`)");
+    assert_eq!(pending["checks"][2]["status"], "skip");
+
+    let _ = run_json([
+        "--db",
+        path_str(&db),
+        "memory",
+        "add",
+        "event",
+        "--summary",
+        "Clock ran ahead",
+        "--writer",
+        "tester",
+        "--justification",
+        "doctor fixture",
+        "--source-uri",
+        "file:///event.md",
+        "--truth-status",
+        "observed",
+        "--authority",
+        "authoritative",
+        "--created-at",
+        "2999-01-01T00:00:00Z",
+    ]);
+    let report = run_json(["--db", path_str(&db), "doctor"]);
+    validate_schema("doctor.response.schema.json", &report);
+    let status = |check: &str| {
+        report["checks"]
+            .as_array()
+            .and_then(|checks| checks.iter().find(|item| item["check"] == check))
+            .map(|item| item["status"].as_str().unwrap_or_default().to_string())
+            .unwrap_or_default()
+    };
+    for check in ["db_readable", "migrations", "foreign_keys", "wal_size"] {
+        assert_eq!(status(check), "ok", "{check} in {report}");
+    }
+    assert_eq!(status("orphaned_rows"), "ok");
+    assert_eq!(status("dangling_links"), "ok");
+    assert_eq!(status("clock_skew"), "warn");
+    assert_eq!(as_i64(&report, "warnings"), 1);
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-030
+#[test]
+fn db_export_and_import_stream_archives_over_stdout_and_stdin() {
+    let sandbox = unique_temp_dir("memorykernel-cli-stream");
+    let db_source = sandbox.join("source.sqlite3");
+    let db_target = sandbox.join("target.sqlite3");
+
+    let _record = run_json([
+        "--db",
+        path_str(&db_source),
+        "memory",
+        "add",
+        "decision",
+        "--summary",
+        "Decision: snapshots stream through pipes",
+        "--writer",
+        "tester",
+        "--justification",
+        "stream export fixture",
+        "--source-uri",
+        "file:///decision.md",
+        "--truth-status",
+        "observed",
+        "--authority",
+        "authoritative",
+    ]);
+    let exported = run_mk(["--db", path_str(&db_source), "db", "export", "--out", "-"]);
+    assert!(exported.status.success(), "{}", String::from_utf8_lossy(&exported.stderr));
+    assert!(exported.stdout.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]), "stdout is not zstd");
+    let summary: Value = serde_json::from_slice(&exported.stderr)
+        .unwrap_or_else(|err| panic!("stderr is not the export summary: {err}"));
+    validate_schema("db-export.response.schema.json", &summary);
+    assert_eq!(as_str(&summary, "format"), "tar-zstd");
+    assert_eq!(as_str(&summary, "out_file"), "-");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mk"))
+        .args(["--db", path_str(&db_target), "db", "import", "--in", "-", "--allow-unsigned"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|err| panic!("failed to execute mk binary: {err}"));
+    child
+        .stdin
+        .take()
+        .unwrap_or_else(|| panic!("mk stdin should be piped"))
+        .write_all(&exported.stdout)
+        .unwrap_or_else(|err| panic!("failed to pipe archive into mk: {err}"));
+    let imported = child
+        .wait_with_output()
+        .unwrap_or_else(|err| panic!("failed to wait for mk import: {err}"));
+    assert!(imported.status.success(), "{}", String::from_utf8_lossy(&imported.stderr));
+    let imported: Value = serde_json::from_slice(&imported.stdout)
+        .unwrap_or_else(|err| panic!("stdout is not the import summary: {err}"));
+    validate_schema("db-import.response.schema.json", &imported);
+    assert_eq!(imported["summary"]["imported_records"], 1);
+    assert_eq!(as_str(&imported, "in_file"), "-");
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-031
+#[test]
+fn query_ask_watch_prints_answer_changes_after_policy_edits() {
+    let sandbox = unique_temp_dir("memorykernel-cli-watch");
+    let db = sandbox.join("kernel.sqlite3");
+    let _migrated = run_json(["--db", path_str(&db), "db", "migrate"]);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mk"))
+        .args([
+            "--output",
+            "ndjson",
+            "--db",
+            path_str(&db),
+            "query",
+            "ask",
+            "--watch",
+            "--text",
+            "Am I allowed to use a USB drive?",
+            "--actor",
+            "user",
+            "--action",
+            "use",
+            "--resource",
+            "usb_drive",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap_or_else(|err| panic!("failed to execute mk binary: {err}"));
+    let stdout = child.stdout.take().unwrap_or_else(|| panic!("mk stdout should be piped"));
+    let (sender, events) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let next_event = || {
+        let line = events
+            .recv_timeout(std::time::Duration::from_secs(30))
+            .unwrap_or_else(|err| panic!("no watch event within 30s: {err}"));
+        let event: Value = serde_json::from_str(&line)
+            .unwrap_or_else(|err| panic!("watch event is not JSON: {err}\n{line}"));
+        validate_schema("query-ask-watch.response.schema.json", &event);
+        event
+    };
+
+    let initial = next_event();
+    assert_eq!(as_str(&initial, "event"), "initial");
+    assert_eq!(initial["answer"]["result"], "inconclusive");
+
+    let added = run_json([
+        "--db",
+        path_str(&db),
+        "memory",
+        "add",
+        "constraint",
+        "--actor",
+        "user",
+        "--action",
+        "use",
+        "--resource",
+        "usb_drive",
+        "--effect",
+        "deny",
+        "--writer",
+        "tester",
+        "--justification",
+        "watch fixture",
+        "--source-uri",
+        "file:///policy.md",
+        "--truth-status",
+        "asserted",
+        "--authority",
+        "authoritative",
+    ]);
+    let changed = next_event();
+    let _ = child.kill();
+    let _ = child.wait();
+    assert_eq!(as_str(&changed, "event"), "changed");
+    assert_eq!(changed["previous_answer"]["result"], "inconclusive");
+    assert_eq!(changed["answer"]["result"], "deny");
+    assert_eq!(
+        changed["added_items"][0]["memory_version_id"], added["memory_version_id"],
+        "the new constraint should be reported as an added item"
+    );
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-032
+#[test]
+fn db_integrity_check_repair_reports_and_requires_explicit_flag() {
+    let sandbox = unique_temp_dir("memorykernel-cli-repair");
+    let db = sandbox.join("kernel.sqlite3");
+    let _migrated = run_json(["--db", path_str(&db), "db", "migrate"]);
+
+    let plain = run_json(["--db", path_str(&db), "db", "integrity-check"]);
+    validate_schema("db-integrity-check.response.schema.json", &plain);
+    assert!(plain.get("repair").is_none());
+
+    let dry_run_alone = run_mk(["--db", path_str(&db), "db", "integrity-check", "--dry-run"]);
+    assert!(!dry_run_alone.status.success(), "--dry-run requires --repair");
+
+    for (extra, dry_run) in [(Some("--dry-run"), true), (None, false)] {
+        let mut args = vec!["--db", path_str(&db), "db", "integrity-check", "--repair"];
+        args.extend(extra);
+        let repaired = run_json(args);
+        validate_schema("db-integrity-check.response.schema.json", &repaired);
+        assert_eq!(repaired["repair"]["dry_run"], dry_run);
+        assert_eq!(repaired["repair"]["dangling_links"], serde_json::json!([]));
+        assert_eq!(repaired["repair"]["stale_context_packages"], serde_json::json!([]));
+        assert_eq!(repaired["repair"]["removed_rows"], 0);
+    }
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-033
+#[test]
+fn query_ask_conflict_resolution_settles_ties_and_records_choice() {
+    let sandbox = unique_temp_dir("memorykernel-cli-conflict-resolution");
+    let db = sandbox.join("kernel.sqlite3");
+    for effect in ["allow", "deny"] {
+        let _ = run_json([
+            "--db",
+            path_str(&db),
+            "memory",
+            "add",
+            "constraint",
+            "--actor",
+            "user",
+            "--action",
+            "use",
+            "--resource",
+            "usb_drive",
+            "--effect",
+            effect,
+            "--writer",
+            "tester",
+            "--justification",
+            "conflict fixture",
+            "--source-uri",
+            "file:///policy.md",
+            "--truth-status",
+            "asserted",
+            "--authority",
+            "authoritative",
+        ]);
+    }
+    let ask = |resolution: &str| {
+        run_json([
+            "--db",
+            path_str(&db),
+            "query",
+            "ask",
+            "--text",
+            "Can I use a USB drive?",
+            "--actor",
+            "user",
+            "--action",
+            "use",
+            "--resource",
+            "usb_drive",
+            "--conflict-resolution",
+            resolution,
+        ])
+    };
+
+    let inconclusive = ask("inconclusive");
+    validate_schema("context-package.response.schema.json", &inconclusive);
+    assert_eq!(inconclusive["answer"]["result"], "inconclusive");
+    assert!(inconclusive["determinism"].get("conflict_resolution").is_none());
+
+    let deny = ask("deny-overrides");
+    validate_schema("context-package.response.schema.json", &deny);
+    assert_eq!(deny["answer"]["result"], "deny");
+    assert_eq!(deny["determinism"]["conflict_resolution"], "deny_overrides");
+    assert_eq!(deny["selected_items"], inconclusive["selected_items"]);
+    assert_ne!(deny["determinism"]["snapshot_id"], inconclusive["determinism"]["snapshot_id"]);
+
+    let allow = ask("allow-overrides");
+    assert_eq!(allow["answer"]["result"], "allow");
+    assert_eq!(allow["determinism"]["conflict_resolution"], "allow_overrides");
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-034
+#[test]
+fn db_stats_summarizes_query_latency_per_mode() {
+    let sandbox = unique_temp_dir("memorykernel-cli-db-stats");
+    let db = sandbox.join("kernel.sqlite3");
+
+    let empty = run_json(["--db", path_str(&db), "db", "stats"]);
+    validate_schema("db-stats.response.schema.json", &empty);
+    assert_eq!(as_i64(&empty, "queries"), 0);
+    assert_eq!(empty["modes"], serde_json::json!([]));
+
+    let _ = run_json([
+        "--db",
+        path_str(&db),
+        "memory",
+        "add",
+        "constraint",
+        "--actor",
+        "user",
+        "--action",
+        "use",
+        "--resource",
+        "usb_drive",
+        "--effect",
+        "deny",
+        "--writer",
+        "tester",
+        "--justification",
+        "stats fixture",
+        "--source-uri",
+        "file:///policy.md",
+        "--truth-status",
+        "asserted",
+        "--authority",
+        "authoritative",
+    ]);
+    for _ in 0..2 {
+        let _ = run_json([
+            "--db",
+            path_str(&db),
+            "query",
+            "ask",
+            "--text",
+            "Can I use a USB drive?",
+            "--actor",
+            "user",
+            "--action",
+            "use",
+            "--resource",
+            "usb_drive",
+        ]);
+    }
+    let _ = run_json(["--db", path_str(&db), "query", "recall", "--text", "usb drive"]);
+
+    let stats = run_json(["--db", path_str(&db), "db", "stats"]);
+    validate_schema("db-stats.response.schema.json", &stats);
+    assert_eq!(as_i64(&stats, "queries"), 3);
+    assert_eq!(stats["modes"][0]["query_mode"], "policy");
+    assert_eq!(stats["modes"][0]["queries"], 2);
+    assert_eq!(stats["modes"][0]["mean_candidate_records"], 1);
+    assert_eq!(stats["modes"][0]["mean_selected_items"], 1);
+    assert_eq!(stats["modes"][1]["query_mode"], "recall");
+    assert_eq!(stats["modes"][1]["queries"], 1);
+
+    let future =
+        run_json(["--db", path_str(&db), "db", "stats", "--since", "2999-01-01T00:00:00Z"]);
+    assert_eq!(as_i64(&future, "queries"), 0);
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-035
+#[test]
+fn context_outcomes_follow_packages_that_selected_a_record() {
+    let sandbox = unique_temp_dir("memorykernel-cli-context-outcomes");
+    let db = sandbox.join("kernel.sqlite3");
+    let add = |record_type: &str, summary: &str| {
+        run_json([
+            "--db",
+            path_str(&db),
+            "memory",
+            "add",
+            record_type,
+            "--summary",
+            summary,
+            "--writer",
+            "tester",
+            "--justification",
+            "outcome feedback fixture",
+            "--source-uri",
+            "file:///review.md",
+            "--truth-status",
+            "observed",
+            "--authority",
+            "authoritative",
+        ])
+    };
+
+    let decision = add("decision", "Decision: require encrypted USB drives");
+    let decision_id = as_str(&decision, "memory_version_id").to_string();
+    let package = run_json([
+        "--db",
+        path_str(&db),
+        "query",
+        "recall",
+        "--text",
+        "encrypted usb drives",
+        "--record-type",
+        "decision",
+    ]);
+    let context_package_id = as_str(&package, "context_package_id").to_string();
+    let outcome = add("outcome", "Outcome: no USB data loss incidents this quarter");
+    let outcome_id = as_str(&outcome, "memory_version_id").to_string();
+
+    let link_args = |context_package_id: &str, outcome_id: &str| {
+        vec![
+            "--db".to_string(),
+            path_str(&db).to_string(),
+            "context".to_string(),
+            "link-outcome".to_string(),
+            "--context-package-id".to_string(),
+            context_package_id.to_string(),
+            "--outcome-memory-version-id".to_string(),
+            outcome_id.to_string(),
+            "--writer".to_string(),
+            "analyst".to_string(),
+            "--justification".to_string(),
+            "quarterly review".to_string(),
+        ]
+    };
+    let linked = run_json(link_args(&context_package_id, &outcome_id));
+    validate_schema("context-link-outcome.response.schema.json", &linked);
+    assert_eq!(linked["outcome_link"]["context_package_id"], context_package_id.as_str());
+    assert!(!run_mk(link_args(&context_package_id, &outcome_id)).status.success());
+    assert!(!run_mk(link_args(&context_package_id, &decision_id)).status.success());
+
+    let followed = run_json([
+        "--db",
+        path_str(&db),
+        "context",
+        "outcomes",
+        "--memory-version-id",
+        &decision_id,
+    ]);
+    validate_schema("context-outcomes.response.schema.json", &followed);
+    assert_eq!(followed["outcomes"][0]["link"]["outcome_memory_version_id"], outcome_id.as_str());
+    assert_eq!(followed["outcomes"][0]["outcome"]["memory_version_id"], outcome_id.as_str());
+    assert_eq!(followed["outcomes"].as_array().map(Vec::len), Some(1));
+
+    let unrelated = run_json([
+        "--db",
+        path_str(&db),
+        "context",
+        "outcomes",
+        "--memory-version-id",
+        &outcome_id,
+    ]);
+    assert_eq!(unrelated["outcomes"], serde_json::json!([]));
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-036
+#[allow(clippy::too_many_lines)]
+#[test]
+fn db_diff_reports_added_removed_and_changed_rows_between_snapshots() {
+    let sandbox = unique_temp_dir("memorykernel-cli-db-diff");
+    let db = sandbox.join("kernel.sqlite3");
+    let add = |summary: &str| {
+        run_json([
+            "--db",
+            path_str(&db),
+            "memory",
+            "add",
+            "decision",
+            "--summary",
+            summary,
+            "--writer",
+            "tester",
+            "--justification",
+            "snapshot diff fixture",
+            "--source-uri",
+            "file:///decisions.md",
+            "--truth-status",
+            "observed",
+            "--authority",
+            "authoritative",
+        ])
+    };
+    let export = |out: &Path| {
+        let _ = run_json(["--db", path_str(&db), "db", "export", "--out", path_str(out)]);
+    };
+
+    let redacted = add("Decision: laptops ship with full-disk encryption");
+    let redacted_id = as_str(&redacted, "memory_version_id").to_string();
+    let _ = add("Decision: USB drives require approval");
+    let snapshot_a = sandbox.join("snapshot-a");
+    export(&snapshot_a);
+
+    let _ = run_json([
+        "--db",
+        path_str(&db),
+        "memory",
+        "redact",
+        "--memory-version-id",
+        &redacted_id,
+        "--writer",
+        "privacy",
+        "--justification",
+        "erasure request",
+    ]);
+    let added = add("Decision: printers require badge release");
+    let added_id = as_str(&added, "memory_version_id").to_string();
+    let package = run_json(["--db", path_str(&db), "query", "recall", "--text", "usb drives"]);
+    let snapshot_b = sandbox.join("snapshot-b");
+    export(&snapshot_b);
+
+    let unused_db = sandbox.join("unused.sqlite3");
+    let diff_args = |a: &Path, b: &Path| {
+        vec![
+            "--db".to_string(),
+            path_str(&unused_db).to_string(),
+            "db".to_string(),
+            "diff".to_string(),
+            "--a".to_string(),
+            path_str(a).to_string(),
+            "--b".to_string(),
+            path_str(b).to_string(),
+        ]
+    };
+    let forward = run_json(diff_args(&snapshot_a, &snapshot_b));
+    validate_schema("db-diff.response.schema.json", &forward);
+    assert_eq!(forward["identical"], false);
+    assert_eq!(
+        forward["records"],
+        serde_json::json!({"added": 1, "removed": 0, "changed": 1, "unchanged": 1})
+    );
+    assert_eq!(
+        forward["context_packages"],
+        serde_json::json!({"added": 1, "removed": 0, "changed": 0, "unchanged": 0})
+    );
+    let changes = forward["changes"]
+        .as_array()
+        .unwrap_or_else(|| panic!("changes should be an array: {forward}"));
+    let change_of = |id: &str| {
+        changes
+            .iter()
+            .find(|change| change["id"] == id)
+            .unwrap_or_else(|| panic!("missing change for {id}: {forward}"))
+    };
+    assert_eq!(change_of(&redacted_id)["change"], "changed");
+    assert_ne!(change_of(&redacted_id)["a_sha256"], change_of(&redacted_id)["b_sha256"]);
+    assert_eq!(change_of(&added_id)["change"], "added");
+    assert_eq!(change_of(&added_id)["a_sha256"], Value::Null);
+    assert_eq!(change_of(as_str(&package, "context_package_id"))["kind"], "context_package");
+    assert!(as_str(&forward, "summary").starts_with("records: 1 added, 0 removed, 1 changed"));
+    assert!(!unused_db.exists(), "db diff must not create the --db store");
+
+    let backward = run_json(diff_args(&snapshot_b, &snapshot_a));
+    assert_eq!(backward["records"]["removed"], 1);
+    assert_eq!(backward["context_packages"]["removed"], 1);
+
+    let same = run_json(diff_args(&snapshot_a, &snapshot_a));
+    assert_eq!(same["identical"], true);
+    assert_eq!(same["changes"], serde_json::json!([]));
+
+    let mut table_args = vec!["--output".to_string(), "table".to_string()];
+    table_args.extend(diff_args(&snapshot_a, &snapshot_b));
+    let table = run_mk(table_args);
+    assert!(table.status.success());
+    let stdout = String::from_utf8_lossy(&table.stdout);
+    assert!(stdout.contains("summary: records: 1 added"), "unexpected table: {stdout}");
+
+    assert!(!run_mk(diff_args(&snapshot_a, &sandbox.join("missing"))).status.success());
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
+
+// Test IDs: TCLI-037
+#[test]
+fn db_import_enforces_configurable_snapshot_limits() {
+    let sandbox = unique_temp_dir("memorykernel-cli-import-limits");
+    let source = sandbox.join("source.sqlite3");
+    let export_dir = sandbox.join("snapshot");
+    let _ = run_json([
+        "--db",
+        path_str(&source),
+        "memory",
+        "add",
+        "decision",
+        "--summary",
+        "Decision: snapshots are size-bounded",
+        "--writer",
+        "tester",
+        "--justification",
+        "import limits fixture",
+        "--source-uri",
+        "file:///limits.md",
+        "--truth-status",
+        "observed",
+        "--authority",
+        "authoritative",
+    ]);
+    let _ = run_json(["--db", path_str(&source), "db", "export", "--out", path_str(&export_dir)]);
+
+    let import = |target: &Path, limit: &[&str]| {
+        let mut args = vec![
+            "--db",
+            path_str(target),
+            "db",
+            "import",
+            "--in",
+            path_str(&export_dir),
+            "--allow-unsigned",
+        ];
+        args.extend_from_slice(limit);
+        run_mk(args)
+    };
+    for (flag, value, message) in [
+        ("--max-line-bytes", "32", "exceeds the 32 byte"),
+        ("--max-rows", "0", "row limit"),
+        ("--max-depth", "1", "nests deeper than 1 levels"),
+    ] {
+        let target = sandbox.join(format!("target{flag}.sqlite3"));
+        let output = import(&target, &[flag, value]);
+        assert!(!output.status.success(), "{flag} {value} should reject the snapshot");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(message), "unexpected stderr for {flag}: {stderr}");
+    }
+
+    let output = import(&sandbox.join("target.sqlite3"), &[]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let _ = fs::remove_dir_all(&sandbox);
+}
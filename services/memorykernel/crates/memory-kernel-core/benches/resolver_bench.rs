@@ -26,6 +26,7 @@ fn mk_constraint(index: usize) -> MemoryRecord {
         },
         supersedes: Vec::new(),
         contradicts: Vec::new(),
+        tags: Vec::new(),
         payload: MemoryPayload::Constraint(ConstraintPayload {
             scope: ConstraintScope {
                 actor: "user".to_string(),
@@ -34,6 +35,7 @@ fn mk_constraint(index: usize) -> MemoryRecord {
             },
             effect,
             note: Some("policy benchmark fixture".to_string()),
+            expires_at: None,
         }),
     }
 }
@@ -72,6 +74,7 @@ fn mk_summary(index: usize) -> MemoryRecord {
         },
         supersedes: Vec::new(),
         contradicts: Vec::new(),
+        tags: Vec::new(),
         payload,
     }
 }
@@ -13,6 +13,38 @@ pub enum KernelError {
     Query(String),
 }
 
+/// Default minimum cosine similarity for recalling a record with no lexical overlap.
+pub const DEFAULT_MIN_SEMANTIC_SIMILARITY: f32 = 0.5;
+/// Weight of the normalized lexical score in the combined semantic recall score.
+pub const SEMANTIC_RECALL_LEXICAL_WEIGHT: f32 = 0.4;
+/// Weight of the cosine similarity in the combined semantic recall score.
+pub const SEMANTIC_RECALL_VECTOR_WEIGHT: f32 = 0.6;
+
+/// Pluggable text embedding model for semantic recall.
+///
+/// Implementations MUST be deterministic: the same model and text always yield the same vector.
+pub trait Embedder {
+    /// Stable model identifier; stored vectors are keyed by it.
+    fn model(&self) -> &str;
+
+    /// Embed `text` as a dense vector.
+    ///
+    /// # Errors
+    /// Returns [`KernelError::Query`] when the text cannot be embedded.
+    fn embed(&self, text: &str) -> Result<Vec<f32>, KernelError>;
+}
+
+/// Precomputed vectors for one semantic recall query.
+#[derive(Debug, Clone, Copy)]
+pub struct SemanticRecall<'a> {
+    pub model: &'a str,
+    pub query_vector: &'a [f32],
+    /// Record vectors by version; records without one are matched lexically only.
+    pub record_vectors: &'a std::collections::BTreeMap<MemoryVersionId, Vec<f32>>,
+    /// Records with no lexical overlap are recalled only at or above this similarity.
+    pub min_similarity: f32,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct MemoryId(pub Ulid);
 
@@ -65,6 +97,9 @@ pub enum RecordType {
     Preference,
     Event,
     Outcome,
+    Task,
+    Risk,
+    Assumption,
 }
 
 impl RecordType {
@@ -76,6 +111,9 @@ impl RecordType {
             Self::Preference => "preference",
             Self::Event => "event",
             Self::Outcome => "outcome",
+            Self::Task => "task",
+            Self::Risk => "risk",
+            Self::Assumption => "assumption",
         }
     }
 
@@ -87,6 +125,9 @@ impl RecordType {
             "preference" => Some(Self::Preference),
             "event" => Some(Self::Event),
             "outcome" => Some(Self::Outcome),
+            "task" => Some(Self::Task),
+            "risk" => Some(Self::Risk),
+            "assumption" => Some(Self::Assumption),
             _ => None,
         }
     }
@@ -219,6 +260,99 @@ impl ConstraintEffect {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Open,
+    InProgress,
+    Done,
+    Cancelled,
+}
+
+impl TaskStatus {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::InProgress => "in_progress",
+            Self::Done => "done",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(Self::Open),
+            "in_progress" => Some(Self::InProgress),
+            "done" => Some(Self::Done),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl RiskSeverity {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Critical => "critical",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "low" => Some(Self::Low),
+            "medium" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            "critical" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AssumptionStatus {
+    Unverified,
+    Validated,
+    Invalidated,
+}
+
+impl AssumptionStatus {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unverified => "unverified",
+            Self::Validated => "validated",
+            Self::Invalidated => "invalidated",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "unverified" => Some(Self::Unverified),
+            "validated" => Some(Self::Validated),
+            "invalidated" => Some(Self::Invalidated),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub struct Provenance {
     pub source_uri: String,
@@ -239,6 +373,13 @@ pub struct ConstraintPayload {
     pub scope: ConstraintScope,
     pub effect: ConstraintEffect,
     pub note: Option<String>,
+    /// Instant at which the constraint stops applying; absent means it never expires.
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expires_at: Option<OffsetDateTime>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
@@ -261,6 +402,24 @@ pub struct OutcomePayload {
     pub summary: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TaskPayload {
+    pub summary: String,
+    pub status: TaskStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct RiskPayload {
+    pub summary: String,
+    pub severity: RiskSeverity,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct AssumptionPayload {
+    pub summary: String,
+    pub status: AssumptionStatus,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 #[serde(tag = "record_type", content = "payload", rename_all = "snake_case")]
 pub enum MemoryPayload {
@@ -269,6 +428,9 @@ pub enum MemoryPayload {
     Preference(PreferencePayload),
     Event(EventPayload),
     Outcome(OutcomePayload),
+    Task(TaskPayload),
+    Risk(RiskPayload),
+    Assumption(AssumptionPayload),
 }
 
 impl MemoryPayload {
@@ -280,6 +442,24 @@ impl MemoryPayload {
             Self::Preference(_) => RecordType::Preference,
             Self::Event(_) => RecordType::Event,
             Self::Outcome(_) => RecordType::Outcome,
+            Self::Task(_) => RecordType::Task,
+            Self::Risk(_) => RecordType::Risk,
+            Self::Assumption(_) => RecordType::Assumption,
+        }
+    }
+
+    /// Free-text summary of every non-constraint payload.
+    #[must_use]
+    pub fn summary(&self) -> Option<&str> {
+        match self {
+            Self::Constraint(_) => None,
+            Self::Decision(payload) => Some(&payload.summary),
+            Self::Preference(payload) => Some(&payload.summary),
+            Self::Event(payload) => Some(&payload.summary),
+            Self::Outcome(payload) => Some(&payload.summary),
+            Self::Task(payload) => Some(&payload.summary),
+            Self::Risk(payload) => Some(&payload.summary),
+            Self::Assumption(payload) => Some(&payload.summary),
         }
     }
 }
@@ -303,6 +483,9 @@ pub struct MemoryRecord {
     pub supersedes: Vec<MemoryVersionId>,
     #[serde(default)]
     pub contradicts: Vec<MemoryVersionId>,
+    /// Free-form labels, such as a team or project, for organizing and filtering records.
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub payload: MemoryPayload,
 }
 
@@ -311,7 +494,7 @@ impl MemoryRecord {
     ///
     /// # Errors
     /// Returns [`KernelError::Validation`] when required identity, accountability,
-    /// provenance, confidence, or payload constraints are violated.
+    /// provenance, confidence, tag, or payload constraints are violated.
     pub fn validate(&self) -> Result<(), KernelError> {
         if self.version == 0 {
             return Err(KernelError::Validation(
@@ -359,6 +542,18 @@ impl MemoryRecord {
             ));
         }
 
+        let mut seen_tags = std::collections::BTreeSet::new();
+        for tag in &self.tags {
+            if tag.trim().is_empty() || tag.trim() != tag {
+                return Err(KernelError::Validation(
+                    "tags MUST be non-empty without surrounding whitespace".to_string(),
+                ));
+            }
+            if !seen_tags.insert(tag.as_str()) {
+                return Err(KernelError::Validation(format!("tags MUST be unique: {tag}")));
+            }
+        }
+
         if self.payload.record_type() == RecordType::Constraint {
             let MemoryPayload::Constraint(constraint) = &self.payload else {
                 return Err(KernelError::Validation("constraint payload mismatch".to_string()));
@@ -372,6 +567,11 @@ impl MemoryRecord {
                     ));
                 }
             }
+            if constraint.expires_at.is_some_and(|expires_at| expires_at <= self.effective_at) {
+                return Err(KernelError::Validation(
+                    "constraint expires_at MUST be after effective_at".to_string(),
+                ));
+            }
         }
 
         Ok(())
@@ -396,6 +596,17 @@ pub enum AnswerResult {
     Inconclusive,
 }
 
+impl AnswerResult {
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+            Self::Inconclusive => "inconclusive",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RuleScores {
     pub scope_match: f32,
@@ -429,6 +640,9 @@ pub struct DeterminismMetadata {
     pub ruleset_version: String,
     pub snapshot_id: String,
     pub tie_breakers: Vec<String>,
+    /// How an allow/deny conflict was settled; omitted for the default `inconclusive`.
+    #[serde(default, skip_serializing_if = "ConflictResolution::is_default")]
+    pub conflict_resolution: ConflictResolution,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -450,40 +664,410 @@ pub struct ContextPackage {
     pub ordering_trace: Vec<String>,
 }
 
+/// Optional confidence half-lives in days, keyed by truth status.
+///
+/// Ordering confidence is multiplied by `0.5^(age_days / half_life_days)`, where age runs from
+/// the record's `effective_at` to the query `as_of`; statuses without a half-life do not decay.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ConfidenceDecay {
+    pub half_life_days: std::collections::BTreeMap<TruthStatus, f32>,
+}
+
+impl ConfidenceDecay {
+    #[must_use]
+    pub fn with_half_life(mut self, truth_status: TruthStatus, days: f32) -> Self {
+        self.half_life_days.insert(truth_status, days);
+        self
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.half_life_days.is_empty()
+    }
+
+    /// Validate that every half-life is finite and positive.
+    ///
+    /// # Errors
+    /// Returns [`KernelError::Validation`] for a non-finite or non-positive half-life.
+    pub fn validate(&self) -> Result<(), KernelError> {
+        for (truth_status, days) in &self.half_life_days {
+            if !days.is_finite() || *days <= 0.0 {
+                return Err(KernelError::Validation(format!(
+                    "half-life for truth_status={} MUST be a positive number of days",
+                    truth_status.as_str()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Confidence used for ordering `record` at `as_of`, rounded to 1e-6.
+    #[must_use]
+    pub fn apply(&self, record: &MemoryRecord, as_of: OffsetDateTime) -> f32 {
+        let confidence = record.confidence.unwrap_or(0.5);
+        let Some(half_life_days) = self.half_life_days.get(&record.truth_status) else {
+            return confidence;
+        };
+        let age_days = ((as_of - record.effective_at).as_seconds_f32() / 86_400.0).max(0.0);
+        round_score(confidence * 0.5_f32.powf(age_days / half_life_days))
+    }
+
+    /// Snapshot-id extra identifying the configured half-lives, so decayed packages replay
+    /// distinctly from undecayed ones.
+    #[must_use]
+    pub fn snapshot_extra(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        let half_lives = self
+            .half_life_days
+            .iter()
+            .map(|(truth_status, days)| format!("{}:{days}", truth_status.as_str()))
+            .collect::<Vec<_>>();
+        Some(format!("confidence_decay={}", half_lives.join(",")))
+    }
+
+    /// Trace line describing the configured half-lives, if any.
+    fn ordering_trace(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        let half_lives = self
+            .half_life_days
+            .iter()
+            .map(|(truth_status, days)| format!("{}={days}d", truth_status.as_str()))
+            .collect::<Vec<_>>();
+        Some(format!("score: confidence half-life decay ({})", half_lives.join(", ")))
+    }
+}
+
+fn ordering_confidence(
+    record: &MemoryRecord,
+    as_of: OffsetDateTime,
+    decay: Option<&ConfidenceDecay>,
+) -> f32 {
+    decay.map_or(record.confidence.unwrap_or(0.5), |decay| decay.apply(record, as_of))
+}
+
+/// Why-reason noting decayed confidence, when ordering confidence differs from the record's.
+fn decay_reason(record: &MemoryRecord, confidence: f32) -> Option<String> {
+    let base = record.confidence.unwrap_or(0.5);
+    (base.to_bits() != confidence.to_bits()).then(|| {
+        format!(
+            "confidence decayed from {base:.6} to {confidence:.6} for truth_status={}",
+            record.truth_status.as_str()
+        )
+    })
+}
+
+/// Active constraint matched by a policy query, as ranked by a [`RulesetStrategy`].
 #[derive(Debug, Clone)]
-struct PolicyCandidate<'a> {
-    record: &'a MemoryRecord,
-    scope_score: u8,
-    confidence: f32,
+pub struct PolicyCandidate<'a> {
+    pub record: &'a MemoryRecord,
+    /// Number of scope fields matched exactly (0-3).
+    pub scope_score: u8,
+    /// Depth of matched `prefix/*` scope globs; deeper is more specific.
+    pub scope_glob_depth: usize,
+    /// Record confidence with `None` treated as `0.5`, after any [`ConfidenceDecay`].
+    pub confidence: f32,
 }
 
-impl PolicyCandidate<'_> {
-    fn cmp(lhs: &Self, rhs: &Self) -> Ordering {
-        rhs.scope_score
-            .cmp(&lhs.scope_score)
-            .then_with(|| rhs.record.authority.rank().cmp(&lhs.record.authority.rank()))
-            .then_with(|| rhs.record.truth_status.rank().cmp(&lhs.record.truth_status.rank()))
-            .then_with(|| rhs.confidence.partial_cmp(&lhs.confidence).unwrap_or(Ordering::Equal))
-            .then_with(|| rhs.record.effective_at.cmp(&lhs.record.effective_at))
+/// Precedence rules that order policy candidates and derive the answer from them.
+///
+/// The ruleset version is recorded in [`DeterminismMetadata::ruleset_version`] so a context
+/// package can be replayed under the same rules.
+pub trait RulesetStrategy {
+    /// Identifier recorded as `ruleset_version`, e.g. `ordering.v1`.
+    fn version(&self) -> &str;
+    /// Ordering tuple recorded as `tie_breakers`.
+    fn tie_breakers(&self) -> Vec<String>;
+    /// Total order over candidates; `Ordering::Less` ranks `lhs` first.
+    fn compare(&self, lhs: &PolicyCandidate<'_>, rhs: &PolicyCandidate<'_>) -> Ordering;
+    /// Derive the answer from selected items in rank order.
+    fn derive_answer(&self, selected: &[ContextItem], records: &[MemoryRecord]) -> Answer;
+    /// Derive the answer, settling a conflict between allow and deny constraints the ruleset
+    /// cannot order with `resolution`. Rulesets that never conflict keep this default.
+    fn derive_resolved_answer(
+        &self,
+        selected: &[ContextItem],
+        records: &[MemoryRecord],
+        _resolution: ConflictResolution,
+    ) -> Answer {
+        self.derive_answer(selected, records)
+    }
+}
+
+/// Default rules: most specific scope, then authority and truth, wins; conflicting
+/// top-precedence constraints are inconclusive.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrecedenceRuleset;
+
+impl RulesetStrategy for PrecedenceRuleset {
+    fn version(&self) -> &'static str {
+        "ordering.v1"
+    }
+
+    fn tie_breakers(&self) -> Vec<String> {
+        default_tie_breakers()
+    }
+
+    fn compare(&self, lhs: &PolicyCandidate<'_>, rhs: &PolicyCandidate<'_>) -> Ordering {
+        precedence_cmp(lhs, rhs)
+    }
+
+    fn derive_answer(&self, selected: &[ContextItem], records: &[MemoryRecord]) -> Answer {
+        derive_answer(selected, records, ConflictResolution::Inconclusive)
+    }
+
+    fn derive_resolved_answer(
+        &self,
+        selected: &[ContextItem],
+        records: &[MemoryRecord],
+        resolution: ConflictResolution,
+    ) -> Answer {
+        derive_answer(selected, records, resolution)
+    }
+}
+
+/// Precedence ordering, but any active matching deny constraint decides the answer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DenyOverridesRuleset;
+
+impl RulesetStrategy for DenyOverridesRuleset {
+    fn version(&self) -> &'static str {
+        "deny-overrides.v1"
+    }
+
+    fn tie_breakers(&self) -> Vec<String> {
+        default_tie_breakers()
+    }
+
+    fn compare(&self, lhs: &PolicyCandidate<'_>, rhs: &PolicyCandidate<'_>) -> Ordering {
+        precedence_cmp(lhs, rhs)
+    }
+
+    fn derive_answer(&self, selected: &[ContextItem], records: &[MemoryRecord]) -> Answer {
+        let effects = selected
+            .iter()
+            .filter_map(|item| constraint_effect_by_version_id(records, item.memory_version_id))
+            .collect::<Vec<_>>();
+        if effects.contains(&ConstraintEffect::Deny) {
+            Answer {
+                result: AnswerResult::Deny,
+                why: "An active matching constraint denies the action (deny overrides)".to_string(),
+            }
+        } else if effects.contains(&ConstraintEffect::Allow) {
+            Answer {
+                result: AnswerResult::Allow,
+                why: "Active matching constraints allow the action and none deny it".to_string(),
+            }
+        } else {
+            Answer {
+                result: AnswerResult::Inconclusive,
+                why: "No active matching constraints were found".to_string(),
+            }
+        }
+    }
+}
+
+/// The most recently effective constraint decides; constraints sharing the latest
+/// `effective_at` with conflicting effects are inconclusive.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MostRecentWinsRuleset;
+
+impl RulesetStrategy for MostRecentWinsRuleset {
+    fn version(&self) -> &'static str {
+        "most-recent-wins.v1"
+    }
+
+    fn tie_breakers(&self) -> Vec<String> {
+        vec![
+            "effective_at desc".to_string(),
+            "created_at desc".to_string(),
+            "scope_specificity desc".to_string(),
+            "scope_glob_depth desc".to_string(),
+            "authority_rank desc".to_string(),
+            "truth_status_rank desc".to_string(),
+            "confidence desc".to_string(),
+            "memory_id asc".to_string(),
+            "memory_version_id asc".to_string(),
+        ]
+    }
+
+    fn compare(&self, lhs: &PolicyCandidate<'_>, rhs: &PolicyCandidate<'_>) -> Ordering {
+        rhs.record
+            .effective_at
+            .cmp(&lhs.record.effective_at)
             .then_with(|| rhs.record.created_at.cmp(&lhs.record.created_at))
-            .then_with(|| lhs.record.memory_id.cmp(&rhs.record.memory_id))
-            .then_with(|| lhs.record.memory_version_id.cmp(&rhs.record.memory_version_id))
+            .then_with(|| precedence_cmp(lhs, rhs))
+    }
+
+    fn derive_answer(&self, selected: &[ContextItem], records: &[MemoryRecord]) -> Answer {
+        self.derive_resolved_answer(selected, records, ConflictResolution::Inconclusive)
+    }
+
+    fn derive_resolved_answer(
+        &self,
+        selected: &[ContextItem],
+        records: &[MemoryRecord],
+        resolution: ConflictResolution,
+    ) -> Answer {
+        let latest = selected.iter().filter_map(|item| {
+            records.iter().find(|record| record.memory_version_id == item.memory_version_id)
+        });
+        let mut latest_effective_at = None;
+        let mut effects = Vec::new();
+        for record in latest {
+            if latest_effective_at.is_some_and(|effective_at| record.effective_at != effective_at) {
+                break;
+            }
+            latest_effective_at = Some(record.effective_at);
+            if let MemoryPayload::Constraint(constraint) = &record.payload {
+                effects.push(constraint.effect);
+            }
+        }
+
+        match (
+            effects.contains(&ConstraintEffect::Allow),
+            effects.contains(&ConstraintEffect::Deny),
+        ) {
+            (true, true) => resolution.settle("Most recent constraints conflict (allow and deny)"),
+            (true, false) => Answer {
+                result: AnswerResult::Allow,
+                why: "Most recently effective constraint allows the action".to_string(),
+            },
+            (false, true) => Answer {
+                result: AnswerResult::Deny,
+                why: "Most recently effective constraint denies the action".to_string(),
+            },
+            (false, false) => Answer {
+                result: AnswerResult::Inconclusive,
+                why: "No active matching constraints were found".to_string(),
+            },
+        }
+    }
+}
+
+/// Built-in policy rulesets selectable per query.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyRuleset {
+    #[default]
+    Precedence,
+    DenyOverrides,
+    MostRecentWins,
+}
+
+impl PolicyRuleset {
+    #[must_use]
+    pub fn strategy(self) -> &'static dyn RulesetStrategy {
+        match self {
+            Self::Precedence => &PrecedenceRuleset,
+            Self::DenyOverrides => &DenyOverridesRuleset,
+            Self::MostRecentWins => &MostRecentWinsRuleset,
+        }
+    }
+
+    /// Resolve the built-in ruleset recorded as `ruleset_version`, for replay.
+    #[must_use]
+    pub fn from_version(version: &str) -> Option<Self> {
+        [Self::Precedence, Self::DenyOverrides, Self::MostRecentWins]
+            .into_iter()
+            .find(|ruleset| ruleset.strategy().version() == version)
+    }
+}
+
+/// How a policy ruleset answers when allow and deny constraints it cannot order conflict.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    /// Report the conflict as `inconclusive` (the historical behavior).
+    #[default]
+    Inconclusive,
+    /// Answer `deny`.
+    DenyOverrides,
+    /// Answer `allow`.
+    AllowOverrides,
+}
+
+impl ConflictResolution {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Inconclusive => "inconclusive",
+            Self::DenyOverrides => "deny_overrides",
+            Self::AllowOverrides => "allow_overrides",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        [Self::Inconclusive, Self::DenyOverrides, Self::AllowOverrides]
+            .into_iter()
+            .find(|resolution| resolution.as_str() == value)
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Snapshot extra for a non-default resolution, so packages resolved differently get
+    /// different snapshot ids.
+    #[must_use]
+    pub fn snapshot_extra(self) -> Option<String> {
+        (!self.is_default()).then(|| format!("conflict_resolution={}", self.as_str()))
+    }
+
+    /// The answer for a conflict described by `conflict`.
+    fn settle(self, conflict: &str) -> Answer {
+        match self {
+            Self::Inconclusive => {
+                Answer { result: AnswerResult::Inconclusive, why: conflict.to_string() }
+            }
+            Self::DenyOverrides => Answer {
+                result: AnswerResult::Deny,
+                why: format!("{conflict}; conflict resolution deny_overrides denies"),
+            },
+            Self::AllowOverrides => Answer {
+                result: AnswerResult::Allow,
+                why: format!("{conflict}; conflict resolution allow_overrides allows"),
+            },
+        }
     }
 }
 
+fn precedence_cmp(lhs: &PolicyCandidate<'_>, rhs: &PolicyCandidate<'_>) -> Ordering {
+    rhs.scope_score
+        .cmp(&lhs.scope_score)
+        .then_with(|| rhs.scope_glob_depth.cmp(&lhs.scope_glob_depth))
+        .then_with(|| rhs.record.authority.rank().cmp(&lhs.record.authority.rank()))
+        .then_with(|| rhs.record.truth_status.rank().cmp(&lhs.record.truth_status.rank()))
+        .then_with(|| rhs.confidence.partial_cmp(&lhs.confidence).unwrap_or(Ordering::Equal))
+        .then_with(|| rhs.record.effective_at.cmp(&lhs.record.effective_at))
+        .then_with(|| rhs.record.created_at.cmp(&lhs.record.created_at))
+        .then_with(|| lhs.record.memory_id.cmp(&rhs.record.memory_id))
+        .then_with(|| lhs.record.memory_version_id.cmp(&rhs.record.memory_version_id))
+}
+
 #[derive(Debug, Clone)]
 struct RecallCandidate<'a> {
     record: &'a MemoryRecord,
     matched_terms: usize,
     total_terms: usize,
     lexical_score: f32,
+    /// Cosine similarity to the query vector; `None` in lexical mode or without a stored vector.
+    similarity: Option<f32>,
+    /// Combined lexical+vector score rounded to 1e-6; always zero in lexical mode.
+    combined_score: f32,
     confidence: f32,
 }
 
 impl RecallCandidate<'_> {
     fn cmp(lhs: &Self, rhs: &Self) -> Ordering {
-        rhs.matched_terms
-            .cmp(&lhs.matched_terms)
+        rhs.combined_score
+            .total_cmp(&lhs.combined_score)
+            .then_with(|| rhs.matched_terms.cmp(&lhs.matched_terms))
             .then_with(|| rhs.record.authority.rank().cmp(&lhs.record.authority.rank()))
             .then_with(|| rhs.record.truth_status.rank().cmp(&lhs.record.truth_status.rank()))
             .then_with(|| rhs.confidence.partial_cmp(&lhs.confidence).unwrap_or(Ordering::Equal))
@@ -498,6 +1082,7 @@ impl RecallCandidate<'_> {
 pub fn default_tie_breakers() -> Vec<String> {
     vec![
         "scope_specificity desc".to_string(),
+        "scope_glob_depth desc".to_string(),
         "authority_rank desc".to_string(),
         "truth_status_rank desc".to_string(),
         "confidence desc".to_string(),
@@ -522,22 +1107,44 @@ pub fn default_recall_tie_breakers() -> Vec<String> {
     ]
 }
 
+#[must_use]
+pub fn default_semantic_recall_tie_breakers() -> Vec<String> {
+    let mut tie_breakers = vec!["combined_score desc".to_string()];
+    tie_breakers.extend(default_recall_tie_breakers());
+    tie_breakers
+}
+
 #[must_use]
 pub fn default_recall_record_types() -> Vec<RecordType> {
-    vec![RecordType::Decision, RecordType::Preference, RecordType::Event, RecordType::Outcome]
+    vec![
+        RecordType::Decision,
+        RecordType::Preference,
+        RecordType::Event,
+        RecordType::Outcome,
+        RecordType::Task,
+        RecordType::Risk,
+        RecordType::Assumption,
+    ]
 }
 
-fn scope_specificity(scope: &ConstraintScope, query: &QueryRequest) -> Option<u8> {
+/// How closely a constraint scope matched a query: exact fields first, then glob prefix depth.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+struct ScopeSpecificity {
+    score: u8,
+    glob_depth: usize,
+}
+
+fn scope_specificity(scope: &ConstraintScope, query: &QueryRequest) -> Option<ScopeSpecificity> {
     let fields = [
         (&scope.actor, &query.actor),
         (&scope.action, &query.action),
         (&scope.resource, &query.resource),
     ];
 
-    let mut specificity_score = 0_u8;
+    let mut specificity = ScopeSpecificity::default();
     for (field, query_value) in fields {
         if field == query_value {
-            specificity_score += 1;
+            specificity.score += 1;
             continue;
         }
 
@@ -545,13 +1152,34 @@ fn scope_specificity(scope: &ConstraintScope, query: &QueryRequest) -> Option<u8
             continue;
         }
 
+        if let Some(depth) = glob_prefix_depth(field, query_value) {
+            specificity.glob_depth += depth;
+            continue;
+        }
+
         return None;
     }
 
-    Some(specificity_score)
+    Some(specificity)
+}
+
+/// Segment depth of a hierarchical `prefix/*` pattern (e.g. `storage/usb/*`, `role:contractor/*`)
+/// when `value` lies strictly beneath `prefix`; any other pattern only matches exactly.
+fn glob_prefix_depth(pattern: &str, value: &str) -> Option<usize> {
+    let prefix = pattern.strip_suffix("/*")?;
+    if prefix.is_empty() || prefix.contains('*') {
+        return None;
+    }
+    let rest = value.strip_prefix(prefix)?.strip_prefix('/')?;
+    if rest.is_empty() {
+        return None;
+    }
+    Some(prefix.split('/').count())
 }
 
-fn collect_superseded_ids(records: &[MemoryRecord]) -> std::collections::BTreeSet<MemoryVersionId> {
+fn collect_superseded_ids<'a>(
+    records: impl IntoIterator<Item = &'a MemoryRecord>,
+) -> std::collections::BTreeSet<MemoryVersionId> {
     let mut superseded_ids = std::collections::BTreeSet::new();
     for record in records {
         for superseded in &record.supersedes {
@@ -579,6 +1207,7 @@ fn collect_policy_candidates_and_exclusions<'a>(
     records: &'a [MemoryRecord],
     query: &QueryRequest,
     superseded_ids: &std::collections::BTreeSet<MemoryVersionId>,
+    decay: Option<&ConfidenceDecay>,
 ) -> (Vec<PolicyCandidate<'a>>, Vec<ContextItem>) {
     let mut candidates: Vec<PolicyCandidate<'a>> = Vec::new();
     let mut excluded: Vec<ContextItem> = Vec::new();
@@ -588,24 +1217,35 @@ fn collect_policy_candidates_and_exclusions<'a>(
             continue;
         };
 
-        let Some(scope_score) = scope_specificity(&constraint.scope, query) else {
+        let Some(specificity) = scope_specificity(&constraint.scope, query) else {
             continue;
         };
 
+        if record.effective_at > query.as_of {
+            excluded.push(excluded_item(record, "not yet effective as of query time"));
+            continue;
+        }
+
         if record.truth_status == TruthStatus::Retracted {
             excluded.push(excluded_item(record, "truth_status is retracted"));
             continue;
         }
 
-        if superseded_ids.contains(&record.memory_version_id) {
-            excluded.push(excluded_item(record, "record is superseded by a newer linked record"));
+        if constraint.expires_at.is_some_and(|expires_at| expires_at <= query.as_of) {
+            excluded.push(excluded_item(record, "constraint expired at or before as_of"));
+            continue;
+        }
+
+        if superseded_ids.contains(&record.memory_version_id) {
+            excluded.push(excluded_item(record, "record is superseded by a newer linked record"));
             continue;
         }
 
         candidates.push(PolicyCandidate {
             record,
-            scope_score,
-            confidence: record.confidence.unwrap_or(0.5),
+            scope_score: specificity.score,
+            scope_glob_depth: specificity.glob_depth,
+            confidence: ordering_confidence(record, query.as_of, decay),
         });
     }
 
@@ -613,6 +1253,16 @@ fn collect_policy_candidates_and_exclusions<'a>(
 }
 
 fn selected_policy_item(index: usize, candidate: &PolicyCandidate<'_>) -> ContextItem {
+    let mut reasons = vec![format!(
+        "scope specificity score={} for actor/action/resource",
+        candidate.scope_score
+    )];
+    if candidate.scope_glob_depth > 0 {
+        reasons.push(format!("scope glob prefix depth={}", candidate.scope_glob_depth));
+    }
+    reasons.push("passed active filters (not retracted, not superseded)".to_string());
+    reasons.extend(decay_reason(candidate.record, candidate.confidence));
+
     ContextItem {
         rank: index + 1,
         memory_version_id: candidate.record.memory_version_id,
@@ -624,13 +1274,7 @@ fn selected_policy_item(index: usize, candidate: &PolicyCandidate<'_>) -> Contex
         authority: candidate.record.authority,
         why: Why {
             included: true,
-            reasons: vec![
-                format!(
-                    "scope specificity score={} for actor/action/resource",
-                    candidate.scope_score
-                ),
-                "passed active filters (not retracted, not superseded)".to_string(),
-            ],
+            reasons,
             rule_scores: Some(RuleScores {
                 scope_match: f32::from(candidate.scope_score) / 3.0,
                 authority_rank: candidate.record.authority.rank(),
@@ -641,7 +1285,9 @@ fn selected_policy_item(index: usize, candidate: &PolicyCandidate<'_>) -> Contex
     }
 }
 
-fn tokenize_query_terms(value: &str) -> Vec<String> {
+/// Normalize free text into the sorted, de-duplicated lexical terms used by recall.
+#[must_use]
+pub fn tokenize_query_terms(value: &str) -> Vec<String> {
     use std::collections::BTreeSet;
 
     let mut terms = BTreeSet::new();
@@ -658,7 +1304,9 @@ fn tokenize_query_terms(value: &str) -> Vec<String> {
     terms.into_iter().collect()
 }
 
-fn record_terms(record: &MemoryRecord) -> std::collections::BTreeSet<String> {
+/// Lexical terms recall matches against for one record (summary, or constraint scope and note).
+#[must_use]
+pub fn record_terms(record: &MemoryRecord) -> std::collections::BTreeSet<String> {
     use std::collections::BTreeSet;
 
     let mut terms = BTreeSet::new();
@@ -675,23 +1323,8 @@ fn record_terms(record: &MemoryRecord) -> std::collections::BTreeSet<String> {
                 }
             }
         }
-        MemoryPayload::Decision(payload) => {
-            for term in tokenize_query_terms(&payload.summary) {
-                terms.insert(term);
-            }
-        }
-        MemoryPayload::Preference(payload) => {
-            for term in tokenize_query_terms(&payload.summary) {
-                terms.insert(term);
-            }
-        }
-        MemoryPayload::Event(payload) => {
-            for term in tokenize_query_terms(&payload.summary) {
-                terms.insert(term);
-            }
-        }
-        MemoryPayload::Outcome(payload) => {
-            for term in tokenize_query_terms(&payload.summary) {
+        payload => {
+            for term in tokenize_query_terms(payload.summary().unwrap_or("")) {
                 terms.insert(term);
             }
         }
@@ -699,12 +1332,81 @@ fn record_terms(record: &MemoryRecord) -> std::collections::BTreeSet<String> {
     terms
 }
 
+/// Keep the latest versions among `records` that carry every tag in `tags`.
+///
+/// Superseded versions are dropped before matching so a memory's tags are always read from its
+/// newest version; with no tags, `records` is returned unchanged.
+#[must_use]
+pub fn retain_tagged(records: Vec<MemoryRecord>, tags: &[String]) -> Vec<MemoryRecord> {
+    if tags.is_empty() {
+        return records;
+    }
+    let superseded = records
+        .iter()
+        .flat_map(|record| record.supersedes.iter().copied())
+        .collect::<std::collections::BTreeSet<_>>();
+    records
+        .into_iter()
+        .filter(|record| {
+            !superseded.contains(&record.memory_version_id)
+                && tags.iter().all(|tag| record.tags.contains(tag))
+        })
+        .collect()
+}
+
+/// Text embedded for one record: its summary, or constraint scope and note.
+#[must_use]
+pub fn record_recall_text(record: &MemoryRecord) -> String {
+    match &record.payload {
+        MemoryPayload::Constraint(payload) => [
+            payload.scope.actor.as_str(),
+            payload.scope.action.as_str(),
+            payload.scope.resource.as_str(),
+            payload.note.as_deref().unwrap_or(""),
+        ]
+        .join(" ")
+        .trim_end()
+        .to_string(),
+        payload => payload.summary().unwrap_or("").to_string(),
+    }
+}
+
+/// Cosine similarity of two vectors, or `None` when their dimensions differ or are empty.
+/// A zero vector has similarity `0.0` with everything.
+#[must_use]
+pub fn cosine_similarity(lhs: &[f32], rhs: &[f32]) -> Option<f32> {
+    if lhs.is_empty() || lhs.len() != rhs.len() {
+        return None;
+    }
+    let (mut dot, mut lhs_norm, mut rhs_norm) = (0.0_f64, 0.0_f64, 0.0_f64);
+    for (left, right) in lhs.iter().zip(rhs) {
+        let (left, right) = (f64::from(*left), f64::from(*right));
+        dot += left * right;
+        lhs_norm += left * left;
+        rhs_norm += right * right;
+    }
+    if lhs_norm == 0.0 || rhs_norm == 0.0 {
+        return Some(0.0);
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let similarity = (dot / (lhs_norm.sqrt() * rhs_norm.sqrt())).clamp(-1.0, 1.0) as f32;
+    Some(similarity)
+}
+
+/// Round a score to 1e-6 so ordering does not hinge on floating-point noise.
+fn round_score(score: f32) -> f32 {
+    (score * 1_000_000.0).round() / 1_000_000.0
+}
+
 fn collect_recall_candidates_and_exclusions<'a>(
     records: &'a [MemoryRecord],
     allowed_types: &std::collections::BTreeSet<RecordType>,
     query_terms: &[String],
     superseded_ids: &std::collections::BTreeSet<MemoryVersionId>,
-) -> (Vec<RecallCandidate<'a>>, Vec<ContextItem>) {
+    semantic: Option<&SemanticRecall<'_>>,
+    as_of: OffsetDateTime,
+    decay: Option<&ConfidenceDecay>,
+) -> Result<(Vec<RecallCandidate<'a>>, Vec<ContextItem>), KernelError> {
     let mut candidates: Vec<RecallCandidate<'a>> = Vec::new();
     let mut excluded: Vec<ContextItem> = Vec::new();
 
@@ -726,26 +1428,87 @@ fn collect_recall_candidates_and_exclusions<'a>(
 
         let terms = record_terms(record);
         let matched_terms = query_terms.iter().filter(|term| terms.contains(*term)).count();
-        if matched_terms == 0 {
-            excluded.push(excluded_item(record, "no lexical overlap with query text"));
-            continue;
-        }
         let matched_terms_f32 = f32::from(u16::try_from(matched_terms).unwrap_or(u16::MAX));
         let total_terms_f32 = f32::from(u16::try_from(query_terms.len()).unwrap_or(u16::MAX));
+        let lexical_score = matched_terms_f32 / total_terms_f32;
+
+        let (similarity, combined_score) = match semantic {
+            None => {
+                if matched_terms == 0 {
+                    excluded.push(excluded_item(record, "no lexical overlap with query text"));
+                    continue;
+                }
+                (None, 0.0)
+            }
+            Some(semantic) => {
+                let similarity = semantic
+                    .record_vectors
+                    .get(&record.memory_version_id)
+                    .map(|vector| {
+                        cosine_similarity(semantic.query_vector, vector).ok_or_else(|| {
+                            KernelError::Query(format!(
+                                "embedding for {} has {} dimensions, query vector has {}",
+                                record.memory_version_id,
+                                vector.len(),
+                                semantic.query_vector.len()
+                            ))
+                        })
+                    })
+                    .transpose()?;
+                if matched_terms == 0
+                    && similarity.map_or(true, |similarity| similarity < semantic.min_similarity)
+                {
+                    excluded.push(excluded_item(
+                        record,
+                        "no lexical overlap and semantic similarity below threshold",
+                    ));
+                    continue;
+                }
+                let combined = SEMANTIC_RECALL_LEXICAL_WEIGHT * lexical_score
+                    + SEMANTIC_RECALL_VECTOR_WEIGHT * similarity.unwrap_or(0.0);
+                (similarity.map(round_score), round_score(combined))
+            }
+        };
 
         candidates.push(RecallCandidate {
             record,
             matched_terms,
             total_terms: query_terms.len(),
-            lexical_score: matched_terms_f32 / total_terms_f32,
-            confidence: record.confidence.unwrap_or(0.5),
+            lexical_score,
+            similarity,
+            combined_score,
+            confidence: ordering_confidence(record, as_of, decay),
         });
     }
 
-    (candidates, excluded)
+    Ok((candidates, excluded))
 }
 
-fn selected_recall_item(index: usize, candidate: &RecallCandidate<'_>) -> ContextItem {
+fn selected_recall_item(
+    index: usize,
+    candidate: &RecallCandidate<'_>,
+    semantic: Option<&SemanticRecall<'_>>,
+) -> ContextItem {
+    let mut reasons = vec![format!(
+        "lexical relevance matched {}/{} normalized terms",
+        candidate.matched_terms, candidate.total_terms
+    )];
+    if let Some(semantic) = semantic {
+        reasons.push(match candidate.similarity {
+            Some(similarity) => {
+                format!("semantic similarity={similarity:.6} (model={})", semantic.model)
+            }
+            None => format!("no stored embedding for model={}", semantic.model),
+        });
+        reasons.push(format!("combined score={:.6}", candidate.combined_score));
+    }
+    reasons.push(format!(
+        "record_type={} included in recall scope",
+        candidate.record.payload.record_type().as_str()
+    ));
+    reasons.push("passed active filters (not retracted, not superseded)".to_string());
+    reasons.extend(decay_reason(candidate.record, candidate.confidence));
+
     ContextItem {
         rank: index + 1,
         memory_version_id: candidate.record.memory_version_id,
@@ -757,19 +1520,13 @@ fn selected_recall_item(index: usize, candidate: &RecallCandidate<'_>) -> Contex
         authority: candidate.record.authority,
         why: Why {
             included: true,
-            reasons: vec![
-                format!(
-                    "lexical relevance matched {}/{} normalized terms",
-                    candidate.matched_terms, candidate.total_terms
-                ),
-                format!(
-                    "record_type={} included in recall scope",
-                    candidate.record.payload.record_type().as_str()
-                ),
-                "passed active filters (not retracted, not superseded)".to_string(),
-            ],
+            reasons,
             rule_scores: Some(RuleScores {
-                scope_match: candidate.lexical_score,
+                scope_match: if semantic.is_some() {
+                    candidate.combined_score
+                } else {
+                    candidate.lexical_score
+                },
                 authority_rank: candidate.record.authority.rank(),
                 truth_status_rank: candidate.record.truth_status.rank(),
                 confidence: candidate.confidence,
@@ -806,6 +1563,32 @@ pub fn build_context_package(
     records: &[MemoryRecord],
     query: QueryRequest,
     snapshot_id: &str,
+) -> Result<ContextPackage, KernelError> {
+    build_context_package_with_ruleset(
+        records,
+        query,
+        snapshot_id,
+        &PrecedenceRuleset,
+        None,
+        ConflictResolution::default(),
+    )
+}
+
+/// Build a deterministic Context Package for a policy query ordered and answered by `ruleset`,
+/// optionally decaying candidate confidence by truth status; allow/deny conflicts the ruleset
+/// cannot order are settled by `resolution`.
+///
+/// # Errors
+/// Returns [`KernelError::Query`] when deterministic snapshot metadata is invalid,
+/// or [`KernelError::Validation`] when any source record violates domain invariants or a
+/// decay half-life is invalid.
+pub fn build_context_package_with_ruleset(
+    records: &[MemoryRecord],
+    query: QueryRequest,
+    snapshot_id: &str,
+    ruleset: &dyn RulesetStrategy,
+    decay: Option<&ConfidenceDecay>,
+    resolution: ConflictResolution,
 ) -> Result<ContextPackage, KernelError> {
     if snapshot_id.trim().is_empty() {
         return Err(KernelError::Query(
@@ -816,41 +1599,197 @@ pub fn build_context_package(
     for record in records {
         record.validate()?;
     }
+    if let Some(decay) = decay {
+        decay.validate()?;
+    }
 
-    let superseded_ids = collect_superseded_ids(records);
+    // A superseding record only takes effect once it is itself effective at `as_of`.
+    let superseded_ids =
+        collect_superseded_ids(records.iter().filter(|record| record.effective_at <= query.as_of));
     let (mut candidates, mut excluded) =
-        collect_policy_candidates_and_exclusions(records, &query, &superseded_ids);
-    candidates.sort_by(PolicyCandidate::cmp);
+        collect_policy_candidates_and_exclusions(records, &query, &superseded_ids, decay);
+    candidates.sort_by(|lhs, rhs| ruleset.compare(lhs, rhs));
     let selected: Vec<ContextItem> = candidates
         .iter()
         .enumerate()
         .map(|(index, candidate)| selected_policy_item(index, candidate))
         .collect();
     assign_exclusion_ranks(&mut excluded);
-    let answer = derive_answer(&selected, records);
+    let answer = ruleset.derive_resolved_answer(&selected, records, resolution);
     let context_package_id = make_context_package_id(&query, snapshot_id)?;
+    let mut ordering_trace = vec![
+        "filter: record_type=constraint".to_string(),
+        "filter: scope_match(actor, action, resource)".to_string(),
+        "exclude: retracted and superseded".to_string(),
+    ];
+    ordering_trace.extend(decay.and_then(ConfidenceDecay::ordering_trace));
+    ordering_trace.push("sort: precedence tuple with deterministic tie-breakers".to_string());
 
     Ok(ContextPackage {
         context_package_id,
         generated_at: query.as_of,
         query,
         determinism: DeterminismMetadata {
-            ruleset_version: "ordering.v1".to_string(),
+            ruleset_version: ruleset.version().to_string(),
             snapshot_id: snapshot_id.to_string(),
-            tie_breakers: default_tie_breakers(),
+            tie_breakers: ruleset.tie_breakers(),
+            conflict_resolution: resolution,
         },
         answer,
         selected_items: selected,
         excluded_items: excluded,
-        ordering_trace: vec![
-            "filter: record_type=constraint".to_string(),
-            "filter: scope_match(actor, action, resource)".to_string(),
-            "exclude: retracted and superseded".to_string(),
-            "sort: precedence tuple with deterministic tie-breakers".to_string(),
-        ],
+        ordering_trace,
+    })
+}
+
+/// How one record fares in a policy query, without building or persisting a package.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecordExplanation {
+    pub query: QueryRequest,
+    pub ruleset_version: String,
+    /// Candidate count after filtering; `item.rank` is the record's position among them, or 0
+    /// when it is not a candidate.
+    pub candidates: usize,
+    pub item: ContextItem,
+}
+
+/// Explain the rule scores or exclusion reason `memory_version_id` would receive for a policy
+/// query ordered by `ruleset`, optionally decaying candidate confidence by truth status.
+///
+/// Records that never reach resolution (non-constraints, out-of-scope constraints) are reported
+/// as excluded with the filter that dropped them.
+///
+/// # Errors
+/// Returns [`KernelError::Query`] when `memory_version_id` is not among `records`, or
+/// [`KernelError::Validation`] when any source record violates domain invariants or a decay
+/// half-life is invalid.
+pub fn explain_record(
+    records: &[MemoryRecord],
+    memory_version_id: MemoryVersionId,
+    query: QueryRequest,
+    ruleset: &dyn RulesetStrategy,
+    decay: Option<&ConfidenceDecay>,
+) -> Result<RecordExplanation, KernelError> {
+    for record in records {
+        record.validate()?;
+    }
+    if let Some(decay) = decay {
+        decay.validate()?;
+    }
+    let Some(target) = records.iter().find(|record| record.memory_version_id == memory_version_id)
+    else {
+        return Err(KernelError::Query(format!(
+            "memory_version_id not found: {memory_version_id}"
+        )));
+    };
+
+    let superseded_ids =
+        collect_superseded_ids(records.iter().filter(|record| record.effective_at <= query.as_of));
+    let (mut candidates, excluded) =
+        collect_policy_candidates_and_exclusions(records, &query, &superseded_ids, decay);
+    candidates.sort_by(|lhs, rhs| ruleset.compare(lhs, rhs));
+
+    let item = if let Some((index, candidate)) = candidates
+        .iter()
+        .enumerate()
+        .find(|(_, candidate)| candidate.record.memory_version_id == memory_version_id)
+    {
+        let mut item = selected_policy_item(index, candidate);
+        item.why.reasons.push(format!(
+            "ranked {} of {} candidates by {}",
+            index + 1,
+            candidates.len(),
+            ruleset.version()
+        ));
+        item
+    } else if let Some(item) =
+        excluded.into_iter().find(|item| item.memory_version_id == memory_version_id)
+    {
+        item
+    } else if let MemoryPayload::Constraint(_) = target.payload {
+        excluded_item(target, "constraint scope does not match actor/action/resource")
+    } else {
+        excluded_item(
+            target,
+            &format!(
+                "record_type={} is not considered by policy queries",
+                target.payload.record_type().as_str()
+            ),
+        )
+    };
+
+    Ok(RecordExplanation {
+        query,
+        ruleset_version: ruleset.version().to_string(),
+        candidates: candidates.len(),
+        item,
     })
 }
 
+/// Answer for one sample query with and without a proposed constraint.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct SimulationOutcome {
+    pub query: QueryRequest,
+    pub baseline: AnswerResult,
+    pub simulated: AnswerResult,
+    pub changed: bool,
+}
+
+/// Evaluate `queries` against `records` with and without the `proposed` constraint under
+/// `ruleset` and optional confidence `decay`.
+///
+/// The proposed record only participates in resolution here; nothing is persisted, so policy
+/// changes (including supersession of existing constraints) can be reviewed before writing.
+///
+/// # Errors
+/// Returns [`KernelError::Validation`] when `proposed` is not a valid constraint record or any
+/// source record violates domain invariants.
+pub fn simulate_constraint(
+    records: &[MemoryRecord],
+    proposed: &MemoryRecord,
+    queries: &[QueryRequest],
+    ruleset: &dyn RulesetStrategy,
+    decay: Option<&ConfidenceDecay>,
+    resolution: ConflictResolution,
+) -> Result<Vec<SimulationOutcome>, KernelError> {
+    if !matches!(proposed.payload, MemoryPayload::Constraint(_)) {
+        return Err(KernelError::Validation("proposed record MUST be a constraint".to_string()));
+    }
+    proposed.validate()?;
+
+    let snapshot_id = format!("simulation_{}", proposed.memory_version_id);
+    let mut with_proposed = records.to_vec();
+    with_proposed.push(proposed.clone());
+
+    queries
+        .iter()
+        .map(|query| {
+            let baseline = build_context_package_with_ruleset(
+                records,
+                query.clone(),
+                &snapshot_id,
+                ruleset,
+                decay,
+                resolution,
+            )?;
+            let simulated = build_context_package_with_ruleset(
+                &with_proposed,
+                query.clone(),
+                &snapshot_id,
+                ruleset,
+                decay,
+                resolution,
+            )?;
+            Ok(SimulationOutcome {
+                query: query.clone(),
+                changed: baseline.answer.result != simulated.answer.result,
+                baseline: baseline.answer.result,
+                simulated: simulated.answer.result,
+            })
+        })
+        .collect()
+}
+
 /// Build a deterministic Context Package for memory recall across selected record types.
 ///
 /// # Errors
@@ -861,6 +1800,61 @@ pub fn build_recall_context_package(
     query: QueryRequest,
     snapshot_id: &str,
     record_types: &[RecordType],
+) -> Result<ContextPackage, KernelError> {
+    build_recall_package(records, query, snapshot_id, record_types, None, None)
+}
+
+/// Build a deterministic recall Context Package with candidate confidence decayed by truth
+/// status relative to `as_of`.
+///
+/// # Errors
+/// Returns [`KernelError::Query`] when deterministic snapshot metadata or query text is invalid,
+/// or [`KernelError::Validation`] when any source record violates domain invariants or a
+/// decay half-life is invalid.
+pub fn build_recall_context_package_with_decay(
+    records: &[MemoryRecord],
+    query: QueryRequest,
+    snapshot_id: &str,
+    record_types: &[RecordType],
+    decay: &ConfidenceDecay,
+) -> Result<ContextPackage, KernelError> {
+    build_recall_package(records, query, snapshot_id, record_types, None, Some(decay))
+}
+
+/// Build a deterministic Context Package for recall that also matches records by embedding
+/// similarity, so paraphrases without shared terms can still be recalled.
+///
+/// Candidates rank by `SEMANTIC_RECALL_LEXICAL_WEIGHT * lexical_score +
+/// SEMANTIC_RECALL_VECTOR_WEIGHT * similarity`, rounded to 1e-6, then by the lexical recall
+/// tuple.
+///
+/// # Errors
+/// Returns [`KernelError::Query`] when deterministic snapshot metadata or query text is invalid
+/// or a record vector's dimensions differ from the query vector, or
+/// [`KernelError::Validation`] when any source record violates domain invariants.
+pub fn build_semantic_recall_context_package(
+    records: &[MemoryRecord],
+    query: QueryRequest,
+    snapshot_id: &str,
+    record_types: &[RecordType],
+    semantic: &SemanticRecall<'_>,
+    decay: Option<&ConfidenceDecay>,
+) -> Result<ContextPackage, KernelError> {
+    if semantic.query_vector.is_empty() {
+        return Err(KernelError::Query(
+            "semantic recall query vector MUST be non-empty".to_string(),
+        ));
+    }
+    build_recall_package(records, query, snapshot_id, record_types, Some(semantic), decay)
+}
+
+fn build_recall_package(
+    records: &[MemoryRecord],
+    query: QueryRequest,
+    snapshot_id: &str,
+    record_types: &[RecordType],
+    semantic: Option<&SemanticRecall<'_>>,
+    decay: Option<&ConfidenceDecay>,
 ) -> Result<ContextPackage, KernelError> {
     use std::collections::BTreeSet;
 
@@ -877,6 +1871,9 @@ pub fn build_recall_context_package(
     for record in records {
         record.validate()?;
     }
+    if let Some(decay) = decay {
+        decay.validate()?;
+    }
 
     let allowed_types = if record_types.is_empty() {
         default_recall_record_types().into_iter().collect::<BTreeSet<_>>()
@@ -897,12 +1894,15 @@ pub fn build_recall_context_package(
         &allowed_types,
         &query_terms,
         &superseded_ids,
-    );
+        semantic,
+        query.as_of,
+        decay,
+    )?;
     candidates.sort_by(RecallCandidate::cmp);
     let selected: Vec<ContextItem> = candidates
         .iter()
         .enumerate()
-        .map(|(index, candidate)| selected_recall_item(index, candidate))
+        .map(|(index, candidate)| selected_recall_item(index, candidate, semantic))
         .collect();
     assign_exclusion_ranks(&mut excluded);
     let context_package_id = make_context_package_id(&query, snapshot_id)?;
@@ -910,15 +1910,37 @@ pub fn build_recall_context_package(
     let mut selected_types = allowed_types.into_iter().map(RecordType::as_str).collect::<Vec<_>>();
     selected_types.sort_unstable();
     let selected_types = selected_types.join(", ");
+    let (ruleset_version, tie_breakers, match_filter) = match semantic {
+        None => (
+            "recall-ordering.v1",
+            default_recall_tie_breakers(),
+            "filter: lexical overlap with normalized query terms",
+        ),
+        Some(_) => (
+            "semantic-recall-ordering.v1",
+            default_semantic_recall_tie_breakers(),
+            "filter: lexical overlap or embedding similarity at or above threshold",
+        ),
+    };
+
+    let mut ordering_trace = vec![
+        format!("filter: record_type in [{selected_types}]"),
+        match_filter.to_string(),
+        "exclude: retracted and superseded".to_string(),
+    ];
+    ordering_trace.extend(decay.and_then(ConfidenceDecay::ordering_trace));
+    ordering_trace
+        .push("sort: recall precedence tuple with deterministic tie-breakers".to_string());
 
     Ok(ContextPackage {
         context_package_id,
         generated_at: query.as_of,
         query,
         determinism: DeterminismMetadata {
-            ruleset_version: "recall-ordering.v1".to_string(),
+            ruleset_version: ruleset_version.to_string(),
             snapshot_id: snapshot_id.to_string(),
-            tie_breakers: default_recall_tie_breakers(),
+            tie_breakers,
+            conflict_resolution: ConflictResolution::default(),
         },
         answer: Answer {
             result: AnswerResult::Inconclusive,
@@ -930,17 +1952,23 @@ pub fn build_recall_context_package(
         },
         selected_items: selected,
         excluded_items: excluded,
-        ordering_trace: vec![
-            format!("filter: record_type in [{selected_types}]"),
-            "filter: lexical overlap with normalized query terms".to_string(),
-            "exclude: retracted and superseded".to_string(),
-            "sort: recall precedence tuple with deterministic tie-breakers".to_string(),
-        ],
+        ordering_trace,
     })
 }
 
-fn derive_answer(selected: &[ContextItem], records: &[MemoryRecord]) -> Answer {
+fn derive_answer(
+    selected: &[ContextItem],
+    records: &[MemoryRecord],
+    resolution: ConflictResolution,
+) -> Answer {
     const DEFAULT_CONFIDENCE: f32 = 0.5;
+    // Group by the (possibly decayed) confidence candidates were ordered by.
+    let ordering_confidence_of = |item: &ContextItem| {
+        item.why
+            .rule_scores
+            .as_ref()
+            .map_or(item.confidence.unwrap_or(DEFAULT_CONFIDENCE), |scores| scores.confidence)
+    };
     let mut top_ranked_ids: Vec<MemoryVersionId> = Vec::new();
     let Some(top) = selected.first() else {
         return Answer {
@@ -950,8 +1978,8 @@ fn derive_answer(selected: &[ContextItem], records: &[MemoryRecord]) -> Answer {
     };
 
     for item in selected {
-        let same_confidence = item.confidence.unwrap_or(DEFAULT_CONFIDENCE).to_bits()
-            == top.confidence.unwrap_or(DEFAULT_CONFIDENCE).to_bits();
+        let same_confidence =
+            ordering_confidence_of(item).to_bits() == ordering_confidence_of(top).to_bits();
         if item.rank == 1 {
             top_ranked_ids.push(item.memory_version_id);
             continue;
@@ -980,10 +2008,7 @@ fn derive_answer(selected: &[ContextItem], records: &[MemoryRecord]) -> Answer {
     }
 
     match (has_allow, has_deny) {
-        (true, true) => Answer {
-            result: AnswerResult::Inconclusive,
-            why: "Top-precedence constraints conflict (allow and deny)".to_string(),
-        },
+        (true, true) => resolution.settle("Top-precedence constraints conflict (allow and deny)"),
         (true, false) => Answer {
             result: AnswerResult::Allow,
             why: "Highest-precedence active constraint allows the action".to_string(),
@@ -1092,6 +2117,7 @@ mod tests {
             },
             supersedes,
             contradicts: vec![],
+            tags: vec![],
             payload: MemoryPayload::Constraint(ConstraintPayload {
                 scope: ConstraintScope {
                     actor: scope_actor.to_string(),
@@ -1100,6 +2126,7 @@ mod tests {
                 },
                 effect,
                 note: None,
+                expires_at: None,
             }),
         }
     }
@@ -1126,6 +2153,18 @@ mod tests {
             RecordType::Outcome => {
                 MemoryPayload::Outcome(OutcomePayload { summary: summary.to_string() })
             }
+            RecordType::Task => MemoryPayload::Task(TaskPayload {
+                summary: summary.to_string(),
+                status: TaskStatus::Open,
+            }),
+            RecordType::Risk => MemoryPayload::Risk(RiskPayload {
+                summary: summary.to_string(),
+                severity: RiskSeverity::High,
+            }),
+            RecordType::Assumption => MemoryPayload::Assumption(AssumptionPayload {
+                summary: summary.to_string(),
+                status: AssumptionStatus::Unverified,
+            }),
             RecordType::Constraint => {
                 panic!("mk_summary does not support constraint payloads")
             }
@@ -1149,6 +2188,7 @@ mod tests {
             },
             supersedes,
             contradicts: vec![],
+            tags: vec![],
             payload,
         }
     }
@@ -1262,6 +2302,33 @@ mod tests {
         );
     }
 
+    // Test IDs: TWR-006
+    #[test]
+    fn validate_rejects_blank_or_duplicate_tags() {
+        let mut record = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2EA"),
+            Authority::Authoritative,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        record.tags = vec!["team-it".to_string(), "team-it".to_string()];
+        assert_validation_error_contains(&record, "tags MUST be unique: team-it");
+
+        record.tags = vec![" team-it".to_string()];
+        assert_validation_error_contains(
+            &record,
+            "tags MUST be non-empty without surrounding whitespace",
+        );
+
+        record.tags = vec!["team-it".to_string(), "project-usb".to_string()];
+        assert!(record.validate().is_ok());
+    }
+
     // Test IDs: TRES-001
     #[test]
     fn retracted_constraints_are_excluded_with_reason() {
@@ -1301,104 +2368,336 @@ mod tests {
             .any(|reason| reason.contains("truth_status is retracted")));
     }
 
-    // Test IDs: TRES-003
+    // Test IDs: TRES-007
     #[test]
-    fn conflicting_top_precedence_constraints_return_inconclusive() {
-        let allow = mk_constraint(
-            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2E0"),
+    fn expired_constraints_are_excluded_at_as_of() {
+        let mut expiring = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2F1"),
             Authority::Authoritative,
             TruthStatus::Asserted,
-            Some(0.8),
-            ConstraintEffect::Allow,
+            Some(0.9),
+            ConstraintEffect::Deny,
             vec![],
             "user",
             "use",
             "usb_drive",
         );
-        let deny = mk_constraint(
-            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2E1"),
-            Authority::Authoritative,
-            TruthStatus::Asserted,
-            Some(0.8),
-            ConstraintEffect::Deny,
+        expiring.payload = match expiring.payload {
+            MemoryPayload::Constraint(payload) => MemoryPayload::Constraint(ConstraintPayload {
+                expires_at: Some(fixture_time() + Duration::days(1)),
+                ..payload
+            }),
+            other => other,
+        };
+        let fallback = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2F2"),
+            Authority::Derived,
+            TruthStatus::Observed,
+            Some(0.6),
+            ConstraintEffect::Allow,
             vec![],
             "user",
             "use",
             "usb_drive",
         );
+        let records = [expiring.clone(), fallback.clone()];
+        let query = |as_of: OffsetDateTime| QueryRequest {
+            text: "Am I allowed to use a USB drive?".to_string(),
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: "usb_drive".to_string(),
+            as_of,
+        };
 
-        let package = match build_context_package(
-            &[allow, deny],
-            QueryRequest {
-                text: "Am I allowed to use a USB drive?".to_string(),
-                actor: "user".to_string(),
-                action: "use".to_string(),
-                resource: "usb_drive".to_string(),
-                as_of: fixture_time(),
-            },
-            "txn_conflict",
-        ) {
+        let before = match build_context_package(&records, query(fixture_time()), "txn_active") {
             Ok(package) => package,
             Err(err) => panic!("context package should build: {err}"),
         };
+        assert_eq!(before.answer.result, AnswerResult::Deny);
+        assert_eq!(before.selected_items[0].memory_version_id, expiring.memory_version_id);
 
-        assert_eq!(package.answer.result, AnswerResult::Inconclusive);
+        let after = match build_context_package(
+            &records,
+            query(fixture_time() + Duration::days(1)),
+            "txn_expired",
+        ) {
+            Ok(package) => package,
+            Err(err) => panic!("context package should build: {err}"),
+        };
+        assert_eq!(after.answer.result, AnswerResult::Allow);
+        assert_eq!(after.selected_items.len(), 1);
+        assert_eq!(after.selected_items[0].memory_version_id, fallback.memory_version_id);
+        assert!(after.excluded_items.iter().any(|item| {
+            item.memory_version_id == expiring.memory_version_id
+                && item.why.reasons.iter().any(|reason| reason.contains("expired"))
+        }));
+
+        let mut invalid = expiring;
+        invalid.payload = match invalid.payload {
+            MemoryPayload::Constraint(payload) => MemoryPayload::Constraint(ConstraintPayload {
+                expires_at: Some(fixture_time()),
+                ..payload
+            }),
+            other => other,
+        };
+        assert_validation_error_contains(&invalid, "expires_at MUST be after effective_at");
     }
 
-    // Test IDs: TID-004
+    // Test IDs: TRES-008
     #[test]
-    fn context_items_include_memory_version_ids_for_selected_and_excluded() {
-        let selected = mk_constraint(
-            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2E2"),
+    fn records_not_yet_effective_are_excluded_and_do_not_supersede() {
+        let original = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2F3"),
             Authority::Authoritative,
             TruthStatus::Asserted,
             Some(0.9),
-            ConstraintEffect::Deny,
+            ConstraintEffect::Allow,
             vec![],
             "user",
             "use",
             "usb_drive",
         );
-        let excluded = mk_constraint(
-            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2E3"),
+        let mut replacement = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2F4"),
             Authority::Authoritative,
-            TruthStatus::Retracted,
-            Some(0.1),
-            ConstraintEffect::Allow,
-            vec![],
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+            vec![original.memory_version_id],
             "user",
             "use",
             "usb_drive",
         );
+        replacement.effective_at = fixture_time() + Duration::days(7);
+        let records = [original.clone(), replacement.clone()];
+        let query = |as_of: OffsetDateTime| QueryRequest {
+            text: "Am I allowed to use a USB drive?".to_string(),
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: "usb_drive".to_string(),
+            as_of,
+        };
 
-        let selected_id = selected.memory_version_id;
-        let excluded_id = excluded.memory_version_id;
-
-        let package = match build_context_package(
-            &[selected, excluded],
-            QueryRequest {
-                text: "Am I allowed to use a USB drive?".to_string(),
-                actor: "user".to_string(),
-                action: "use".to_string(),
-                resource: "usb_drive".to_string(),
-                as_of: fixture_time(),
-            },
-            "txn_ids",
+        let historical = match build_context_package(&records, query(fixture_time()), "txn_past") {
+            Ok(package) => package,
+            Err(err) => panic!("context package should build: {err}"),
+        };
+        assert_eq!(historical.answer.result, AnswerResult::Allow);
+        assert_eq!(historical.selected_items.len(), 1);
+        assert_eq!(historical.selected_items[0].memory_version_id, original.memory_version_id);
+        assert!(historical.excluded_items.iter().any(|item| {
+            item.memory_version_id == replacement.memory_version_id
+                && item
+                    .why
+                    .reasons
+                    .iter()
+                    .any(|reason| reason == "not yet effective as of query time")
+        }));
+
+        let current = match build_context_package(
+            &records,
+            query(fixture_time() + Duration::days(7)),
+            "txn_current",
         ) {
             Ok(package) => package,
             Err(err) => panic!("context package should build: {err}"),
         };
-
-        assert_eq!(package.selected_items.len(), 1);
-        assert_eq!(package.excluded_items.len(), 1);
-        assert_eq!(package.selected_items[0].memory_version_id, selected_id);
-        assert_eq!(package.excluded_items[0].memory_version_id, excluded_id);
+        assert_eq!(current.answer.result, AnswerResult::Deny);
+        assert_eq!(current.selected_items[0].memory_version_id, replacement.memory_version_id);
+        assert!(current.excluded_items.iter().any(|item| {
+            item.memory_version_id == original.memory_version_id
+                && item.why.reasons.iter().any(|reason| reason.contains("superseded"))
+        }));
     }
 
-    // Test IDs: TRES-004
+    // Test IDs: TRES-009
     #[test]
-    fn deterministic_sort_prefers_authority_then_truth_then_confidence() {
-        let a = mk_constraint(
+    fn hierarchical_scope_globs_prefer_deeper_matches() {
+        let scoped = |id: &str, effect: ConstraintEffect, actor: &str, resource: &str| {
+            mk_constraint(
+                fixture_id(id),
+                Authority::Authoritative,
+                TruthStatus::Asserted,
+                Some(0.9),
+                effect,
+                vec![],
+                actor,
+                "use",
+                resource,
+            )
+        };
+        let storage = scoped(
+            "01HZY9D4Q3SG7PV9A6EXJ8N2F5",
+            ConstraintEffect::Allow,
+            "role:contractor/*",
+            "storage/*",
+        );
+        let usb = scoped(
+            "01HZY9D4Q3SG7PV9A6EXJ8N2F6",
+            ConstraintEffect::Deny,
+            "role:contractor/*",
+            "storage/usb/*",
+        );
+        let wildcard = scoped("01HZY9D4Q3SG7PV9A6EXJ8N2F7", ConstraintEffect::Allow, "*", "*");
+        let partial_segment =
+            scoped("01HZY9D4Q3SG7PV9A6EXJ8N2F8", ConstraintEffect::Allow, "*", "storage/us*");
+        let query = |actor: &str, resource: &str| QueryRequest {
+            text: "Can I copy files?".to_string(),
+            actor: actor.to_string(),
+            action: "use".to_string(),
+            resource: resource.to_string(),
+            as_of: fixture_time(),
+        };
+        let records = [wildcard.clone(), storage.clone(), usb.clone(), partial_segment];
+
+        let package = match build_context_package(
+            &records,
+            query("role:contractor/alice", "storage/usb/sandisk"),
+            "txn_glob",
+        ) {
+            Ok(package) => package,
+            Err(err) => panic!("context package should build: {err}"),
+        };
+        let selected =
+            package.selected_items.iter().map(|item| item.memory_version_id).collect::<Vec<_>>();
+        assert_eq!(
+            selected,
+            vec![usb.memory_version_id, storage.memory_version_id, wildcard.memory_version_id]
+        );
+        assert!(package.selected_items[0]
+            .why
+            .reasons
+            .iter()
+            .any(|reason| reason == "scope glob prefix depth=3"));
+        assert!(package.determinism.tie_breakers.contains(&"scope_glob_depth desc".to_string()));
+
+        // Globs never match the prefix itself, and exact matches outrank any glob.
+        let exact = scoped(
+            "01HZY9D4Q3SG7PV9A6EXJ8N2F9",
+            ConstraintEffect::Allow,
+            "role:contractor/alice",
+            "storage/usb/*",
+        );
+        let package = match build_context_package(
+            &[usb.clone(), storage, exact.clone()],
+            query("role:contractor/alice", "storage"),
+            "txn_glob_prefix",
+        ) {
+            Ok(package) => package,
+            Err(err) => panic!("context package should build: {err}"),
+        };
+        assert!(package.selected_items.is_empty());
+
+        let package = match build_context_package(
+            &[usb.clone(), exact.clone()],
+            query("role:contractor/alice", "storage/usb/sandisk"),
+            "txn_glob_exact",
+        ) {
+            Ok(package) => package,
+            Err(err) => panic!("context package should build: {err}"),
+        };
+        assert_eq!(package.selected_items[0].memory_version_id, exact.memory_version_id);
+    }
+
+    // Test IDs: TRES-003
+    #[test]
+    fn conflicting_top_precedence_constraints_return_inconclusive() {
+        let allow = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2E0"),
+            Authority::Authoritative,
+            TruthStatus::Asserted,
+            Some(0.8),
+            ConstraintEffect::Allow,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        let deny = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2E1"),
+            Authority::Authoritative,
+            TruthStatus::Asserted,
+            Some(0.8),
+            ConstraintEffect::Deny,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+
+        let package = match build_context_package(
+            &[allow, deny],
+            QueryRequest {
+                text: "Am I allowed to use a USB drive?".to_string(),
+                actor: "user".to_string(),
+                action: "use".to_string(),
+                resource: "usb_drive".to_string(),
+                as_of: fixture_time(),
+            },
+            "txn_conflict",
+        ) {
+            Ok(package) => package,
+            Err(err) => panic!("context package should build: {err}"),
+        };
+
+        assert_eq!(package.answer.result, AnswerResult::Inconclusive);
+        assert_eq!(package.determinism.conflict_resolution, ConflictResolution::Inconclusive);
+    }
+
+    // Test IDs: TID-004
+    #[test]
+    fn context_items_include_memory_version_ids_for_selected_and_excluded() {
+        let selected = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2E2"),
+            Authority::Authoritative,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        let excluded = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2E3"),
+            Authority::Authoritative,
+            TruthStatus::Retracted,
+            Some(0.1),
+            ConstraintEffect::Allow,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+
+        let selected_id = selected.memory_version_id;
+        let excluded_id = excluded.memory_version_id;
+
+        let package = match build_context_package(
+            &[selected, excluded],
+            QueryRequest {
+                text: "Am I allowed to use a USB drive?".to_string(),
+                actor: "user".to_string(),
+                action: "use".to_string(),
+                resource: "usb_drive".to_string(),
+                as_of: fixture_time(),
+            },
+            "txn_ids",
+        ) {
+            Ok(package) => package,
+            Err(err) => panic!("context package should build: {err}"),
+        };
+
+        assert_eq!(package.selected_items.len(), 1);
+        assert_eq!(package.excluded_items.len(), 1);
+        assert_eq!(package.selected_items[0].memory_version_id, selected_id);
+        assert_eq!(package.excluded_items[0].memory_version_id, excluded_id);
+    }
+
+    // Test IDs: TRES-004
+    #[test]
+    fn deterministic_sort_prefers_authority_then_truth_then_confidence() {
+        let a = mk_constraint(
             fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2DF"),
             Authority::Derived,
             TruthStatus::Asserted,
@@ -1843,6 +3142,486 @@ mod tests {
         assert_eq!(package.answer.result, AnswerResult::Inconclusive);
     }
 
+    // Test IDs: TRES-010
+    #[test]
+    fn semantic_recall_ranks_paraphrases_by_combined_score() {
+        let record = |id: &str, summary: &str| {
+            mk_summary(
+                fixture_id(id),
+                RecordType::Decision,
+                Authority::Authoritative,
+                TruthStatus::Observed,
+                Some(0.8),
+                summary,
+                vec![],
+            )
+        };
+        let paraphrase = record("01K1D3A7E9J5MNNN8F5JVCJY51", "Removable USB storage is blocked");
+        let lexical = record("01K1D3A7E9J5MNNN8F5JVCJY52", "Thumb drive inventory was audited");
+        let unrelated = record("01K1D3A7E9J5MNNN8F5JVCJY53", "Printer toner was replaced");
+        let records = vec![paraphrase.clone(), lexical.clone(), unrelated.clone()];
+        let vectors = std::collections::BTreeMap::from([
+            (paraphrase.memory_version_id, vec![0.9, 0.1, 0.0]),
+            (lexical.memory_version_id, vec![0.1, 0.9, 0.0]),
+            (unrelated.memory_version_id, vec![0.0, 0.0, 1.0]),
+        ]);
+        let query = QueryRequest {
+            text: "blocked thumb drive".to_string(),
+            actor: "*".to_string(),
+            action: "*".to_string(),
+            resource: "*".to_string(),
+            as_of: fixture_time(),
+        };
+        let semantic = SemanticRecall {
+            model: "fixture-embedder",
+            query_vector: &[1.0, 0.0, 0.0],
+            record_vectors: &vectors,
+            min_similarity: DEFAULT_MIN_SEMANTIC_SIMILARITY,
+        };
+
+        let package = match build_semantic_recall_context_package(
+            &records,
+            query.clone(),
+            "txn_semantic_recall",
+            &[],
+            &semantic,
+            None,
+        ) {
+            Ok(package) => package,
+            Err(err) => panic!("semantic recall package should build: {err}"),
+        };
+        let selected =
+            package.selected_items.iter().map(|item| item.memory_version_id).collect::<Vec<_>>();
+        assert_eq!(selected, vec![paraphrase.memory_version_id, lexical.memory_version_id]);
+        assert_eq!(package.excluded_items.len(), 1);
+        assert_eq!(package.excluded_items[0].memory_version_id, unrelated.memory_version_id);
+        assert_eq!(package.determinism.ruleset_version, "semantic-recall-ordering.v1");
+        assert_eq!(package.determinism.tie_breakers, default_semantic_recall_tie_breakers());
+        assert!(package.selected_items[0]
+            .why
+            .reasons
+            .iter()
+            .any(|reason| reason.starts_with("semantic similarity=0.99")));
+
+        let lexical_only =
+            match build_recall_context_package(&records, query.clone(), "txn_semantic_recall", &[])
+            {
+                Ok(package) => package,
+                Err(err) => panic!("lexical recall package should build: {err}"),
+            };
+        assert_eq!(lexical_only.selected_items.len(), 2);
+        assert_eq!(lexical_only.determinism.ruleset_version, "recall-ordering.v1");
+
+        let mismatched =
+            std::collections::BTreeMap::from([(paraphrase.memory_version_id, vec![1.0, 0.0])]);
+        let err = build_semantic_recall_context_package(
+            &records,
+            query,
+            "txn_semantic_recall",
+            &[],
+            &SemanticRecall { record_vectors: &mismatched, ..semantic },
+            None,
+        );
+        assert!(matches!(err, Err(KernelError::Query(message)) if message.contains("dimensions")));
+    }
+
+    // Test IDs: TRES-011
+    #[test]
+    fn simulate_constraint_reports_flipped_answers_without_mutating_input() {
+        let allow = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2F7"),
+            Authority::Authoritative,
+            TruthStatus::Observed,
+            Some(0.9),
+            ConstraintEffect::Allow,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        let proposed = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2F8"),
+            Authority::Authoritative,
+            TruthStatus::Observed,
+            Some(0.9),
+            ConstraintEffect::Deny,
+            vec![allow.memory_version_id],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        let query = |resource: &str| QueryRequest {
+            text: "Can I use it?".to_string(),
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: resource.to_string(),
+            as_of: fixture_time(),
+        };
+        let records = vec![allow];
+
+        let outcomes = match simulate_constraint(
+            &records,
+            &proposed,
+            &[query("usb_drive"), query("printer")],
+            &PrecedenceRuleset,
+            None,
+            ConflictResolution::default(),
+        ) {
+            Ok(outcomes) => outcomes,
+            Err(err) => panic!("simulation should succeed: {err}"),
+        };
+
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].baseline, AnswerResult::Allow);
+        assert_eq!(outcomes[0].simulated, AnswerResult::Deny);
+        assert!(outcomes[0].changed);
+        assert_eq!(outcomes[1].baseline, AnswerResult::Inconclusive);
+        assert_eq!(outcomes[1].simulated, AnswerResult::Inconclusive);
+        assert!(!outcomes[1].changed);
+        assert_eq!(records.len(), 1);
+
+        let not_constraint = MemoryRecord {
+            payload: MemoryPayload::Decision(DecisionPayload { summary: "x".to_string() }),
+            ..proposed
+        };
+        assert!(matches!(
+            simulate_constraint(
+                &records,
+                &not_constraint,
+                &[query("usb_drive")],
+                &PrecedenceRuleset,
+                None,
+                ConflictResolution::default(),
+            ),
+            Err(KernelError::Validation(_))
+        ));
+    }
+
+    // Test IDs: TRES-012
+    #[test]
+    fn policy_rulesets_change_answer_and_record_version() {
+        let allow = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2F9"),
+            Authority::Authoritative,
+            TruthStatus::Observed,
+            Some(0.9),
+            ConstraintEffect::Allow,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        let mut deny = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2FA"),
+            Authority::Derived,
+            TruthStatus::Asserted,
+            Some(0.6),
+            ConstraintEffect::Deny,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        deny.effective_at = fixture_time() + Duration::days(1);
+        let records = vec![allow.clone(), deny.clone()];
+        let query = QueryRequest {
+            text: "Can I use a USB drive?".to_string(),
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: "usb_drive".to_string(),
+            as_of: fixture_time() + Duration::days(2),
+        };
+
+        let build = |ruleset: PolicyRuleset| match build_context_package_with_ruleset(
+            &records,
+            query.clone(),
+            "txn_rulesets",
+            ruleset.strategy(),
+            None,
+            ConflictResolution::default(),
+        ) {
+            Ok(package) => package,
+            Err(err) => panic!("context package should build: {err}"),
+        };
+
+        let precedence = build(PolicyRuleset::Precedence);
+        assert_eq!(precedence.answer.result, AnswerResult::Allow);
+        assert_eq!(precedence.determinism.ruleset_version, "ordering.v1");
+        assert_eq!(precedence.selected_items[0].memory_version_id, allow.memory_version_id);
+
+        let deny_overrides = build(PolicyRuleset::DenyOverrides);
+        assert_eq!(deny_overrides.answer.result, AnswerResult::Deny);
+        assert_eq!(deny_overrides.determinism.ruleset_version, "deny-overrides.v1");
+        assert_eq!(deny_overrides.selected_items[0].memory_version_id, allow.memory_version_id);
+
+        let most_recent = build(PolicyRuleset::MostRecentWins);
+        assert_eq!(most_recent.answer.result, AnswerResult::Deny);
+        assert_eq!(most_recent.determinism.ruleset_version, "most-recent-wins.v1");
+        assert_eq!(most_recent.selected_items[0].memory_version_id, deny.memory_version_id);
+        assert_eq!(most_recent.determinism.tie_breakers[0], "effective_at desc");
+
+        for ruleset in
+            [PolicyRuleset::Precedence, PolicyRuleset::DenyOverrides, PolicyRuleset::MostRecentWins]
+        {
+            assert_eq!(PolicyRuleset::from_version(ruleset.strategy().version()), Some(ruleset));
+        }
+        assert_eq!(PolicyRuleset::from_version("recall-ordering.v1"), None);
+    }
+
+    // Test IDs: TRES-013
+    #[test]
+    fn confidence_decay_reorders_candidates_and_explains_scores() {
+        let mut stale_allow = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2FB"),
+            Authority::Authoritative,
+            TruthStatus::Inferred,
+            Some(0.9),
+            ConstraintEffect::Allow,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        stale_allow.effective_at = fixture_time() - Duration::days(60);
+        let fresh_deny = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2FC"),
+            Authority::Authoritative,
+            TruthStatus::Inferred,
+            Some(0.6),
+            ConstraintEffect::Deny,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        let records = vec![stale_allow.clone(), fresh_deny.clone()];
+        let query = QueryRequest {
+            text: "Can I use a USB drive?".to_string(),
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: "usb_drive".to_string(),
+            as_of: fixture_time(),
+        };
+        let decay = ConfidenceDecay::default().with_half_life(TruthStatus::Inferred, 30.0);
+
+        let build = |decay: Option<&ConfidenceDecay>| match build_context_package_with_ruleset(
+            &records,
+            query.clone(),
+            "txn_decay",
+            &PrecedenceRuleset,
+            decay,
+            ConflictResolution::default(),
+        ) {
+            Ok(package) => package,
+            Err(err) => panic!("context package should build: {err}"),
+        };
+
+        let undecayed = build(None);
+        assert_eq!(undecayed.answer.result, AnswerResult::Allow);
+
+        let decayed = build(Some(&decay));
+        assert_eq!(decayed.answer.result, AnswerResult::Deny);
+        assert_eq!(decayed.selected_items[0].memory_version_id, fresh_deny.memory_version_id);
+        let stale = &decayed.selected_items[1];
+        assert_eq!(stale.confidence, Some(0.9));
+        assert_eq!(stale.why.rule_scores.as_ref().map(|scores| scores.confidence), Some(0.225));
+        assert!(stale.why.reasons.iter().any(|reason| reason.contains("confidence decayed")));
+        assert!(decayed.ordering_trace.iter().any(|line| line.contains("inferred=30d")));
+
+        let invalid = ConfidenceDecay::default().with_half_life(TruthStatus::Inferred, 0.0);
+        assert!(matches!(
+            build_recall_context_package_with_decay(
+                &records,
+                query.clone(),
+                "txn_decay",
+                &[],
+                &invalid
+            ),
+            Err(KernelError::Validation(_))
+        ));
+    }
+
+    // Test IDs: TRES-014
+    #[test]
+    fn explain_record_reports_rank_scores_and_exclusion_reasons() {
+        let winner = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2FD"),
+            Authority::Authoritative,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        let runner_up = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2FE"),
+            Authority::Derived,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Allow,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        let mut out_of_scope = runner_up.clone();
+        out_of_scope.memory_version_id = MemoryVersionId::new();
+        if let MemoryPayload::Constraint(constraint) = &mut out_of_scope.payload {
+            constraint.scope.resource = "printer".to_string();
+        }
+        let mut retracted = runner_up.clone();
+        retracted.memory_version_id = MemoryVersionId::new();
+        retracted.truth_status = TruthStatus::Retracted;
+        let decision = mk_summary(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2FH"),
+            RecordType::Decision,
+            Authority::Authoritative,
+            TruthStatus::Asserted,
+            Some(0.9),
+            "USB drives are allowed",
+            vec![],
+        );
+        let records = vec![winner, runner_up, out_of_scope, retracted, decision];
+        let query = QueryRequest {
+            text: "Can I use a USB drive?".to_string(),
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: "usb_drive".to_string(),
+            as_of: fixture_time(),
+        };
+        let explain = |id: MemoryVersionId| match explain_record(
+            &records,
+            id,
+            query.clone(),
+            &PrecedenceRuleset,
+            None,
+        ) {
+            Ok(explanation) => explanation,
+            Err(err) => panic!("explanation should build: {err}"),
+        };
+
+        let second = explain(records[1].memory_version_id);
+        assert_eq!(second.candidates, 2);
+        assert_eq!(second.item.rank, 2);
+        assert!(second.item.why.included);
+        assert_eq!(
+            second.item.why.rule_scores.as_ref().map(|scores| scores.authority_rank),
+            Some(Authority::Derived.rank())
+        );
+        assert_eq!(
+            second.item.why.reasons.last().map(String::as_str),
+            Some("ranked 2 of 2 candidates by ordering.v1")
+        );
+        assert_eq!(explain(records[0].memory_version_id).item.rank, 1);
+
+        let cases = [
+            (records[3].memory_version_id, "truth_status is retracted"),
+            (records[2].memory_version_id, "constraint scope does not match actor/action/resource"),
+            (
+                records[4].memory_version_id,
+                "record_type=decision is not considered by policy queries",
+            ),
+        ];
+        for (id, reason) in cases {
+            let explanation = explain(id);
+            assert_eq!(explanation.item.rank, 0);
+            assert!(!explanation.item.why.included);
+            assert_eq!(explanation.item.why.reasons, vec![reason.to_string()]);
+            assert!(explanation.item.why.rule_scores.is_none());
+        }
+
+        assert!(matches!(
+            explain_record(
+                &records,
+                MemoryVersionId::new(),
+                query.clone(),
+                &PrecedenceRuleset,
+                None
+            ),
+            Err(KernelError::Query(_))
+        ));
+    }
+
+    // Test IDs: TRES-015
+    #[test]
+    fn conflict_resolution_settles_ties_and_is_recorded() {
+        let allow = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2FJ"),
+            Authority::Authoritative,
+            TruthStatus::Asserted,
+            Some(0.8),
+            ConstraintEffect::Allow,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        let deny = mk_constraint(
+            fixture_id("01HZY9D4Q3SG7PV9A6EXJ8N2FK"),
+            Authority::Authoritative,
+            TruthStatus::Asserted,
+            Some(0.8),
+            ConstraintEffect::Deny,
+            vec![],
+            "user",
+            "use",
+            "usb_drive",
+        );
+        let records = vec![allow, deny];
+        let query = QueryRequest {
+            text: "Can I use a USB drive?".to_string(),
+            actor: "user".to_string(),
+            action: "use".to_string(),
+            resource: "usb_drive".to_string(),
+            as_of: fixture_time(),
+        };
+        let build = |ruleset: PolicyRuleset, resolution: ConflictResolution| {
+            match build_context_package_with_ruleset(
+                &records,
+                query.clone(),
+                "txn_conflict_resolution",
+                ruleset.strategy(),
+                None,
+                resolution,
+            ) {
+                Ok(package) => package,
+                Err(err) => panic!("context package should build: {err}"),
+            }
+        };
+
+        for ruleset in [PolicyRuleset::Precedence, PolicyRuleset::MostRecentWins] {
+            for (resolution, expected) in [
+                (ConflictResolution::Inconclusive, AnswerResult::Inconclusive),
+                (ConflictResolution::DenyOverrides, AnswerResult::Deny),
+                (ConflictResolution::AllowOverrides, AnswerResult::Allow),
+            ] {
+                let package = build(ruleset, resolution);
+                assert_eq!(package.answer.result, expected, "{ruleset:?} {resolution:?}");
+                assert_eq!(package.determinism.conflict_resolution, resolution);
+            }
+        }
+
+        let inconclusive = build(PolicyRuleset::Precedence, ConflictResolution::Inconclusive);
+        let json = serde_json::to_value(&inconclusive.determinism)
+            .unwrap_or_else(|err| panic!("determinism should serialize: {err}"));
+        assert!(json.get("conflict_resolution").is_none());
+        let denied = build(PolicyRuleset::Precedence, ConflictResolution::DenyOverrides);
+        let json = serde_json::to_value(&denied.determinism)
+            .unwrap_or_else(|err| panic!("determinism should serialize: {err}"));
+        assert_eq!(json["conflict_resolution"], "deny_overrides");
+        assert_eq!(
+            ConflictResolution::parse("allow_overrides"),
+            Some(ConflictResolution::AllowOverrides)
+        );
+        assert_eq!(
+            ConflictResolution::DenyOverrides.snapshot_extra().as_deref(),
+            Some("conflict_resolution=deny_overrides")
+        );
+        assert_eq!(ConflictResolution::Inconclusive.snapshot_extra(), None);
+    }
+
     // Test IDs: TPERF-001
     #[test]
     fn policy_context_package_meets_baseline_budget() {
@@ -0,0 +1,315 @@
+//! MCP tool catalog: `ask` and `recall` read the store, `add` writes one record.
+
+use anyhow::{anyhow, bail, Context, Result};
+use memory_kernel_api::{
+    AddConstraintRequest, AddSummaryRequest, AskRequest, MemoryKernelApi, RecallRequest,
+};
+use memory_kernel_core::{
+    AssumptionStatus, Authority, ConflictResolution, ConstraintEffect, PolicyRuleset, RecordType,
+    RiskSeverity, TaskStatus, TruthStatus,
+};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use time::OffsetDateTime;
+
+const RECORD_TYPES: [&str; 8] =
+    ["constraint", "decision", "preference", "event", "outcome", "task", "risk", "assumption"];
+
+/// Tools bound to one store handle. Records written through `add` carry the configured
+/// writer, so agents cannot impersonate other writers.
+#[derive(Debug, Clone)]
+pub struct Tools {
+    api: MemoryKernelApi,
+    writer: String,
+    read_only: bool,
+}
+
+impl Tools {
+    pub fn new(api: MemoryKernelApi, writer: String, read_only: bool) -> Self {
+        Self { api, writer, read_only }
+    }
+
+    /// `tools/list` entries; `add` is omitted in read-only mode.
+    pub fn definitions(&self) -> Vec<Value> {
+        let mut tools = vec![ask_definition(), recall_definition()];
+        if !self.read_only {
+            tools.push(add_definition());
+        }
+        tools
+    }
+
+    /// Run tool `name`. Returns `None` for a tool this server does not offer, so the caller can
+    /// answer with a protocol error; argument and store failures are tool results instead.
+    pub fn call(&self, name: &str, arguments: Value) -> Option<Result<Value>> {
+        let outcome = match name {
+            "ask" => self.ask(arguments),
+            "recall" => self.recall(arguments),
+            "add" if !self.read_only => self.add(arguments),
+            _ => return None,
+        };
+        Some(outcome)
+    }
+
+    fn ask(&self, arguments: Value) -> Result<Value> {
+        let args: AskArgs = parse_arguments(arguments)?;
+        let package = self.api.query_ask(AskRequest {
+            text: args.text,
+            actor: args.actor,
+            action: args.action,
+            resource: args.resource,
+            as_of: args.as_of,
+            ruleset: args.ruleset,
+            conflict_resolution: args.conflict_resolution,
+        })?;
+        Ok(serde_json::to_value(package)?)
+    }
+
+    fn recall(&self, arguments: Value) -> Result<Value> {
+        let args: RecallArgs = parse_arguments(arguments)?;
+        let package = self.api.query_recall(RecallRequest {
+            text: args.text,
+            record_types: args.record_types,
+            as_of: args.as_of,
+            tags: args.tags,
+        })?;
+        Ok(serde_json::to_value(package)?)
+    }
+
+    fn add(&self, arguments: Value) -> Result<Value> {
+        let args: AddArgs = parse_arguments(arguments)?;
+        let record = if args.record_type == RecordType::Constraint {
+            self.api.add_constraint(args.into_constraint(self.writer.clone())?)?
+        } else {
+            self.api.add_summary(args.into_summary(self.writer.clone())?)?
+        };
+        Ok(serde_json::to_value(record)?)
+    }
+}
+
+fn parse_arguments<T: DeserializeOwned>(arguments: Value) -> Result<T> {
+    serde_json::from_value(arguments).context("invalid tool arguments")
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AskArgs {
+    text: String,
+    actor: String,
+    action: String,
+    resource: String,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    as_of: Option<OffsetDateTime>,
+    #[serde(default)]
+    ruleset: PolicyRuleset,
+    #[serde(default)]
+    conflict_resolution: Option<ConflictResolution>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RecallArgs {
+    text: String,
+    #[serde(default)]
+    record_types: Vec<RecordType>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    as_of: Option<OffsetDateTime>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AddArgs {
+    record_type: RecordType,
+    summary: Option<String>,
+    actor: Option<String>,
+    action: Option<String>,
+    resource: Option<String>,
+    effect: Option<ConstraintEffect>,
+    note: Option<String>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    expires_at: Option<OffsetDateTime>,
+    task_status: Option<TaskStatus>,
+    risk_severity: Option<RiskSeverity>,
+    assumption_status: Option<AssumptionStatus>,
+    justification: String,
+    source_uri: String,
+    #[serde(default)]
+    evidence: Vec<String>,
+    confidence: Option<f32>,
+    #[serde(default = "default_truth_status")]
+    truth_status: TruthStatus,
+    #[serde(default = "default_authority")]
+    authority: Authority,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    effective_at: Option<OffsetDateTime>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+fn default_truth_status() -> TruthStatus {
+    TruthStatus::Asserted
+}
+
+/// Agent-written records default to the lowest authority until a human promotes them.
+fn default_authority() -> Authority {
+    Authority::Note
+}
+
+impl AddArgs {
+    fn into_constraint(self, writer: String) -> Result<AddConstraintRequest> {
+        if self.summary.is_some() {
+            bail!("constraint records take actor/action/resource/effect, not summary");
+        }
+        let required = |value: Option<String>, field: &str| {
+            value.ok_or_else(|| anyhow!("{field} is required for record_type=constraint"))
+        };
+        Ok(AddConstraintRequest {
+            actor: required(self.actor, "actor")?,
+            action: required(self.action, "action")?,
+            resource: required(self.resource, "resource")?,
+            effect: self
+                .effect
+                .ok_or_else(|| anyhow!("effect is required for record_type=constraint"))?,
+            note: self.note,
+            expires_at: self.expires_at,
+            memory_id: None,
+            version: 1,
+            writer,
+            justification: self.justification,
+            source_uri: self.source_uri,
+            source_hash: None,
+            evidence: self.evidence,
+            confidence: self.confidence,
+            truth_status: self.truth_status,
+            authority: self.authority,
+            created_at: None,
+            effective_at: self.effective_at,
+            supersedes: Vec::new(),
+            contradicts: Vec::new(),
+            tags: self.tags,
+        })
+    }
+
+    fn into_summary(self, writer: String) -> Result<AddSummaryRequest> {
+        let record_type = self.record_type.as_str();
+        if self.actor.is_some()
+            || self.action.is_some()
+            || self.resource.is_some()
+            || self.effect.is_some()
+            || self.note.is_some()
+            || self.expires_at.is_some()
+        {
+            bail!(
+                "actor/action/resource/effect/note/expires_at apply only to record_type=constraint"
+            );
+        }
+        Ok(AddSummaryRequest {
+            record_type: self.record_type,
+            summary: self
+                .summary
+                .ok_or_else(|| anyhow!("summary is required for record_type={record_type}"))?,
+            task_status: self.task_status,
+            risk_severity: self.risk_severity,
+            assumption_status: self.assumption_status,
+            memory_id: None,
+            version: 1,
+            writer,
+            justification: self.justification,
+            source_uri: self.source_uri,
+            source_hash: None,
+            evidence: self.evidence,
+            confidence: self.confidence,
+            truth_status: self.truth_status,
+            authority: self.authority,
+            created_at: None,
+            effective_at: self.effective_at,
+            supersedes: Vec::new(),
+            contradicts: Vec::new(),
+            tags: self.tags,
+        })
+    }
+}
+
+fn ask_definition() -> Value {
+    json!({
+        "name": "ask",
+        "title": "Ask policy",
+        "description": "Decide whether an actor may perform an action on a resource from the stored constraints. Returns a context package whose answer is allow, deny, or inconclusive, with the records that decided it.",
+        "inputSchema": {
+            "type": "object",
+            "properties": {
+                "text": { "type": "string", "description": "The question in natural language." },
+                "actor": { "type": "string", "description": "Who acts, e.g. a user or role; `*` matches any." },
+                "action": { "type": "string" },
+                "resource": { "type": "string" },
+                "as_of": { "type": "string", "format": "date-time", "description": "Evaluate as of this RFC 3339 time; defaults to now." },
+                "ruleset": { "type": "string", "enum": ["precedence", "deny_overrides", "most_recent_wins"] },
+                "conflict_resolution": { "type": "string", "enum": ["inconclusive", "deny_overrides", "allow_overrides"], "description": "How an unresolved allow/deny conflict is answered; defaults to the server setting." }
+            },
+            "required": ["text", "actor", "action", "resource"],
+            "additionalProperties": false
+        },
+        "annotations": { "readOnlyHint": true, "openWorldHint": false }
+    })
+}
+
+fn recall_definition() -> Value {
+    json!({
+        "name": "recall",
+        "title": "Recall memory",
+        "description": "Retrieve decisions, preferences, events, outcomes and other records relevant to a text query, ranked deterministically.",
+        "inputSchema": {
+            "type": "object",
+            "properties": {
+                "text": { "type": "string" },
+                "record_types": {
+                    "type": "array",
+                    "items": { "type": "string", "enum": RECORD_TYPES },
+                    "description": "Record types to search; defaults to every type except constraint."
+                },
+                "as_of": { "type": "string", "format": "date-time" },
+                "tags": { "type": "array", "items": { "type": "string" }, "description": "Only recall records carrying every one of these tags." }
+            },
+            "required": ["text"],
+            "additionalProperties": false
+        },
+        "annotations": { "readOnlyHint": true, "openWorldHint": false }
+    })
+}
+
+fn add_definition() -> Value {
+    json!({
+        "name": "add",
+        "title": "Add memory record",
+        "description": "Write one memory record. Constraints take actor, action, resource and effect; every other record type takes summary. Records default to truth_status asserted and authority note.",
+        "inputSchema": {
+            "type": "object",
+            "properties": {
+                "record_type": { "type": "string", "enum": RECORD_TYPES },
+                "summary": { "type": "string" },
+                "actor": { "type": "string" },
+                "action": { "type": "string" },
+                "resource": { "type": "string" },
+                "effect": { "type": "string", "enum": ["allow", "deny"] },
+                "note": { "type": "string" },
+                "expires_at": { "type": "string", "format": "date-time" },
+                "task_status": { "type": "string", "enum": ["open", "in_progress", "done", "cancelled"] },
+                "risk_severity": { "type": "string", "enum": ["low", "medium", "high", "critical"] },
+                "assumption_status": { "type": "string", "enum": ["unverified", "validated", "invalidated"] },
+                "justification": { "type": "string", "description": "Why this record is being written." },
+                "source_uri": { "type": "string", "description": "Where the information came from." },
+                "evidence": { "type": "array", "items": { "type": "string" } },
+                "confidence": { "type": "number", "minimum": 0, "maximum": 1 },
+                "truth_status": { "type": "string", "enum": ["asserted", "observed", "inferred", "speculative"] },
+                "authority": { "type": "string", "enum": ["authoritative", "derived", "note"] },
+                "effective_at": { "type": "string", "format": "date-time" },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["record_type", "justification", "source_uri"],
+            "additionalProperties": false
+        },
+        "annotations": { "readOnlyHint": false, "destructiveHint": false, "idempotentHint": false, "openWorldHint": false }
+    })
+}
@@ -0,0 +1,193 @@
+//! Log subscriber setup for the service: human-readable `pretty` output or one JSON object
+//! per line, carrying the fields of every enclosing span (such as `request_id`).
+
+use std::fmt;
+use std::io::IsTerminal;
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::{Map, Value};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tracing::field::{Field, Visit};
+use tracing::span::Record;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::EnvFilter;
+
+/// Output format selected with `--log-format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    Pretty,
+    Json,
+}
+
+/// Install the global subscriber, writing to stderr. `level` takes `EnvFilter` directives such
+/// as `info` or `warn,memory_kernel_service=debug`.
+///
+/// # Errors
+/// Returns an error when `level` does not parse or a subscriber is already installed.
+pub fn init(level: &str, format: LogFormat) -> Result<()> {
+    let filter =
+        EnvFilter::try_new(level).with_context(|| format!("invalid --log-level {level:?}"))?;
+    let builder = tracing_subscriber::fmt().with_env_filter(filter).with_writer(std::io::stderr);
+    let installed = match format {
+        LogFormat::Pretty => builder.with_ansi(std::io::stderr().is_terminal()).pretty().try_init(),
+        LogFormat::Json => {
+            builder.with_ansi(false).fmt_fields(JsonFields).event_format(JsonEvents).try_init()
+        }
+    };
+    installed.map_err(|err| anyhow!("failed to install logger: {err}"))
+}
+
+/// Collects recorded fields into a JSON object, keeping numbers and booleans typed.
+#[derive(Default)]
+struct JsonVisitor(Map<String, Value>);
+
+impl Visit for JsonVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_owned(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_owned(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_owned(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_owned(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_owned(), Value::from(format!("{value:?}")));
+    }
+}
+
+/// Stores span fields as a serialized JSON object so [`JsonEvents`] can merge them back.
+struct JsonFields;
+
+impl<'writer> FormatFields<'writer> for JsonFields {
+    fn format_fields<R: RecordFields>(
+        &self,
+        mut writer: Writer<'writer>,
+        fields: R,
+    ) -> fmt::Result {
+        let mut visitor = JsonVisitor::default();
+        fields.record(&mut visitor);
+        write!(writer, "{}", Value::Object(visitor.0))
+    }
+
+    fn add_fields(
+        &self,
+        current: &'writer mut FormattedFields<Self>,
+        fields: &Record<'_>,
+    ) -> fmt::Result {
+        let mut visitor = JsonVisitor(serde_json::from_str(&current.fields).unwrap_or_default());
+        fields.record(&mut visitor);
+        current.fields = Value::Object(visitor.0).to_string();
+        Ok(())
+    }
+}
+
+/// One JSON line per event: timestamp, level, target, enclosing span fields (outermost first),
+/// then the event's own fields.
+struct JsonEvents;
+
+impl<S> FormatEvent<S, JsonFields> for JsonEvents
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, JsonFields>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut line = Map::new();
+        if let Ok(timestamp) = OffsetDateTime::now_utc().format(&Rfc3339) {
+            line.insert("timestamp".to_owned(), Value::from(timestamp));
+        }
+        line.insert("level".to_owned(), Value::from(metadata.level().as_str()));
+        line.insert("target".to_owned(), Value::from(metadata.target()));
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                let extensions = span.extensions();
+                let Some(fields) = extensions.get::<FormattedFields<JsonFields>>() else {
+                    continue;
+                };
+                if let Ok(Value::Object(fields)) = serde_json::from_str(fields) {
+                    line.extend(fields);
+                }
+            }
+        }
+        let mut visitor = JsonVisitor::default();
+        event.record(&mut visitor);
+        line.extend(visitor.0);
+        writeln!(writer, "{}", Value::Object(line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap_or_else(std::sync::PoisonError::into_inner).extend(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // Test IDs: TSVC-031
+    #[test]
+    fn json_format_writes_event_and_span_fields_per_line() {
+        assert!(init("memory_kernel_service=loud", LogFormat::Json).is_err());
+
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .fmt_fields(JsonFields)
+            .event_format(JsonEvents)
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", request_id = "01TEST", path = "/v1/health");
+            let _entered = span.enter();
+            tracing::info!(status = 200_u16, slow = false, "request completed");
+        });
+
+        let output = captured.0.lock().unwrap_or_else(std::sync::PoisonError::into_inner).clone();
+        let output = String::from_utf8(output).unwrap_or_else(|err| panic!("not UTF-8: {err}"));
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1, "{output}");
+        let line: Value = serde_json::from_str(lines[0])
+            .unwrap_or_else(|err| panic!("log line is not JSON: {err}; line={}", lines[0]));
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["request_id"], "01TEST");
+        assert_eq!(line["path"], "/v1/health");
+        assert_eq!(line["status"], 200);
+        assert_eq!(line["slow"], false);
+        assert_eq!(line["message"], "request completed");
+        assert!(line["timestamp"].as_str().is_some_and(|ts| ts.ends_with('Z')));
+    }
+}
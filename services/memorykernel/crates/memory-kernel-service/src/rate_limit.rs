@@ -0,0 +1,103 @@
+//! Per-client token buckets shielding the single store writer from runaway clients.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Buckets tracked before idle (fully refilled) ones are evicted.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Identity a request is metered under: its API key when it presents a configured one,
+/// otherwise its peer address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClientKey {
+    ApiKey([u8; 32]),
+    Ip(IpAddr),
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Token-bucket limiter: each client holds up to `burst` requests and regains
+/// `per_minute` of them every minute.
+#[derive(Debug)]
+pub struct RateLimiter {
+    burst: f64,
+    per_second: f64,
+    buckets: Mutex<HashMap<ClientKey, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Returns `None` when `per_minute` is `0`, which disables limiting. `burst` defaults to
+    /// `per_minute`.
+    pub fn new(per_minute: u32, burst: Option<u32>) -> Option<Self> {
+        if per_minute == 0 {
+            return None;
+        }
+        Some(Self {
+            burst: f64::from(burst.unwrap_or(per_minute).max(1)),
+            per_second: f64::from(per_minute) / 60.0,
+            buckets: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Take one request token for `client`, or return how long until one is available.
+    pub fn acquire(&self, client: ClientKey) -> Result<(), Duration> {
+        self.acquire_at(client, Instant::now())
+    }
+
+    fn acquire_at(&self, client: ClientKey, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(&client) {
+            buckets.retain(|_, bucket| self.refill(bucket, now) < self.burst);
+        }
+        let bucket =
+            buckets.entry(client).or_insert(Bucket { tokens: self.burst, refilled_at: now });
+        let tokens = self.refill(bucket, now);
+        if tokens >= 1.0 {
+            bucket.tokens = tokens - 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - tokens) / self.per_second))
+        }
+    }
+
+    fn refill(&self, bucket: &mut Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.per_second).min(self.burst);
+        bucket.refilled_at = now;
+        bucket.tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    // Test IDs: TSVC-028
+    #[test]
+    fn rate_limiter_meters_clients_independently_and_refills() {
+        assert!(RateLimiter::new(0, Some(5)).is_none());
+        let limiter =
+            RateLimiter::new(60, Some(2)).unwrap_or_else(|| panic!("limiter should be enabled"));
+        let start = Instant::now();
+        let ip = ClientKey::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let key = ClientKey::ApiKey([7; 32]);
+
+        assert_eq!(limiter.acquire_at(ip, start), Ok(()));
+        assert_eq!(limiter.acquire_at(ip, start), Ok(()));
+        let wait = limiter.acquire_at(ip, start).err();
+        assert_eq!(wait, Some(Duration::from_secs(1)));
+        assert_eq!(limiter.acquire_at(key, start), Ok(()));
+
+        let later = start + Duration::from_millis(1500);
+        assert_eq!(limiter.acquire_at(ip, later), Ok(()));
+        assert!(limiter.acquire_at(ip, later).is_err());
+    }
+}
@@ -0,0 +1,280 @@
+//! HTTPS termination for the service.
+//!
+//! TLS and plaintext clients share the bound port: each connection is sniffed on its first
+//! byte, handshakes go through rustls, and anything else gets the configured
+//! [`PlaintextPolicy`].
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use axum::extract::ConnectInfo;
+use axum::http::header::HOST;
+use axum::http::uri::PathAndQuery;
+use axum::http::{HeaderMap, StatusCode, Uri};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::{Extension, Router};
+use hyper_util::rt::TokioIo;
+use hyper_util::service::TowerToHyperService;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tokio_rustls::rustls::crypto::ring;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::TlsAcceptor;
+
+/// Content type of a TLS record that opens a handshake (`ClientHello`).
+const TLS_HANDSHAKE_RECORD: u8 = 0x16;
+
+/// What a TLS-enabled service does with plaintext HTTP connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PlaintextPolicy {
+    /// Close the connection without a response.
+    Deny,
+    /// Answer every request with `308 Permanent Redirect` to the same URL over `https`.
+    Redirect,
+}
+
+/// Builds a rustls acceptor from a PEM certificate chain and PEM private key.
+///
+/// # Errors
+/// Returns an error when either file cannot be read or parsed, the chain is empty, or the key
+/// does not fit the certificate.
+pub fn load_acceptor(cert_path: &Path, key_path: &Path) -> Result<TlsAcceptor> {
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+        .with_context(|| format!("failed to read TLS certificate {}", cert_path.display()))?;
+    if certs.is_empty() {
+        bail!("TLS certificate {} contains no certificates", cert_path.display());
+    }
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .with_context(|| format!("failed to read TLS private key {}", key_path.display()))?;
+    let mut config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .context("failed to select TLS protocol versions")?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .context("TLS private key does not match the certificate")?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Serves `app` over TLS on `listener`, applying `plaintext` to non-TLS connections. Once
+/// `shutdown` flips, stops accepting and returns after open connections finish their
+/// in-flight requests.
+///
+/// # Errors
+/// Never returns an error; the signature matches `axum::serve`.
+pub async fn serve(
+    listener: TcpListener,
+    app: Router,
+    acceptor: TlsAcceptor,
+    plaintext: PlaintextPolicy,
+    shutdown: watch::Receiver<bool>,
+) -> Result<()> {
+    let mut connections = JoinSet::new();
+    loop {
+        let accepted = tokio::select! {
+            accepted = listener.accept() => accepted,
+            () = crate::drained(shutdown.clone()) => break,
+        };
+        // Reap finished connections so the set does not grow with the connection count.
+        while connections.try_join_next().is_some() {}
+        let (stream, peer) = match accepted {
+            Ok(accepted) => accepted,
+            Err(err) => {
+                // Usually descriptor exhaustion; back off like `axum::serve` does.
+                tracing::warn!("failed to accept connection: {err}");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+        // Same peer-address extension `into_make_service_with_connect_info` provides.
+        let app = app.clone().layer(Extension(ConnectInfo(peer)));
+        let acceptor = acceptor.clone();
+        let shutdown = shutdown.clone();
+        connections.spawn(async move {
+            // Failed handshakes and dropped clients only affect their own connection.
+            let _ = serve_connection(stream, app, acceptor, plaintext, shutdown).await;
+        });
+    }
+    while connections.join_next().await.is_some() {}
+    Ok(())
+}
+
+async fn serve_connection(
+    stream: TcpStream,
+    app: Router,
+    acceptor: TlsAcceptor,
+    plaintext: PlaintextPolicy,
+    shutdown: watch::Receiver<bool>,
+) -> Result<()> {
+    let mut first = [0_u8; 1];
+    if stream.peek(&mut first).await? == 0 {
+        return Ok(());
+    }
+    if first[0] == TLS_HANDSHAKE_RECORD {
+        let stream = acceptor.accept(stream).await?;
+        return serve_http1(stream, app, shutdown).await;
+    }
+    match plaintext {
+        PlaintextPolicy::Deny => Ok(()),
+        PlaintextPolicy::Redirect => {
+            serve_http1(stream, Router::new().fallback(redirect_to_https), shutdown).await
+        }
+    }
+}
+
+async fn serve_http1<S>(io: S, app: Router, shutdown: watch::Receiver<bool>) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let connection = hyper::server::conn::http1::Builder::new()
+        .serve_connection(TokioIo::new(io), TowerToHyperService::new(app));
+    tokio::pin!(connection);
+    tokio::select! {
+        result = connection.as_mut() => return Ok(result?),
+        () = crate::drained(shutdown) => connection.as_mut().graceful_shutdown(),
+    }
+    // Finish the in-flight request, then close instead of keeping the connection alive.
+    connection.await?;
+    Ok(())
+}
+
+async fn redirect_to_https(headers: HeaderMap, uri: Uri) -> Response {
+    // The TLS listener shares the port, so the `Host` header (port included) is kept as-is.
+    let Some(host) = headers.get(HOST).and_then(|value| value.to_str().ok()) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    let path = uri.path_and_query().map_or("/", PathAndQuery::as_str);
+    Redirect::permanent(&format!("https://{host}{path}")).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use axum::routing::get;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+    use tokio_rustls::TlsConnector;
+
+    use super::*;
+
+    const REQUEST: &[u8] =
+        b"GET /v1/health?probe=1 HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls").join(name)
+    }
+
+    async fn spawn_server(
+        plaintext: PlaintextPolicy,
+    ) -> (std::net::SocketAddr, watch::Sender<bool>, tokio::task::JoinHandle<Result<()>>) {
+        let acceptor = load_acceptor(&fixture("server.pem"), &fixture("server.key"))
+            .unwrap_or_else(|err| panic!("fixture certificate should load: {err:#}"));
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap_or_else(|err| panic!("failed to bind test listener: {err}"));
+        let addr = listener.local_addr().unwrap_or_else(|err| panic!("no local addr: {err}"));
+        let app = Router::new().route("/v1/health", get(|| async { "ok" }));
+        let (shutdown, draining) = watch::channel(false);
+        let server = tokio::spawn(serve(listener, app, acceptor, plaintext, draining));
+        (addr, shutdown, server)
+    }
+
+    async fn exchange<S: AsyncRead + AsyncWrite + Unpin>(mut stream: S) -> String {
+        stream.write_all(REQUEST).await.unwrap_or_else(|err| panic!("write failed: {err}"));
+        let mut response = Vec::new();
+        // A denied connection may surface as a reset rather than a clean EOF.
+        let _ = stream.read_to_end(&mut response).await;
+        String::from_utf8_lossy(&response).into_owned()
+    }
+
+    async fn connect(addr: std::net::SocketAddr) -> TcpStream {
+        TcpStream::connect(addr).await.unwrap_or_else(|err| panic!("connect failed: {err}"))
+    }
+
+    fn connector() -> TlsConnector {
+        let mut roots = RootCertStore::empty();
+        for cert in CertificateDer::pem_file_iter(fixture("ca.pem"))
+            .unwrap_or_else(|err| panic!("failed to read CA fixture: {err}"))
+        {
+            let cert = cert.unwrap_or_else(|err| panic!("invalid CA fixture: {err}"));
+            roots.add(cert).unwrap_or_else(|err| panic!("CA fixture rejected: {err}"));
+        }
+        let config = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap_or_else(|err| panic!("no TLS versions: {err}"))
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        TlsConnector::from(Arc::new(config))
+    }
+
+    // Test IDs: TSVC-027
+    #[tokio::test]
+    async fn tls_listener_serves_https_and_applies_plaintext_policy() {
+        let (addr, _shutdown, _server) = spawn_server(PlaintextPolicy::Redirect).await;
+        let server_name =
+            "localhost".try_into().unwrap_or_else(|err| panic!("invalid server name: {err}"));
+        let tls = connector()
+            .connect(server_name, connect(addr).await)
+            .await
+            .unwrap_or_else(|err| panic!("TLS handshake failed: {err}"));
+        let response = exchange(tls).await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.ends_with("ok"), "{response}");
+
+        let response = exchange(connect(addr).await).await;
+        assert!(response.starts_with("HTTP/1.1 308"), "{response}");
+        assert!(
+            response.contains("location: https://localhost/v1/health?probe=1\r\n"),
+            "{response}"
+        );
+
+        let (addr, _shutdown, _server) = spawn_server(PlaintextPolicy::Deny).await;
+        assert_eq!(exchange(connect(addr).await).await, "");
+
+        let missing = load_acceptor(&fixture("missing.pem"), &fixture("server.key"));
+        assert!(missing.is_err_and(|err| format!("{err:#}").contains("missing.pem")));
+        let mismatched = load_acceptor(&fixture("ca.pem"), &fixture("server.key"));
+        assert!(mismatched.is_err());
+    }
+
+    // Test IDs: TSVC-034
+    #[tokio::test]
+    async fn tls_listener_drains_keep_alive_connections_on_shutdown() {
+        let (addr, shutdown, server) = spawn_server(PlaintextPolicy::Deny).await;
+        let server_name =
+            "localhost".try_into().unwrap_or_else(|err| panic!("invalid server name: {err}"));
+        let mut tls = connector()
+            .connect(server_name, connect(addr).await)
+            .await
+            .unwrap_or_else(|err| panic!("TLS handshake failed: {err}"));
+        tls.write_all(b"GET /v1/health HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap_or_else(|err| panic!("write failed: {err}"));
+        let mut response = Vec::new();
+        while !response.ends_with(b"ok") {
+            let mut chunk = [0_u8; 512];
+            let read =
+                tls.read(&mut chunk).await.unwrap_or_else(|err| panic!("read failed: {err}"));
+            assert!(read > 0, "connection closed before the response completed");
+            response.extend_from_slice(&chunk[..read]);
+        }
+
+        shutdown.send_replace(true);
+        let stopped = tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap_or_else(|_| panic!("server did not drain within 5s"))
+            .unwrap_or_else(|err| panic!("server task failed: {err}"));
+        assert!(stopped.is_ok());
+        let mut rest = Vec::new();
+        let _ = tls.read_to_end(&mut rest).await;
+        assert!(rest.is_empty(), "idle connection should close without data");
+    }
+}
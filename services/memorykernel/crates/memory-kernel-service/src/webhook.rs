@@ -0,0 +1,562 @@
+//! Signed webhook notifications for constraint and decision writes.
+//!
+//! Events are queued as records are written and sent in the background, retrying failed
+//! attempts with exponential backoff. Every attempt is logged to a `webhook_deliveries` table in
+//! a service-owned `SQLite` file, kept apart from the memory store and its snapshots.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use hmac::{Hmac, Mac};
+use memory_kernel_core::{MemoryId, MemoryRecord, MemoryVersionId, RecordType, TruthStatus};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use sha2::Sha256;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tokio::sync::{mpsc, Semaphore};
+use ulid::Ulid;
+
+/// Environment variable holding the signing secret when `--webhook-secret-file` is not given.
+pub const SECRET_ENV: &str = "MEMORY_KERNEL_WEBHOOK_SECRET";
+
+const MIN_SECRET_LEN: usize = 16;
+
+/// `t=<unix seconds>,v1=<hex HMAC-SHA256 of "<t>.<body>">`.
+pub const SIGNATURE_HEADER: &str = "x-memorykernel-signature";
+const EVENT_HEADER: &str = "x-memorykernel-event";
+const DELIVERY_HEADER: &str = "x-memorykernel-delivery";
+
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_CONCURRENT_DELIVERIES: usize = 8;
+
+const CREATE_DELIVERIES_SQL: &str = r"
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  event_id TEXT NOT NULL,
+  event_type TEXT NOT NULL,
+  memory_version_id TEXT NOT NULL,
+  url TEXT NOT NULL,
+  attempt INTEGER NOT NULL CHECK (attempt > 0),
+  status TEXT NOT NULL CHECK (status IN ('delivered', 'retrying', 'failed')),
+  http_status INTEGER,
+  error TEXT,
+  attempted_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_event ON webhook_deliveries(event_id);
+";
+
+/// Where and how events are delivered. `Debug` never prints the secret.
+#[derive(Clone)]
+pub struct WebhookConfig {
+    pub url: String,
+    secret: Vec<u8>,
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+}
+
+impl std::fmt::Debug for WebhookConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookConfig")
+            .field("url", &self.url)
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WebhookConfig {
+    /// # Errors
+    /// Returns an error when the secret is shorter than 16 bytes or `max_attempts` is `0`.
+    pub fn new(url: String, secret: &str, max_attempts: u32) -> Result<Self> {
+        let secret = secret.trim();
+        if secret.len() < MIN_SECRET_LEN {
+            bail!("webhook secret MUST be at least {MIN_SECRET_LEN} characters");
+        }
+        if max_attempts == 0 {
+            bail!("webhook max attempts MUST be at least 1");
+        }
+        Ok(Self {
+            url,
+            secret: secret.as_bytes().to_vec(),
+            max_attempts,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+        })
+    }
+
+    /// Read the secret from `secret_file` when given, else from [`SECRET_ENV`].
+    ///
+    /// # Errors
+    /// Returns an error when neither source holds a secret, or [`WebhookConfig::new`] rejects it.
+    pub fn resolve(url: String, secret_file: Option<&Path>, max_attempts: u32) -> Result<Self> {
+        let secret = if let Some(path) = secret_file {
+            std::fs::read_to_string(path)
+                .with_context(|| format!("failed to read webhook secret {}", path.display()))?
+        } else {
+            std::env::var(SECRET_ENV).map_err(|_| {
+                anyhow!("--webhook-url needs a signing secret: pass --webhook-secret-file or set {SECRET_ENV}")
+            })?
+        };
+        Self::new(url, &secret, max_attempts)
+    }
+
+    /// Signature header value for `body` sent at `timestamp`.
+    pub fn sign(&self, timestamp: i64, body: &[u8]) -> String {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.secret)
+            .unwrap_or_else(|_| unreachable!("HMAC accepts keys of any length"));
+        mac.update(format!("{timestamp}.").as_bytes());
+        mac.update(body);
+        format!("t={timestamp},v1={}", hex::encode(mac.finalize().into_bytes()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum EventType {
+    #[serde(rename = "record.added")]
+    Added,
+    #[serde(rename = "record.retracted")]
+    Retracted,
+    #[serde(rename = "record.superseded")]
+    Superseded,
+}
+
+impl EventType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Added => "record.added",
+            Self::Retracted => "record.retracted",
+            Self::Superseded => "record.superseded",
+        }
+    }
+}
+
+/// Body of one webhook delivery. It identifies the record without its payload text, so
+/// receivers fetch content through the authenticated API.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub event_id: Ulid,
+    #[serde(rename = "type")]
+    pub event_type: EventType,
+    #[serde(with = "time::serde::rfc3339")]
+    pub occurred_at: OffsetDateTime,
+    pub namespace: String,
+    pub record: WebhookRecord,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookRecord {
+    pub memory_version_id: MemoryVersionId,
+    pub memory_id: MemoryId,
+    pub version: u32,
+    pub record_type: RecordType,
+    pub truth_status: TruthStatus,
+    pub writer: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub effective_at: OffsetDateTime,
+    pub supersedes: Vec<MemoryVersionId>,
+}
+
+impl WebhookEvent {
+    /// Event for a newly written `record`, or `None` unless it is a constraint or decision.
+    ///
+    /// A `retracted` record is a retraction, one that supersedes earlier versions a
+    /// supersession, and anything else an addition.
+    pub fn for_record(record: &MemoryRecord, namespace: &str) -> Option<Self> {
+        let record_type = record.payload.record_type();
+        if !matches!(record_type, RecordType::Constraint | RecordType::Decision) {
+            return None;
+        }
+        let event_type = if record.truth_status == TruthStatus::Retracted {
+            EventType::Retracted
+        } else if record.supersedes.is_empty() {
+            EventType::Added
+        } else {
+            EventType::Superseded
+        };
+        Some(Self {
+            event_id: Ulid::new(),
+            event_type,
+            occurred_at: OffsetDateTime::now_utc(),
+            namespace: namespace.to_string(),
+            record: WebhookRecord {
+                memory_version_id: record.memory_version_id,
+                memory_id: record.memory_id,
+                version: record.version,
+                record_type,
+                truth_status: record.truth_status,
+                writer: record.writer.clone(),
+                created_at: record.created_at,
+                effective_at: record.effective_at,
+                supersedes: record.supersedes.clone(),
+            },
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    Delivered,
+    /// Failed, with another attempt scheduled.
+    Retrying,
+    /// Failed on the last allowed attempt.
+    Failed,
+}
+
+impl DeliveryStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Delivered => "delivered",
+            Self::Retrying => "retrying",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DeliveryAttempt<'a> {
+    event: &'a WebhookEvent,
+    url: &'a str,
+    attempt: u32,
+    status: DeliveryStatus,
+    http_status: Option<u16>,
+    error: Option<&'a str>,
+}
+
+/// The `webhook_deliveries` table: one row per attempt.
+#[derive(Debug)]
+pub struct DeliveryLog {
+    conn: Mutex<Connection>,
+}
+
+impl DeliveryLog {
+    /// Open or create the log at `path`.
+    ///
+    /// # Errors
+    /// Returns an error when the file cannot be opened or the table cannot be created.
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("failed to open webhook delivery log {}", path.display()))?;
+        conn.busy_timeout(Duration::from_secs(5))?;
+        conn.execute_batch(CREATE_DELIVERIES_SQL).context("failed to create webhook_deliveries")?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    fn record(&self, attempt: &DeliveryAttempt<'_>) -> Result<()> {
+        let conn = self.conn.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        conn.execute(
+            "INSERT INTO webhook_deliveries(
+                event_id, event_type, memory_version_id, url, attempt, status, http_status, error,
+                attempted_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                attempt.event.event_id.to_string(),
+                attempt.event.event_type.as_str(),
+                attempt.event.record.memory_version_id.to_string(),
+                attempt.url,
+                attempt.attempt,
+                attempt.status.as_str(),
+                attempt.http_status,
+                attempt.error,
+                OffsetDateTime::now_utc().format(&Rfc3339)?,
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+/// Handle for queueing events; clones share one background delivery task.
+#[derive(Debug, Clone)]
+pub struct Webhooks {
+    queue: mpsc::UnboundedSender<WebhookEvent>,
+}
+
+impl Webhooks {
+    /// Start the background delivery task. Must be called inside a Tokio runtime.
+    ///
+    /// Events still undelivered when the process exits are lost; the log shows their last
+    /// attempt.
+    pub fn spawn(config: WebhookConfig, log: DeliveryLog) -> Self {
+        let (queue, events) = mpsc::unbounded_channel();
+        tokio::spawn(run(Arc::new(config), Arc::new(log), events));
+        Self { queue }
+    }
+
+    /// Queue an event for `record` when it is a constraint or decision.
+    pub fn notify(&self, record: &MemoryRecord, namespace: &str) {
+        if let Some(event) = WebhookEvent::for_record(record, namespace) {
+            let _ = self.queue.send(event);
+        }
+    }
+}
+
+async fn run(
+    config: Arc<WebhookConfig>,
+    log: Arc<DeliveryLog>,
+    mut events: mpsc::UnboundedReceiver<WebhookEvent>,
+) {
+    let agent = ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).redirects(0).build();
+    let slots = Arc::new(Semaphore::new(MAX_CONCURRENT_DELIVERIES));
+    while let Some(event) = events.recv().await {
+        let Ok(slot) = Arc::clone(&slots).acquire_owned().await else {
+            return;
+        };
+        let (config, log, agent) = (Arc::clone(&config), Arc::clone(&log), agent.clone());
+        tokio::spawn(async move {
+            deliver(&config, &log, &agent, &event).await;
+            drop(slot);
+        });
+    }
+}
+
+async fn deliver(
+    config: &Arc<WebhookConfig>,
+    log: &Arc<DeliveryLog>,
+    agent: &ureq::Agent,
+    event: &WebhookEvent,
+) {
+    let body = match serde_json::to_vec(event) {
+        Ok(body) => Arc::new(body),
+        Err(err) => {
+            tracing::error!(event_id = %event.event_id, "failed to encode webhook event: {err}");
+            return;
+        }
+    };
+    let mut backoff = config.initial_backoff;
+    for attempt in 1..=config.max_attempts {
+        let sent = {
+            let (config, agent, body) = (Arc::clone(config), agent.clone(), Arc::clone(&body));
+            let (event_type, event_id) = (event.event_type, event.event_id);
+            tokio::task::spawn_blocking(move || post(&config, &agent, event_type, event_id, &body))
+                .await
+                .unwrap_or_else(|err| Err((None, format!("delivery task failed: {err}"))))
+        };
+        let (status, http_status, error) = match sent {
+            Ok(code) => (DeliveryStatus::Delivered, Some(code), None),
+            Err((code, error)) if attempt < config.max_attempts => {
+                (DeliveryStatus::Retrying, code, Some(error))
+            }
+            Err((code, error)) => (DeliveryStatus::Failed, code, Some(error)),
+        };
+        let entry = DeliveryAttempt {
+            event,
+            url: &config.url,
+            attempt,
+            status,
+            http_status,
+            error: error.as_deref(),
+        };
+        if let Err(err) = log.record(&entry) {
+            tracing::warn!(event_id = %event.event_id, "failed to log webhook delivery: {err:#}");
+        }
+        match status {
+            DeliveryStatus::Delivered => return,
+            DeliveryStatus::Retrying => {
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+            DeliveryStatus::Failed => tracing::warn!(
+                event_id = %event.event_id,
+                attempts = attempt,
+                error = error.as_deref().unwrap_or_default(),
+                "webhook delivery failed"
+            ),
+        }
+    }
+}
+
+/// POST one signed attempt; `Ok` carries a 2xx status, `Err` the status (if any) and reason.
+fn post(
+    config: &WebhookConfig,
+    agent: &ureq::Agent,
+    event_type: EventType,
+    event_id: Ulid,
+    body: &[u8],
+) -> Result<u16, (Option<u16>, String)> {
+    let signature = config.sign(OffsetDateTime::now_utc().unix_timestamp(), body);
+    let sent = agent
+        .post(&config.url)
+        .set("content-type", "application/json")
+        .set(EVENT_HEADER, event_type.as_str())
+        .set(DELIVERY_HEADER, &event_id.to_string())
+        .set(SIGNATURE_HEADER, &signature)
+        .send_bytes(body);
+    match sent {
+        Ok(response) if (200..300).contains(&response.status()) => Ok(response.status()),
+        Ok(response) => Err((Some(response.status()), format!("HTTP {}", response.status()))),
+        Err(ureq::Error::Status(code, _)) => Err((Some(code), format!("HTTP {code}"))),
+        Err(ureq::Error::Transport(err)) => Err((None, err.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::body::Bytes;
+    use axum::extract::State;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use memory_kernel_core::{
+        Authority, ConstraintEffect, ConstraintPayload, ConstraintScope, MemoryPayload, Provenance,
+    };
+
+    use super::*;
+
+    type Received = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+    fn constraint_record(
+        truth_status: TruthStatus,
+        supersedes: Vec<MemoryVersionId>,
+    ) -> MemoryRecord {
+        let now = OffsetDateTime::now_utc();
+        MemoryRecord {
+            memory_version_id: MemoryVersionId::new(),
+            memory_id: MemoryId::new(),
+            version: 1,
+            created_at: now,
+            effective_at: now,
+            truth_status,
+            authority: Authority::Authoritative,
+            confidence: None,
+            writer: "tester".to_string(),
+            justification: "test".to_string(),
+            provenance: Provenance {
+                source_uri: "file:///policy.md".to_string(),
+                source_hash: None,
+                evidence: Vec::new(),
+            },
+            supersedes,
+            contradicts: Vec::new(),
+            tags: Vec::new(),
+            payload: MemoryPayload::Constraint(ConstraintPayload {
+                scope: ConstraintScope {
+                    actor: "user".to_string(),
+                    action: "use".to_string(),
+                    resource: "usb".to_string(),
+                },
+                effect: ConstraintEffect::Deny,
+                note: None,
+                expires_at: None,
+            }),
+        }
+    }
+
+    /// Receiver answering `500` to its first request and `204` afterwards.
+    async fn spawn_receiver() -> (String, Received) {
+        let received = Received::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = Router::new().route(
+            "/hook",
+            post(
+                |State((received, calls)): State<(Received, Arc<AtomicUsize>)>,
+                 headers: HeaderMap,
+                 body: Bytes| async move {
+                    received
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .push((headers, body));
+                    if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    } else {
+                        StatusCode::NO_CONTENT
+                    }
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap_or_else(|err| panic!("failed to bind receiver: {err}"));
+        let addr = listener.local_addr().unwrap_or_else(|err| panic!("no local addr: {err}"));
+        let app = app.with_state((Arc::clone(&received), calls));
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (format!("http://{addr}/hook"), received)
+    }
+
+    // Test IDs: TSVC-036
+    #[tokio::test]
+    async fn webhook_deliveries_are_signed_retried_and_logged() {
+        let previous = MemoryVersionId::new();
+        let added = constraint_record(TruthStatus::Asserted, Vec::new());
+        let superseding = constraint_record(TruthStatus::Asserted, vec![previous]);
+        let retracted = constraint_record(TruthStatus::Retracted, vec![previous]);
+        let kind = |record: &MemoryRecord| {
+            WebhookEvent::for_record(record, "default").map(|event| event.event_type)
+        };
+        assert_eq!(kind(&added), Some(EventType::Added));
+        assert_eq!(kind(&superseding), Some(EventType::Superseded));
+        assert_eq!(kind(&retracted), Some(EventType::Retracted));
+        let mut event_record = added.clone();
+        event_record.payload =
+            MemoryPayload::Event(memory_kernel_core::EventPayload { summary: "ran".to_string() });
+        assert_eq!(kind(&event_record), None);
+        assert!(WebhookConfig::new("http://x".to_string(), "short", 3).is_err());
+
+        let (url, received) = spawn_receiver().await;
+        let log_path =
+            std::env::temp_dir().join(format!("memorykernel-webhooks-{}.sqlite3", Ulid::new()));
+        let mut config = WebhookConfig::new(url.clone(), "0123456789abcdef-secret", 3)
+            .unwrap_or_else(|err| panic!("config should build: {err:#}"));
+        config.initial_backoff = Duration::from_millis(10);
+        let log = DeliveryLog::open(&log_path)
+            .unwrap_or_else(|err| panic!("delivery log should open: {err:#}"));
+        let webhooks = Webhooks::spawn(config.clone(), log);
+        webhooks.notify(&event_record, "default");
+        webhooks.notify(&retracted, "team-a");
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while received.lock().unwrap_or_else(std::sync::PoisonError::into_inner).len() < 2 {
+            assert!(tokio::time::Instant::now() < deadline, "webhook was not retried");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let deliveries = received.lock().unwrap_or_else(std::sync::PoisonError::into_inner).clone();
+        assert_eq!(deliveries.len(), 2, "only the retraction should be delivered");
+        let (headers, body) = &deliveries[1];
+        let header = |name: &str| {
+            headers.get(name).and_then(|value| value.to_str().ok()).unwrap_or_default().to_string()
+        };
+        assert_eq!(header(EVENT_HEADER), "record.retracted");
+        let signature = header(SIGNATURE_HEADER);
+        let timestamp: i64 = signature
+            .strip_prefix("t=")
+            .and_then(|rest| rest.split(',').next())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_else(|| panic!("malformed signature header {signature}"));
+        assert_eq!(signature, config.sign(timestamp, body));
+        let payload: serde_json::Value = serde_json::from_slice(body)
+            .unwrap_or_else(|err| panic!("webhook body is not JSON: {err}"));
+        assert_eq!(payload["type"], "record.retracted");
+        assert_eq!(payload["namespace"], "team-a");
+        assert_eq!(payload["event_id"], header(DELIVERY_HEADER));
+        assert_eq!(payload["record"]["memory_version_id"], retracted.memory_version_id.to_string());
+        assert!(payload["record"].get("payload").is_none());
+
+        let conn = Connection::open(&log_path)
+            .unwrap_or_else(|err| panic!("failed to reopen delivery log: {err}"));
+        let mut rows = Vec::new();
+        while rows.len() < 2 {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT attempt, status, http_status FROM webhook_deliveries ORDER BY id ASC",
+                )
+                .unwrap_or_else(|err| panic!("failed to query delivery log: {err}"));
+            rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get::<_, u32>(0)?, row.get::<_, String>(1)?, row.get::<_, u16>(2)?))
+                })
+                .and_then(Iterator::collect::<rusqlite::Result<Vec<_>>>)
+                .unwrap_or_else(|err| panic!("failed to read delivery log: {err}"));
+            assert!(tokio::time::Instant::now() < deadline, "delivery log incomplete: {rows:?}");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(rows, [(1, "retrying".to_string(), 500), (2, "delivered".to_string(), 204)]);
+        let _ = std::fs::remove_file(log_path);
+    }
+}
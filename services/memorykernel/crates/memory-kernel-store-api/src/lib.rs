@@ -0,0 +1,796 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use memory_kernel_core::{
+    AnswerResult, ContextPackage, LinkType, MemoryId, MemoryRecord, MemoryVersionId, RecordType,
+    TruthStatus,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// Maximum lexical matches a recall query loads via [`MemoryStore::recall_candidates`].
+pub const DEFAULT_RECALL_CANDIDATE_LIMIT: usize = 1_000;
+
+/// Namespace used when none is selected; rows written before schema v9 belong to it.
+pub const DEFAULT_NAMESPACE: &str = "default";
+
+/// Text that replaces every payload field scrubbed by [`MemoryStore::redact`].
+pub const REDACTION_MARKER: &str = "[REDACTED]";
+
+/// Builds the successor record in [`MemoryStore::write_successor`] from the previous record
+/// and the next free version.
+pub type SuccessorBuilder<'a> = Box<dyn FnOnce(&MemoryRecord, u32) -> Result<MemoryRecord> + 'a>;
+
+/// Record and context package persistence shared by the `SQLite` and Postgres backends.
+///
+/// Every call is scoped to the store's namespace. Backup and integrity checks stay
+/// backend-specific.
+pub trait MemoryStore: Send + std::fmt::Debug {
+    /// Namespace every call is scoped to.
+    fn namespace(&self) -> &str;
+
+    /// Scope subsequent calls to `namespace`.
+    ///
+    /// # Errors
+    /// Returns an error when `namespace` is not 1-64 ASCII letters, digits, `-`, `_`, or `.`.
+    fn set_namespace(&mut self, namespace: &str) -> Result<()>;
+
+    /// Report current and target schema versions plus pending migrations.
+    ///
+    /// # Errors
+    /// Returns an error when schema metadata cannot be read or initialized.
+    fn schema_status(&self) -> Result<SchemaStatus>;
+
+    /// Apply all forward migrations up to the latest supported schema version.
+    ///
+    /// # Errors
+    /// Returns an error when any migration step fails.
+    fn migrate(&mut self) -> Result<()>;
+
+    /// Reclaim space and refresh planner statistics, optionally copying the compacted database
+    /// to `vacuum_into`.
+    ///
+    /// # Errors
+    /// Returns an error when maintenance fails or the backend cannot vacuum into a file.
+    fn maintenance(&self, vacuum_into: Option<&Path>) -> Result<MaintenanceReport>;
+
+    /// Persist one validated append-only memory record and its payload, link, and tag rows.
+    ///
+    /// # Errors
+    /// Returns an error when validation fails or any write fails.
+    fn write_record(&mut self, record: &MemoryRecord) -> Result<()>;
+
+    /// Persist `records` atomically, in slice order; lineage links may point at records later
+    /// in the same batch.
+    ///
+    /// # Errors
+    /// Returns an error naming the zero-based batch index when a record fails validation or its
+    /// write fails. Nothing is written on error.
+    fn write_records_batch(&mut self, records: &[MemoryRecord]) -> Result<()>;
+
+    /// Append the next version of the memory holding `previous`, built by `build`. Lookup,
+    /// version allocation, and the write are atomic, so concurrent writers cannot claim the
+    /// same version.
+    ///
+    /// # Errors
+    /// Returns an error when `previous` does not exist or is already superseded, when `build`
+    /// fails or breaks the successor rules, or when any write fails.
+    fn write_successor(
+        &mut self,
+        previous: MemoryVersionId,
+        build: SuccessorBuilder<'_>,
+    ) -> Result<MemoryRecord>;
+
+    /// Persist one explicit lineage link between two memory version IDs.
+    ///
+    /// # Errors
+    /// Returns an error when accountability fields are empty or persistence fails.
+    fn add_link(
+        &mut self,
+        from: MemoryVersionId,
+        to: MemoryVersionId,
+        link_type: LinkType,
+        writer: &str,
+        justification: &str,
+    ) -> Result<()>;
+
+    /// Replace the payload text of `memory_version_id` with [`REDACTION_MARKER`] and record the
+    /// redaction; identity, lineage links, tags, and provenance are kept.
+    ///
+    /// # Errors
+    /// Returns an error when accountability fields are empty, the version does not exist in this
+    /// namespace, a redaction already exists for it, or any write fails.
+    fn redact(
+        &mut self,
+        memory_version_id: MemoryVersionId,
+        writer: &str,
+        justification: &str,
+    ) -> Result<Redaction>;
+
+    /// Stream every record ordered by `created_at` descending, then `memory_id`, then
+    /// `memory_version_id`.
+    ///
+    /// # Errors
+    /// Returns an error when the read cannot be started.
+    fn iter_records(&self) -> Result<Box<dyn Iterator<Item = Result<MemoryRecord>> + '_>>;
+
+    /// Load one page of records matching `filter`, in [`MemoryStore::iter_records`] order.
+    ///
+    /// # Errors
+    /// Returns an error when `limit` is zero, the `after` cursor does not exist, or rows cannot
+    /// be read.
+    fn list_records_filtered(&self, filter: &RecordFilter) -> Result<RecordPage>;
+
+    /// List `(memory_id, memory_version_id)` for every record without loading payloads.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read.
+    fn list_record_keys(&self) -> Result<Vec<(MemoryId, MemoryVersionId)>>;
+
+    /// Report `contradicts` links between active record versions, grouped by the `memory_id` of
+    /// the contradicting version.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read.
+    fn list_contradictions(&self) -> Result<ContradictionReport>;
+
+    /// Load recall candidates: records of `record_types` carrying every tag in `tags` and
+    /// sharing at least one lexical term with `query`, best matches first and capped at
+    /// `limit`, plus any records that supersede them.
+    ///
+    /// # Errors
+    /// Returns an error when `limit` is zero or the lookup fails.
+    fn recall_candidates_tagged(
+        &self,
+        query: &str,
+        record_types: &[RecordType],
+        tags: &[String],
+        limit: usize,
+    ) -> Result<Vec<MemoryRecord>>;
+
+    /// Load recall candidates as [`MemoryStore::recall_candidates_tagged`] without a tag filter.
+    ///
+    /// # Errors
+    /// Returns an error when `limit` is zero or the lookup fails.
+    fn recall_candidates(
+        &self,
+        query: &str,
+        record_types: &[RecordType],
+        limit: usize,
+    ) -> Result<Vec<MemoryRecord>> {
+        self.recall_candidates_tagged(query, record_types, &[], limit)
+    }
+
+    /// Store `model` embeddings for record versions atomically, replacing earlier vectors for
+    /// the same version and model.
+    ///
+    /// # Errors
+    /// Returns an error when `model` is blank, a vector is empty or not finite, a record version
+    /// does not exist, or any write fails.
+    fn put_record_embeddings(
+        &mut self,
+        model: &str,
+        embeddings: &[(MemoryVersionId, Vec<f32>)],
+    ) -> Result<()>;
+
+    /// Load every stored `model` embedding for records in this namespace, keyed by version.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read.
+    fn record_embeddings(&self, model: &str) -> Result<BTreeMap<MemoryVersionId, Vec<f32>>>;
+
+    /// Persist one Context Package artifact.
+    ///
+    /// # Errors
+    /// Returns an error when serialization or the write fails.
+    fn save_context_package(&mut self, package: &ContextPackage) -> Result<()>;
+
+    /// Retrieve a Context Package by its stable identifier.
+    ///
+    /// # Errors
+    /// Returns an error when lookup or JSON deserialization fails.
+    fn get_context_package(&self, context_package_id: &str) -> Result<Option<ContextPackage>>;
+
+    /// Load one page of context packages matching `filter`, newest `generated_at` first with
+    /// ties broken by `context_package_id`.
+    ///
+    /// # Errors
+    /// Returns an error when `limit` is zero, the `after` cursor does not exist, or rows cannot
+    /// be read.
+    fn list_context_packages(&self, filter: &ContextPackageFilter) -> Result<ContextPackagePage>;
+
+    /// Append the timing of one query to this namespace's query metrics.
+    ///
+    /// # Errors
+    /// Returns an error when the write fails.
+    fn record_query_metric(&mut self, metric: &QueryMetric) -> Result<()>;
+
+    /// List this namespace's query metrics recorded at or after `since` (every metric when
+    /// unset), oldest first.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read or decoded.
+    fn list_query_metrics(&self, since: Option<OffsetDateTime>) -> Result<Vec<QueryMetric>>;
+
+    /// Attach the Outcome record `outcome` to the context package that informed the decision
+    /// it reports on.
+    ///
+    /// # Errors
+    /// Returns an error when accountability fields are empty, the package or record does not
+    /// exist in this namespace, the record is not an Outcome, the pair is already linked, or
+    /// the write fails.
+    fn link_package_outcome(
+        &mut self,
+        context_package_id: &str,
+        outcome: MemoryVersionId,
+        writer: &str,
+        justification: &str,
+    ) -> Result<PackageOutcome>;
+
+    /// List the outcomes linked to context packages whose `selected_items` include
+    /// `memory_version_id`, oldest package first, then by `linked_at`.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read or decoded.
+    fn outcomes_following(
+        &self,
+        memory_version_id: MemoryVersionId,
+    ) -> Result<Vec<FollowingOutcome>>;
+
+    /// Export this namespace's records, context packages, and redactions as deterministic
+    /// NDJSON files plus a `manifest.json` in `out_dir`.
+    ///
+    /// # Errors
+    /// Returns an error when the backend cannot export snapshots, or export files cannot be
+    /// created, written, or serialized.
+    fn export_snapshot(&self, out_dir: &Path) -> Result<ExportManifest>;
+
+    /// Import a snapshot directory written by [`MemoryStore::export_snapshot`], skipping rows
+    /// that already exist when `skip_existing` is set.
+    ///
+    /// # Errors
+    /// Returns an error when the backend cannot import snapshots, or migration, manifest
+    /// validation, parsing, duplicate handling, or writes fail.
+    fn import_snapshot(&mut self, in_dir: &Path, skip_existing: bool) -> Result<ImportSummary>;
+}
+
+impl<T: MemoryStore + ?Sized> MemoryStore for Box<T> {
+    fn namespace(&self) -> &str {
+        (**self).namespace()
+    }
+
+    fn set_namespace(&mut self, namespace: &str) -> Result<()> {
+        (**self).set_namespace(namespace)
+    }
+
+    fn schema_status(&self) -> Result<SchemaStatus> {
+        (**self).schema_status()
+    }
+
+    fn migrate(&mut self) -> Result<()> {
+        (**self).migrate()
+    }
+
+    fn maintenance(&self, vacuum_into: Option<&Path>) -> Result<MaintenanceReport> {
+        (**self).maintenance(vacuum_into)
+    }
+
+    fn write_record(&mut self, record: &MemoryRecord) -> Result<()> {
+        (**self).write_record(record)
+    }
+
+    fn write_records_batch(&mut self, records: &[MemoryRecord]) -> Result<()> {
+        (**self).write_records_batch(records)
+    }
+
+    fn write_successor(
+        &mut self,
+        previous: MemoryVersionId,
+        build: SuccessorBuilder<'_>,
+    ) -> Result<MemoryRecord> {
+        (**self).write_successor(previous, build)
+    }
+
+    fn add_link(
+        &mut self,
+        from: MemoryVersionId,
+        to: MemoryVersionId,
+        link_type: LinkType,
+        writer: &str,
+        justification: &str,
+    ) -> Result<()> {
+        (**self).add_link(from, to, link_type, writer, justification)
+    }
+
+    fn redact(
+        &mut self,
+        memory_version_id: MemoryVersionId,
+        writer: &str,
+        justification: &str,
+    ) -> Result<Redaction> {
+        (**self).redact(memory_version_id, writer, justification)
+    }
+
+    fn iter_records(&self) -> Result<Box<dyn Iterator<Item = Result<MemoryRecord>> + '_>> {
+        (**self).iter_records()
+    }
+
+    fn list_records_filtered(&self, filter: &RecordFilter) -> Result<RecordPage> {
+        (**self).list_records_filtered(filter)
+    }
+
+    fn list_record_keys(&self) -> Result<Vec<(MemoryId, MemoryVersionId)>> {
+        (**self).list_record_keys()
+    }
+
+    fn list_contradictions(&self) -> Result<ContradictionReport> {
+        (**self).list_contradictions()
+    }
+
+    fn recall_candidates_tagged(
+        &self,
+        query: &str,
+        record_types: &[RecordType],
+        tags: &[String],
+        limit: usize,
+    ) -> Result<Vec<MemoryRecord>> {
+        (**self).recall_candidates_tagged(query, record_types, tags, limit)
+    }
+
+    fn put_record_embeddings(
+        &mut self,
+        model: &str,
+        embeddings: &[(MemoryVersionId, Vec<f32>)],
+    ) -> Result<()> {
+        (**self).put_record_embeddings(model, embeddings)
+    }
+
+    fn record_embeddings(&self, model: &str) -> Result<BTreeMap<MemoryVersionId, Vec<f32>>> {
+        (**self).record_embeddings(model)
+    }
+
+    fn save_context_package(&mut self, package: &ContextPackage) -> Result<()> {
+        (**self).save_context_package(package)
+    }
+
+    fn get_context_package(&self, context_package_id: &str) -> Result<Option<ContextPackage>> {
+        (**self).get_context_package(context_package_id)
+    }
+
+    fn list_context_packages(&self, filter: &ContextPackageFilter) -> Result<ContextPackagePage> {
+        (**self).list_context_packages(filter)
+    }
+
+    fn record_query_metric(&mut self, metric: &QueryMetric) -> Result<()> {
+        (**self).record_query_metric(metric)
+    }
+
+    fn list_query_metrics(&self, since: Option<OffsetDateTime>) -> Result<Vec<QueryMetric>> {
+        (**self).list_query_metrics(since)
+    }
+
+    fn link_package_outcome(
+        &mut self,
+        context_package_id: &str,
+        outcome: MemoryVersionId,
+        writer: &str,
+        justification: &str,
+    ) -> Result<PackageOutcome> {
+        (**self).link_package_outcome(context_package_id, outcome, writer, justification)
+    }
+
+    fn outcomes_following(
+        &self,
+        memory_version_id: MemoryVersionId,
+    ) -> Result<Vec<FollowingOutcome>> {
+        (**self).outcomes_following(memory_version_id)
+    }
+
+    fn export_snapshot(&self, out_dir: &Path) -> Result<ExportManifest> {
+        (**self).export_snapshot(out_dir)
+    }
+
+    fn import_snapshot(&mut self, in_dir: &Path, skip_existing: bool) -> Result<ImportSummary> {
+        (**self).import_snapshot(in_dir, skip_existing)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SchemaStatus {
+    pub current_version: i64,
+    pub target_version: i64,
+    pub pending_versions: Vec<i64>,
+    pub inferred_from_legacy: bool,
+}
+
+/// Outcome of [`MemoryStore::maintenance`]. Byte counts are zero for stores without a local
+/// database file, such as in-memory databases.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MaintenanceReport {
+    pub wal_bytes_before: u64,
+    pub wal_bytes_after: u64,
+    /// WAL bytes released by the truncating checkpoint.
+    pub reclaimed_bytes: u64,
+    /// A reader or writer kept the checkpoint from completing; retry later.
+    pub checkpoint_busy: bool,
+    pub checkpointed_frames: i64,
+    pub database_bytes: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vacuum_into: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vacuum_bytes: Option<u64>,
+}
+
+/// Kind of query timed by a [`QueryMetric`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMode {
+    Policy,
+    Recall,
+}
+
+impl QueryMode {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Policy => "policy",
+            Self::Recall => "recall",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "policy" => Some(Self::Policy),
+            "recall" => Some(Self::Recall),
+            _ => None,
+        }
+    }
+}
+
+/// Timing of one ask or recall query, written by [`MemoryStore::record_query_metric`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QueryMetric {
+    pub query_mode: QueryMode,
+    /// Package the query produced.
+    pub context_package_id: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub recorded_at: OffsetDateTime,
+    /// Time from loading candidates to the finished package, excluding persistence.
+    pub duration_micros: u64,
+    /// Records loaded from the store for resolution.
+    pub candidate_records: u64,
+    pub selected_items: u64,
+    pub excluded_items: u64,
+}
+
+impl QueryMetric {
+    /// Metric for `package`, built in `elapsed` from `candidate_records` loaded records and
+    /// recorded now.
+    #[must_use]
+    pub fn for_package(
+        query_mode: QueryMode,
+        package: &ContextPackage,
+        elapsed: Duration,
+        candidate_records: usize,
+    ) -> Self {
+        let count = |value: usize| u64::try_from(value).unwrap_or(u64::MAX);
+        Self {
+            query_mode,
+            context_package_id: package.context_package_id.clone(),
+            recorded_at: OffsetDateTime::now_utc(),
+            duration_micros: u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX),
+            candidate_records: count(candidate_records),
+            selected_items: count(package.selected_items.len()),
+            excluded_items: count(package.excluded_items.len()),
+        }
+    }
+}
+
+/// Latency and size summary of one query mode, from [`summarize_query_metrics`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QueryModeStats {
+    pub query_mode: QueryMode,
+    pub queries: u64,
+    pub mean_micros: u64,
+    /// Nearest-rank percentiles of `duration_micros`.
+    pub p50_micros: u64,
+    pub p95_micros: u64,
+    pub max_micros: u64,
+    pub mean_candidate_records: u64,
+    pub mean_selected_items: u64,
+    #[serde(with = "time::serde::rfc3339")]
+    pub first_recorded_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub last_recorded_at: OffsetDateTime,
+}
+
+/// Summarize `metrics` per query mode, in [`QueryMode`] order; modes without metrics are
+/// omitted. Means round down.
+#[must_use]
+pub fn summarize_query_metrics(metrics: &[QueryMetric]) -> Vec<QueryModeStats> {
+    let mut by_mode = BTreeMap::<QueryMode, Vec<&QueryMetric>>::new();
+    for metric in metrics {
+        by_mode.entry(metric.query_mode).or_default().push(metric);
+    }
+    by_mode
+        .into_iter()
+        .filter_map(|(query_mode, metrics)| {
+            let mut durations =
+                metrics.iter().map(|metric| metric.duration_micros).collect::<Vec<_>>();
+            durations.sort_unstable();
+            let queries = u64::try_from(metrics.len()).ok()?;
+            let mean = |total: u64| total / queries;
+            let percentile =
+                |percent: usize| durations[(durations.len() * percent).div_ceil(100) - 1];
+            Some(QueryModeStats {
+                query_mode,
+                queries,
+                mean_micros: mean(durations.iter().sum()),
+                p50_micros: percentile(50),
+                p95_micros: percentile(95),
+                max_micros: *durations.last()?,
+                mean_candidate_records: mean(
+                    metrics.iter().map(|metric| metric.candidate_records).sum(),
+                ),
+                mean_selected_items: mean(metrics.iter().map(|metric| metric.selected_items).sum()),
+                first_recorded_at: metrics.iter().map(|metric| metric.recorded_at).min()?,
+                last_recorded_at: metrics.iter().map(|metric| metric.recorded_at).max()?,
+            })
+        })
+        .collect()
+}
+
+/// Scrub of one record version's payload by [`MemoryStore::redact`]; the version keeps its
+/// identity, lineage links, and provenance.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Redaction {
+    pub memory_version_id: MemoryVersionId,
+    pub writer: String,
+    pub justification: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub redacted_at: OffsetDateTime,
+}
+
+/// Outcome record attached by [`MemoryStore::link_package_outcome`] to the context package
+/// that informed the decision it reports on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PackageOutcome {
+    pub context_package_id: String,
+    pub outcome_memory_version_id: MemoryVersionId,
+    pub writer: String,
+    pub justification: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub linked_at: OffsetDateTime,
+}
+
+/// Outcome that followed a context package selecting a given record, from
+/// [`MemoryStore::outcomes_following`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FollowingOutcome {
+    pub link: PackageOutcome,
+    /// `generated_at` of the linked context package.
+    #[serde(with = "time::serde::rfc3339")]
+    pub generated_at: OffsetDateTime,
+    pub outcome: MemoryRecord,
+}
+
+/// Filters and keyset pagination for [`MemoryStore::list_records_filtered`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct RecordFilter {
+    pub record_type: Option<RecordType>,
+    pub writer: Option<String>,
+    pub truth_status: Option<TruthStatus>,
+    /// Inclusive lower bound on `effective_at`.
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub effective_from: Option<OffsetDateTime>,
+    /// Exclusive upper bound on `effective_at`.
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub effective_to: Option<OffsetDateTime>,
+    /// Resume after this record, as returned in [`RecordPage::next_after`].
+    pub after: Option<MemoryVersionId>,
+    /// Only records carrying every one of these tags.
+    pub tags: Vec<String>,
+    /// Only records whose `provenance.source_uri` starts with this text.
+    pub source_uri_prefix: Option<String>,
+    /// Only records whose `provenance.source_hash` equals this digest.
+    pub source_hash: Option<String>,
+    /// Maximum number of records to return; all matching records when unset.
+    pub limit: Option<usize>,
+}
+
+/// One page of filtered records.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecordPage {
+    pub records: Vec<MemoryRecord>,
+    /// Cursor for the next page; `None` when no further records match.
+    pub next_after: Option<MemoryVersionId>,
+}
+
+/// Filters and keyset pagination for [`MemoryStore::list_context_packages`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ContextPackageFilter {
+    /// Inclusive lower bound on `generated_at`; also accepted as `since`.
+    #[serde(with = "time::serde::rfc3339::option", alias = "since")]
+    pub generated_from: Option<OffsetDateTime>,
+    /// Exclusive upper bound on `generated_at`.
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub generated_to: Option<OffsetDateTime>,
+    /// Exact match on the query actor.
+    pub actor: Option<String>,
+    /// Exact match on the query resource.
+    pub resource: Option<String>,
+    pub answer_result: Option<AnswerResult>,
+    /// Resume after this package, as returned in [`ContextPackagePage::next_after`]; also
+    /// accepted as `cursor`.
+    #[serde(alias = "cursor")]
+    pub after: Option<String>,
+    /// Maximum number of packages to return; all matching packages when unset.
+    pub limit: Option<usize>,
+}
+
+/// One page of filtered context packages.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContextPackagePage {
+    pub packages: Vec<ContextPackage>,
+    /// Cursor for the next page; `None` when no further packages match.
+    pub next_after: Option<String>,
+}
+
+/// One side of a `contradicts` link.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContradictionEndpoint {
+    pub memory_id: MemoryId,
+    pub memory_version_id: MemoryVersionId,
+    pub version: u32,
+    pub record_type: RecordType,
+    pub truth_status: TruthStatus,
+}
+
+/// A `contradicts` link whose endpoints are both active (not retracted, not superseded).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Contradiction {
+    pub from: ContradictionEndpoint,
+    pub to: ContradictionEndpoint,
+    pub writer: String,
+    pub justification: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+/// Unresolved contradictions raised by versions of one `memory_id`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContradictionGroup {
+    pub memory_id: MemoryId,
+    pub contradictions: Vec<Contradiction>,
+}
+
+/// Unresolved contradictions grouped by the contradicting record's `memory_id`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContradictionReport {
+    pub contradiction_count: usize,
+    pub groups: Vec<ContradictionGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportFileDigest {
+    pub path: String,
+    pub sha256: String,
+    pub records: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportManifest {
+    pub schema_version: i64,
+    pub exported_at: String,
+    pub files: Vec<ExportFileDigest>,
+    /// Namespace the snapshot was exported from; omitted for the default namespace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// Record selection for a partial snapshot; omitted for full exports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<ExportFilter>,
+    /// Watermark this delta export starts after; omitted for full exports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<ExportCheckpoint>,
+    /// Watermark covered by this export; pass it to `SqliteStore::export_snapshot_since` for the
+    /// next delta. Omitted by manifests written before schema v10.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint: Option<ExportCheckpoint>,
+}
+
+/// Highest record and context package write sequence numbers covered by an export.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportCheckpoint {
+    pub records_write_seq: i64,
+    pub context_packages_write_seq: i64,
+    /// Omitted by manifests written before schema v11, which carry no redactions.
+    #[serde(default)]
+    pub redactions_write_seq: i64,
+}
+
+/// Record selection for a partial snapshot export; empty fields match everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ExportFilter {
+    pub record_types: Vec<RecordType>,
+    /// Inclusive lower bound on `created_at`.
+    #[serde(with = "time::serde::rfc3339::option", skip_serializing_if = "Option::is_none")]
+    pub created_from: Option<OffsetDateTime>,
+    /// Exclusive upper bound on `created_at`.
+    #[serde(with = "time::serde::rfc3339::option", skip_serializing_if = "Option::is_none")]
+    pub created_to: Option<OffsetDateTime>,
+    pub writers: Vec<String>,
+}
+
+impl ExportFilter {
+    /// Whether this filter selects every record.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.record_types.is_empty()
+            && self.created_from.is_none()
+            && self.created_to.is_none()
+            && self.writers.is_empty()
+    }
+
+    /// Whether this filter selects `record`.
+    #[must_use]
+    pub fn matches(&self, record: &MemoryRecord) -> bool {
+        (self.record_types.is_empty() || self.record_types.contains(&record.payload.record_type()))
+            && self.created_from.map_or(true, |from| record.created_at >= from)
+            && self.created_to.map_or(true, |to| record.created_at < to)
+            && (self.writers.is_empty() || self.writers.contains(&record.writer))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub imported_records: usize,
+    pub skipped_existing_records: usize,
+    pub imported_context_packages: usize,
+    pub skipped_existing_context_packages: usize,
+    /// Redactions applied to records in this store, including records imported by this call.
+    #[serde(default)]
+    pub applied_redactions: usize,
+    /// Redactions for records this store lacks or has already redacted.
+    #[serde(default)]
+    pub skipped_redactions: usize,
+    /// Watermark of the imported snapshot, from its manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint: Option<ExportCheckpoint>,
+}
+
+/// Smallest string ordered after every string that starts with `prefix`, comparing code points,
+/// so prefix filters can run as index range scans. `None` when every such string is unbounded
+/// above (`prefix` is empty or made only of `char::MAX`).
+#[must_use]
+pub fn prefix_upper_bound(prefix: &str) -> Option<String> {
+    let mut chars = prefix.chars().collect::<Vec<_>>();
+    while let Some(last) = chars.pop() {
+        if let Some(next) = (u32::from(last) + 1..=u32::from(char::MAX)).find_map(char::from_u32) {
+            chars.push(next);
+            return Some(chars.into_iter().collect());
+        }
+    }
+    None
+}
+
+/// Check that `namespace` is usable as a store namespace.
+///
+/// # Errors
+/// Returns an error when `namespace` is not 1-64 ASCII letters, digits, `-`, `_`, or `.`.
+pub fn validate_namespace(namespace: &str) -> Result<()> {
+    let valid = (1..=64).contains(&namespace.len())
+        && namespace.chars().all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "namespace MUST be 1-64 ASCII letters, digits, '-', '_', or '.': {namespace:?}"
+        ))
+    }
+}
@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use memory_kernel_core::{
+    Authority, ConstraintEffect, ConstraintPayload, ConstraintScope, MemoryId, MemoryPayload,
+    MemoryRecord, MemoryVersionId, TruthStatus,
+};
+use memory_kernel_store_sqlite::SqliteStore;
+use time::OffsetDateTime;
+
+const RECORDS: usize = 200;
+
+fn mk_constraint(index: usize) -> MemoryRecord {
+    MemoryRecord {
+        memory_version_id: MemoryVersionId::new(),
+        memory_id: MemoryId::new(),
+        version: 1,
+        created_at: OffsetDateTime::UNIX_EPOCH,
+        effective_at: OffsetDateTime::UNIX_EPOCH,
+        truth_status: TruthStatus::Asserted,
+        authority: Authority::Authoritative,
+        confidence: Some(0.8),
+        writer: "bench".to_string(),
+        justification: "benchmark fixture".to_string(),
+        provenance: memory_kernel_core::Provenance {
+            source_uri: "file:///bench-policy.md".to_string(),
+            source_hash: Some("sha256:abc123".to_string()),
+            evidence: Vec::new(),
+        },
+        supersedes: Vec::new(),
+        contradicts: Vec::new(),
+        tags: vec!["bench".to_string()],
+        payload: MemoryPayload::Constraint(ConstraintPayload {
+            scope: ConstraintScope {
+                actor: "user".to_string(),
+                action: "use".to_string(),
+                resource: format!("device_{index}"),
+            },
+            effect: ConstraintEffect::Deny,
+            note: Some("policy import benchmark fixture".to_string()),
+            expires_at: None,
+        }),
+    }
+}
+
+/// A migrated, empty file-backed store, so commits pay for WAL writes as they do in production.
+fn fresh_store() -> (SqliteStore, PathBuf) {
+    let path =
+        std::env::temp_dir().join(format!("memorykernel-bench-{}.sqlite3", ulid::Ulid::new()));
+    let mut store = match SqliteStore::open(&path) {
+        Ok(store) => store,
+        Err(err) => panic!("failed to open benchmark store: {err}"),
+    };
+    if let Err(err) = store.migrate() {
+        panic!("failed to migrate benchmark store: {err}");
+    }
+    (store, path)
+}
+
+fn remove_store(path: &Path) {
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+    }
+}
+
+fn bench_write_record(c: &mut Criterion) {
+    c.bench_function("write_record_200_records_autocommit", |b| {
+        b.iter_batched(
+            || (fresh_store(), (0..RECORDS).map(mk_constraint).collect::<Vec<_>>()),
+            |((mut store, path), records)| {
+                for record in &records {
+                    if let Err(err) = store.write_record(record) {
+                        panic!("autocommit benchmark write failed: {err}");
+                    }
+                }
+                drop(store);
+                remove_store(&path);
+            },
+            BatchSize::PerIteration,
+        );
+    });
+
+    c.bench_function("write_record_200_records_batch", |b| {
+        b.iter_batched(
+            || (fresh_store(), (0..RECORDS).map(mk_constraint).collect::<Vec<_>>()),
+            |((mut store, path), records)| {
+                let written = store.begin_batch().and_then(|()| {
+                    for record in &records {
+                        store.write_record(record)?;
+                    }
+                    store.commit_batch()
+                });
+                if let Err(err) = written {
+                    panic!("batch benchmark write failed: {err}");
+                }
+                drop(store);
+                remove_store(&path);
+            },
+            BatchSize::PerIteration,
+        );
+    });
+}
+
+criterion_group!(write_benches, bench_write_record);
+criterion_main!(write_benches);
@@ -1,22 +1,50 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fs::{self, File};
-use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::path::Path;
 use std::str::FromStr;
 
 use anyhow::{anyhow, Context, Result};
 use memory_kernel_core::{
-    Authority, ConstraintEffect, ConstraintPayload, ConstraintScope, ContextPackage, KernelError,
-    LinkType, MemoryId, MemoryPayload, MemoryRecord, MemoryVersionId, RecordType, TruthStatus,
+    record_terms, tokenize_query_terms, AssumptionStatus, Authority, ConstraintEffect,
+    ConstraintPayload, ConstraintScope, ContextPackage, KernelError, LinkType, MemoryId,
+    MemoryPayload, MemoryRecord, MemoryVersionId, RecordType, RiskSeverity, TaskStatus,
+    TruthStatus,
+};
+use rusqlite::{
+    params, params_from_iter, Connection, DatabaseName, OptionalExtension, TransactionBehavior,
 };
-use rusqlite::{params, Connection, DatabaseName, OptionalExtension};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use time::OffsetDateTime;
 use ulid::Ulid;
 
-const LATEST_SCHEMA_VERSION: i64 = 2;
+pub use memory_kernel_store_api::summarize_query_metrics;
+use memory_kernel_store_api::{prefix_upper_bound, validate_namespace};
+pub use memory_kernel_store_api::{
+    ContextPackageFilter, ContextPackagePage, Contradiction, ContradictionEndpoint,
+    ContradictionGroup, ContradictionReport, ExportCheckpoint, ExportFileDigest, ExportFilter,
+    ExportManifest, FollowingOutcome, ImportSummary, MaintenanceReport, MemoryStore,
+    PackageOutcome, QueryMetric, QueryMode, QueryModeStats, RecordFilter, RecordPage, Redaction,
+    SchemaStatus, SuccessorBuilder, DEFAULT_NAMESPACE, DEFAULT_RECALL_CANDIDATE_LIMIT,
+    REDACTION_MARKER,
+};
+
+const LATEST_SCHEMA_VERSION: i64 = 14;
+
+const DEFAULT_RECORD_BATCH_SIZE: usize = 500;
+
+/// Cached prepared statements per connection; covers every statement on the record write path.
+const STATEMENT_CACHE_CAPACITY: usize = 64;
+
+const EXPORT_RECORDS_FILE: &str = "memory_records.ndjson";
+const EXPORT_CONTEXT_PACKAGES_FILE: &str = "context_packages.ndjson";
+const EXPORT_MANIFEST_FILE: &str = "manifest.json";
+const EXPORT_REDACTIONS_FILE: &str = "redactions.ndjson";
+
+/// Environment variable holding a hex store key, used when no key file is given.
+pub const STORE_KEY_ENV: &str = "MEMORY_KERNEL_DB_KEY";
 
 const CREATE_SCHEMA_MIGRATIONS_SQL: &str = r"
 CREATE TABLE IF NOT EXISTS schema_migrations (
@@ -192,38 +220,462 @@ CREATE INDEX IF NOT EXISTS idx_memory_links_from ON memory_links(from_memory_ver
 CREATE INDEX IF NOT EXISTS idx_memory_links_to ON memory_links(to_memory_version_id);
 ";
 
+const MIGRATION_003_SQL: &str = r"
+CREATE INDEX IF NOT EXISTS idx_memory_records_list_order
+  ON memory_records(created_at DESC, memory_id ASC, memory_version_id ASC);
+";
+
+// Holds `memory_kernel_core::record_terms` so matches equal recall's lexical overlap.
+const MIGRATION_004_SQL: &str = r#"
+CREATE VIRTUAL TABLE IF NOT EXISTS memory_recall_fts USING fts5(
+  memory_version_id UNINDEXED,
+  terms,
+  tokenize = "unicode61 tokenchars '_-'"
+);
+"#;
+
+// Reads the text `record_terms` tokenizes straight from the payload tables, so the v4 backfill
+// does not depend on columns added by later migrations.
+const MIGRATION_004_RECALL_SOURCE_SQL: &str = r"
+SELECT memory_version_id,
+       actor || ' ' || action || ' ' || resource || ' ' || COALESCE(note, '')
+  FROM constraint_payloads
+UNION ALL SELECT memory_version_id, summary FROM decision_payloads
+UNION ALL SELECT memory_version_id, summary FROM preference_payloads
+UNION ALL SELECT memory_version_id, summary FROM event_payloads
+UNION ALL SELECT memory_version_id, summary FROM outcome_payloads
+";
+
+const MIGRATION_005_SQL: &str = r"
+ALTER TABLE constraint_payloads ADD COLUMN expires_at TEXT;
+";
+
+// SQLite cannot alter a CHECK constraint, so memory_records is rebuilt to admit the new types.
+const MIGRATION_006_SQL: &str = r"
+CREATE TABLE memory_records_v6 (
+  memory_version_id TEXT PRIMARY KEY,
+  memory_id TEXT NOT NULL,
+  version INTEGER NOT NULL CHECK (version >= 1),
+  record_type TEXT NOT NULL CHECK (record_type IN ('constraint','decision','preference','event','outcome','task','risk','assumption')),
+  created_at TEXT NOT NULL,
+  effective_at TEXT NOT NULL,
+  truth_status TEXT NOT NULL CHECK (truth_status IN ('asserted','observed','inferred','speculative','retracted')),
+  authority TEXT NOT NULL CHECK (authority IN ('authoritative','derived','note')),
+  confidence REAL,
+  writer TEXT NOT NULL,
+  justification TEXT NOT NULL,
+  source_uri TEXT NOT NULL,
+  source_hash TEXT,
+  evidence_json TEXT NOT NULL,
+  UNIQUE(memory_id, version)
+);
+
+INSERT INTO memory_records_v6(
+  memory_version_id, memory_id, version, record_type, created_at, effective_at,
+  truth_status, authority, confidence, writer, justification,
+  source_uri, source_hash, evidence_json
+)
+SELECT
+  memory_version_id, memory_id, version, record_type, created_at, effective_at,
+  truth_status, authority, confidence, writer, justification,
+  source_uri, source_hash, evidence_json
+FROM memory_records;
+
+DROP TABLE memory_records;
+ALTER TABLE memory_records_v6 RENAME TO memory_records;
+
+CREATE INDEX IF NOT EXISTS idx_memory_records_type ON memory_records(record_type);
+CREATE INDEX IF NOT EXISTS idx_memory_records_memory_id ON memory_records(memory_id);
+CREATE INDEX IF NOT EXISTS idx_memory_records_effective_at ON memory_records(effective_at);
+CREATE INDEX IF NOT EXISTS idx_memory_records_list_order
+  ON memory_records(created_at DESC, memory_id ASC, memory_version_id ASC);
+
+CREATE TABLE IF NOT EXISTS task_payloads (
+  memory_version_id TEXT PRIMARY KEY,
+  summary TEXT NOT NULL,
+  status TEXT NOT NULL CHECK (status IN ('open','in_progress','done','cancelled')),
+  FOREIGN KEY (memory_version_id) REFERENCES memory_records(memory_version_id)
+);
+
+CREATE TABLE IF NOT EXISTS risk_payloads (
+  memory_version_id TEXT PRIMARY KEY,
+  summary TEXT NOT NULL,
+  severity TEXT NOT NULL CHECK (severity IN ('low','medium','high','critical')),
+  FOREIGN KEY (memory_version_id) REFERENCES memory_records(memory_version_id)
+);
+
+CREATE TABLE IF NOT EXISTS assumption_payloads (
+  memory_version_id TEXT PRIMARY KEY,
+  summary TEXT NOT NULL,
+  status TEXT NOT NULL CHECK (status IN ('unverified','validated','invalidated')),
+  FOREIGN KEY (memory_version_id) REFERENCES memory_records(memory_version_id)
+);
+";
+
+// Vectors are little-endian f32 bytes; one row per record version and embedding model.
+const MIGRATION_007_SQL: &str = r"
+CREATE TABLE IF NOT EXISTS record_embeddings (
+  memory_version_id TEXT NOT NULL,
+  model TEXT NOT NULL CHECK (length(trim(model)) > 0),
+  dimensions INTEGER NOT NULL CHECK (dimensions > 0),
+  vector BLOB NOT NULL CHECK (length(vector) = dimensions * 4),
+  created_at TEXT NOT NULL,
+  PRIMARY KEY (memory_version_id, model),
+  FOREIGN KEY (memory_version_id) REFERENCES memory_records(memory_version_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_record_embeddings_model ON record_embeddings(model);
+";
+
+// `position` keeps each record's tags in their written order.
+const MIGRATION_008_SQL: &str = r"
+CREATE TABLE IF NOT EXISTS memory_tags (
+  memory_version_id TEXT NOT NULL,
+  tag TEXT NOT NULL CHECK (length(trim(tag)) > 0),
+  position INTEGER NOT NULL CHECK (position >= 0),
+  PRIMARY KEY (memory_version_id, tag),
+  FOREIGN KEY (memory_version_id) REFERENCES memory_records(memory_version_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_tags_tag ON memory_tags(tag, memory_version_id);
+";
+
+// Existing rows take the default namespace; `apply_migration_9` adds each `namespace` column
+// only when missing. The indexes lead with namespace so scoped lists keep ordered index scans.
+const MIGRATION_009_SQL: &str = r"
+CREATE INDEX IF NOT EXISTS idx_memory_records_namespace_order
+  ON memory_records(namespace, created_at DESC, memory_id ASC, memory_version_id ASC);
+CREATE INDEX IF NOT EXISTS idx_context_packages_namespace_order
+  ON context_packages(namespace, generated_at DESC, context_package_id ASC);
+";
+
+// `apply_migration_10` adds each `write_seq` column only when missing and numbers existing rows
+// in rowid order; new rows take the next value, so delta exports can select rows by watermark.
+const MIGRATION_010_SQL: &str = r"
+UPDATE memory_records SET write_seq = rowid WHERE write_seq IS NULL;
+UPDATE context_packages SET write_seq = rowid WHERE write_seq IS NULL;
+CREATE INDEX IF NOT EXISTS idx_memory_records_write_seq ON memory_records(write_seq);
+CREATE INDEX IF NOT EXISTS idx_context_packages_write_seq ON context_packages(write_seq);
+";
+
+// Redacted payloads stay in place with their text replaced; this table records who scrubbed each
+// version and why. `write_seq` lets delta exports carry redactions of already-exported records.
+const MIGRATION_011_SQL: &str = r"
+CREATE TABLE IF NOT EXISTS redactions (
+  memory_version_id TEXT PRIMARY KEY,
+  writer TEXT NOT NULL CHECK (length(trim(writer)) > 0),
+  justification TEXT NOT NULL CHECK (length(trim(justification)) > 0),
+  redacted_at TEXT NOT NULL,
+  write_seq INTEGER NOT NULL,
+  FOREIGN KEY (memory_version_id) REFERENCES memory_records(memory_version_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_redactions_write_seq ON redactions(write_seq);
+";
+
+// Provenance lookups: `source_uri` prefixes run as range scans, `source_hash` as equality.
+const MIGRATION_012_SQL: &str = r"
+CREATE INDEX IF NOT EXISTS idx_memory_records_source_uri
+  ON memory_records(namespace, source_uri);
+CREATE INDEX IF NOT EXISTS idx_memory_records_source_hash
+  ON memory_records(namespace, source_hash);
+";
+
+// One row per ask or recall query. `context_packages.generated_at` is already covered by the v9
+// namespace index, so this version only adds the metrics table.
+const MIGRATION_013_SQL: &str = r"
+CREATE TABLE IF NOT EXISTS query_metrics (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  namespace TEXT NOT NULL,
+  query_mode TEXT NOT NULL CHECK (query_mode IN ('policy','recall')),
+  context_package_id TEXT NOT NULL,
+  recorded_at TEXT NOT NULL,
+  duration_micros INTEGER NOT NULL CHECK (duration_micros >= 0),
+  candidate_records INTEGER NOT NULL CHECK (candidate_records >= 0),
+  selected_items INTEGER NOT NULL CHECK (selected_items >= 0),
+  excluded_items INTEGER NOT NULL CHECK (excluded_items >= 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_query_metrics_namespace_recorded
+  ON query_metrics(namespace, recorded_at);
+";
+
+// Outcome feedback: which Outcome records followed the decision a context package informed.
+const MIGRATION_014_SQL: &str = r"
+CREATE TABLE IF NOT EXISTS package_outcomes (
+  context_package_id TEXT NOT NULL,
+  outcome_memory_version_id TEXT NOT NULL,
+  namespace TEXT NOT NULL,
+  writer TEXT NOT NULL CHECK (length(trim(writer)) > 0),
+  justification TEXT NOT NULL CHECK (length(trim(justification)) > 0),
+  linked_at TEXT NOT NULL,
+  PRIMARY KEY (context_package_id, outcome_memory_version_id),
+  FOREIGN KEY (context_package_id) REFERENCES context_packages(context_package_id),
+  FOREIGN KEY (outcome_memory_version_id) REFERENCES memory_records(memory_version_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_package_outcomes_outcome
+  ON package_outcomes(outcome_memory_version_id);
+";
+
+const INSERT_LINK_SQL: &str = "INSERT INTO memory_links(
+    from_memory_version_id, to_memory_version_id, link_type, writer, justification, created_at
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6)";
+
+const ACTIVE_CONTRADICTIONS_SQL: &str = "SELECT
+    f.memory_id, f.memory_version_id, f.version, f.record_type, f.truth_status,
+    t.memory_id, t.memory_version_id, t.version, t.record_type, t.truth_status,
+    l.writer, l.justification, l.created_at
+ FROM memory_links l
+ JOIN memory_records f ON f.memory_version_id = l.from_memory_version_id
+ JOIN memory_records t ON t.memory_version_id = l.to_memory_version_id
+ WHERE l.link_type = 'contradicts'
+   AND f.namespace = ?1
+   AND f.truth_status <> 'retracted'
+   AND t.truth_status <> 'retracted'
+   AND NOT EXISTS (
+     SELECT 1 FROM memory_links s
+      WHERE s.link_type = 'supersedes'
+        AND s.to_memory_version_id IN (f.memory_version_id, t.memory_version_id)
+   )
+ ORDER BY f.memory_id ASC, f.memory_version_id ASC, t.memory_version_id ASC, l.id ASC";
+
+const RECORD_SELECT_SQL: &str = "SELECT
+    memory_version_id, memory_id, version, record_type, created_at, effective_at,
+    truth_status, authority, confidence, writer, justification,
+    source_uri, source_hash, evidence_json
+ FROM memory_records";
+
+/// Tables keyed by `memory_version_id` whose rows belong to one record version.
+const VERSION_SCOPED_TABLES: &[&str] = &[
+    "constraint_payloads",
+    "decision_payloads",
+    "preference_payloads",
+    "event_payloads",
+    "outcome_payloads",
+    "task_payloads",
+    "risk_payloads",
+    "assumption_payloads",
+    "memory_tags",
+    "record_embeddings",
+    "redactions",
+    "memory_recall_fts",
+];
+
+const RECORD_ORDER_SQL: &str = "ORDER BY created_at DESC, memory_id ASC, memory_version_id ASC";
+
+#[derive(Debug)]
 pub struct SqliteStore {
     conn: Connection,
+    namespace: String,
+    import_limits: ImportLimits,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct SchemaStatus {
-    pub current_version: i64,
-    pub target_version: i64,
-    pub pending_versions: Vec<i64>,
-    pub inferred_from_legacy: bool,
+/// Bounds checked while streaming a snapshot's NDJSON files, before any row is parsed, so a
+/// hostile snapshot is rejected instead of exhausting memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportLimits {
+    /// Longest accepted NDJSON line in bytes, excluding the newline; also caps `manifest.json`.
+    pub max_line_bytes: usize,
+    /// Most rows accepted from one snapshot file.
+    pub max_rows: usize,
+    /// Deepest accepted nesting of JSON objects and arrays within one row.
+    pub max_depth: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct ExportFileDigest {
-    pub path: String,
-    pub sha256: String,
-    pub records: usize,
+impl Default for ImportLimits {
+    /// 8 MiB lines, one million rows per file, and 64 levels of nesting.
+    fn default() -> Self {
+        Self { max_line_bytes: 8 * 1024 * 1024, max_rows: 1_000_000, max_depth: 64 }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct ExportManifest {
-    pub schema_version: i64,
-    pub exported_at: String,
-    pub files: Vec<ExportFileDigest>,
+/// A snapshot read one file at a time from a stream such as an archive. Each file is checked
+/// against the import limits and parsed as it arrives, so the raw files are never buffered.
+#[derive(Debug)]
+pub struct SnapshotStream {
+    manifest: ExportManifest,
+    limits: ImportLimits,
+    digests: BTreeMap<String, (String, usize)>,
+    records: Vec<MemoryRecord>,
+    packages: Vec<ContextPackage>,
+    redactions: Vec<Redaction>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct ImportSummary {
-    pub imported_records: usize,
-    pub skipped_existing_records: usize,
-    pub imported_context_packages: usize,
-    pub skipped_existing_context_packages: usize,
+impl SnapshotStream {
+    /// Start reading the files of the snapshot described by `manifest`.
+    ///
+    /// # Errors
+    /// Returns an error when the manifest has an unsupported schema version, lists a file twice,
+    /// misses a required file, or lists more rows than `limits` allow.
+    pub fn new(manifest: ExportManifest, limits: ImportLimits) -> Result<Self> {
+        required_manifest_files(&manifest, &limits)?;
+        Ok(Self {
+            manifest,
+            limits,
+            digests: BTreeMap::new(),
+            records: Vec::new(),
+            packages: Vec::new(),
+            redactions: Vec::new(),
+        })
+    }
+
+    /// Read the snapshot file at manifest path `path` from `reader`.
+    ///
+    /// # Errors
+    /// Returns an error when the manifest does not list `path`, the file was already read, it
+    /// breaks the import limits, or a row fails to parse.
+    pub fn read_file(&mut self, path: &str, reader: impl BufRead) -> Result<()> {
+        if !manifest_lists(&self.manifest, path) {
+            return Err(anyhow!("snapshot file {path} is not listed in its manifest"));
+        }
+        if self.digests.contains_key(path) {
+            return Err(anyhow!("snapshot contains duplicate file {path}"));
+        }
+        let limits = &self.limits;
+        let digest = match path {
+            EXPORT_RECORDS_FILE => read_ndjson_into(reader, &path, limits, &mut self.records)?,
+            EXPORT_CONTEXT_PACKAGES_FILE => {
+                read_ndjson_into(reader, &path, limits, &mut self.packages)?
+            }
+            EXPORT_REDACTIONS_FILE => {
+                read_ndjson_into(reader, &path, limits, &mut self.redactions)?
+            }
+            _ => ndjson_digest_and_records(reader, &path, limits)?,
+        };
+        self.digests.insert(path.to_string(), digest);
+        Ok(())
+    }
+}
+
+/// 32-byte raw key for an encrypted (`SQLCipher`) store. Opening with a key requires the
+/// `sqlcipher` feature; `Debug` never prints the key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct StoreKey([u8; 32]);
+
+impl StoreKey {
+    #[must_use]
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Parse a key from 64 hex characters; surrounding whitespace is ignored.
+    ///
+    /// # Errors
+    /// Returns an error when `value` is not hex or does not decode to 32 bytes.
+    pub fn from_hex(value: &str) -> Result<Self> {
+        let bytes = hex::decode(value.trim()).context("store key must be hex")?;
+        let bytes: [u8; 32] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            anyhow!("store key must decode to exactly 32 bytes (got {})", bytes.len())
+        })?;
+        Ok(Self(bytes))
+    }
+
+    /// Read the key from `key_file` when given, else from [`STORE_KEY_ENV`] when set; `None`
+    /// means the store is opened unencrypted.
+    ///
+    /// # Errors
+    /// Returns an error when the key file cannot be read or either source is not a valid key.
+    pub fn resolve(key_file: Option<&Path>) -> Result<Option<Self>> {
+        if let Some(path) = key_file {
+            let body = fs::read_to_string(path)
+                .with_context(|| format!("failed to read store key file {}", path.display()))?;
+            return Self::from_hex(&body)
+                .map(Some)
+                .with_context(|| format!("invalid store key file {}", path.display()));
+        }
+        match std::env::var(STORE_KEY_ENV) {
+            Ok(value) => {
+                Self::from_hex(&value).map(Some).with_context(|| format!("invalid {STORE_KEY_ENV}"))
+            }
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(err) => Err(anyhow!("invalid {STORE_KEY_ENV}: {err}")),
+        }
+    }
+
+    /// `SQLCipher` raw-key literal, e.g. for `PRAGMA key` and `ATTACH ... KEY`.
+    #[cfg(feature = "sqlcipher")]
+    fn sqlcipher_literal(&self) -> String {
+        format!("x'{}'", hex::encode(self.0))
+    }
+}
+
+impl std::fmt::Debug for StoreKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StoreKey(..)")
+    }
+}
+
+/// A snapshot whose manifest is complete but whose NDJSON files are serialized again only as
+/// each one is written, so an archive can stream them without buffering the file contents.
+pub struct SnapshotExport {
+    manifest: ExportManifest,
+    rows: ExportRows,
+    lens: BTreeMap<String, u64>,
+}
+
+impl SnapshotExport {
+    /// Manifest describing the files, digests, and checkpoint of this snapshot.
+    #[must_use]
+    pub fn manifest(&self) -> &ExportManifest {
+        &self.manifest
+    }
+
+    /// Byte length of the NDJSON file at manifest path `path`, e.g. for a tar entry header.
+    #[must_use]
+    pub fn file_len(&self, path: &str) -> Option<u64> {
+        self.lens.get(path).copied()
+    }
+
+    /// Serialize the NDJSON file at manifest path `path` into `writer`.
+    ///
+    /// # Errors
+    /// Returns an error when the manifest does not list `path`, a row cannot be serialized or
+    /// written, or the output differs from the length and digest recorded in the manifest.
+    pub fn write_file(&self, path: &str, writer: &mut impl Write) -> Result<()> {
+        let mut counter = ByteCounter { inner: writer, written: 0 };
+        let digest = match path {
+            EXPORT_RECORDS_FILE => write_ndjson(&mut counter, &self.rows.records, &path)?,
+            EXPORT_CONTEXT_PACKAGES_FILE => {
+                write_ndjson(&mut counter, &self.rows.context_packages, &path)?
+            }
+            EXPORT_REDACTIONS_FILE => write_ndjson(&mut counter, &self.rows.redactions, &path)?,
+            _ => return Err(anyhow!("snapshot file {path} is not listed in its manifest")),
+        };
+        let listed = self.manifest.files.iter().find(|file| file.path == path);
+        if self.file_len(path) != Some(counter.written)
+            || listed.map(|file| file.sha256.as_str()) != Some(digest.0.as_str())
+        {
+            return Err(anyhow!("snapshot file {path} changed while it was written"));
+        }
+        Ok(())
+    }
+}
+
+/// Passes writes through to `inner` while counting the bytes written.
+struct ByteCounter<W> {
+    inner: W,
+    written: u64,
+}
+
+impl<W: Write> Write for ByteCounter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.written += u64::try_from(written).map_err(std::io::Error::other)?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Rows selected for one export, up to `checkpoint`.
+struct ExportRows {
+    checkpoint: ExportCheckpoint,
+    records: Vec<MemoryRecord>,
+    context_packages: Vec<ContextPackage>,
+    redactions: Vec<Redaction>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -234,6 +686,43 @@ pub struct ForeignKeyViolation {
     pub fk_index: i64,
 }
 
+/// Row of a per-version table whose `memory_version_id` has no `memory_records` row.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OrphanedRow {
+    pub table: String,
+    pub memory_version_id: String,
+}
+
+/// `memory_links` row whose source or target version has no `memory_records` row.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DanglingLink {
+    pub link_id: i64,
+    pub from_memory_version_id: String,
+    pub to_memory_version_id: String,
+    pub link_type: String,
+}
+
+/// Context package whose selected or excluded items name record versions missing from
+/// `memory_records`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StaleContextPackage {
+    pub context_package_id: String,
+    pub missing_memory_version_ids: Vec<String>,
+}
+
+/// Referential problems found by [`SqliteStore::repair_integrity`], and what it removed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RepairReport {
+    /// When `true`, nothing was removed and the `removed_*` counts are zero.
+    pub dry_run: bool,
+    pub dangling_links: Vec<DanglingLink>,
+    pub orphaned_rows: Vec<OrphanedRow>,
+    pub stale_context_packages: Vec<StaleContextPackage>,
+    pub removed_links: usize,
+    pub removed_rows: usize,
+    pub removed_context_packages: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct IntegrityReport {
     pub quick_check_ok: bool,
@@ -248,8 +737,23 @@ impl SqliteStore {
     /// # Errors
     /// Returns an error when the database cannot be opened or pragmas cannot be applied.
     pub fn open(path: &Path) -> Result<Self> {
+        Self::open_with_key(path, None)
+    }
+
+    /// Open a store that is encrypted at rest with `key`, or a plaintext store when `key` is
+    /// `None`.
+    ///
+    /// # Errors
+    /// Returns an error when the database cannot be opened, `key` does not unlock it, a key is
+    /// given without the `sqlcipher` feature, or pragmas cannot be applied.
+    pub fn open_with_key(path: &Path, key: Option<&StoreKey>) -> Result<Self> {
         let conn = Connection::open(path)
             .with_context(|| format!("failed to open sqlite database at {}", path.display()))?;
+        if let Some(key) = key {
+            unlock_store(&conn, key).with_context(|| {
+                format!("failed to unlock sqlite database at {}", path.display())
+            })?;
+        }
 
         conn.execute_batch(
             "PRAGMA journal_mode = WAL;
@@ -257,8 +761,42 @@ impl SqliteStore {
              PRAGMA busy_timeout = 5000;",
         )
         .context("failed to configure sqlite pragmas")?;
+        conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+
+        Ok(Self {
+            conn,
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            import_limits: ImportLimits::default(),
+        })
+    }
+
+    /// Namespace that scopes every record and context package read or written by this store.
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Scope subsequent reads and writes to `namespace`.
+    ///
+    /// # Errors
+    /// Returns an error when `namespace` is not 1-64 ASCII letters, digits, `-`, `_`, or `.`.
+    pub fn set_namespace(&mut self, namespace: &str) -> Result<()> {
+        validate_namespace(namespace)?;
+        namespace.clone_into(&mut self.namespace);
+        Ok(())
+    }
 
-        Ok(Self { conn })
+    /// Bounds applied to snapshot files read by [`Self::import_snapshot`] and
+    /// [`Self::import_snapshot_in_memory`]; pass them to [`SnapshotStream::new`] for
+    /// [`Self::import_snapshot_stream`].
+    #[must_use]
+    pub fn import_limits(&self) -> ImportLimits {
+        self.import_limits
+    }
+
+    /// Replace the bounds applied to snapshot files read by later imports.
+    pub fn set_import_limits(&mut self, limits: ImportLimits) {
+        self.import_limits = limits;
     }
 
     /// Report current and target schema versions plus pending migrations.
@@ -304,6 +842,66 @@ impl SqliteStore {
             version = current_schema_version(&self.conn)?;
         }
 
+        if version < 3 {
+            self.apply_migration_3()?;
+            version = current_schema_version(&self.conn)?;
+        }
+
+        if version < 4 {
+            self.apply_migration_4()?;
+            version = current_schema_version(&self.conn)?;
+        }
+
+        if version < 5 {
+            self.apply_migration_5()?;
+            version = current_schema_version(&self.conn)?;
+        }
+
+        if version < 6 {
+            self.apply_migration_6()?;
+            version = current_schema_version(&self.conn)?;
+        }
+
+        if version < 7 {
+            self.apply_migration_7()?;
+            version = current_schema_version(&self.conn)?;
+        }
+
+        if version < 8 {
+            self.apply_migration_8()?;
+            version = current_schema_version(&self.conn)?;
+        }
+
+        if version < 9 {
+            self.apply_migration_9()?;
+            version = current_schema_version(&self.conn)?;
+        }
+
+        if version < 10 {
+            self.apply_migration_10()?;
+            version = current_schema_version(&self.conn)?;
+        }
+
+        if version < 11 {
+            self.apply_migration_11()?;
+            version = current_schema_version(&self.conn)?;
+        }
+
+        if version < 12 {
+            self.apply_migration_12()?;
+            version = current_schema_version(&self.conn)?;
+        }
+
+        if version < 13 {
+            self.apply_migration_13()?;
+            version = current_schema_version(&self.conn)?;
+        }
+
+        if version < 14 {
+            self.apply_migration_14()?;
+            version = current_schema_version(&self.conn)?;
+        }
+
         if version != LATEST_SCHEMA_VERSION {
             return Err(anyhow!(
                 "unsupported schema version {version}; expected {LATEST_SCHEMA_VERSION}"
@@ -446,113 +1044,670 @@ impl SqliteStore {
         Ok(())
     }
 
-    /// Persist one validated append-only memory record and its payload/link rows.
-    ///
-    /// # Errors
-    /// Returns an error when validation fails or any write in the transaction fails.
-    pub fn write_record(&mut self, record: &MemoryRecord) -> Result<()> {
-        record.validate().map_err(|err| anyhow!("record validation failed: {err}"))?;
+    fn apply_migration_3(&mut self) -> Result<()> {
+        let tx = self.conn.transaction().context("failed to start migration v3 transaction")?;
+        tx.execute_batch(MIGRATION_003_SQL).context("failed to create record ordering index")?;
+        record_schema_version(&tx, 3)?;
+        tx.commit().context("failed to commit migration v3")?;
+        Ok(())
+    }
 
-        let tx = self.conn.transaction().context("failed to start transaction")?;
+    fn apply_migration_4(&mut self) -> Result<()> {
+        let tx = self.conn.transaction().context("failed to start migration v4 transaction")?;
+        tx.execute_batch(MIGRATION_004_SQL).context("failed to create recall FTS table")?;
+        {
+            let mut stmt = tx.prepare(MIGRATION_004_RECALL_SOURCE_SQL)?;
+            let mut rows = stmt.query([])?;
+            while let Some(row) = rows.next()? {
+                let memory_version_id: String = row.get(0)?;
+                let source: String = row.get(1)?;
+                let terms = tokenize_query_terms(&source).join(" ");
+                insert_recall_terms_row(&tx, &memory_version_id, &terms)?;
+            }
+        }
+        record_schema_version(&tx, 4)?;
+        tx.commit().context("failed to commit migration v4")?;
+        Ok(())
+    }
 
-        tx.execute(
-            "INSERT INTO memory_records(
-                memory_version_id, memory_id, version, record_type, created_at, effective_at,
-                truth_status, authority, confidence, writer, justification,
-                source_uri, source_hash, evidence_json
-            ) VALUES (
-                ?1, ?2, ?3, ?4, ?5, ?6,
-                ?7, ?8, ?9, ?10, ?11,
-                ?12, ?13, ?14
-            )",
-            params![
-                record.memory_version_id.to_string(),
-                record.memory_id.to_string(),
-                i64::from(record.version),
-                record.payload.record_type().as_str(),
-                rfc3339(record.created_at)?,
-                rfc3339(record.effective_at)?,
-                record.truth_status.as_str(),
-                record.authority.as_str(),
-                record.confidence,
-                record.writer,
-                record.justification,
-                record.provenance.source_uri,
-                record.provenance.source_hash,
-                serde_json::to_string(&record.provenance.evidence)
-                    .context("failed to serialize evidence")?,
-            ],
-        )
-        .context("failed to insert memory record")?;
+    fn apply_migration_5(&mut self) -> Result<()> {
+        let tx = self.conn.transaction().context("failed to start migration v5 transaction")?;
+        tx.execute_batch(MIGRATION_005_SQL).context("failed to add constraint expires_at")?;
+        record_schema_version(&tx, 5)?;
+        tx.commit().context("failed to commit migration v5")?;
+        Ok(())
+    }
 
-        Self::insert_payload(&tx, record)?;
-        Self::insert_links(&tx, record, LinkType::Supersedes, &record.supersedes)?;
-        Self::insert_links(&tx, record, LinkType::Contradicts, &record.contradicts)?;
+    fn apply_migration_6(&mut self) -> Result<()> {
+        // Rebuilding a referenced table requires foreign keys off; the pragma is a no-op inside a
+        // transaction, so it is toggled around it and links are re-checked before commit.
+        self.conn
+            .execute_batch("PRAGMA foreign_keys = OFF;")
+            .context("failed to disable foreign keys for migration v6")?;
+        let result = self.rebuild_records_for_v6();
+        self.conn
+            .execute_batch("PRAGMA foreign_keys = ON;")
+            .context("failed to re-enable foreign keys after migration v6")?;
+        result
+    }
 
-        tx.commit().context("failed to commit write transaction")?;
+    fn apply_migration_7(&mut self) -> Result<()> {
+        let tx = self.conn.transaction().context("failed to start migration v7 transaction")?;
+        tx.execute_batch(MIGRATION_007_SQL).context("failed to create record_embeddings")?;
+        record_schema_version(&tx, 7)?;
+        tx.commit().context("failed to commit migration v7")?;
         Ok(())
     }
 
-    /// Load all persisted memory records with payloads and lineage links.
-    ///
-    /// # Errors
-    /// Returns an error when rows cannot be read or decoded from `SQLite`.
-    pub fn list_records(&self) -> Result<Vec<MemoryRecord>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT
-                memory_version_id, memory_id, version, record_type, created_at, effective_at,
-                truth_status, authority, confidence, writer, justification,
-                source_uri, source_hash, evidence_json
-             FROM memory_records
-             ORDER BY created_at DESC, memory_id ASC, memory_version_id ASC",
+    fn apply_migration_8(&mut self) -> Result<()> {
+        let tx = self.conn.transaction().context("failed to start migration v8 transaction")?;
+        tx.execute_batch(MIGRATION_008_SQL).context("failed to create memory_tags")?;
+        record_schema_version(&tx, 8)?;
+        tx.commit().context("failed to commit migration v8")?;
+        Ok(())
+    }
+
+    fn apply_migration_9(&mut self) -> Result<()> {
+        let tx = self.conn.transaction().context("failed to start migration v9 transaction")?;
+        for table in ["memory_records", "context_packages"] {
+            if !table_has_column(&tx, table, "namespace")? {
+                tx.execute_batch(&format!(
+                    "ALTER TABLE {table} ADD COLUMN namespace TEXT NOT NULL DEFAULT 'default';"
+                ))
+                .with_context(|| format!("failed to add {table}.namespace"))?;
+            }
+        }
+        tx.execute_batch(MIGRATION_009_SQL).context("failed to create namespace indexes")?;
+        record_schema_version(&tx, 9)?;
+        tx.commit().context("failed to commit migration v9")?;
+        Ok(())
+    }
+
+    fn apply_migration_10(&mut self) -> Result<()> {
+        let tx = self.conn.transaction().context("failed to start migration v10 transaction")?;
+        for table in ["memory_records", "context_packages"] {
+            if !table_has_column(&tx, table, "write_seq")? {
+                tx.execute_batch(&format!("ALTER TABLE {table} ADD COLUMN write_seq INTEGER;"))
+                    .with_context(|| format!("failed to add {table}.write_seq"))?;
+            }
+        }
+        tx.execute_batch(MIGRATION_010_SQL).context("failed to number existing rows")?;
+        record_schema_version(&tx, 10)?;
+        tx.commit().context("failed to commit migration v10")?;
+        Ok(())
+    }
+
+    fn apply_migration_11(&mut self) -> Result<()> {
+        let tx = self.conn.transaction().context("failed to start migration v11 transaction")?;
+        tx.execute_batch(MIGRATION_011_SQL).context("failed to create redactions")?;
+        record_schema_version(&tx, 11)?;
+        tx.commit().context("failed to commit migration v11")?;
+        Ok(())
+    }
+
+    fn apply_migration_12(&mut self) -> Result<()> {
+        let tx = self.conn.transaction().context("failed to start migration v12 transaction")?;
+        tx.execute_batch(MIGRATION_012_SQL).context("failed to create provenance indexes")?;
+        record_schema_version(&tx, 12)?;
+        tx.commit().context("failed to commit migration v12")?;
+        Ok(())
+    }
+
+    fn apply_migration_13(&mut self) -> Result<()> {
+        let tx = self.conn.transaction().context("failed to start migration v13 transaction")?;
+        tx.execute_batch(MIGRATION_013_SQL).context("failed to create query_metrics")?;
+        record_schema_version(&tx, 13)?;
+        tx.commit().context("failed to commit migration v13")?;
+        Ok(())
+    }
+
+    fn apply_migration_14(&mut self) -> Result<()> {
+        let tx = self.conn.transaction().context("failed to start migration v14 transaction")?;
+        tx.execute_batch(MIGRATION_014_SQL).context("failed to create package_outcomes")?;
+        record_schema_version(&tx, 14)?;
+        tx.commit().context("failed to commit migration v14")?;
+        Ok(())
+    }
+
+    fn rebuild_records_for_v6(&mut self) -> Result<()> {
+        let tx = self.conn.transaction().context("failed to start migration v6 transaction")?;
+        tx.execute_batch(MIGRATION_006_SQL)
+            .context("failed to rebuild memory_records for task/risk/assumption types")?;
+        let violations: i64 =
+            tx.query_row("SELECT COUNT(*) FROM pragma_foreign_key_check", [], |row| row.get(0))?;
+        if violations > 0 {
+            return Err(anyhow!("migration v6 left {violations} foreign key violation(s)"));
+        }
+        record_schema_version(&tx, 6)?;
+        tx.commit().context("failed to commit migration v6")?;
+        Ok(())
+    }
+
+    /// Open an explicit write batch: later writes on this store join one immediate
+    /// transaction instead of committing individually, until [`SqliteStore::commit_batch`] or
+    /// [`SqliteStore::rollback_batch`].
+    ///
+    /// Each write inside the batch still applies atomically (a failed write leaves earlier ones
+    /// in place), and reads on this store see the uncommitted rows. Dropping the store with a
+    /// batch open discards it.
+    ///
+    /// # Errors
+    /// Returns an error when a batch or other transaction is already open, or the write lock
+    /// cannot be taken.
+    pub fn begin_batch(&mut self) -> Result<()> {
+        if !self.conn.is_autocommit() {
+            return Err(anyhow!("a write batch is already open"));
+        }
+        self.conn.execute_batch("BEGIN IMMEDIATE").context("failed to begin write batch")?;
+        Ok(())
+    }
+
+    /// Commit every write made since [`SqliteStore::begin_batch`].
+    ///
+    /// # Errors
+    /// Returns an error when no batch is open or the commit fails; the batch stays open on a
+    /// failed commit.
+    pub fn commit_batch(&mut self) -> Result<()> {
+        if self.conn.is_autocommit() {
+            return Err(anyhow!("no write batch is open"));
+        }
+        self.conn.execute_batch("COMMIT").context("failed to commit write batch")?;
+        Ok(())
+    }
+
+    /// Discard every write made since [`SqliteStore::begin_batch`].
+    ///
+    /// # Errors
+    /// Returns an error when no batch is open or the rollback fails.
+    pub fn rollback_batch(&mut self) -> Result<()> {
+        if self.conn.is_autocommit() {
+            return Err(anyhow!("no write batch is open"));
+        }
+        self.conn.execute_batch("ROLLBACK").context("failed to roll back write batch")?;
+        Ok(())
+    }
+
+    /// Persist one validated append-only memory record and its payload/link rows.
+    ///
+    /// # Errors
+    /// Returns an error when validation fails or any write in the transaction fails.
+    pub fn write_record(&mut self, record: &MemoryRecord) -> Result<()> {
+        record.validate().map_err(|err| anyhow!("record validation failed: {err}"))?;
+
+        let tx = write_tx(&mut self.conn, TransactionBehavior::Deferred)?;
+        Self::insert_record(&tx, &self.namespace, record)?;
+        tx.commit().context("failed to commit write transaction")?;
+        Ok(())
+    }
+
+    /// Persist `records` atomically in one transaction, in slice order.
+    ///
+    /// Every record is validated before anything is written. Foreign keys are checked at
+    /// commit, so lineage links may point at records later in the same batch.
+    ///
+    /// # Errors
+    /// Returns an error naming the zero-based batch index when a record fails validation or
+    /// its write fails, or when the commit fails. Nothing is written on error.
+    pub fn write_records_batch(&mut self, records: &[MemoryRecord]) -> Result<()> {
+        for (index, record) in records.iter().enumerate() {
+            record.validate().map_err(|err| anyhow!("record {index} validation failed: {err}"))?;
+        }
+
+        let tx = write_tx(&mut self.conn, TransactionBehavior::Deferred)?;
+        tx.execute_batch("PRAGMA defer_foreign_keys = ON;")
+            .context("failed to defer foreign key checks")?;
+        for (index, record) in records.iter().enumerate() {
+            Self::insert_record(&tx, &self.namespace, record)
+                .with_context(|| format!("failed to write batch record {index}"))?;
+        }
+        tx.commit().context("failed to commit batch write transaction")?;
+        Ok(())
+    }
+
+    /// Append the next version of the memory holding `previous`, built by `build` from the
+    /// previous record and the next free `version`. Lookup, version allocation, and the write
+    /// share one immediate transaction, so concurrent writers cannot claim the same version.
+    ///
+    /// The built record MUST keep the previous `memory_id`, use the allocated version, and list
+    /// `previous` in `supersedes`.
+    ///
+    /// # Errors
+    /// Returns an error when `previous` does not exist or is already superseded, when `build`
+    /// fails, when the built record breaks the rules above or fails validation, or when any
+    /// write fails. Nothing is written on error.
+    pub fn write_successor(
+        &mut self,
+        previous: MemoryVersionId,
+        build: impl FnOnce(&MemoryRecord, u32) -> Result<MemoryRecord>,
+    ) -> Result<MemoryRecord> {
+        let tx = write_tx(&mut self.conn, TransactionBehavior::Immediate)?;
+
+        let mut query = RecordQuery::in_namespace(&self.namespace);
+        let param = query.bind(previous.to_string());
+        query.conditions.push(format!("memory_version_id = {param}"));
+        let rows = query.fetch(&tx, Some(1))?;
+        let Some(previous_record) = hydrate_records(&tx, rows)?.pop() else {
+            return Err(anyhow!("memory_version_id not found: {previous}"));
+        };
+
+        let superseded_by: Option<String> = tx
+            .query_row(
+                "SELECT from_memory_version_id FROM memory_links
+                 WHERE link_type = 'supersedes' AND to_memory_version_id = ?1
+                 ORDER BY id ASC LIMIT 1",
+                params![previous.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(successor) = superseded_by {
+            return Err(anyhow!(
+                "memory_version_id {previous} is already superseded by {successor}"
+            ));
+        }
+
+        let latest: u32 = tx.query_row(
+            "SELECT MAX(version) FROM memory_records WHERE memory_id = ?1",
+            params![previous_record.memory_id.to_string()],
+            |row| row.get(0),
         )?;
+        let next_version =
+            latest.checked_add(1).ok_or_else(|| anyhow!("memory version overflow"))?;
+
+        let record = build(&previous_record, next_version)?;
+        if record.memory_id != previous_record.memory_id
+            || record.version != next_version
+            || !record.supersedes.contains(&previous)
+        {
+            return Err(anyhow!(
+                "successor MUST keep memory_id, use version {next_version}, and supersede {previous}"
+            ));
+        }
+        record.validate().map_err(|err| anyhow!("record validation failed: {err}"))?;
+
+        Self::insert_record(&tx, &self.namespace, &record)?;
+        tx.commit().context("failed to commit successor transaction")?;
+        Ok(record)
+    }
+
+    fn insert_record(conn: &Connection, namespace: &str, record: &MemoryRecord) -> Result<()> {
+        conn.prepare_cached(
+            "INSERT INTO memory_records(
+                memory_version_id, memory_id, version, record_type, created_at, effective_at,
+                truth_status, authority, confidence, writer, justification,
+                source_uri, source_hash, evidence_json, namespace, write_seq
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6,
+                ?7, ?8, ?9, ?10, ?11,
+                ?12, ?13, ?14, ?15,
+                (SELECT COALESCE(MAX(write_seq), 0) + 1 FROM memory_records)
+            )",
+        )?
+        .execute(params![
+            record.memory_version_id.to_string(),
+            record.memory_id.to_string(),
+            i64::from(record.version),
+            record.payload.record_type().as_str(),
+            rfc3339(record.created_at)?,
+            rfc3339(record.effective_at)?,
+            record.truth_status.as_str(),
+            record.authority.as_str(),
+            record.confidence,
+            record.writer,
+            record.justification,
+            record.provenance.source_uri,
+            record.provenance.source_hash,
+            serde_json::to_string(&record.provenance.evidence)
+                .context("failed to serialize evidence")?,
+            namespace,
+        ])
+        .context("failed to insert memory record")?;
+
+        Self::insert_payload(conn, record)?;
+        Self::insert_links(conn, namespace, record, LinkType::Supersedes, &record.supersedes)?;
+        Self::insert_links(conn, namespace, record, LinkType::Contradicts, &record.contradicts)?;
+        Self::insert_tags(conn, record)?;
+        insert_recall_terms(conn, record)?;
+        Ok(())
+    }
+
+    /// Load all persisted memory records with payloads and lineage links.
+    ///
+    /// Use [`SqliteStore::iter_records`] to avoid holding the whole table in memory.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read or decoded from `SQLite`.
+    pub fn list_records(&self) -> Result<Vec<MemoryRecord>> {
+        self.iter_records()?.collect()
+    }
+
+    /// Stream persisted memory records in [`SqliteStore::list_records`] order.
+    ///
+    /// Records are read in keyset-paginated batches with one payload and one link query per
+    /// batch. When no transaction is open on this connection the iterator reads from a single
+    /// snapshot, released once it is exhausted or dropped.
+    ///
+    /// # Errors
+    /// Returns an error when the read snapshot cannot be started.
+    pub fn iter_records(&self) -> Result<RecordIter<'_>> {
+        self.iter_records_batched(DEFAULT_RECORD_BATCH_SIZE)
+    }
+
+    /// Stream persisted memory records, reading at most `batch_size` records per query.
+    ///
+    /// # Errors
+    /// Returns an error when `batch_size` is zero or the read snapshot cannot be started.
+    pub fn iter_records_batched(&self, batch_size: usize) -> Result<RecordIter<'_>> {
+        record_iter(&self.conn, &self.namespace, batch_size)
+    }
+
+    /// Load one page of records matching `filter`, in [`SqliteStore::list_records`] order.
+    ///
+    /// # Errors
+    /// Returns an error when `limit` is zero, the `after` cursor does not exist, or rows
+    /// cannot be read or decoded from `SQLite`.
+    pub fn list_records_filtered(&self, filter: &RecordFilter) -> Result<RecordPage> {
+        if filter.limit == Some(0) {
+            return Err(anyhow!("limit MUST be >= 1"));
+        }
+
+        let mut query = RecordQuery::in_namespace(&self.namespace);
+        if let Some(record_type) = filter.record_type {
+            let param = query.bind(record_type.as_str().to_string());
+            query.conditions.push(format!("record_type = {param}"));
+        }
+        if let Some(writer) = &filter.writer {
+            let param = query.bind(writer.clone());
+            query.conditions.push(format!("writer = {param}"));
+        }
+        if let Some(truth_status) = filter.truth_status {
+            let param = query.bind(truth_status.as_str().to_string());
+            query.conditions.push(format!("truth_status = {param}"));
+        }
+        if !filter.tags.is_empty() {
+            query.require_tags(&filter.tags);
+        }
+        if let Some(prefix) = &filter.source_uri_prefix {
+            let param = query.bind(prefix.clone());
+            query.conditions.push(format!("source_uri >= {param}"));
+            if let Some(bound) = prefix_upper_bound(prefix) {
+                let param = query.bind(bound);
+                query.conditions.push(format!("source_uri < {param}"));
+            }
+        }
+        if let Some(source_hash) = &filter.source_hash {
+            let param = query.bind(source_hash.clone());
+            query.conditions.push(format!("source_hash = {param}"));
+        }
+        // Stored offsets and fractional seconds vary, so compare instants rather than text.
+        if let Some(from) = filter.effective_from {
+            let param = query.bind(rfc3339(from)?);
+            query.conditions.push(format!("julianday(effective_at) >= julianday({param})"));
+        }
+        if let Some(to) = filter.effective_to {
+            let param = query.bind(rfc3339(to)?);
+            query.conditions.push(format!("julianday(effective_at) < julianday({param})"));
+        }
+        if let Some(after) = filter.after {
+            let cursor = self
+                .conn
+                .query_row(
+                    "SELECT created_at, memory_id, memory_version_id
+                     FROM memory_records WHERE memory_version_id = ?1 AND namespace = ?2",
+                    params![after.to_string(), self.namespace],
+                    |row| {
+                        Ok(RecordCursor {
+                            created_at: row.get(0)?,
+                            memory_id: row.get(1)?,
+                            memory_version_id: row.get(2)?,
+                        })
+                    },
+                )
+                .optional()?
+                .ok_or_else(|| anyhow!("cursor record not found: {after}"))?;
+            query.resume_after(&cursor);
+        }
+
+        // Fetch one extra row to learn whether another page exists.
+        let mut rows = query.fetch(&self.conn, filter.limit.map(|limit| limit + 1))?;
+        let next_after = match filter.limit {
+            Some(limit) if rows.len() > limit => {
+                rows.truncate(limit);
+                rows.last().map(|row| row.record.memory_version_id)
+            }
+            _ => None,
+        };
+
+        Ok(RecordPage { records: hydrate_records(&self.conn, rows)?, next_after })
+    }
+
+    /// Load recall candidates: records of `record_types` sharing at least one lexical term with
+    /// `query`, best FTS matches first and capped at `limit`, plus any records that supersede
+    /// them so superseded matches are still excluded by recall.
+    ///
+    /// # Errors
+    /// Returns an error when `limit` is zero or the FTS lookup or record reads fail.
+    pub fn recall_candidates(
+        &self,
+        query: &str,
+        record_types: &[RecordType],
+        limit: usize,
+    ) -> Result<Vec<MemoryRecord>> {
+        self.recall_candidates_tagged(query, record_types, &[], limit)
+    }
 
-        let mut rows = stmt.query([])?;
-        let mut records = Vec::new();
+    /// Load recall candidates as [`SqliteStore::recall_candidates`], matching only records that
+    /// carry every tag in `tags`. Superseding records are still loaded regardless of their tags.
+    ///
+    /// # Errors
+    /// Returns an error when `limit` is zero or the FTS lookup or record reads fail.
+    pub fn recall_candidates_tagged(
+        &self,
+        query: &str,
+        record_types: &[RecordType],
+        tags: &[String],
+        limit: usize,
+    ) -> Result<Vec<MemoryRecord>> {
+        if limit == 0 {
+            return Err(anyhow!("limit MUST be >= 1"));
+        }
+        let terms = tokenize_query_terms(query);
+        if terms.is_empty() || record_types.is_empty() {
+            return Ok(Vec::new());
+        }
 
+        let mut values: Vec<rusqlite::types::Value> = vec![terms
+            .iter()
+            .map(|term| format!("\"{term}\""))
+            .collect::<Vec<_>>()
+            .join(" OR ")
+            .into()];
+        values
+            .extend(record_types.iter().map(|record_type| record_type.as_str().to_string().into()));
+        let tags = tags.iter().collect::<BTreeSet<_>>();
+        let tag_condition = if tags.is_empty() {
+            String::new()
+        } else {
+            let first = values.len() + 1;
+            values.extend(tags.iter().map(|tag| (*tag).clone().into()));
+            values.push(i64::try_from(tags.len()).unwrap_or(i64::MAX).into());
+            format!(
+                " AND memory_records.memory_version_id IN (
+                   SELECT memory_version_id FROM memory_tags WHERE tag IN ({})
+                   GROUP BY memory_version_id HAVING COUNT(*) = ?{})",
+                (first..first + tags.len())
+                    .map(|index| format!("?{index}"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                values.len()
+            )
+        };
+        values.push(self.namespace.clone().into());
+        let namespace_param = values.len();
+        values.push(i64::try_from(limit).unwrap_or(i64::MAX).into());
+        let sql = format!(
+            "SELECT memory_recall_fts.memory_version_id
+             FROM memory_recall_fts
+             JOIN memory_records
+               ON memory_records.memory_version_id = memory_recall_fts.memory_version_id
+             WHERE memory_recall_fts MATCH ?1 AND memory_records.record_type IN ({}){tag_condition}
+               AND memory_records.namespace = ?{namespace_param}
+             ORDER BY bm25(memory_recall_fts) ASC, memory_recall_fts.memory_version_id ASC
+             LIMIT ?{}",
+            sql_placeholders(record_types.len()),
+            values.len()
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut matched = BTreeSet::new();
+        let mut rows = stmt.query(params_from_iter(values))?;
         while let Some(row) = rows.next()? {
-            let memory_version_id_raw: String = row.get(0)?;
-            let memory_id_raw: String = row.get(1)?;
-            let record_type_raw: String = row.get(3)?;
-            let memory_version_id = parse_memory_version_id(&memory_version_id_raw)?;
-            let memory_id = parse_memory_id(&memory_id_raw)?;
-            let record_type = RecordType::parse(&record_type_raw)
-                .ok_or_else(|| anyhow!("unknown record_type: {record_type_raw}"))?;
-
-            let payload = self.load_payload(memory_version_id, record_type)?;
-            let supersedes = self.load_links(memory_version_id, LinkType::Supersedes)?;
-            let contradicts = self.load_links(memory_version_id, LinkType::Contradicts)?;
-
-            let truth_status_raw: String = row.get(6)?;
-            let authority_raw: String = row.get(7)?;
-            let evidence_json: String = row.get(13)?;
-
-            records.push(MemoryRecord {
-                memory_version_id,
-                memory_id,
-                version: row.get::<_, u32>(2)?,
-                payload,
-                created_at: parse_rfc3339(&row.get::<_, String>(4)?)?,
-                effective_at: parse_rfc3339(&row.get::<_, String>(5)?)?,
-                truth_status: TruthStatus::parse(&truth_status_raw)
-                    .ok_or_else(|| anyhow!("unknown truth_status: {truth_status_raw}"))?,
-                authority: Authority::parse(&authority_raw)
-                    .ok_or_else(|| anyhow!("unknown authority: {authority_raw}"))?,
-                confidence: row.get(8)?,
-                writer: row.get(9)?,
-                justification: row.get(10)?,
-                provenance: memory_kernel_core::Provenance {
-                    source_uri: row.get(11)?,
-                    source_hash: row.get(12)?,
-                    evidence: serde_json::from_str(&evidence_json)
-                        .context("failed to deserialize evidence")?,
-                },
-                supersedes,
-                contradicts,
-            });
+            matched.insert(row.get::<_, String>(0)?);
+        }
+        if matched.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let superseders_sql = format!(
+            "SELECT DISTINCT from_memory_version_id FROM memory_links
+             WHERE link_type = ?1 AND to_memory_version_id IN ({})",
+            sql_placeholders(matched.len())
+        );
+        let mut stmt = self.conn.prepare(&superseders_sql)?;
+        let mut superseders = Vec::new();
+        let mut rows = stmt.query(params_from_iter(
+            std::iter::once(LinkType::Supersedes.as_str())
+                .chain(matched.iter().map(String::as_str)),
+        ))?;
+        while let Some(row) = rows.next()? {
+            superseders.push(row.get::<_, String>(0)?);
+        }
+        matched.extend(superseders);
+
+        let mut query = RecordQuery::in_namespace(&self.namespace);
+        let placeholders =
+            matched.into_iter().map(|id| query.bind(id)).collect::<Vec<_>>().join(", ");
+        query.conditions.push(format!("memory_version_id IN ({placeholders})"));
+        let rows = query.fetch(&self.conn, None)?;
+        hydrate_records(&self.conn, rows)
+    }
+
+    /// List `(memory_id, memory_version_id)` for every record without loading payloads.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read or decoded from `SQLite`.
+    pub fn list_record_keys(&self) -> Result<Vec<(MemoryId, MemoryVersionId)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT memory_id, memory_version_id FROM memory_records WHERE namespace = ?1",
+        )?;
+        let mut rows = stmt.query(params![self.namespace])?;
+        let mut keys = Vec::new();
+        while let Some(row) = rows.next()? {
+            keys.push((
+                parse_memory_id(&row.get::<_, String>(0)?)?,
+                parse_memory_version_id(&row.get::<_, String>(1)?)?,
+            ));
+        }
+        Ok(keys)
+    }
+
+    /// Report `contradicts` links between active record versions, grouped by the `memory_id` of
+    /// the contradicting (`from`) version. Retracted versions and versions targeted by a
+    /// `supersedes` link are treated as resolved and omitted.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read or decoded from `SQLite`.
+    pub fn list_contradictions(&self) -> Result<ContradictionReport> {
+        let mut stmt = self.conn.prepare(ACTIVE_CONTRADICTIONS_SQL)?;
+        let mut rows = stmt.query(params![self.namespace])?;
+        let mut report = ContradictionReport::default();
+        while let Some(row) = rows.next()? {
+            let contradiction = Contradiction {
+                from: decode_contradiction_endpoint(row, 0)?,
+                to: decode_contradiction_endpoint(row, 5)?,
+                writer: row.get(10)?,
+                justification: row.get(11)?,
+                created_at: parse_rfc3339(&row.get::<_, String>(12)?)?,
+            };
+            report.contradiction_count += 1;
+            match report.groups.last_mut() {
+                Some(group) if group.memory_id == contradiction.from.memory_id => {
+                    group.contradictions.push(contradiction);
+                }
+                _ => report.groups.push(ContradictionGroup {
+                    memory_id: contradiction.from.memory_id,
+                    contradictions: vec![contradiction],
+                }),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Store `model` embeddings for record versions in one transaction, replacing any vector
+    /// previously stored for the same version and model.
+    ///
+    /// # Errors
+    /// Returns an error when `model` is blank, a vector is empty or not finite, a record
+    /// version does not exist, or any write fails. Nothing is written on error.
+    pub fn put_record_embeddings(
+        &mut self,
+        model: &str,
+        embeddings: &[(MemoryVersionId, Vec<f32>)],
+    ) -> Result<()> {
+        if model.trim().is_empty() {
+            return Err(anyhow!("embedding model cannot be empty"));
+        }
+        let tx = write_tx(&mut self.conn, TransactionBehavior::Deferred)?;
+        let now = now_rfc3339()?;
+        for (memory_version_id, vector) in embeddings {
+            ensure_same_namespace(&tx, &self.namespace, *memory_version_id)?;
+            if vector.is_empty() || vector.iter().any(|value| !value.is_finite()) {
+                return Err(anyhow!(
+                    "embedding validation failed for {memory_version_id}: vector MUST be non-empty and finite"
+                ));
+            }
+            let bytes = vector.iter().flat_map(|value| value.to_le_bytes()).collect::<Vec<_>>();
+            tx.execute(
+                "INSERT INTO record_embeddings(memory_version_id, model, dimensions, vector, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(memory_version_id, model) DO UPDATE SET
+                   dimensions = excluded.dimensions,
+                   vector = excluded.vector,
+                   created_at = excluded.created_at",
+                params![
+                    memory_version_id.to_string(),
+                    model,
+                    i64::try_from(vector.len()).unwrap_or(i64::MAX),
+                    bytes,
+                    now
+                ],
+            )
+            .with_context(|| format!("failed to store embedding for {memory_version_id}"))?;
         }
+        tx.commit().context("failed to commit embedding transaction")?;
+        Ok(())
+    }
 
-        Ok(records)
+    /// Load every stored `model` embedding for records in this namespace, keyed by version.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read or decoded from `SQLite`.
+    pub fn record_embeddings(&self, model: &str) -> Result<BTreeMap<MemoryVersionId, Vec<f32>>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT e.memory_version_id, e.vector FROM record_embeddings e
+             JOIN memory_records r ON r.memory_version_id = e.memory_version_id
+             WHERE e.model = ?1 AND r.namespace = ?2",
+        )?;
+        let mut rows = stmt.query(params![model, self.namespace])?;
+        let mut embeddings = BTreeMap::new();
+        while let Some(row) = rows.next()? {
+            let memory_version_id = parse_memory_version_id(&row.get::<_, String>(0)?)?;
+            let bytes = row.get::<_, Vec<u8>>(1)?;
+            let vector = bytes
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .collect();
+            embeddings.insert(memory_version_id, vector);
+        }
+        Ok(embeddings)
     }
 
     /// Persist one explicit lineage link between two memory version IDs.
@@ -574,40 +1729,89 @@ impl SqliteStore {
             return Err(anyhow!("justification MUST be provided for every link write"));
         }
 
-        let tx = self.conn.transaction().context("failed to start transaction")?;
-        tx.execute(
-            "INSERT INTO memory_links(
-                from_memory_version_id, to_memory_version_id, link_type, writer, justification, created_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![
+        let tx = write_tx(&mut self.conn, TransactionBehavior::Deferred)?;
+        for endpoint in [from, to] {
+            ensure_same_namespace(&tx, &self.namespace, endpoint)?;
+        }
+        tx.prepare_cached(INSERT_LINK_SQL)?
+            .execute(params![
                 from.to_string(),
                 to.to_string(),
                 link_type.as_str(),
                 writer,
                 justification,
                 now_rfc3339()?
-            ],
-        )
-        .context("failed to insert memory link")?;
+            ])
+            .context("failed to insert memory link")?;
         tx.commit().context("failed to commit link transaction")?;
         Ok(())
     }
 
+    /// Replace the payload text of `memory_version_id` with [`REDACTION_MARKER`], e.g. to honor
+    /// an erasure request, and record the redaction.
+    ///
+    /// The version keeps its identity, truth status, lineage links, tags, and provenance; its
+    /// recall terms are rebuilt from the marker and its stored embeddings are deleted. Earlier or
+    /// later versions of the same memory are not touched and need their own redaction.
+    ///
+    /// # Errors
+    /// Returns an error when accountability fields are empty, the version does not exist in this
+    /// namespace, a redaction already exists for it, or any write fails.
+    pub fn redact(
+        &mut self,
+        memory_version_id: MemoryVersionId,
+        writer: &str,
+        justification: &str,
+    ) -> Result<Redaction> {
+        if writer.trim().is_empty() {
+            return Err(anyhow!("writer MUST be provided for every redaction"));
+        }
+        if justification.trim().is_empty() {
+            return Err(anyhow!("justification MUST be provided for every redaction"));
+        }
+
+        let redaction = Redaction {
+            memory_version_id,
+            writer: writer.to_string(),
+            justification: justification.to_string(),
+            redacted_at: OffsetDateTime::now_utc(),
+        };
+        let tx = write_tx(&mut self.conn, TransactionBehavior::Immediate)?;
+        if redaction_exists(&tx, memory_version_id)? {
+            return Err(anyhow!(
+                "redaction already exists for memory_version_id {memory_version_id}"
+            ));
+        }
+        apply_redaction(&tx, &self.namespace, &redaction)?;
+        tx.commit().context("failed to commit redaction transaction")?;
+        Ok(redaction)
+    }
+
+    /// List redactions of records in this namespace, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read or decoded from `SQLite`.
+    pub fn list_redactions(&self) -> Result<Vec<Redaction>> {
+        self.redactions_written_between(0, i64::MAX)
+    }
+
     /// Persist one Context Package artifact.
     ///
     /// # Errors
     /// Returns an error when serialization or transaction writes fail.
     pub fn save_context_package(&mut self, package: &ContextPackage) -> Result<()> {
-        let tx = self.conn.transaction().context("failed to start transaction")?;
-        tx.execute(
-            "INSERT INTO context_packages(context_package_id, generated_at, package_json)
-             VALUES (?1, ?2, ?3)",
-            params![
-                package.context_package_id,
-                rfc3339(package.generated_at)?,
-                serde_json::to_string(package).context("failed to serialize context package")?,
-            ],
-        )
+        let tx = write_tx(&mut self.conn, TransactionBehavior::Deferred)?;
+        tx.prepare_cached(
+            "INSERT INTO context_packages(
+                context_package_id, generated_at, package_json, namespace, write_seq
+             ) VALUES (?1, ?2, ?3, ?4, (SELECT COALESCE(MAX(write_seq), 0) + 1 FROM context_packages))",
+        )?
+        .execute(params![
+            package.context_package_id,
+            rfc3339(package.generated_at)?,
+            serde_json::to_string(package).context("failed to serialize context package")?,
+            self.namespace,
+        ])
         .context("failed to persist context package")?;
         tx.commit().context("failed to commit context package transaction")?;
         Ok(())
@@ -618,11 +1822,12 @@ impl SqliteStore {
     /// # Errors
     /// Returns an error when lookup or JSON deserialization fails.
     pub fn get_context_package(&self, context_package_id: &str) -> Result<Option<ContextPackage>> {
-        let mut stmt = self
-            .conn
-            .prepare("SELECT package_json FROM context_packages WHERE context_package_id = ?1")?;
+        let mut stmt = self.conn.prepare(
+            "SELECT package_json FROM context_packages
+             WHERE context_package_id = ?1 AND namespace = ?2",
+        )?;
         let value = stmt
-            .query_row(params![context_package_id], |row| row.get::<_, String>(0))
+            .query_row(params![context_package_id, self.namespace], |row| row.get::<_, String>(0))
             .optional()?;
 
         match value {
@@ -635,41 +1840,386 @@ impl SqliteStore {
         }
     }
 
-    /// Export records and context packages as deterministic NDJSON plus manifest.
+    /// Load one page of context packages matching `filter`, newest `generated_at` first with
+    /// ties broken by `context_package_id`.
     ///
     /// # Errors
-    /// Returns an error when export files cannot be created, written, or serialized.
-    pub fn export_snapshot(&self, out_dir: &Path) -> Result<ExportManifest> {
-        fs::create_dir_all(out_dir)
-            .with_context(|| format!("failed to create export directory {}", out_dir.display()))?;
-
-        let records = self.list_records()?;
-        let context_packages = self.list_context_packages()?;
-
-        let records_path = out_dir.join("memory_records.ndjson");
-        let record_digest = write_ndjson_file(&records_path, &records)?;
+    /// Returns an error when `limit` is zero, the `after` cursor does not exist, or rows
+    /// cannot be read or decoded from `SQLite`.
+    pub fn list_context_packages(
+        &self,
+        filter: &ContextPackageFilter,
+    ) -> Result<ContextPackagePage> {
+        if filter.limit == Some(0) {
+            return Err(anyhow!("limit MUST be >= 1"));
+        }
 
-        let packages_path = out_dir.join("context_packages.ndjson");
-        let package_digest = write_ndjson_file(&packages_path, &context_packages)?;
+        let mut conditions = Vec::new();
+        let mut values: Vec<rusqlite::types::Value> = Vec::new();
+        let mut bind = |value: rusqlite::types::Value| {
+            values.push(value);
+            format!("?{}", values.len())
+        };
+        let namespace = bind(self.namespace.clone().into());
+        conditions.push(format!("namespace = {namespace}"));
+        // Stored offsets and fractional seconds vary, so compare instants rather than text.
+        if let Some(from) = filter.generated_from {
+            let param = bind(rfc3339(from)?.into());
+            conditions.push(format!("julianday(generated_at) >= julianday({param})"));
+        }
+        if let Some(to) = filter.generated_to {
+            let param = bind(rfc3339(to)?.into());
+            conditions.push(format!("julianday(generated_at) < julianday({param})"));
+        }
+        if let Some(actor) = &filter.actor {
+            let param = bind(actor.clone().into());
+            conditions.push(format!("json_extract(package_json, '$.query.actor') = {param}"));
+        }
+        if let Some(resource) = &filter.resource {
+            let param = bind(resource.clone().into());
+            conditions.push(format!("json_extract(package_json, '$.query.resource') = {param}"));
+        }
+        if let Some(answer_result) = &filter.answer_result {
+            let param = bind(answer_result.as_str().to_string().into());
+            conditions.push(format!("json_extract(package_json, '$.answer.result') = {param}"));
+        }
+        if let Some(after) = &filter.after {
+            let generated_at: String = self
+                .conn
+                .query_row(
+                    "SELECT generated_at FROM context_packages
+                     WHERE context_package_id = ?1 AND namespace = ?2",
+                    params![after, self.namespace],
+                    |row| row.get(0),
+                )
+                .optional()?
+                .ok_or_else(|| anyhow!("cursor package not found: {after}"))?;
+            let generated_at = bind(generated_at.into());
+            let after = bind(after.clone().into());
+            conditions.push(format!(
+                "(generated_at < {generated_at} \
+                 OR (generated_at = {generated_at} AND context_package_id > {after}))"
+            ));
+        }
 
-        let manifest = ExportManifest {
-            schema_version: LATEST_SCHEMA_VERSION,
-            exported_at: now_rfc3339()?,
-            files: vec![
-                ExportFileDigest {
-                    path: "memory_records.ndjson".to_string(),
-                    sha256: record_digest.0,
-                    records: record_digest.1,
-                },
-                ExportFileDigest {
-                    path: "context_packages.ndjson".to_string(),
-                    sha256: package_digest.0,
-                    records: package_digest.1,
-                },
-            ],
+        let mut sql = "SELECT package_json FROM context_packages WHERE ".to_string();
+        sql.push_str(&conditions.join(" AND "));
+        sql.push_str(" ORDER BY generated_at DESC, context_package_id ASC");
+        // Fetch one extra row to learn whether another page exists.
+        if let Some(limit) = filter.limit {
+            let param = bind(i64::try_from(limit + 1).unwrap_or(i64::MAX).into());
+            sql.push_str(" LIMIT ");
+            sql.push_str(&param);
+        }
+
+        let mut stmt = self.conn.prepare_cached(&sql)?;
+        let rows = stmt.query_map(params_from_iter(values), |row| row.get::<_, String>(0))?;
+        let mut packages = Vec::new();
+        for row in rows {
+            let raw = row?;
+            let parsed = serde_json::from_str::<ContextPackage>(&raw)
+                .context("failed to deserialize context package row")?;
+            packages.push(parsed);
+        }
+        let next_after = match filter.limit {
+            Some(limit) if packages.len() > limit => {
+                packages.truncate(limit);
+                packages.last().map(|package| package.context_package_id.clone())
+            }
+            _ => None,
+        };
+
+        Ok(ContextPackagePage { packages, next_after })
+    }
+
+    /// Append the timing of one query to this namespace's `query_metrics`.
+    ///
+    /// # Errors
+    /// Returns an error when a count does not fit in `SQLite` or the insert fails.
+    pub fn record_query_metric(&mut self, metric: &QueryMetric) -> Result<()> {
+        let count = |value: u64| i64::try_from(value).context("query metric value out of range");
+        let tx = write_tx(&mut self.conn, TransactionBehavior::Deferred)?;
+        tx.prepare_cached(
+            "INSERT INTO query_metrics(
+                namespace, query_mode, context_package_id, recorded_at, duration_micros,
+                candidate_records, selected_items, excluded_items
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?
+        .execute(params![
+            self.namespace,
+            metric.query_mode.as_str(),
+            metric.context_package_id,
+            rfc3339(metric.recorded_at)?,
+            count(metric.duration_micros)?,
+            count(metric.candidate_records)?,
+            count(metric.selected_items)?,
+            count(metric.excluded_items)?,
+        ])
+        .context("failed to persist query metric")?;
+        tx.commit().context("failed to commit query metric")?;
+        Ok(())
+    }
+
+    /// List this namespace's query metrics recorded at or after `since`, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read or decoded from `SQLite`.
+    pub fn list_query_metrics(&self, since: Option<OffsetDateTime>) -> Result<Vec<QueryMetric>> {
+        let since = since.map(rfc3339).transpose()?;
+        let mut stmt = self.conn.prepare(
+            "SELECT query_mode, context_package_id, recorded_at, duration_micros,
+                    candidate_records, selected_items, excluded_items
+             FROM query_metrics
+             WHERE namespace = ?1 AND (?2 IS NULL OR julianday(recorded_at) >= julianday(?2))
+             ORDER BY julianday(recorded_at) ASC, id ASC",
+        )?;
+        let rows = stmt.query_map(params![self.namespace, since], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                [row.get::<_, i64>(3)?, row.get(4)?, row.get(5)?, row.get(6)?],
+            ))
+        })?;
+        rows.map(|row| {
+            let (query_mode, context_package_id, recorded_at, counts) = row?;
+            let [duration_micros, candidate_records, selected_items, excluded_items] =
+                counts.map(|value| u64::try_from(value).unwrap_or_default());
+            Ok(QueryMetric {
+                query_mode: QueryMode::parse(&query_mode)
+                    .ok_or_else(|| anyhow!("unknown query_mode in query_metrics: {query_mode}"))?,
+                context_package_id,
+                recorded_at: parse_rfc3339(&recorded_at)?,
+                duration_micros,
+                candidate_records,
+                selected_items,
+                excluded_items,
+            })
+        })
+        .collect()
+    }
+
+    /// Attach the Outcome record `outcome` to the context package that informed the decision
+    /// it reports on, so later queries can ask what followed packages selecting a record.
+    ///
+    /// # Errors
+    /// Returns an error when accountability fields are empty, the package or record does not
+    /// exist in this namespace, the record is not an Outcome, the pair is already linked, or
+    /// the write fails.
+    pub fn link_package_outcome(
+        &mut self,
+        context_package_id: &str,
+        outcome: MemoryVersionId,
+        writer: &str,
+        justification: &str,
+    ) -> Result<PackageOutcome> {
+        if writer.trim().is_empty() {
+            return Err(anyhow!("writer MUST be provided for every outcome link"));
+        }
+        if justification.trim().is_empty() {
+            return Err(anyhow!("justification MUST be provided for every outcome link"));
+        }
+
+        let link = PackageOutcome {
+            context_package_id: context_package_id.to_string(),
+            outcome_memory_version_id: outcome,
+            writer: writer.to_string(),
+            justification: justification.to_string(),
+            linked_at: OffsetDateTime::now_utc(),
         };
+        let tx = write_tx(&mut self.conn, TransactionBehavior::Immediate)?;
+        let package_exists: bool = tx.query_row(
+            "SELECT EXISTS(
+                SELECT 1 FROM context_packages WHERE context_package_id = ?1 AND namespace = ?2
+             )",
+            params![context_package_id, self.namespace],
+            |row| row.get(0),
+        )?;
+        if !package_exists {
+            return Err(anyhow!("context package not found: {context_package_id}"));
+        }
+        let record_type: String = tx
+            .query_row(
+                "SELECT record_type FROM memory_records
+                 WHERE memory_version_id = ?1 AND namespace = ?2",
+                params![outcome.to_string(), self.namespace],
+                |row| row.get(0),
+            )
+            .optional()?
+            .ok_or_else(|| anyhow!("memory_version_id not found: {outcome}"))?;
+        if record_type != RecordType::Outcome.as_str() {
+            return Err(anyhow!(
+                "memory_version_id {outcome} is a {record_type} record, not an outcome"
+            ));
+        }
+        let inserted = tx
+            .prepare_cached(
+                "INSERT OR IGNORE INTO package_outcomes(
+                    context_package_id, outcome_memory_version_id, namespace, writer,
+                    justification, linked_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?
+            .execute(params![
+                link.context_package_id,
+                outcome.to_string(),
+                self.namespace,
+                link.writer,
+                link.justification,
+                rfc3339(link.linked_at)?,
+            ])
+            .context("failed to persist package outcome")?;
+        if inserted == 0 {
+            return Err(anyhow!(
+                "outcome {outcome} is already linked to context package {context_package_id}"
+            ));
+        }
+        tx.commit().context("failed to commit package outcome transaction")?;
+        Ok(link)
+    }
 
-        let manifest_path = out_dir.join("manifest.json");
+    /// List the outcomes linked to context packages in this namespace whose `selected_items`
+    /// include `memory_version_id`, oldest package first, then by `linked_at`.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read or decoded from `SQLite`.
+    pub fn outcomes_following(
+        &self,
+        memory_version_id: MemoryVersionId,
+    ) -> Result<Vec<FollowingOutcome>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT o.context_package_id, p.generated_at, o.outcome_memory_version_id,
+                    o.writer, o.justification, o.linked_at
+             FROM package_outcomes o
+             JOIN context_packages p ON p.context_package_id = o.context_package_id
+             WHERE o.namespace = ?1
+               AND EXISTS (
+                 SELECT 1 FROM json_each(p.package_json, '$.selected_items') item
+                 WHERE json_extract(item.value, '$.memory_version_id') = ?2
+               )
+             ORDER BY julianday(p.generated_at) ASC, o.context_package_id ASC,
+                      julianday(o.linked_at) ASC, o.outcome_memory_version_id ASC",
+        )?;
+        let rows =
+            stmt.query_map(params![self.namespace, memory_version_id.to_string()], |row| {
+                Ok([
+                    row.get::<_, String>(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ])
+            })?;
+        let mut followed = Vec::new();
+        for row in rows {
+            let [context_package_id, generated_at, outcome, writer, justification, linked_at] =
+                row?;
+            followed.push((
+                PackageOutcome {
+                    context_package_id,
+                    outcome_memory_version_id: parse_memory_version_id(&outcome)?,
+                    writer,
+                    justification,
+                    linked_at: parse_rfc3339(&linked_at)?,
+                },
+                parse_rfc3339(&generated_at)?,
+            ));
+        }
+        if followed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query = RecordQuery::in_namespace(&self.namespace);
+        let ids = followed
+            .iter()
+            .map(|(link, _)| link.outcome_memory_version_id.to_string())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|id| query.bind(id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        query.conditions.push(format!("memory_version_id IN ({ids})"));
+        let rows = query.fetch(&self.conn, None)?;
+        let outcomes = hydrate_records(&self.conn, rows)?
+            .into_iter()
+            .map(|record| (record.memory_version_id, record))
+            .collect::<BTreeMap<_, _>>();
+        followed
+            .into_iter()
+            .map(|(link, generated_at)| {
+                let outcome =
+                    outcomes.get(&link.outcome_memory_version_id).cloned().ok_or_else(|| {
+                        anyhow!("outcome record missing: {}", link.outcome_memory_version_id)
+                    })?;
+                Ok(FollowingOutcome { link, generated_at, outcome })
+            })
+            .collect()
+    }
+
+    /// Export records and context packages as deterministic NDJSON plus manifest.
+    ///
+    /// # Errors
+    /// Returns an error when export files cannot be created, written, or serialized.
+    pub fn export_snapshot(&self, out_dir: &Path) -> Result<ExportManifest> {
+        self.export_snapshot_filtered(out_dir, &ExportFilter::default())
+    }
+
+    /// Export only records and context packages written after `since`, typically the
+    /// `checkpoint` of the previous export's manifest.
+    ///
+    /// Importing the delta into a store that already holds the earlier export applies it on
+    /// top; the manifest carries the new `checkpoint` for the next delta.
+    ///
+    /// # Errors
+    /// Returns an error when export files cannot be created, written, or serialized.
+    pub fn export_snapshot_since(
+        &self,
+        out_dir: &Path,
+        since: ExportCheckpoint,
+    ) -> Result<ExportManifest> {
+        self.export_snapshot_with(out_dir, &ExportFilter::default(), Some(since))
+    }
+
+    /// Export the records selected by `filter` as a partial snapshot, recording the filter in the
+    /// manifest.
+    ///
+    /// Records linked from selected records (`supersedes`, `contradicts`) are exported too, so
+    /// the snapshot imports on its own; context packages are kept only when every item they
+    /// reference is exported.
+    ///
+    /// # Errors
+    /// Returns an error when export files cannot be created, written, or serialized.
+    pub fn export_snapshot_filtered(
+        &self,
+        out_dir: &Path,
+        filter: &ExportFilter,
+    ) -> Result<ExportManifest> {
+        self.export_snapshot_with(out_dir, filter, None)
+    }
+
+    fn export_snapshot_with(
+        &self,
+        out_dir: &Path,
+        filter: &ExportFilter,
+        since: Option<ExportCheckpoint>,
+    ) -> Result<ExportManifest> {
+        fs::create_dir_all(out_dir)
+            .with_context(|| format!("failed to create export directory {}", out_dir.display()))?;
+
+        let rows = self.export_rows(filter, since)?;
+        let record_digest = write_ndjson_file(&out_dir.join(EXPORT_RECORDS_FILE), &rows.records)?;
+        let package_digest =
+            write_ndjson_file(&out_dir.join(EXPORT_CONTEXT_PACKAGES_FILE), &rows.context_packages)?;
+        let redaction_digest =
+            write_ndjson_file(&out_dir.join(EXPORT_REDACTIONS_FILE), &rows.redactions)?;
+        let manifest = self.export_manifest(
+            filter,
+            since,
+            &rows,
+            [record_digest, package_digest, redaction_digest],
+        )?;
+
+        let manifest_path = out_dir.join(EXPORT_MANIFEST_FILE);
         let manifest_json =
             serde_json::to_vec_pretty(&manifest).context("failed to serialize export manifest")?;
         fs::write(&manifest_path, manifest_json).with_context(|| {
@@ -679,27 +2229,270 @@ impl SqliteStore {
         Ok(manifest)
     }
 
+    /// Build the manifest of a snapshot without keeping its files: each NDJSON file is
+    /// serialized once into a byte counter for its digest and length, and again by
+    /// [`SnapshotExport::write_file`] when it is written out.
+    ///
+    /// # Errors
+    /// Returns an error when rows cannot be read or serialized.
+    pub fn export_snapshot_stream(
+        &self,
+        filter: &ExportFilter,
+        since: Option<ExportCheckpoint>,
+    ) -> Result<SnapshotExport> {
+        let rows = self.export_rows(filter, since)?;
+        let (records_len, record_digest) = ndjson_len(&rows.records, &EXPORT_RECORDS_FILE)?;
+        let (packages_len, package_digest) =
+            ndjson_len(&rows.context_packages, &EXPORT_CONTEXT_PACKAGES_FILE)?;
+        let (redactions_len, redaction_digest) =
+            ndjson_len(&rows.redactions, &EXPORT_REDACTIONS_FILE)?;
+        let lens = BTreeMap::from([
+            (EXPORT_RECORDS_FILE.to_string(), records_len),
+            (EXPORT_CONTEXT_PACKAGES_FILE.to_string(), packages_len),
+            (EXPORT_REDACTIONS_FILE.to_string(), redactions_len),
+        ]);
+        let manifest = self.export_manifest(
+            filter,
+            since,
+            &rows,
+            [record_digest, package_digest, redaction_digest],
+        )?;
+        Ok(SnapshotExport { manifest, rows, lens })
+    }
+
+    fn export_rows(
+        &self,
+        filter: &ExportFilter,
+        since: Option<ExportCheckpoint>,
+    ) -> Result<ExportRows> {
+        // Take the watermark first so rows written during the export land in the next delta.
+        let checkpoint = self.export_checkpoint()?;
+        let since = since.unwrap_or_default();
+        let (mut records, mut context_packages) = self.rows_written_between(since, checkpoint)?;
+        let mut redactions = self.redactions_written_between(
+            since.redactions_write_seq,
+            checkpoint.redactions_write_seq,
+        )?;
+        if !filter.is_empty() {
+            records = select_export_records(records, filter);
+            let exported =
+                records.iter().map(|record| record.memory_version_id).collect::<BTreeSet<_>>();
+            context_packages.retain(|package| {
+                package
+                    .selected_items
+                    .iter()
+                    .chain(&package.excluded_items)
+                    .all(|item| exported.contains(&item.memory_version_id))
+            });
+            redactions.retain(|redaction| exported.contains(&redaction.memory_version_id));
+        }
+        Ok(ExportRows { checkpoint, records, context_packages, redactions })
+    }
+
+    fn export_manifest(
+        &self,
+        filter: &ExportFilter,
+        since: Option<ExportCheckpoint>,
+        rows: &ExportRows,
+        digests: [(String, usize); 3],
+    ) -> Result<ExportManifest> {
+        let files = [EXPORT_RECORDS_FILE, EXPORT_CONTEXT_PACKAGES_FILE, EXPORT_REDACTIONS_FILE]
+            .into_iter()
+            .zip(digests)
+            .map(|(path, (sha256, records))| ExportFileDigest {
+                path: path.to_string(),
+                sha256,
+                records,
+            })
+            .collect();
+        Ok(ExportManifest {
+            schema_version: LATEST_SCHEMA_VERSION,
+            exported_at: now_rfc3339()?,
+            files,
+            namespace: (self.namespace != DEFAULT_NAMESPACE).then(|| self.namespace.clone()),
+            filter: (!filter.is_empty()).then(|| filter.clone()),
+            since,
+            checkpoint: Some(rows.checkpoint),
+        })
+    }
+
+    fn export_checkpoint(&self) -> Result<ExportCheckpoint> {
+        self.conn
+            .query_row(
+                "SELECT
+                    (SELECT COALESCE(MAX(write_seq), 0) FROM memory_records),
+                    (SELECT COALESCE(MAX(write_seq), 0) FROM context_packages),
+                    (SELECT COALESCE(MAX(write_seq), 0) FROM redactions)",
+                [],
+                |row| {
+                    Ok(ExportCheckpoint {
+                        records_write_seq: row.get(0)?,
+                        context_packages_write_seq: row.get(1)?,
+                        redactions_write_seq: row.get(2)?,
+                    })
+                },
+            )
+            .context("failed to read export checkpoint")
+    }
+
+    /// Records and context packages in this namespace written after `since` and at or before
+    /// `until`, in list order.
+    fn rows_written_between(
+        &self,
+        since: ExportCheckpoint,
+        until: ExportCheckpoint,
+    ) -> Result<(Vec<MemoryRecord>, Vec<ContextPackage>)> {
+        let mut query = RecordQuery::in_namespace(&self.namespace);
+        let from = query.bind(since.records_write_seq);
+        let to = query.bind(until.records_write_seq);
+        query.conditions.push(format!("write_seq > {from} AND write_seq <= {to}"));
+        let records = hydrate_records(&self.conn, query.fetch(&self.conn, None)?)?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT package_json FROM context_packages
+             WHERE namespace = ?1 AND write_seq > ?2 AND write_seq <= ?3
+             ORDER BY generated_at DESC, context_package_id ASC",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                self.namespace,
+                since.context_packages_write_seq,
+                until.context_packages_write_seq
+            ],
+            |row| row.get::<_, String>(0),
+        )?;
+        let mut packages = Vec::new();
+        for row in rows {
+            packages.push(
+                serde_json::from_str(&row?)
+                    .context("failed to deserialize stored context package")?,
+            );
+        }
+        Ok((records, packages))
+    }
+
+    /// Redactions of records in this namespace with `write_seq` in `(since, until]`, oldest first.
+    fn redactions_written_between(&self, since: i64, until: i64) -> Result<Vec<Redaction>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT r.memory_version_id, r.writer, r.justification, r.redacted_at
+             FROM redactions r
+             JOIN memory_records m ON m.memory_version_id = r.memory_version_id
+             WHERE m.namespace = ?1 AND r.write_seq > ?2 AND r.write_seq <= ?3
+             ORDER BY r.write_seq ASC",
+        )?;
+        let mut rows = stmt.query(params![self.namespace, since, until])?;
+        let mut redactions = Vec::new();
+        while let Some(row) = rows.next()? {
+            redactions.push(Redaction {
+                memory_version_id: parse_memory_version_id(&row.get::<_, String>(0)?)?,
+                writer: row.get(1)?,
+                justification: row.get(2)?,
+                redacted_at: parse_rfc3339(&row.get::<_, String>(3)?)?,
+            });
+        }
+        Ok(redactions)
+    }
+
     /// Import an exported snapshot directory into this database.
     ///
     /// # Errors
     /// Returns an error when migration, parsing, duplicate handling, or writes fail.
     pub fn import_snapshot(&mut self, in_dir: &Path, skip_existing: bool) -> Result<ImportSummary> {
         self.migrate()?;
-        let manifest_path = in_dir.join("manifest.json");
+        let limits = self.import_limits;
+        let manifest_path = in_dir.join(EXPORT_MANIFEST_FILE);
+        if file_len(&manifest_path) > u64::try_from(limits.max_line_bytes).unwrap_or(u64::MAX) {
+            return Err(anyhow!(
+                "manifest {} exceeds the {} byte limit",
+                manifest_path.display(),
+                limits.max_line_bytes
+            ));
+        }
         let manifest = read_export_manifest(&manifest_path)?;
-        validate_import_manifest(in_dir, &manifest)?;
+        validate_import_manifest(&manifest, &limits, |required| {
+            let file_path = in_dir.join(required);
+            if !file_path.exists() {
+                return Err(anyhow!("manifest references missing file {}", file_path.display()));
+            }
+            ndjson_digest_and_records(open_ndjson_file(&file_path)?, &file_path.display(), &limits)
+        })?;
+
+        let records_path = in_dir.join(EXPORT_RECORDS_FILE);
+        let records =
+            read_ndjson(open_ndjson_file(&records_path)?, &records_path.display(), &limits)?;
+        let packages_path = in_dir.join(EXPORT_CONTEXT_PACKAGES_FILE);
+        let packages =
+            read_ndjson(open_ndjson_file(&packages_path)?, &packages_path.display(), &limits)?;
+        let redactions = if manifest_lists(&manifest, EXPORT_REDACTIONS_FILE) {
+            let redactions_path = in_dir.join(EXPORT_REDACTIONS_FILE);
+            read_ndjson(open_ndjson_file(&redactions_path)?, &redactions_path.display(), &limits)?
+        } else {
+            Vec::new()
+        };
+        self.apply_import(&manifest, records, packages, &redactions, skip_existing)
+    }
+
+    /// Import a snapshot whose NDJSON files are held in memory, keyed by manifest path, e.g. as
+    /// read from an archive.
+    ///
+    /// # Errors
+    /// Returns an error when migration, manifest validation, parsing, duplicate handling, or
+    /// writes fail.
+    pub fn import_snapshot_in_memory(
+        &mut self,
+        manifest: &ExportManifest,
+        files: &BTreeMap<String, Vec<u8>>,
+        skip_existing: bool,
+    ) -> Result<ImportSummary> {
+        let mut snapshot = SnapshotStream::new(manifest.clone(), self.import_limits)?;
+        for (path, body) in files {
+            snapshot.read_file(path, body.as_slice())?;
+        }
+        self.import_snapshot_stream(snapshot, skip_existing)
+    }
 
-        let records_path = in_dir.join("memory_records.ndjson");
-        let package_path = in_dir.join("context_packages.ndjson");
+    /// Import a snapshot whose files were read through a [`SnapshotStream`].
+    ///
+    /// # Errors
+    /// Returns an error when migration, manifest validation, duplicate handling, or writes fail.
+    pub fn import_snapshot_stream(
+        &mut self,
+        snapshot: SnapshotStream,
+        skip_existing: bool,
+    ) -> Result<ImportSummary> {
+        self.migrate()?;
+        let SnapshotStream { manifest, limits, digests, records, packages, redactions } = snapshot;
+        validate_import_manifest(&manifest, &limits, |required| {
+            digests
+                .get(required)
+                .cloned()
+                .ok_or_else(|| anyhow!("manifest references missing file {required}"))
+        })?;
+        let redactions =
+            if manifest_lists(&manifest, EXPORT_REDACTIONS_FILE) { redactions } else { Vec::new() };
+        self.apply_import(&manifest, records, packages, &redactions, skip_existing)
+    }
 
+    fn apply_import(
+        &mut self,
+        manifest: &ExportManifest,
+        records: Vec<MemoryRecord>,
+        packages: Vec<ContextPackage>,
+        redactions: &[Redaction],
+        skip_existing: bool,
+    ) -> Result<ImportSummary> {
         let mut summary = ImportSummary {
             imported_records: 0,
             skipped_existing_records: 0,
             imported_context_packages: 0,
             skipped_existing_context_packages: 0,
+            applied_redactions: 0,
+            skipped_redactions: 0,
+            checkpoint: manifest.checkpoint,
         };
 
-        for record in read_ndjson_file::<MemoryRecord>(&records_path)? {
+        let mut new_records = Vec::new();
+        for record in records {
             if self.record_exists(record.memory_version_id)? {
                 if skip_existing {
                     summary.skipped_existing_records += 1;
@@ -711,11 +2504,13 @@ impl SqliteStore {
                     record.memory_version_id
                 ));
             }
-            self.write_record(&record)?;
-            summary.imported_records += 1;
+            new_records.push(record);
         }
+        // Exports list newest records first, so lineage links may point later in the file.
+        self.write_records_batch(&new_records)?;
+        summary.imported_records = new_records.len();
 
-        for package in read_ndjson_file::<ContextPackage>(&package_path)? {
+        for package in packages {
             if self.context_package_exists(&package.context_package_id)? {
                 if skip_existing {
                     summary.skipped_existing_context_packages += 1;
@@ -731,9 +2526,63 @@ impl SqliteStore {
             summary.imported_context_packages += 1;
         }
 
+        // Imported records already carry redacted payloads; replaying the redactions scrubs
+        // copies this store held before the import.
+        let tx = write_tx(&mut self.conn, TransactionBehavior::Deferred)?;
+        for redaction in redactions {
+            if redaction_exists(&tx, redaction.memory_version_id)?
+                || !record_in_namespace(&tx, &self.namespace, redaction.memory_version_id)?
+            {
+                summary.skipped_redactions += 1;
+                continue;
+            }
+            apply_redaction(&tx, &self.namespace, redaction)?;
+            summary.applied_redactions += 1;
+        }
+        tx.commit().context("failed to commit redaction import transaction")?;
+
         Ok(summary)
     }
 
+    /// Write an encrypted copy of this plaintext store to `out_file`, keyed with `key`; the
+    /// original is left untouched so callers can swap files once the copy is verified.
+    ///
+    /// # Errors
+    /// Returns an error when `out_file` exists, the `sqlcipher` feature is disabled, or the
+    /// export fails.
+    pub fn encrypt_to(&self, out_file: &Path, key: &StoreKey) -> Result<()> {
+        if out_file.exists() {
+            return Err(anyhow!("encrypted store already exists: {}", out_file.display()));
+        }
+        self.sqlcipher_export(out_file, key)
+            .with_context(|| format!("failed to write encrypted store {}", out_file.display()))
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    fn sqlcipher_export(&self, out_file: &Path, key: &StoreKey) -> Result<()> {
+        let out = out_file
+            .to_str()
+            .ok_or_else(|| anyhow!("store path must be UTF-8: {}", out_file.display()))?;
+        self.conn
+            .execute(
+                "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+                params![out, key.sqlcipher_literal()],
+            )
+            .context("failed to attach encrypted store")?;
+        let exported = self
+            .conn
+            .query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()))
+            .context("failed to copy rows into encrypted store");
+        self.conn.execute("DETACH DATABASE encrypted", []).context("failed to detach store")?;
+        exported
+    }
+
+    #[cfg(not(feature = "sqlcipher"))]
+    #[allow(clippy::unused_self)]
+    fn sqlcipher_export(&self, _out_file: &Path, _key: &StoreKey) -> Result<()> {
+        Err(sqlcipher_unavailable())
+    }
+
     /// Create a `SQLite` backup file of the current main database.
     ///
     /// # Errors
@@ -806,200 +2655,298 @@ impl SqliteStore {
         })
     }
 
-    fn insert_payload(tx: &rusqlite::Transaction<'_>, record: &MemoryRecord) -> Result<()> {
+    /// Refresh planner statistics with `PRAGMA optimize`, checkpoint and truncate the WAL, and
+    /// optionally write a compacted copy with `VACUUM INTO`.
+    ///
+    /// # Errors
+    /// Returns an error when `vacuum_into` already exists or a maintenance statement fails.
+    pub fn maintenance(&self, vacuum_into: Option<&Path>) -> Result<MaintenanceReport> {
+        if let Some(out) = vacuum_into.filter(|out| out.exists()) {
+            return Err(anyhow!("vacuum target already exists: {}", out.display()));
+        }
+        let wal_bytes_before = self.wal_bytes();
+        // Optimize first: refreshed statistics are written to the WAL the checkpoint truncates.
+        self.conn.execute_batch("PRAGMA optimize;").context("failed to run PRAGMA optimize")?;
+        let (busy, checkpointed_frames) = self
+            .conn
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(2)?))
+            })
+            .context("failed to run PRAGMA wal_checkpoint(TRUNCATE)")?;
+        let database_bytes = self
+            .conn
+            .query_row(
+                "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .context("failed to read database size")?;
+
+        let vacuum_bytes = match vacuum_into {
+            Some(out) => {
+                let target = out
+                    .to_str()
+                    .ok_or_else(|| anyhow!("vacuum target must be UTF-8: {}", out.display()))?;
+                self.conn
+                    .execute("VACUUM INTO ?1", [target])
+                    .with_context(|| format!("failed to vacuum into {}", out.display()))?;
+                Some(file_len(out))
+            }
+            None => None,
+        };
+
+        let wal_bytes_after = self.wal_bytes();
+        Ok(MaintenanceReport {
+            wal_bytes_before,
+            wal_bytes_after,
+            reclaimed_bytes: wal_bytes_before.saturating_sub(wal_bytes_after),
+            checkpoint_busy: busy != 0,
+            checkpointed_frames,
+            database_bytes: u64::try_from(database_bytes).unwrap_or_default(),
+            vacuum_into: vacuum_into.map(|out| out.display().to_string()),
+            vacuum_bytes,
+        })
+    }
+
+    /// Current size of the write-ahead log; zero for in-memory databases and truncated logs.
+    #[must_use]
+    pub fn wal_bytes(&self) -> u64 {
+        self.conn.path().filter(|path| !path.is_empty()).map_or(0, |path| {
+            let mut wal = std::ffi::OsString::from(path);
+            wal.push("-wal");
+            file_len(Path::new(&wal))
+        })
+    }
+
+    /// Rows of payload, tag, embedding, redaction, and recall-term tables left without their
+    /// record version, across all namespaces.
+    ///
+    /// # Errors
+    /// Returns an error when a table is missing (the store is not migrated) or a query fails.
+    pub fn orphaned_rows(&self) -> Result<Vec<OrphanedRow>> {
+        query_orphaned_rows(&self.conn)
+    }
+
+    /// Links whose source or target version is missing, across all namespaces.
+    ///
+    /// # Errors
+    /// Returns an error when the link query fails.
+    pub fn dangling_links(&self) -> Result<Vec<DanglingLink>> {
+        query_dangling_links(&self.conn)
+    }
+
+    /// Context packages naming record versions that are no longer stored, across all namespaces.
+    ///
+    /// # Errors
+    /// Returns an error when the package query fails.
+    pub fn stale_context_packages(&self) -> Result<Vec<StaleContextPackage>> {
+        query_stale_context_packages(&self.conn)
+    }
+
+    /// Find dangling links, orphaned per-version rows, and stale context packages, and unless
+    /// `dry_run` delete them all in one transaction. Outcome links of a stale package are
+    /// deleted with it.
+    ///
+    /// Records are never touched; the deleted rows are the ones no record version reaches.
+    ///
+    /// # Errors
+    /// Returns an error when a probe or delete fails; the transaction is then rolled back.
+    pub fn repair_integrity(&mut self, dry_run: bool) -> Result<RepairReport> {
+        let tx = write_tx(&mut self.conn, TransactionBehavior::Immediate)?;
+        let mut report = RepairReport {
+            dry_run,
+            dangling_links: query_dangling_links(&tx)?,
+            orphaned_rows: query_orphaned_rows(&tx)?,
+            stale_context_packages: query_stale_context_packages(&tx)?,
+            removed_links: 0,
+            removed_rows: 0,
+            removed_context_packages: 0,
+        };
+        if dry_run {
+            return Ok(report);
+        }
+
+        let mut delete_link = tx.prepare_cached("DELETE FROM memory_links WHERE id = ?1")?;
+        for link in &report.dangling_links {
+            report.removed_links += delete_link.execute(params![link.link_id])?;
+        }
+        drop(delete_link);
+        for table in VERSION_SCOPED_TABLES {
+            report.removed_rows += tx
+                .execute(
+                    &format!(
+                        "DELETE FROM {table}
+                         WHERE NOT EXISTS (
+                           SELECT 1 FROM memory_records r
+                           WHERE r.memory_version_id = {table}.memory_version_id
+                         )"
+                    ),
+                    [],
+                )
+                .with_context(|| format!("failed to delete orphaned rows from {table}"))?;
+        }
+        let mut delete_outcomes =
+            tx.prepare_cached("DELETE FROM package_outcomes WHERE context_package_id = ?1")?;
+        let mut delete_package =
+            tx.prepare_cached("DELETE FROM context_packages WHERE context_package_id = ?1")?;
+        for package in &report.stale_context_packages {
+            delete_outcomes.execute(params![package.context_package_id])?;
+            report.removed_context_packages +=
+                delete_package.execute(params![package.context_package_id])?;
+        }
+        drop(delete_outcomes);
+        drop(delete_package);
+        tx.commit().context("failed to commit integrity repair transaction")?;
+        Ok(report)
+    }
+
+    /// Latest record `created_at` across all namespaces, compared as instants so mixed UTC
+    /// offsets order correctly; `None` for an empty store.
+    ///
+    /// # Errors
+    /// Returns an error when the query fails or the stored timestamp is not RFC3339.
+    pub fn latest_record_created_at(&self) -> Result<Option<OffsetDateTime>> {
+        let latest: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT created_at FROM memory_records ORDER BY julianday(created_at) DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("failed to read latest record created_at")?;
+        latest.as_deref().map(parse_rfc3339).transpose()
+    }
+
+    fn insert_payload(conn: &Connection, record: &MemoryRecord) -> Result<()> {
         match &record.payload {
             MemoryPayload::Constraint(payload) => {
-                tx.execute(
-                    "INSERT INTO constraint_payloads(memory_version_id, actor, action, resource, effect, note)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                    params![
-                        record.memory_version_id.to_string(),
-                        payload.scope.actor,
-                        payload.scope.action,
-                        payload.scope.resource,
-                        payload.effect.as_str(),
-                        payload.note,
-                    ],
-                )
+                conn.prepare_cached(
+                    "INSERT INTO constraint_payloads(
+                        memory_version_id, actor, action, resource, effect, note, expires_at
+                     )
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                )?
+                .execute(params![
+                    record.memory_version_id.to_string(),
+                    payload.scope.actor,
+                    payload.scope.action,
+                    payload.scope.resource,
+                    payload.effect.as_str(),
+                    payload.note,
+                    payload.expires_at.map(rfc3339).transpose()?,
+                ])
                 .context("failed to insert constraint payload")?;
             }
             MemoryPayload::Decision(payload) => {
-                tx.execute(
+                conn.prepare_cached(
                     "INSERT INTO decision_payloads(memory_version_id, summary) VALUES (?1, ?2)",
-                    params![record.memory_version_id.to_string(), payload.summary],
-                )
+                )?
+                .execute(params![record.memory_version_id.to_string(), payload.summary])
                 .context("failed to insert decision payload")?;
             }
             MemoryPayload::Preference(payload) => {
-                tx.execute(
+                conn.prepare_cached(
                     "INSERT INTO preference_payloads(memory_version_id, summary) VALUES (?1, ?2)",
-                    params![record.memory_version_id.to_string(), payload.summary],
-                )
+                )?
+                .execute(params![record.memory_version_id.to_string(), payload.summary])
                 .context("failed to insert preference payload")?;
             }
             MemoryPayload::Event(payload) => {
-                tx.execute(
+                conn.prepare_cached(
                     "INSERT INTO event_payloads(memory_version_id, summary) VALUES (?1, ?2)",
-                    params![record.memory_version_id.to_string(), payload.summary],
-                )
+                )?
+                .execute(params![record.memory_version_id.to_string(), payload.summary])
                 .context("failed to insert event payload")?;
             }
             MemoryPayload::Outcome(payload) => {
-                tx.execute(
+                conn.prepare_cached(
                     "INSERT INTO outcome_payloads(memory_version_id, summary) VALUES (?1, ?2)",
-                    params![record.memory_version_id.to_string(), payload.summary],
-                )
+                )?
+                .execute(params![record.memory_version_id.to_string(), payload.summary])
                 .context("failed to insert outcome payload")?;
             }
-        }
-
-        Ok(())
-    }
-
+            MemoryPayload::Task(payload) => {
+                conn.prepare_cached(
+                    "INSERT INTO task_payloads(memory_version_id, summary, status)
+                     VALUES (?1, ?2, ?3)",
+                )?
+                .execute(params![
+                    record.memory_version_id.to_string(),
+                    payload.summary,
+                    payload.status.as_str()
+                ])
+                .context("failed to insert task payload")?;
+            }
+            MemoryPayload::Risk(payload) => {
+                conn.prepare_cached(
+                    "INSERT INTO risk_payloads(memory_version_id, summary, severity)
+                     VALUES (?1, ?2, ?3)",
+                )?
+                .execute(params![
+                    record.memory_version_id.to_string(),
+                    payload.summary,
+                    payload.severity.as_str()
+                ])
+                .context("failed to insert risk payload")?;
+            }
+            MemoryPayload::Assumption(payload) => {
+                conn.prepare_cached(
+                    "INSERT INTO assumption_payloads(memory_version_id, summary, status)
+                     VALUES (?1, ?2, ?3)",
+                )?
+                .execute(params![
+                    record.memory_version_id.to_string(),
+                    payload.summary,
+                    payload.status.as_str()
+                ])
+                .context("failed to insert assumption payload")?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn insert_links(
-        tx: &rusqlite::Transaction<'_>,
+        conn: &Connection,
+        namespace: &str,
         record: &MemoryRecord,
         link_type: LinkType,
         targets: &[MemoryVersionId],
     ) -> Result<()> {
         let now = now_rfc3339()?;
         for target in targets {
-            tx.execute(
-                "INSERT INTO memory_links(
-                    from_memory_version_id, to_memory_version_id, link_type, writer, justification, created_at
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                params![
+            // Targets later in the same batch are not written yet; they share its namespace.
+            ensure_same_namespace(conn, namespace, *target)?;
+            conn.prepare_cached(INSERT_LINK_SQL)?
+                .execute(params![
                     record.memory_version_id.to_string(),
                     target.to_string(),
                     link_type.as_str(),
                     record.writer,
                     record.justification,
                     now
-                ],
-            )
-            .context("failed to insert memory link")?;
+                ])
+                .context("failed to insert memory link")?;
         }
 
         Ok(())
     }
 
-    fn load_payload(
-        &self,
-        memory_version_id: MemoryVersionId,
-        record_type: RecordType,
-    ) -> Result<MemoryPayload> {
-        match record_type {
-            RecordType::Constraint => {
-                let mut stmt = self.conn.prepare(
-                    "SELECT actor, action, resource, effect, note
-                     FROM constraint_payloads
-                     WHERE memory_version_id = ?1",
-                )?;
-                let payload = stmt
-                    .query_row(params![memory_version_id.to_string()], |row| {
-                        let effect_raw: String = row.get(3)?;
-                        let effect = ConstraintEffect::parse(&effect_raw).ok_or_else(|| {
-                            rusqlite::Error::FromSqlConversionFailure(
-                                3,
-                                rusqlite::types::Type::Text,
-                                Box::new(KernelError::Validation(format!(
-                                    "invalid constraint effect: {effect_raw}"
-                                ))),
-                            )
-                        })?;
-
-                        Ok(ConstraintPayload {
-                            scope: ConstraintScope {
-                                actor: row.get(0)?,
-                                action: row.get(1)?,
-                                resource: row.get(2)?,
-                            },
-                            effect,
-                            note: row.get(4)?,
-                        })
-                    })
-                    .optional()?
-                    .ok_or_else(|| anyhow!("missing constraint payload for {memory_version_id}"))?;
-
-                Ok(MemoryPayload::Constraint(payload))
-            }
-            RecordType::Decision => {
-                let summary = self.load_summary("decision_payloads", memory_version_id)?;
-                Ok(MemoryPayload::Decision(memory_kernel_core::DecisionPayload { summary }))
-            }
-            RecordType::Preference => {
-                let summary = self.load_summary("preference_payloads", memory_version_id)?;
-                Ok(MemoryPayload::Preference(memory_kernel_core::PreferencePayload { summary }))
-            }
-            RecordType::Event => {
-                let summary = self.load_summary("event_payloads", memory_version_id)?;
-                Ok(MemoryPayload::Event(memory_kernel_core::EventPayload { summary }))
-            }
-            RecordType::Outcome => {
-                let summary = self.load_summary("outcome_payloads", memory_version_id)?;
-                Ok(MemoryPayload::Outcome(memory_kernel_core::OutcomePayload { summary }))
-            }
-        }
-    }
-
-    fn load_summary(&self, table_name: &str, memory_version_id: MemoryVersionId) -> Result<String> {
-        let query = format!("SELECT summary FROM {table_name} WHERE memory_version_id = ?1");
-        let mut stmt = self.conn.prepare(&query)?;
-        let value = stmt
-            .query_row(params![memory_version_id.to_string()], |row| row.get::<_, String>(0))
-            .optional()?
-            .ok_or_else(|| anyhow!("missing payload in {table_name} for {memory_version_id}"))?;
-        Ok(value)
-    }
-
-    fn load_links(
-        &self,
-        memory_version_id: MemoryVersionId,
-        link_type: LinkType,
-    ) -> Result<Vec<MemoryVersionId>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT to_memory_version_id FROM memory_links
-             WHERE from_memory_version_id = ?1 AND link_type = ?2
-             ORDER BY id ASC",
+    fn insert_tags(conn: &Connection, record: &MemoryRecord) -> Result<()> {
+        let mut stmt = conn.prepare_cached(
+            "INSERT INTO memory_tags(memory_version_id, tag, position) VALUES (?1, ?2, ?3)",
         )?;
-
-        let rows =
-            stmt.query_map(params![memory_version_id.to_string(), link_type.as_str()], |row| {
-                let raw: String = row.get(0)?;
-                let parsed = Ulid::from_str(&raw).map_err(|_| {
-                    rusqlite::Error::FromSqlConversionFailure(
-                        0,
-                        rusqlite::types::Type::Text,
-                        Box::new(std::io::Error::new(
-                            std::io::ErrorKind::InvalidData,
-                            format!("invalid ULID in link row: {raw}"),
-                        )),
-                    )
-                })?;
-                Ok(MemoryVersionId(parsed))
-            })?;
-
-        let mut ids = Vec::new();
-        for row in rows {
-            ids.push(row?);
+        for (position, tag) in record.tags.iter().enumerate() {
+            stmt.execute(params![
+                record.memory_version_id.to_string(),
+                tag,
+                i64::try_from(position).unwrap_or(i64::MAX)
+            ])
+            .context("failed to insert memory tag")?;
         }
 
-        Ok(ids)
-    }
-
-    fn list_context_packages(&self) -> Result<Vec<ContextPackage>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT package_json FROM context_packages ORDER BY generated_at DESC, context_package_id ASC",
-        )?;
-        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
-        let mut packages = Vec::new();
-        for row in rows {
-            let raw = row?;
-            let parsed = serde_json::from_str::<ContextPackage>(&raw)
-                .context("failed to deserialize context package row")?;
-            packages.push(parsed);
-        }
-        Ok(packages)
+        Ok(())
     }
 
     fn record_exists(&self, memory_version_id: MemoryVersionId) -> Result<bool> {
@@ -1021,721 +2968,2903 @@ impl SqliteStore {
     }
 }
 
-#[derive(Debug)]
-struct LegacyRecordRow {
-    memory_id: String,
-    version: i64,
-    record_type: String,
-    created_at: String,
-    effective_at: String,
-    truth_status: String,
-    authority: String,
-    confidence: Option<f64>,
-    writer: String,
-    justification: String,
-    source_uri: String,
-    source_hash: Option<String>,
-    evidence_json: String,
-}
+impl MemoryStore for SqliteStore {
+    fn namespace(&self) -> &str {
+        SqliteStore::namespace(self)
+    }
 
-fn apply_migration_1(conn: &Connection) -> Result<()> {
-    conn.execute_batch(MIGRATION_001_SQL).context("failed to apply migration v1")?;
-    record_schema_version(conn, 1)?;
-    Ok(())
-}
+    fn set_namespace(&mut self, namespace: &str) -> Result<()> {
+        SqliteStore::set_namespace(self, namespace)
+    }
 
-fn copy_constraint_payloads_to_v2(
-    tx: &rusqlite::Transaction<'_>,
-    id_map: &BTreeMap<String, String>,
-) -> Result<()> {
-    let mut stmt = tx.prepare(
-        "SELECT memory_id, actor, action, resource, effect, note
-         FROM constraint_payloads",
-    )?;
-    let rows = stmt.query_map([], |row| {
-        Ok((
-            row.get::<_, String>(0)?,
-            row.get::<_, String>(1)?,
-            row.get::<_, String>(2)?,
-            row.get::<_, String>(3)?,
-            row.get::<_, String>(4)?,
-            row.get::<_, Option<String>>(5)?,
-        ))
-    })?;
+    fn schema_status(&self) -> Result<SchemaStatus> {
+        SqliteStore::schema_status(self)
+    }
 
-    for row in rows {
-        let (memory_id, actor, action, resource, effect, note) = row?;
-        let memory_version_id = mapped_version_id(id_map, &memory_id)?;
-        tx.execute(
-            "INSERT INTO constraint_payloads_v2(memory_version_id, actor, action, resource, effect, note)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![memory_version_id, actor, action, resource, effect, note],
-        )
-        .context("failed to copy constraint payload into v2")?;
+    fn migrate(&mut self) -> Result<()> {
+        SqliteStore::migrate(self)
     }
 
-    Ok(())
-}
+    fn maintenance(&self, vacuum_into: Option<&Path>) -> Result<MaintenanceReport> {
+        SqliteStore::maintenance(self, vacuum_into)
+    }
 
-fn copy_summary_payloads_to_v2(
-    tx: &rusqlite::Transaction<'_>,
-    source_table: &str,
-    target_table: &str,
-    id_map: &BTreeMap<String, String>,
-) -> Result<()> {
-    let query = format!("SELECT memory_id, summary FROM {source_table}");
-    let mut stmt = tx.prepare(&query)?;
-    let rows =
-        stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    fn write_record(&mut self, record: &MemoryRecord) -> Result<()> {
+        SqliteStore::write_record(self, record)
+    }
 
-    for row in rows {
-        let (memory_id, summary) = row?;
-        let memory_version_id = mapped_version_id(id_map, &memory_id)?;
-        let insert =
-            format!("INSERT INTO {target_table}(memory_version_id, summary) VALUES (?1, ?2)");
-        tx.execute(&insert, params![memory_version_id, summary])
-            .with_context(|| format!("failed to copy payload row into {target_table}"))?;
+    fn write_records_batch(&mut self, records: &[MemoryRecord]) -> Result<()> {
+        SqliteStore::write_records_batch(self, records)
     }
 
-    Ok(())
-}
+    fn write_successor(
+        &mut self,
+        previous: MemoryVersionId,
+        build: SuccessorBuilder<'_>,
+    ) -> Result<MemoryRecord> {
+        SqliteStore::write_successor(self, previous, build)
+    }
 
-fn copy_links_to_v2(
-    tx: &rusqlite::Transaction<'_>,
-    id_map: &BTreeMap<String, String>,
-) -> Result<()> {
-    let mut stmt = tx.prepare(
-        "SELECT from_memory_id, to_memory_id, link_type, writer, justification, created_at
-         FROM memory_links
-         ORDER BY id ASC",
-    )?;
-    let rows = stmt.query_map([], |row| {
-        Ok((
-            row.get::<_, String>(0)?,
-            row.get::<_, String>(1)?,
-            row.get::<_, String>(2)?,
-            row.get::<_, String>(3)?,
-            row.get::<_, String>(4)?,
-            row.get::<_, String>(5)?,
-        ))
-    })?;
+    fn add_link(
+        &mut self,
+        from: MemoryVersionId,
+        to: MemoryVersionId,
+        link_type: LinkType,
+        writer: &str,
+        justification: &str,
+    ) -> Result<()> {
+        SqliteStore::add_link(self, from, to, link_type, writer, justification)
+    }
 
-    for row in rows {
-        let (from_memory_id, to_memory_id, link_type, writer, justification, created_at) = row?;
-        let from_memory_version_id = mapped_version_id(id_map, &from_memory_id)?;
-        let to_memory_version_id = mapped_version_id(id_map, &to_memory_id)?;
+    fn redact(
+        &mut self,
+        memory_version_id: MemoryVersionId,
+        writer: &str,
+        justification: &str,
+    ) -> Result<Redaction> {
+        SqliteStore::redact(self, memory_version_id, writer, justification)
+    }
 
-        tx.execute(
-            "INSERT INTO memory_links_v2(
-                from_memory_version_id, to_memory_version_id, link_type, writer, justification, created_at
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![
-                from_memory_version_id,
-                to_memory_version_id,
-                link_type,
-                writer,
-                justification,
-                created_at,
-            ],
-        )
-        .context("failed to copy memory link into v2")?;
+    fn iter_records(&self) -> Result<Box<dyn Iterator<Item = Result<MemoryRecord>> + '_>> {
+        Ok(Box::new(SqliteStore::iter_records(self)?))
     }
 
-    Ok(())
-}
+    fn list_records_filtered(&self, filter: &RecordFilter) -> Result<RecordPage> {
+        SqliteStore::list_records_filtered(self, filter)
+    }
 
-fn mapped_version_id(id_map: &BTreeMap<String, String>, memory_id: &str) -> Result<String> {
-    id_map.get(memory_id).cloned().ok_or_else(|| {
-        anyhow!("migration mapping missing memory_version_id for legacy memory_id {memory_id}")
-    })
-}
+    fn list_record_keys(&self) -> Result<Vec<(MemoryId, MemoryVersionId)>> {
+        SqliteStore::list_record_keys(self)
+    }
 
-fn table_exists(conn: &Connection, table_name: &str) -> Result<bool> {
-    let exists = conn
-        .query_row(
-            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
-            params![table_name],
-            |row| row.get::<_, i64>(0),
-        )
-        .with_context(|| format!("failed to check if table exists: {table_name}"))?;
-    Ok(exists == 1)
-}
+    fn list_contradictions(&self) -> Result<ContradictionReport> {
+        SqliteStore::list_contradictions(self)
+    }
 
-fn table_has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
-    if !table_exists(conn, table)? {
-        return Ok(false);
+    fn recall_candidates_tagged(
+        &self,
+        query: &str,
+        record_types: &[RecordType],
+        tags: &[String],
+        limit: usize,
+    ) -> Result<Vec<MemoryRecord>> {
+        SqliteStore::recall_candidates_tagged(self, query, record_types, tags, limit)
     }
 
-    let mut stmt = conn
-        .prepare(&format!("PRAGMA table_info({table})"))
-        .with_context(|| format!("failed to inspect table_info for {table}"))?;
-    let mut rows = stmt.query([])?;
+    fn put_record_embeddings(
+        &mut self,
+        model: &str,
+        embeddings: &[(MemoryVersionId, Vec<f32>)],
+    ) -> Result<()> {
+        SqliteStore::put_record_embeddings(self, model, embeddings)
+    }
 
-    while let Some(row) = rows.next()? {
-        let name: String = row.get(1)?;
-        if name == column {
-            return Ok(true);
-        }
+    fn record_embeddings(&self, model: &str) -> Result<BTreeMap<MemoryVersionId, Vec<f32>>> {
+        SqliteStore::record_embeddings(self, model)
     }
 
-    Ok(false)
-}
+    fn save_context_package(&mut self, package: &ContextPackage) -> Result<()> {
+        SqliteStore::save_context_package(self, package)
+    }
 
-fn current_schema_version(conn: &Connection) -> Result<i64> {
-    let version = conn
-        .query_row("SELECT COALESCE(MAX(version), 0) FROM schema_migrations", [], |row| {
-            row.get::<_, i64>(0)
-        })
-        .context("failed to read current schema version")?;
-    Ok(version)
-}
+    fn get_context_package(&self, context_package_id: &str) -> Result<Option<ContextPackage>> {
+        SqliteStore::get_context_package(self, context_package_id)
+    }
 
-fn detect_effective_schema_version(conn: &Connection) -> Result<(i64, bool)> {
-    let recorded = current_schema_version(conn)?;
-    if recorded > 0 {
-        return Ok((recorded, false));
+    fn list_context_packages(&self, filter: &ContextPackageFilter) -> Result<ContextPackagePage> {
+        SqliteStore::list_context_packages(self, filter)
     }
 
-    if !table_exists(conn, "memory_records")? {
-        return Ok((0, false));
+    fn record_query_metric(&mut self, metric: &QueryMetric) -> Result<()> {
+        SqliteStore::record_query_metric(self, metric)
     }
 
-    if table_has_column(conn, "memory_records", "memory_version_id")? {
-        return Ok((2, true));
+    fn list_query_metrics(&self, since: Option<OffsetDateTime>) -> Result<Vec<QueryMetric>> {
+        SqliteStore::list_query_metrics(self, since)
     }
 
-    if table_has_column(conn, "memory_records", "memory_id")? {
-        return Ok((1, true));
+    fn link_package_outcome(
+        &mut self,
+        context_package_id: &str,
+        outcome: MemoryVersionId,
+        writer: &str,
+        justification: &str,
+    ) -> Result<PackageOutcome> {
+        SqliteStore::link_package_outcome(self, context_package_id, outcome, writer, justification)
     }
 
-    Err(anyhow!(
-        "database schema is invalid: memory_records has neither memory_id nor memory_version_id"
-    ))
-}
+    fn outcomes_following(
+        &self,
+        memory_version_id: MemoryVersionId,
+    ) -> Result<Vec<FollowingOutcome>> {
+        SqliteStore::outcomes_following(self, memory_version_id)
+    }
 
-fn record_schema_version(conn: &Connection, version: i64) -> Result<()> {
-    let now = now_rfc3339()?;
-    conn.execute(
-        "INSERT OR IGNORE INTO schema_migrations(version, applied_at) VALUES (?1, ?2)",
-        params![version, now],
-    )
-    .with_context(|| format!("failed to record migration version {version}"))?;
-    Ok(())
+    fn export_snapshot(&self, out_dir: &Path) -> Result<ExportManifest> {
+        SqliteStore::export_snapshot(self, out_dir)
+    }
+
+    fn import_snapshot(&mut self, in_dir: &Path, skip_existing: bool) -> Result<ImportSummary> {
+        SqliteStore::import_snapshot(self, in_dir, skip_existing)
+    }
 }
 
-fn now_rfc3339() -> Result<String> {
-    rfc3339(OffsetDateTime::now_utc())
+/// Fallible streaming iterator over persisted memory records.
+///
+/// Created by [`SqliteStore::iter_records`]; yields records in `list_records` order and stops
+/// after the first error.
+pub struct RecordIter<'a> {
+    conn: &'a Connection,
+    namespace: &'a str,
+    batch_size: usize,
+    buffer: VecDeque<MemoryRecord>,
+    cursor: Option<RecordCursor>,
+    finished: bool,
+    owns_snapshot: bool,
 }
 
-fn rfc3339(value: OffsetDateTime) -> Result<String> {
-    value
-        .format(&time::format_description::well_known::Rfc3339)
-        .context("failed to format RFC3339 timestamp")
+/// Ordering key of the last record read, used to resume the next batch.
+#[derive(Debug, Clone)]
+struct RecordCursor {
+    created_at: String,
+    memory_id: String,
+    memory_version_id: String,
 }
 
-fn parse_rfc3339(value: &str) -> Result<OffsetDateTime> {
-    OffsetDateTime::parse(value, &time::format_description::well_known::Rfc3339)
-        .with_context(|| format!("invalid RFC3339 timestamp: {value}"))
+#[derive(Debug)]
+struct RecordRow {
+    key: RecordCursor,
+    record_type: RecordType,
+    record: MemoryRecord,
 }
 
-fn parse_memory_id(raw: &str) -> Result<MemoryId> {
-    let parsed = Ulid::from_string(raw).with_context(|| format!("invalid ULID: {raw}"))?;
-    Ok(MemoryId(parsed))
+impl RecordIter<'_> {
+    fn fetch_batch(&mut self) -> Result<()> {
+        let mut query = RecordQuery::in_namespace(self.namespace);
+        if let Some(cursor) = &self.cursor {
+            query.resume_after(cursor);
+        }
+        let rows = query.fetch(self.conn, Some(self.batch_size))?;
+        let Some(last) = rows.last() else {
+            self.finish();
+            return Ok(());
+        };
+        self.cursor = Some(last.key.clone());
+
+        let exhausted = rows.len() < self.batch_size;
+        let records = hydrate_records(self.conn, rows)?;
+        if exhausted {
+            self.finish();
+        }
+        self.buffer.extend(records);
+        Ok(())
+    }
+
+    fn finish(&mut self) {
+        self.finished = true;
+        if self.owns_snapshot {
+            self.owns_snapshot = false;
+            let _ = self.conn.execute_batch("COMMIT");
+        }
+    }
 }
 
-fn parse_memory_version_id(raw: &str) -> Result<MemoryVersionId> {
-    let parsed = Ulid::from_string(raw).with_context(|| format!("invalid ULID: {raw}"))?;
-    Ok(MemoryVersionId(parsed))
+impl Iterator for RecordIter<'_> {
+    type Item = Result<MemoryRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() && !self.finished {
+            if let Err(err) = self.fetch_batch() {
+                self.buffer.clear();
+                self.finish();
+                return Some(Err(err));
+            }
+        }
+        self.buffer.pop_front().map(Ok)
+    }
 }
 
-fn write_ndjson_file<T: Serialize>(path: &Path, values: &[T]) -> Result<(String, usize)> {
-    let file = File::create(path)
-        .with_context(|| format!("failed to create export file {}", path.display()))?;
-    let mut writer = BufWriter::new(file);
-    let mut hasher = Sha256::new();
+impl Drop for RecordIter<'_> {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
 
-    for value in values {
-        let line = serde_json::to_string(value).context("failed to serialize NDJSON row")?;
-        writer
-            .write_all(line.as_bytes())
-            .with_context(|| format!("failed to write export file {}", path.display()))?;
-        writer
-            .write_all(b"\n")
-            .with_context(|| format!("failed to write export file {}", path.display()))?;
-        hasher.update(line.as_bytes());
-        hasher.update(b"\n");
+/// Open a record iterator over `namespace` on `conn`, taking a read snapshot when no
+/// transaction is open.
+fn record_iter<'a>(
+    conn: &'a Connection,
+    namespace: &'a str,
+    batch_size: usize,
+) -> Result<RecordIter<'a>> {
+    if batch_size == 0 {
+        return Err(anyhow!("record batch size MUST be >= 1"));
     }
 
-    writer.flush().with_context(|| format!("failed to flush export file {}", path.display()))?;
+    let owns_snapshot = conn.is_autocommit();
+    if owns_snapshot {
+        conn.execute_batch("BEGIN DEFERRED").context("failed to start read snapshot")?;
+    }
 
-    Ok((format!("{:x}", hasher.finalize()), values.len()))
+    Ok(RecordIter {
+        conn,
+        namespace,
+        batch_size,
+        buffer: VecDeque::new(),
+        cursor: None,
+        finished: false,
+        owns_snapshot,
+    })
 }
 
-fn read_ndjson_file<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>> {
-    let file = File::open(path)
-        .with_context(|| format!("failed to open NDJSON file {}", path.display()))?;
-    let reader = BufReader::new(file);
-    let mut values = Vec::new();
+/// Transaction for one write call: its own transaction with `behavior`, or a savepoint inside
+/// a batch opened by [`SqliteStore::begin_batch`], which already holds the write lock.
+enum WriteTx<'conn> {
+    Own(rusqlite::Transaction<'conn>),
+    Batch(rusqlite::Savepoint<'conn>),
+}
 
-    for (index, line) in reader.lines().enumerate() {
-        let line = line.with_context(|| {
-            format!("failed to read line {} from {}", index + 1, path.display())
-        })?;
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
+impl WriteTx<'_> {
+    fn commit(self) -> rusqlite::Result<()> {
+        match self {
+            Self::Own(tx) => tx.commit(),
+            Self::Batch(savepoint) => savepoint.commit(),
         }
-        let value = serde_json::from_str(trimmed).with_context(|| {
-            format!("failed to parse NDJSON row {} from {}", index + 1, path.display())
-        })?;
-        values.push(value);
     }
+}
 
-    Ok(values)
+impl std::ops::Deref for WriteTx<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        match self {
+            Self::Own(tx) => tx,
+            Self::Batch(savepoint) => savepoint,
+        }
+    }
 }
 
-fn read_export_manifest(path: &Path) -> Result<ExportManifest> {
-    let bytes = fs::read(path)
-        .with_context(|| format!("failed to read manifest file {}", path.display()))?;
-    serde_json::from_slice(&bytes)
-        .with_context(|| format!("failed to parse manifest JSON {}", path.display()))
+fn query_orphaned_rows(conn: &Connection) -> Result<Vec<OrphanedRow>> {
+    let mut orphans = Vec::new();
+    for table in VERSION_SCOPED_TABLES {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT DISTINCT t.memory_version_id FROM {table} t
+             WHERE NOT EXISTS (
+               SELECT 1 FROM memory_records r WHERE r.memory_version_id = t.memory_version_id
+             )
+             ORDER BY t.memory_version_id ASC"
+        ))?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        for memory_version_id in rows {
+            orphans.push(OrphanedRow {
+                table: (*table).to_string(),
+                memory_version_id: memory_version_id?,
+            });
+        }
+    }
+    Ok(orphans)
 }
 
-fn ndjson_digest_and_records(path: &Path) -> Result<(String, usize)> {
-    let file = File::open(path)
-        .with_context(|| format!("failed to open NDJSON file {}", path.display()))?;
-    let reader = BufReader::new(file);
-    let mut hasher = Sha256::new();
-    let mut records = 0_usize;
+fn query_dangling_links(conn: &Connection) -> Result<Vec<DanglingLink>> {
+    let mut stmt = conn.prepare(
+        "SELECT l.id, l.from_memory_version_id, l.to_memory_version_id, l.link_type
+         FROM memory_links l
+         WHERE NOT EXISTS (
+                 SELECT 1 FROM memory_records r
+                 WHERE r.memory_version_id = l.from_memory_version_id)
+            OR NOT EXISTS (
+                 SELECT 1 FROM memory_records r
+                 WHERE r.memory_version_id = l.to_memory_version_id)
+         ORDER BY l.id ASC",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(DanglingLink {
+            link_id: row.get(0)?,
+            from_memory_version_id: row.get(1)?,
+            to_memory_version_id: row.get(2)?,
+            link_type: row.get(3)?,
+        })
+    })?;
+    rows.collect::<rusqlite::Result<Vec<_>>>().context("failed to read dangling links")
+}
 
-    for (index, line) in reader.lines().enumerate() {
-        let line = line.with_context(|| {
-            format!("failed to read line {} from {}", index + 1, path.display())
-        })?;
-        hasher.update(line.as_bytes());
-        hasher.update(b"\n");
-        if !line.trim().is_empty() {
-            records += 1;
+fn query_stale_context_packages(conn: &Connection) -> Result<Vec<StaleContextPackage>> {
+    let mut stmt = conn.prepare(
+        "SELECT p.context_package_id, json_extract(item.value, '$.memory_version_id')
+         FROM context_packages p
+         JOIN json_each(p.package_json, '$.selected_items') item
+         WHERE NOT EXISTS (
+           SELECT 1 FROM memory_records r
+           WHERE r.memory_version_id = json_extract(item.value, '$.memory_version_id'))
+         UNION
+         SELECT p.context_package_id, json_extract(item.value, '$.memory_version_id')
+         FROM context_packages p
+         JOIN json_each(p.package_json, '$.excluded_items') item
+         WHERE NOT EXISTS (
+           SELECT 1 FROM memory_records r
+           WHERE r.memory_version_id = json_extract(item.value, '$.memory_version_id'))
+         ORDER BY 1 ASC, 2 ASC",
+    )?;
+    let rows =
+        stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    let mut packages: Vec<StaleContextPackage> = Vec::new();
+    for row in rows {
+        let (context_package_id, memory_version_id) = row?;
+        match packages.last_mut() {
+            Some(package) if package.context_package_id == context_package_id => {
+                package.missing_memory_version_ids.push(memory_version_id);
+            }
+            _ => packages.push(StaleContextPackage {
+                context_package_id,
+                missing_memory_version_ids: vec![memory_version_id],
+            }),
         }
     }
+    Ok(packages)
+}
 
-    Ok((format!("{:x}", hasher.finalize()), records))
+fn write_tx(conn: &mut Connection, behavior: TransactionBehavior) -> Result<WriteTx<'_>> {
+    if conn.is_autocommit() {
+        let tx = conn.transaction_with_behavior(behavior).context("failed to start transaction")?;
+        Ok(WriteTx::Own(tx))
+    } else {
+        Ok(WriteTx::Batch(conn.savepoint().context("failed to start batch savepoint")?))
+    }
 }
 
-fn validate_import_manifest(in_dir: &Path, manifest: &ExportManifest) -> Result<()> {
-    if manifest.schema_version <= 0 || manifest.schema_version > LATEST_SCHEMA_VERSION {
-        return Err(anyhow!(
-            "unsupported export schema version {}; supported range is 1..={}",
-            manifest.schema_version,
-            LATEST_SCHEMA_VERSION
+/// Reject references to an existing record version stored under another namespace.
+fn ensure_same_namespace(
+    conn: &Connection,
+    namespace: &str,
+    memory_version_id: MemoryVersionId,
+) -> Result<()> {
+    let stored: Option<String> = conn
+        .prepare_cached("SELECT namespace FROM memory_records WHERE memory_version_id = ?1")?
+        .query_row(params![memory_version_id.to_string()], |row| row.get(0))
+        .optional()?;
+    match stored {
+        Some(stored) if stored != namespace => Err(anyhow!(
+            "memory_version_id {memory_version_id} belongs to namespace {stored}, not {namespace}"
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn record_in_namespace(
+    conn: &Connection,
+    namespace: &str,
+    memory_version_id: MemoryVersionId,
+) -> Result<bool> {
+    let exists = conn.query_row(
+        "SELECT EXISTS(
+            SELECT 1 FROM memory_records WHERE memory_version_id = ?1 AND namespace = ?2
+         )",
+        params![memory_version_id.to_string(), namespace],
+        |row| row.get::<_, i64>(0),
+    )?;
+    Ok(exists == 1)
+}
+
+fn redaction_exists(conn: &Connection, memory_version_id: MemoryVersionId) -> Result<bool> {
+    let exists = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM redactions WHERE memory_version_id = ?1)",
+        params![memory_version_id.to_string()],
+        |row| row.get::<_, i64>(0),
+    )?;
+    Ok(exists == 1)
+}
+
+/// Scrub the payload of `redaction.memory_version_id` and record the redaction.
+fn apply_redaction(conn: &Connection, namespace: &str, redaction: &Redaction) -> Result<()> {
+    let id = redaction.memory_version_id.to_string();
+    let record_type: Option<String> = conn
+        .query_row(
+            "SELECT record_type FROM memory_records
+             WHERE memory_version_id = ?1 AND namespace = ?2",
+            params![id, namespace],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let Some(record_type_raw) = record_type else {
+        return Err(anyhow!("memory_version_id not found: {id}"));
+    };
+    let record_type = RecordType::parse(&record_type_raw)
+        .ok_or_else(|| anyhow!("unknown record_type: {record_type_raw}"))?;
+
+    if record_type == RecordType::Constraint {
+        conn.execute(
+            "UPDATE constraint_payloads
+             SET actor = ?2, action = ?2, resource = ?2,
+                 note = CASE WHEN note IS NULL THEN NULL ELSE ?2 END
+             WHERE memory_version_id = ?1",
+            params![id, REDACTION_MARKER],
+        )
+    } else {
+        conn.execute(
+            &format!(
+                "UPDATE {} SET summary = ?2 WHERE memory_version_id = ?1",
+                payload_table(record_type)
+            ),
+            params![id, REDACTION_MARKER],
+        )
+    }
+    .context("failed to redact payload")?;
+
+    conn.execute("DELETE FROM memory_recall_fts WHERE memory_version_id = ?1", params![id])
+        .context("failed to remove recall terms")?;
+    insert_recall_terms_row(conn, &id, &tokenize_query_terms(REDACTION_MARKER).join(" "))?;
+    conn.execute("DELETE FROM record_embeddings WHERE memory_version_id = ?1", params![id])
+        .context("failed to remove record embeddings")?;
+
+    conn.execute(
+        "INSERT INTO redactions(memory_version_id, writer, justification, redacted_at, write_seq)
+         VALUES (?1, ?2, ?3, ?4, (SELECT COALESCE(MAX(write_seq), 0) + 1 FROM redactions))",
+        params![id, redaction.writer, redaction.justification, rfc3339(redaction.redacted_at)?],
+    )
+    .context("failed to insert redaction")?;
+    Ok(())
+}
+
+fn insert_recall_terms(conn: &Connection, record: &MemoryRecord) -> Result<()> {
+    let terms = record_terms(record).into_iter().collect::<Vec<_>>().join(" ");
+    insert_recall_terms_row(conn, &record.memory_version_id.to_string(), &terms)
+}
+
+fn insert_recall_terms_row(conn: &Connection, memory_version_id: &str, terms: &str) -> Result<()> {
+    conn.prepare_cached("INSERT INTO memory_recall_fts(memory_version_id, terms) VALUES (?1, ?2)")?
+        .execute(params![memory_version_id, terms])
+        .context("failed to index recall terms")?;
+    Ok(())
+}
+
+/// `memory_records` query in list order with positional parameters bound as they are added.
+#[derive(Debug, Default)]
+struct RecordQuery {
+    conditions: Vec<String>,
+    values: Vec<rusqlite::types::Value>,
+}
+
+impl RecordQuery {
+    fn in_namespace(namespace: &str) -> Self {
+        let mut query = Self::default();
+        let param = query.bind(namespace.to_string());
+        query.conditions.push(format!("namespace = {param}"));
+        query
+    }
+
+    fn bind(&mut self, value: impl Into<rusqlite::types::Value>) -> String {
+        self.values.push(value.into());
+        format!("?{}", self.values.len())
+    }
+
+    /// Restrict to records carrying every tag in `tags`.
+    fn require_tags(&mut self, tags: &[String]) {
+        let tags = tags.iter().collect::<BTreeSet<_>>();
+        let count = self.bind(i64::try_from(tags.len()).unwrap_or(i64::MAX));
+        let placeholders =
+            tags.into_iter().map(|tag| self.bind(tag.clone())).collect::<Vec<_>>().join(", ");
+        self.conditions.push(format!(
+            "memory_version_id IN (SELECT memory_version_id FROM memory_tags \
+             WHERE tag IN ({placeholders}) GROUP BY memory_version_id HAVING COUNT(*) = {count})"
         ));
     }
 
-    let mut by_path: BTreeMap<&str, &ExportFileDigest> = BTreeMap::new();
-    for file in &manifest.files {
-        if by_path.insert(file.path.as_str(), file).is_some() {
-            return Err(anyhow!("manifest contains duplicate file entry: {}", file.path));
-        }
+    /// Restrict to records ordered after `cursor`; the leading `created_at <=` bound lets
+    /// `SQLite` seek into the ordering index instead of rescanning it.
+    fn resume_after(&mut self, cursor: &RecordCursor) {
+        let created_at = self.bind(cursor.created_at.clone());
+        let memory_id = self.bind(cursor.memory_id.clone());
+        let memory_version_id = self.bind(cursor.memory_version_id.clone());
+        self.conditions.push(format!(
+            "created_at <= {created_at} AND (created_at < {created_at} OR memory_id > {memory_id} \
+             OR (memory_id = {memory_id} AND memory_version_id > {memory_version_id}))"
+        ));
     }
 
-    for required in ["memory_records.ndjson", "context_packages.ndjson"] {
-        let Some(expected) = by_path.get(required) else {
-            return Err(anyhow!("manifest is missing required file entry: {required}"));
-        };
-        let file_path = in_dir.join(required);
-        if !file_path.exists() {
-            return Err(anyhow!("manifest references missing file {}", file_path.display()));
+    fn fetch(mut self, conn: &Connection, limit: Option<usize>) -> Result<Vec<RecordRow>> {
+        let mut sql = RECORD_SELECT_SQL.to_string();
+        if !self.conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&self.conditions.join(" AND "));
+        }
+        sql.push(' ');
+        sql.push_str(RECORD_ORDER_SQL);
+        if let Some(limit) = limit {
+            let param = self.bind(i64::try_from(limit).unwrap_or(i64::MAX));
+            sql.push_str(" LIMIT ");
+            sql.push_str(&param);
         }
 
-        let (actual_sha256, actual_records) = ndjson_digest_and_records(&file_path)?;
-        if actual_sha256 != expected.sha256 {
-            return Err(anyhow!(
-                "manifest digest mismatch for {required}: expected {}, got {}",
-                expected.sha256,
-                actual_sha256
-            ));
+        let mut stmt = conn.prepare_cached(&sql)?;
+        let mut rows = stmt.query(params_from_iter(self.values))?;
+        let mut decoded = Vec::new();
+        while let Some(row) = rows.next()? {
+            decoded.push(decode_record_row(row)?);
         }
-        if actual_records != expected.records {
-            return Err(anyhow!(
-                "manifest record count mismatch for {required}: expected {}, got {}",
-                expected.records,
-                actual_records
-            ));
+        Ok(decoded)
+    }
+}
+
+/// Attach payloads, lineage links, and tags to decoded rows with one query per table.
+fn hydrate_records(conn: &Connection, mut rows: Vec<RecordRow>) -> Result<Vec<MemoryRecord>> {
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut payloads = load_payloads(conn, &rows)?;
+    let mut links = load_links(conn, &rows)?;
+    let mut tags = load_tags(conn, &rows)?;
+
+    for row in &mut rows {
+        let version_id = &row.key.memory_version_id;
+        row.record.payload = payloads.remove(version_id).ok_or_else(|| {
+            anyhow!("missing payload in {} for {version_id}", payload_table(row.record_type))
+        })?;
+        if let Some((supersedes, contradicts)) = links.remove(version_id) {
+            row.record.supersedes = supersedes;
+            row.record.contradicts = contradicts;
+        }
+        if let Some(record_tags) = tags.remove(version_id) {
+            row.record.tags = record_tags;
         }
     }
 
-    Ok(())
+    Ok(rows.into_iter().map(|row| row.record).collect())
 }
 
-#[cfg(test)]
-mod tests {
-    use std::collections::BTreeSet;
-    use std::fs;
-    use std::thread;
+fn decode_contradiction_endpoint(
+    row: &rusqlite::Row<'_>,
+    offset: usize,
+) -> Result<ContradictionEndpoint> {
+    let record_type_raw: String = row.get(offset + 3)?;
+    let truth_status_raw: String = row.get(offset + 4)?;
+    Ok(ContradictionEndpoint {
+        memory_id: parse_memory_id(&row.get::<_, String>(offset)?)?,
+        memory_version_id: parse_memory_version_id(&row.get::<_, String>(offset + 1)?)?,
+        version: row.get(offset + 2)?,
+        record_type: RecordType::parse(&record_type_raw)
+            .ok_or_else(|| anyhow!("unknown record_type: {record_type_raw}"))?,
+        truth_status: TruthStatus::parse(&truth_status_raw)
+            .ok_or_else(|| anyhow!("unknown truth_status: {truth_status_raw}"))?,
+    })
+}
 
-    use super::*;
-    use memory_kernel_core::{
-        build_context_package, ConstraintEffect, ConstraintPayload, ConstraintScope, MemoryPayload,
-        Provenance, QueryRequest,
+fn decode_record_row(row: &rusqlite::Row<'_>) -> Result<RecordRow> {
+    let memory_version_id_raw: String = row.get(0)?;
+    let memory_id_raw: String = row.get(1)?;
+    let record_type_raw: String = row.get(3)?;
+    let created_at_raw: String = row.get(4)?;
+    let record_type = RecordType::parse(&record_type_raw)
+        .ok_or_else(|| anyhow!("unknown record_type: {record_type_raw}"))?;
+
+    let truth_status_raw: String = row.get(6)?;
+    let authority_raw: String = row.get(7)?;
+    let evidence_json: String = row.get(13)?;
+
+    let record = MemoryRecord {
+        memory_version_id: parse_memory_version_id(&memory_version_id_raw)?,
+        memory_id: parse_memory_id(&memory_id_raw)?,
+        version: row.get::<_, u32>(2)?,
+        // Replaced by the batched payload lookup in `hydrate_records`.
+        payload: MemoryPayload::Decision(memory_kernel_core::DecisionPayload {
+            summary: String::new(),
+        }),
+        created_at: parse_rfc3339(&created_at_raw)?,
+        effective_at: parse_rfc3339(&row.get::<_, String>(5)?)?,
+        truth_status: TruthStatus::parse(&truth_status_raw)
+            .ok_or_else(|| anyhow!("unknown truth_status: {truth_status_raw}"))?,
+        authority: Authority::parse(&authority_raw)
+            .ok_or_else(|| anyhow!("unknown authority: {authority_raw}"))?,
+        confidence: row.get(8)?,
+        writer: row.get(9)?,
+        justification: row.get(10)?,
+        provenance: memory_kernel_core::Provenance {
+            source_uri: row.get(11)?,
+            source_hash: row.get(12)?,
+            evidence: serde_json::from_str(&evidence_json)
+                .context("failed to deserialize evidence")?,
+        },
+        supersedes: Vec::new(),
+        contradicts: Vec::new(),
+        tags: Vec::new(),
     };
 
-    fn insert_legacy_constraint_record(
-        conn: &Connection,
-        memory_id: MemoryId,
-        created_at: &str,
-        confidence: f64,
-        justification: &str,
-    ) -> Result<()> {
-        conn.execute(
-            "INSERT INTO memory_records(
-                memory_id, version, record_type, created_at, effective_at,
-                truth_status, authority, confidence, writer, justification,
-                source_uri, source_hash, evidence_json
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
-            params![
-                memory_id.to_string(),
-                1_i64,
-                "constraint",
-                created_at,
-                created_at,
-                "asserted",
-                "authoritative",
-                confidence,
-                "tester",
-                justification,
-                "file:///policy.md",
-                "sha256:abc123",
-                "[]",
-            ],
-        )?;
+    Ok(RecordRow {
+        key: RecordCursor {
+            created_at: created_at_raw,
+            memory_id: memory_id_raw,
+            memory_version_id: memory_version_id_raw,
+        },
+        record_type,
+        record,
+    })
+}
 
-        Ok(())
+fn payload_table(record_type: RecordType) -> &'static str {
+    match record_type {
+        RecordType::Constraint => "constraint_payloads",
+        RecordType::Decision => "decision_payloads",
+        RecordType::Preference => "preference_payloads",
+        RecordType::Event => "event_payloads",
+        RecordType::Outcome => "outcome_payloads",
+        RecordType::Task => "task_payloads",
+        RecordType::Risk => "risk_payloads",
+        RecordType::Assumption => "assumption_payloads",
     }
+}
 
-    fn insert_legacy_constraint_payload(conn: &Connection, memory_id: MemoryId) -> Result<()> {
-        conn.execute(
-            "INSERT INTO constraint_payloads(memory_id, actor, action, resource, effect, note)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![
-                memory_id.to_string(),
-                "user",
-                "use",
-                "usb_drive",
-                "deny",
-                Option::<String>::None,
-            ],
-        )?;
-        Ok(())
+/// Column holding the status/severity of summary payloads that carry one.
+fn payload_state_column(record_type: RecordType) -> Option<&'static str> {
+    match record_type {
+        RecordType::Task | RecordType::Assumption => Some("status"),
+        RecordType::Risk => Some("severity"),
+        RecordType::Constraint
+        | RecordType::Decision
+        | RecordType::Preference
+        | RecordType::Event
+        | RecordType::Outcome => None,
     }
+}
 
-    fn insert_legacy_supersedes_link(
-        conn: &Connection,
-        from_memory_id: MemoryId,
-        to_memory_id: MemoryId,
-        created_at: &str,
-    ) -> Result<()> {
-        conn.execute(
-            "INSERT INTO memory_links(from_memory_id, to_memory_id, link_type, writer, justification, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![
-                from_memory_id.to_string(),
-                to_memory_id.to_string(),
-                "supersedes",
-                "tester",
-                "legacy link",
-                created_at,
-            ],
-        )?;
-        Ok(())
+fn sql_placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+/// Load payloads for one batch of records, keyed by raw `memory_version_id`.
+fn load_payloads(conn: &Connection, rows: &[RecordRow]) -> Result<BTreeMap<String, MemoryPayload>> {
+    let mut ids_by_type: BTreeMap<&'static str, (RecordType, Vec<&str>)> = BTreeMap::new();
+    for row in rows {
+        ids_by_type
+            .entry(payload_table(row.record_type))
+            .or_insert_with(|| (row.record_type, Vec::new()))
+            .1
+            .push(row.key.memory_version_id.as_str());
+    }
+
+    let mut payloads = BTreeMap::new();
+    for (table_name, (record_type, ids)) in ids_by_type {
+        let placeholders = sql_placeholders(ids.len());
+        if record_type == RecordType::Constraint {
+            let query = format!(
+                "SELECT memory_version_id, actor, action, resource, effect, note, expires_at
+                 FROM constraint_payloads
+                 WHERE memory_version_id IN ({placeholders})"
+            );
+            let mut stmt = conn.prepare(&query)?;
+            let mut result = stmt.query(params_from_iter(ids))?;
+            while let Some(row) = result.next()? {
+                let effect_raw: String = row.get(4)?;
+                let effect = ConstraintEffect::parse(&effect_raw).ok_or_else(|| {
+                    KernelError::Validation(format!("invalid constraint effect: {effect_raw}"))
+                })?;
+                let payload = ConstraintPayload {
+                    scope: ConstraintScope {
+                        actor: row.get(1)?,
+                        action: row.get(2)?,
+                        resource: row.get(3)?,
+                    },
+                    effect,
+                    note: row.get(5)?,
+                    expires_at: row
+                        .get::<_, Option<String>>(6)?
+                        .as_deref()
+                        .map(parse_rfc3339)
+                        .transpose()?,
+                };
+                payloads.insert(row.get::<_, String>(0)?, MemoryPayload::Constraint(payload));
+            }
+            continue;
+        }
+
+        let state_column = payload_state_column(record_type).unwrap_or("NULL");
+        let query = format!(
+            "SELECT memory_version_id, summary, {state_column} FROM {table_name}
+             WHERE memory_version_id IN ({placeholders})"
+        );
+        let mut stmt = conn.prepare(&query)?;
+        let mut result = stmt.query(params_from_iter(ids))?;
+        while let Some(row) = result.next()? {
+            let payload = summary_payload(
+                record_type,
+                row.get(1)?,
+                row.get::<_, Option<String>>(2)?.as_deref(),
+            )?;
+            payloads.insert(row.get::<_, String>(0)?, payload);
+        }
+    }
+
+    Ok(payloads)
+}
+
+fn summary_payload(
+    record_type: RecordType,
+    summary: String,
+    state: Option<&str>,
+) -> Result<MemoryPayload> {
+    let state =
+        || state.ok_or_else(|| anyhow!("{} payload is missing its state", record_type.as_str()));
+    Ok(match record_type {
+        RecordType::Decision => {
+            MemoryPayload::Decision(memory_kernel_core::DecisionPayload { summary })
+        }
+        RecordType::Preference => {
+            MemoryPayload::Preference(memory_kernel_core::PreferencePayload { summary })
+        }
+        RecordType::Event => MemoryPayload::Event(memory_kernel_core::EventPayload { summary }),
+        RecordType::Outcome => {
+            MemoryPayload::Outcome(memory_kernel_core::OutcomePayload { summary })
+        }
+        RecordType::Task => {
+            let raw = state()?;
+            let status = TaskStatus::parse(raw)
+                .ok_or_else(|| KernelError::Validation(format!("invalid task status: {raw}")))?;
+            MemoryPayload::Task(memory_kernel_core::TaskPayload { summary, status })
+        }
+        RecordType::Risk => {
+            let raw = state()?;
+            let severity = RiskSeverity::parse(raw)
+                .ok_or_else(|| KernelError::Validation(format!("invalid risk severity: {raw}")))?;
+            MemoryPayload::Risk(memory_kernel_core::RiskPayload { summary, severity })
+        }
+        RecordType::Assumption => {
+            let raw = state()?;
+            let status = AssumptionStatus::parse(raw).ok_or_else(|| {
+                KernelError::Validation(format!("invalid assumption status: {raw}"))
+            })?;
+            MemoryPayload::Assumption(memory_kernel_core::AssumptionPayload { summary, status })
+        }
+        RecordType::Constraint => {
+            return Err(anyhow!("constraint records do not have summary payloads"));
+        }
+    })
+}
+
+type RecordLinks = (Vec<MemoryVersionId>, Vec<MemoryVersionId>);
+
+/// Load `(supersedes, contradicts)` links for one batch of records, keyed by raw source ID.
+fn load_links(conn: &Connection, rows: &[RecordRow]) -> Result<BTreeMap<String, RecordLinks>> {
+    let query = format!(
+        "SELECT from_memory_version_id, to_memory_version_id, link_type FROM memory_links
+         WHERE from_memory_version_id IN ({})
+         ORDER BY id ASC",
+        sql_placeholders(rows.len())
+    );
+    let mut stmt = conn.prepare(&query)?;
+    let mut result =
+        stmt.query(params_from_iter(rows.iter().map(|row| row.key.memory_version_id.as_str())))?;
+
+    let mut links: BTreeMap<String, RecordLinks> = BTreeMap::new();
+    while let Some(row) = result.next()? {
+        let to_raw: String = row.get(1)?;
+        let link_type_raw: String = row.get(2)?;
+        let to = Ulid::from_str(&to_raw)
+            .map(MemoryVersionId)
+            .map_err(|_| anyhow!("invalid ULID in link row: {to_raw}"))?;
+        let entry = links.entry(row.get::<_, String>(0)?).or_default();
+        if link_type_raw == LinkType::Supersedes.as_str() {
+            entry.0.push(to);
+        } else if link_type_raw == LinkType::Contradicts.as_str() {
+            entry.1.push(to);
+        } else {
+            return Err(anyhow!("unknown link_type: {link_type_raw}"));
+        }
+    }
+
+    Ok(links)
+}
+
+/// Load tags in written order for one batch of records, keyed by raw version ID.
+fn load_tags(conn: &Connection, rows: &[RecordRow]) -> Result<BTreeMap<String, Vec<String>>> {
+    let query = format!(
+        "SELECT memory_version_id, tag FROM memory_tags
+         WHERE memory_version_id IN ({})
+         ORDER BY memory_version_id ASC, position ASC",
+        sql_placeholders(rows.len())
+    );
+    let mut stmt = conn.prepare(&query)?;
+    let mut result =
+        stmt.query(params_from_iter(rows.iter().map(|row| row.key.memory_version_id.as_str())))?;
+
+    let mut tags: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    while let Some(row) = result.next()? {
+        tags.entry(row.get::<_, String>(0)?).or_default().push(row.get(1)?);
+    }
+
+    Ok(tags)
+}
+
+#[derive(Debug)]
+struct LegacyRecordRow {
+    memory_id: String,
+    version: i64,
+    record_type: String,
+    created_at: String,
+    effective_at: String,
+    truth_status: String,
+    authority: String,
+    confidence: Option<f64>,
+    writer: String,
+    justification: String,
+    source_uri: String,
+    source_hash: Option<String>,
+    evidence_json: String,
+}
+
+fn apply_migration_1(conn: &Connection) -> Result<()> {
+    conn.execute_batch(MIGRATION_001_SQL).context("failed to apply migration v1")?;
+    record_schema_version(conn, 1)?;
+    Ok(())
+}
+
+fn copy_constraint_payloads_to_v2(
+    tx: &rusqlite::Transaction<'_>,
+    id_map: &BTreeMap<String, String>,
+) -> Result<()> {
+    let mut stmt = tx.prepare(
+        "SELECT memory_id, actor, action, resource, effect, note
+         FROM constraint_payloads",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, Option<String>>(5)?,
+        ))
+    })?;
+
+    for row in rows {
+        let (memory_id, actor, action, resource, effect, note) = row?;
+        let memory_version_id = mapped_version_id(id_map, &memory_id)?;
+        tx.execute(
+            "INSERT INTO constraint_payloads_v2(memory_version_id, actor, action, resource, effect, note)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![memory_version_id, actor, action, resource, effect, note],
+        )
+        .context("failed to copy constraint payload into v2")?;
+    }
+
+    Ok(())
+}
+
+fn copy_summary_payloads_to_v2(
+    tx: &rusqlite::Transaction<'_>,
+    source_table: &str,
+    target_table: &str,
+    id_map: &BTreeMap<String, String>,
+) -> Result<()> {
+    let query = format!("SELECT memory_id, summary FROM {source_table}");
+    let mut stmt = tx.prepare(&query)?;
+    let rows =
+        stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+
+    for row in rows {
+        let (memory_id, summary) = row?;
+        let memory_version_id = mapped_version_id(id_map, &memory_id)?;
+        let insert =
+            format!("INSERT INTO {target_table}(memory_version_id, summary) VALUES (?1, ?2)");
+        tx.execute(&insert, params![memory_version_id, summary])
+            .with_context(|| format!("failed to copy payload row into {target_table}"))?;
+    }
+
+    Ok(())
+}
+
+fn copy_links_to_v2(
+    tx: &rusqlite::Transaction<'_>,
+    id_map: &BTreeMap<String, String>,
+) -> Result<()> {
+    let mut stmt = tx.prepare(
+        "SELECT from_memory_id, to_memory_id, link_type, writer, justification, created_at
+         FROM memory_links
+         ORDER BY id ASC",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, String>(5)?,
+        ))
+    })?;
+
+    for row in rows {
+        let (from_memory_id, to_memory_id, link_type, writer, justification, created_at) = row?;
+        let from_memory_version_id = mapped_version_id(id_map, &from_memory_id)?;
+        let to_memory_version_id = mapped_version_id(id_map, &to_memory_id)?;
+
+        tx.execute(
+            "INSERT INTO memory_links_v2(
+                from_memory_version_id, to_memory_version_id, link_type, writer, justification, created_at
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                from_memory_version_id,
+                to_memory_version_id,
+                link_type,
+                writer,
+                justification,
+                created_at,
+            ],
+        )
+        .context("failed to copy memory link into v2")?;
+    }
+
+    Ok(())
+}
+
+fn mapped_version_id(id_map: &BTreeMap<String, String>, memory_id: &str) -> Result<String> {
+    id_map.get(memory_id).cloned().ok_or_else(|| {
+        anyhow!("migration mapping missing memory_version_id for legacy memory_id {memory_id}")
+    })
+}
+
+fn table_exists(conn: &Connection, table_name: &str) -> Result<bool> {
+    let exists = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+            params![table_name],
+            |row| row.get::<_, i64>(0),
+        )
+        .with_context(|| format!("failed to check if table exists: {table_name}"))?;
+    Ok(exists == 1)
+}
+
+fn table_has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    if !table_exists(conn, table)? {
+        return Ok(false);
+    }
+
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({table})"))
+        .with_context(|| format!("failed to inspect table_info for {table}"))?;
+    let mut rows = stmt.query([])?;
+
+    while let Some(row) = rows.next()? {
+        let name: String = row.get(1)?;
+        if name == column {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+fn current_schema_version(conn: &Connection) -> Result<i64> {
+    let version = conn
+        .query_row("SELECT COALESCE(MAX(version), 0) FROM schema_migrations", [], |row| {
+            row.get::<_, i64>(0)
+        })
+        .context("failed to read current schema version")?;
+    Ok(version)
+}
+
+fn detect_effective_schema_version(conn: &Connection) -> Result<(i64, bool)> {
+    let recorded = current_schema_version(conn)?;
+    if recorded > 0 {
+        return Ok((recorded, false));
+    }
+
+    if !table_exists(conn, "memory_records")? {
+        return Ok((0, false));
+    }
+
+    if table_has_column(conn, "memory_records", "memory_version_id")? {
+        return Ok((2, true));
+    }
+
+    if table_has_column(conn, "memory_records", "memory_id")? {
+        return Ok((1, true));
+    }
+
+    Err(anyhow!(
+        "database schema is invalid: memory_records has neither memory_id nor memory_version_id"
+    ))
+}
+
+fn record_schema_version(conn: &Connection, version: i64) -> Result<()> {
+    let now = now_rfc3339()?;
+    conn.execute(
+        "INSERT OR IGNORE INTO schema_migrations(version, applied_at) VALUES (?1, ?2)",
+        params![version, now],
+    )
+    .with_context(|| format!("failed to record migration version {version}"))?;
+    Ok(())
+}
+
+fn now_rfc3339() -> Result<String> {
+    rfc3339(OffsetDateTime::now_utc())
+}
+
+fn rfc3339(value: OffsetDateTime) -> Result<String> {
+    value
+        .format(&time::format_description::well_known::Rfc3339)
+        .context("failed to format RFC3339 timestamp")
+}
+
+fn parse_rfc3339(value: &str) -> Result<OffsetDateTime> {
+    OffsetDateTime::parse(value, &time::format_description::well_known::Rfc3339)
+        .with_context(|| format!("invalid RFC3339 timestamp: {value}"))
+}
+
+fn parse_memory_id(raw: &str) -> Result<MemoryId> {
+    let parsed = Ulid::from_string(raw).with_context(|| format!("invalid ULID: {raw}"))?;
+    Ok(MemoryId(parsed))
+}
+
+fn parse_memory_version_id(raw: &str) -> Result<MemoryVersionId> {
+    let parsed = Ulid::from_string(raw).with_context(|| format!("invalid ULID: {raw}"))?;
+    Ok(MemoryVersionId(parsed))
+}
+
+#[cfg(feature = "sqlcipher")]
+fn unlock_store(conn: &Connection, key: &StoreKey) -> Result<()> {
+    conn.pragma_update(None, "key", key.sqlcipher_literal())
+        .context("failed to apply store key")?;
+    // SQLCipher only checks the key on first read.
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))
+        .context("store key is wrong or the database is not encrypted")
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+fn unlock_store(_conn: &Connection, _key: &StoreKey) -> Result<()> {
+    Err(sqlcipher_unavailable())
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+fn sqlcipher_unavailable() -> anyhow::Error {
+    anyhow!(
+        "store encryption requires memory-kernel-store-sqlite built with the `sqlcipher` feature"
+    )
+}
+
+fn file_len(path: &Path) -> u64 {
+    fs::metadata(path).map_or(0, |metadata| metadata.len())
+}
+
+fn write_ndjson_file<T: Serialize>(path: &Path, values: &[T]) -> Result<(String, usize)> {
+    let file = File::create(path)
+        .with_context(|| format!("failed to create export file {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    let digest = write_ndjson(&mut writer, values, &path.display())?;
+    writer.flush().with_context(|| format!("failed to flush export file {}", path.display()))?;
+    Ok(digest)
+}
+
+/// Write `values` as NDJSON, returning the SHA-256 digest and row count for the manifest.
+fn write_ndjson<T: Serialize>(
+    writer: &mut impl Write,
+    values: &[T],
+    label: &dyn std::fmt::Display,
+) -> Result<(String, usize)> {
+    let mut hasher = Sha256::new();
+    for value in values {
+        let line = serde_json::to_string(value).context("failed to serialize NDJSON row")?;
+        writer
+            .write_all(line.as_bytes())
+            .with_context(|| format!("failed to write export file {label}"))?;
+        writer.write_all(b"\n").with_context(|| format!("failed to write export file {label}"))?;
+        hasher.update(line.as_bytes());
+        hasher.update(b"\n");
+    }
+
+    Ok((format!("{:x}", hasher.finalize()), values.len()))
+}
+
+/// Serialize `values` as NDJSON into a byte counter, returning the length alongside the digest
+/// and row count that [`write_ndjson`] reports.
+fn ndjson_len<T: Serialize>(
+    values: &[T],
+    label: &dyn std::fmt::Display,
+) -> Result<(u64, (String, usize))> {
+    let mut counter = ByteCounter { inner: std::io::sink(), written: 0 };
+    let digest = write_ndjson(&mut counter, values, label)?;
+    Ok((counter.written, digest))
+}
+
+fn open_ndjson_file(path: &Path) -> Result<BufReader<File>> {
+    let file = File::open(path)
+        .with_context(|| format!("failed to open NDJSON file {}", path.display()))?;
+    Ok(BufReader::new(file))
+}
+
+fn read_ndjson<T: DeserializeOwned>(
+    reader: impl BufRead,
+    label: &dyn std::fmt::Display,
+    limits: &ImportLimits,
+) -> Result<Vec<T>> {
+    let mut values = Vec::new();
+    for_each_ndjson_line(reader, label, limits, |line_no, line| {
+        let trimmed = line.trim();
+        if !trimmed.is_empty() {
+            let value = serde_json::from_str(trimmed)
+                .with_context(|| format!("failed to parse NDJSON row {line_no} from {label}"))?;
+            values.push(value);
+        }
+        Ok(())
+    })?;
+    Ok(values)
+}
+
+/// Parse the rows of `reader` into `values`, returning the digest and row count that
+/// [`ndjson_digest_and_records`] would, from the same single pass.
+fn read_ndjson_into<T: DeserializeOwned>(
+    reader: impl BufRead,
+    label: &dyn std::fmt::Display,
+    limits: &ImportLimits,
+    values: &mut Vec<T>,
+) -> Result<(String, usize)> {
+    let mut hasher = Sha256::new();
+    let mut records = 0_usize;
+    for_each_ndjson_line(reader, label, limits, |line_no, line| {
+        hasher.update(line.as_bytes());
+        hasher.update(b"\n");
+        let trimmed = line.trim();
+        if !trimmed.is_empty() {
+            records += 1;
+            let value = serde_json::from_str(trimmed)
+                .with_context(|| format!("failed to parse NDJSON row {line_no} from {label}"))?;
+            values.push(value);
+        }
+        Ok(())
+    })?;
+    Ok((format!("{:x}", hasher.finalize()), records))
+}
+
+/// Call `visit` with each line of `reader` and its 1-based number, buffering at most
+/// `limits.max_line_bytes` of a line and rejecting overlong lines, rows nested deeper than
+/// `limits.max_depth`, and files with more than `limits.max_rows` non-empty lines before `visit`
+/// sees them. Lines lose their `\n` or `\r\n` terminator.
+fn for_each_ndjson_line(
+    mut reader: impl BufRead,
+    label: &dyn std::fmt::Display,
+    limits: &ImportLimits,
+    mut visit: impl FnMut(usize, &str) -> Result<()>,
+) -> Result<()> {
+    // One byte past the limit tells an overlong line from one that fits exactly.
+    let read_cap = u64::try_from(limits.max_line_bytes).unwrap_or(u64::MAX).saturating_add(1);
+    let mut buffer = Vec::new();
+    let mut rows = 0_usize;
+    for line_no in 1.. {
+        buffer.clear();
+        let read = (&mut reader)
+            .take(read_cap)
+            .read_until(b'\n', &mut buffer)
+            .with_context(|| format!("failed to read line {line_no} from {label}"))?;
+        if read == 0 {
+            break;
+        }
+        if buffer.last() == Some(&b'\n') {
+            buffer.pop();
+            if buffer.last() == Some(&b'\r') {
+                buffer.pop();
+            }
+        }
+        if buffer.len() > limits.max_line_bytes {
+            return Err(anyhow!(
+                "line {line_no} of {label} exceeds the {} byte line limit",
+                limits.max_line_bytes
+            ));
+        }
+        let line = std::str::from_utf8(&buffer)
+            .with_context(|| format!("line {line_no} of {label} is not valid UTF-8"))?;
+        if !line.trim().is_empty() {
+            rows += 1;
+            if rows > limits.max_rows {
+                return Err(anyhow!("{label} exceeds the {} row limit", limits.max_rows));
+            }
+            if json_nesting_exceeds(line.as_bytes(), limits.max_depth) {
+                return Err(anyhow!(
+                    "line {line_no} of {label} nests deeper than {} levels",
+                    limits.max_depth
+                ));
+            }
+        }
+        visit(line_no, line)?;
+    }
+    Ok(())
+}
+
+/// Whether objects and arrays in `json` nest deeper than `max_depth`, counting brackets outside
+/// string literals; malformed JSON is left for the parser to reject.
+fn json_nesting_exceeds(json: &[u8], max_depth: usize) -> bool {
+    let (mut depth, mut in_string, mut escaped) = (0_usize, false, false);
+    for &byte in json {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if byte == b'\\' {
+                escaped = true;
+            } else if byte == b'"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => {
+                depth += 1;
+                if depth > max_depth {
+                    return true;
+                }
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Keep records matching `filter` plus every record reachable from them through lineage links,
+/// preserving export order.
+fn select_export_records(records: Vec<MemoryRecord>, filter: &ExportFilter) -> Vec<MemoryRecord> {
+    let by_id =
+        records.iter().map(|record| (record.memory_version_id, record)).collect::<BTreeMap<_, _>>();
+    let mut pending = records
+        .iter()
+        .filter(|record| filter.matches(record))
+        .map(|record| record.memory_version_id)
+        .collect::<VecDeque<_>>();
+    let mut selected = BTreeSet::new();
+    while let Some(id) = pending.pop_front() {
+        if !selected.insert(id) {
+            continue;
+        }
+        if let Some(record) = by_id.get(&id) {
+            pending.extend(record.supersedes.iter().chain(&record.contradicts).copied());
+        }
+    }
+    records.into_iter().filter(|record| selected.contains(&record.memory_version_id)).collect()
+}
+
+fn read_export_manifest(path: &Path) -> Result<ExportManifest> {
+    let bytes = fs::read(path)
+        .with_context(|| format!("failed to read manifest file {}", path.display()))?;
+    serde_json::from_slice(&bytes)
+        .with_context(|| format!("failed to parse manifest JSON {}", path.display()))
+}
+
+fn ndjson_digest_and_records(
+    reader: impl BufRead,
+    label: &dyn std::fmt::Display,
+    limits: &ImportLimits,
+) -> Result<(String, usize)> {
+    let mut hasher = Sha256::new();
+    let mut records = 0_usize;
+    for_each_ndjson_line(reader, label, limits, |_, line| {
+        hasher.update(line.as_bytes());
+        hasher.update(b"\n");
+        if !line.trim().is_empty() {
+            records += 1;
+        }
+        Ok(())
+    })?;
+    Ok((format!("{:x}", hasher.finalize()), records))
+}
+
+/// Whether the manifest has an entry for the file at `path`.
+fn manifest_lists(manifest: &ExportManifest, path: &str) -> bool {
+    manifest.files.iter().any(|file| file.path == path)
+}
+
+/// Check the manifest's schema version and file entries, comparing each required file against
+/// the digest and row count returned by `digest`.
+fn validate_import_manifest(
+    manifest: &ExportManifest,
+    limits: &ImportLimits,
+    digest: impl Fn(&str) -> Result<(String, usize)>,
+) -> Result<()> {
+    for expected in required_manifest_files(manifest, limits)? {
+        let required = expected.path.as_str();
+        let (actual_sha256, actual_records) = digest(required)?;
+        if actual_sha256 != expected.sha256 {
+            return Err(anyhow!(
+                "manifest digest mismatch for {required}: expected {}, got {}",
+                expected.sha256,
+                actual_sha256
+            ));
+        }
+        if actual_records != expected.records {
+            return Err(anyhow!(
+                "manifest record count mismatch for {required}: expected {}, got {}",
+                expected.records,
+                actual_records
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check the manifest's schema version and file entries against `limits`, returning the entries
+/// of the files an import reads.
+fn required_manifest_files<'m>(
+    manifest: &'m ExportManifest,
+    limits: &ImportLimits,
+) -> Result<Vec<&'m ExportFileDigest>> {
+    if manifest.schema_version <= 0 || manifest.schema_version > LATEST_SCHEMA_VERSION {
+        return Err(anyhow!(
+            "unsupported export schema version {}; supported range is 1..={}",
+            manifest.schema_version,
+            LATEST_SCHEMA_VERSION
+        ));
+    }
+
+    let mut by_path: BTreeMap<&str, &ExportFileDigest> = BTreeMap::new();
+    for file in &manifest.files {
+        if by_path.insert(file.path.as_str(), file).is_some() {
+            return Err(anyhow!("manifest contains duplicate file entry: {}", file.path));
+        }
+    }
+
+    // Manifests written before schema v11 carry no redactions file.
+    let optional = [EXPORT_REDACTIONS_FILE].into_iter().filter(|path| by_path.contains_key(path));
+    let mut required_files = Vec::new();
+    for required in [EXPORT_RECORDS_FILE, EXPORT_CONTEXT_PACKAGES_FILE].into_iter().chain(optional)
+    {
+        let Some(expected) = by_path.get(required) else {
+            return Err(anyhow!("manifest is missing required file entry: {required}"));
+        };
+        if expected.records > limits.max_rows {
+            return Err(anyhow!(
+                "manifest lists {} rows for {required}, over the {} row limit",
+                expected.records,
+                limits.max_rows
+            ));
+        }
+        required_files.push(*expected);
+    }
+
+    Ok(required_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::thread;
+
+    use super::*;
+    use memory_kernel_core::{
+        build_context_package, AnswerResult, ConstraintEffect, ConstraintPayload, ConstraintScope,
+        MemoryPayload, Provenance, QueryRequest,
+    };
+    use proptest::prelude::*;
+
+    fn insert_legacy_constraint_record(
+        conn: &Connection,
+        memory_id: MemoryId,
+        created_at: &str,
+        confidence: f64,
+        justification: &str,
+    ) -> Result<()> {
+        conn.execute(
+            "INSERT INTO memory_records(
+                memory_id, version, record_type, created_at, effective_at,
+                truth_status, authority, confidence, writer, justification,
+                source_uri, source_hash, evidence_json
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                memory_id.to_string(),
+                1_i64,
+                "constraint",
+                created_at,
+                created_at,
+                "asserted",
+                "authoritative",
+                confidence,
+                "tester",
+                justification,
+                "file:///policy.md",
+                "sha256:abc123",
+                "[]",
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn insert_legacy_constraint_payload(conn: &Connection, memory_id: MemoryId) -> Result<()> {
+        conn.execute(
+            "INSERT INTO constraint_payloads(memory_id, actor, action, resource, effect, note)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                memory_id.to_string(),
+                "user",
+                "use",
+                "usb_drive",
+                "deny",
+                Option::<String>::None,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn insert_legacy_supersedes_link(
+        conn: &Connection,
+        from_memory_id: MemoryId,
+        to_memory_id: MemoryId,
+        created_at: &str,
+    ) -> Result<()> {
+        conn.execute(
+            "INSERT INTO memory_links(from_memory_id, to_memory_id, link_type, writer, justification, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                from_memory_id.to_string(),
+                to_memory_id.to_string(),
+                "supersedes",
+                "tester",
+                "legacy link",
+                created_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn mk_store_constraint_record(
+        memory_id: MemoryId,
+        version: u32,
+        truth_status: TruthStatus,
+        confidence: Option<f32>,
+        effect: ConstraintEffect,
+    ) -> MemoryRecord {
+        MemoryRecord {
+            memory_version_id: MemoryVersionId::new(),
+            memory_id,
+            version,
+            created_at: OffsetDateTime::now_utc(),
+            effective_at: OffsetDateTime::now_utc(),
+            truth_status,
+            authority: Authority::Authoritative,
+            confidence,
+            writer: "tester".to_string(),
+            justification: "fixture".to_string(),
+            provenance: Provenance {
+                source_uri: "file:///policy.md".to_string(),
+                source_hash: Some("sha256:abc123".to_string()),
+                evidence: vec![],
+            },
+            supersedes: vec![],
+            contradicts: vec![],
+            tags: vec![],
+            payload: MemoryPayload::Constraint(ConstraintPayload {
+                scope: ConstraintScope {
+                    actor: "user".to_string(),
+                    action: "use".to_string(),
+                    resource: "usb_drive".to_string(),
+                },
+                effect,
+                note: None,
+                expires_at: None,
+            }),
+        }
+    }
+
+    // Test IDs: TDB-002
+    #[test]
+    fn sqlite_constraints_enforce_checks_and_foreign_keys() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let check_result = store.conn.execute(
+            "INSERT INTO memory_records(
+                memory_version_id, memory_id, version, record_type, created_at, effective_at,
+                truth_status, authority, confidence, writer, justification,
+                source_uri, source_hash, evidence_json
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6,
+                ?7, ?8, ?9, ?10, ?11,
+                ?12, ?13, ?14
+            )",
+            params![
+                MemoryVersionId::new().to_string(),
+                MemoryId::new().to_string(),
+                1_i64,
+                "not_a_valid_record_type",
+                "2026-01-01T00:00:00Z",
+                "2026-01-01T00:00:00Z",
+                "asserted",
+                "authoritative",
+                0.5_f64,
+                "tester",
+                "check invalid enum",
+                "file:///policy.md",
+                "sha256:abc123",
+                "[]",
+            ],
+        );
+        assert!(check_result.is_err());
+
+        let fk_result = store.conn.execute(
+            "INSERT INTO memory_links(
+                from_memory_version_id, to_memory_version_id, link_type, writer, justification, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                MemoryVersionId::new().to_string(),
+                MemoryVersionId::new().to_string(),
+                "supersedes",
+                "tester",
+                "foreign key invalid",
+                "2026-01-01T00:00:00Z",
+            ],
+        );
+        assert!(fk_result.is_err());
+
+        Ok(())
+    }
+
+    // Test IDs: TID-001
+    #[test]
+    fn inserted_records_receive_distinct_memory_version_ids() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let a = mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.8),
+            ConstraintEffect::Deny,
+        );
+        let b = mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Allow,
+        );
+
+        store.write_record(&a)?;
+        store.write_record(&b)?;
+
+        let records = store.list_records()?;
+        let ids = records.iter().map(|record| record.memory_version_id).collect::<BTreeSet<_>>();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(ids.len(), 2);
+        Ok(())
+    }
+
+    // Test IDs: TID-002
+    #[test]
+    fn duplicate_memory_id_version_is_rejected() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let memory_id = MemoryId::new();
+        let first = mk_store_constraint_record(
+            memory_id,
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+        );
+        let second = mk_store_constraint_record(
+            memory_id,
+            1,
+            TruthStatus::Observed,
+            Some(0.95),
+            ConstraintEffect::Allow,
+        );
+
+        store.write_record(&first)?;
+        let second_err = store.write_record(&second);
+        assert!(second_err.is_err());
+
+        Ok(())
+    }
+
+    // Test IDs: TDB-003
+    #[test]
+    fn write_and_read_constraint_round_trip() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let record = MemoryRecord {
+            memory_version_id: MemoryVersionId::new(),
+            memory_id: MemoryId::new(),
+            version: 1,
+            created_at: OffsetDateTime::now_utc(),
+            effective_at: OffsetDateTime::now_utc(),
+            truth_status: TruthStatus::Asserted,
+            authority: Authority::Authoritative,
+            confidence: Some(0.95),
+            writer: "tester".to_string(),
+            justification: "seed policy".to_string(),
+            provenance: Provenance {
+                source_uri: "file:///policy.md".to_string(),
+                source_hash: Some("sha256:abc123".to_string()),
+                evidence: vec![],
+            },
+            supersedes: vec![],
+            contradicts: vec![],
+            tags: vec![],
+            payload: MemoryPayload::Constraint(ConstraintPayload {
+                scope: ConstraintScope {
+                    actor: "user".to_string(),
+                    action: "use".to_string(),
+                    resource: "usb_drive".to_string(),
+                },
+                effect: ConstraintEffect::Deny,
+                note: None,
+                expires_at: None,
+            }),
+        };
+
+        store.write_record(&record)?;
+        let records = store.list_records()?;
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].memory_id, record.memory_id);
+
+        let package = build_context_package(
+            &records,
+            QueryRequest {
+                text: "Am I allowed to use a USB drive?".to_string(),
+                actor: "user".to_string(),
+                action: "use".to_string(),
+                resource: "usb_drive".to_string(),
+                as_of: OffsetDateTime::now_utc(),
+            },
+            "txn_fixture",
+        )?;
+
+        assert_eq!(package.answer.result, memory_kernel_core::AnswerResult::Deny);
+        Ok(())
+    }
+
+    // Test IDs: TID-003
+    #[test]
+    fn supersedes_links_round_trip_by_memory_version_id() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let entity_id = MemoryId::new();
+        let old = MemoryRecord {
+            memory_version_id: MemoryVersionId::new(),
+            memory_id: entity_id,
+            version: 1,
+            created_at: OffsetDateTime::now_utc(),
+            effective_at: OffsetDateTime::now_utc(),
+            truth_status: TruthStatus::Asserted,
+            authority: Authority::Authoritative,
+            confidence: Some(0.8),
+            writer: "tester".to_string(),
+            justification: "seed policy v1".to_string(),
+            provenance: Provenance {
+                source_uri: "file:///policy.md".to_string(),
+                source_hash: Some("sha256:abc123".to_string()),
+                evidence: vec![],
+            },
+            supersedes: vec![],
+            contradicts: vec![],
+            tags: vec![],
+            payload: MemoryPayload::Constraint(ConstraintPayload {
+                scope: ConstraintScope {
+                    actor: "user".to_string(),
+                    action: "use".to_string(),
+                    resource: "usb_drive".to_string(),
+                },
+                effect: ConstraintEffect::Deny,
+                note: None,
+                expires_at: None,
+            }),
+        };
+
+        let new = MemoryRecord {
+            memory_version_id: MemoryVersionId::new(),
+            memory_id: entity_id,
+            version: 2,
+            created_at: OffsetDateTime::now_utc(),
+            effective_at: OffsetDateTime::now_utc(),
+            truth_status: TruthStatus::Asserted,
+            authority: Authority::Authoritative,
+            confidence: Some(0.95),
+            writer: "tester".to_string(),
+            justification: "seed policy v2".to_string(),
+            provenance: Provenance {
+                source_uri: "file:///policy.md".to_string(),
+                source_hash: Some("sha256:def456".to_string()),
+                evidence: vec![],
+            },
+            supersedes: vec![old.memory_version_id],
+            contradicts: vec![],
+            tags: vec![],
+            payload: MemoryPayload::Constraint(ConstraintPayload {
+                scope: ConstraintScope {
+                    actor: "user".to_string(),
+                    action: "use".to_string(),
+                    resource: "usb_drive".to_string(),
+                },
+                effect: ConstraintEffect::Deny,
+                note: Some("new baseline".to_string()),
+                expires_at: None,
+            }),
+        };
+
+        store.write_record(&old)?;
+        store.write_record(&new)?;
+
+        let records = store.list_records()?;
+        let Some(loaded_new) =
+            records.iter().find(|record| record.memory_version_id == new.memory_version_id)
+        else {
+            return Err(anyhow!(
+                "new record not found by memory_version_id {}",
+                new.memory_version_id
+            ));
+        };
+
+        assert_eq!(loaded_new.supersedes, vec![old.memory_version_id]);
+        Ok(())
+    }
+
+    // Test IDs: TDB-010
+    #[test]
+    fn iter_records_streams_batches_in_list_order() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let base = OffsetDateTime::from_unix_timestamp(1_700_000_000)?;
+        let mut written = Vec::new();
+        for index in 0..7_i64 {
+            let mut record = mk_store_constraint_record(
+                MemoryId::new(),
+                1,
+                TruthStatus::Asserted,
+                Some(0.8),
+                ConstraintEffect::Allow,
+            );
+            // Pairs share a timestamp so batches split on the memory_id tie-breaker.
+            record.created_at = base + time::Duration::seconds(index / 2);
+            record.effective_at = record.created_at;
+            if index % 3 == 0 {
+                record.payload = MemoryPayload::Decision(memory_kernel_core::DecisionPayload {
+                    summary: format!("decision {index}"),
+                });
+            }
+            if let Some(previous) = written.last().map(|prev: &MemoryRecord| prev.memory_version_id)
+            {
+                if index % 2 == 0 {
+                    record.supersedes = vec![previous];
+                } else {
+                    record.contradicts = vec![previous];
+                }
+            }
+            store.write_record(&record)?;
+            written.push(record);
+        }
+
+        written.sort_by(|lhs, rhs| {
+            rhs.created_at
+                .cmp(&lhs.created_at)
+                .then_with(|| lhs.memory_id.to_string().cmp(&rhs.memory_id.to_string()))
+                .then_with(|| {
+                    lhs.memory_version_id.to_string().cmp(&rhs.memory_version_id.to_string())
+                })
+        });
+
+        let streamed = store.iter_records_batched(2)?.collect::<Result<Vec<_>>>()?;
+        assert_eq!(streamed, written);
+        assert_eq!(store.list_records()?, written);
+
+        // The read snapshot is released once the iterator is exhausted.
+        assert!(store.conn.is_autocommit());
+
+        let mut partial = store.iter_records_batched(3)?;
+        assert!(partial.next().is_some());
+        assert!(!store.conn.is_autocommit());
+        drop(partial);
+        assert!(store.conn.is_autocommit());
+
+        assert!(store.iter_records_batched(0).is_err());
+        Ok(())
+    }
+
+    // Test IDs: TDB-011
+    #[test]
+    fn list_records_filtered_applies_filters_and_keyset_pages() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let base = OffsetDateTime::from_unix_timestamp(1_700_000_000)?;
+        for index in 0..6_i64 {
+            let mut record = mk_store_constraint_record(
+                MemoryId::new(),
+                1,
+                if index % 2 == 0 { TruthStatus::Asserted } else { TruthStatus::Observed },
+                Some(0.8),
+                ConstraintEffect::Deny,
+            );
+            record.created_at = base + time::Duration::seconds(index);
+            record.effective_at = record.created_at;
+            if index == 5 {
+                record.writer = "importer".to_string();
+                record.payload = MemoryPayload::Event(memory_kernel_core::EventPayload {
+                    summary: "imported event".to_string(),
+                });
+            }
+            store.write_record(&record)?;
+        }
+
+        let all = store.list_records()?;
+        let unfiltered = store.list_records_filtered(&RecordFilter::default())?;
+        assert_eq!(unfiltered.records, all);
+        assert_eq!(unfiltered.next_after, None);
+
+        let mut paged = Vec::new();
+        let mut filter = RecordFilter { limit: Some(4), ..RecordFilter::default() };
+        loop {
+            let page = store.list_records_filtered(&filter)?;
+            paged.extend(page.records);
+            let Some(next_after) = page.next_after else {
+                break;
+            };
+            filter.after = Some(next_after);
+        }
+        assert_eq!(paged, all);
+
+        let by_type = store.list_records_filtered(&RecordFilter {
+            record_type: Some(RecordType::Event),
+            ..RecordFilter::default()
+        })?;
+        assert_eq!(by_type.records.len(), 1);
+        assert_eq!(by_type.records[0].writer, "importer");
+
+        let by_writer_and_status = store.list_records_filtered(&RecordFilter {
+            writer: Some("tester".to_string()),
+            truth_status: Some(TruthStatus::Observed),
+            ..RecordFilter::default()
+        })?;
+        assert_eq!(by_writer_and_status.records.len(), 2);
+
+        let by_range = store.list_records_filtered(&RecordFilter {
+            effective_from: Some(base + time::Duration::seconds(1)),
+            effective_to: Some(base + time::Duration::seconds(3)),
+            ..RecordFilter::default()
+        })?;
+        let effective =
+            by_range.records.iter().map(|record| record.effective_at).collect::<Vec<_>>();
+        assert_eq!(
+            effective,
+            vec![base + time::Duration::seconds(2), base + time::Duration::seconds(1)]
+        );
+
+        assert!(store
+            .list_records_filtered(&RecordFilter { limit: Some(0), ..RecordFilter::default() })
+            .is_err());
+        assert!(store
+            .list_records_filtered(&RecordFilter {
+                after: Some(MemoryVersionId::new()),
+                ..RecordFilter::default()
+            })
+            .is_err());
+        Ok(())
+    }
+
+    // Test IDs: TDB-012
+    #[test]
+    fn recall_candidates_match_full_recall_and_survive_backfill() -> Result<()> {
+        use memory_kernel_core::{build_recall_context_package, DecisionPayload, EventPayload};
+
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let summary_record = |payload: MemoryPayload| {
+            let mut record = mk_store_constraint_record(
+                MemoryId::new(),
+                1,
+                TruthStatus::Observed,
+                Some(0.7),
+                ConstraintEffect::Deny,
+            );
+            record.payload = payload;
+            record
+        };
+        let approval = summary_record(MemoryPayload::Decision(DecisionPayload {
+            summary: "USB media requires manager approval".to_string(),
+        }));
+        let mut replacement = summary_record(MemoryPayload::Decision(DecisionPayload {
+            summary: "Removable storage policy replaced".to_string(),
+        }));
+        replacement.supersedes = vec![approval.memory_version_id];
+        let incident = summary_record(MemoryPayload::Event(EventPayload {
+            summary: "USB incident reported".to_string(),
+        }));
+        let lunch = summary_record(MemoryPayload::Event(EventPayload {
+            summary: "Team lunch".to_string(),
+        }));
+        let constraint = mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+        );
+        for record in [&approval, &replacement, &incident, &lunch, &constraint] {
+            store.write_record(record)?;
+        }
+
+        let record_types = [RecordType::Decision, RecordType::Event];
+        let candidates = store.recall_candidates("usb approval", &record_types, 10)?;
+        let candidate_ids =
+            candidates.iter().map(|record| record.memory_version_id).collect::<BTreeSet<_>>();
+        assert_eq!(
+            candidate_ids,
+            BTreeSet::from([
+                approval.memory_version_id,
+                replacement.memory_version_id,
+                incident.memory_version_id
+            ])
+        );
+
+        let query = QueryRequest {
+            text: "usb approval".to_string(),
+            actor: "*".to_string(),
+            action: "*".to_string(),
+            resource: "*".to_string(),
+            as_of: OffsetDateTime::now_utc(),
+        };
+        let from_candidates =
+            build_recall_context_package(&candidates, query.clone(), "snap", &record_types)?;
+        let from_all =
+            build_recall_context_package(&store.list_records()?, query, "snap", &record_types)?;
+        assert_eq!(from_candidates.selected_items, from_all.selected_items);
+        assert!(from_candidates.excluded_items.iter().any(|item| {
+            item.memory_version_id == approval.memory_version_id
+                && item.why.reasons.iter().any(|reason| reason.contains("superseded"))
+        }));
+
+        let best = store.recall_candidates("usb approval", &[RecordType::Decision], 1)?;
+        assert!(best.iter().any(|record| record.memory_version_id == approval.memory_version_id));
+        assert!(store.recall_candidates("?!", &record_types, 10)?.is_empty());
+        assert!(store.recall_candidates("usb", &record_types, 0).is_err());
+
+        // Rolling back to the v3 shape and migrating again backfills the index from stored records.
+        store.conn.execute_batch(
+            "DROP TABLE memory_recall_fts;
+             ALTER TABLE constraint_payloads DROP COLUMN expires_at;
+             DELETE FROM schema_migrations WHERE version >= 4;",
+        )?;
+        store.migrate()?;
+        let backfilled = store.recall_candidates("incident", &record_types, 10)?;
+        assert_eq!(backfilled.len(), 1);
+        assert_eq!(backfilled[0].memory_version_id, incident.memory_version_id);
+        Ok(())
+    }
+
+    // Test IDs: TDB-013
+    #[test]
+    fn constraint_expires_at_round_trips() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let mut expiring = mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+        );
+        if let MemoryPayload::Constraint(payload) = &mut expiring.payload {
+            payload.expires_at = Some(expiring.effective_at + time::Duration::days(30));
+        }
+        let open_ended = mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Allow,
+        );
+        store.write_record(&expiring)?;
+        store.write_record(&open_ended)?;
+
+        let stored = store.list_records()?;
+        for written in [&expiring, &open_ended] {
+            let Some(found) =
+                stored.iter().find(|record| record.memory_version_id == written.memory_version_id)
+            else {
+                return Err(anyhow!("record missing after write"));
+            };
+            assert_eq!(found.payload, written.payload);
+        }
+        Ok(())
+    }
+
+    // Test IDs: TDB-014
+    #[test]
+    fn task_risk_and_assumption_payloads_round_trip() -> Result<()> {
+        use memory_kernel_core::{AssumptionPayload, RiskPayload, TaskPayload};
+
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let mut written = Vec::new();
+        for payload in [
+            MemoryPayload::Task(TaskPayload {
+                summary: "Rotate USB keys".to_string(),
+                status: TaskStatus::InProgress,
+            }),
+            MemoryPayload::Risk(RiskPayload {
+                summary: "USB exfiltration".to_string(),
+                severity: RiskSeverity::High,
+            }),
+            MemoryPayload::Assumption(AssumptionPayload {
+                summary: "USB ports are disabled".to_string(),
+                status: AssumptionStatus::Invalidated,
+            }),
+        ] {
+            let mut record = mk_store_constraint_record(
+                MemoryId::new(),
+                1,
+                TruthStatus::Observed,
+                Some(0.7),
+                ConstraintEffect::Deny,
+            );
+            record.payload = payload;
+            store.write_record(&record)?;
+            written.push(record);
+        }
+
+        let stored = store.list_records()?;
+        for record in &written {
+            let Some(found) =
+                stored.iter().find(|stored| stored.memory_version_id == record.memory_version_id)
+            else {
+                return Err(anyhow!("record missing after write"));
+            };
+            assert_eq!(found.payload, record.payload);
+        }
+
+        let filter =
+            RecordFilter { record_type: Some(RecordType::Risk), ..RecordFilter::default() };
+        let page = store.list_records_filtered(&filter)?;
+        assert_eq!(page.records.len(), 1);
+        assert_eq!(page.records[0].memory_version_id, written[1].memory_version_id);
+
+        let candidates = store.recall_candidates("usb", &[RecordType::Task], 10)?;
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].memory_version_id, written[0].memory_version_id);
+
+        let bad_status = store.conn.execute(
+            "INSERT INTO task_payloads(memory_version_id, summary, status) VALUES (?1, 'x', 'later')",
+            params![written[1].memory_version_id.to_string()],
+        );
+        assert!(bad_status.is_err());
+        Ok(())
+    }
+
+    // Test IDs: TDB-015
+    #[test]
+    fn list_contradictions_skips_retracted_and_superseded_versions() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let record = |truth_status: TruthStatus| {
+            mk_store_constraint_record(
+                MemoryId::new(),
+                1,
+                truth_status,
+                Some(0.8),
+                ConstraintEffect::Deny,
+            )
+        };
+        let anchor = record(TruthStatus::Asserted);
+        let mut active = record(TruthStatus::Observed);
+        active.contradicts = vec![anchor.memory_version_id];
+        let mut retracted = record(TruthStatus::Retracted);
+        retracted.contradicts = vec![anchor.memory_version_id];
+        let mut stale = record(TruthStatus::Asserted);
+        stale.contradicts = vec![anchor.memory_version_id];
+        let mut replacement = record(TruthStatus::Asserted);
+        replacement.supersedes = vec![stale.memory_version_id];
+        for record in [&anchor, &active, &retracted, &stale, &replacement] {
+            store.write_record(record)?;
+        }
+
+        let report = store.list_contradictions()?;
+        assert_eq!(report.contradiction_count, 1);
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.groups[0].memory_id, active.memory_id);
+        let contradiction = &report.groups[0].contradictions[0];
+        assert_eq!(contradiction.from.memory_version_id, active.memory_version_id);
+        assert_eq!(contradiction.to.memory_version_id, anchor.memory_version_id);
+        assert_eq!(contradiction.to.truth_status, TruthStatus::Asserted);
+
+        store.add_link(
+            replacement.memory_version_id,
+            anchor.memory_version_id,
+            LinkType::Supersedes,
+            "tester",
+            "anchor replaced",
+        )?;
+        assert_eq!(store.list_contradictions()?, ContradictionReport::default());
+        Ok(())
+    }
+
+    // Test IDs: TDB-016
+    #[test]
+    fn write_records_batch_is_atomic_and_allows_forward_links() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let record = |memory_id: MemoryId, version: u32| {
+            mk_store_constraint_record(
+                memory_id,
+                version,
+                TruthStatus::Asserted,
+                Some(0.9),
+                ConstraintEffect::Deny,
+            )
+        };
+        let memory_id = MemoryId::new();
+        let original = record(memory_id, 1);
+        let mut replacement = record(memory_id, 2);
+        replacement.supersedes = vec![original.memory_version_id];
+        let unrelated = record(MemoryId::new(), 1);
+        store.write_records_batch(&[replacement.clone(), unrelated.clone(), original.clone()])?;
+
+        let stored = store.list_records()?;
+        assert_eq!(stored.len(), 3);
+        let Some(found) =
+            stored.iter().find(|stored| stored.memory_version_id == replacement.memory_version_id)
+        else {
+            return Err(anyhow!("replacement missing after batch write"));
+        };
+        assert_eq!(found.supersedes, vec![original.memory_version_id]);
+
+        let fresh = record(MemoryId::new(), 1);
+        let mut duplicate = record(memory_id, 3);
+        duplicate.memory_version_id = unrelated.memory_version_id;
+        let Err(err) = store.write_records_batch(&[fresh.clone(), duplicate]) else {
+            return Err(anyhow!("duplicate memory_version_id must fail the batch"));
+        };
+        assert!(format!("{err:#}").contains("batch record 1"));
+
+        let mut invalid = record(MemoryId::new(), 1);
+        invalid.writer = String::new();
+        let Err(err) = store.write_records_batch(&[fresh, invalid]) else {
+            return Err(anyhow!("invalid record must fail the batch"));
+        };
+        assert!(err.to_string().contains("record 1 validation failed"));
+
+        assert_eq!(store.list_records()?.len(), 3);
+        Ok(())
     }
 
-    fn mk_store_constraint_record(
-        memory_id: MemoryId,
-        version: u32,
-        truth_status: TruthStatus,
-        confidence: Option<f32>,
-        effect: ConstraintEffect,
-    ) -> MemoryRecord {
-        MemoryRecord {
-            memory_version_id: MemoryVersionId::new(),
-            memory_id,
-            version,
-            created_at: OffsetDateTime::now_utc(),
-            effective_at: OffsetDateTime::now_utc(),
-            truth_status,
-            authority: Authority::Authoritative,
-            confidence,
-            writer: "tester".to_string(),
-            justification: "fixture".to_string(),
-            provenance: Provenance {
-                source_uri: "file:///policy.md".to_string(),
-                source_hash: Some("sha256:abc123".to_string()),
-                evidence: vec![],
-            },
-            supersedes: vec![],
-            contradicts: vec![],
-            payload: MemoryPayload::Constraint(ConstraintPayload {
-                scope: ConstraintScope {
-                    actor: "user".to_string(),
+    // Test IDs: TDB-017
+    #[test]
+    fn list_context_packages_filters_and_keyset_pages() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+        store.write_record(&mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+        ))?;
+        let records = store.list_records()?;
+
+        let base = OffsetDateTime::now_utc();
+        for (id, actor, resource, minutes) in [
+            ("ctx_a", "user", "usb_drive", 0),
+            ("ctx_b", "user", "printer", 10),
+            ("ctx_c", "contractor", "usb_drive", 20),
+        ] {
+            let mut package = build_context_package(
+                &records,
+                QueryRequest {
+                    text: format!("Can {actor} use {resource}?"),
+                    actor: actor.to_string(),
                     action: "use".to_string(),
-                    resource: "usb_drive".to_string(),
+                    resource: resource.to_string(),
+                    as_of: base,
                 },
-                effect,
-                note: None,
-            }),
+                "snap_list_packages",
+            )?;
+            package.context_package_id = id.to_string();
+            package.generated_at = base + time::Duration::minutes(minutes);
+            store.save_context_package(&package)?;
         }
+        let ids = |page: &ContextPackagePage| {
+            page.packages
+                .iter()
+                .map(|package| package.context_package_id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let all = store.list_context_packages(&ContextPackageFilter::default())?;
+        assert_eq!(ids(&all), vec!["ctx_c", "ctx_b", "ctx_a"]);
+        assert_eq!(all.next_after, None);
+
+        let by_actor = ContextPackageFilter {
+            actor: Some("user".to_string()),
+            ..ContextPackageFilter::default()
+        };
+        assert_eq!(ids(&store.list_context_packages(&by_actor)?), vec!["ctx_b", "ctx_a"]);
+
+        let denied = ContextPackageFilter {
+            answer_result: Some(AnswerResult::Deny),
+            resource: Some("usb_drive".to_string()),
+            ..ContextPackageFilter::default()
+        };
+        assert_eq!(ids(&store.list_context_packages(&denied)?), vec!["ctx_a"]);
+
+        let window = ContextPackageFilter {
+            generated_from: Some(base + time::Duration::minutes(10)),
+            generated_to: Some(base + time::Duration::minutes(20)),
+            ..ContextPackageFilter::default()
+        };
+        assert_eq!(ids(&store.list_context_packages(&window)?), vec!["ctx_b"]);
+
+        let exact =
+            store.list_context_packages(&ContextPackageFilter { limit: Some(2), ..by_actor })?;
+        assert_eq!(ids(&exact), vec!["ctx_b", "ctx_a"]);
+        assert_eq!(exact.next_after, None);
+        let first = store.list_context_packages(&ContextPackageFilter {
+            limit: Some(2),
+            ..ContextPackageFilter::default()
+        })?;
+        assert_eq!(first.next_after.as_deref(), Some("ctx_b"));
+        let second = store.list_context_packages(&ContextPackageFilter {
+            limit: Some(2),
+            after: first.next_after,
+            ..ContextPackageFilter::default()
+        })?;
+        assert_eq!(ids(&second), vec!["ctx_a"]);
+        assert_eq!(second.next_after, None);
+
+        assert!(store
+            .list_context_packages(&ContextPackageFilter {
+                limit: Some(0),
+                ..ContextPackageFilter::default()
+            })
+            .is_err());
+        Ok(())
     }
 
-    // Test IDs: TDB-002
+    // Test IDs: TDB-018
     #[test]
-    fn sqlite_constraints_enforce_checks_and_foreign_keys() -> Result<()> {
+    fn record_embeddings_round_trip_per_model() -> Result<()> {
         let mut store = SqliteStore::open(Path::new(":memory:"))?;
         store.migrate()?;
+        let record = mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+        );
+        store.write_record(&record)?;
+        let id = record.memory_version_id;
+
+        store.put_record_embeddings("model-a", &[(id, vec![0.25, -1.5, 3.0])])?;
+        store.put_record_embeddings("model-b", &[(id, vec![1.0])])?;
+        assert_eq!(store.record_embeddings("model-a")?.get(&id), Some(&vec![0.25, -1.5, 3.0]));
+        assert_eq!(store.record_embeddings("model-b")?.get(&id), Some(&vec![1.0]));
+        assert!(store.record_embeddings("model-c")?.is_empty());
+
+        store.put_record_embeddings("model-a", &[(id, vec![2.0, 0.5])])?;
+        assert_eq!(store.record_embeddings("model-a")?.get(&id), Some(&vec![2.0, 0.5]));
+
+        assert!(store.put_record_embeddings("model-a", &[(id, vec![])]).is_err());
+        assert!(store.put_record_embeddings("model-a", &[(id, vec![f32::NAN])]).is_err());
+        assert!(store.put_record_embeddings(" ", &[(id, vec![1.0])]).is_err());
+        let missing =
+            store.put_record_embeddings("model-a", &[(MemoryVersionId::new(), vec![1.0])]);
+        assert!(missing.is_err());
+        assert_eq!(store.record_embeddings("model-a")?.len(), 1);
+        Ok(())
+    }
 
-        let check_result = store.conn.execute(
-            "INSERT INTO memory_records(
-                memory_version_id, memory_id, version, record_type, created_at, effective_at,
-                truth_status, authority, confidence, writer, justification,
-                source_uri, source_hash, evidence_json
-            ) VALUES (
-                ?1, ?2, ?3, ?4, ?5, ?6,
-                ?7, ?8, ?9, ?10, ?11,
-                ?12, ?13, ?14
-            )",
-            params![
-                MemoryVersionId::new().to_string(),
-                MemoryId::new().to_string(),
-                1_i64,
-                "not_a_valid_record_type",
-                "2026-01-01T00:00:00Z",
-                "2026-01-01T00:00:00Z",
-                "asserted",
-                "authoritative",
-                0.5_f64,
-                "tester",
-                "check invalid enum",
-                "file:///policy.md",
-                "sha256:abc123",
-                "[]",
-            ],
+    // Test IDs: TDB-019
+    #[test]
+    fn record_tags_round_trip_and_filter_list_and_recall() -> Result<()> {
+        use memory_kernel_core::DecisionPayload;
+
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let tagged = |summary: &str, tags: &[&str]| {
+            let mut record = mk_store_constraint_record(
+                MemoryId::new(),
+                1,
+                TruthStatus::Observed,
+                Some(0.7),
+                ConstraintEffect::Deny,
+            );
+            record.payload =
+                MemoryPayload::Decision(DecisionPayload { summary: summary.to_string() });
+            record.tags = tags.iter().map(ToString::to_string).collect();
+            record
+        };
+        let both = tagged("USB approval for payments", &["team-payments", "project-usb"]);
+        let payments = tagged("USB ban for payments laptops", &["team-payments"]);
+        let untagged = tagged("USB approval everywhere", &[]);
+        for record in [&both, &payments, &untagged] {
+            store.write_record(record)?;
+        }
+
+        let listed = store.list_records()?;
+        let stored_both = listed
+            .iter()
+            .find(|record| record.memory_version_id == both.memory_version_id)
+            .ok_or_else(|| anyhow!("tagged record missing"))?;
+        assert_eq!(stored_both.tags, both.tags);
+
+        let ids = |records: &[MemoryRecord]| {
+            records.iter().map(|record| record.memory_version_id).collect::<BTreeSet<_>>()
+        };
+        let filter = |tags: &[&str]| RecordFilter {
+            tags: tags.iter().map(ToString::to_string).collect(),
+            ..RecordFilter::default()
+        };
+        let by_team = store.list_records_filtered(&filter(&["team-payments"]))?;
+        assert_eq!(
+            ids(&by_team.records),
+            BTreeSet::from([both.memory_version_id, payments.memory_version_id])
+        );
+        let by_both = store.list_records_filtered(&filter(&["project-usb", "team-payments"]))?;
+        assert_eq!(ids(&by_both.records), BTreeSet::from([both.memory_version_id]));
+        assert!(store.list_records_filtered(&filter(&["team-unknown"]))?.records.is_empty());
+
+        let team_tags = ["team-payments".to_string()];
+        let recalled = store.recall_candidates_tagged(
+            "usb approval",
+            &[RecordType::Decision],
+            &team_tags,
+            10,
+        )?;
+        assert_eq!(
+            ids(&recalled),
+            BTreeSet::from([both.memory_version_id, payments.memory_version_id])
         );
-        assert!(check_result.is_err());
 
-        let fk_result = store.conn.execute(
-            "INSERT INTO memory_links(
-                from_memory_version_id, to_memory_version_id, link_type, writer, justification, created_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![
-                MemoryVersionId::new().to_string(),
-                MemoryVersionId::new().to_string(),
-                "supersedes",
-                "tester",
-                "foreign key invalid",
-                "2026-01-01T00:00:00Z",
-            ],
+        let mut invalid = tagged("Duplicate tags", &["team-payments", "team-payments"]);
+        assert!(store.write_record(&invalid).is_err());
+        invalid.tags = vec![" ".to_string()];
+        assert!(store.write_record(&invalid).is_err());
+        Ok(())
+    }
+
+    // Test IDs: TDB-020
+    #[test]
+    fn namespaces_isolate_records_links_and_context_packages() -> Result<()> {
+        use memory_kernel_core::{build_context_package, QueryRequest};
+
+        let path = std::env::temp_dir().join(format!("mk-namespaces-{}.sqlite3", Ulid::new()));
+        let mut team_a = SqliteStore::open(&path)?;
+        team_a.migrate()?;
+        team_a.set_namespace("team-a")?;
+        let mut team_b = SqliteStore::open(&path)?;
+        team_b.set_namespace("team-b")?;
+        let default_store = SqliteStore::open(&path)?;
+        assert_eq!(default_store.namespace(), DEFAULT_NAMESPACE);
+
+        let record_a = mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
         );
-        assert!(fk_result.is_err());
+        team_a.write_record(&record_a)?;
+        let record_b = mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Allow,
+        );
+        team_b.write_record(&record_b)?;
 
+        let ids = |store: &SqliteStore| -> Result<Vec<MemoryVersionId>> {
+            Ok(store.list_records()?.iter().map(|record| record.memory_version_id).collect())
+        };
+        assert_eq!(ids(&team_a)?, vec![record_a.memory_version_id]);
+        assert_eq!(ids(&team_b)?, vec![record_b.memory_version_id]);
+        assert!(ids(&default_store)?.is_empty());
+        assert_eq!(team_a.list_record_keys()?.len(), 1);
+        let cursor_elsewhere = team_b.list_records_filtered(&RecordFilter {
+            after: Some(record_a.memory_version_id),
+            ..RecordFilter::default()
+        });
+        assert!(cursor_elsewhere.is_err());
+
+        let cross_link = team_b.add_link(
+            record_b.memory_version_id,
+            record_a.memory_version_id,
+            LinkType::Contradicts,
+            "tester",
+            "cross-namespace",
+        );
+        assert!(cross_link.is_err());
+        let mut cross_supersede = record_b.clone();
+        cross_supersede.memory_version_id = MemoryVersionId::new();
+        cross_supersede.version = 2;
+        cross_supersede.supersedes = vec![record_a.memory_version_id];
+        assert!(team_b.write_record(&cross_supersede).is_err());
+        assert!(team_b.write_successor(record_a.memory_version_id, |_, _| unreachable!()).is_err());
+
+        let mut package = build_context_package(
+            &team_a.list_records()?,
+            QueryRequest {
+                text: "Can user use usb_drive?".to_string(),
+                actor: "user".to_string(),
+                action: "use".to_string(),
+                resource: "usb_drive".to_string(),
+                as_of: OffsetDateTime::now_utc(),
+            },
+            "snap_namespaces",
+        )?;
+        package.context_package_id = "ctx_team_a".to_string();
+        team_a.save_context_package(&package)?;
+        assert!(team_a.get_context_package("ctx_team_a")?.is_some());
+        assert!(team_b.get_context_package("ctx_team_a")?.is_none());
+        let listed = team_b.list_context_packages(&ContextPackageFilter::default())?;
+        assert!(listed.packages.is_empty());
+
+        assert!(team_a.set_namespace("").is_err());
+        assert!(team_a.set_namespace("team a").is_err());
+        assert_eq!(team_a.namespace(), "team-a");
+
+        drop((team_a, team_b, default_store));
+        let _ = fs::remove_file(&path);
         Ok(())
     }
 
-    // Test IDs: TID-001
+    // Test IDs: TDB-021
     #[test]
-    fn inserted_records_receive_distinct_memory_version_ids() -> Result<()> {
-        let mut store = SqliteStore::open(Path::new(":memory:"))?;
-        store.migrate()?;
+    fn filtered_export_selects_records_with_lineage_and_records_filter() -> Result<()> {
+        use memory_kernel_core::{build_context_package, QueryRequest};
 
-        let a = mk_store_constraint_record(
+        let mut source = SqliteStore::open(Path::new(":memory:"))?;
+        source.migrate()?;
+        source.set_namespace("team-a")?;
+
+        let base = mk_store_constraint_record(
             MemoryId::new(),
             1,
             TruthStatus::Asserted,
-            Some(0.8),
+            Some(0.9),
+            ConstraintEffect::Allow,
+        );
+        source.write_record(&base)?;
+        let mut successor = mk_store_constraint_record(
+            base.memory_id,
+            2,
+            TruthStatus::Asserted,
+            Some(0.9),
             ConstraintEffect::Deny,
         );
-        let b = mk_store_constraint_record(
+        successor.writer = "alice".to_string();
+        successor.supersedes = vec![base.memory_version_id];
+        source.write_record(&successor)?;
+        let mut unrelated = mk_store_constraint_record(
             MemoryId::new(),
             1,
             TruthStatus::Asserted,
             Some(0.9),
             ConstraintEffect::Allow,
         );
+        unrelated.writer = "bob".to_string();
+        source.write_record(&unrelated)?;
 
-        store.write_record(&a)?;
-        store.write_record(&b)?;
+        let package = build_context_package(
+            &source.list_records()?,
+            QueryRequest {
+                text: "Can I use a USB drive?".to_string(),
+                actor: "user".to_string(),
+                action: "use".to_string(),
+                resource: "usb_drive".to_string(),
+                as_of: OffsetDateTime::now_utc(),
+            },
+            "txn_filtered_export",
+        )?;
+        source.save_context_package(&package)?;
 
-        let records = store.list_records()?;
-        let ids = records.iter().map(|record| record.memory_version_id).collect::<BTreeSet<_>>();
+        let filter = ExportFilter {
+            record_types: vec![RecordType::Constraint],
+            writers: vec!["alice".to_string()],
+            ..ExportFilter::default()
+        };
+        let export_dir = std::env::temp_dir().join(format!("memorykernel-export-{}", Ulid::new()));
+        let manifest = source.export_snapshot_filtered(&export_dir, &filter)?;
+        assert_eq!(manifest.filter.as_ref(), Some(&filter));
+        assert_eq!(manifest.namespace.as_deref(), Some("team-a"));
+        assert_eq!(manifest.files[0].records, 2);
+        assert_eq!(manifest.files[1].records, 0);
 
-        assert_eq!(records.len(), 2);
-        assert_eq!(ids.len(), 2);
+        let mut target = SqliteStore::open(Path::new(":memory:"))?;
+        let summary = target.import_snapshot(&export_dir, true)?;
+        assert_eq!(summary.imported_records, 2);
+        let imported = target
+            .list_records()?
+            .into_iter()
+            .map(|record| record.memory_version_id)
+            .collect::<BTreeSet<_>>();
+        assert_eq!(imported, BTreeSet::from([base.memory_version_id, successor.memory_version_id]));
+
+        let full = source.export_snapshot(&export_dir)?;
+        assert_eq!(full.filter, None);
+        assert_eq!(full.files[0].records, 3);
+        assert_eq!(full.files[1].records, 1);
+
+        fs::remove_dir_all(&export_dir).with_context(|| {
+            format!("failed to cleanup temp export dir {}", export_dir.display())
+        })?;
         Ok(())
     }
 
-    // Test IDs: TID-002
+    // Test IDs: TDB-022
     #[test]
-    fn duplicate_memory_id_version_is_rejected() -> Result<()> {
-        let mut store = SqliteStore::open(Path::new(":memory:"))?;
-        store.migrate()?;
-
-        let memory_id = MemoryId::new();
-        let first = mk_store_constraint_record(
-            memory_id,
+    fn delta_export_applies_on_top_of_previous_import() -> Result<()> {
+        let mut source = SqliteStore::open(Path::new(":memory:"))?;
+        source.migrate()?;
+        let base = mk_store_constraint_record(
+            MemoryId::new(),
             1,
             TruthStatus::Asserted,
             Some(0.9),
+            ConstraintEffect::Allow,
+        );
+        source.write_record(&base)?;
+
+        let full_dir = std::env::temp_dir().join(format!("memorykernel-export-{}", Ulid::new()));
+        let full = source.export_snapshot(&full_dir)?;
+        assert_eq!(full.since, None);
+        let Some(checkpoint) = full.checkpoint else {
+            return Err(anyhow!("full export should record a checkpoint"));
+        };
+        let mut target = SqliteStore::open(Path::new(":memory:"))?;
+        assert_eq!(target.import_snapshot(&full_dir, true)?.checkpoint, Some(checkpoint));
+
+        // Backdated successor: selected by write order, not by `created_at`.
+        let mut successor = mk_store_constraint_record(
+            base.memory_id,
+            2,
+            TruthStatus::Asserted,
+            Some(0.9),
             ConstraintEffect::Deny,
         );
-        let second = mk_store_constraint_record(
-            memory_id,
+        successor.created_at = base.created_at - time::Duration::days(1);
+        successor.supersedes = vec![base.memory_version_id];
+        source.write_record(&successor)?;
+
+        let delta_dir = std::env::temp_dir().join(format!("memorykernel-export-{}", Ulid::new()));
+        let delta = source.export_snapshot_since(&delta_dir, checkpoint)?;
+        assert_eq!(delta.since, Some(checkpoint));
+        assert_eq!(delta.files[0].records, 1);
+        assert_eq!(
+            delta.checkpoint.map(|checkpoint| checkpoint.records_write_seq),
+            Some(checkpoint.records_write_seq + 1)
+        );
+
+        let summary = target.import_snapshot(&delta_dir, true)?;
+        assert_eq!(summary.imported_records, 1);
+        assert_eq!(summary.checkpoint, delta.checkpoint);
+        assert_eq!(target.list_records()?.len(), 2);
+
+        let empty =
+            source.export_snapshot_since(&delta_dir, delta.checkpoint.unwrap_or_default())?;
+        assert_eq!(empty.files[0].records, 0);
+        assert_eq!(empty.checkpoint, delta.checkpoint);
+
+        for dir in [&full_dir, &delta_dir] {
+            fs::remove_dir_all(dir)
+                .with_context(|| format!("failed to cleanup temp export dir {}", dir.display()))?;
+        }
+        Ok(())
+    }
+
+    // Test IDs: TDB-023
+    #[test]
+    fn store_key_parses_hex_and_redacts_debug() -> Result<()> {
+        let key = StoreKey::from_hex(&format!("{}\n", "ab".repeat(32)))?;
+        assert_eq!(key, StoreKey::from_bytes([0xab; 32]));
+        assert_eq!(format!("{key:?}"), "StoreKey(..)");
+        assert!(StoreKey::from_hex("abcd").is_err());
+        assert!(StoreKey::from_hex(&"zz".repeat(32)).is_err());
+        Ok(())
+    }
+
+    // Test IDs: TDB-024
+    #[cfg(not(feature = "sqlcipher"))]
+    #[test]
+    fn keyed_open_requires_sqlcipher_feature() {
+        let key = StoreKey::from_bytes([7; 32]);
+        let Err(err) = SqliteStore::open_with_key(Path::new(":memory:"), Some(&key)) else {
+            panic!("keyed open should fail without the sqlcipher feature");
+        };
+        assert!(format!("{err:#}").contains("sqlcipher"));
+    }
+
+    // Test IDs: TDB-024
+    #[cfg(feature = "sqlcipher")]
+    #[test]
+    fn encrypt_to_writes_store_that_only_opens_with_its_key() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("memorykernel-sqlcipher-{}", Ulid::new()));
+        fs::create_dir_all(&dir)?;
+        let plaintext_path = dir.join("plain.sqlite3");
+        let encrypted_path = dir.join("encrypted.sqlite3");
+        let mut plaintext = SqliteStore::open(&plaintext_path)?;
+        plaintext.migrate()?;
+        let record = mk_store_constraint_record(
+            MemoryId::new(),
             1,
-            TruthStatus::Observed,
-            Some(0.95),
+            TruthStatus::Asserted,
+            Some(0.9),
             ConstraintEffect::Allow,
         );
+        plaintext.write_record(&record)?;
+
+        let key = StoreKey::from_bytes([7; 32]);
+        plaintext.encrypt_to(&encrypted_path, &key)?;
+        assert!(plaintext.encrypt_to(&encrypted_path, &key).is_err());
+        let encrypted = SqliteStore::open_with_key(&encrypted_path, Some(&key))?;
+        assert_eq!(encrypted.schema_status()?.pending_versions, Vec::<i64>::new());
+        assert_eq!(encrypted.list_records()?, vec![record]);
+
+        assert!(SqliteStore::open(&encrypted_path).is_err());
+        let wrong = StoreKey::from_bytes([8; 32]);
+        assert!(SqliteStore::open_with_key(&encrypted_path, Some(&wrong)).is_err());
+        assert!(SqliteStore::open_with_key(&plaintext_path, Some(&key)).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+        Ok(())
+    }
+
+    // Test IDs: TDB-025
+    #[test]
+    fn maintenance_truncates_wal_and_vacuums_into_copy() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("memorykernel-maintenance-{}", Ulid::new()));
+        fs::create_dir_all(&dir)?;
+        let mut store = SqliteStore::open(&dir.join("kernel.sqlite3"))?;
+        store.migrate()?;
+        for version in 1..=20 {
+            store.write_record(&mk_store_constraint_record(
+                MemoryId::new(),
+                version,
+                TruthStatus::Asserted,
+                Some(0.9),
+                ConstraintEffect::Allow,
+            ))?;
+        }
+
+        let vacuum_path = dir.join("compact.sqlite3");
+        let report = store.maintenance(Some(&vacuum_path))?;
+        assert!(report.wal_bytes_before > 0);
+        assert_eq!(report.wal_bytes_after, 0);
+        assert_eq!(report.reclaimed_bytes, report.wal_bytes_before);
+        assert!(!report.checkpoint_busy);
+        assert!(report.database_bytes > 0);
+        assert_eq!(report.vacuum_bytes, Some(fs::metadata(&vacuum_path)?.len()));
+        assert_eq!(SqliteStore::open(&vacuum_path)?.list_records()?.len(), 20);
+        assert!(store.maintenance(Some(&vacuum_path)).is_err());
+
+        let in_memory = SqliteStore::open(Path::new(":memory:"))?.maintenance(None)?;
+        assert_eq!((in_memory.wal_bytes_before, in_memory.vacuum_bytes), (0, None));
+
+        let _ = fs::remove_dir_all(&dir);
+        Ok(())
+    }
+
+    #[test]
+    fn orphaned_rows_and_dangling_links_name_missing_versions() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+        assert_eq!(store.latest_record_created_at()?, None);
+        let mut record = mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+        );
+        record.created_at = OffsetDateTime::parse(
+            "2030-01-01T00:00:00+02:00",
+            &time::format_description::well_known::Rfc3339,
+        )?;
+        store.write_record(&record)?;
+        assert_eq!(store.latest_record_created_at()?, Some(record.created_at));
+        assert!(store.orphaned_rows()?.is_empty());
+        assert!(store.dangling_links()?.is_empty());
+
+        let missing = MemoryVersionId::new().to_string();
+        store.conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
+        store.conn.execute(
+            "INSERT INTO decision_payloads(memory_version_id, summary) VALUES (?1, 'lost')",
+            [&missing],
+        )?;
+        insert_recall_terms_row(&store.conn, &missing, "lost")?;
+        store.conn.execute(
+            INSERT_LINK_SQL,
+            params![
+                record.memory_version_id.to_string(),
+                missing,
+                "supersedes",
+                "tester",
+                "fixture",
+                now_rfc3339()?
+            ],
+        )?;
 
-        store.write_record(&first)?;
-        let second_err = store.write_record(&second);
-        assert!(second_err.is_err());
-
+        let orphans = store.orphaned_rows()?;
+        assert_eq!(
+            orphans.iter().map(|orphan| orphan.table.as_str()).collect::<Vec<_>>(),
+            vec!["decision_payloads", "memory_recall_fts"]
+        );
+        assert!(orphans.iter().all(|orphan| orphan.memory_version_id == missing));
+        let dangling = store.dangling_links()?;
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].to_memory_version_id, missing);
+        assert_eq!(dangling[0].link_type, "supersedes");
         Ok(())
     }
 
-    // Test IDs: TDB-003
     #[test]
-    fn write_and_read_constraint_round_trip() -> Result<()> {
+    fn repair_integrity_dry_run_reports_and_repair_removes_unreachable_rows() -> Result<()> {
         let mut store = SqliteStore::open(Path::new(":memory:"))?;
         store.migrate()?;
-
-        let record = MemoryRecord {
-            memory_version_id: MemoryVersionId::new(),
-            memory_id: MemoryId::new(),
-            version: 1,
-            created_at: OffsetDateTime::now_utc(),
-            effective_at: OffsetDateTime::now_utc(),
-            truth_status: TruthStatus::Asserted,
-            authority: Authority::Authoritative,
-            confidence: Some(0.95),
-            writer: "tester".to_string(),
-            justification: "seed policy".to_string(),
-            provenance: Provenance {
-                source_uri: "file:///policy.md".to_string(),
-                source_hash: Some("sha256:abc123".to_string()),
-                evidence: vec![],
-            },
-            supersedes: vec![],
-            contradicts: vec![],
-            payload: MemoryPayload::Constraint(ConstraintPayload {
-                scope: ConstraintScope {
-                    actor: "user".to_string(),
-                    action: "use".to_string(),
-                    resource: "usb_drive".to_string(),
-                },
-                effect: ConstraintEffect::Deny,
-                note: None,
-            }),
-        };
-
+        let record = mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+        );
         store.write_record(&record)?;
-        let records = store.list_records()?;
-
-        assert_eq!(records.len(), 1);
-        assert_eq!(records[0].memory_id, record.memory_id);
+        let kept = record.memory_version_id.to_string();
+        let missing = MemoryVersionId::new().to_string();
 
-        let package = build_context_package(
-            &records,
-            QueryRequest {
-                text: "Am I allowed to use a USB drive?".to_string(),
-                actor: "user".to_string(),
-                action: "use".to_string(),
-                resource: "usb_drive".to_string(),
-                as_of: OffsetDateTime::now_utc(),
-            },
-            "txn_fixture",
+        store.conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
+        store.conn.execute(
+            "INSERT INTO decision_payloads(memory_version_id, summary) VALUES (?1, 'lost')",
+            [&missing],
+        )?;
+        store.conn.execute(
+            INSERT_LINK_SQL,
+            params![kept, missing, "supersedes", "tester", "fixture", now_rfc3339()?],
         )?;
+        for (context_package_id, memory_version_id) in
+            [("pkg-kept", &kept), ("pkg-stale", &missing)]
+        {
+            store.conn.execute(
+                "INSERT INTO context_packages(
+                    context_package_id, generated_at, package_json, namespace, write_seq
+                 ) VALUES (?1, ?2, ?3, 'default', 1)",
+                params![
+                    context_package_id,
+                    now_rfc3339()?,
+                    serde_json::json!({
+                        "selected_items": [{ "memory_version_id": kept }],
+                        "excluded_items": [{ "memory_version_id": memory_version_id }],
+                    })
+                    .to_string()
+                ],
+            )?;
+        }
 
-        assert_eq!(package.answer.result, memory_kernel_core::AnswerResult::Deny);
+        let preview = store.repair_integrity(true)?;
+        assert_eq!(preview.dangling_links.len(), 1);
+        assert_eq!(preview.orphaned_rows.len(), 1);
+        assert_eq!(
+            preview.stale_context_packages,
+            vec![StaleContextPackage {
+                context_package_id: "pkg-stale".to_string(),
+                missing_memory_version_ids: vec![missing.clone()],
+            }]
+        );
+        assert_eq!(preview.removed_links + preview.removed_rows, 0);
+        assert_eq!(store.stale_context_packages()?.len(), 1);
+
+        let repaired = store.repair_integrity(false)?;
+        assert_eq!(
+            (repaired.removed_links, repaired.removed_rows, repaired.removed_context_packages),
+            (1, 1, 1)
+        );
+        assert!(store.dangling_links()?.is_empty());
+        assert!(store.orphaned_rows()?.is_empty());
+        assert!(store.stale_context_packages()?.is_empty());
+        let remaining: i64 =
+            store.conn.query_row("SELECT COUNT(*) FROM context_packages", [], |row| row.get(0))?;
+        assert_eq!(remaining, 1);
+        assert_eq!(store.list_records()?.len(), 1);
         Ok(())
     }
 
-    // Test IDs: TID-003
+    // Test IDs: TDB-026
     #[test]
-    fn supersedes_links_round_trip_by_memory_version_id() -> Result<()> {
+    fn redact_scrubs_payload_keeps_lineage_and_replays_through_export() -> Result<()> {
         let mut store = SqliteStore::open(Path::new(":memory:"))?;
         store.migrate()?;
-
-        let entity_id = MemoryId::new();
-        let old = MemoryRecord {
-            memory_version_id: MemoryVersionId::new(),
-            memory_id: entity_id,
-            version: 1,
-            created_at: OffsetDateTime::now_utc(),
-            effective_at: OffsetDateTime::now_utc(),
-            truth_status: TruthStatus::Asserted,
-            authority: Authority::Authoritative,
-            confidence: Some(0.8),
-            writer: "tester".to_string(),
-            justification: "seed policy v1".to_string(),
-            provenance: Provenance {
-                source_uri: "file:///policy.md".to_string(),
-                source_hash: Some("sha256:abc123".to_string()),
-                evidence: vec![],
-            },
-            supersedes: vec![],
-            contradicts: vec![],
-            payload: MemoryPayload::Constraint(ConstraintPayload {
-                scope: ConstraintScope {
-                    actor: "user".to_string(),
-                    action: "use".to_string(),
-                    resource: "usb_drive".to_string(),
-                },
-                effect: ConstraintEffect::Deny,
-                note: None,
-            }),
-        };
-
-        let new = MemoryRecord {
-            memory_version_id: MemoryVersionId::new(),
-            memory_id: entity_id,
-            version: 2,
-            created_at: OffsetDateTime::now_utc(),
-            effective_at: OffsetDateTime::now_utc(),
-            truth_status: TruthStatus::Asserted,
-            authority: Authority::Authoritative,
-            confidence: Some(0.95),
-            writer: "tester".to_string(),
-            justification: "seed policy v2".to_string(),
-            provenance: Provenance {
-                source_uri: "file:///policy.md".to_string(),
-                source_hash: Some("sha256:def456".to_string()),
-                evidence: vec![],
-            },
-            supersedes: vec![old.memory_version_id],
-            contradicts: vec![],
-            payload: MemoryPayload::Constraint(ConstraintPayload {
-                scope: ConstraintScope {
-                    actor: "user".to_string(),
-                    action: "use".to_string(),
-                    resource: "usb_drive".to_string(),
-                },
-                effect: ConstraintEffect::Deny,
-                note: Some("new baseline".to_string()),
-            }),
-        };
-
-        store.write_record(&old)?;
-        store.write_record(&new)?;
+        let mut record = mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+        );
+        if let MemoryPayload::Constraint(payload) = &mut record.payload {
+            payload.note = Some("reported by alice@example.com".to_string());
+        }
+        let mut successor = mk_store_constraint_record(
+            record.memory_id,
+            2,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Allow,
+        );
+        successor.supersedes = vec![record.memory_version_id];
+        store.write_records_batch(&[record.clone(), successor.clone()])?;
+        let id = record.memory_version_id;
+        store.put_record_embeddings("model-a", &[(id, vec![1.0])])?;
+
+        let mut replica = SqliteStore::open(Path::new(":memory:"))?;
+        let base_dir = std::env::temp_dir().join(format!("memorykernel-redact-{}", Ulid::new()));
+        let base = store.export_snapshot(&base_dir.join("base"))?;
+        replica.import_snapshot(&base_dir.join("base"), false)?;
+
+        assert!(store.redact(id, " ", "erasure request").is_err());
+        assert!(store.redact(MemoryVersionId::new(), "privacy", "erasure request").is_err());
+        let redaction = store.redact(id, "privacy", "erasure request")?;
+        assert!(store.redact(id, "privacy", "erasure request").is_err());
+        assert_eq!(store.list_redactions()?, vec![redaction.clone()]);
 
         let records = store.list_records()?;
-        let Some(loaded_new) =
-            records.iter().find(|record| record.memory_version_id == new.memory_version_id)
-        else {
-            return Err(anyhow!(
-                "new record not found by memory_version_id {}",
-                new.memory_version_id
-            ));
+        let redacted = records.iter().find(|r| r.memory_version_id == id);
+        let Some(MemoryPayload::Constraint(payload)) = redacted.map(|r| &r.payload) else {
+            return Err(anyhow!("redacted constraint missing"));
         };
+        assert_eq!(payload.scope.actor, REDACTION_MARKER);
+        assert_eq!(payload.note.as_deref(), Some(REDACTION_MARKER));
+        assert_eq!(payload.effect, ConstraintEffect::Deny);
+        let kept = records.iter().find(|r| r.memory_version_id == successor.memory_version_id);
+        assert_eq!(kept.map(|r| r.supersedes.clone()), Some(vec![id]));
+        assert_eq!(kept.map(|r| r.payload.clone()), Some(successor.payload.clone()));
+        assert!(store.record_embeddings("model-a")?.is_empty());
+        let matches = store.recall_candidates("usb_drive", &[RecordType::Constraint], 10)?;
+        assert!(matches.iter().all(|r| r.memory_version_id != id));
+
+        let delta = store
+            .export_snapshot_since(&base_dir.join("delta"), base.checkpoint.unwrap_or_default())?;
+        assert_eq!(delta.files.iter().map(|f| f.records).collect::<Vec<_>>(), vec![0, 0, 1]);
+        let summary = replica.import_snapshot(&base_dir.join("delta"), false)?;
+        assert_eq!((summary.applied_redactions, summary.skipped_redactions), (1, 0));
+        assert_eq!(replica.list_redactions()?, vec![redaction]);
+        let replayed = replica.list_records()?;
+        assert_eq!(
+            replayed.iter().find(|r| r.memory_version_id == id).map(|r| r.payload.clone()),
+            redacted.map(|r| r.payload.clone())
+        );
+        let again = replica.import_snapshot(&base_dir.join("delta"), false)?;
+        assert_eq!((again.applied_redactions, again.skipped_redactions), (0, 1));
 
-        assert_eq!(loaded_new.supersedes, vec![old.memory_version_id]);
+        let _ = fs::remove_dir_all(&base_dir);
         Ok(())
     }
 
@@ -1771,7 +5900,7 @@ mod tests {
         store.migrate()?;
 
         let version = current_schema_version(&store.conn)?;
-        assert_eq!(version, 2);
+        assert_eq!(version, LATEST_SCHEMA_VERSION);
 
         let records = store.list_records()?;
         assert_eq!(records.len(), 2);
@@ -1820,8 +5949,8 @@ mod tests {
 
         let status = store.schema_status()?;
         assert_eq!(status.current_version, 1);
-        assert_eq!(status.target_version, 2);
-        assert_eq!(status.pending_versions, vec![2]);
+        assert_eq!(status.target_version, 14);
+        assert_eq!(status.pending_versions, vec![2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
         assert!(status.inferred_from_legacy);
 
         Ok(())
@@ -1851,6 +5980,7 @@ mod tests {
             },
             supersedes: vec![],
             contradicts: vec![],
+            tags: vec![],
             payload: MemoryPayload::Constraint(ConstraintPayload {
                 scope: ConstraintScope {
                     actor: "user".to_string(),
@@ -1859,6 +5989,7 @@ mod tests {
                 },
                 effect: ConstraintEffect::Deny,
                 note: None,
+                expires_at: None,
             }),
         };
 
@@ -1878,9 +6009,10 @@ mod tests {
 
         let export_dir = std::env::temp_dir().join(format!("memorykernel-export-{}", Ulid::new()));
         let manifest = source.export_snapshot(&export_dir)?;
-        assert_eq!(manifest.files.len(), 2);
+        assert_eq!(manifest.files.len(), 3);
         assert!(export_dir.join("memory_records.ndjson").exists());
         assert!(export_dir.join("context_packages.ndjson").exists());
+        assert!(export_dir.join("redactions.ndjson").exists());
         assert!(export_dir.join("manifest.json").exists());
 
         let mut target = SqliteStore::open(Path::new(":memory:"))?;
@@ -1963,6 +6095,7 @@ mod tests {
             },
             supersedes: vec![],
             contradicts: vec![],
+            tags: vec![],
             payload: MemoryPayload::Constraint(ConstraintPayload {
                 scope: ConstraintScope {
                     actor: "user".to_string(),
@@ -1971,6 +6104,7 @@ mod tests {
                 },
                 effect: ConstraintEffect::Deny,
                 note: Some("backup flow".to_string()),
+                expires_at: None,
             }),
         };
         source.write_record(&record)?;
@@ -2001,7 +6135,7 @@ mod tests {
         let report = store.integrity_check()?;
         assert!(report.quick_check_ok);
         assert!(report.foreign_key_violations.is_empty());
-        assert_eq!(report.schema_status.current_version, 2);
+        assert_eq!(report.schema_status.current_version, LATEST_SCHEMA_VERSION);
 
         Ok(())
     }
@@ -2047,6 +6181,7 @@ mod tests {
                         },
                         supersedes: vec![],
                         contradicts: vec![],
+                        tags: vec![],
                         payload: MemoryPayload::Constraint(ConstraintPayload {
                             scope: ConstraintScope {
                                 actor: "user".to_string(),
@@ -2055,6 +6190,7 @@ mod tests {
                             },
                             effect: ConstraintEffect::Deny,
                             note: Some("concurrency write".to_string()),
+                            expires_at: None,
                         }),
                     };
                     store.write_record(&record)?;
@@ -2103,4 +6239,344 @@ mod tests {
 
         Ok(())
     }
+
+    // Test IDs: TDB-027
+    #[test]
+    fn write_batch_commits_or_discards_writes_together() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("memorykernel-batch-{}", Ulid::new()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("kernel.sqlite3");
+        let mut store = SqliteStore::open(&path)?;
+        store.migrate()?;
+        let reader = SqliteStore::open(&path)?;
+        let record = || {
+            mk_store_constraint_record(
+                MemoryId::new(),
+                1,
+                TruthStatus::Asserted,
+                Some(0.9),
+                ConstraintEffect::Deny,
+            )
+        };
+
+        assert!(store.commit_batch().is_err());
+        store.begin_batch()?;
+        assert!(store.begin_batch().is_err());
+        let first = record();
+        store.write_record(&first)?;
+        assert!(store.write_record(&first).is_err());
+        let successor = store.write_successor(first.memory_version_id, |previous, version| {
+            let mut next = previous.clone();
+            next.memory_version_id = MemoryVersionId::new();
+            next.version = version;
+            next.supersedes = vec![previous.memory_version_id];
+            Ok(next)
+        })?;
+        assert_eq!(store.list_records()?.len(), 2);
+        assert!(reader.list_records()?.is_empty());
+        store.commit_batch()?;
+        let committed = reader.list_records()?;
+        assert_eq!(committed.len(), 2);
+        assert!(committed.iter().any(|r| r.memory_version_id == successor.memory_version_id));
+
+        store.begin_batch()?;
+        store.write_record(&record())?;
+        store.rollback_batch()?;
+        assert!(store.rollback_batch().is_err());
+        assert_eq!(reader.list_records()?.len(), 2);
+
+        drop((store, reader));
+        let _ = fs::remove_dir_all(&dir);
+        Ok(())
+    }
+
+    // Test IDs: TDB-028
+    #[test]
+    fn list_records_filtered_matches_source_uri_prefix_and_hash() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+
+        let sources = [
+            ("https://wiki.example/policy/usb", "sha256:aaa"),
+            ("https://wiki.example/policy/usb-v2", "sha256:bbb"),
+            ("https://wiki.example/policy0", "sha256:aaa"),
+            ("https://wiki.example/other", "sha256:ccc"),
+        ];
+        for (source_uri, source_hash) in sources {
+            let mut record = mk_store_constraint_record(
+                MemoryId::new(),
+                1,
+                TruthStatus::Asserted,
+                Some(0.8),
+                ConstraintEffect::Deny,
+            );
+            record.provenance.source_uri = source_uri.to_string();
+            record.provenance.source_hash = Some(source_hash.to_string());
+            store.write_record(&record)?;
+        }
+        let uris = |filter: RecordFilter| -> Result<Vec<String>> {
+            let mut uris = store
+                .list_records_filtered(&filter)?
+                .records
+                .into_iter()
+                .map(|record| record.provenance.source_uri)
+                .collect::<Vec<_>>();
+            uris.sort();
+            Ok(uris)
+        };
+
+        assert_eq!(
+            uris(RecordFilter {
+                source_uri_prefix: Some("https://wiki.example/policy/".to_string()),
+                ..RecordFilter::default()
+            })?,
+            vec!["https://wiki.example/policy/usb", "https://wiki.example/policy/usb-v2"]
+        );
+        assert_eq!(
+            uris(RecordFilter {
+                source_hash: Some("sha256:aaa".to_string()),
+                ..RecordFilter::default()
+            })?,
+            vec!["https://wiki.example/policy/usb", "https://wiki.example/policy0"]
+        );
+        assert_eq!(
+            uris(RecordFilter {
+                source_uri_prefix: Some("https://wiki.example/policy".to_string()),
+                source_hash: Some("sha256:aaa".to_string()),
+                ..RecordFilter::default()
+            })?,
+            vec!["https://wiki.example/policy/usb", "https://wiki.example/policy0"]
+        );
+        assert!(uris(RecordFilter {
+            source_uri_prefix: Some("https://wiki.example/policy/usb-v3".to_string()),
+            ..RecordFilter::default()
+        })?
+        .is_empty());
+
+        let indexes: i64 = store.conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index'
+               AND name IN ('idx_memory_records_source_uri', 'idx_memory_records_source_hash')",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(indexes, 2);
+        Ok(())
+    }
+
+    // Test IDs: TDB-029
+    #[test]
+    fn package_outcomes_link_and_follow_selected_records() -> Result<()> {
+        let mut store = SqliteStore::open(Path::new(":memory:"))?;
+        store.migrate()?;
+        let constraint = mk_store_constraint_record(
+            MemoryId::new(),
+            1,
+            TruthStatus::Asserted,
+            Some(0.9),
+            ConstraintEffect::Deny,
+        );
+        store.write_record(&constraint)?;
+        let mut outcomes = Vec::new();
+        for summary in ["USB incidents dropped", "Audit finding closed"] {
+            let mut outcome = mk_store_constraint_record(
+                MemoryId::new(),
+                1,
+                TruthStatus::Observed,
+                Some(0.8),
+                ConstraintEffect::Deny,
+            );
+            outcome.payload = MemoryPayload::Outcome(memory_kernel_core::OutcomePayload {
+                summary: summary.to_string(),
+            });
+            store.write_record(&outcome)?;
+            outcomes.push(outcome.memory_version_id);
+        }
+
+        let base = OffsetDateTime::now_utc();
+        for (id, resource, minutes) in [
+            ("ctx_late", "usb_drive", 10),
+            ("ctx_early", "usb_drive", 0),
+            ("ctx_other", "printer", 5),
+        ] {
+            let mut package = build_context_package(
+                &[constraint.clone()],
+                QueryRequest {
+                    text: format!("Can I use {resource}?"),
+                    actor: "user".to_string(),
+                    action: "use".to_string(),
+                    resource: resource.to_string(),
+                    as_of: base,
+                },
+                "snap_package_outcomes",
+            )?;
+            package.context_package_id = id.to_string();
+            package.generated_at = base + time::Duration::minutes(minutes);
+            store.save_context_package(&package)?;
+        }
+
+        let link = store.link_package_outcome("ctx_late", outcomes[0], "analyst", "observed")?;
+        assert_eq!(link.context_package_id, "ctx_late");
+        assert_eq!(link.outcome_memory_version_id, outcomes[0]);
+        store.link_package_outcome("ctx_early", outcomes[1], "analyst", "observed")?;
+        store.link_package_outcome("ctx_other", outcomes[1], "analyst", "observed")?;
+
+        let followed = store.outcomes_following(constraint.memory_version_id)?;
+        let pairs = followed
+            .iter()
+            .map(|item| (item.link.context_package_id.as_str(), item.outcome.memory_version_id))
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, vec![("ctx_early", outcomes[1]), ("ctx_late", outcomes[0])]);
+        assert_eq!(followed[0].generated_at, base);
+        assert!(matches!(followed[1].outcome.payload, MemoryPayload::Outcome(_)));
+        assert!(store.outcomes_following(outcomes[0])?.is_empty());
+
+        let Err(err) = store.link_package_outcome("ctx_late", outcomes[0], "analyst", "again")
+        else {
+            return Err(anyhow!("linking the same outcome twice must fail"));
+        };
+        assert!(err.to_string().contains("already linked"));
+        let Err(err) = store.link_package_outcome(
+            "ctx_late",
+            constraint.memory_version_id,
+            "analyst",
+            "observed",
+        ) else {
+            return Err(anyhow!("linking a constraint as an outcome must fail"));
+        };
+        assert!(err.to_string().contains("not an outcome"));
+        assert!(store
+            .link_package_outcome("ctx_missing", outcomes[0], "analyst", "observed")
+            .is_err());
+        assert!(store.link_package_outcome("ctx_late", outcomes[1], " ", "observed").is_err());
+
+        store.set_namespace("tenant-b")?;
+        assert!(store.outcomes_following(constraint.memory_version_id)?.is_empty());
+        Ok(())
+    }
+
+    /// Write every file of `export` into memory, keyed by manifest path, checking each one
+    /// against the length the export reported for its archive header.
+    fn snapshot_files(export: &SnapshotExport) -> Result<BTreeMap<String, Vec<u8>>> {
+        let mut files = BTreeMap::new();
+        for file in &export.manifest().files {
+            let mut body = Vec::new();
+            export.write_file(&file.path, &mut body)?;
+            assert_eq!(export.file_len(&file.path), Some(u64::try_from(body.len())?));
+            files.insert(file.path.clone(), body);
+        }
+        Ok(files)
+    }
+
+    // Test IDs: TDB-030
+    #[test]
+    fn import_rejects_snapshots_over_import_limits() -> Result<()> {
+        let mut source = SqliteStore::open(Path::new(":memory:"))?;
+        source.migrate()?;
+        for _ in 0..3 {
+            source.write_record(&mk_store_constraint_record(
+                MemoryId::new(),
+                1,
+                TruthStatus::Asserted,
+                Some(0.9),
+                ConstraintEffect::Deny,
+            ))?;
+        }
+        let snapshot = source.export_snapshot_stream(&ExportFilter::default(), None)?;
+        let manifest = snapshot.manifest().clone();
+        let files = snapshot_files(&snapshot)?;
+        let import_with = |limits: ImportLimits, manifest: &ExportManifest| {
+            let mut target = SqliteStore::open(Path::new(":memory:"))?;
+            target.set_import_limits(limits);
+            target.import_snapshot_in_memory(manifest, &files, true)
+        };
+        let defaults = ImportLimits::default();
+
+        let Err(err) = import_with(ImportLimits { max_line_bytes: 64, ..defaults }, &manifest)
+        else {
+            return Err(anyhow!("expected the line limit to reject the snapshot"));
+        };
+        assert!(err.to_string().contains("exceeds the 64 byte line limit"), "{err}");
+
+        let Err(err) = import_with(ImportLimits { max_depth: 1, ..defaults }, &manifest) else {
+            return Err(anyhow!("expected the depth limit to reject the snapshot"));
+        };
+        assert!(err.to_string().contains("nests deeper than 1 levels"), "{err}");
+
+        let Err(err) = import_with(ImportLimits { max_rows: 2, ..defaults }, &manifest) else {
+            return Err(anyhow!("expected the row limit to reject the manifest"));
+        };
+        assert!(err.to_string().contains("over the 2 row limit"), "{err}");
+
+        // A manifest understating its row count is caught while streaming, not after parsing.
+        let mut understated = manifest.clone();
+        for file in &mut understated.files {
+            file.records = file.records.min(2);
+        }
+        let Err(err) = import_with(ImportLimits { max_rows: 2, ..defaults }, &understated) else {
+            return Err(anyhow!("expected the row limit to reject the file"));
+        };
+        assert!(err.to_string().contains("exceeds the 2 row limit"), "{err}");
+
+        assert_eq!(import_with(defaults, &manifest)?.imported_records, 3);
+        Ok(())
+    }
+
+    // Test IDs: TDB-032
+    #[test]
+    fn snapshot_stream_rejects_unlisted_and_duplicate_files() -> Result<()> {
+        let mut source = SqliteStore::open(Path::new(":memory:"))?;
+        source.migrate()?;
+        let export = source.export_snapshot_stream(&ExportFilter::default(), None)?;
+        let manifest = export.manifest().clone();
+        let files = snapshot_files(&export)?;
+        let Err(err) = export.write_file("extra.ndjson", &mut Vec::new()) else {
+            return Err(anyhow!("expected an unlisted file to be refused on export"));
+        };
+        assert!(err.to_string().contains("not listed in its manifest"), "{err}");
+
+        let mut snapshot = SnapshotStream::new(manifest, ImportLimits::default())?;
+        let Err(err) = snapshot.read_file("extra.ndjson", b"{}\n".as_slice()) else {
+            return Err(anyhow!("expected an unlisted file to be rejected"));
+        };
+        assert!(err.to_string().contains("not listed in its manifest"), "{err}");
+
+        let records = files.get(EXPORT_RECORDS_FILE).map_or(&[][..], Vec::as_slice);
+        snapshot.read_file(EXPORT_RECORDS_FILE, records)?;
+        let Err(err) = snapshot.read_file(EXPORT_RECORDS_FILE, records) else {
+            return Err(anyhow!("expected a duplicate file to be rejected"));
+        };
+        assert!(err.to_string().contains("duplicate file"), "{err}");
+
+        let mut target = SqliteStore::open(Path::new(":memory:"))?;
+        let Err(err) = target.import_snapshot_stream(snapshot, false) else {
+            return Err(anyhow!("expected the unread packages file to be reported missing"));
+        };
+        assert!(err.to_string().contains("missing file"), "{err}");
+        Ok(())
+    }
+
+    // Test IDs: TDB-031
+    proptest! {
+        #[test]
+        fn property_ndjson_reader_never_yields_rows_over_limits(
+            corpus in proptest::collection::vec(any::<u8>(), 0..512),
+            depth in 0_usize..96,
+        ) {
+            let limits = ImportLimits { max_line_bytes: 40, max_rows: 4, max_depth: 8 };
+            let mut rows = 0_usize;
+            let mut longest = 0_usize;
+            let _ = for_each_ndjson_line(corpus.as_slice(), &"corpus", &limits, |_, line| {
+                rows += usize::from(!line.trim().is_empty());
+                longest = longest.max(line.len());
+                Ok(())
+            });
+            prop_assert!(rows <= limits.max_rows);
+            prop_assert!(longest <= limits.max_line_bytes);
+            // Arbitrary bytes MUST surface as errors, never as panics.
+            let _ = read_ndjson::<serde_json::Value>(corpus.as_slice(), &"corpus", &limits);
+
+            let nested = format!("{}\"[{{\"{}", "[".repeat(depth), "]".repeat(depth));
+            prop_assert_eq!(json_nesting_exceeds(nested.as_bytes(), 8), depth > 8);
+        }
+    }
 }
@@ -122,6 +122,7 @@ non_2xx_envelope_policy = {
         ],
         "optional": [
             "error.details",
+            "request_id",
         ],
         "forbids": [
             "legacy_error",
@@ -136,6 +137,7 @@ non_2xx_envelope_policy = {
         ],
         "optional": [
             "error.details",
+            "request_id",
         ],
         "forbids": [
             "legacy_error",
@@ -125,6 +125,11 @@ expected_codes = [
     "migration_failed",
     "query_failed",
     "context_lookup_failed",
+    "unauthorized",
+    "forbidden",
+    "payload_too_large",
+    "rate_limited",
+    "shutting_down",
     "internal_error",
 ]
 if data["error_code_enum"] != expected_codes:
@@ -171,6 +176,11 @@ for code in \
   migration_failed \
   query_failed \
   context_lookup_failed \
+  unauthorized \
+  forbidden \
+  payload_too_large \
+  rate_limited \
+  shutting_down \
   internal_error; do
   require_grep "\\- ${code}" "$openapi"
   require_grep "\"${code}\"" "$manifest"
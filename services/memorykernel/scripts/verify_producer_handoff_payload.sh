@@ -152,7 +152,7 @@ def assert_policy(payload, name):
             ["service_contract_version", "error.code", "error.message"]
         ):
             raise SystemExit(f"{name}: invalid {key}.requires")
-        if sorted(policy_entry.get("optional", [])) != ["error.details"]:
+        if sorted(policy_entry.get("optional", [])) != ["error.details", "request_id"]:
             raise SystemExit(f"{name}: invalid {key}.optional")
         if sorted(policy_entry.get("forbids", [])) != sorted(["legacy_error", "api_contract_version"]):
             raise SystemExit(f"{name}: invalid {key}.forbids")
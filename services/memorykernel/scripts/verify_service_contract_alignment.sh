@@ -109,6 +109,11 @@ for code in \
   migration_failed \
   query_failed \
   context_lookup_failed \
+  unauthorized \
+  forbidden \
+  payload_too_large \
+  rate_limited \
+  shutting_down \
   internal_error; do
   require_grep "\\- ${code}" "$openapi"
 done
@@ -0,0 +1,64 @@
+#!/usr/bin/env swift
+// Biometric Auth Helper for AssistSupport
+// Uses macOS LocalAuthentication (Touch ID) to gate release of the master key
+//
+// Build: swiftc biometric_auth.swift -o biometric_auth
+// Usage: biometric_auth check
+//        biometric_auth authenticate <reason>
+
+import Foundation
+import LocalAuthentication
+
+struct AuthOutput: Codable {
+    let success: Bool
+    let error: String?
+}
+
+func emit(_ output: AuthOutput) -> Never {
+    let encoder = JSONEncoder()
+    if let data = try? encoder.encode(output), let json = String(data: data, encoding: .utf8) {
+        print(json)
+    } else {
+        print("{\"success\":false,\"error\":\"Failed to encode output\"}")
+    }
+    exit(output.success ? 0 : 1)
+}
+
+func check() -> AuthOutput {
+    let context = LAContext()
+    var error: NSError?
+    let available = context.canEvaluatePolicy(.deviceOwnerAuthenticationWithBiometrics, error: &error)
+    return AuthOutput(success: available, error: error?.localizedDescription)
+}
+
+func authenticate(reason: String) -> AuthOutput {
+    let context = LAContext()
+    var error: NSError?
+    guard context.canEvaluatePolicy(.deviceOwnerAuthenticationWithBiometrics, error: &error) else {
+        return AuthOutput(success: false, error: error?.localizedDescription ?? "Biometrics unavailable")
+    }
+
+    let semaphore = DispatchSemaphore(value: 0)
+    var output = AuthOutput(success: false, error: "Authentication did not complete")
+    context.evaluatePolicy(.deviceOwnerAuthenticationWithBiometrics, localizedReason: reason) { success, evalError in
+        output = AuthOutput(success: success, error: evalError?.localizedDescription)
+        semaphore.signal()
+    }
+    semaphore.wait()
+    return output
+}
+
+let args = CommandLine.arguments
+guard args.count >= 2 else {
+    emit(AuthOutput(success: false, error: "Usage: biometric_auth check|authenticate <reason>"))
+}
+
+switch args[1] {
+case "check":
+    emit(check())
+case "authenticate":
+    let reason = args.count >= 3 ? args[2] : "unlock AssistSupport"
+    emit(authenticate(reason: reason))
+default:
+    emit(AuthOutput(success: false, error: "Unknown command: \(args[1])"))
+}
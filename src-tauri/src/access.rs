@@ -0,0 +1,164 @@
+//! Restricted namespace access
+//!
+//! Namespaces can be marked restricted. Reads that touch them (search,
+//! generation context, chunk retrieval, batch exports) are allowed only while
+//! restricted access is unlocked. An unlock expires after
+//! `RESTRICTED_UNLOCK_MINUTES` and is cleared when the app is locked. Every
+//! denial is audited.
+
+use crate::audit;
+use crate::db::{Database, DbError};
+use crate::kb::search::SearchResult;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeSet, HashSet};
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// How long an unlock lasts
+pub const RESTRICTED_UNLOCK_MINUTES: i64 = 15;
+
+static UNLOCKED_UNTIL: Mutex<Option<DateTime<Utc>>> = Mutex::new(None);
+
+#[derive(Debug, Error)]
+pub enum AccessError {
+    #[error("Namespace '{0}' is restricted; unlock restricted namespaces to access it")]
+    Restricted(String),
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+}
+
+/// Unlock restricted namespaces, returning when the unlock expires
+pub fn unlock() -> DateTime<Utc> {
+    let until = Utc::now() + Duration::minutes(RESTRICTED_UNLOCK_MINUTES);
+    if let Ok(mut unlocked) = UNLOCKED_UNTIL.lock() {
+        *unlocked = Some(until);
+    }
+    until
+}
+
+/// Lock restricted namespaces again
+pub fn lock() {
+    if let Ok(mut unlocked) = UNLOCKED_UNTIL.lock() {
+        *unlocked = None;
+    }
+}
+
+/// When the current unlock expires, or None while locked
+pub fn unlocked_until() -> Option<DateTime<Utc>> {
+    UNLOCKED_UNTIL
+        .lock()
+        .ok()
+        .and_then(|unlocked| *unlocked)
+        .filter(|until| *until > Utc::now())
+}
+
+pub fn is_unlocked() -> bool {
+    unlocked_until().is_some()
+}
+
+/// Allow `action` on a namespace only if it is unrestricted or access is
+/// unlocked
+pub fn check_namespace(db: &Database, namespace_id: &str, action: &str) -> Result<(), AccessError> {
+    if is_unlocked() || !db.restricted_namespace_ids()?.contains(namespace_id) {
+        return Ok(());
+    }
+    audit::audit_namespace_access_denied(namespace_id, action);
+    Err(AccessError::Restricted(namespace_id.to_string()))
+}
+
+/// Allow `action` on a document only if its namespace is accessible
+pub fn check_document(db: &Database, document_id: &str, action: &str) -> Result<(), AccessError> {
+    match db.document_namespace(document_id)? {
+        Some(namespace_id) => check_namespace(db, &namespace_id, action),
+        None => Ok(()),
+    }
+}
+
+/// Drop search results from restricted namespaces unless access is unlocked
+pub fn filter_results(
+    db: &Database,
+    results: Vec<SearchResult>,
+    action: &str,
+) -> Result<Vec<SearchResult>, AccessError> {
+    if is_unlocked() {
+        return Ok(results);
+    }
+    let restricted = db.restricted_namespace_ids()?;
+    if restricted.is_empty() {
+        return Ok(results);
+    }
+
+    let (allowed, denied) = retain_unrestricted(results, &restricted);
+    for namespace_id in denied {
+        audit::audit_namespace_access_denied(&namespace_id, action);
+    }
+    Ok(allowed)
+}
+
+/// Split off results in `restricted` namespaces, returning the kept results
+/// and the namespaces that had results removed
+fn retain_unrestricted(
+    results: Vec<SearchResult>,
+    restricted: &HashSet<String>,
+) -> (Vec<SearchResult>, BTreeSet<String>) {
+    let mut denied = BTreeSet::new();
+    let allowed = results
+        .into_iter()
+        .filter(|result| match &result.namespace_id {
+            Some(namespace_id) if restricted.contains(namespace_id) => {
+                denied.insert(namespace_id.clone());
+                false
+            }
+            _ => true,
+        })
+        .collect();
+    (allowed, denied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kb::search::SearchSource;
+
+    fn result(chunk_id: &str, namespace_id: Option<&str>) -> SearchResult {
+        SearchResult {
+            chunk_id: chunk_id.to_string(),
+            document_id: format!("doc-{}", chunk_id),
+            file_path: String::new(),
+            title: None,
+            heading_path: None,
+            content: String::new(),
+            snippet: String::new(),
+            score: 1.0,
+            source: SearchSource::Fts5,
+            namespace_id: namespace_id.map(|n| n.to_string()),
+            source_type: None,
+        }
+    }
+
+    #[test]
+    fn test_retain_unrestricted() {
+        let restricted: HashSet<String> = ["hr".to_string()].into_iter().collect();
+        let (allowed, denied) = retain_unrestricted(
+            vec![
+                result("1", Some("default")),
+                result("2", Some("hr")),
+                result("3", None),
+                result("4", Some("hr")),
+            ],
+            &restricted,
+        );
+        let ids: Vec<&str> = allowed.iter().map(|r| r.chunk_id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "3"]);
+        assert_eq!(denied.into_iter().collect::<Vec<_>>(), vec!["hr"]);
+    }
+
+    #[test]
+    fn test_unlock_and_lock() {
+        let until = unlock();
+        assert!(is_unlocked());
+        assert_eq!(unlocked_until(), Some(until));
+        lock();
+        assert!(!is_unlocked());
+    }
+}
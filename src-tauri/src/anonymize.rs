@@ -0,0 +1,366 @@
+//! Pseudonymization for shareable exports
+//!
+//! Replaces user names, email addresses, hostnames and ticket IDs with stable
+//! pseudonyms (`user-0001`, `user-0001@host-0002.example.invalid`,
+//! `host-0002.example.invalid`, `TICKET-0001`) so drafts, backups and audit
+//! logs can be shared with vendors or used for model evaluation.
+//!
+//! The original -> pseudonym mapping is kept in `anonymization_map.json` in the
+//! app data directory, so a value gets the same pseudonym in every export. The
+//! mapping holds the original values and is never included in an export.
+
+use crate::db::get_app_data_dir;
+use crate::security::{set_secure_permissions, FILE_PERMISSIONS};
+use once_cell::sync::Lazy;
+use regex_lite::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// Mapping file name in the app data directory
+pub const MAPPING_FILE_NAME: &str = "anonymization_map.json";
+
+/// Domain used for pseudonymized hosts (RFC 2606 reserved, never resolves)
+const PSEUDONYM_DOMAIN: &str = "example.invalid";
+
+const MAPPING_VERSION: u32 = 1;
+
+static EMAIL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"([A-Za-z0-9._%+-]+)@((?:[A-Za-z0-9-]+\.)+[A-Za-z]{2,24})\b").unwrap()
+});
+static HOME_PATH_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:/Users/|/home/|[A-Za-z]:\\Users\\)([A-Za-z0-9._-]+)").unwrap());
+static MENTION_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"@([A-Za-z0-9_][A-Za-z0-9_.-]*[A-Za-z0-9_]|[A-Za-z0-9_])").unwrap());
+static TICKET_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:[A-Z][A-Z0-9]{1,9}-[0-9]+|(?:INC|REQ|RITM|CHG|PRB|SCTASK|TASK)[0-9]{5,})\b")
+        .unwrap()
+});
+static HOST_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:[A-Za-z0-9](?:[A-Za-z0-9-]*[A-Za-z0-9])?\.)+[A-Za-z]{2,24}\b").unwrap()
+});
+
+/// Uppercase prefixes that look like ticket keys but are standards/algorithms
+const NON_TICKET_PREFIXES: &[&str] = &[
+    "AES", "CVE", "ECDSA", "HTTP", "ISO", "MD", "PKCS", "RFC", "RSA", "SHA", "SSL", "TICKET",
+    "TLS", "UTF", "WPA",
+];
+
+/// Trailing labels that mark a file name rather than a hostname
+const FILE_EXTENSIONS: &[&str] = &[
+    "app", "cfg", "conf", "csv", "dll", "dmg", "doc", "docx", "exe", "gif", "gz", "htm", "html",
+    "ini", "jpeg", "jpg", "js", "json", "jsx", "lock", "log", "md", "msi", "pdf", "pkg", "plist",
+    "png", "ppt", "pptx", "ps1", "py", "rs", "sh", "svg", "toml", "ts", "tsx", "txt", "xls",
+    "xlsx", "xml", "yaml", "yml", "zip",
+];
+
+/// Serializes load-modify-save cycles on the mapping file
+static MAPPING_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Error)]
+pub enum AnonymizeError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid anonymization mapping: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Kinds of values that get pseudonyms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PseudonymKind {
+    User,
+    Host,
+    Ticket,
+}
+
+/// Persisted original -> pseudonym tables
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PseudonymMap {
+    version: u32,
+    #[serde(default)]
+    users: BTreeMap<String, String>,
+    #[serde(default)]
+    hosts: BTreeMap<String, String>,
+    #[serde(default)]
+    tickets: BTreeMap<String, String>,
+}
+
+impl PseudonymMap {
+    fn table(&mut self, kind: PseudonymKind) -> &mut BTreeMap<String, String> {
+        match kind {
+            PseudonymKind::User => &mut self.users,
+            PseudonymKind::Host => &mut self.hosts,
+            PseudonymKind::Ticket => &mut self.tickets,
+        }
+    }
+}
+
+enum MatchKind {
+    Email { local: String, domain: String },
+    Value(PseudonymKind),
+}
+
+/// An identifying value found in text, in byte offsets of the source
+struct Match {
+    start: usize,
+    end: usize,
+    kind: MatchKind,
+}
+
+/// Replaces identifying values with consistent pseudonyms
+#[derive(Debug, Default)]
+pub struct Pseudonymizer {
+    map: PseudonymMap,
+    path: Option<PathBuf>,
+    changed: bool,
+}
+
+impl Pseudonymizer {
+    /// A pseudonymizer whose mapping is not persisted
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Load the mapping from `path` (empty if the file doesn't exist yet)
+    pub fn load(path: &Path) -> Result<Self, AnonymizeError> {
+        let map = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(path)?)?
+        } else {
+            PseudonymMap::default()
+        };
+        Ok(Self {
+            map,
+            path: Some(path.to_path_buf()),
+            changed: false,
+        })
+    }
+
+    /// Write new mappings back to the mapping file
+    pub fn save(&mut self) -> Result<(), AnonymizeError> {
+        let Some(path) = self.path.as_ref().filter(|_| self.changed) else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.map.version = MAPPING_VERSION;
+        fs::write(path, serde_json::to_string_pretty(&self.map)?)?;
+        set_secure_permissions(path, FILE_PERMISSIONS)?;
+        self.changed = false;
+        Ok(())
+    }
+
+    /// Get (or assign) the pseudonym for a value
+    pub fn pseudonym(&mut self, kind: PseudonymKind, original: &str) -> String {
+        let key = match kind {
+            PseudonymKind::Ticket => original.to_uppercase(),
+            _ => original.to_lowercase(),
+        };
+        let table = self.map.table(kind);
+        if let Some(existing) = table.get(&key) {
+            return existing.clone();
+        }
+        // Already a pseudonym (e.g. re-exporting anonymized data)
+        if table.values().any(|v| v.eq_ignore_ascii_case(original)) {
+            return original.to_string();
+        }
+
+        let n = table.len() + 1;
+        let pseudonym = match kind {
+            PseudonymKind::User => format!("user-{:04}", n),
+            PseudonymKind::Host => format!("host-{:04}.{}", n, PSEUDONYM_DOMAIN),
+            PseudonymKind::Ticket => format!("TICKET-{:04}", n),
+        };
+        table.insert(key, pseudonym.clone());
+        self.changed = true;
+        pseudonym
+    }
+
+    /// Pseudonymize emails, home-directory user names, @mentions, ticket IDs
+    /// and hostnames in free text
+    pub fn anonymize_text(&mut self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut cursor = 0;
+        for m in find_matches(text) {
+            let value = match &m.kind {
+                MatchKind::Email { local, domain } => format!(
+                    "{}@{}",
+                    self.pseudonym(PseudonymKind::User, local),
+                    self.pseudonym(PseudonymKind::Host, domain)
+                ),
+                MatchKind::Value(kind) => self.pseudonym(*kind, &text[m.start..m.end]),
+            };
+            out.push_str(&text[cursor..m.start]);
+            out.push_str(&value);
+            cursor = m.end;
+        }
+        out.push_str(&text[cursor..]);
+        out
+    }
+
+    /// Pseudonymize every string value in a JSON document (keys are kept)
+    pub fn anonymize_json(&mut self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(s) => *s = self.anonymize_text(s),
+            serde_json::Value::Array(items) => {
+                items.iter_mut().for_each(|item| self.anonymize_json(item))
+            }
+            serde_json::Value::Object(fields) => fields
+                .values_mut()
+                .for_each(|field| self.anonymize_json(field)),
+            _ => {}
+        }
+    }
+}
+
+/// Path of the persistent mapping file
+pub fn mapping_path() -> PathBuf {
+    get_app_data_dir().join(MAPPING_FILE_NAME)
+}
+
+/// Run `f` with the persistent mapping, saving any new pseudonyms afterwards
+pub fn with_mapping<T>(f: impl FnOnce(&mut Pseudonymizer) -> T) -> Result<T, AnonymizeError> {
+    let _guard = MAPPING_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut pseudonymizer = Pseudonymizer::load(&mapping_path())?;
+    let result = f(&mut pseudonymizer);
+    pseudonymizer.save()?;
+    Ok(result)
+}
+
+fn is_pseudonym_host(host: &str) -> bool {
+    host.to_ascii_lowercase()
+        .ends_with(&format!(".{}", PSEUDONYM_DOMAIN))
+}
+
+fn looks_like_host(candidate: &str) -> bool {
+    let tld = candidate.rsplit('.').next().unwrap_or_default();
+    !is_pseudonym_host(candidate) && !FILE_EXTENSIONS.contains(&tld.to_ascii_lowercase().as_str())
+}
+
+/// Find identifying values in text, in order and without overlaps. On
+/// overlap the earliest (then longest) match wins, so an email's domain isn't
+/// also matched as a hostname.
+fn find_matches(text: &str) -> Vec<Match> {
+    let mut matches = Vec::new();
+    let value = |start, end, kind| Match {
+        start,
+        end,
+        kind: MatchKind::Value(kind),
+    };
+
+    for caps in EMAIL_RE.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        if is_pseudonym_host(&caps[2]) {
+            continue;
+        }
+        matches.push(Match {
+            start: whole.start(),
+            end: whole.end(),
+            kind: MatchKind::Email {
+                local: caps[1].to_string(),
+                domain: caps[2].to_string(),
+            },
+        });
+    }
+    for caps in HOME_PATH_RE.captures_iter(text) {
+        let name = caps.get(1).unwrap();
+        matches.push(value(name.start(), name.end(), PseudonymKind::User));
+    }
+    for caps in MENTION_RE.captures_iter(text) {
+        let (whole, name) = (caps.get(0).unwrap(), caps.get(1).unwrap());
+        // Skip an `@` inside a word (emails are matched above)
+        let inside_word = text[..whole.start()]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '.' || c == '_');
+        if !inside_word {
+            matches.push(value(name.start(), name.end(), PseudonymKind::User));
+        }
+    }
+    for m in TICKET_RE.find_iter(text) {
+        let prefix = m.as_str().split('-').next().unwrap_or_default();
+        if !NON_TICKET_PREFIXES.contains(&prefix) {
+            matches.push(value(m.start(), m.end(), PseudonymKind::Ticket));
+        }
+    }
+    for m in HOST_RE.find_iter(text) {
+        if looks_like_host(m.as_str()) {
+            matches.push(value(m.start(), m.end(), PseudonymKind::Host));
+        }
+    }
+
+    matches.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+    let mut cursor = 0;
+    matches.retain(|m| {
+        let keep = m.start >= cursor;
+        if keep {
+            cursor = m.end;
+        }
+        keep
+    });
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anonymize_text() {
+        let mut p = Pseudonymizer::in_memory();
+        let text = "Ticket PROJ-42 from jane.doe@acme.com: VPN to vpn.acme.com fails \
+                    for @jdoe, see /Users/jdoe/notes.txt and INC0012345 (UTF-8, SHA-256)";
+        let out = p.anonymize_text(text);
+
+        assert_eq!(
+            out,
+            "Ticket TICKET-0001 from user-0001@host-0001.example.invalid: VPN to \
+             host-0002.example.invalid fails for @user-0002, see /Users/user-0002/notes.txt \
+             and TICKET-0002 (UTF-8, SHA-256)"
+        );
+        // Already-anonymized text is left alone
+        assert_eq!(p.anonymize_text(&out), out);
+    }
+
+    #[test]
+    fn test_mapping_is_consistent_across_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(MAPPING_FILE_NAME);
+
+        let mut first = Pseudonymizer::load(&path).unwrap();
+        let a = first.anonymize_text("Contact admin@corp.example.com about ABC-7");
+        first.save().unwrap();
+
+        let mut second = Pseudonymizer::load(&path).unwrap();
+        assert_eq!(
+            second.anonymize_text("ABC-7 / ADMIN@corp.example.com / ABC-8"),
+            "TICKET-0001 / user-0001@host-0001.example.invalid / TICKET-0002"
+        );
+        assert_eq!(
+            a,
+            "Contact user-0001@host-0001.example.invalid about TICKET-0001"
+        );
+    }
+
+    #[test]
+    fn test_anonymize_json() {
+        let mut p = Pseudonymizer::in_memory();
+        let mut value = serde_json::json!({
+            "ticket_id": "HELP-1",
+            "notes": ["ping db01.internal.corp", 3],
+            "reporter@acme.com": true,
+        });
+        p.anonymize_json(&mut value);
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "ticket_id": "TICKET-0001",
+                "notes": ["ping host-0001.example.invalid", 3],
+                "reporter@acme.com": true,
+            })
+        );
+    }
+}
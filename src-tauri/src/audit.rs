@@ -5,30 +5,58 @@
 //!
 //! Features:
 //! - Structured JSON format
-//! - Automatic log rotation (max 5MB per file, keep 5 files)
+//! - Configurable retention: the live log is gzip-archived once it reaches a
+//!   size limit, and archives are pruned by count and age
 //! - Never logs secrets (tokens, keys, passwords)
 //! - Thread-safe writes
 //! - In-memory ring buffer fallback for reliability
 //! - Critical event logging with guaranteed delivery
+//! - Tamper-evident hash chain: each entry records the previous entry's hash
+//!   and its own SHA-256, and the newest hash is kept in `audit.head` so
+//!   modification, deletion or truncation can be detected by `verify_audit_log`
 
+use crate::anonymize::Pseudonymizer;
 use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::VecDeque;
 use std::fs::{self, OpenOptions};
 use std::io::{BufRead, BufReader, BufWriter, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::time::Duration;
 
-/// Maximum size for a single log file (5MB)
-const MAX_LOG_SIZE: u64 = 5 * 1024 * 1024;
+/// Default size at which the live log is archived (5MB)
+const DEFAULT_MAX_LOG_SIZE: u64 = 5 * 1024 * 1024;
 
-/// Number of rotated log files to keep
-const MAX_LOG_FILES: usize = 5;
+/// Smallest allowed archive threshold
+const MIN_LOG_SIZE: u64 = 64 * 1024;
+
+/// Default number of compressed archives kept
+const DEFAULT_MAX_ARCHIVES: usize = 20;
+
+/// Default archive age limit
+const DEFAULT_RETENTION_DAYS: u32 = 365;
+
+/// Numbered rotation files (audit.log.1 ..) written by older versions,
+/// migrated into archives on init
+const LEGACY_ROTATED_FILES: usize = 5;
 
 /// Audit log file name
 const AUDIT_LOG_NAME: &str = "audit.log";
 
+/// Directory holding compressed archives
+const AUDIT_ARCHIVE_DIR: &str = "audit_archive";
+
+/// Retention settings file
+const AUDIT_RETENTION_NAME: &str = "audit_retention.json";
+
+/// File holding the hash of the newest chained entry
+const AUDIT_HEAD_NAME: &str = "audit.head";
+
 /// Maximum entries in the fallback ring buffer
 const RING_BUFFER_SIZE: usize = 100;
 
@@ -70,9 +98,13 @@ pub enum AuditEventType {
     KeyRotated,
     KeyStorageModeChanged,
 
-    // Token events
+    // Token events (TokenSet/TokenCleared predate the secrets vault and are
+    // kept so older logs still parse)
     TokenSet,
     TokenCleared,
+    SecretCreated,
+    SecretRotated,
+    SecretDeleted,
 
     // Jira events
     JiraConfigured,
@@ -84,6 +116,8 @@ pub enum AuditEventType {
     EncryptionFailed,
     DecryptionFailed,
     AuthenticationFailed,
+    NamespaceAccessDenied,
+    RestrictedAccessUnlocked,
 
     // App lifecycle
     AppInitialized,
@@ -103,6 +137,9 @@ impl std::fmt::Display for AuditEventType {
             Self::KeyStorageModeChanged => write!(f, "key_storage_mode_changed"),
             Self::TokenSet => write!(f, "token_set"),
             Self::TokenCleared => write!(f, "token_cleared"),
+            Self::SecretCreated => write!(f, "secret_created"),
+            Self::SecretRotated => write!(f, "secret_rotated"),
+            Self::SecretDeleted => write!(f, "secret_deleted"),
             Self::JiraConfigured => write!(f, "jira_configured"),
             Self::JiraHttpOptIn => write!(f, "jira_http_opt_in"),
             Self::JiraConnectionFailed => write!(f, "jira_connection_failed"),
@@ -110,6 +147,8 @@ impl std::fmt::Display for AuditEventType {
             Self::EncryptionFailed => write!(f, "encryption_failed"),
             Self::DecryptionFailed => write!(f, "decryption_failed"),
             Self::AuthenticationFailed => write!(f, "authentication_failed"),
+            Self::NamespaceAccessDenied => write!(f, "namespace_access_denied"),
+            Self::RestrictedAccessUnlocked => write!(f, "restricted_access_unlocked"),
             Self::AppInitialized => write!(f, "app_initialized"),
             Self::DatabaseRepaired => write!(f, "database_repaired"),
             Self::VectorStoreRebuilt => write!(f, "vector_store_rebuilt"),
@@ -118,6 +157,71 @@ impl std::fmt::Display for AuditEventType {
     }
 }
 
+impl AuditSeverity {
+    /// CEF severity (0-10)
+    pub fn cef_severity(&self) -> u8 {
+        match self {
+            Self::Info => 3,
+            Self::Warning => 5,
+            Self::Error => 7,
+            Self::Critical => 10,
+        }
+    }
+
+    /// Syslog severity level (RFC 5424)
+    pub fn syslog_level(&self) -> u8 {
+        match self {
+            Self::Info => 6,
+            Self::Warning => 4,
+            Self::Error => 3,
+            Self::Critical => 2,
+        }
+    }
+}
+
+impl AuditEventType {
+    /// Stable numeric ID for SIEM rules. IDs are never reused or renumbered.
+    pub fn event_id(&self) -> u32 {
+        match self {
+            Self::KeyGenerated => 1001,
+            Self::KeyMigrated => 1002,
+            Self::KeyRotated => 1003,
+            Self::KeyStorageModeChanged => 1004,
+            Self::TokenSet => 1101,
+            Self::TokenCleared => 1102,
+            Self::SecretCreated => 1103,
+            Self::SecretRotated => 1104,
+            Self::SecretDeleted => 1105,
+            Self::JiraConfigured => 1201,
+            Self::JiraHttpOptIn => 1202,
+            Self::JiraConnectionFailed => 1203,
+            Self::PathValidationFailed => 1301,
+            Self::EncryptionFailed => 1302,
+            Self::DecryptionFailed => 1303,
+            Self::AuthenticationFailed => 1304,
+            Self::NamespaceAccessDenied => 1305,
+            Self::RestrictedAccessUnlocked => 1306,
+            Self::AppInitialized => 1401,
+            Self::DatabaseRepaired => 1402,
+            Self::VectorStoreRebuilt => 1403,
+            Self::Custom(_) => 9000,
+        }
+    }
+}
+
+/// Audit export format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditExportFormat {
+    /// Pretty-printed JSON array of entries
+    #[default]
+    Json,
+    /// One flattened JSON object per line (Splunk HEC, Sentinel)
+    Jsonl,
+    /// ArcSight Common Event Format, one event per line
+    Cef,
+}
+
 /// Audit log entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditEntry {
@@ -136,6 +240,15 @@ pub struct AuditEntry {
     /// Additional context (no secrets)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<serde_json::Value>,
+
+    /// Hash of the previous entry in the chain (None for the first entry,
+    /// and for entries written before chaining)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
+
+    /// SHA-256 of this entry (with `hash` unset), set when written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
 }
 
 impl AuditEntry {
@@ -147,6 +260,8 @@ impl AuditEntry {
             severity,
             message: message.into(),
             context: None,
+            prev_hash: None,
+            hash: None,
         }
     }
 
@@ -155,6 +270,20 @@ impl AuditEntry {
         self.context = Some(context);
         self
     }
+
+    /// Stable ID of this entry: its chain hash, or the content hash for
+    /// entries written before chaining
+    pub fn entry_id(&self) -> String {
+        self.hash.clone().unwrap_or_else(|| self.compute_hash())
+    }
+
+    /// Hash of the entry's content, including `prev_hash` but not `hash`
+    pub fn compute_hash(&self) -> String {
+        let mut unhashed = self.clone();
+        unhashed.hash = None;
+        let json = serde_json::to_string(&unhashed).unwrap_or_default();
+        hex::encode(Sha256::digest(json.as_bytes()))
+    }
 }
 
 /// Global audit logger
@@ -180,9 +309,59 @@ pub fn set_error_callback(callback: Option<AuditErrorCallback>) {
     }
 }
 
+/// Audit log retention settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRetention {
+    /// Size at which the live log is compressed into an archive
+    pub max_file_bytes: u64,
+    /// Archives kept; the oldest are deleted beyond this
+    pub max_archives: usize,
+    /// Archives older than this are deleted (0 disables age-based pruning)
+    pub max_age_days: u32,
+}
+
+impl Default for AuditRetention {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: DEFAULT_MAX_LOG_SIZE,
+            max_archives: DEFAULT_MAX_ARCHIVES,
+            max_age_days: DEFAULT_RETENTION_DAYS,
+        }
+    }
+}
+
+impl AuditRetention {
+    fn validate(&self) -> Result<(), AuditError> {
+        if self.max_file_bytes < MIN_LOG_SIZE {
+            return Err(AuditError::InvalidRetention(format!(
+                "max_file_bytes must be at least {}",
+                MIN_LOG_SIZE
+            )));
+        }
+        if self.max_archives == 0 {
+            return Err(AuditError::InvalidRetention(
+                "max_archives must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A compressed audit log segment
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditArchive {
+    pub name: String,
+    pub size_bytes: u64,
+    /// When the segment was archived
+    pub archived_at: DateTime<Utc>,
+}
+
 /// Audit logger
 pub struct AuditLogger {
     log_dir: PathBuf,
+    /// Hash of the last entry written, continued by the next write
+    last_hash: Option<String>,
+    retention: AuditRetention,
 }
 
 impl AuditLogger {
@@ -195,7 +374,17 @@ impl AuditLogger {
         // Create directory with secure permissions (0o700)
         crate::security::create_secure_dir(&log_dir).map_err(|e| AuditError::IO(e.to_string()))?;
 
-        let logger = Self { log_dir };
+        let last_hash = read_head(&log_dir);
+        let retention = read_retention(&log_dir);
+        let logger = Self {
+            log_dir,
+            last_hash,
+            retention,
+        };
+        logger.migrate_legacy_rotations();
+        if let Err(e) = logger.prune_archives() {
+            eprintln!("[audit] Warning: failed to prune archives: {}", e);
+        }
 
         let mut guard = AUDIT_LOGGER.lock().map_err(|_| AuditError::LockFailed)?;
         *guard = Some(logger);
@@ -208,12 +397,12 @@ impl AuditLogger {
         self.log_dir.join(AUDIT_LOG_NAME)
     }
 
-    /// Get rotated log file path
+    /// Get legacy numbered rotation file path
     fn rotated_path(&self, index: usize) -> PathBuf {
         self.log_dir.join(format!("{}.{}", AUDIT_LOG_NAME, index))
     }
 
-    /// Rotate logs if needed
+    /// Archive the live log once it reaches the retention size limit
     fn rotate_if_needed(&self) -> Result<(), AuditError> {
         let log_path = self.log_path();
 
@@ -223,39 +412,92 @@ impl AuditLogger {
 
         let metadata = fs::metadata(&log_path).map_err(|e| AuditError::IO(e.to_string()))?;
 
-        if metadata.len() < MAX_LOG_SIZE {
+        if metadata.len() < self.retention.max_file_bytes {
             return Ok(());
         }
 
-        // Rotate existing files
-        for i in (0..MAX_LOG_FILES - 1).rev() {
-            let from = if i == 0 {
-                self.log_path()
-            } else {
-                self.rotated_path(i)
-            };
-            let to = self.rotated_path(i + 1);
+        self.archive_file(&log_path, Utc::now())?;
+        if let Err(e) = self.prune_archives() {
+            eprintln!("[audit] Warning: failed to prune archives: {}", e);
+        }
 
-            if from.exists() {
-                if let Err(e) = fs::rename(&from, &to) {
-                    eprintln!("[audit] Warning: log rotation rename failed: {}", e);
-                }
-            }
+        Ok(())
+    }
+
+    /// Compress `path` into the archive directory and remove it
+    fn archive_file(&self, path: &Path, archived_at: DateTime<Utc>) -> Result<PathBuf, AuditError> {
+        let archive_dir = self.log_dir.join(AUDIT_ARCHIVE_DIR);
+        crate::security::create_secure_dir(&archive_dir)
+            .map_err(|e| AuditError::IO(e.to_string()))?;
+
+        // Names sort chronologically; nudge forward on collision
+        let mut archived_at = archived_at;
+        let mut archive_path = archive_dir.join(archive_name(archived_at));
+        while archive_path.exists() {
+            archived_at += chrono::Duration::milliseconds(1);
+            archive_path = archive_dir.join(archive_name(archived_at));
         }
 
-        // Delete oldest if it exists
-        let oldest = self.rotated_path(MAX_LOG_FILES);
-        if oldest.exists() {
-            if let Err(e) = fs::remove_file(&oldest) {
-                eprintln!("[audit] Warning: failed to remove oldest log file: {}", e);
+        let mut source = fs::File::open(path).map_err(|e| AuditError::IO(e.to_string()))?;
+        let target = fs::File::create(&archive_path).map_err(|e| AuditError::IO(e.to_string()))?;
+        let _ = crate::security::set_secure_permissions(
+            &archive_path,
+            crate::security::FILE_PERMISSIONS,
+        );
+        let mut encoder = GzEncoder::new(target, Compression::default());
+        std::io::copy(&mut source, &mut encoder).map_err(|e| AuditError::IO(e.to_string()))?;
+        encoder
+            .finish()
+            .and_then(|file| file.sync_all())
+            .map_err(|e| AuditError::IO(e.to_string()))?;
+
+        fs::remove_file(path).map_err(|e| AuditError::IO(e.to_string()))?;
+        Ok(archive_path)
+    }
+
+    /// Move numbered rotation files from older versions into archives
+    fn migrate_legacy_rotations(&self) {
+        for index in (1..=LEGACY_ROTATED_FILES).rev() {
+            let path = self.rotated_path(index);
+            if !path.exists() {
+                continue;
+            }
+            let modified = fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .map(DateTime::<Utc>::from)
+                .unwrap_or_else(|_| Utc::now());
+            if let Err(e) = self.archive_file(&path, modified) {
+                eprintln!(
+                    "[audit] Warning: failed to archive {}: {}",
+                    path.display(),
+                    e
+                );
             }
         }
+    }
 
-        Ok(())
+    /// Delete archives beyond the retention count or age. Returns the number
+    /// deleted.
+    fn prune_archives(&self) -> Result<usize, AuditError> {
+        let archives = list_archives_in(&self.log_dir)?;
+        let excess = archives.len().saturating_sub(self.retention.max_archives);
+        let cutoff = (self.retention.max_age_days > 0)
+            .then(|| Utc::now() - chrono::Duration::days(self.retention.max_age_days as i64));
+
+        let mut deleted = 0;
+        for (index, archive) in archives.iter().enumerate() {
+            let expired = cutoff.is_some_and(|cutoff| archive.archived_at < cutoff);
+            if index < excess || expired {
+                let path = self.log_dir.join(AUDIT_ARCHIVE_DIR).join(&archive.name);
+                fs::remove_file(&path).map_err(|e| AuditError::IO(e.to_string()))?;
+                deleted += 1;
+            }
+        }
+        Ok(deleted)
     }
 
-    /// Write an audit entry
-    fn write(&self, entry: &AuditEntry) -> Result<(), AuditError> {
+    /// Write an audit entry, chaining it to the previous one
+    fn write(&mut self, entry: &AuditEntry) -> Result<(), AuditError> {
         self.rotate_if_needed()?;
 
         let log_path = self.log_path();
@@ -275,18 +517,39 @@ impl AuditLogger {
             );
         }
 
+        let mut chained = entry.clone();
+        chained.prev_hash = self.last_hash.clone();
+        let hash = chained.compute_hash();
+        chained.hash = Some(hash.clone());
+
         let mut writer = BufWriter::new(file);
-        let line =
-            serde_json::to_string(entry).map_err(|e| AuditError::Serialization(e.to_string()))?;
+        let line = serde_json::to_string(&chained)
+            .map_err(|e| AuditError::Serialization(e.to_string()))?;
 
         writeln!(writer, "{}", line).map_err(|e| AuditError::IO(e.to_string()))?;
         writer.flush().map_err(|e| AuditError::IO(e.to_string()))?;
 
+        self.write_head(&hash)?;
+        self.last_hash = Some(hash);
+        Ok(())
+    }
+
+    /// Record the newest hash so truncation of the log can be detected
+    fn write_head(&self, hash: &str) -> Result<(), AuditError> {
+        let head_path = self.log_dir.join(AUDIT_HEAD_NAME);
+        let is_new = !head_path.exists();
+        fs::write(&head_path, hash).map_err(|e| AuditError::IO(e.to_string()))?;
+        if is_new {
+            let _ = crate::security::set_secure_permissions(
+                &head_path,
+                crate::security::FILE_PERMISSIONS,
+            );
+        }
         Ok(())
     }
 
     /// Attempt to flush buffered entries to disk
-    fn flush_buffer(&self) -> usize {
+    fn flush_buffer(&mut self) -> usize {
         let mut flushed = 0;
 
         // Get entries from buffer
@@ -363,6 +626,10 @@ pub enum AuditError {
     LockFailed,
     #[error("Logger not initialized")]
     NotInitialized,
+    #[error("Invalid retention settings: {0}")]
+    InvalidRetention(String),
+    #[error("Audit archive not found: {0}")]
+    ArchiveNotFound(String),
 }
 
 fn audit_log_dir() -> Result<PathBuf, AuditError> {
@@ -371,14 +638,94 @@ fn audit_log_dir() -> Result<PathBuf, AuditError> {
         .ok_or(AuditError::LogDirNotFound)
 }
 
+fn read_head(log_dir: &Path) -> Option<String> {
+    fs::read_to_string(log_dir.join(AUDIT_HEAD_NAME))
+        .ok()
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+}
+
+fn read_retention(log_dir: &Path) -> AuditRetention {
+    fs::read_to_string(log_dir.join(AUDIT_RETENTION_NAME))
+        .ok()
+        .and_then(|json| serde_json::from_str::<AuditRetention>(&json).ok())
+        .filter(|retention| retention.validate().is_ok())
+        .unwrap_or_default()
+}
+
+fn archive_name(archived_at: DateTime<Utc>) -> String {
+    format!("audit-{}.log.gz", archived_at.format("%Y%m%dT%H%M%S%.3fZ"))
+}
+
+/// Archives in `log_dir`, oldest first
+fn list_archives_in(log_dir: &Path) -> Result<Vec<AuditArchive>, AuditError> {
+    let archive_dir = log_dir.join(AUDIT_ARCHIVE_DIR);
+    if !archive_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut archives = Vec::new();
+    for entry in fs::read_dir(&archive_dir).map_err(|e| AuditError::IO(e.to_string()))? {
+        let entry = entry.map_err(|e| AuditError::IO(e.to_string()))?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(stamp) = name
+            .strip_prefix("audit-")
+            .and_then(|rest| rest.strip_suffix(".log.gz"))
+        else {
+            continue;
+        };
+        let Ok(archived_at) = chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%dT%H%M%S%.3fZ")
+        else {
+            continue;
+        };
+        let size_bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        archives.push(AuditArchive {
+            name,
+            size_bytes,
+            archived_at: archived_at.and_utc(),
+        });
+    }
+
+    archives.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(archives)
+}
+
+/// Open a log segment, decompressing archives
+fn open_segment(path: &Path) -> Result<Box<dyn BufRead>, AuditError> {
+    let file = fs::File::open(path).map_err(|e| AuditError::IO(e.to_string()))?;
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        Ok(Box::new(BufReader::new(GzDecoder::new(file))))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Parse the entries in a log segment, skipping unreadable lines
+fn read_segment_entries(path: &Path) -> Result<Vec<AuditEntry>, AuditError> {
+    let mut entries = Vec::new();
+    for line in open_segment(path)?.lines() {
+        let line = line.map_err(|e| AuditError::IO(e.to_string()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
 fn list_audit_log_files() -> Result<Vec<PathBuf>, AuditError> {
-    let log_dir = audit_log_dir()?;
+    list_audit_log_files_in(&audit_log_dir()?)
+}
+
+fn list_audit_log_files_in(log_dir: &Path) -> Result<Vec<PathBuf>, AuditError> {
     if !log_dir.exists() {
         return Ok(Vec::new());
     }
 
     let mut files: Vec<(usize, PathBuf)> = Vec::new();
-    for entry in fs::read_dir(&log_dir).map_err(|e| AuditError::IO(e.to_string()))? {
+    for entry in fs::read_dir(log_dir).map_err(|e| AuditError::IO(e.to_string()))? {
         let entry = entry.map_err(|e| AuditError::IO(e.to_string()))?;
         let name = entry.file_name();
         let name = name.to_string_lossy();
@@ -391,9 +738,26 @@ fn list_audit_log_files() -> Result<Vec<PathBuf>, AuditError> {
         }
     }
 
-    // Oldest first: highest index, then current log (index 0) last.
-    files.sort_by(|a, b| b.0.cmp(&a.0));
-    Ok(files.into_iter().map(|(_, path)| path).collect())
+    // Oldest first: legacy numbered files (highest index first), then
+    // archives, then the current log (index 0) last.
+    files.sort_by_key(|(index, _)| std::cmp::Reverse(*index));
+    let current = files
+        .last()
+        .filter(|(index, _)| *index == 0)
+        .map(|(_, path)| path.clone());
+    let mut paths: Vec<PathBuf> = files
+        .into_iter()
+        .filter(|(index, _)| *index > 0)
+        .map(|(_, path)| path)
+        .collect();
+    let archive_dir = log_dir.join(AUDIT_ARCHIVE_DIR);
+    paths.extend(
+        list_archives_in(log_dir)?
+            .into_iter()
+            .map(|archive| archive_dir.join(archive.name)),
+    );
+    paths.extend(current);
+    Ok(paths)
 }
 
 /// Read audit entries from log files, optionally limiting results to the most recent entries.
@@ -402,35 +766,295 @@ pub fn read_audit_entries(limit: Option<usize>) -> Result<Vec<AuditEntry>, Audit
     let mut entries = Vec::new();
 
     for path in files {
-        let file = OpenOptions::new()
-            .read(true)
-            .open(&path)
-            .map_err(|e| AuditError::IO(e.to_string()))?;
-        let reader = BufReader::new(file);
-        for line in reader.lines() {
+        entries.extend(read_segment_entries(&path)?);
+    }
+
+    if let Some(limit) = limit {
+        if entries.len() > limit {
+            entries = entries[entries.len() - limit..].to_vec();
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Kind of problem found while verifying the audit chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditChainIssueKind {
+    /// Line is not a valid entry
+    Unparseable,
+    /// Entry content doesn't match its hash
+    Modified,
+    /// Entry doesn't follow the previous one (entries deleted or reordered)
+    BrokenLink,
+    /// Entry without a hash after chaining began (inserted)
+    Unchained,
+    /// Newest entry doesn't match the recorded head (log truncated)
+    Truncated,
+}
+
+/// A problem found while verifying the audit chain
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditChainIssue {
+    pub kind: AuditChainIssueKind,
+    /// Log file name
+    pub file: String,
+    /// 1-based line number (0 for issues not tied to a line)
+    pub line: usize,
+    pub message: String,
+}
+
+/// Result of verifying the audit log chain
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditVerification {
+    pub valid: bool,
+    /// Chained entries checked
+    pub entries_checked: usize,
+    /// Entries written before chaining was introduced
+    pub legacy_entries: usize,
+    pub issues: Vec<AuditChainIssue>,
+}
+
+/// Verify the hash chain across all audit log files
+pub fn verify_audit_log() -> Result<AuditVerification, AuditError> {
+    let log_dir = audit_log_dir()?;
+    verify_audit_files(&list_audit_log_files_in(&log_dir)?, read_head(&log_dir))
+}
+
+/// Verify the hash chain across `files` (oldest first) against the recorded
+/// head hash. The first chained entry may point at an entry rotated away.
+fn verify_audit_files(
+    files: &[PathBuf],
+    head: Option<String>,
+) -> Result<AuditVerification, AuditError> {
+    let mut issues = Vec::new();
+    let mut entries_checked = 0;
+    let mut legacy_entries = 0;
+    let mut last_hash: Option<String> = None;
+    let mut last_location = (String::new(), 0);
+
+    for path in files {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        for (index, line) in open_segment(path)?.lines().enumerate() {
             let line = line.map_err(|e| AuditError::IO(e.to_string()))?;
             if line.trim().is_empty() {
                 continue;
             }
-            if let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) {
-                entries.push(entry);
+            let mut issue = |kind, message: String| {
+                issues.push(AuditChainIssue {
+                    kind,
+                    file: file_name.clone(),
+                    line: index + 1,
+                    message,
+                })
+            };
+
+            let entry = match serde_json::from_str::<AuditEntry>(&line) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    issue(AuditChainIssueKind::Unparseable, e.to_string());
+                    continue;
+                }
+            };
+            let Some(hash) = entry.hash.clone() else {
+                if last_hash.is_some() {
+                    issue(
+                        AuditChainIssueKind::Unchained,
+                        "Entry has no hash after chaining began".to_string(),
+                    );
+                } else {
+                    legacy_entries += 1;
+                }
+                continue;
+            };
+
+            entries_checked += 1;
+            if entry.compute_hash() != hash {
+                issue(
+                    AuditChainIssueKind::Modified,
+                    "Entry content does not match its hash".to_string(),
+                );
+            }
+            if last_hash.is_some() && entry.prev_hash != last_hash {
+                issue(
+                    AuditChainIssueKind::BrokenLink,
+                    "Entry does not follow the previous entry".to_string(),
+                );
             }
+            last_hash = Some(hash);
+            last_location = (file_name.clone(), index + 1);
         }
     }
 
-    if let Some(limit) = limit {
-        if entries.len() > limit {
-            entries = entries[entries.len() - limit..].to_vec();
+    if let Some(head) = head {
+        if last_hash.as_deref() != Some(head.as_str()) {
+            issues.push(AuditChainIssue {
+                kind: AuditChainIssueKind::Truncated,
+                file: last_location.0,
+                line: last_location.1,
+                message: "Newest entry does not match the recorded head".to_string(),
+            });
         }
     }
 
-    Ok(entries)
+    Ok(AuditVerification {
+        valid: issues.is_empty(),
+        entries_checked,
+        legacy_entries,
+        issues,
+    })
+}
+
+/// Format entries for export
+pub fn format_audit_entries(
+    entries: &[AuditEntry],
+    format: AuditExportFormat,
+) -> Result<String, AuditError> {
+    match format {
+        AuditExportFormat::Json => serde_json::to_string_pretty(entries)
+            .map_err(|e| AuditError::Serialization(e.to_string())),
+        AuditExportFormat::Jsonl => entries
+            .iter()
+            .map(|entry| {
+                serde_json::to_string(&siem_record(entry))
+                    .map(|line| line + "\n")
+                    .map_err(|e| AuditError::Serialization(e.to_string()))
+            })
+            .collect(),
+        AuditExportFormat::Cef => Ok(entries.iter().map(|e| cef_line(e) + "\n").collect()),
+    }
+}
+
+/// Pseudonymize entries for sharing. The hash chain is dropped: the hashes
+/// cover the original text and would let a recipient confirm guessed values.
+pub fn anonymize_entries(
+    entries: &[AuditEntry],
+    pseudonymizer: &mut Pseudonymizer,
+) -> Vec<AuditEntry> {
+    entries
+        .iter()
+        .map(|entry| {
+            let mut entry = entry.clone();
+            entry.message = pseudonymizer.anonymize_text(&entry.message);
+            if let Some(context) = entry.context.as_mut() {
+                pseudonymizer.anonymize_json(context);
+            }
+            if let AuditEventType::Custom(name) = &mut entry.event {
+                *name = pseudonymizer.anonymize_text(name);
+            }
+            entry.prev_hash = None;
+            entry.hash = None;
+            entry
+        })
+        .collect()
+}
+
+/// Flattened record with the fields SIEMs index on
+fn siem_record(entry: &AuditEntry) -> serde_json::Value {
+    serde_json::json!({
+        "timestamp": entry.timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        "vendor": "AssistSupport",
+        "product": "AssistSupport",
+        "product_version": env!("CARGO_PKG_VERSION"),
+        "entry_id": entry.entry_id(),
+        "event_id": entry.event.event_id(),
+        "event": entry.event.to_string(),
+        "severity": entry.severity.to_string(),
+        "severity_level": entry.severity.syslog_level(),
+        "message": entry.message,
+        "context": entry.context,
+    })
+}
+
+fn cef_line(entry: &AuditEntry) -> String {
+    let mut extension = format!(
+        "rt={} msg={} cs1Label=entryId cs1={}",
+        entry.timestamp.timestamp_millis(),
+        cef_escape_extension(&entry.message),
+        entry.entry_id(),
+    );
+    if let Some(context) = &entry.context {
+        extension.push_str(&format!(
+            " cs2Label=context cs2={}",
+            cef_escape_extension(&context.to_string())
+        ));
+    }
+    format!(
+        "CEF:0|AssistSupport|AssistSupport|{}|{}|{}|{}|{}",
+        cef_escape_header(env!("CARGO_PKG_VERSION")),
+        entry.event.event_id(),
+        cef_escape_header(&entry.event.to_string()),
+        entry.severity.cef_severity(),
+        extension
+    )
+}
+
+/// Escape a CEF header field (backslash and pipe)
+fn cef_escape_header(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Escape a CEF extension value (backslash, equals and newlines)
+fn cef_escape_extension(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Current retention settings
+pub fn get_audit_retention() -> Result<AuditRetention, AuditError> {
+    Ok(read_retention(&audit_log_dir()?))
+}
+
+/// Save retention settings and apply them immediately
+pub fn set_audit_retention(retention: AuditRetention) -> Result<(), AuditError> {
+    retention.validate()?;
+    let log_dir = audit_log_dir()?;
+    crate::security::create_secure_dir(&log_dir).map_err(|e| AuditError::IO(e.to_string()))?;
+    let json = serde_json::to_string_pretty(&retention)
+        .map_err(|e| AuditError::Serialization(e.to_string()))?;
+    fs::write(log_dir.join(AUDIT_RETENTION_NAME), json)
+        .map_err(|e| AuditError::IO(e.to_string()))?;
+
+    let mut guard = AUDIT_LOGGER.lock().map_err(|_| AuditError::LockFailed)?;
+    if let Some(logger) = guard.as_mut() {
+        logger.retention = retention;
+        logger.rotate_if_needed()?;
+        logger.prune_archives()?;
+    }
+    Ok(())
+}
+
+/// Archived segments, oldest first
+pub fn list_audit_archives() -> Result<Vec<AuditArchive>, AuditError> {
+    list_archives_in(&audit_log_dir()?)
+}
+
+/// Entries of one archived segment
+pub fn read_archive_entries(name: &str) -> Result<Vec<AuditEntry>, AuditError> {
+    let log_dir = audit_log_dir()?;
+    // Only names of listed archives are accepted
+    let archive = list_archives_in(&log_dir)?
+        .into_iter()
+        .find(|archive| archive.name == name)
+        .ok_or_else(|| AuditError::ArchiveNotFound(name.to_string()))?;
+    read_segment_entries(&log_dir.join(AUDIT_ARCHIVE_DIR).join(archive.name))
 }
 
 /// Log an audit event (synchronous, returns errors)
 pub fn log_audit(entry: AuditEntry) -> Result<(), AuditError> {
-    let guard = AUDIT_LOGGER.lock().map_err(|_| AuditError::LockFailed)?;
-    let logger = guard.as_ref().ok_or(AuditError::NotInitialized)?;
+    let mut guard = AUDIT_LOGGER.lock().map_err(|_| AuditError::LockFailed)?;
+    let logger = guard.as_mut().ok_or(AuditError::NotInitialized)?;
 
     // Attempt to flush buffered entries periodically
     if should_attempt_flush() {
@@ -508,8 +1132,8 @@ pub fn log_audit_async(entry: AuditEntry) {
 ///
 /// Use this for events that MUST be logged for security compliance.
 pub fn log_audit_critical(entry: AuditEntry) -> Result<(), AuditError> {
-    let guard = AUDIT_LOGGER.lock().map_err(|_| AuditError::LockFailed)?;
-    let logger = guard.as_ref().ok_or(AuditError::NotInitialized)?;
+    let mut guard = AUDIT_LOGGER.lock().map_err(|_| AuditError::LockFailed)?;
+    let logger = guard.as_mut().ok_or(AuditError::NotInitialized)?;
 
     // First try to flush any buffered entries
     logger.flush_buffer();
@@ -535,11 +1159,11 @@ pub fn get_buffered_count() -> usize {
 /// Manually flush buffered entries to disk
 /// Returns the number of entries successfully flushed
 pub fn flush_buffered_entries() -> usize {
-    let guard = match AUDIT_LOGGER.lock() {
+    let mut guard = match AUDIT_LOGGER.lock() {
         Ok(g) => g,
         Err(_) => return 0,
     };
-    let logger = match guard.as_ref() {
+    let logger = match guard.as_mut() {
         Some(l) => l,
         None => return 0,
     };
@@ -588,24 +1212,32 @@ pub fn audit_storage_mode_changed(new_mode: &str) {
     ));
 }
 
-/// Log token set (CRITICAL - security event, never logs the token value)
-pub fn audit_token_set(token_name: &str) {
-    // Security-critical: token changes must be logged
-    let _ = log_audit_critical(AuditEntry::new(
-        AuditEventType::TokenSet,
-        AuditSeverity::Info,
-        format!("Token set: {}", token_name),
-    ));
+/// Log a vault secret change (CRITICAL - security event, never logs the value)
+fn audit_secret_change(event: AuditEventType, verb: &str, secret_name: &str) {
+    // Security-critical: secret changes must be logged
+    let _ = log_audit_critical(
+        AuditEntry::new(
+            event,
+            AuditSeverity::Info,
+            format!("Secret {}: {}", verb, secret_name),
+        )
+        .with_context(serde_json::json!({ "secret": secret_name })),
+    );
 }
 
-/// Log token cleared (CRITICAL - security event)
-pub fn audit_token_cleared(token_name: &str) {
-    // Security-critical: token changes must be logged
-    let _ = log_audit_critical(AuditEntry::new(
-        AuditEventType::TokenCleared,
-        AuditSeverity::Info,
-        format!("Token cleared: {}", token_name),
-    ));
+/// Log a secret added to the vault
+pub fn audit_secret_created(secret_name: &str) {
+    audit_secret_change(AuditEventType::SecretCreated, "created", secret_name);
+}
+
+/// Log a secret's value being replaced
+pub fn audit_secret_rotated(secret_name: &str) {
+    audit_secret_change(AuditEventType::SecretRotated, "rotated", secret_name);
+}
+
+/// Log a secret removed from the vault
+pub fn audit_secret_deleted(secret_name: &str) {
+    audit_secret_change(AuditEventType::SecretDeleted, "deleted", secret_name);
 }
 
 /// Log Jira HTTP opt-in (CRITICAL - security warning)
@@ -673,6 +1305,33 @@ pub fn audit_security_failure(event: AuditEventType, message: &str) {
     let _ = log_audit_critical(AuditEntry::new(event, AuditSeverity::Error, message));
 }
 
+/// Log a denied read of a restricted namespace (CRITICAL - security event)
+pub fn audit_namespace_access_denied(namespace_id: &str, action: &str) {
+    let _ = log_audit_critical(
+        AuditEntry::new(
+            AuditEventType::NamespaceAccessDenied,
+            AuditSeverity::Warning,
+            format!("Access to restricted namespace denied: {}", action),
+        )
+        .with_context(serde_json::json!({
+            "namespace_id": namespace_id,
+            "action": action,
+        })),
+    );
+}
+
+/// Log restricted namespaces being unlocked (CRITICAL - security event)
+pub fn audit_restricted_access_unlocked(method: &str) {
+    let _ = log_audit_critical(
+        AuditEntry::new(
+            AuditEventType::RestrictedAccessUnlocked,
+            AuditSeverity::Info,
+            "Restricted namespaces unlocked",
+        )
+        .with_context(serde_json::json!({ "method": method })),
+    );
+}
+
 /// Log database repair (informational)
 pub fn audit_database_repaired(details: &str) {
     log_audit_best_effort(AuditEntry::new(
@@ -764,6 +1423,51 @@ pub fn audit_model_integrity_unverified(model_id: &str, sha256: &str) {
     );
 }
 
+pub fn audit_model_integrity_failed(model_id: &str, expected: &str, actual: &str) {
+    log_audit_best_effort(
+        AuditEntry::new(
+            AuditEventType::Custom("model_integrity_failed".to_string()),
+            AuditSeverity::Error,
+            format!("Model integrity check failed: {}", model_id),
+        )
+        .with_context(serde_json::json!({
+            "model_id": model_id,
+            "expected_sha256": expected,
+            "actual_sha256": actual,
+        })),
+    );
+}
+
+pub fn audit_draft_emailed(draft_id: &str, ticket_id: Option<&str>, recipient_count: usize) {
+    log_audit_best_effort(
+        AuditEntry::new(
+            AuditEventType::Custom("draft_emailed".to_string()),
+            AuditSeverity::Info,
+            format!("Draft emailed: {}", draft_id),
+        )
+        .with_context(serde_json::json!({
+            "draft_id": draft_id,
+            "ticket_id": ticket_id,
+            "recipient_count": recipient_count,
+        })),
+    );
+}
+
+pub fn audit_draft_published(draft_id: &str, space: &str, page_id: &str) {
+    log_audit_best_effort(
+        AuditEntry::new(
+            AuditEventType::Custom("draft_published".to_string()),
+            AuditSeverity::Info,
+            format!("Draft published to Confluence: {}", draft_id),
+        )
+        .with_context(serde_json::json!({
+            "draft_id": draft_id,
+            "space": space,
+            "page_id": page_id,
+        })),
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -875,6 +1579,194 @@ mod tests {
         assert!(!CALLBACK_CALLED.load(std::sync::atomic::Ordering::SeqCst));
     }
 
+    #[test]
+    fn test_audit_chain_verification() {
+        let log_dir =
+            std::env::temp_dir().join(format!("assistsupport-audit-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&log_dir).unwrap();
+        let mut logger = AuditLogger {
+            log_dir: log_dir.clone(),
+            last_hash: None,
+            retention: AuditRetention::default(),
+        };
+        for i in 0..4 {
+            logger
+                .write(&AuditEntry::new(
+                    AuditEventType::TokenSet,
+                    AuditSeverity::Info,
+                    format!("Token set: {}", i),
+                ))
+                .unwrap();
+        }
+        let log_path = logger.log_path();
+        let verify = || {
+            verify_audit_files(
+                &list_audit_log_files_in(&log_dir).unwrap(),
+                read_head(&log_dir),
+            )
+            .unwrap()
+        };
+
+        let result = verify();
+        assert!(result.valid);
+        assert_eq!(result.entries_checked, 4);
+
+        let original = fs::read_to_string(&log_path).unwrap();
+        let lines: Vec<&str> = original.lines().collect();
+
+        // Modified entry
+        fs::write(&log_path, original.replace("Token set: 1", "Token set: 9")).unwrap();
+        let result = verify();
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(result.issues[0].kind, AuditChainIssueKind::Modified);
+        assert_eq!(result.issues[0].line, 2);
+
+        // Deleted entry
+        fs::write(
+            &log_path,
+            format!("{}\n{}\n{}\n", lines[0], lines[2], lines[3]),
+        )
+        .unwrap();
+        let result = verify();
+        assert_eq!(result.issues[0].kind, AuditChainIssueKind::BrokenLink);
+
+        // Truncated log
+        fs::write(&log_path, format!("{}\n{}\n", lines[0], lines[1])).unwrap();
+        let result = verify();
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(result.issues[0].kind, AuditChainIssueKind::Truncated);
+
+        fs::remove_dir_all(&log_dir).unwrap();
+    }
+
+    #[test]
+    fn test_audit_archive_rotation() {
+        let log_dir =
+            std::env::temp_dir().join(format!("assistsupport-audit-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&log_dir).unwrap();
+        // Legacy numbered rotation from an older version
+        fs::write(log_dir.join("audit.log.1"), "").unwrap();
+
+        let mut logger = AuditLogger {
+            log_dir: log_dir.clone(),
+            last_hash: None,
+            retention: AuditRetention {
+                max_file_bytes: 1,
+                max_archives: 3,
+                max_age_days: 0,
+            },
+        };
+        logger.migrate_legacy_rotations();
+        assert!(!log_dir.join("audit.log.1").exists());
+        assert_eq!(list_archives_in(&log_dir).unwrap().len(), 1);
+
+        // Every write after the first archives the previous one
+        for i in 0..6 {
+            logger
+                .write(&AuditEntry::new(
+                    AuditEventType::TokenSet,
+                    AuditSeverity::Info,
+                    format!("Token set: {}", i),
+                ))
+                .unwrap();
+        }
+        let archives = list_archives_in(&log_dir).unwrap();
+        assert_eq!(archives.len(), 3);
+        assert!(archives.windows(2).all(|w| w[0].name < w[1].name));
+
+        let files = list_audit_log_files_in(&log_dir).unwrap();
+        assert_eq!(files.last().unwrap(), &logger.log_path());
+        let messages: Vec<String> = files
+            .iter()
+            .flat_map(|path| read_segment_entries(path).unwrap())
+            .map(|entry| entry.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Token set: 2",
+                "Token set: 3",
+                "Token set: 4",
+                "Token set: 5"
+            ]
+        );
+
+        // The chain continues across archives; the first entry's predecessor
+        // was pruned
+        let result = verify_audit_files(&files, read_head(&log_dir)).unwrap();
+        assert!(result.valid);
+        assert_eq!(result.entries_checked, 4);
+
+        assert!(AuditRetention {
+            max_archives: 0,
+            ..AuditRetention::default()
+        }
+        .validate()
+        .is_err());
+
+        fs::remove_dir_all(&log_dir).unwrap();
+    }
+
+    #[test]
+    fn test_siem_export_formats() {
+        let entries = vec![
+            AuditEntry::new(
+                AuditEventType::Custom("a|b".to_string()),
+                AuditSeverity::Critical,
+                "key=value\nnext line",
+            )
+            .with_context(serde_json::json!({"mode": "passphrase"})),
+            AuditEntry::new(AuditEventType::KeyRotated, AuditSeverity::Info, "Rotated"),
+        ];
+
+        let cef = format_audit_entries(&entries, AuditExportFormat::Cef).unwrap();
+        let lines: Vec<&str> = cef.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("CEF:0|AssistSupport|AssistSupport|"));
+        assert!(lines[0].contains("|9000|custom:a\\|b|10|"));
+        assert!(lines[0].contains("msg=key\\=value\\nnext line "));
+        assert!(lines[0].contains("cs2Label=context"));
+        assert!(lines[1].contains("|1003|key_rotated|3|"));
+        assert!(lines[1].contains(&format!("cs1={}", entries[1].entry_id())));
+
+        let jsonl = format_audit_entries(&entries, AuditExportFormat::Jsonl).unwrap();
+        let records: Vec<serde_json::Value> = jsonl
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1]["event_id"], 1003);
+        assert_eq!(records[1]["severity_level"], 6);
+        assert_eq!(records[0]["context"]["mode"], "passphrase");
+        assert_eq!(records[1]["entry_id"], entries[1].entry_id());
+    }
+
+    #[test]
+    fn test_anonymize_entries() {
+        let mut entry = AuditEntry::new(
+            AuditEventType::JiraConfigured,
+            AuditSeverity::Info,
+            "Jira configured for jira.acme.com by admin@acme.com",
+        )
+        .with_context(serde_json::json!({"ticket": "OPS-12"}));
+        entry.prev_hash = Some("abc".to_string());
+        entry.hash = Some(entry.compute_hash());
+
+        let mut pseudonymizer = Pseudonymizer::in_memory();
+        let anonymized = anonymize_entries(&[entry], &mut pseudonymizer);
+        assert_eq!(
+            anonymized[0].message,
+            "Jira configured for host-0001.example.invalid by \
+             user-0001@host-0002.example.invalid"
+        );
+        assert_eq!(
+            anonymized[0].context,
+            Some(serde_json::json!({"ticket": "TICKET-0001"}))
+        );
+        assert!(anonymized[0].prev_hash.is_none());
+        assert!(anonymized[0].hash.is_none());
+    }
+
     #[test]
     fn test_log_audit_without_init_returns_error() {
         // This test runs without initializing the logger
@@ -5,13 +5,15 @@
 //!
 //! Supports optional password-based encryption using Argon2id + AES-256-GCM.
 
+use crate::anonymize::Pseudonymizer;
 use crate::db::{CustomVariable, Database, DecisionTree, ResponseTemplate, SavedDraft};
 use crate::security::ExportCrypto;
 use crate::validation::validate_within_home;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::{Cursor, Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use zip::write::SimpleFileOptions;
 use zip::{ZipArchive, ZipWriter};
 
@@ -53,6 +55,9 @@ pub struct ExportSummary {
     pub trees_count: usize,
     pub path: String,
     pub encrypted: bool,
+    /// Names, emails, hostnames and ticket IDs were pseudonymized
+    #[serde(default)]
+    pub anonymized: bool,
 }
 
 /// Summary of import operation
@@ -110,12 +115,24 @@ pub enum BackupError {
     Encryption(String),
 }
 
-/// Export all app data to a ZIP file (optionally encrypted with password)
+/// Export all app data to a ZIP file (optionally encrypted with password).
+/// With a `pseudonymizer`, every string value is pseudonymized so the backup
+/// can be shared.
 pub fn export_backup(
     db: &Database,
     output_path: &Path,
     password: Option<&str>,
+    mut pseudonymizer: Option<&mut Pseudonymizer>,
 ) -> Result<ExportSummary, BackupError> {
+    let anonymized = pseudonymizer.is_some();
+    let mut section_json = |value: serde_json::Value| -> Result<String, BackupError> {
+        let mut value = value;
+        if let Some(p) = pseudonymizer.as_deref_mut() {
+            p.anonymize_json(&mut value);
+        }
+        Ok(serde_json::to_string_pretty(&value)?)
+    };
+
     // Create ZIP in memory first
     let mut zip_buffer = Vec::new();
     {
@@ -138,21 +155,21 @@ pub fn export_backup(
             .list_drafts(10000)
             .map_err(|e| BackupError::Database(e.to_string()))?;
         zip.start_file("drafts.json", options)?;
-        zip.write_all(serde_json::to_string_pretty(&drafts)?.as_bytes())?;
+        zip.write_all(section_json(serde_json::to_value(&drafts)?)?.as_bytes())?;
 
         // Export templates
         let templates = db
             .list_templates()
             .map_err(|e| BackupError::Database(e.to_string()))?;
         zip.start_file("templates.json", options)?;
-        zip.write_all(serde_json::to_string_pretty(&templates)?.as_bytes())?;
+        zip.write_all(section_json(serde_json::to_value(&templates)?)?.as_bytes())?;
 
         // Export custom variables
         let variables = db
             .list_custom_variables()
             .map_err(|e| BackupError::Database(e.to_string()))?;
         zip.start_file("variables.json", options)?;
-        zip.write_all(serde_json::to_string_pretty(&variables)?.as_bytes())?;
+        zip.write_all(section_json(serde_json::to_value(&variables)?)?.as_bytes())?;
 
         // Export custom decision trees only (source='custom')
         let all_trees = db
@@ -163,17 +180,17 @@ pub fn export_backup(
             .filter(|t| t.source == "custom")
             .collect();
         zip.start_file("trees.json", options)?;
-        zip.write_all(serde_json::to_string_pretty(&custom_trees)?.as_bytes())?;
+        zip.write_all(section_json(serde_json::to_value(&custom_trees)?)?.as_bytes())?;
 
         // Export settings
         let settings = export_settings(db)?;
         zip.start_file("settings.json", options)?;
-        zip.write_all(serde_json::to_string_pretty(&settings)?.as_bytes())?;
+        zip.write_all(section_json(serde_json::to_value(&settings)?)?.as_bytes())?;
 
         // Export KB config (folder path only)
         let kb_config = export_kb_config(db)?;
         zip.start_file("kb_config.json", options)?;
-        zip.write_all(serde_json::to_string_pretty(&kb_config)?.as_bytes())?;
+        zip.write_all(section_json(serde_json::to_value(&kb_config)?)?.as_bytes())?;
 
         zip.finish()?;
     }
@@ -220,9 +237,172 @@ pub fn export_backup(
         trees_count,
         path: output_path.display().to_string(),
         encrypted,
+        anonymized,
     })
 }
 
+/// Settings key holding the ID of the automatic backup job schedule
+pub const BACKUP_SCHEDULE_SETTING: &str = "backup_schedule_id";
+
+/// Settings key holding the time of the last successful backup (RFC 3339)
+pub const LAST_BACKUP_SETTING: &str = "last_backup_at";
+
+/// File name prefix for backups written by scheduled jobs
+pub const SCHEDULED_BACKUP_PREFIX: &str = "assistsupport-backup-";
+
+/// How often automatic backups run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupFrequency {
+    Daily,
+    Weekly,
+}
+
+impl BackupFrequency {
+    /// Cron expression for the schedule (02:00 local time, Sundays for weekly)
+    pub fn cron(&self) -> &'static str {
+        match self {
+            Self::Daily => "0 2 * * *",
+            Self::Weekly => "0 2 * * 0",
+        }
+    }
+
+    /// How long without a successful backup before it counts as overdue.
+    /// Allows for the app being closed at the scheduled time.
+    pub fn overdue_after(&self) -> chrono::Duration {
+        match self {
+            Self::Daily => chrono::Duration::hours(36),
+            Self::Weekly => chrono::Duration::days(9),
+        }
+    }
+}
+
+/// Whether an automatic backup is overdue. Before the first success, the
+/// time the schedule was last configured is the reference point.
+pub fn is_backup_overdue(
+    frequency: BackupFrequency,
+    last_success: Option<DateTime<Utc>>,
+    scheduled_since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> bool {
+    let reference = last_success.unwrap_or(scheduled_since);
+    now - reference > frequency.overdue_after()
+}
+
+/// Time of the last successful backup, if any
+pub fn last_backup_at(db: &Database) -> Option<DateTime<Utc>> {
+    let value: String = db
+        .conn()
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?",
+            [LAST_BACKUP_SETTING],
+            |row| row.get(0),
+        )
+        .ok()?;
+    DateTime::parse_from_rfc3339(&value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Record a successful backup
+pub fn record_backup_success(db: &Database, at: DateTime<Utc>) -> Result<(), BackupError> {
+    db.conn()
+        .execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+            rusqlite::params![LAST_BACKUP_SETTING, at.to_rfc3339()],
+        )
+        .map_err(|e| BackupError::Database(e.to_string()))?;
+    Ok(())
+}
+
+/// Automatic backup settings with their current status
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupScheduleStatus {
+    pub schedule_id: String,
+    pub enabled: bool,
+    pub frequency: BackupFrequency,
+    pub folder: String,
+    /// Number of scheduled backups to keep (None keeps all)
+    pub retention: Option<usize>,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub overdue: bool,
+}
+
+/// ID of the automatic backup job schedule, if one has been set up
+pub fn backup_schedule_id(db: &Database) -> Option<String> {
+    db.conn()
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?",
+            [BACKUP_SCHEDULE_SETTING],
+            |row| row.get(0),
+        )
+        .ok()
+}
+
+/// Load the automatic backup schedule, if one is configured
+pub fn backup_schedule_status(db: &Database) -> Result<Option<BackupScheduleStatus>, BackupError> {
+    let Some(schedule_id) = backup_schedule_id(db) else {
+        return Ok(None);
+    };
+    let Some(schedule) = db
+        .get_job_schedule(&schedule_id)
+        .map_err(|e| BackupError::Database(e.to_string()))?
+    else {
+        return Ok(None);
+    };
+
+    let params = schedule.params.unwrap_or_default();
+    let frequency =
+        serde_json::from_value(params["frequency"].clone()).unwrap_or(BackupFrequency::Daily);
+    let last_success_at = last_backup_at(db);
+    let overdue = schedule.enabled
+        && is_backup_overdue(frequency, last_success_at, schedule.updated_at, Utc::now());
+    Ok(Some(BackupScheduleStatus {
+        schedule_id: schedule.id,
+        enabled: schedule.enabled,
+        frequency,
+        folder: params["folder"].as_str().unwrap_or_default().to_string(),
+        retention: params["retention"].as_u64().map(|r| r as usize),
+        next_run_at: schedule.next_run_at,
+        last_success_at,
+        overdue,
+    }))
+}
+
+/// Backups written by scheduled jobs in `folder`, oldest first
+pub fn scheduled_backups(folder: &Path) -> Result<Vec<PathBuf>, BackupError> {
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(folder)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with(SCHEDULED_BACKUP_PREFIX) && name.ends_with(".zip")
+                    })
+        })
+        .collect();
+    // Names embed a sortable timestamp
+    backups.sort();
+    Ok(backups)
+}
+
+/// Delete the oldest scheduled backups in `folder`, keeping the newest
+/// `retention`. Returns the deleted paths.
+pub fn prune_backups(folder: &Path, retention: usize) -> Result<Vec<PathBuf>, BackupError> {
+    let backups = scheduled_backups(folder)?;
+    let excess = backups.len().saturating_sub(retention);
+    let mut deleted = Vec::with_capacity(excess);
+    for path in backups.into_iter().take(excess) {
+        std::fs::remove_file(&path)?;
+        deleted.push(path);
+    }
+    Ok(deleted)
+}
+
 /// Check if a file is an encrypted backup
 fn is_encrypted_backup(path: &Path) -> Result<Option<EncryptedBackupHeader>, BackupError> {
     let mut file = File::open(path)?;
@@ -520,6 +700,10 @@ fn import_setting(db: &Database, key: &str, value: &str) -> Result<(), BackupErr
 }
 
 fn sanitize_imported_setting(key: &str, value: &str) -> Option<String> {
+    // Backup bookkeeping describes the machine the backup came from
+    if key == BACKUP_SCHEDULE_SETTING || key == LAST_BACKUP_SETTING {
+        return None;
+    }
     if key != "kb_folder" {
         return Some(value.to_string());
     }
@@ -541,7 +725,7 @@ fn sanitize_imported_setting(key: &str, value: &str) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::sanitize_imported_setting;
+    use super::*;
 
     #[test]
     fn sanitize_imported_setting_accepts_non_kb_keys() {
@@ -560,4 +744,54 @@ mod tests {
         let result = sanitize_imported_setting("kb_folder", "/path/that/does/not/exist");
         assert!(result.is_none());
     }
+
+    #[test]
+    fn sanitize_imported_setting_skips_backup_state() {
+        assert!(sanitize_imported_setting(LAST_BACKUP_SETTING, "2026-01-01T00:00:00Z").is_none());
+        assert!(sanitize_imported_setting(BACKUP_SCHEDULE_SETTING, "abc").is_none());
+    }
+
+    #[test]
+    fn backup_overdue_after_grace_period() {
+        let since = DateTime::parse_from_rfc3339("2026-03-01T02:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let hours = |h| since + chrono::Duration::hours(h);
+        let overdue =
+            |frequency, last_success, now| is_backup_overdue(frequency, last_success, since, now);
+
+        assert!(!overdue(BackupFrequency::Daily, None, hours(30)));
+        assert!(overdue(BackupFrequency::Daily, None, hours(40)));
+        assert!(!overdue(BackupFrequency::Daily, Some(hours(30)), hours(40)));
+        assert!(!overdue(BackupFrequency::Weekly, None, hours(8 * 24)));
+        assert!(overdue(BackupFrequency::Weekly, None, hours(10 * 24)));
+    }
+
+    #[test]
+    fn prune_backups_keeps_newest() {
+        let folder =
+            std::env::temp_dir().join(format!("assistsupport-prune-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&folder).unwrap();
+        for name in [
+            "assistsupport-backup-20260301-020000.zip",
+            "assistsupport-backup-20260302-020000.zip",
+            "assistsupport-backup-20260303-020000.zip",
+            "manual-backup.zip",
+        ] {
+            std::fs::write(folder.join(name), b"zip").unwrap();
+        }
+
+        let deleted = prune_backups(&folder, 2).unwrap();
+        assert_eq!(
+            deleted,
+            vec![folder.join("assistsupport-backup-20260301-020000.zip")]
+        );
+        assert!(folder
+            .join("assistsupport-backup-20260303-020000.zip")
+            .exists());
+        assert!(folder.join("manual-backup.zip").exists());
+        assert!(prune_backups(&folder, 2).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
 }
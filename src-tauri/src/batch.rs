@@ -0,0 +1,320 @@
+//! CSV import/export for batch response generation
+//!
+//! Lets ITSM systems without a direct integration feed tickets into a batch
+//! job: each CSV row (ticket ID, subject, body) becomes one batch item, and
+//! results are written back as CSV with the generated response appended.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Maximum rows accepted from a single CSV import
+pub const MAX_IMPORT_ROWS: usize = 1000;
+
+const TICKET_ID_HEADERS: &[&str] = &["ticket_id", "ticket", "id", "key", "number"];
+const SUBJECT_HEADERS: &[&str] = &["subject", "title", "summary", "short_description"];
+const BODY_HEADERS: &[&str] = &["body", "description", "details", "text", "message"];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BatchCsvError {
+    #[error("CSV is empty")]
+    Empty,
+    #[error("Missing required column: {0}")]
+    MissingColumn(&'static str),
+    #[error("Unterminated quoted field starting on line {0}")]
+    UnterminatedQuote(usize),
+    #[error("Row {row}: {message}")]
+    InvalidRow { row: usize, message: String },
+    #[error("Too many rows: {0} (max {MAX_IMPORT_ROWS})")]
+    TooManyRows(usize),
+}
+
+/// A ticket imported from CSV
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TicketRow {
+    pub ticket_id: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl TicketRow {
+    /// Text sent to the model for this ticket
+    pub fn input_text(&self) -> String {
+        match (self.subject.is_empty(), self.body.is_empty()) {
+            (false, false) => format!("{}\n\n{}", self.subject, self.body),
+            (false, true) => self.subject.clone(),
+            _ => self.body.clone(),
+        }
+    }
+}
+
+/// One exported row: the imported ticket plus its generated response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TicketResultRow<'a> {
+    pub ticket_id: &'a str,
+    pub subject: &'a str,
+    pub body: &'a str,
+    pub response: &'a str,
+    pub duration_ms: u64,
+    pub sources: Vec<&'a str>,
+}
+
+/// Split CSV content into records (RFC 4180: quoted fields may contain
+/// commas, doubled quotes and line breaks). Blank lines are skipped.
+fn parse_records(content: &str) -> Result<Vec<(usize, Vec<String>)>, BatchCsvError> {
+    parse_delimited(content, ',')
+}
+
+/// Split delimited text (CSV, TSV) into records, each with the 1-based line
+/// it starts on
+pub(crate) fn parse_delimited(
+    content: &str,
+    delimiter: char,
+) -> Result<Vec<(usize, Vec<String>)>, BatchCsvError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut field_started = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut quote_line = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push('\n');
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if !field_started => {
+                in_quotes = true;
+                field_started = true;
+                quote_line = line;
+            }
+            c if c == delimiter => {
+                record.push(std::mem::take(&mut field));
+                field_started = false;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(std::mem::take(&mut field));
+                field_started = false;
+                if record.iter().any(|f| !f.trim().is_empty()) {
+                    records.push((record_line, std::mem::take(&mut record)));
+                } else {
+                    record.clear();
+                }
+                line += 1;
+                record_line = line;
+            }
+            _ => {
+                field.push(c);
+                field_started = true;
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(BatchCsvError::UnterminatedQuote(quote_line));
+    }
+    record.push(field);
+    if record.iter().any(|f| !f.trim().is_empty()) {
+        records.push((record_line, record));
+    }
+    Ok(records)
+}
+
+fn find_column(headers: &[String], names: &[&str]) -> Option<usize> {
+    headers.iter().position(|h| {
+        let normalized = h.trim().to_lowercase().replace([' ', '-'], "_");
+        names.contains(&normalized.as_str())
+    })
+}
+
+/// Parse a ticket CSV. The header row must contain a ticket ID column and at
+/// least one of subject/body (common ITSM column names are accepted, e.g.
+/// `Number`, `Short Description`, `Description`).
+pub fn parse_ticket_csv(content: &str) -> Result<Vec<TicketRow>, BatchCsvError> {
+    let mut records = parse_records(content)?.into_iter();
+    let (_, headers) = records.next().ok_or(BatchCsvError::Empty)?;
+
+    let id_col = find_column(&headers, TICKET_ID_HEADERS)
+        .ok_or(BatchCsvError::MissingColumn("ticket_id"))?;
+    let subject_col = find_column(&headers, SUBJECT_HEADERS);
+    let body_col = find_column(&headers, BODY_HEADERS);
+    if subject_col.is_none() && body_col.is_none() {
+        return Err(BatchCsvError::MissingColumn("subject or body"));
+    }
+
+    let field = |record: &[String], col: Option<usize>| -> String {
+        col.and_then(|c| record.get(c))
+            .map(|v| v.trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let mut rows = Vec::new();
+    for (line, record) in records {
+        let row = TicketRow {
+            ticket_id: field(&record, Some(id_col)),
+            subject: field(&record, subject_col),
+            body: field(&record, body_col),
+        };
+        if row.ticket_id.is_empty() {
+            return Err(BatchCsvError::InvalidRow {
+                row: line,
+                message: "ticket ID is empty".into(),
+            });
+        }
+        if row.subject.is_empty() && row.body.is_empty() {
+            return Err(BatchCsvError::InvalidRow {
+                row: line,
+                message: "subject and body are both empty".into(),
+            });
+        }
+        rows.push(row);
+    }
+
+    if rows.is_empty() {
+        return Err(BatchCsvError::Empty);
+    }
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(BatchCsvError::TooManyRows(rows.len()));
+    }
+    Ok(rows)
+}
+
+/// Quote a CSV field when needed. Fields starting with a formula character
+/// are prefixed with `'` so spreadsheet apps don't evaluate them.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Write imported tickets and their generated responses back to CSV
+pub fn write_ticket_results_csv(rows: &[TicketResultRow<'_>]) -> String {
+    let mut out = String::from("ticket_id,subject,body,response,duration_ms,sources\n");
+    for row in rows {
+        out.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            csv_field(row.ticket_id),
+            csv_field(row.subject),
+            csv_field(row.body),
+            csv_field(row.response),
+            row.duration_ms,
+            csv_field(&row.sources.join("; ")),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ticket_csv() {
+        let csv = "Ticket ID,Subject,Body\r\n\
+                   INC-1,VPN down,\"Cannot connect, error 809\"\r\n\
+                   \r\n\
+                   INC-2,\"Printer \"\"jammed\"\"\",\"Line one\nLine two\"\r\n";
+        let rows = parse_ticket_csv(csv).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].ticket_id, "INC-1");
+        assert_eq!(rows[0].body, "Cannot connect, error 809");
+        assert_eq!(rows[1].subject, "Printer \"jammed\"");
+        assert_eq!(rows[1].body, "Line one\nLine two");
+        assert_eq!(
+            rows[1].input_text(),
+            "Printer \"jammed\"\n\nLine one\nLine two"
+        );
+    }
+
+    #[test]
+    fn test_parse_itsm_column_names() {
+        let csv = "\u{feff}Number,Short Description,Priority\nINC0010001,Password reset,3\n";
+        let rows = parse_ticket_csv(csv).unwrap();
+        assert_eq!(rows[0].ticket_id, "INC0010001");
+        assert_eq!(rows[0].subject, "Password reset");
+        assert_eq!(rows[0].body, "");
+        assert_eq!(rows[0].input_text(), "Password reset");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_ticket_csv(""), Err(BatchCsvError::Empty));
+        assert_eq!(
+            parse_ticket_csv("ticket_id,subject\n"),
+            Err(BatchCsvError::Empty)
+        );
+        assert_eq!(
+            parse_ticket_csv("subject,body\na,b\n"),
+            Err(BatchCsvError::MissingColumn("ticket_id"))
+        );
+        assert_eq!(
+            parse_ticket_csv("ticket_id,priority\n1,high\n"),
+            Err(BatchCsvError::MissingColumn("subject or body"))
+        );
+        assert_eq!(
+            parse_ticket_csv("ticket_id,subject\n1,\"open\n"),
+            Err(BatchCsvError::UnterminatedQuote(2))
+        );
+        assert!(matches!(
+            parse_ticket_csv("ticket_id,subject,body\n,a,b\n"),
+            Err(BatchCsvError::InvalidRow { row: 2, .. })
+        ));
+        assert!(matches!(
+            parse_ticket_csv("ticket_id,subject,body\n1,a,b\n2,,\n"),
+            Err(BatchCsvError::InvalidRow { row: 3, .. })
+        ));
+
+        let mut many = String::from("ticket_id,subject\n");
+        for i in 0..=MAX_IMPORT_ROWS {
+            many.push_str(&format!("{},s\n", i));
+        }
+        assert_eq!(
+            parse_ticket_csv(&many),
+            Err(BatchCsvError::TooManyRows(MAX_IMPORT_ROWS + 1))
+        );
+    }
+
+    #[test]
+    fn test_write_results_round_trip() {
+        let rows = vec![TicketResultRow {
+            ticket_id: "INC-1",
+            subject: "VPN, again",
+            body: "Says \"809\"",
+            response: "=Try this\nthen reboot",
+            duration_ms: 1200,
+            sources: vec!["chunk-1", "chunk-2"],
+        }];
+        let csv = write_ticket_results_csv(&rows);
+        assert!(csv.starts_with("ticket_id,subject,body,response,duration_ms,sources\n"));
+        assert!(csv.contains("\"'=Try this\nthen reboot\""));
+
+        let parsed = parse_ticket_csv(&csv).unwrap();
+        assert_eq!(parsed[0].ticket_id, "INC-1");
+        assert_eq!(parsed[0].subject, "VPN, again");
+        assert_eq!(parsed[0].body, "Says \"809\"");
+    }
+}
@@ -0,0 +1,362 @@
+//! Batch export of saved drafts for archival
+//!
+//! Every draft matching a [`DraftExportFilter`] is written to its own file in
+//! a new timestamped folder, alongside a `manifest.json` recording the filter,
+//! each exported file with its size and SHA-256, and any drafts that failed to
+//! render. Drafts that fail are listed in the manifest rather than aborting
+//! the export.
+
+use crate::db::{DraftStatus, SavedDraft};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Manifest written next to the exported drafts
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Prefix for export folders created inside the chosen output directory
+pub const BATCH_EXPORT_PREFIX: &str = "drafts-export-";
+
+#[derive(Debug, Error)]
+pub enum BatchExportError {
+    #[error("Invalid date {0:?} (expected YYYY-MM-DD or RFC 3339)")]
+    InvalidDate(String),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to write manifest: {0}")]
+    Manifest(#[from] serde_json::Error),
+}
+
+/// Which drafts to export
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DraftExportFilter {
+    /// Statuses to include; empty includes every status
+    #[serde(default)]
+    pub statuses: Vec<DraftStatus>,
+    /// Earliest creation time, inclusive (YYYY-MM-DD or RFC 3339)
+    #[serde(default)]
+    pub created_from: Option<String>,
+    /// Latest creation time, exclusive (YYYY-MM-DD or RFC 3339)
+    #[serde(default)]
+    pub created_before: Option<String>,
+    /// Only drafts that cite a KB source from this namespace
+    #[serde(default)]
+    pub namespace_id: Option<String>,
+    /// Include autosaves (skipped by default)
+    #[serde(default)]
+    pub include_autosaves: bool,
+}
+
+impl DraftExportFilter {
+    /// Check that the date bounds parse
+    pub fn validate(&self) -> Result<(), BatchExportError> {
+        for date in [&self.created_from, &self.created_before]
+            .into_iter()
+            .flatten()
+        {
+            parse_bound(date)?;
+        }
+        Ok(())
+    }
+
+    /// Whether a draft passes the filter. Drafts with an unreadable
+    /// `created_at` only match when no date bounds are set.
+    pub fn matches(&self, draft: &SavedDraft) -> bool {
+        if draft.is_autosave && !self.include_autosaves {
+            return false;
+        }
+        if !self.statuses.is_empty() && !self.statuses.contains(&draft.status) {
+            return false;
+        }
+
+        if self.created_from.is_some() || self.created_before.is_some() {
+            let Ok(created) = parse_bound(&draft.created_at) else {
+                return false;
+            };
+            let bound = |b: &Option<String>| b.as_deref().and_then(|d| parse_bound(d).ok());
+            if bound(&self.created_from).is_some_and(|from| created < from)
+                || bound(&self.created_before).is_some_and(|before| created >= before)
+            {
+                return false;
+            }
+        }
+
+        match &self.namespace_id {
+            Some(namespace) => draft_namespaces(draft).iter().any(|ns| ns == namespace),
+            None => true,
+        }
+    }
+}
+
+/// Parse a date (start of day, UTC) or RFC 3339 timestamp
+fn parse_bound(value: &str) -> Result<DateTime<Utc>, BatchExportError> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|time| time.and_utc())
+        .ok_or_else(|| BatchExportError::InvalidDate(value.to_string()))
+}
+
+/// Namespaces of the KB sources a draft cites
+fn draft_namespaces(draft: &SavedDraft) -> Vec<String> {
+    draft
+        .kb_sources_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<Vec<serde_json::Value>>(json).ok())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|s| s["namespace_id"].as_str().map(str::to_string))
+        .collect()
+}
+
+/// An exported draft file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub draft_id: String,
+    pub ticket_id: Option<String>,
+    pub status: DraftStatus,
+    pub created_at: String,
+    /// File name relative to the export folder
+    pub file: String,
+    pub bytes: u64,
+    pub sha256: String,
+}
+
+/// A draft that could not be exported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestFailure {
+    pub draft_id: String,
+    pub error: String,
+}
+
+/// Summary written to `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchExportManifest {
+    pub exported_at: String,
+    pub app_version: String,
+    pub format: String,
+    pub filter: DraftExportFilter,
+    /// Drafts that matched the filter
+    pub matched: usize,
+    pub exported: Vec<ManifestEntry>,
+    pub failed: Vec<ManifestFailure>,
+    /// The export stopped early because the job was cancelled
+    pub cancelled: bool,
+}
+
+/// Result of a batch export
+#[derive(Debug, Clone)]
+pub struct BatchExportResult {
+    pub folder: PathBuf,
+    pub manifest: BatchExportManifest,
+}
+
+/// Export file name: creation date, then the draft ID (always unique)
+fn file_name(draft: &SavedDraft, extension: &str) -> String {
+    let date = parse_bound(&draft.created_at)
+        .map(|t| t.format("%Y%m%d").to_string())
+        .unwrap_or_else(|_| "undated".to_string());
+    let id: String = draft
+        .id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}-{}.{}", date, id, extension)
+}
+
+/// Write `drafts` into a new folder under `out_dir`.
+///
+/// `render` produces each draft's file content; `on_progress` is called with
+/// the number of drafts processed and returns `false` to stop early.
+pub fn export_drafts(
+    drafts: &[SavedDraft],
+    filter: &DraftExportFilter,
+    out_dir: &Path,
+    format: &str,
+    extension: &str,
+    mut render: impl FnMut(&SavedDraft) -> Result<String, String>,
+    mut on_progress: impl FnMut(usize) -> bool,
+) -> Result<BatchExportResult, BatchExportError> {
+    let folder = out_dir.join(format!(
+        "{}{}",
+        BATCH_EXPORT_PREFIX,
+        Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::create_dir_all(&folder)?;
+
+    let mut manifest = BatchExportManifest {
+        exported_at: Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        format: format.to_string(),
+        filter: filter.clone(),
+        matched: drafts.len(),
+        exported: Vec::new(),
+        failed: Vec::new(),
+        cancelled: false,
+    };
+
+    for (i, draft) in drafts.iter().enumerate() {
+        if !on_progress(i) {
+            manifest.cancelled = true;
+            break;
+        }
+        let content = match render(draft) {
+            Ok(content) => content,
+            Err(error) => {
+                manifest.failed.push(ManifestFailure {
+                    draft_id: draft.id.clone(),
+                    error,
+                });
+                continue;
+            }
+        };
+
+        let file = file_name(draft, extension);
+        std::fs::write(folder.join(&file), &content)?;
+        manifest.exported.push(ManifestEntry {
+            draft_id: draft.id.clone(),
+            ticket_id: draft.ticket_id.clone(),
+            status: draft.status,
+            created_at: draft.created_at.clone(),
+            file,
+            bytes: content.len() as u64,
+            sha256: hex::encode(Sha256::digest(content.as_bytes())),
+        });
+    }
+    if !manifest.cancelled {
+        on_progress(drafts.len());
+    }
+
+    let json = serde_json::to_string_pretty(&manifest)?;
+    std::fs::write(folder.join(MANIFEST_FILE), json)?;
+    Ok(BatchExportResult { folder, manifest })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(id: &str, created_at: &str, status: DraftStatus, sources: Option<&str>) -> SavedDraft {
+        SavedDraft {
+            id: id.to_string(),
+            input_text: "input".to_string(),
+            summary_text: None,
+            diagnosis_json: None,
+            response_text: Some(format!("response {}", id)),
+            ticket_id: None,
+            kb_sources_json: sources.map(str::to_string),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            is_autosave: false,
+            model_name: None,
+            case_intake_json: None,
+            status,
+            handoff_summary: None,
+            finalized_at: None,
+            finalized_by: None,
+            policy_citations_json: None,
+        }
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let filter = DraftExportFilter {
+            statuses: vec![DraftStatus::Finalized],
+            created_from: Some("2026-09-01".into()),
+            created_before: Some("2026-10-01".into()),
+            namespace_id: Some("hr".into()),
+            include_autosaves: false,
+        };
+        assert!(filter.validate().is_ok());
+
+        let sources = r#"[{"title":"Leave policy","namespace_id":"hr"}]"#;
+        let mut d = draft(
+            "a",
+            "2026-09-30T23:59:00Z",
+            DraftStatus::Finalized,
+            Some(sources),
+        );
+        assert!(filter.matches(&d));
+
+        d.created_at = "2026-10-01T00:00:00Z".into();
+        assert!(!filter.matches(&d));
+        d.created_at = "2026-09-01T00:00:00+00:00".into();
+        assert!(filter.matches(&d));
+
+        d.status = DraftStatus::Draft;
+        assert!(!filter.matches(&d));
+        d.status = DraftStatus::Finalized;
+
+        d.is_autosave = true;
+        assert!(!filter.matches(&d));
+        d.is_autosave = false;
+
+        d.kb_sources_json = Some(r#"[{"title":"VPN","namespace_id":"it"}]"#.into());
+        assert!(!filter.matches(&d));
+
+        assert!(DraftExportFilter::default().matches(&d));
+        assert!(DraftExportFilter {
+            created_from: Some("September".into()),
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_export_writes_files_and_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let drafts = vec![
+            draft("d-1", "2026-09-02T10:00:00Z", DraftStatus::Finalized, None),
+            draft("d/2", "2026-09-03T10:00:00Z", DraftStatus::Finalized, None),
+            draft("d-3", "not a date", DraftStatus::Archived, None),
+        ];
+
+        let result = export_drafts(
+            &drafts,
+            &DraftExportFilter::default(),
+            dir.path(),
+            "plaintext",
+            "txt",
+            |d| match d.id.as_str() {
+                "d-3" => Err("render failed".to_string()),
+                _ => Ok(d.response_text.clone().unwrap_or_default()),
+            },
+            |_| true,
+        )
+        .unwrap();
+
+        let files: Vec<&str> = result
+            .manifest
+            .exported
+            .iter()
+            .map(|e| e.file.as_str())
+            .collect();
+        assert_eq!(files, vec!["20260902-d-1.txt", "20260903-d_2.txt"]);
+        assert_eq!(
+            std::fs::read_to_string(result.folder.join("20260903-d_2.txt")).unwrap(),
+            "response d/2"
+        );
+        assert_eq!(result.manifest.failed[0].draft_id, "d-3");
+        assert_eq!(result.manifest.matched, 3);
+
+        let manifest: BatchExportManifest = serde_json::from_str(
+            &std::fs::read_to_string(result.folder.join(MANIFEST_FILE)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(manifest.exported, result.manifest.exported);
+        assert!(!manifest.cancelled);
+    }
+}
@@ -1,10 +1,22 @@
 //! Backup, restore, and export commands
 
-use crate::backup::{ExportSummary, ImportPreview, ImportSummary};
+use crate::backup::{
+    BackupFrequency, BackupScheduleStatus, ExportSummary, ImportPreview, ImportSummary,
+    BACKUP_SCHEDULE_SETTING,
+};
+use crate::jobs::schedule::{self, JobSchedule};
+use crate::jobs::JobType;
+use crate::validation::validate_within_home;
 use crate::AppState;
+use chrono::Utc;
+use std::path::Path;
 use tauri::State;
 use tauri_plugin_dialog::DialogExt;
 
+/// Scheduled backups kept when no retention is given
+const DEFAULT_BACKUP_RETENTION: usize = 7;
+const MAX_BACKUP_RETENTION: usize = 365;
+
 // ============================================================================
 // Export Commands
 // ============================================================================
@@ -98,21 +110,34 @@ pub async fn export_draft(
 // Backup/Restore Commands
 // ============================================================================
 
-/// Export all app data to a backup file (optionally encrypted with password)
+/// Export all app data to a backup file (optionally encrypted with password).
+/// With `anonymize`, names, emails, hostnames and ticket IDs are pseudonymized
+/// for sharing; such exports don't count as the latest backup.
 #[tauri::command]
 pub async fn export_backup(
     app: tauri::AppHandle,
     state: State<'_, AppState>,
     password: Option<String>,
+    anonymize: Option<bool>,
 ) -> Result<ExportSummary, String> {
+    let anonymize = anonymize.unwrap_or(false);
     let db_lock = state.db.lock().map_err(|e| e.to_string())?;
     let db = db_lock.as_ref().ok_or("Database not initialized")?;
 
     // Determine file extension based on encryption
-    let (filename, filter_name, extensions) = if password.is_some() {
-        ("assistsupport-backup.enc", "Encrypted Backup", &["enc"][..])
-    } else {
-        ("assistsupport-backup.zip", "ZIP Archive", &["zip"][..])
+    let (filename, filter_name, extensions) = match (password.is_some(), anonymize) {
+        (true, false) => ("assistsupport-backup.enc", "Encrypted Backup", &["enc"][..]),
+        (true, true) => (
+            "assistsupport-backup-anonymized.enc",
+            "Encrypted Backup",
+            &["enc"][..],
+        ),
+        (false, false) => ("assistsupport-backup.zip", "ZIP Archive", &["zip"][..]),
+        (false, true) => (
+            "assistsupport-backup-anonymized.zip",
+            "ZIP Archive",
+            &["zip"][..],
+        ),
     };
 
     // Show save file dialog
@@ -129,8 +154,20 @@ pub async fn export_backup(
                 .as_path()
                 .ok_or_else(|| "Invalid file path".to_string())?;
 
-            crate::backup::export_backup(db, file_path, password.as_deref())
-                .map_err(|e| e.to_string())
+            if anonymize {
+                return crate::anonymize::with_mapping(|p| {
+                    crate::backup::export_backup(db, file_path, password.as_deref(), Some(p))
+                })
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string());
+            }
+
+            let summary = crate::backup::export_backup(db, file_path, password.as_deref(), None)
+                .map_err(|e| e.to_string())?;
+            if let Err(e) = crate::backup::record_backup_success(db, Utc::now()) {
+                tracing::warn!("Failed to record backup time: {}", e);
+            }
+            Ok(summary)
         }
         None => Err("Export cancelled".to_string()),
     }
@@ -194,3 +231,79 @@ pub async fn import_backup(
         None => Err("Import cancelled".to_string()),
     }
 }
+
+// ============================================================================
+// Automatic Backup Commands
+// ============================================================================
+
+/// Get the automatic backup schedule and its last success, if configured
+#[tauri::command]
+pub fn get_backup_schedule(
+    state: State<'_, AppState>,
+) -> Result<Option<BackupScheduleStatus>, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    crate::backup::backup_schedule_status(db).map_err(|e| e.to_string())
+}
+
+/// Create or update the automatic backup schedule. Backups run at 02:00
+/// (Sundays for weekly) into `folder`, keeping the newest `retention` files.
+#[tauri::command]
+pub fn set_backup_schedule(
+    state: State<'_, AppState>,
+    frequency: BackupFrequency,
+    folder: String,
+    retention: Option<usize>,
+    enabled: Option<bool>,
+) -> Result<BackupScheduleStatus, String> {
+    let folder = validate_within_home(Path::new(&folder)).map_err(|e| e.to_string())?;
+    let retention = retention
+        .unwrap_or(DEFAULT_BACKUP_RETENTION)
+        .clamp(1, MAX_BACKUP_RETENTION);
+    let params = serde_json::json!({
+        "folder": folder.to_string_lossy(),
+        "retention": retention,
+        "frequency": frequency,
+    });
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    let existing = match crate::backup::backup_schedule_id(db) {
+        Some(id) => db.get_job_schedule(&id).map_err(|e| e.to_string())?,
+        None => None,
+    };
+    let mut schedule = match existing {
+        Some(mut schedule) => {
+            let now = Utc::now();
+            schedule.cron = frequency.cron().to_string();
+            schedule.params = Some(params);
+            schedule.next_run_at =
+                schedule::next_run_after(&schedule.cron, now).map_err(|e| e.to_string())?;
+            schedule.updated_at = now;
+            schedule
+        }
+        None => JobSchedule::new(
+            "Automatic backup",
+            frequency.cron(),
+            JobType::Backup,
+            Some(params),
+        )
+        .map_err(|e| e.to_string())?,
+    };
+    if let Some(enabled) = enabled {
+        schedule.enabled = enabled;
+    }
+
+    db.save_job_schedule(&schedule).map_err(|e| e.to_string())?;
+    db.conn()
+        .execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+            rusqlite::params![BACKUP_SCHEDULE_SETTING, schedule.id],
+        )
+        .map_err(|e| e.to_string())?;
+
+    crate::backup::backup_schedule_status(db)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Backup schedule not saved".to_string())
+}
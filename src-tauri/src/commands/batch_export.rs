@@ -0,0 +1,129 @@
+//! Batch draft export command and job runner
+
+use super::export_templates::render_with_template;
+use super::{parse_draft_export_format, render_draft_export, report_job_progress};
+use crate::batch_export::{self, DraftExportFilter};
+use crate::export_templates::Template;
+use crate::jobs::{Job, JobContext, JobType};
+use crate::validation::validate_within_home;
+use crate::AppState;
+use std::path::Path;
+use tauri::{AppHandle, Manager, State};
+
+/// Format prefix selecting a saved export template, e.g. `template:<id>`
+const TEMPLATE_FORMAT_PREFIX: &str = "template:";
+
+/// File extension for template exports (templates produce free-form text)
+const TEMPLATE_EXTENSION: &str = "txt";
+
+/// Start a job that exports every draft matching `filter` into a new folder
+/// under `out_dir`, with a `manifest.json` summary. `format` is a draft export
+/// format (`plaintext`, `html`, `styled_html:dark`, ...) or `template:<id>`
+/// for a saved export template. Returns the job ID.
+#[tauri::command]
+pub fn export_drafts_batch(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    filter: DraftExportFilter,
+    format: String,
+    out_dir: String,
+) -> Result<String, String> {
+    filter.validate().map_err(|e| e.to_string())?;
+    let out_dir = validate_within_home(Path::new(&out_dir)).map_err(|e| e.to_string())?;
+
+    let job = Job::new(JobType::ExportDrafts).with_metadata(serde_json::json!({
+        "params": {
+            "filter": filter,
+            "format": format,
+            "out_dir": out_dir.to_string_lossy(),
+        },
+    }));
+    {
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+        if let Some(template_id) = format.strip_prefix(TEMPLATE_FORMAT_PREFIX) {
+            db.get_export_template(template_id)
+                .map_err(|e| format!("Export template not found: {}", e))?;
+        }
+        db.create_job(&job).map_err(|e| e.to_string())?;
+    }
+
+    let job_id = job.id.clone();
+    tauri::async_runtime::spawn(super::schedules::run_job(app, None, job));
+    Ok(job_id)
+}
+
+/// Run an `export_drafts` job and return its summary line
+pub(crate) fn run_export_drafts_job(
+    app: &AppHandle,
+    ctx: &JobContext,
+    params: &serde_json::Value,
+) -> Result<String, String> {
+    let filter: DraftExportFilter = serde_json::from_value(params["filter"].clone())
+        .map_err(|e| format!("Invalid export filter: {}", e))?;
+    let format = params["format"].as_str().unwrap_or("plaintext");
+    let out_dir = params["out_dir"]
+        .as_str()
+        .ok_or("Export job has no output folder")?;
+    let out_dir = validate_within_home(Path::new(out_dir)).map_err(|e| e.to_string())?;
+
+    // Load everything up front so the database isn't held while writing files
+    let (drafts, template, variables) = {
+        let state = app.state::<AppState>();
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+        let drafts: Vec<_> = db
+            .list_all_drafts()
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .filter(|draft| filter.matches(draft))
+            .collect();
+        let template = match format.strip_prefix(TEMPLATE_FORMAT_PREFIX) {
+            Some(template_id) => {
+                let template = db
+                    .get_export_template(template_id)
+                    .map_err(|e| format!("Export template not found: {}", e))?;
+                Some(Template::parse(&template.content).map_err(|e| e.to_string())?)
+            }
+            None => None,
+        };
+        let variables = match template {
+            Some(_) => db.list_custom_variables().map_err(|e| e.to_string())?,
+            None => Vec::new(),
+        };
+        (drafts, template, variables)
+    };
+
+    let draft_format = parse_draft_export_format(format);
+    let extension = match template {
+        Some(_) => TEMPLATE_EXTENSION,
+        None => draft_format.extension(),
+    };
+    let total = drafts.len() as u64;
+    let result = batch_export::export_drafts(
+        &drafts,
+        &filter,
+        &out_dir,
+        format,
+        extension,
+        |draft| match &template {
+            Some(template) => render_with_template(template, &variables, draft, None),
+            None => render_draft_export(draft, draft_format, None),
+        },
+        |done| {
+            let message = format!("Exported {} of {} drafts", done, total);
+            report_job_progress(app, ctx, "exporting", done as u64, Some(total), &message);
+            !ctx.is_cancelled()
+        },
+    )
+    .map_err(|e| e.to_string())?;
+
+    let manifest = &result.manifest;
+    Ok(format!(
+        "Exported {} of {} drafts to {} ({} failed)",
+        manifest.exported.len(),
+        manifest.matched,
+        result.folder.display(),
+        manifest.failed.len()
+    ))
+}
@@ -0,0 +1,248 @@
+//! Confluence publishing and ingestion commands
+
+use super::{draft_sources, IngestResult};
+use crate::audit;
+use crate::confluence::{
+    self, ConfluenceClient, ConfluenceConfig, PagePublication, PublishedPage,
+    CONFLUENCE_INTEGRATION,
+};
+use crate::db::DraftStatus;
+use crate::security::{vault::Vault, TOKEN_CONFLUENCE};
+use crate::sources::confluence::{space_namespace, ConfluenceIngester};
+use crate::validation::normalize_and_validate_namespace_id;
+use crate::AppState;
+use serde::Serialize;
+use tauri::State;
+
+/// Confluence settings as exposed to the UI (the API token is never returned)
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfluenceSettings {
+    pub enabled: bool,
+    pub config: Option<ConfluenceConfig>,
+    pub has_token: bool,
+}
+
+/// Result of ingesting a Confluence space
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfluenceIngestResponse {
+    pub namespace_id: String,
+    pub total_pages: usize,
+    pub ingested: usize,
+    pub skipped: usize,
+    pub removed: usize,
+    pub errors: usize,
+    pub documents: Vec<IngestResult>,
+}
+
+fn has_token() -> bool {
+    Vault::get(TOKEN_CONFLUENCE)
+        .map(|t| t.is_some())
+        .unwrap_or(false)
+}
+
+/// Configure Confluence publishing.
+/// The connection is tested before anything is saved; `api_token` is only
+/// updated when provided.
+#[tauri::command]
+pub async fn configure_confluence(
+    state: State<'_, AppState>,
+    enabled: bool,
+    config: ConfluenceConfig,
+    api_token: Option<String>,
+) -> Result<(), String> {
+    config.validate().map_err(|e| e.to_string())?;
+
+    let api_token = match api_token.filter(|t| !t.is_empty()) {
+        Some(token) => Some(token),
+        None if enabled => Some(
+            Vault::get(TOKEN_CONFLUENCE)
+                .map_err(|e| e.to_string())?
+                .ok_or("A Confluence API token is required")?,
+        ),
+        None => None,
+    };
+
+    if let Some(token) = &api_token {
+        let client = ConfluenceClient::new(&config.base_url, &config.email, token);
+        if !client.test_connection().await.map_err(|e| e.to_string())? {
+            return Err("Connection failed - check credentials".to_string());
+        }
+        Vault::put(TOKEN_CONFLUENCE, token).map_err(|e| e.to_string())?;
+    }
+
+    let config_json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.set_integration_config(CONFLUENCE_INTEGRATION, enabled, Some(&config_json))
+        .map_err(|e| e.to_string())
+}
+
+/// Get Confluence settings (without the API token)
+#[tauri::command]
+pub fn get_confluence_config(state: State<'_, AppState>) -> Result<ConfluenceSettings, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    let record = db
+        .get_integration_config(CONFLUENCE_INTEGRATION)
+        .map_err(|e| e.to_string())?;
+
+    let (enabled, config) = match record {
+        Some(r) => {
+            let config = r
+                .config_json
+                .as_deref()
+                .map(serde_json::from_str)
+                .transpose()
+                .map_err(|e| e.to_string())?;
+            (r.enabled, config)
+        }
+        None => (false, None),
+    };
+
+    Ok(ConfluenceSettings {
+        enabled,
+        config,
+        has_token: has_token(),
+    })
+}
+
+/// Remove the Confluence API token and settings
+#[tauri::command]
+pub fn clear_confluence_config(state: State<'_, AppState>) -> Result<(), String> {
+    let _ = Vault::delete(TOKEN_CONFLUENCE);
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.set_integration_config(CONFLUENCE_INTEGRATION, false, None)
+        .map_err(|e| e.to_string())
+}
+
+/// Publish a finalized draft as a new Confluence page.
+/// The page contains the summary, the response and the cited KB sources.
+/// `parent_page` may be a page ID or a page title in `space`; the title
+/// defaults to the first line of the draft summary.
+#[tauri::command]
+pub async fn publish_draft_to_confluence(
+    state: State<'_, AppState>,
+    draft_id: String,
+    space: String,
+    parent_page: Option<String>,
+    title: Option<String>,
+) -> Result<PublishedPage, String> {
+    let space = space.trim().to_string();
+    confluence::validate_space_key(&space).map_err(|e| e.to_string())?;
+
+    // Build the page without holding the DB lock across network calls
+    let (client, page) = {
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+        let client = ConfluenceClient::from_db(db)
+            .map_err(|e| e.to_string())?
+            .ok_or("Confluence publishing is not enabled")?;
+        let draft = db.get_draft(&draft_id).map_err(|e| e.to_string())?;
+
+        if draft.status != DraftStatus::Finalized {
+            return Err("Only finalized drafts can be published".to_string());
+        }
+        let response = draft.response_text.clone().unwrap_or_default();
+        if response.trim().is_empty() {
+            return Err("Draft has no response to publish".to_string());
+        }
+
+        let page = PagePublication {
+            title: confluence::page_title(title.as_deref(), draft.summary_text.as_deref()),
+            sources: draft_sources(&draft),
+            summary: draft.summary_text,
+            response,
+            ticket_id: draft.ticket_id,
+        };
+        (client, page)
+    };
+
+    let parent_id = match parent_page.as_deref().map(str::trim) {
+        Some(parent) if !parent.is_empty() => Some(
+            client
+                .resolve_parent(&space, parent)
+                .await
+                .map_err(|e| e.to_string())?,
+        ),
+        _ => None,
+    };
+
+    let published = client
+        .create_page(&space, parent_id.as_deref(), &page)
+        .await
+        .map_err(|e| e.to_string())?;
+    audit::audit_draft_published(&draft_id, &space, &published.page_id);
+    Ok(published)
+}
+
+/// Ingest the pages of a Confluence space into the knowledge base.
+/// Pages go to `namespace_id`, or to the space's own namespace
+/// (`confluence-<space>`) when none is given. Re-ingesting only re-indexes
+/// pages whose version changed and removes pages deleted from the space.
+/// Uses block_in_place to run async operations while holding DB lock
+#[tauri::command]
+pub fn ingest_confluence(
+    state: State<'_, AppState>,
+    space_key: String,
+    namespace_id: Option<String>,
+) -> Result<ConfluenceIngestResponse, String> {
+    let space_key = space_key.trim().to_string();
+    confluence::validate_space_key(&space_key).map_err(|e| e.to_string())?;
+    let namespace_id = match namespace_id.filter(|ns| !ns.trim().is_empty()) {
+        Some(ns) => normalize_and_validate_namespace_id(&ns),
+        None => space_namespace(&space_key),
+    }
+    .map_err(|e| e.to_string())?;
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    let client = ConfluenceClient::from_db(db)
+        .map_err(|e| e.to_string())?
+        .ok_or("Confluence is not enabled")?;
+
+    // Ensure namespace exists
+    db.ensure_namespace_exists(&namespace_id)
+        .map_err(|e| e.to_string())?;
+
+    let ingester = ConfluenceIngester::new(client);
+    let result = tokio::task::block_in_place(|| {
+        tokio::runtime::Handle::current().block_on(ingester.ingest_space(
+            db,
+            &space_key,
+            &namespace_id,
+        ))
+    })
+    .map_err(|e| e.to_string())?;
+
+    crate::notifications::dispatch(
+        db,
+        &crate::notifications::Notification::ingestion_completed(
+            &namespace_id,
+            &format!("Confluence space {}", space_key),
+            result.ingested,
+            result.errors,
+        ),
+    );
+
+    Ok(ConfluenceIngestResponse {
+        namespace_id: result.namespace_id,
+        total_pages: result.total_pages,
+        ingested: result.ingested,
+        skipped: result.skipped,
+        removed: result.removed,
+        errors: result.errors,
+        documents: result
+            .documents
+            .into_iter()
+            .map(|d| IngestResult {
+                document_id: d.id,
+                title: d.title,
+                source_uri: d.source_uri,
+                chunk_count: d.chunk_count,
+                word_count: d.word_count,
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,144 @@
+//! Website crawl command and job runner
+
+use super::report_job_progress;
+use crate::jobs::{Job, JobContext, JobType};
+use crate::kb::ingest::crawl::{CrawlOptions, WebCrawler};
+use crate::kb::ingest::web::WebIngester;
+use crate::kb::ingest::CancellationToken;
+use crate::kb::network::canonicalize_url;
+use crate::validation::{normalize_and_validate_namespace_id, validate_url};
+use crate::AppState;
+use tauri::{AppHandle, Manager, State};
+
+/// Start a job that crawls a site from `url`, following links up to the
+/// depth and page limits in `options` and indexing each page into
+/// `namespace_id`. Progress is reported as pages fetched and indexed.
+/// Returns the job ID.
+#[tauri::command]
+pub fn crawl_url(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    url: String,
+    namespace_id: String,
+    options: Option<CrawlOptions>,
+) -> Result<String, String> {
+    let url = url.trim();
+    validate_url(url).map_err(|e| e.to_string())?;
+    let url = canonicalize_url(url).map_err(|e| e.to_string())?;
+    let namespace_id =
+        normalize_and_validate_namespace_id(&namespace_id).map_err(|e| e.to_string())?;
+    let options = options.unwrap_or_default();
+    options.validate().map_err(|e| e.to_string())?;
+
+    let job = Job::new(JobType::IngestWeb).with_metadata(serde_json::json!({
+        "params": {
+            "url": url,
+            "namespace_id": namespace_id,
+            "options": options,
+        },
+    }));
+    {
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+        db.ensure_namespace_exists(&namespace_id)
+            .map_err(|e| e.to_string())?;
+        db.create_job(&job).map_err(|e| e.to_string())?;
+    }
+
+    let job_id = job.id.clone();
+    tauri::async_runtime::spawn(super::schedules::run_job(app, None, job));
+    Ok(job_id)
+}
+
+/// Run an `ingest_web` crawl job and return its summary line. The database
+/// is locked only while a page is indexed, not during fetches or delays.
+pub(crate) async fn run_crawl_job(
+    app: &AppHandle,
+    ctx: &JobContext,
+    params: &serde_json::Value,
+) -> Result<String, String> {
+    let url = params["url"].as_str().ok_or("Crawl job has no URL")?;
+    let namespace_id = params["namespace_id"]
+        .as_str()
+        .ok_or("Crawl job has no namespace")?;
+    let options: CrawlOptions = match params.get("options") {
+        Some(options) if !options.is_null() => serde_json::from_value(options.clone())
+            .map_err(|e| format!("Invalid crawl options: {}", e))?,
+        _ => CrawlOptions::default(),
+    };
+    let max_pages = options.max_pages as u64;
+
+    let cancel_token = CancellationToken::new();
+    let mut crawler = WebCrawler::new(options).await.map_err(|e| e.to_string())?;
+    let report = crawler
+        .crawl(
+            url,
+            &cancel_token,
+            |page| {
+                let state = app.state::<AppState>();
+                let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+                let db = db_lock.as_ref().ok_or("Database not initialized")?;
+                WebIngester::index_page(db, page, namespace_id, &cancel_token, None)
+                    .map_err(|e| e.to_string())
+            },
+            |report, next_url| {
+                if ctx.is_cancelled() {
+                    cancel_token.cancel();
+                }
+                let message = format!(
+                    "Fetched {}, indexed {}: {}",
+                    report.pages_fetched, report.pages_indexed, next_url
+                );
+                report_job_progress(
+                    app,
+                    ctx,
+                    "crawling",
+                    report.pages_fetched as u64,
+                    Some(max_pages),
+                    &message,
+                );
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let summary = format!(
+        "Crawled {}: fetched {} pages, indexed {}, {} blocked by robots.txt, {} links filtered, {} errors",
+        url,
+        report.pages_fetched,
+        report.pages_indexed,
+        report.blocked_by_robots,
+        report.links_filtered,
+        report.errors
+    );
+    report_job_progress(
+        app,
+        ctx,
+        "crawling",
+        report.pages_fetched as u64,
+        Some(report.pages_fetched as u64),
+        &summary,
+    );
+
+    {
+        let state = app.state::<AppState>();
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        if let Some(db) = db_lock.as_ref() {
+            crate::notifications::dispatch(
+                db,
+                &crate::notifications::Notification::ingestion_completed(
+                    namespace_id,
+                    url,
+                    report.pages_indexed,
+                    report.errors,
+                ),
+            );
+        }
+    }
+
+    // Fail (and possibly retry) the run only when nothing could be fetched
+    if report.pages_fetched == 0 && report.errors > 0 {
+        return Err(format!("Could not fetch any page from {}", url));
+    }
+    Ok(summary)
+}
@@ -1,24 +1,64 @@
 //! Diagnostics and health check commands
 
+use crate::audit::AuditSeverity;
+use crate::crash::CrashReport;
+use crate::db::{
+    get_crashes_dir, get_db_path, get_downloads_dir, get_logs_dir, get_models_dir, get_vectors_dir,
+};
 use crate::diagnostics::{
-    check_database_health, check_embedding_health, check_filesystem_health, check_llm_health,
+    check_backup_health, check_crash_health, check_database_health, check_embedding_health,
+    check_filesystem_health, check_llm_health, cleanup_suggestions, directory_size,
     get_database_stats, get_failure_modes, get_resource_metrics, get_vector_maintenance_info,
     repair_database, run_database_maintenance, ComponentHealth, DatabaseStats, FailureMode,
-    HealthStatus, LlmResourceLimits, RepairResult, ResourceMetrics, SystemHealth,
+    HealthStatus, LlmResourceLimits, PerformanceProfile, ProfileStep, ReclaimableSpace,
+    RepairResult, ResourceMetrics, StorageBreakdown, StorageItem, SystemHealth,
     VectorMaintenanceInfo,
 };
+use crate::jobs::JobStatus;
+use crate::llm::{GenerationParams, LlmEngine};
+use crate::metrics::{
+    downsample, take_latencies, MetricsRange, MetricsSample, MetricsTimeseries,
+    METRICS_SAMPLE_INTERVAL_SECS,
+};
+use crate::support_bundle::{
+    summarize_errors, tail_file, write_bundle, SupportBundleSummary, MAX_AUDIT_ENTRIES,
+    MAX_LOG_FILES, MAX_LOG_TAIL_BYTES,
+};
 use crate::AppState;
-use tauri::State;
+use std::path::PathBuf;
+use std::time::Instant;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_dialog::DialogExt;
+
+/// Query used for the search and embedding steps of a performance profile
+const PROFILE_QUERY: &str = "how do I reset my VPN password";
+
+/// Prompt used for the generation step of a performance profile
+const PROFILE_PROMPT: &str = "Reply with one short sentence confirming you are ready to help.";
+const PROFILE_MAX_TOKENS: u32 = 32;
+const PROFILE_SEARCH_LIMIT: usize = 10;
+
+/// Profile runs returned when no limit is given
+const DEFAULT_PROFILE_HISTORY: usize = 20;
+
+/// Most recent autosaves per draft that cleanup keeps
+const AUTOSAVE_KEEP_COUNT: usize = 10;
+
+/// Failed jobs whose errors are summarized in a support bundle
+const MAX_BUNDLE_FAILED_JOBS: usize = 200;
 
 /// Get comprehensive system health status
 #[tauri::command]
 pub async fn get_system_health(state: State<'_, AppState>) -> Result<SystemHealth, String> {
-    // Check database
-    let database = {
+    // Check database and backups
+    let (database, backups) = {
         let db_lock = state.db.lock().map_err(|e| e.to_string())?;
         match db_lock.as_ref() {
-            Some(db) => check_database_health(db),
-            None => ComponentHealth::unavailable("Database", "Not initialized"),
+            Some(db) => (check_database_health(db), check_backup_health(db)),
+            None => (
+                ComponentHealth::unavailable("Database", "Not initialized"),
+                ComponentHealth::unavailable("Backups", "Database not initialized"),
+            ),
         }
     };
 
@@ -43,13 +83,18 @@ pub async fn get_system_health(state: State<'_, AppState>) -> Result<SystemHealt
     // Check file system
     let file_system = check_filesystem_health();
 
+    // Check for crash reports from previous sessions
+    let crash_reports = check_crash_health();
+
     // Calculate overall status
     let overall_status = database
         .status
         .worst(vector_store.status)
         .worst(llm_engine.status)
         .worst(embedding_model.status)
-        .worst(file_system.status);
+        .worst(file_system.status)
+        .worst(backups.status)
+        .worst(crash_reports.status);
 
     Ok(SystemHealth {
         database,
@@ -57,6 +102,8 @@ pub async fn get_system_health(state: State<'_, AppState>) -> Result<SystemHealt
         llm_engine,
         embedding_model,
         file_system,
+        backups,
+        crash_reports,
         overall_status,
         checked_at: chrono::Utc::now().to_rfc3339(),
     })
@@ -238,3 +285,546 @@ pub async fn get_vector_maintenance_info_cmd(
     let vectors = state.vectors.read().await;
     Ok(get_vector_maintenance_info(vectors.as_ref()).await)
 }
+
+/// Time a standard workload (FTS search, embedding, vector search, a short
+/// generation and a DB write) and store the result, so slowdowns can be
+/// compared against earlier runs. Steps whose component isn't loaded are
+/// skipped.
+#[tauri::command]
+pub async fn run_performance_profile(
+    state: State<'_, AppState>,
+) -> Result<PerformanceProfile, String> {
+    let mut steps = Vec::new();
+
+    // FTS search
+    {
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+        let start = Instant::now();
+        steps.push(match db.fts_search(PROFILE_QUERY, PROFILE_SEARCH_LIMIT) {
+            Ok(results) => ProfileStep::ok(
+                "fts_search",
+                start.elapsed(),
+                format!("{} results", results.len()),
+            ),
+            Err(e) => ProfileStep::failed("fts_search", start.elapsed(), &e.to_string()),
+        });
+    }
+
+    // Query embedding
+    let embedding = {
+        let embeddings = state.embeddings.read();
+        match embeddings.as_ref().filter(|e| e.is_model_loaded()) {
+            Some(engine) => {
+                let start = Instant::now();
+                match engine.embed(PROFILE_QUERY) {
+                    Ok(embedding) => {
+                        steps.push(ProfileStep::ok(
+                            "embed",
+                            start.elapsed(),
+                            format!("{} dimensions", embedding.len()),
+                        ));
+                        Some(embedding)
+                    }
+                    Err(e) => {
+                        steps.push(ProfileStep::failed(
+                            "embed",
+                            start.elapsed(),
+                            &e.to_string(),
+                        ));
+                        None
+                    }
+                }
+            }
+            None => {
+                steps.push(ProfileStep::skipped("embed", "Embedding model not loaded"));
+                None
+            }
+        }
+    };
+
+    // Vector search
+    {
+        let vectors = state.vectors.read().await;
+        let step = match (vectors.as_ref().filter(|v| v.is_enabled()), embedding) {
+            (Some(store), Some(embedding)) => {
+                let start = Instant::now();
+                match store.search_similar(&embedding, PROFILE_SEARCH_LIMIT).await {
+                    Ok(results) => ProfileStep::ok(
+                        "vector_search",
+                        start.elapsed(),
+                        format!("{} results", results.len()),
+                    ),
+                    Err(e) => ProfileStep::failed("vector_search", start.elapsed(), &e.to_string()),
+                }
+            }
+            (None, _) => ProfileStep::skipped("vector_search", "Vector search disabled"),
+            (Some(_), None) => ProfileStep::skipped("vector_search", "No query embedding"),
+        };
+        steps.push(step);
+    }
+
+    // Short generation
+    let engine_state = state
+        .llm
+        .read()
+        .as_ref()
+        .filter(|e| e.is_model_loaded())
+        .map(|e| e.state.clone());
+    match engine_state {
+        Some(engine_state) => {
+            let engine = LlmEngine {
+                state: engine_state,
+            };
+            let params = GenerationParams {
+                max_tokens: PROFILE_MAX_TOKENS,
+                ..Default::default()
+            };
+            let start = Instant::now();
+            steps.push(match engine.generate(PROFILE_PROMPT, params).await {
+                Ok(text) => ProfileStep::ok(
+                    "generate",
+                    start.elapsed(),
+                    format!("{} characters", text.len()),
+                ),
+                Err(e) => ProfileStep::failed("generate", start.elapsed(), &e.to_string()),
+            });
+        }
+        None => steps.push(ProfileStep::skipped("generate", "No model loaded")),
+    }
+
+    // DB write, then store the profile
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    let start = Instant::now();
+    let write = db
+        .conn()
+        .execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES ('performance_probe', ?)",
+            [chrono::Utc::now().to_rfc3339()],
+        )
+        .and_then(|_| {
+            db.conn()
+                .execute("DELETE FROM settings WHERE key = 'performance_probe'", [])
+        });
+    steps.push(match write {
+        Ok(_) => ProfileStep::ok("db_write", start.elapsed(), "insert + delete".to_string()),
+        Err(e) => ProfileStep::failed("db_write", start.elapsed(), &e.to_string()),
+    });
+
+    let profile = PerformanceProfile::new(steps);
+    db.save_performance_profile(&profile)
+        .map_err(|e| e.to_string())?;
+    Ok(profile)
+}
+
+/// List stored performance profile runs, newest first
+#[tauri::command]
+pub fn list_performance_profiles(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<PerformanceProfile>, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.list_performance_profiles(limit.unwrap_or(DEFAULT_PROFILE_HISTORY))
+        .map_err(|e| e.to_string())
+}
+
+/// Report disk usage by storage area (database, vectors, models, downloads,
+/// backups, logs, autosaves) and by KB namespace, with suggested cleanups
+#[tauri::command]
+pub async fn get_storage_breakdown(state: State<'_, AppState>) -> Result<StorageBreakdown, String> {
+    let vector_dim = {
+        let vectors = state.vectors.read().await;
+        vectors
+            .as_ref()
+            .filter(|v| v.is_enabled())
+            .map(|v| v.embedding_dim())
+    };
+
+    let mut items = Vec::new();
+    let mut space = ReclaimableSpace::default();
+
+    // Database file, including the WAL and shared-memory files
+    let db_path = get_db_path();
+    let db_file_bytes: u64 = ["", "-wal", "-shm"]
+        .iter()
+        .filter_map(|suffix| {
+            let mut path = db_path.clone().into_os_string();
+            path.push(suffix);
+            std::fs::metadata(path).ok().map(|m| m.len())
+        })
+        .sum();
+    items.push(StorageItem {
+        name: "database".to_string(),
+        path: Some(db_path.display().to_string()),
+        size_bytes: db_file_bytes,
+        item_count: 1,
+    });
+
+    let (mut namespaces, loaded_models, backups_folder) = {
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+        let pragma = |name: &str| -> u64 {
+            db.conn()
+                .query_row(&format!("PRAGMA {}", name), [], |r| r.get::<_, i64>(0))
+                .map(|v| v.max(0) as u64)
+                .unwrap_or(0)
+        };
+        let page_size = pragma("page_size");
+        space.database_bytes = pragma("page_count") * page_size;
+        space.database_free_bytes = pragma("freelist_count") * page_size;
+
+        let autosaves = db
+            .get_autosave_usage(AUTOSAVE_KEEP_COUNT)
+            .map_err(|e| e.to_string())?;
+        items.push(StorageItem {
+            name: "autosaves".to_string(),
+            path: None,
+            size_bytes: autosaves.bytes.max(0) as u64,
+            item_count: autosaves.count.max(0) as u64,
+        });
+        space.old_autosaves = autosaves.reclaimable_count.max(0) as u64;
+        space.old_autosave_bytes = autosaves.reclaimable_bytes.max(0) as u64;
+
+        let loaded_models: Vec<PathBuf> = ["llm", "embeddings"]
+            .iter()
+            .filter_map(|model_type| db.get_model_state(model_type).ok().flatten())
+            .map(|(path, _)| std::fs::canonicalize(&path).unwrap_or_else(|_| path.into()))
+            .collect();
+        let backups_folder = crate::backup::backup_schedule_status(db)
+            .ok()
+            .flatten()
+            .map(|status| PathBuf::from(status.folder));
+        (
+            db.get_namespace_storage().map_err(|e| e.to_string())?,
+            loaded_models,
+            backups_folder,
+        )
+    };
+
+    let vectors_dir = get_vectors_dir();
+    let (size_bytes, item_count) = directory_size(&vectors_dir);
+    items.push(StorageItem {
+        name: "vectors".to_string(),
+        path: Some(vectors_dir.display().to_string()),
+        size_bytes,
+        item_count,
+    });
+    if let Some(dim) = vector_dim {
+        for namespace in &mut namespaces {
+            namespace.estimated_vector_bytes = namespace.chunk_count.max(0) as u64 * dim as u64 * 4;
+        }
+    }
+
+    // Models, and downloaded models that aren't the last loaded ones
+    let models_dir = get_models_dir();
+    let (size_bytes, item_count) = directory_size(&models_dir);
+    items.push(StorageItem {
+        name: "models".to_string(),
+        path: Some(models_dir.display().to_string()),
+        size_bytes,
+        item_count,
+    });
+    if !loaded_models.is_empty() {
+        if let Ok(entries) = std::fs::read_dir(&models_dir) {
+            for path in entries.flatten().map(|e| e.path()) {
+                let is_model = path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("gguf"));
+                let canonical = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+                if is_model && !loaded_models.contains(&canonical) {
+                    let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                    let name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    space.unused_models.push((name, size));
+                }
+            }
+        }
+    }
+
+    // Incomplete downloads
+    let downloads_dir = get_downloads_dir();
+    let (size_bytes, item_count) = directory_size(&downloads_dir);
+    items.push(StorageItem {
+        name: "downloads".to_string(),
+        path: Some(downloads_dir.display().to_string()),
+        size_bytes,
+        item_count,
+    });
+    if let Ok(entries) = std::fs::read_dir(&downloads_dir) {
+        for path in entries.flatten().map(|e| e.path()) {
+            if path.extension().is_some_and(|ext| ext == "partial") {
+                space.partial_downloads += 1;
+                space.partial_download_bytes +=
+                    std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            }
+        }
+    }
+
+    // Scheduled backups (other files in the backup folder aren't ours)
+    if let Some(folder) = backups_folder {
+        let backups = crate::backup::scheduled_backups(&folder).unwrap_or_default();
+        items.push(StorageItem {
+            name: "backups".to_string(),
+            path: Some(folder.display().to_string()),
+            size_bytes: backups
+                .iter()
+                .filter_map(|path| std::fs::metadata(path).ok())
+                .map(|m| m.len())
+                .sum(),
+            item_count: backups.len() as u64,
+        });
+    }
+
+    let logs_dir = get_logs_dir();
+    let (size_bytes, item_count) = directory_size(&logs_dir);
+    items.push(StorageItem {
+        name: "logs".to_string(),
+        path: Some(logs_dir.display().to_string()),
+        size_bytes,
+        item_count,
+    });
+    space.log_bytes = size_bytes;
+
+    // Autosaves live inside the database file, so they aren't added again
+    let total_bytes = items
+        .iter()
+        .filter(|item| item.path.is_some())
+        .map(|item| item.size_bytes)
+        .sum();
+
+    Ok(StorageBreakdown {
+        items,
+        namespaces,
+        total_bytes,
+        suggestions: cleanup_suggestions(&space),
+        checked_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Export a support bundle: system health, model/config info, anonymized
+/// error summaries, recent audit entries and log tails, with PII scrubbed
+#[tauri::command]
+pub async fn export_diagnostics_bundle(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SupportBundleSummary, String> {
+    let health = get_system_health(state.clone()).await?;
+    let vector_info = {
+        let vectors = state.vectors.read().await;
+        vectors
+            .as_ref()
+            .map(|v| (v.is_enabled(), v.embedding_dim()))
+    };
+    let llm_model = state.llm.read().as_ref().and_then(|e| e.model_info());
+    let embedding_model = state
+        .embeddings
+        .read()
+        .as_ref()
+        .and_then(|e| e.model_info());
+    let llm_limits = get_llm_resource_limits(state.clone())?;
+
+    let mut errors = Vec::new();
+    let (database_stats, job_counts) = {
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+        for job in db
+            .list_jobs(Some(JobStatus::Failed), MAX_BUNDLE_FAILED_JOBS)
+            .map_err(|e| e.to_string())?
+        {
+            if let Some(error) = job.error {
+                errors.push((format!("job:{}", job.job_type), error, job.updated_at));
+            }
+        }
+        (
+            get_database_stats(db, &get_db_path()).ok(),
+            db.get_job_counts().map_err(|e| e.to_string())?,
+        )
+    };
+
+    let audit_entries =
+        crate::audit::read_audit_entries(Some(MAX_AUDIT_ENTRIES)).unwrap_or_default();
+    errors.extend(
+        audit_entries
+            .iter()
+            .filter(|e| matches!(e.severity, AuditSeverity::Error | AuditSeverity::Critical))
+            .map(|e| (format!("audit:{}", e.event), e.message.clone(), e.timestamp)),
+    );
+
+    let to_json =
+        |value: serde_json::Value| serde_json::to_string_pretty(&value).unwrap_or_default();
+    let mut files = vec![
+        (
+            "manifest.json".to_string(),
+            to_json(serde_json::json!({
+                "created_at": chrono::Utc::now().to_rfc3339(),
+                "app_version": env!("CARGO_PKG_VERSION"),
+                "os": std::env::consts::OS,
+                "arch": std::env::consts::ARCH,
+            })),
+        ),
+        (
+            "system_health.json".to_string(),
+            serde_json::to_string_pretty(&health).map_err(|e| e.to_string())?,
+        ),
+        (
+            "config.json".to_string(),
+            to_json(serde_json::json!({
+                "llm_model": llm_model,
+                "embedding_model": embedding_model,
+                "vector_store": vector_info.map(|(enabled, dim)| serde_json::json!({
+                    "enabled": enabled,
+                    "embedding_dim": dim,
+                })),
+                "llm_resource_limits": llm_limits,
+                "database": database_stats,
+                "job_counts": job_counts,
+            })),
+        ),
+        (
+            "errors.json".to_string(),
+            serde_json::to_string_pretty(&summarize_errors(errors)).map_err(|e| e.to_string())?,
+        ),
+        (
+            "logs/audit.jsonl".to_string(),
+            audit_entries
+                .iter()
+                .filter_map(|e| serde_json::to_string(e).ok())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+    ];
+
+    // Crash reports the user opted in to sharing
+    for report in crate::crash::list_reports(&get_crashes_dir())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|r| r.include_in_bundle)
+    {
+        files.push((
+            format!("crashes/{}.json", report.id),
+            serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?,
+        ));
+    }
+
+    // Tails of the most recently modified log files
+    let mut logs: Vec<(std::time::SystemTime, PathBuf)> = std::fs::read_dir(get_logs_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| {
+                    let meta = e.metadata().ok().filter(|m| m.is_file())?;
+                    Some((meta.modified().ok()?, e.path()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    logs.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    for (_, path) in logs.into_iter().take(MAX_LOG_FILES) {
+        let Some(name) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            continue;
+        };
+        match tail_file(&path, MAX_LOG_TAIL_BYTES) {
+            Ok(content) => files.push((format!("logs/{}", name), content)),
+            Err(e) => tracing::warn!("Skipping log {} in support bundle: {}", name, e),
+        }
+    }
+
+    let default_filename = format!(
+        "assistsupport-diagnostics-{}.zip",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S")
+    );
+    let file_handle = app
+        .dialog()
+        .file()
+        .set_file_name(&default_filename)
+        .add_filter("ZIP Archive", &["zip"])
+        .blocking_save_file();
+
+    let path = file_handle.ok_or("Export cancelled")?;
+    let file_path = path
+        .as_path()
+        .ok_or_else(|| "Invalid file path".to_string())?;
+    write_bundle(file_path, &files).map_err(|e| e.to_string())
+}
+
+/// List crash reports from previous sessions, newest first
+#[tauri::command]
+pub fn list_crash_reports() -> Result<Vec<CrashReport>, String> {
+    crate::crash::list_reports(&get_crashes_dir()).map_err(|e| e.to_string())
+}
+
+/// Mark a crash report reviewed, and choose whether support bundles include it
+#[tauri::command]
+pub fn review_crash_report(id: String, include_in_bundle: bool) -> Result<CrashReport, String> {
+    crate::crash::review_report(&get_crashes_dir(), &id, include_in_bundle)
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a crash report
+#[tauri::command]
+pub fn delete_crash_report(id: String) -> Result<(), String> {
+    crate::crash::delete_report(&get_crashes_dir(), &id).map_err(|e| e.to_string())
+}
+
+/// Take a metrics sample every `METRICS_SAMPLE_INTERVAL_SECS` for the
+/// diagnostics dashboard. Samples are skipped while the database is locked.
+pub async fn run_metrics_sampler(app: AppHandle) {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(METRICS_SAMPLE_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        let (generation_latency_ms, search_latency_ms) = take_latencies();
+        let state = app.state::<AppState>();
+        let Ok(db_lock) = state.db.lock() else {
+            continue;
+        };
+        let Some(db) = db_lock.as_ref() else {
+            continue;
+        };
+
+        let job_queue_depth = db
+            .get_job_counts()
+            .map(|counts| {
+                counts
+                    .iter()
+                    .filter(|(status, _)| status == "queued")
+                    .map(|(_, count)| *count as u32)
+                    .sum()
+            })
+            .unwrap_or(0);
+        let sample = MetricsSample {
+            sampled_at: chrono::Utc::now(),
+            memory_bytes: get_resource_metrics().memory_bytes,
+            generation_latency_ms,
+            search_latency_ms,
+            job_queue_depth,
+        };
+        if let Err(e) = db.record_metrics_sample(&sample) {
+            tracing::warn!("Failed to record metrics sample: {}", e);
+        }
+    }
+}
+
+/// Metrics samples for `range`, averaged into buckets for charting
+#[tauri::command]
+pub fn get_metrics_timeseries(
+    state: State<'_, AppState>,
+    range: MetricsRange,
+) -> Result<MetricsTimeseries, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    let samples = db
+        .get_metrics_samples(chrono::Utc::now() - range.duration())
+        .map_err(|e| e.to_string())?;
+    Ok(MetricsTimeseries {
+        range,
+        bucket_secs: range.bucket().num_seconds(),
+        points: downsample(&samples, range.bucket()),
+    })
+}
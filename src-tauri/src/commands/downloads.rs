@@ -0,0 +1,88 @@
+//! Model download network settings (proxy, mirrors and bandwidth)
+
+use super::get_model_source;
+use crate::db::Database;
+use crate::downloads::{DownloadNetworkConfig, DOWNLOADS_INTEGRATION};
+use crate::security::{vault::Vault, TOKEN_DOWNLOAD_PROXY_PASSWORD};
+use crate::AppState;
+use serde::Serialize;
+use tauri::State;
+
+/// Download network settings as exposed to the UI (the proxy password is never returned)
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadNetworkSettings {
+    pub config: DownloadNetworkConfig,
+    pub has_proxy_password: bool,
+}
+
+fn has_proxy_password() -> bool {
+    Vault::get(TOKEN_DOWNLOAD_PROXY_PASSWORD)
+        .map(|t| t.is_some())
+        .unwrap_or(false)
+}
+
+/// Load the saved download network settings (defaults when unset)
+pub(crate) fn load_download_network_config(db: &Database) -> Result<DownloadNetworkConfig, String> {
+    let record = db
+        .get_integration_config(DOWNLOADS_INTEGRATION)
+        .map_err(|e| e.to_string())?;
+    Ok(record
+        .and_then(|r| r.config_json)
+        .map(|json| serde_json::from_str(&json))
+        .transpose()
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default())
+}
+
+/// Configure the download proxy, per-model mirrors and bandwidth limits.
+/// `proxy_password` is only updated when provided.
+#[tauri::command]
+pub fn configure_download_network(
+    state: State<'_, AppState>,
+    mut config: DownloadNetworkConfig,
+    proxy_password: Option<String>,
+) -> Result<(), String> {
+    config.validate().map_err(|e| e.to_string())?;
+    for model_id in config.mirrors.keys() {
+        get_model_source(model_id)?;
+    }
+    config.mirrors.retain(|_, mirrors| !mirrors.is_empty());
+
+    if let Some(password) = proxy_password.filter(|p| !p.is_empty()) {
+        Vault::put(TOKEN_DOWNLOAD_PROXY_PASSWORD, &password).map_err(|e| e.to_string())?;
+    }
+
+    let enabled = config.proxy.is_some()
+        || !config.mirrors.is_empty()
+        || config.max_bytes_per_sec.is_some()
+        || config.pause_during_generation;
+    let config_json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.set_integration_config(DOWNLOADS_INTEGRATION, enabled, Some(&config_json))
+        .map_err(|e| e.to_string())
+}
+
+/// Get download network settings (without the proxy password)
+#[tauri::command]
+pub fn get_download_network_config(
+    state: State<'_, AppState>,
+) -> Result<DownloadNetworkSettings, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    Ok(DownloadNetworkSettings {
+        config: load_download_network_config(db)?,
+        has_proxy_password: has_proxy_password(),
+    })
+}
+
+/// Remove the download proxy, its password, all mirrors and bandwidth limits
+#[tauri::command]
+pub fn clear_download_network_config(state: State<'_, AppState>) -> Result<(), String> {
+    let _ = Vault::delete(TOKEN_DOWNLOAD_PROXY_PASSWORD);
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.set_integration_config(DOWNLOADS_INTEGRATION, false, None)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,189 @@
+//! SMTP email commands
+
+use super::{parse_draft_export_format, render_draft_export};
+use crate::audit;
+use crate::email::{
+    self, EmailAttachment, OutgoingEmail, SmtpConfig, SmtpMailer, SMTP_INTEGRATION,
+};
+use crate::exports::{ExportFormat, SafeExportOptions};
+use crate::security::{vault::Vault, TOKEN_SMTP_PASSWORD};
+use crate::AppState;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tauri::State;
+
+/// SMTP settings as exposed to the UI (the password is never returned)
+#[derive(Debug, Clone, Serialize)]
+pub struct SmtpSettings {
+    pub enabled: bool,
+    pub config: Option<SmtpConfig>,
+    pub has_password: bool,
+}
+
+fn has_password() -> bool {
+    Vault::get(TOKEN_SMTP_PASSWORD)
+        .map(|t| t.is_some())
+        .unwrap_or(false)
+}
+
+fn attachment_for(format: ExportFormat, base_name: &str, data: String) -> EmailAttachment {
+    let content_type = match format {
+        ExportFormat::Html | ExportFormat::TicketHtml | ExportFormat::StyledHtml(_) => {
+            "text/html; charset=utf-8"
+        }
+        ExportFormat::Json => "application/json",
+        ExportFormat::Plaintext => "text/plain; charset=utf-8",
+    };
+    EmailAttachment {
+        filename: format!("{}.{}", base_name, format.extension()),
+        content_type,
+        data: data.into_bytes(),
+    }
+}
+
+/// Configure SMTP email sending.
+/// `password` is only updated when provided, so the UI can change server
+/// settings and templates without re-entering it.
+#[tauri::command]
+pub fn configure_smtp(
+    state: State<'_, AppState>,
+    enabled: bool,
+    config: SmtpConfig,
+    password: Option<String>,
+) -> Result<(), String> {
+    config.validate().map_err(|e| e.to_string())?;
+
+    if let Some(password) = password.filter(|p| !p.is_empty()) {
+        Vault::put(TOKEN_SMTP_PASSWORD, &password).map_err(|e| e.to_string())?;
+    }
+
+    if enabled && config.username.is_some() && !has_password() {
+        return Err("An SMTP password is required when a username is set".to_string());
+    }
+
+    let config_json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.set_integration_config(SMTP_INTEGRATION, enabled, Some(&config_json))
+        .map_err(|e| e.to_string())
+}
+
+/// Get SMTP settings (without the password)
+#[tauri::command]
+pub fn get_smtp_config(state: State<'_, AppState>) -> Result<SmtpSettings, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    let record = db
+        .get_integration_config(SMTP_INTEGRATION)
+        .map_err(|e| e.to_string())?;
+
+    let (enabled, config) = match record {
+        Some(r) => {
+            let config = r
+                .config_json
+                .as_deref()
+                .map(serde_json::from_str)
+                .transpose()
+                .map_err(|e| e.to_string())?;
+            (r.enabled, config)
+        }
+        None => (false, None),
+    };
+
+    Ok(SmtpSettings {
+        enabled,
+        config,
+        has_password: has_password(),
+    })
+}
+
+/// Remove the SMTP password and settings
+#[tauri::command]
+pub fn clear_smtp_config(state: State<'_, AppState>) -> Result<(), String> {
+    let _ = Vault::delete(TOKEN_SMTP_PASSWORD);
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.set_integration_config(SMTP_INTEGRATION, false, None)
+        .map_err(|e| e.to_string())
+}
+
+/// Email a draft response.
+/// Subject and body come from the SMTP templates; the draft is attached in
+/// `attachment_format` ("plaintext", "html", "ticket_html", "json"), or not
+/// attached when `attachment_format` is `None`.
+#[tauri::command]
+pub async fn send_draft_email(
+    state: State<'_, AppState>,
+    draft_id: String,
+    to: Vec<String>,
+    cc: Option<Vec<String>>,
+    attachment_format: Option<String>,
+    safe_export: Option<SafeExportOptions>,
+) -> Result<(), String> {
+    let cc = cc.unwrap_or_default();
+    for address in to.iter().chain(cc.iter()) {
+        email::parse_address(address).map_err(|e| e.to_string())?;
+    }
+
+    // Build the message without holding the DB lock across the SMTP session
+    let (mailer, message, ticket_id) = {
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+        let mailer = SmtpMailer::from_db(db)
+            .map_err(|e| e.to_string())?
+            .ok_or("SMTP email is not enabled")?;
+        let draft = db.get_draft(&draft_id).map_err(|e| e.to_string())?;
+
+        let response = draft.response_text.clone().unwrap_or_default();
+        if response.trim().is_empty() {
+            return Err("Draft has no response to send".to_string());
+        }
+        let summary = draft.summary_text.clone().unwrap_or_default();
+        let title = summary
+            .lines()
+            .chain(draft.input_text.lines())
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or("Support request")
+            .chars()
+            .take(80)
+            .collect::<String>();
+
+        let vars = BTreeMap::from([
+            ("draft_id".to_string(), draft.id.clone()),
+            (
+                "ticket_id".to_string(),
+                draft.ticket_id.clone().unwrap_or_default(),
+            ),
+            ("title".to_string(), title),
+            ("summary".to_string(), summary),
+            ("response".to_string(), response),
+            (
+                "agent".to_string(),
+                draft.finalized_by.clone().unwrap_or_default(),
+            ),
+        ]);
+
+        let mut message = OutgoingEmail::from_templates(mailer.config(), &vars, to, cc);
+        if let Some(format) = attachment_format.as_deref() {
+            let format = parse_draft_export_format(format);
+            let base_name = match draft.ticket_id.as_deref() {
+                Some(ticket) if !ticket.is_empty() => format!("draft-{}", ticket),
+                _ => format!("draft-{}", draft.id),
+            };
+            let data = render_draft_export(&draft, format, safe_export.as_ref())?;
+            message = message.with_attachment(attachment_for(format, &base_name, data));
+        }
+        (mailer, message, draft.ticket_id)
+    };
+
+    let recipient_count = message.to.len() + message.cc.len();
+    match mailer.send(&message).await {
+        Ok(()) => {
+            audit::audit_draft_emailed(&draft_id, ticket_id.as_deref(), recipient_count);
+            Ok(())
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
@@ -0,0 +1,115 @@
+//! User-defined export template commands
+
+use super::prepare_draft_export;
+use crate::db::{CustomVariable, ExportTemplate, SavedDraft};
+use crate::export_templates::{draft_context, Template};
+use crate::exports::SafeExportOptions;
+use crate::AppState;
+use tauri::State;
+
+/// List all export templates
+#[tauri::command]
+pub fn list_export_templates(state: State<'_, AppState>) -> Result<Vec<ExportTemplate>, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.list_export_templates().map_err(|e| e.to_string())
+}
+
+/// Get a single export template by ID
+#[tauri::command]
+pub fn get_export_template(
+    state: State<'_, AppState>,
+    template_id: String,
+) -> Result<ExportTemplate, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.get_export_template(&template_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Create or update an export template, returning its ID.
+/// The content is parsed first so broken templates are rejected on save.
+#[tauri::command]
+pub fn save_export_template(
+    state: State<'_, AppState>,
+    mut template: ExportTemplate,
+) -> Result<String, String> {
+    template.name = template.name.trim().to_string();
+    if template.name.is_empty() {
+        return Err("Template name is required".to_string());
+    }
+    Template::parse(&template.content).map_err(|e| e.to_string())?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    if template.id.is_empty() {
+        template.id = uuid::Uuid::new_v4().to_string();
+    }
+    if template.created_at.is_empty() {
+        template.created_at = now.clone();
+    }
+    template.updated_at = now;
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.save_export_template(&template)
+        .map_err(|e| e.to_string())?;
+    Ok(template.id)
+}
+
+/// Delete an export template by ID
+#[tauri::command]
+pub fn delete_export_template(
+    state: State<'_, AppState>,
+    template_id: String,
+) -> Result<(), String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.delete_export_template(&template_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Export a draft using a saved export template
+#[tauri::command]
+pub fn export_draft_with_template(
+    state: State<'_, AppState>,
+    draft_id: String,
+    template_id: String,
+    safe_export: Option<SafeExportOptions>,
+) -> Result<String, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    let draft = db.get_draft(&draft_id).map_err(|e| e.to_string())?;
+    let template = db
+        .get_export_template(&template_id)
+        .map_err(|e| e.to_string())?;
+    let template = Template::parse(&template.content).map_err(|e| e.to_string())?;
+    let variables = db.list_custom_variables().map_err(|e| e.to_string())?;
+    render_with_template(&template, &variables, &draft, safe_export.as_ref())
+}
+
+/// Render unsaved template content against a draft (editor preview)
+#[tauri::command]
+pub fn preview_export_template(
+    state: State<'_, AppState>,
+    draft_id: String,
+    content: String,
+) -> Result<String, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    let draft = db.get_draft(&draft_id).map_err(|e| e.to_string())?;
+    let template = Template::parse(&content).map_err(|e| e.to_string())?;
+    let variables = db.list_custom_variables().map_err(|e| e.to_string())?;
+    render_with_template(&template, &variables, &draft, None)
+}
+
+/// Render a draft with a parsed template and the current custom variables
+pub(crate) fn render_with_template(
+    template: &Template,
+    variables: &[CustomVariable],
+    draft: &SavedDraft,
+    safe_export: Option<&SafeExportOptions>,
+) -> Result<String, String> {
+    let (draft, sources) = prepare_draft_export(draft, safe_export)?;
+    let context = draft_context(&draft, &sources, variables, safe_export);
+    Ok(template.render(&context))
+}
@@ -0,0 +1,235 @@
+//! RSS/Atom feed source commands
+
+use super::IngestResult;
+use crate::db::IngestSource;
+use crate::jobs::schedule::JobSchedule;
+use crate::jobs::JobType;
+use crate::kb::ingest::IngestError;
+use crate::kb::network::canonicalize_url;
+use crate::sources::feed::{feed_source, FeedIngester, FEED_SOURCE_TYPE};
+use crate::validation::{normalize_and_validate_namespace_id, validate_url};
+use crate::AppState;
+use serde::Serialize;
+use tauri::State;
+
+/// Poll schedule for new feeds: hourly
+const DEFAULT_FEED_POLL_CRON: &str = "0 * * * *";
+
+/// A registered feed with its polling schedule
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedInfo {
+    pub source: IngestSource,
+    pub schedule: Option<JobSchedule>,
+}
+
+/// Result of polling a feed
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedPollResponse {
+    pub source_id: String,
+    pub title: Option<String>,
+    pub total_entries: usize,
+    pub ingested: usize,
+    pub skipped: usize,
+    pub errors: usize,
+    pub documents: Vec<IngestResult>,
+}
+
+/// Schedule ID stored in a feed source's metadata
+fn schedule_id(source: &IngestSource) -> Option<String> {
+    let metadata: serde_json::Value =
+        serde_json::from_str(source.metadata_json.as_deref()?).ok()?;
+    metadata["schedule_id"].as_str().map(str::to_string)
+}
+
+fn get_feed(db: &crate::db::Database, source_id: &str) -> Result<IngestSource, String> {
+    let source = db
+        .get_ingest_source(source_id)
+        .map_err(|_| "Feed not found".to_string())?;
+    if source.source_type != FEED_SOURCE_TYPE {
+        return Err("Source is not a feed".to_string());
+    }
+    Ok(source)
+}
+
+/// Register a feed URL whose new entries are ingested into `namespace_id`.
+/// `cron` sets the polling schedule (five-field, local time; hourly by
+/// default). The feed is first polled when its schedule runs; call
+/// `poll_feed` to fetch it right away.
+#[tauri::command]
+pub fn add_feed(
+    state: State<'_, AppState>,
+    url: String,
+    namespace_id: String,
+    cron: Option<String>,
+) -> Result<FeedInfo, String> {
+    let url = url.trim();
+    validate_url(url).map_err(|e| e.to_string())?;
+    let url = canonicalize_url(url).map_err(|e| e.to_string())?;
+    let namespace_id =
+        normalize_and_validate_namespace_id(&namespace_id).map_err(|e| e.to_string())?;
+    let cron = cron
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| DEFAULT_FEED_POLL_CRON.to_string());
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    if db
+        .find_ingest_source(FEED_SOURCE_TYPE, &url, &namespace_id)
+        .map_err(|e| e.to_string())?
+        .is_some()
+    {
+        return Err("This feed is already registered in the namespace".to_string());
+    }
+    db.ensure_namespace_exists(&namespace_id)
+        .map_err(|e| e.to_string())?;
+
+    let mut source = feed_source(&url, &namespace_id, None);
+    let schedule = JobSchedule::new(
+        &format!("Poll feed {}", url),
+        &cron,
+        JobType::PollFeeds,
+        Some(serde_json::json!({ "source_id": source.id })),
+    )
+    .map_err(|e| e.to_string())?;
+    source.metadata_json = Some(serde_json::json!({ "schedule_id": schedule.id }).to_string());
+
+    db.save_ingest_source(&source).map_err(|e| e.to_string())?;
+    db.save_job_schedule(&schedule).map_err(|e| e.to_string())?;
+
+    Ok(FeedInfo {
+        source,
+        schedule: Some(schedule),
+    })
+}
+
+/// List registered feeds, optionally in one namespace
+#[tauri::command]
+pub fn list_feeds(
+    state: State<'_, AppState>,
+    namespace_id: Option<String>,
+) -> Result<Vec<FeedInfo>, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    let sources = db
+        .list_ingest_sources(namespace_id.as_deref())
+        .map_err(|e| e.to_string())?;
+    let mut feeds = Vec::new();
+    for source in sources
+        .into_iter()
+        .filter(|s| s.source_type == FEED_SOURCE_TYPE)
+    {
+        let schedule = match schedule_id(&source) {
+            Some(id) => db.get_job_schedule(&id).map_err(|e| e.to_string())?,
+            None => None,
+        };
+        feeds.push(FeedInfo { source, schedule });
+    }
+    Ok(feeds)
+}
+
+/// Fetch a feed now and ingest entries not seen before
+/// The feed is fetched without holding the DB lock; the lock is taken only
+/// to index its entries
+#[tauri::command]
+pub fn poll_feed(
+    state: State<'_, AppState>,
+    source_id: String,
+) -> Result<FeedPollResponse, String> {
+    let source = {
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+        get_feed(db, &source_id)?
+    };
+
+    let (ingester, fetched) = tokio::task::block_in_place(|| {
+        tokio::runtime::Handle::current().block_on(async {
+            let ingester = FeedIngester::new().await?;
+            let fetched = ingester.fetch(&source).await;
+            Ok::<_, IngestError>((ingester, fetched))
+        })
+    })
+    .map_err(|e| e.to_string())?;
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    let result = match fetched {
+        Ok(fetched) => ingester.index(db, &source, fetched),
+        Err(e) => {
+            FeedIngester::record_fetch_failure(db, &source, &e).map_err(|e| e.to_string())?;
+            Err(e)
+        }
+    }
+    .map_err(|e| e.to_string())?;
+
+    if result.ingested > 0 || result.errors > 0 {
+        crate::notifications::dispatch(
+            db,
+            &crate::notifications::Notification::ingestion_completed(
+                &source.namespace_id,
+                result.title.as_deref().unwrap_or(&source.source_uri),
+                result.ingested,
+                result.errors,
+            ),
+        );
+    }
+
+    Ok(FeedPollResponse {
+        source_id: result.source_id,
+        title: result.title,
+        total_entries: result.total_entries,
+        ingested: result.ingested,
+        skipped: result.skipped,
+        errors: result.errors,
+        documents: result
+            .documents
+            .into_iter()
+            .map(|d| IngestResult {
+                document_id: d.id,
+                title: d.title,
+                source_uri: d.source_uri,
+                chunk_count: d.chunk_count,
+                word_count: d.word_count,
+            })
+            .collect(),
+    })
+}
+
+/// Remove a feed, its polling schedule and the documents ingested from it
+#[tauri::command]
+pub fn remove_feed(state: State<'_, AppState>, source_id: String) -> Result<(), String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    let source = get_feed(db, &source_id)?;
+
+    if let Some(schedule_id) = schedule_id(&source) {
+        db.delete_job_schedule(&schedule_id)
+            .map_err(|e| e.to_string())?;
+    }
+    db.delete_documents_for_source(&source.id)
+        .map_err(|e| e.to_string())?;
+    db.delete_ingest_source(&source.id)
+        .map_err(|e| e.to_string())
+}
+
+/// IDs of the feeds a `poll_feeds` job covers: `params.source_id`, or every
+/// registered feed when absent
+pub(crate) fn feeds_to_poll(
+    state: State<'_, AppState>,
+    params: &serde_json::Value,
+) -> Result<Vec<String>, String> {
+    if let Some(source_id) = params["source_id"].as_str() {
+        return Ok(vec![source_id.to_string()]);
+    }
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    Ok(db
+        .list_ingest_sources(None)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|s| s.source_type == FEED_SOURCE_TYPE)
+        .map(|s| s.id)
+        .collect())
+}
@@ -0,0 +1,204 @@
+//! Follow-up reminder commands
+
+use crate::db::FollowUp;
+use crate::follow_ups::{self, follow_up_title};
+use crate::validation::{validate_ticket_id, validate_within_home};
+use crate::AppState;
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_notification::NotificationExt;
+
+const DEFAULT_LIST_LIMIT: usize = 200;
+
+/// Create a follow-up reminder on a draft and/or ticket.
+/// Provide either `due_at` (RFC 3339) or `due_in_days`. When only a draft is
+/// given, the draft's ticket ID is used.
+#[tauri::command]
+pub fn create_follow_up(
+    state: State<'_, AppState>,
+    draft_id: Option<String>,
+    ticket_id: Option<String>,
+    due_at: Option<String>,
+    due_in_days: Option<u32>,
+    note: Option<String>,
+) -> Result<FollowUp, String> {
+    let due_at = match (due_at.as_deref(), due_in_days) {
+        (Some(due_at), _) => follow_ups::normalize_due_at(due_at)?,
+        (None, Some(days)) => follow_ups::due_in_days(Utc::now(), days),
+        (None, None) => return Err("A due date is required".to_string()),
+    };
+    if let Some(ticket) = ticket_id.as_deref() {
+        validate_ticket_id(ticket).map_err(|e| e.to_string())?;
+    }
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    let ticket_id = match (&draft_id, ticket_id) {
+        (_, Some(ticket)) => Some(ticket),
+        (Some(draft_id), None) => db.get_draft(draft_id).map_err(|e| e.to_string())?.ticket_id,
+        (None, None) => return Err("A draft or ticket ID is required".to_string()),
+    };
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+
+    db.create_follow_up(
+        draft_id.as_deref(),
+        ticket_id.as_deref(),
+        &due_at,
+        note.as_deref(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// List follow-ups ordered by due date
+#[tauri::command]
+pub fn list_follow_ups(
+    state: State<'_, AppState>,
+    include_completed: Option<bool>,
+    limit: Option<usize>,
+) -> Result<Vec<FollowUp>, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.list_follow_ups(
+        include_completed.unwrap_or(false),
+        limit.unwrap_or(DEFAULT_LIST_LIMIT),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// List pending follow-ups that are due now or overdue
+#[tauri::command]
+pub fn list_due_follow_ups(state: State<'_, AppState>) -> Result<Vec<FollowUp>, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.list_due_follow_ups(&follow_ups::format_timestamp(Utc::now()))
+        .map_err(|e| e.to_string())
+}
+
+/// List pending follow-ups for a draft
+#[tauri::command]
+pub fn get_draft_follow_ups(
+    state: State<'_, AppState>,
+    draft_id: String,
+) -> Result<Vec<FollowUp>, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.list_follow_ups_for_draft(&draft_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Move a follow-up to a new due date
+#[tauri::command]
+pub fn reschedule_follow_up(
+    state: State<'_, AppState>,
+    follow_up_id: String,
+    due_at: String,
+) -> Result<(), String> {
+    let due_at = follow_ups::normalize_due_at(&due_at)?;
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.reschedule_follow_up(&follow_up_id, &due_at)
+        .map_err(|e| e.to_string())
+}
+
+/// Mark a follow-up as done
+#[tauri::command]
+pub fn complete_follow_up(state: State<'_, AppState>, follow_up_id: String) -> Result<(), String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.complete_follow_up(&follow_up_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a follow-up
+#[tauri::command]
+pub fn delete_follow_up(state: State<'_, AppState>, follow_up_id: String) -> Result<(), String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.delete_follow_up(&follow_up_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Show a local notification for each newly due follow-up and emit
+/// `follow-up:due` with the list. Each follow-up notifies once until it is
+/// rescheduled; the UI polls this periodically.
+#[tauri::command]
+pub fn notify_due_follow_ups(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<FollowUp>, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    let due: Vec<FollowUp> = db
+        .list_due_follow_ups(&follow_ups::format_timestamp(Utc::now()))
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|f| f.notified_at.is_none())
+        .collect();
+
+    for follow_up in &due {
+        let body = follow_up
+            .note
+            .clone()
+            .unwrap_or_else(|| "This follow-up is due.".to_string());
+        if let Err(e) = app
+            .notification()
+            .builder()
+            .title(follow_up_title(follow_up))
+            .body(body)
+            .show()
+        {
+            tracing::warn!("Failed to show follow-up notification: {}", e);
+        }
+        db.mark_follow_up_notified(&follow_up.id)
+            .map_err(|e| e.to_string())?;
+    }
+
+    if !due.is_empty() {
+        let _ = app.emit("follow-up:due", &due);
+    }
+    Ok(due)
+}
+
+/// Export follow-ups to an ICS calendar file and return its path.
+/// Exports the given IDs, or all pending follow-ups when `follow_up_ids` is
+/// omitted. Writes to `path` (within the home directory) or the exports folder.
+#[tauri::command]
+pub fn export_follow_ups_ics(
+    state: State<'_, AppState>,
+    follow_up_ids: Option<Vec<String>>,
+    path: Option<String>,
+) -> Result<String, String> {
+    let follow_ups = {
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+        match follow_up_ids {
+            Some(ids) => ids
+                .iter()
+                .map(|id| db.get_follow_up(id))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| e.to_string())?,
+            None => db
+                .list_follow_ups(false, DEFAULT_LIST_LIMIT)
+                .map_err(|e| e.to_string())?,
+        }
+    };
+    if follow_ups.is_empty() {
+        return Err("No follow-ups to export".to_string());
+    }
+
+    let now = Utc::now();
+    let path: PathBuf = match path {
+        Some(path) => validate_within_home(Path::new(&path)).map_err(|e| e.to_string())?,
+        None => {
+            let export_dir = crate::db::get_app_data_dir().join("exports");
+            std::fs::create_dir_all(&export_dir).map_err(|e| e.to_string())?;
+            export_dir.join(format!("follow_ups_{}.ics", now.format("%Y%m%d_%H%M%S")))
+        }
+    };
+
+    std::fs::write(&path, follow_ups::to_ics(&follow_ups, now)).map_err(|e| e.to_string())?;
+    Ok(path.to_string_lossy().to_string())
+}
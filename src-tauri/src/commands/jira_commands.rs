@@ -1,4 +1,5 @@
 use super::*;
+use crate::jira::DraftFieldValues;
 
 pub(crate) fn is_jira_configured_impl(state: State<'_, AppState>) -> Result<bool, String> {
     let db_lock = state.db.lock().map_err(|e| e.to_string())?;
@@ -10,9 +11,7 @@ pub(crate) fn is_jira_configured_impl(state: State<'_, AppState>) -> Result<bool
         |row| row.get(0),
     );
 
-    let has_token = FileKeyStore::get_token(TOKEN_JIRA)
-        .map(|t| t.is_some())
-        .unwrap_or(false);
+    let has_token = Vault::get(TOKEN_JIRA).map(|t| t.is_some()).unwrap_or(false);
 
     Ok(base_url.is_ok() && has_token)
 }
@@ -73,8 +72,7 @@ pub(crate) async fn configure_jira_impl(
     }
 
     // Store token in file storage
-    FileKeyStore::store_token(TOKEN_JIRA, &api_token).map_err(|e| e.to_string())?;
-    audit::audit_token_set("jira");
+    Vault::put(TOKEN_JIRA, &api_token).map_err(|e| e.to_string())?;
 
     // Store config in DB
     let db_lock = state.db.lock().map_err(|e| e.to_string())?;
@@ -120,8 +118,7 @@ pub(crate) async fn configure_jira_impl(
 
 pub(crate) fn clear_jira_config_impl(state: State<'_, AppState>) -> Result<(), String> {
     // Delete token from file storage
-    let _ = FileKeyStore::delete_token(TOKEN_JIRA);
-    audit::audit_token_cleared("jira");
+    let _ = Vault::delete(TOKEN_JIRA);
 
     // Delete config from DB
     let db_lock = state.db.lock().map_err(|e| e.to_string())?;
@@ -136,3 +133,113 @@ pub(crate) fn clear_jira_config_impl(state: State<'_, AppState>) -> Result<(), S
 
     Ok(())
 }
+
+fn load_jira_field_mappings(db: &Database) -> Result<Vec<JiraFieldMapping>, String> {
+    let raw: Result<String, _> = db.conn().query_row(
+        "SELECT value FROM settings WHERE key = ?",
+        rusqlite::params![JIRA_FIELD_MAPPINGS_SETTING],
+        |row| row.get(0),
+    );
+
+    match raw {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| e.to_string()),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(Vec::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn store_jira_field_mappings(db: &Database, mappings: &[JiraFieldMapping]) -> Result<(), String> {
+    let json = serde_json::to_string(mappings).map_err(|e| e.to_string())?;
+    db.conn()
+        .execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+            rusqlite::params![JIRA_FIELD_MAPPINGS_SETTING, json],
+        )
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Find the custom field mapping for the project a ticket belongs to
+pub(crate) fn find_jira_field_mapping(
+    db: &Database,
+    ticket_key: &str,
+) -> Result<Option<JiraFieldMapping>, String> {
+    let Some(project_key) = crate::jira::project_key_for_ticket(ticket_key) else {
+        return Ok(None);
+    };
+    Ok(load_jira_field_mappings(db)?
+        .into_iter()
+        .find(|m| m.project_key.eq_ignore_ascii_case(project_key)))
+}
+
+/// Resolve the draft metadata values that can be mapped onto Jira fields
+pub(crate) fn draft_field_values(draft: &crate::db::SavedDraft) -> DraftFieldValues {
+    let intake: crate::db::CaseIntake = draft
+        .case_intake_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default();
+
+    let diagnostic_notes = draft
+        .diagnosis_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(json).ok())
+        .and_then(|v| v["notes"].as_str().map(|s| s.to_string()));
+
+    let kb_article_link = draft
+        .kb_sources_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<Vec<serde_json::Value>>(json).ok())
+        .and_then(|sources| {
+            sources
+                .iter()
+                .find_map(|s| s["url"].as_str().map(|u| u.to_string()))
+        });
+
+    DraftFieldValues {
+        resolution_category: intake.custom_fields.get("resolution_category").cloned(),
+        root_cause: intake
+            .custom_fields
+            .get("root_cause")
+            .cloned()
+            .or(diagnostic_notes),
+        kb_article_link,
+    }
+}
+
+pub(crate) fn get_jira_field_mappings_impl(
+    state: State<'_, AppState>,
+) -> Result<Vec<JiraFieldMapping>, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    load_jira_field_mappings(db)
+}
+
+pub(crate) fn save_jira_field_mapping_impl(
+    state: State<'_, AppState>,
+    mut mapping: JiraFieldMapping,
+) -> Result<(), String> {
+    mapping.project_key = mapping.project_key.trim().to_uppercase();
+    mapping.validate().map_err(|e| e.to_string())?;
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    let mut mappings = load_jira_field_mappings(db)?;
+    mappings.retain(|m| m.project_key != mapping.project_key);
+    mappings.push(mapping);
+    mappings.sort_by(|a, b| a.project_key.cmp(&b.project_key));
+    store_jira_field_mappings(db, &mappings)
+}
+
+pub(crate) fn delete_jira_field_mapping_impl(
+    state: State<'_, AppState>,
+    project_key: String,
+) -> Result<(), String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    let mut mappings = load_jira_field_mappings(db)?;
+    mappings.retain(|m| !m.project_key.eq_ignore_ascii_case(project_key.trim()));
+    store_jira_field_mappings(db, &mappings)
+}
@@ -3,6 +3,9 @@ use std::sync::Mutex as StdMutex;
 
 use crate::kb::watcher::KbWatcher;
 
+/// Settings key holding the last KB health warning signature (for notification dedupe)
+const KB_HEALTH_WARNING_SETTING: &str = "notifications_last_kb_health_warning";
+
 /// Global watcher instance
 static KB_WATCHER: Lazy<StdMutex<Option<KbWatcher>>> = Lazy::new(|| StdMutex::new(None));
 
@@ -62,9 +65,103 @@ pub(crate) fn get_kb_folder_impl(state: State<'_, AppState>) -> Result<Option<St
     }
 }
 
-pub(crate) async fn index_kb_impl(
-    window: tauri::Window,
+/// Whether images embedded in DOCX/ODT files are OCR'd during indexing (default off)
+fn office_image_ocr_enabled(db: &Database) -> Result<bool, String> {
+    let result: Result<String, _> = db.conn().query_row(
+        "SELECT value FROM settings WHERE key = ?",
+        rusqlite::params![KB_OFFICE_IMAGE_OCR_SETTING],
+        |row| row.get(0),
+    );
+
+    match result {
+        Ok(value) => Ok(value == "true"),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(false),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+pub(crate) fn get_kb_office_image_ocr_impl(state: State<'_, AppState>) -> Result<bool, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    office_image_ocr_enabled(db)
+}
+
+pub(crate) fn set_kb_office_image_ocr_impl(
     state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    db.conn()
+        .execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+            rusqlite::params![KB_OFFICE_IMAGE_OCR_SETTING, enabled.to_string()],
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Spreadsheet indexing options, defaulting when unset or unreadable
+fn tabular_options(db: &Database) -> Result<TabularOptions, String> {
+    let result: Result<String, _> = db.conn().query_row(
+        "SELECT value FROM settings WHERE key = ?",
+        rusqlite::params![KB_TABULAR_OPTIONS_SETTING],
+        |row| row.get(0),
+    );
+
+    match result {
+        Ok(value) => Ok(serde_json::from_str(&value).unwrap_or_default()),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(TabularOptions::default()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+pub(crate) fn get_kb_tabular_options_impl(
+    state: State<'_, AppState>,
+) -> Result<TabularOptions, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    tabular_options(db)
+}
+
+pub(crate) fn set_kb_tabular_options_impl(
+    state: State<'_, AppState>,
+    mut options: TabularOptions,
+) -> Result<(), String> {
+    if options.rows_per_chunk == 0 || options.rows_per_chunk > MAX_ROWS_PER_CHUNK {
+        return Err(format!(
+            "Rows per chunk must be between 1 and {}",
+            MAX_ROWS_PER_CHUNK
+        ));
+    }
+    for names in [&mut options.sheets, &mut options.columns] {
+        *names = names
+            .iter()
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty())
+            .collect();
+    }
+    let value = serde_json::to_string(&options).map_err(|e| e.to_string())?;
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    db.conn()
+        .execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+            rusqlite::params![KB_TABULAR_OPTIONS_SETTING, value],
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+pub(crate) async fn index_kb_impl<R: tauri::Runtime>(
+    window: impl Emitter<R>,
+    state: State<'_, AppState>,
+    on_progress: impl Fn(&crate::kb::indexer::IndexProgress),
 ) -> Result<IndexResult, String> {
     let db_lock = state.db.lock().map_err(|e| e.to_string())?;
     let db = db_lock.as_ref().ok_or("Database not initialized")?;
@@ -85,17 +182,60 @@ pub(crate) async fn index_kb_impl(
     }
 
     // Run indexing with progress events
-    let indexer = KbIndexer::new();
+    let indexer = KbIndexer::new()
+        .with_office_image_ocr(office_image_ocr_enabled(db)?)
+        .with_tabular_options(tabular_options(db)?);
     let result = indexer
         .index_folder(db, &validated_path, |progress| {
             // Emit progress event to frontend
             let _ = window.emit("kb:indexing:progress", &progress);
+            on_progress(&progress);
         })
         .map_err(|e| e.to_string())?;
 
+    notify_kb_health_warnings(db);
+
     Ok(result)
 }
 
+/// Send a KB health warning notification when the set of warnings changed
+/// since the last check. Runs after indexing so viewing health stats never
+/// triggers outbound notifications.
+fn notify_kb_health_warnings(db: &Database) {
+    let stats = match db.get_kb_health_stats() {
+        Ok(stats) => stats,
+        Err(e) => {
+            tracing::warn!("Failed to check KB health: {}", e);
+            return;
+        }
+    };
+
+    // Notify only when the set of warnings changes, not on every index run
+    let warnings = stats.warnings();
+    let signature = warnings.join("|");
+    let previous: Option<String> = db
+        .conn()
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?",
+            rusqlite::params![KB_HEALTH_WARNING_SETTING],
+            |row| row.get(0),
+        )
+        .ok();
+    if previous.as_deref().unwrap_or("") == signature {
+        return;
+    }
+    let _ = db.conn().execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+        rusqlite::params![KB_HEALTH_WARNING_SETTING, &signature],
+    );
+    if !warnings.is_empty() {
+        crate::notifications::dispatch(
+            db,
+            &crate::notifications::Notification::kb_health_warning(&warnings),
+        );
+    }
+}
+
 pub(crate) fn get_kb_stats_impl(state: State<'_, AppState>) -> Result<IndexStats, String> {
     let db_lock = state.db.lock().map_err(|e| e.to_string())?;
     let db = db_lock.as_ref().ok_or("Database not initialized")?;
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 const MEMORY_KERNEL_ENABLE_ENV: &str = "ASSISTSUPPORT_ENABLE_MEMORY_KERNEL";
 const MEMORY_KERNEL_BASE_URL_ENV: &str = "ASSISTSUPPORT_MEMORY_KERNEL_BASE_URL";
 const MEMORY_KERNEL_TIMEOUT_MS_ENV: &str = "ASSISTSUPPORT_MEMORY_KERNEL_TIMEOUT_MS";
+const MEMORY_KERNEL_API_KEY_ENV: &str = "ASSISTSUPPORT_MEMORY_KERNEL_API_KEY";
 const FALLBACK_REASON_FEATURE_DISABLED: &str = "feature-disabled";
 const FALLBACK_REASON_OFFLINE: &str = "offline";
 const FALLBACK_REASON_TIMEOUT: &str = "timeout";
@@ -21,6 +22,9 @@ const FALLBACK_REASON_NETWORK_ERROR: &str = "network-error";
 const FALLBACK_REASON_QUERY_ERROR: &str = "query-error";
 const FALLBACK_REASON_EMPTY_CONTEXT: &str = "empty-context";
 const FALLBACK_REASON_UNKNOWN: &str = "unknown";
+const POLICY_GATE_MAX_RELATED: usize = 3;
+const DECISION_SUMMARY_MAX_CHARS: usize = 280;
+const DECISION_DEFAULT_WRITER: &str = "assistsupport";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryKernelIntegrationPin {
@@ -63,6 +67,86 @@ pub struct MemoryKernelEnrichmentResult {
     pub preflight: MemoryKernelPreflightStatus,
 }
 
+/// Memory item a policy gate decision relies on, cited by `memory_version_id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryKernelPolicyCitation {
+    pub memory_version_id: String,
+    pub record_type: String,
+    pub rank: u64,
+    pub reason: Option<String>,
+}
+
+/// Outcome of the policy gate run before a draft is generated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryKernelPolicyGateResult {
+    /// Whether MemoryKernel answered; `false` means drafting proceeds without policy context.
+    pub applied: bool,
+    /// `allowed`, `blocked`, or `fallback`.
+    pub status: String,
+    /// Policy answer (`allow`, `deny`, or `inconclusive`); `None` in fallback.
+    pub decision: Option<String>,
+    /// Whether drafting must be refused because a deny constraint applies.
+    pub blocked: bool,
+    pub message: String,
+    pub fallback_reason: Option<String>,
+    pub machine_error_code: Option<String>,
+    pub ask_context_package_id: Option<String>,
+    pub recall_context_package_id: Option<String>,
+    /// Constraints selected by the policy ask, highest ranked first; a deny cites the first.
+    pub constraints: Vec<MemoryKernelPolicyCitation>,
+    /// Decisions, outcomes, and other memory selected by recall.
+    pub related: Vec<MemoryKernelPolicyCitation>,
+    /// Section injected into the draft prompt; `None` in fallback.
+    pub prompt_context: Option<String>,
+    pub preflight: MemoryKernelPreflightStatus,
+}
+
+/// Outcome of recording a finalized draft as a MemoryKernel decision.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryKernelDecisionRecordResult {
+    pub recorded: bool,
+    /// `recorded`, `skipped` (no consent or opted out), or `fallback`.
+    pub status: String,
+    pub message: String,
+    pub fallback_reason: Option<String>,
+    pub machine_error_code: Option<String>,
+    pub memory_id: Option<String>,
+    pub memory_version_id: Option<String>,
+    /// `None` when skipped before contacting the service.
+    pub preflight: Option<MemoryKernelPreflightStatus>,
+}
+
+/// Finalized draft to record as a decision.
+pub(crate) struct FinalizedDraftDecision {
+    pub draft_id: String,
+    pub ticket_id: Option<String>,
+    /// First line of the draft summary, or of its input when there is no summary.
+    pub title: String,
+    /// Agent who finalized the draft.
+    pub finalized_by: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct AddDecisionRequest {
+    record_type: &'static str,
+    summary: String,
+    memory_id: Option<String>,
+    version: u32,
+    writer: String,
+    justification: String,
+    source_uri: String,
+    source_hash: Option<String>,
+    evidence: Vec<String>,
+    confidence: Option<f32>,
+    truth_status: &'static str,
+    authority: &'static str,
+    created_at: Option<String>,
+    effective_at: Option<String>,
+    supersedes: Vec<String>,
+    contradicts: Vec<String>,
+    tags: Vec<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct QueryAskRequest {
     text: String,
@@ -71,6 +155,13 @@ struct QueryAskRequest {
     resource: String,
 }
 
+#[derive(Debug, Serialize)]
+struct QueryRecallRequest {
+    text: String,
+    record_types: Vec<String>,
+    as_of: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ServiceEnvelope<T> {
     service_contract_version: String,
@@ -136,6 +227,13 @@ fn integration_timeout_ms(pin: &MemoryKernelIntegrationPin) -> u64 {
         .unwrap_or(pin.default_timeout_ms)
 }
 
+fn integration_api_key() -> Option<String> {
+    std::env::var(MEMORY_KERNEL_API_KEY_ENV)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
 fn preflight_status_template(
     pin: &MemoryKernelIntegrationPin,
     enabled: bool,
@@ -496,55 +594,45 @@ pub async fn get_memory_kernel_preflight_status() -> Result<MemoryKernelPrefligh
     Ok(run_preflight_internal(&client, &pin, enabled, &base_url).await)
 }
 
-#[tauri::command]
-pub async fn memory_kernel_query_ask(
-    user_input: String,
-) -> Result<MemoryKernelEnrichmentResult, String> {
-    let trimmed = user_input.trim();
-    if trimmed.is_empty() {
-        return Err("user_input cannot be empty".to_string());
-    }
+/// Why a MemoryKernel request produced no usable envelope.
+struct RequestFailure {
+    message: String,
+    fallback_reason: &'static str,
+    machine_error_code: Option<String>,
+}
 
-    let pin = INTEGRATION_PIN.clone();
-    let enabled = integration_enabled();
-    let base_url = integration_base_url(&pin);
-    let timeout_ms = integration_timeout_ms(&pin);
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_millis(timeout_ms))
+fn build_client(pin: &MemoryKernelIntegrationPin) -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_millis(integration_timeout_ms(
+            pin,
+        )))
         .build()
-        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
-    let preflight = run_preflight_internal(&client, &pin, enabled, &base_url).await;
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
 
-    if !preflight.enrichment_enabled {
-        return Ok(fallback_result(
-            preflight.clone(),
-            preflight.message.clone(),
-            preflight_fallback_reason(&preflight.status),
-            None,
-        ));
+/// POST `request` to a MemoryKernel endpoint and return the envelope's `data`, mapping
+/// transport, HTTP, payload, and contract failures to fallback reasons. `label` names the
+/// request in messages, e.g. "query ask". Sends the configured API key, if any.
+async fn post_envelope<T: Serialize>(
+    client: &reqwest::Client,
+    pin: &MemoryKernelIntegrationPin,
+    base_url: &str,
+    path: &str,
+    label: &str,
+    request: &T,
+) -> Result<serde_json::Value, RequestFailure> {
+    let mut builder = client.post(format!("{base_url}{path}")).json(request);
+    if let Some(api_key) = integration_api_key() {
+        builder = builder.bearer_auth(api_key);
     }
-
-    let request = QueryAskRequest {
-        text: trimmed.to_string(),
-        actor: "support_agent".to_string(),
-        action: "resolve".to_string(),
-        resource: "support_ticket".to_string(),
-    };
-
-    let response = match client
-        .post(format!("{base_url}/v1/query/ask"))
-        .json(&request)
-        .send()
-        .await
-    {
+    let response = match builder.send().await {
         Ok(resp) => resp,
         Err(err) => {
-            return Ok(fallback_result(
-                preflight,
-                format!("MemoryKernel query ask failed: {}", err),
-                fallback_reason_for_query_error(&err),
-                None,
-            ));
+            return Err(RequestFailure {
+                message: format!("MemoryKernel {} failed: {}", label, err),
+                fallback_reason: fallback_reason_for_query_error(&err),
+                machine_error_code: None,
+            });
         }
     };
 
@@ -561,14 +649,16 @@ pub async fn memory_kernel_query_ask(
         let message = match machine_code.as_deref() {
             Some(error_code) => match legacy_error.as_deref() {
                 Some(legacy_message) => format!(
-                    "MemoryKernel query ask returned HTTP {} [{}]: {} (legacy_error: {})",
+                    "MemoryKernel {} returned HTTP {} [{}]: {} (legacy_error: {})",
+                    label,
                     code.as_u16(),
                     error_code,
                     body,
                     legacy_message
                 ),
                 None => format!(
-                    "MemoryKernel query ask returned HTTP {} [{}]: {}",
+                    "MemoryKernel {} returned HTTP {} [{}]: {}",
+                    label,
                     code.as_u16(),
                     error_code,
                     body
@@ -576,64 +666,109 @@ pub async fn memory_kernel_query_ask(
             },
             None => match legacy_error.as_deref() {
                 Some(legacy_message) => format!(
-                    "MemoryKernel query ask returned HTTP {}: {} (legacy_error: {})",
+                    "MemoryKernel {} returned HTTP {}: {} (legacy_error: {})",
+                    label,
                     code.as_u16(),
                     body,
                     legacy_message
                 ),
                 None => format!(
-                    "MemoryKernel query ask returned HTTP {}: {}",
+                    "MemoryKernel {} returned HTTP {}: {}",
+                    label,
                     code.as_u16(),
                     body
                 ),
             },
         };
-        return Ok(fallback_result(
-            preflight,
+        return Err(RequestFailure {
             message,
             fallback_reason,
-            machine_code,
-        ));
+            machine_error_code: machine_code,
+        });
     }
 
     let body = response.text().await.unwrap_or_default();
     let envelope: ServiceEnvelope<serde_json::Value> = match serde_json::from_str(&body) {
         Ok(payload) => payload,
         Err(_) => {
-            return Ok(fallback_result(
-                preflight,
-                "MemoryKernel query ask returned malformed JSON envelope".to_string(),
-                FALLBACK_REASON_MALFORMED_PAYLOAD,
-                None,
-            ));
+            return Err(RequestFailure {
+                message: format!("MemoryKernel {} returned malformed JSON envelope", label),
+                fallback_reason: FALLBACK_REASON_MALFORMED_PAYLOAD,
+                machine_error_code: None,
+            });
         }
     };
 
     if !contracts_match(
-        &pin,
+        pin,
         &envelope.service_contract_version,
         &envelope.api_contract_version,
     ) {
-        return Ok(fallback_result(
-            preflight,
-            format!(
-                "MemoryKernel query ask contract mismatch (expected {}/{}, got {}/{})",
+        return Err(RequestFailure {
+            message: format!(
+                "MemoryKernel {} contract mismatch (expected {}/{}, got {}/{})",
+                label,
                 pin.expected_service_contract_version,
                 pin.expected_api_contract_version,
                 envelope.service_contract_version,
                 envelope.api_contract_version
             ),
-            FALLBACK_REASON_VERSION_MISMATCH,
+            fallback_reason: FALLBACK_REASON_VERSION_MISMATCH,
+            machine_error_code: None,
+        });
+    }
+
+    Ok(envelope.data)
+}
+
+#[tauri::command]
+pub async fn memory_kernel_query_ask(
+    user_input: String,
+) -> Result<MemoryKernelEnrichmentResult, String> {
+    let trimmed = user_input.trim();
+    if trimmed.is_empty() {
+        return Err("user_input cannot be empty".to_string());
+    }
+
+    let pin = INTEGRATION_PIN.clone();
+    let enabled = integration_enabled();
+    let base_url = integration_base_url(&pin);
+    let client = build_client(&pin)?;
+    let preflight = run_preflight_internal(&client, &pin, enabled, &base_url).await;
+
+    if !preflight.enrichment_enabled {
+        return Ok(fallback_result(
+            preflight.clone(),
+            preflight.message.clone(),
+            preflight_fallback_reason(&preflight.status),
             None,
         ));
     }
 
-    let context_package_id = envelope
-        .data
-        .get("context_package_id")
-        .and_then(serde_json::Value::as_str)
-        .map(ToString::to_string);
-    let enrichment_text = build_enrichment_text(&envelope.data);
+    let request = support_ticket_ask(trimmed);
+    let data = match post_envelope(
+        &client,
+        &pin,
+        &base_url,
+        "/v1/query/ask",
+        "query ask",
+        &request,
+    )
+    .await
+    {
+        Ok(data) => data,
+        Err(failure) => {
+            return Ok(fallback_result(
+                preflight,
+                failure.message,
+                failure.fallback_reason,
+                failure.machine_error_code,
+            ));
+        }
+    };
+
+    let context_package_id = context_package_id(&data);
+    let enrichment_text = build_enrichment_text(&data);
 
     Ok(MemoryKernelEnrichmentResult {
         applied: enrichment_text.is_some(),
@@ -659,6 +794,424 @@ pub async fn memory_kernel_query_ask(
     })
 }
 
+fn support_ticket_ask(text: &str) -> QueryAskRequest {
+    QueryAskRequest {
+        text: text.to_string(),
+        actor: "support_agent".to_string(),
+        action: "resolve".to_string(),
+        resource: "support_ticket".to_string(),
+    }
+}
+
+fn context_package_id(context_package: &serde_json::Value) -> Option<String> {
+    context_package
+        .get("context_package_id")
+        .and_then(serde_json::Value::as_str)
+        .map(ToString::to_string)
+}
+
+/// Selected items of a context package, optionally only those of `record_type`, in rank order.
+fn policy_citations(
+    context_package: &serde_json::Value,
+    record_type: Option<&str>,
+) -> Vec<MemoryKernelPolicyCitation> {
+    let Some(items) = context_package
+        .get("selected_items")
+        .and_then(serde_json::Value::as_array)
+    else {
+        return Vec::new();
+    };
+    let mut citations: Vec<MemoryKernelPolicyCitation> = items
+        .iter()
+        .filter_map(|item| {
+            let memory_version_id = item
+                .get("memory_version_id")
+                .and_then(serde_json::Value::as_str)?;
+            let item_type = item
+                .get("record_type")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("unknown");
+            if record_type.is_some_and(|wanted| wanted != item_type) {
+                return None;
+            }
+            Some(MemoryKernelPolicyCitation {
+                memory_version_id: memory_version_id.to_string(),
+                record_type: item_type.to_string(),
+                rank: item
+                    .get("rank")
+                    .and_then(serde_json::Value::as_u64)
+                    .unwrap_or(u64::MAX),
+                reason: item
+                    .pointer("/why/reasons")
+                    .and_then(serde_json::Value::as_array)
+                    .and_then(|reasons| reasons.first())
+                    .and_then(serde_json::Value::as_str)
+                    .map(ToString::to_string),
+            })
+        })
+        .collect();
+    citations.sort_by_key(|citation| citation.rank);
+    citations
+}
+
+/// Prompt section carrying the policy answer, its constraints, and related memory, each cited
+/// by `memory_version_id` so the draft can reference them.
+fn build_policy_prompt_context(
+    decision: &str,
+    why: Option<&str>,
+    constraints: &[MemoryKernelPolicyCitation],
+    related: &[MemoryKernelPolicyCitation],
+) -> String {
+    let mut lines = vec![format!("Policy decision: {}", decision)];
+    if let Some(why) = why {
+        lines.push(format!("Why: {}", why));
+    }
+    if !constraints.is_empty() {
+        lines.push("Constraints (cite by memory_version_id):".to_string());
+        for citation in constraints {
+            lines.push(format!(
+                "- {}: {}",
+                citation.memory_version_id,
+                citation.reason.as_deref().unwrap_or("No reason provided")
+            ));
+        }
+    }
+    if !related.is_empty() {
+        lines.push("Related memory:".to_string());
+        for citation in related.iter().take(POLICY_GATE_MAX_RELATED) {
+            lines.push(format!(
+                "- [{}] {}: {}",
+                citation.record_type,
+                citation.memory_version_id,
+                citation.reason.as_deref().unwrap_or("No reason provided")
+            ));
+        }
+    }
+    lines.push(
+        "Do not suggest anything these constraints deny; cite the memory_version_id of any constraint the response relies on."
+            .to_string(),
+    );
+    lines.join("\n")
+}
+
+fn policy_gate_fallback(
+    preflight: MemoryKernelPreflightStatus,
+    message: String,
+    fallback_reason: &'static str,
+    machine_error_code: Option<String>,
+) -> MemoryKernelPolicyGateResult {
+    MemoryKernelPolicyGateResult {
+        applied: false,
+        status: "fallback".to_string(),
+        decision: None,
+        blocked: false,
+        message,
+        fallback_reason: Some(fallback_reason.to_string()),
+        machine_error_code,
+        ask_context_package_id: None,
+        recall_context_package_id: None,
+        constraints: Vec::new(),
+        related: Vec::new(),
+        prompt_context: None,
+        preflight,
+    }
+}
+
+/// Ask MemoryKernel whether resolving `user_input` is allowed and recall related memory.
+///
+/// A `deny` answer blocks drafting and cites the highest-ranked selected constraint. When the
+/// service is disabled or unavailable the gate falls back without blocking, like enrichment;
+/// a failed recall only drops the related memory.
+pub(crate) async fn evaluate_policy_gate(
+    user_input: &str,
+) -> Result<MemoryKernelPolicyGateResult, String> {
+    let pin = INTEGRATION_PIN.clone();
+    let enabled = integration_enabled();
+    let base_url = integration_base_url(&pin);
+    let client = build_client(&pin)?;
+    let preflight = run_preflight_internal(&client, &pin, enabled, &base_url).await;
+
+    if !preflight.enrichment_enabled {
+        return Ok(policy_gate_fallback(
+            preflight.clone(),
+            preflight.message.clone(),
+            preflight_fallback_reason(&preflight.status),
+            None,
+        ));
+    }
+
+    let ask = match post_envelope(
+        &client,
+        &pin,
+        &base_url,
+        "/v1/query/ask",
+        "query ask",
+        &support_ticket_ask(user_input),
+    )
+    .await
+    {
+        Ok(data) => data,
+        Err(failure) => {
+            return Ok(policy_gate_fallback(
+                preflight,
+                failure.message,
+                failure.fallback_reason,
+                failure.machine_error_code,
+            ));
+        }
+    };
+    let Some(decision) = ask
+        .pointer("/answer/result")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_ascii_lowercase)
+    else {
+        return Ok(policy_gate_fallback(
+            preflight,
+            "MemoryKernel query ask returned no policy decision".to_string(),
+            FALLBACK_REASON_MALFORMED_PAYLOAD,
+            None,
+        ));
+    };
+
+    let recall_request = QueryRecallRequest {
+        text: user_input.to_string(),
+        record_types: Vec::new(),
+        as_of: None,
+    };
+    let recall = post_envelope(
+        &client,
+        &pin,
+        &base_url,
+        "/v1/query/recall",
+        "query recall",
+        &recall_request,
+    )
+    .await;
+
+    let constraints = policy_citations(&ask, Some("constraint"));
+    let (related, recall_context_package_id, recall_note) = match &recall {
+        Ok(data) => (policy_citations(data, None), context_package_id(data), None),
+        Err(failure) => (Vec::new(), None, Some(failure.message.clone())),
+    };
+    let why = ask
+        .pointer("/answer/why")
+        .and_then(serde_json::Value::as_str);
+    let blocked = decision == "deny";
+    let message = if blocked {
+        match constraints.first() {
+            Some(citation) => format!(
+                "Drafting refused: MemoryKernel deny constraint {} applies ({})",
+                citation.memory_version_id,
+                why.unwrap_or("no reason provided")
+            ),
+            None => format!(
+                "Drafting refused: MemoryKernel denied this request ({})",
+                why.unwrap_or("no reason provided")
+            ),
+        }
+    } else {
+        format!("MemoryKernel policy decision: {}", decision)
+    };
+
+    Ok(MemoryKernelPolicyGateResult {
+        applied: true,
+        status: if blocked { "blocked" } else { "allowed" }.to_string(),
+        prompt_context: Some(build_policy_prompt_context(
+            &decision,
+            why,
+            &constraints,
+            &related,
+        )),
+        decision: Some(decision),
+        blocked,
+        message: match recall_note {
+            Some(note) => format!("{} (related memory unavailable: {})", message, note),
+            None => message,
+        },
+        fallback_reason: None,
+        machine_error_code: None,
+        ask_context_package_id: context_package_id(&ask),
+        recall_context_package_id,
+        constraints,
+        related,
+        preflight,
+    })
+}
+
+/// Run the draft policy gate for `user_input` without generating anything.
+#[tauri::command]
+pub async fn memory_kernel_policy_gate(
+    user_input: String,
+) -> Result<MemoryKernelPolicyGateResult, String> {
+    let trimmed = user_input.trim();
+    if trimmed.is_empty() {
+        return Err("user_input cannot be empty".to_string());
+    }
+    evaluate_policy_gate(trimmed).await
+}
+
+/// `record_type=decision` write for a finalized draft: the ticket id is the source URI (the
+/// draft URI when there is no ticket) and the finalizing agent is the writer.
+fn decision_request(decision: &FinalizedDraftDecision) -> AddDecisionRequest {
+    let draft_uri = format!("assistsupport://draft/{}", decision.draft_id);
+    let ticket_id = decision
+        .ticket_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|ticket| !ticket.is_empty());
+    let title = decision.title.trim();
+    let summary = match ticket_id {
+        Some(ticket) => format!("Finalized response for {}: {}", ticket, title),
+        None => format!("Finalized response: {}", title),
+    };
+    AddDecisionRequest {
+        record_type: "decision",
+        summary: summary.chars().take(DECISION_SUMMARY_MAX_CHARS).collect(),
+        memory_id: None,
+        version: 1,
+        writer: decision
+            .finalized_by
+            .as_deref()
+            .map(str::trim)
+            .filter(|writer| !writer.is_empty())
+            .unwrap_or(DECISION_DEFAULT_WRITER)
+            .to_string(),
+        justification: "Support agent finalized the draft response".to_string(),
+        source_uri: ticket_id.map_or_else(|| draft_uri.clone(), ToString::to_string),
+        source_hash: None,
+        evidence: vec![draft_uri],
+        confidence: None,
+        truth_status: "asserted",
+        authority: "derived",
+        created_at: None,
+        effective_at: None,
+        supersedes: Vec::new(),
+        contradicts: Vec::new(),
+        tags: vec!["assistsupport".to_string(), "finalized-draft".to_string()],
+    }
+}
+
+pub(crate) fn decision_record_skipped(message: &str) -> MemoryKernelDecisionRecordResult {
+    MemoryKernelDecisionRecordResult {
+        recorded: false,
+        status: "skipped".to_string(),
+        message: message.to_string(),
+        fallback_reason: None,
+        machine_error_code: None,
+        memory_id: None,
+        memory_version_id: None,
+        preflight: None,
+    }
+}
+
+fn decision_record_fallback(
+    preflight: MemoryKernelPreflightStatus,
+    message: String,
+    fallback_reason: &'static str,
+    machine_error_code: Option<String>,
+) -> MemoryKernelDecisionRecordResult {
+    MemoryKernelDecisionRecordResult {
+        recorded: false,
+        status: "fallback".to_string(),
+        message,
+        fallback_reason: Some(fallback_reason.to_string()),
+        machine_error_code,
+        memory_id: None,
+        memory_version_id: None,
+        preflight: Some(preflight),
+    }
+}
+
+/// Write a finalized draft to MemoryKernel as a decision record.
+///
+/// Callers check consent first. Failures fall back without an error so finalizing a draft
+/// never depends on the service.
+pub(crate) async fn record_finalized_draft_decision(
+    decision: &FinalizedDraftDecision,
+) -> Result<MemoryKernelDecisionRecordResult, String> {
+    let pin = INTEGRATION_PIN.clone();
+    let enabled = integration_enabled();
+    let base_url = integration_base_url(&pin);
+    let client = build_client(&pin)?;
+    let preflight = run_preflight_internal(&client, &pin, enabled, &base_url).await;
+
+    if !preflight.enrichment_enabled {
+        return Ok(decision_record_fallback(
+            preflight.clone(),
+            preflight.message.clone(),
+            preflight_fallback_reason(&preflight.status),
+            None,
+        ));
+    }
+
+    let record = match post_envelope(
+        &client,
+        &pin,
+        &base_url,
+        "/v1/memory/add/summary",
+        "memory add summary",
+        &decision_request(decision),
+    )
+    .await
+    {
+        Ok(data) => data,
+        Err(failure) => {
+            return Ok(decision_record_fallback(
+                preflight,
+                failure.message,
+                failure.fallback_reason,
+                failure.machine_error_code,
+            ));
+        }
+    };
+    let field = |name: &str| {
+        record
+            .get(name)
+            .and_then(serde_json::Value::as_str)
+            .map(ToString::to_string)
+    };
+    let memory_version_id = field("memory_version_id");
+
+    Ok(MemoryKernelDecisionRecordResult {
+        recorded: true,
+        status: "recorded".to_string(),
+        message: format!(
+            "Recorded MemoryKernel decision {}",
+            memory_version_id.as_deref().unwrap_or("(unknown version)")
+        ),
+        fallback_reason: None,
+        machine_error_code: None,
+        memory_id: field("memory_id"),
+        memory_version_id,
+        preflight: Some(preflight),
+    })
+}
+
+/// Record a finalized draft as a decision unless consent is off or the draft opted out with
+/// `record_decision: Some(false)`. `decision` is `None` when the draft could not be reloaded.
+pub(crate) async fn record_finalized_draft_if_allowed(
+    decision: Option<&FinalizedDraftDecision>,
+    consented: bool,
+    record_decision: Option<bool>,
+) -> Result<MemoryKernelDecisionRecordResult, String> {
+    if !consented {
+        return Ok(decision_record_skipped(
+            "Recording finalized drafts in MemoryKernel is not enabled",
+        ));
+    }
+    if record_decision == Some(false) {
+        return Ok(decision_record_skipped(
+            "Draft opted out of MemoryKernel decision recording",
+        ));
+    }
+    match decision {
+        Some(decision) => record_finalized_draft_decision(decision).await,
+        None => Ok(decision_record_skipped(
+            "Finalized draft could not be reloaded",
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -772,6 +1325,63 @@ mod tests {
         )
     }
 
+    fn fixture_query_deny() -> String {
+        format!(
+            "{{\"service_contract_version\":\"{}\",\"api_contract_version\":\"{}\",\"data\":{{\"context_package_id\":\"ctx_deny\",\"answer\":{{\"result\":\"deny\",\"why\":\"Policy denies unencrypted removable media\"}},\"selected_items\":[{{\"rank\":2,\"memory_version_id\":\"mv_low\",\"record_type\":\"constraint\",\"memory_id\":\"usb-general\",\"why\":{{\"reasons\":[\"scope matched\"]}}}},{{\"rank\":1,\"memory_version_id\":\"mv_usb_deny\",\"record_type\":\"constraint\",\"memory_id\":\"usb-policy\",\"why\":{{\"reasons\":[\"authoritative deny\"]}}}}]}}}}",
+            INTEGRATION_PIN.expected_service_contract_version,
+            INTEGRATION_PIN.expected_api_contract_version
+        )
+    }
+
+    fn fixture_recall() -> String {
+        format!(
+            "{{\"service_contract_version\":\"{}\",\"api_contract_version\":\"{}\",\"data\":{{\"context_package_id\":\"ctx_recall\",\"selected_items\":[{{\"rank\":1,\"memory_version_id\":\"mv_decision\",\"record_type\":\"decision\",\"memory_id\":\"usb-exception\",\"why\":{{\"reasons\":[\"recent decision\"]}}}}]}}}}",
+            INTEGRATION_PIN.expected_service_contract_version,
+            INTEGRATION_PIN.expected_api_contract_version
+        )
+    }
+
+    fn policy_gate_responses(
+        ask_body: String,
+        recall_status: u16,
+        recall_body: String,
+    ) -> Vec<MockResponse> {
+        vec![
+            MockResponse {
+                method: "GET",
+                path: "/v1/health",
+                status: 200,
+                body: fixture_health_ok(),
+                content_type: "application/json",
+                delay_ms: 0,
+            },
+            MockResponse {
+                method: "POST",
+                path: "/v1/db/schema-version",
+                status: 200,
+                body: fixture_schema_ok(),
+                content_type: "application/json",
+                delay_ms: 0,
+            },
+            MockResponse {
+                method: "POST",
+                path: "/v1/query/ask",
+                status: 200,
+                body: ask_body,
+                content_type: "application/json",
+                delay_ms: 0,
+            },
+            MockResponse {
+                method: "POST",
+                path: "/v1/query/recall",
+                status: recall_status,
+                body: recall_body,
+                content_type: "application/json",
+                delay_ms: 0,
+            },
+        ]
+    }
+
     fn fixture_transitional_legacy_error(code: &str, message: &str, legacy_message: &str) -> String {
         format!(
             "{{\"service_contract_version\":\"{}\",\"error\":{{\"code\":\"{}\",\"message\":\"{}\"}},\"legacy_error\":\"{}\"}}",
@@ -1280,6 +1890,229 @@ mod tests {
         assert!(text.contains("removable-media-policy"));
     }
 
+    #[tokio::test]
+    async fn policy_gate_blocks_deny_and_cites_top_constraint() {
+        let _guard = ENV_LOCK.lock().expect("env lock poisoned");
+        let (base_url, handle) = spawn_mock_server(policy_gate_responses(
+            fixture_query_deny(),
+            200,
+            fixture_recall(),
+        ));
+        set_test_env(&base_url, 750, true);
+
+        let result = memory_kernel_policy_gate("Can I copy files to a USB drive?".to_string())
+            .await
+            .expect("policy gate command should not fail");
+        assert!(result.applied);
+        assert!(result.blocked);
+        assert_eq!(result.status, "blocked");
+        assert_eq!(result.decision.as_deref(), Some("deny"));
+        assert!(result.message.contains("mv_usb_deny"));
+        assert_eq!(result.constraints.len(), 2);
+        assert_eq!(result.constraints[0].memory_version_id, "mv_usb_deny");
+        assert_eq!(result.related[0].memory_version_id, "mv_decision");
+        assert_eq!(result.ask_context_package_id.as_deref(), Some("ctx_deny"));
+        assert_eq!(
+            result.recall_context_package_id.as_deref(),
+            Some("ctx_recall")
+        );
+
+        handle.join().expect("server thread panicked");
+        clear_test_env();
+    }
+
+    #[tokio::test]
+    async fn policy_gate_allows_with_prompt_context_when_recall_fails() {
+        let _guard = ENV_LOCK.lock().expect("env lock poisoned");
+        let (base_url, handle) = spawn_mock_server(policy_gate_responses(
+            fixture_query_allow(),
+            500,
+            fixture_typed_error("query_failed", "recall failed"),
+        ));
+        set_test_env(&base_url, 750, true);
+
+        let result = memory_kernel_policy_gate("Can I use a USB drive?".to_string())
+            .await
+            .expect("policy gate command should not fail");
+        assert!(result.applied);
+        assert!(!result.blocked);
+        assert_eq!(result.status, "allowed");
+        assert!(result.related.is_empty());
+        assert!(result.message.contains("related memory unavailable"));
+        assert!(result
+            .prompt_context
+            .as_deref()
+            .unwrap_or_default()
+            .contains("Policy decision: allow"));
+
+        handle.join().expect("server thread panicked");
+        clear_test_env();
+    }
+
+    #[tokio::test]
+    async fn policy_gate_does_not_block_when_disabled() {
+        let _guard = ENV_LOCK.lock().expect("env lock poisoned");
+        set_test_env("http://127.0.0.1:9", 200, false);
+
+        let result = memory_kernel_policy_gate("Need policy guidance".to_string())
+            .await
+            .expect("policy gate command should not fail");
+        assert!(!result.applied);
+        assert!(!result.blocked);
+        assert_eq!(result.status, "fallback");
+        assert_eq!(
+            result.fallback_reason.as_deref(),
+            Some(FALLBACK_REASON_FEATURE_DISABLED)
+        );
+        assert!(result.prompt_context.is_none());
+
+        clear_test_env();
+    }
+
+    #[test]
+    fn policy_prompt_context_cites_constraints_by_version_id() {
+        let constraints = vec![MemoryKernelPolicyCitation {
+            memory_version_id: "mv_usb_deny".to_string(),
+            record_type: "constraint".to_string(),
+            rank: 1,
+            reason: Some("authoritative deny".to_string()),
+        }];
+        let text = build_policy_prompt_context("deny", Some("unencrypted"), &constraints, &[]);
+        assert!(text.contains("Policy decision: deny"));
+        assert!(text.contains("- mv_usb_deny: authoritative deny"));
+        assert!(!text.contains("Related memory"));
+    }
+
+    fn finalized_draft(
+        ticket_id: Option<&str>,
+        finalized_by: Option<&str>,
+    ) -> FinalizedDraftDecision {
+        FinalizedDraftDecision {
+            draft_id: "draft-1".to_string(),
+            ticket_id: ticket_id.map(ToString::to_string),
+            title: "VPN drops after sleep".to_string(),
+            finalized_by: finalized_by.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn decision_request_uses_ticket_as_source_and_agent_as_writer() {
+        let request = decision_request(&finalized_draft(Some("IT-42"), Some("jordan")));
+        assert_eq!(request.record_type, "decision");
+        assert_eq!(request.source_uri, "IT-42");
+        assert_eq!(request.writer, "jordan");
+        assert_eq!(
+            request.summary,
+            "Finalized response for IT-42: VPN drops after sleep"
+        );
+        assert_eq!(
+            request.evidence,
+            vec!["assistsupport://draft/draft-1".to_string()]
+        );
+
+        let request = decision_request(&finalized_draft(None, Some("  ")));
+        assert_eq!(request.source_uri, "assistsupport://draft/draft-1");
+        assert_eq!(request.writer, DECISION_DEFAULT_WRITER);
+    }
+
+    #[tokio::test]
+    async fn record_finalized_draft_decision_returns_written_version() {
+        let _guard = ENV_LOCK.lock().expect("env lock poisoned");
+        let record_body = format!(
+            "{{\"service_contract_version\":\"{}\",\"api_contract_version\":\"{}\",\"data\":{{\"memory_id\":\"mem_1\",\"memory_version_id\":\"mv_1\",\"record_type\":\"decision\"}}}}",
+            INTEGRATION_PIN.expected_service_contract_version,
+            INTEGRATION_PIN.expected_api_contract_version
+        );
+        let (base_url, handle) = spawn_mock_server(vec![
+            MockResponse {
+                method: "GET",
+                path: "/v1/health",
+                status: 200,
+                body: fixture_health_ok(),
+                content_type: "application/json",
+                delay_ms: 0,
+            },
+            MockResponse {
+                method: "POST",
+                path: "/v1/db/schema-version",
+                status: 200,
+                body: fixture_schema_ok(),
+                content_type: "application/json",
+                delay_ms: 0,
+            },
+            MockResponse {
+                method: "POST",
+                path: "/v1/memory/add/summary",
+                status: 200,
+                body: record_body,
+                content_type: "application/json",
+                delay_ms: 0,
+            },
+        ]);
+        set_test_env(&base_url, 750, true);
+
+        let result =
+            record_finalized_draft_decision(&finalized_draft(Some("IT-42"), Some("jordan")))
+                .await
+                .expect("decision record should not fail");
+        assert!(result.recorded);
+        assert_eq!(result.status, "recorded");
+        assert_eq!(result.memory_id.as_deref(), Some("mem_1"));
+        assert_eq!(result.memory_version_id.as_deref(), Some("mv_1"));
+
+        handle.join().expect("server thread panicked");
+        clear_test_env();
+    }
+
+    #[tokio::test]
+    async fn record_finalized_draft_decision_falls_back_when_disabled() {
+        let _guard = ENV_LOCK.lock().expect("env lock poisoned");
+        set_test_env("http://127.0.0.1:9", 200, false);
+
+        let result = record_finalized_draft_decision(&finalized_draft(Some("IT-42"), None))
+            .await
+            .expect("decision record should not fail");
+        assert!(!result.recorded);
+        assert_eq!(result.status, "fallback");
+        assert_eq!(
+            result.fallback_reason.as_deref(),
+            Some(FALLBACK_REASON_FEATURE_DISABLED)
+        );
+
+        clear_test_env();
+    }
+
+    #[tokio::test]
+    async fn finalized_draft_is_not_recorded_without_consent_or_when_opted_out() {
+        let _guard = ENV_LOCK.lock().expect("env lock poisoned");
+        // An enabled integration at a closed port would fall back if it were contacted.
+        set_test_env("http://127.0.0.1:9", 200, true);
+        let decision = finalized_draft(Some("IT-42"), Some("jordan"));
+
+        let result = record_finalized_draft_if_allowed(Some(&decision), false, Some(true))
+            .await
+            .expect("decision record should not fail");
+        assert!(!result.recorded);
+        assert_eq!(result.status, "skipped");
+        assert!(result.message.contains("not enabled"));
+        assert!(result.preflight.is_none());
+
+        let result = record_finalized_draft_if_allowed(Some(&decision), true, Some(false))
+            .await
+            .expect("decision record should not fail");
+        assert!(!result.recorded);
+        assert_eq!(result.status, "skipped");
+        assert!(result.message.contains("opted out"));
+        assert!(result.preflight.is_none());
+
+        let result = record_finalized_draft_if_allowed(Some(&decision), true, None)
+            .await
+            .expect("decision record should not fail");
+        assert_eq!(result.status, "fallback");
+
+        clear_test_env();
+    }
+
     #[test]
     fn disabled_feature_returns_disabled_preflight_template() {
         let pin = INTEGRATION_PIN.clone();
@@ -1,4 +1,8 @@
 use super::*;
+use crate::db::StartupTimings;
+
+/// Startup timings returned when no limit is given
+const DEFAULT_STARTUP_TIMINGS: usize = 10;
 
 pub(crate) fn get_model_state_impl(state: State<'_, AppState>) -> Result<ModelStateResult, String> {
     let db_lock = state.db.lock().map_err(|e| e.to_string())?;
@@ -54,3 +58,14 @@ pub(crate) fn get_startup_metrics_impl(
         }),
     }
 }
+
+pub(crate) fn get_startup_timings_impl(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<StartupTimings>, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    db.list_startup_timings(limit.unwrap_or(DEFAULT_STARTUP_TIMINGS))
+        .map_err(|e| e.to_string())
+}
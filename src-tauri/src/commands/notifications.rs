@@ -0,0 +1,454 @@
+//! Notification integration commands (Slack, Microsoft Teams, webhooks)
+
+use crate::audit;
+use crate::db::Database;
+use crate::notifications::slack::{self, SlackConfig, SlackNotifier, SLACK_INTEGRATION};
+use crate::notifications::teams::{self, TeamsConfig, TeamsNotifier, TEAMS_INTEGRATION};
+use crate::notifications::webhooks::{
+    self, WebhookDispatcher, WebhookEndpoint, WebhooksConfig, MIN_SECRET_LEN,
+};
+use crate::notifications::{EventPreferences, Notification, NotificationEvent};
+use crate::security::{vault::Vault, TOKEN_SLACK_BOT, TOKEN_SLACK_WEBHOOK, TOKEN_TEAMS_WEBHOOK};
+use crate::AppState;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+/// Slack settings as exposed to the UI (secrets are never returned)
+#[derive(Debug, Clone, Serialize)]
+pub struct SlackSettings {
+    pub enabled: bool,
+    pub config: SlackConfig,
+    pub has_webhook_url: bool,
+    pub has_bot_token: bool,
+}
+
+/// Teams settings as exposed to the UI (secrets are never returned)
+#[derive(Debug, Clone, Serialize)]
+pub struct TeamsSettings {
+    pub enabled: bool,
+    pub config: TeamsConfig,
+    pub has_webhook_url: bool,
+}
+
+/// Webhook endpoint as exposed to the UI (the signing secret is never returned)
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookSettings {
+    #[serde(flatten)]
+    pub endpoint: WebhookEndpoint,
+    pub has_secret: bool,
+}
+
+/// Webhook endpoint fields editable from the UI (`id` is None for new endpoints)
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookInput {
+    pub id: Option<String>,
+    pub name: String,
+    pub url: String,
+    pub events: Vec<NotificationEvent>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Result of saving a webhook. `generated_secret` is set only when a new
+/// signing secret was generated; it is shown once and never returned again.
+#[derive(Debug, Clone, Serialize)]
+pub struct SavedWebhook {
+    pub webhook: WebhookSettings,
+    pub generated_secret: Option<String>,
+}
+
+/// Combined notification settings for all integrations
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationSettings {
+    pub events: Vec<NotificationEvent>,
+    pub slack: SlackSettings,
+    pub teams: TeamsSettings,
+    pub webhooks: Vec<WebhookSettings>,
+}
+
+fn has_token(name: &str) -> bool {
+    Vault::get(name).map(|t| t.is_some()).unwrap_or(false)
+}
+
+/// Load (enabled, config) for an integration, defaulting when unset
+fn load_integration<T: DeserializeOwned + Default>(
+    db: &Database,
+    integration_type: &str,
+) -> Result<(bool, T), String> {
+    let record = db
+        .get_integration_config(integration_type)
+        .map_err(|e| e.to_string())?;
+
+    match record {
+        Some(r) => {
+            let config = r
+                .config_json
+                .as_deref()
+                .map(serde_json::from_str)
+                .transpose()
+                .map_err(|e| e.to_string())?
+                .unwrap_or_default();
+            Ok((r.enabled, config))
+        }
+        None => Ok((false, T::default())),
+    }
+}
+
+fn save_integration<T: Serialize>(
+    db: &Database,
+    integration_type: &str,
+    enabled: bool,
+    config: &T,
+) -> Result<(), String> {
+    let config_json = serde_json::to_string(config).map_err(|e| e.to_string())?;
+    db.set_integration_config(integration_type, enabled, Some(&config_json))
+        .map_err(|e| e.to_string())
+}
+
+fn slack_settings(db: &Database) -> Result<SlackSettings, String> {
+    let (enabled, config) = load_integration(db, SLACK_INTEGRATION)?;
+    Ok(SlackSettings {
+        enabled,
+        config,
+        has_webhook_url: has_token(TOKEN_SLACK_WEBHOOK),
+        has_bot_token: has_token(TOKEN_SLACK_BOT),
+    })
+}
+
+fn webhook_settings(db: &Database) -> Result<Vec<WebhookSettings>, String> {
+    let config = WebhooksConfig::load(db).map_err(|e| e.to_string())?;
+    Ok(config
+        .endpoints
+        .into_iter()
+        .map(|endpoint| WebhookSettings {
+            has_secret: has_token(&endpoint.secret_key()),
+            endpoint,
+        })
+        .collect())
+}
+
+fn teams_settings(db: &Database) -> Result<TeamsSettings, String> {
+    let (enabled, config) = load_integration(db, TEAMS_INTEGRATION)?;
+    Ok(TeamsSettings {
+        enabled,
+        config,
+        has_webhook_url: has_token(TOKEN_TEAMS_WEBHOOK),
+    })
+}
+
+/// Configure Slack notifications.
+/// `webhook_url` / `bot_token` are only updated when provided, so the UI can
+/// change templates and toggles without re-entering secrets.
+#[tauri::command]
+pub fn configure_slack(
+    state: State<'_, AppState>,
+    enabled: bool,
+    config: SlackConfig,
+    webhook_url: Option<String>,
+    bot_token: Option<String>,
+) -> Result<(), String> {
+    config.validate().map_err(|e| e.to_string())?;
+
+    // Validate everything before persisting so a bad input can't leave a
+    // half-saved configuration
+    let webhook_url = webhook_url.as_deref().map(str::trim);
+    if let Some(url) = webhook_url {
+        slack::validate_webhook_url(url).map_err(|e| e.to_string())?;
+    }
+    let bot_token = bot_token.as_deref().map(str::trim);
+    if let Some(token) = bot_token {
+        if !token.starts_with("xoxb-") {
+            return Err("Slack bot tokens must start with xoxb-".to_string());
+        }
+    }
+
+    let has_webhook = webhook_url.is_some() || has_token(TOKEN_SLACK_WEBHOOK);
+    let has_bot = bot_token.is_some() || has_token(TOKEN_SLACK_BOT);
+    if enabled && !has_webhook && !has_bot {
+        return Err("A Slack webhook URL or bot token is required".to_string());
+    }
+    if enabled && has_bot && config.channel.is_none() {
+        return Err("A channel is required when using a Slack bot token".to_string());
+    }
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    if let Some(url) = webhook_url {
+        Vault::put(TOKEN_SLACK_WEBHOOK, url).map_err(|e| e.to_string())?;
+    }
+    if let Some(token) = bot_token {
+        Vault::put(TOKEN_SLACK_BOT, token).map_err(|e| e.to_string())?;
+    }
+    save_integration(db, SLACK_INTEGRATION, enabled, &config)
+}
+
+/// Get Slack notification settings (without secrets)
+#[tauri::command]
+pub fn get_slack_config(state: State<'_, AppState>) -> Result<SlackSettings, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    slack_settings(db)
+}
+
+/// Remove Slack credentials and disable Slack notifications
+#[tauri::command]
+pub fn clear_slack_config(state: State<'_, AppState>) -> Result<(), String> {
+    let _ = Vault::delete(TOKEN_SLACK_WEBHOOK);
+    let _ = Vault::delete(TOKEN_SLACK_BOT);
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.set_integration_config(SLACK_INTEGRATION, false, None)
+        .map_err(|e| e.to_string())
+}
+
+/// Configure Microsoft Teams notifications.
+/// `webhook_url` is only updated when provided.
+#[tauri::command]
+pub fn configure_teams(
+    state: State<'_, AppState>,
+    enabled: bool,
+    config: TeamsConfig,
+    webhook_url: Option<String>,
+) -> Result<(), String> {
+    if let Some(url) = webhook_url.as_deref().map(str::trim) {
+        teams::validate_webhook_url(url).map_err(|e| e.to_string())?;
+        Vault::put(TOKEN_TEAMS_WEBHOOK, url).map_err(|e| e.to_string())?;
+    }
+
+    if enabled && !has_token(TOKEN_TEAMS_WEBHOOK) {
+        return Err("A Teams webhook URL is required".to_string());
+    }
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    save_integration(db, TEAMS_INTEGRATION, enabled, &config)
+}
+
+/// Get Teams notification settings (without secrets)
+#[tauri::command]
+pub fn get_teams_config(state: State<'_, AppState>) -> Result<TeamsSettings, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    teams_settings(db)
+}
+
+/// Remove the Teams webhook and disable Teams notifications
+#[tauri::command]
+pub fn clear_teams_config(state: State<'_, AppState>) -> Result<(), String> {
+    let _ = Vault::delete(TOKEN_TEAMS_WEBHOOK);
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.set_integration_config(TEAMS_INTEGRATION, false, None)
+        .map_err(|e| e.to_string())
+}
+
+/// Get notification settings for every integration in one call
+#[tauri::command]
+pub fn get_notification_settings(
+    state: State<'_, AppState>,
+) -> Result<NotificationSettings, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    Ok(NotificationSettings {
+        events: NotificationEvent::ALL.to_vec(),
+        slack: slack_settings(db)?,
+        teams: teams_settings(db)?,
+        webhooks: webhook_settings(db)?,
+    })
+}
+
+/// Update event toggles and templates for an integration ("slack" or "teams")
+/// without touching credentials or other integration-specific settings.
+#[tauri::command]
+pub fn update_notification_preferences(
+    state: State<'_, AppState>,
+    integration: String,
+    enabled: bool,
+    preferences: EventPreferences,
+) -> Result<(), String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    match integration.as_str() {
+        SLACK_INTEGRATION => {
+            let (_, mut config): (bool, SlackConfig) = load_integration(db, SLACK_INTEGRATION)?;
+            if enabled && !has_token(TOKEN_SLACK_WEBHOOK) && !has_token(TOKEN_SLACK_BOT) {
+                return Err("Slack is not configured".to_string());
+            }
+            config.preferences = preferences;
+            save_integration(db, SLACK_INTEGRATION, enabled, &config)
+        }
+        TEAMS_INTEGRATION => {
+            let (_, mut config): (bool, TeamsConfig) = load_integration(db, TEAMS_INTEGRATION)?;
+            if enabled && !has_token(TOKEN_TEAMS_WEBHOOK) {
+                return Err("Teams is not configured".to_string());
+            }
+            config.preferences = preferences;
+            save_integration(db, TEAMS_INTEGRATION, enabled, &config)
+        }
+        other => Err(format!("Unknown notification integration: {}", other)),
+    }
+}
+
+/// Send a test message through a configured integration ("slack" or "teams")
+#[tauri::command]
+pub async fn send_test_notification(
+    state: State<'_, AppState>,
+    integration: String,
+) -> Result<(), String> {
+    const TEST_MESSAGE: &str = "AssistSupport test notification";
+
+    match integration.as_str() {
+        SLACK_INTEGRATION => {
+            // Load configuration without holding the DB lock across the request
+            let notifier = {
+                let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+                let db = db_lock.as_ref().ok_or("Database not initialized")?;
+                SlackNotifier::from_db(db)
+                    .map_err(|e| e.to_string())?
+                    .ok_or("Slack notifications are not enabled")?
+            };
+            notifier.send(TEST_MESSAGE).await.map_err(|e| e.to_string())
+        }
+        TEAMS_INTEGRATION => {
+            let notifier = {
+                let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+                let db = db_lock.as_ref().ok_or("Database not initialized")?;
+                TeamsNotifier::from_db(db)
+                    .map_err(|e| e.to_string())?
+                    .ok_or("Teams notifications are not enabled")?
+            };
+            let card = teams::build_card("AssistSupport", TEST_MESSAGE, &Default::default());
+            notifier.send(&card).await.map_err(|e| e.to_string())
+        }
+        other => Err(format!("Unknown notification integration: {}", other)),
+    }
+}
+
+/// List configured webhook endpoints (without secrets)
+#[tauri::command]
+pub fn list_webhooks(state: State<'_, AppState>) -> Result<Vec<WebhookSettings>, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    webhook_settings(db)
+}
+
+/// Create or update a webhook endpoint.
+/// `secret` replaces the signing secret when provided; a new endpoint without
+/// one gets a generated secret, returned once in `generated_secret`.
+#[tauri::command]
+pub fn save_webhook(
+    state: State<'_, AppState>,
+    webhook: WebhookInput,
+    secret: Option<String>,
+) -> Result<SavedWebhook, String> {
+    let endpoint = WebhookEndpoint {
+        id: webhook
+            .id
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        name: webhook.name.trim().to_string(),
+        url: webhook.url.trim().to_string(),
+        events: webhook.events,
+        enabled: webhook.enabled,
+    };
+    endpoint.validate().map_err(|e| e.to_string())?;
+
+    let secret = secret.map(|s| s.trim().to_string());
+    if secret.as_ref().is_some_and(|s| s.len() < MIN_SECRET_LEN) {
+        return Err(format!(
+            "Webhook secrets must be at least {} characters",
+            MIN_SECRET_LEN
+        ));
+    }
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    let mut config = WebhooksConfig::load(db).map_err(|e| e.to_string())?;
+
+    let secret_key = endpoint.secret_key();
+    let mut generated_secret = None;
+    let secret = match secret {
+        Some(secret) => Some(secret),
+        None if !has_token(&secret_key) => {
+            let secret = webhooks::generate_secret();
+            generated_secret = Some(secret.clone());
+            Some(secret)
+        }
+        None => None,
+    };
+
+    config.upsert(endpoint.clone()).map_err(|e| e.to_string())?;
+    if let Some(secret) = secret {
+        Vault::put(&secret_key, &secret).map_err(|e| e.to_string())?;
+    }
+    config.save(db).map_err(|e| e.to_string())?;
+
+    Ok(SavedWebhook {
+        webhook: WebhookSettings {
+            endpoint,
+            has_secret: true,
+        },
+        generated_secret,
+    })
+}
+
+/// Delete a webhook endpoint and its signing secret
+#[tauri::command]
+pub fn delete_webhook(state: State<'_, AppState>, webhook_id: String) -> Result<(), String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    let mut config = WebhooksConfig::load(db).map_err(|e| e.to_string())?;
+
+    let endpoint = config
+        .remove(&webhook_id)
+        .ok_or_else(|| format!("Webhook not found: {}", webhook_id))?;
+    config.save(db).map_err(|e| e.to_string())?;
+
+    let _ = Vault::delete(&endpoint.secret_key());
+    Ok(())
+}
+
+/// Generate a new signing secret for a webhook and return it (shown once)
+#[tauri::command]
+pub fn rotate_webhook_secret(
+    state: State<'_, AppState>,
+    webhook_id: String,
+) -> Result<String, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    let config = WebhooksConfig::load(db).map_err(|e| e.to_string())?;
+    if !config.endpoints.iter().any(|e| e.id == webhook_id) {
+        return Err(format!("Webhook not found: {}", webhook_id));
+    }
+
+    let secret = webhooks::generate_secret();
+    Vault::put(&webhooks::secret_key(&webhook_id), &secret).map_err(|e| e.to_string())?;
+    Ok(secret)
+}
+
+/// Send a signed test delivery to a webhook (event `draft_ready` with sample data)
+#[tauri::command]
+pub async fn send_test_webhook(
+    state: State<'_, AppState>,
+    webhook_id: String,
+) -> Result<(), String> {
+    let (dispatcher, delivery) = {
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+        let dispatcher = WebhookDispatcher::from_db(db).map_err(|e| e.to_string())?;
+        let notification = Notification::draft_ready("test", None, "AssistSupport test delivery")
+            .with_var("test", "true");
+        let delivery = dispatcher
+            .delivery_to(&webhook_id, &notification)
+            .ok_or("Webhook is not enabled or has no signing secret")?;
+        (dispatcher, delivery)
+    };
+    dispatcher.send(&delivery).await.map_err(|e| e.to_string())
+}
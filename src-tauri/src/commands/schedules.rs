@@ -0,0 +1,780 @@
+//! Recurring job schedule and job chain commands, and the background
+//! scheduler, which also resumes jobs interrupted by the previous shutdown and
+//! starts chained jobs once their dependencies succeed
+
+use super::kb_commands::index_kb_impl;
+use super::report_job_progress;
+use crate::backup::SCHEDULED_BACKUP_PREFIX;
+use crate::jobs::dag::{self, DependencyState};
+use crate::jobs::retry::{is_retryable_error, retry_async};
+use crate::jobs::schedule::{self, CronSchedule, JobSchedule};
+use crate::jobs::{
+    events, Job, JobContext, JobDoneEvent, JobPriority, JobStatus, JobType, LogLevel,
+    BATCH_GENERATE_JOB,
+};
+use crate::kb::indexer::IndexProgress;
+use crate::validation::validate_within_home;
+use crate::AppState;
+use chrono::{Local, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// How often the scheduler checks for due schedules
+const SCHEDULER_TICK_SECS: u64 = 30;
+const DEFAULT_STALE_DAYS: u32 = 7;
+const MAX_PREVIEW_RUNS: usize = 20;
+const MAX_CHAIN_STEPS: usize = 20;
+
+/// Check job-type specific parameters before a schedule is saved
+fn validate_params(job_type: &JobType, params: Option<&serde_json::Value>) -> Result<(), String> {
+    if !job_type.is_schedulable() {
+        return Err(format!("Job type cannot be scheduled: {}", job_type));
+    }
+    if *job_type == JobType::Backup {
+        let folder = params
+            .and_then(|p| p["folder"].as_str())
+            .ok_or("Backup schedules require a destination folder")?;
+        validate_within_home(Path::new(folder)).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// List job schedules with their next and last run
+#[tauri::command]
+pub fn list_job_schedules(state: State<'_, AppState>) -> Result<Vec<JobSchedule>, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.list_job_schedules().map_err(|e| e.to_string())
+}
+
+/// Create a recurring job schedule.
+/// `cron` is a five-field cron expression in local time (or @hourly, @daily,
+/// @weekly, @monthly). Backup schedules need `params.folder` and accept
+/// `params.retention` (scheduled backups to keep); stale-source marking
+/// accepts `params.days_threshold`; feed polling accepts `params.source_id`
+/// (every feed when omitted).
+#[tauri::command]
+pub fn create_job_schedule(
+    state: State<'_, AppState>,
+    name: String,
+    cron: String,
+    job_type: String,
+    params: Option<serde_json::Value>,
+    enabled: Option<bool>,
+) -> Result<JobSchedule, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Schedule name is required".to_string());
+    }
+    let job_type: JobType = job_type
+        .parse()
+        .map_err(|_| format!("Invalid job type: {}", job_type))?;
+    validate_params(&job_type, params.as_ref())?;
+
+    let mut schedule =
+        JobSchedule::new(name, &cron, job_type, params).map_err(|e| e.to_string())?;
+    schedule.enabled = enabled.unwrap_or(true);
+
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.save_job_schedule(&schedule).map_err(|e| e.to_string())?;
+    Ok(schedule)
+}
+
+/// Update a schedule's name, cron expression, parameters or enabled flag.
+/// The next run is recomputed from now.
+#[tauri::command]
+pub fn update_job_schedule(
+    state: State<'_, AppState>,
+    schedule_id: String,
+    name: Option<String>,
+    cron: Option<String>,
+    params: Option<serde_json::Value>,
+    enabled: Option<bool>,
+) -> Result<JobSchedule, String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    let mut schedule = db
+        .get_job_schedule(&schedule_id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Schedule not found")?;
+
+    if let Some(name) = name.as_deref().map(str::trim) {
+        if name.is_empty() {
+            return Err("Schedule name is required".to_string());
+        }
+        schedule.name = name.to_string();
+    }
+    if let Some(cron) = cron {
+        schedule.cron = cron.trim().to_string();
+    }
+    if let Some(params) = params {
+        validate_params(&schedule.job_type, Some(&params))?;
+        schedule.params = Some(params);
+    }
+    if let Some(enabled) = enabled {
+        schedule.enabled = enabled;
+    }
+
+    let now = Utc::now();
+    schedule.next_run_at =
+        schedule::next_run_after(&schedule.cron, now).map_err(|e| e.to_string())?;
+    schedule.updated_at = now;
+    db.save_job_schedule(&schedule).map_err(|e| e.to_string())?;
+    Ok(schedule)
+}
+
+/// Delete a schedule (jobs from past runs are kept)
+#[tauri::command]
+pub fn delete_job_schedule(state: State<'_, AppState>, schedule_id: String) -> Result<(), String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    db.delete_job_schedule(&schedule_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Preview the next run times (RFC 3339, local offset) for a cron expression
+#[tauri::command]
+pub fn preview_cron_schedule(cron: String, count: Option<usize>) -> Result<Vec<String>, String> {
+    let schedule = cron.parse::<CronSchedule>().map_err(|e| e.to_string())?;
+    let count = count.unwrap_or(5).min(MAX_PREVIEW_RUNS);
+    Ok(schedule
+        .upcoming(&Local::now(), count)
+        .iter()
+        .map(|t| t.to_rfc3339())
+        .collect())
+}
+
+/// Run a schedule immediately and return the job ID.
+/// The regular next run time is left unchanged.
+#[tauri::command]
+pub fn run_job_schedule_now(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    schedule_id: String,
+) -> Result<String, String> {
+    let job = {
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+        let schedule = db
+            .get_job_schedule(&schedule_id)
+            .map_err(|e| e.to_string())?
+            .ok_or("Schedule not found")?;
+        if !state.jobs.begin_scheduled_run(&schedule.id) {
+            return Err("This schedule is already running".to_string());
+        }
+        let job = schedule.create_job().with_priority(JobPriority::High);
+        let created = db.create_job(&job).and_then(|_| {
+            db.record_job_schedule_run(
+                &schedule.id,
+                Some(&job.id),
+                Utc::now(),
+                schedule.next_run_at,
+            )
+        });
+        if let Err(e) = created {
+            state.jobs.end_scheduled_run(&schedule.id);
+            return Err(e.to_string());
+        }
+        job
+    };
+
+    let job_id = job.id.clone();
+    tauri::async_runtime::spawn(run_job(app, Some(schedule_id), job));
+    Ok(job_id)
+}
+
+/// A step in a job chain
+#[derive(Debug, Clone, Deserialize)]
+pub struct JobChainStep {
+    /// Key other steps use to depend on this one
+    pub key: String,
+    pub job_type: String,
+    #[serde(default)]
+    pub params: Option<serde_json::Value>,
+    #[serde(default)]
+    pub priority: Option<JobPriority>,
+    /// Keys of steps that must succeed first
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+/// Create a chain of jobs that run in dependency order (e.g. re-index, then
+/// re-embed). A step starts once every step it depends on has succeeded and
+/// fails without running if any of them fails. Returns job IDs in step order.
+#[tauri::command]
+pub fn create_job_chain(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    steps: Vec<JobChainStep>,
+) -> Result<Vec<String>, String> {
+    if steps.is_empty() {
+        return Err("A job chain needs at least one step".to_string());
+    }
+    if steps.len() > MAX_CHAIN_STEPS {
+        return Err(format!(
+            "A job chain can have at most {} steps",
+            MAX_CHAIN_STEPS
+        ));
+    }
+
+    let mut job_types = Vec::with_capacity(steps.len());
+    for step in &steps {
+        let job_type: JobType = step
+            .job_type
+            .parse()
+            .map_err(|_| format!("Invalid job type: {}", step.job_type))?;
+        validate_params(&job_type, step.params.as_ref())?;
+        job_types.push(job_type);
+    }
+    let graph: Vec<(&str, Vec<&str>)> = steps
+        .iter()
+        .map(|s| {
+            let deps = s.depends_on.iter().map(String::as_str).collect();
+            (s.key.as_str(), deps)
+        })
+        .collect();
+    let order = dag::topological_order(&graph).map_err(|e| e.to_string())?;
+
+    // Create jobs in dependency order so each step knows its dependencies' IDs
+    let chain_id = uuid::Uuid::new_v4().to_string();
+    let mut job_ids: HashMap<&str, String> = HashMap::new();
+    let mut jobs = Vec::with_capacity(steps.len());
+    for i in order {
+        let step = &steps[i];
+        let depends_on = step
+            .depends_on
+            .iter()
+            .filter_map(|key| job_ids.get(key.as_str()).cloned())
+            .collect();
+        let mut job = Job::new(job_types[i].clone())
+            .with_metadata(serde_json::json!({
+                "chain_id": chain_id,
+                "chain_step": step.key,
+                "params": step.params,
+            }))
+            .with_dependencies(depends_on);
+        if let Some(priority) = step.priority {
+            job = job.with_priority(priority);
+        }
+        job_ids.insert(&step.key, job.id.clone());
+        jobs.push(job);
+    }
+
+    {
+        let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+        let db = db_lock.as_ref().ok_or("Database not initialized")?;
+        for job in &jobs {
+            db.create_job(job).map_err(|e| e.to_string())?;
+        }
+    }
+
+    // Steps without dependencies start now; the rest are started as their
+    // dependencies finish
+    for job in jobs.into_iter().filter(|j| j.depends_on.is_empty()) {
+        tauri::async_runtime::spawn(run_job(app.clone(), None, job));
+    }
+    Ok(steps
+        .iter()
+        .map(|s| job_ids[s.key.as_str()].clone())
+        .collect())
+}
+
+/// Background loop that starts due schedules. On the first tick after the
+/// database is unlocked, jobs interrupted by the previous shutdown are resumed
+/// and runs missed while the app was closed execute once.
+pub async fn run_scheduler(app: AppHandle) {
+    if !app.state::<AppState>().jobs.start_scheduler() {
+        return;
+    }
+
+    let mut interval = tokio::time::interval(Duration::from_secs(SCHEDULER_TICK_SECS));
+    let mut recovered = false;
+    loop {
+        interval.tick().await;
+        if !recovered {
+            let Some(jobs) = recover_interrupted_jobs(&app) else {
+                continue;
+            };
+            recovered = true;
+            for job in jobs {
+                tauri::async_runtime::spawn(resume_job(app.clone(), job));
+            }
+        }
+        for (schedule_id, job) in claim_due_jobs(&app) {
+            tauri::async_runtime::spawn(run_job(app.clone(), Some(schedule_id), job));
+        }
+        spawn_ready_jobs(&app);
+    }
+}
+
+/// Re-queue jobs left queued or running by the previous shutdown and return
+/// the ones to resume. Jobs that cannot be resumed are marked failed so they
+/// don't sit in the queue forever. Returns None until the database is unlocked.
+fn recover_interrupted_jobs(app: &AppHandle) -> Option<Vec<Job>> {
+    let state = app.state::<AppState>();
+    let db_lock = state.db.lock().ok()?;
+    let db = db_lock.as_ref()?;
+
+    let interrupted = match db.list_interrupted_jobs(state.jobs.started_at()) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            tracing::warn!("Failed to load interrupted jobs: {}", e);
+            return Some(Vec::new());
+        }
+    };
+
+    let mut resumed = Vec::new();
+    for job in interrupted {
+        // Jobs still waiting on dependencies are started by `spawn_ready_jobs`
+        if job.status == JobStatus::Queued && !job.depends_on.is_empty() {
+            continue;
+        }
+        let was_running = job.status == JobStatus::Running;
+        let resumable = job.job_type.is_resumable()
+            && job
+                .schedule_id()
+                .is_none_or(|id| state.jobs.begin_scheduled_run(id));
+        if !resumable {
+            let _ = db.update_job_status(
+                &job.id,
+                JobStatus::Failed,
+                Some("Interrupted by app restart"),
+            );
+            let _ = db.add_job_log(
+                &job.id,
+                LogLevel::Error,
+                "Interrupted by app restart and cannot be resumed",
+            );
+            continue;
+        }
+
+        let message = if was_running {
+            "Interrupted by app restart; resuming"
+        } else {
+            "Resuming queued job after app restart"
+        };
+        let _ = db.update_job_status(&job.id, JobStatus::Queued, None);
+        let _ = db.add_job_log(&job.id, LogLevel::Info, message);
+        tracing::info!("Resuming {} job {}", job.job_type, job.id);
+        resumed.push(job);
+    }
+    Some(resumed)
+}
+
+/// Resume an interrupted job with the runner for its type
+async fn resume_job(app: AppHandle, job: Job) {
+    if job.job_type == JobType::Custom(BATCH_GENERATE_JOB.to_string()) {
+        let job_id = job.id.clone();
+        if let Err(e) = super::resume_batch_generate(&app, job).await {
+            tracing::warn!("Failed to resume batch job {}: {}", job_id, e);
+        }
+    } else {
+        let schedule_id = job.schedule_id().map(str::to_string);
+        run_job(app, schedule_id, job).await;
+    }
+}
+
+/// Start chained jobs whose dependencies have all succeeded
+fn spawn_ready_jobs(app: &AppHandle) {
+    for job in claim_ready_jobs(app) {
+        tauri::async_runtime::spawn(run_job(app.clone(), None, job));
+    }
+}
+
+/// Claim queued jobs whose dependencies have all succeeded, and fail the ones
+/// with a failed or cancelled dependency. Repeats until no more failures
+/// propagate, so the rest of a broken chain fails at once.
+fn claim_ready_jobs(app: &AppHandle) -> Vec<Job> {
+    let state = app.state::<AppState>();
+    let Ok(db_lock) = state.db.lock() else {
+        return Vec::new();
+    };
+    let Some(db) = db_lock.as_ref() else {
+        return Vec::new();
+    };
+
+    let mut ready = Vec::new();
+    loop {
+        let waiting = match db.list_jobs_with_pending_dependencies() {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                tracing::warn!("Failed to load chained jobs: {}", e);
+                break;
+            }
+        };
+
+        let mut propagated = false;
+        for job in waiting {
+            if state.jobs.is_job_active(&job.id) {
+                continue;
+            }
+            let statuses = match db.get_job_statuses(&job.depends_on) {
+                Ok(statuses) => statuses,
+                Err(e) => {
+                    tracing::warn!("Failed to check dependencies of job {}: {}", job.id, e);
+                    continue;
+                }
+            };
+            match dag::dependency_state(&job.depends_on, &statuses) {
+                DependencyState::Waiting => {}
+                DependencyState::Ready => {
+                    // Registering marks the job active so it is claimed once
+                    state.jobs.register_job(&job.id);
+                    ready.push(job);
+                }
+                DependencyState::Failed(reason) => {
+                    // Only a recorded failure counts as progress; otherwise
+                    // the job stays queued and looping again would spin
+                    if let Err(e) = db.update_job_status(&job.id, JobStatus::Failed, Some(&reason))
+                    {
+                        tracing::warn!("Failed to fail chained job {}: {}", job.id, e);
+                        continue;
+                    }
+                    let _ =
+                        db.add_job_log(&job.id, LogLevel::Error, &format!("Not run: {}", reason));
+                    let _ = app.emit(
+                        events::JOB_DONE,
+                        JobDoneEvent {
+                            job_id: job.id,
+                            status: JobStatus::Failed,
+                            error: Some(reason),
+                        },
+                    );
+                    propagated = true;
+                }
+            }
+        }
+        if !propagated {
+            break;
+        }
+    }
+    ready
+}
+
+/// Create jobs for due schedules and advance their next run times.
+/// A schedule whose previous run is still active skips this run.
+fn claim_due_jobs(app: &AppHandle) -> Vec<(String, Job)> {
+    let state = app.state::<AppState>();
+    let Ok(db_lock) = state.db.lock() else {
+        return Vec::new();
+    };
+    let Some(db) = db_lock.as_ref() else {
+        return Vec::new();
+    };
+
+    let now = Utc::now();
+    let due = match db.list_due_job_schedules(now) {
+        Ok(due) => due,
+        Err(e) => {
+            tracing::warn!("Failed to load job schedules: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut claimed = Vec::new();
+    for schedule in due {
+        let next_run_at = schedule::next_run_after(&schedule.cron, now).unwrap_or(None);
+        if !state.jobs.begin_scheduled_run(&schedule.id) {
+            tracing::info!(
+                "Skipping run of '{}': previous run still active",
+                schedule.name
+            );
+            let _ = db.record_job_schedule_run(&schedule.id, None, now, next_run_at);
+            continue;
+        }
+
+        let job = schedule.create_job();
+        let result = db.create_job(&job).and_then(|_| {
+            db.record_job_schedule_run(&schedule.id, Some(&job.id), now, next_run_at)
+        });
+        match result {
+            Ok(()) => claimed.push((schedule.id, job)),
+            Err(e) => {
+                tracing::warn!("Failed to start scheduled job '{}': {}", schedule.name, e);
+                state.jobs.end_scheduled_run(&schedule.id);
+            }
+        }
+    }
+    claimed
+}
+
+/// Execute one job, recording status and logs. `schedule_id` is released
+/// when the run ends.
+pub(crate) async fn run_job(app: AppHandle, schedule_id: Option<String>, job: Job) {
+    let state = app.state::<AppState>();
+    let ctx = JobContext::new(job.id.clone(), state.jobs.register_job(&job.id));
+    let record =
+        |status: Option<JobStatus>, level: LogLevel, message: &str, error: Option<&str>| {
+            if let Ok(db_lock) = state.db.lock() {
+                if let Some(db) = db_lock.as_ref() {
+                    if let Some(status) = status {
+                        let _ = db.update_job_status(&job.id, status, error);
+                    }
+                    let _ = db.add_job_log(&job.id, level, message);
+                }
+            }
+            let _ = app.emit(events::JOB_LOG, ctx.log_event(level, message));
+        };
+
+    // Wait for a slot in the job's concurrency group
+    let permit = state
+        .jobs
+        .acquire_slot(&job.job_type, job.priority, &ctx.cancel_token)
+        .await;
+    let result = if permit.is_some() {
+        record(
+            Some(JobStatus::Running),
+            LogLevel::Info,
+            "Run started",
+            None,
+        );
+        let policy = state.jobs.retry_policy(&job.job_type);
+        retry_async(
+            &policy,
+            |e: &String| is_retryable_error(e),
+            |attempt, e, delay| {
+                let message = format!(
+                    "Attempt {} of {} failed: {}; retrying in {}s",
+                    attempt,
+                    policy.max_attempts,
+                    e,
+                    delay.as_secs()
+                );
+                record(None, LogLevel::Warning, &message, None);
+                !ctx.is_cancelled()
+            },
+            || execute_job(&app, &job, &ctx),
+        )
+        .await
+    } else {
+        Err("Cancelled while waiting to run".to_string())
+    };
+    drop(permit);
+
+    let (status, error) = match result {
+        _ if ctx.is_cancelled() => {
+            record(
+                Some(JobStatus::Cancelled),
+                LogLevel::Warning,
+                "Run cancelled",
+                None,
+            );
+            (JobStatus::Cancelled, None)
+        }
+        Ok(summary) => {
+            record(Some(JobStatus::Succeeded), LogLevel::Info, &summary, None);
+            (JobStatus::Succeeded, None)
+        }
+        Err(e) => {
+            record(
+                Some(JobStatus::Failed),
+                LogLevel::Error,
+                &e,
+                Some(e.as_str()),
+            );
+            (JobStatus::Failed, Some(e))
+        }
+    };
+
+    state.jobs.unregister_job(&job.id);
+    if let Some(schedule_id) = schedule_id {
+        state.jobs.end_scheduled_run(&schedule_id);
+    }
+    let _ = app.emit(events::JOB_DONE, ctx.done_event(status, error));
+    spawn_ready_jobs(&app);
+}
+
+/// Run the work for a job type and return a summary line
+async fn execute_job(app: &AppHandle, job: &Job, ctx: &JobContext) -> Result<String, String> {
+    let params = job
+        .metadata
+        .as_ref()
+        .map(|m| m["params"].clone())
+        .unwrap_or_default();
+
+    match job.job_type {
+        JobType::IndexKb => {
+            let result = index_kb_impl(app.clone(), app.state(), |progress| match progress {
+                IndexProgress::Processing {
+                    current,
+                    total,
+                    file_name,
+                } => {
+                    let (current, total) = (*current as u64, *total as u64);
+                    report_job_progress(app, ctx, "indexing", current, Some(total), file_name);
+                }
+                IndexProgress::PageProcessed {
+                    current,
+                    total,
+                    file_name,
+                    page,
+                    total_pages,
+                } => {
+                    let (current, total) = (*current as u64, *total as u64);
+                    let message = format!("{} (page {} of {})", file_name, page, total_pages);
+                    report_job_progress(app, ctx, "indexing", current, Some(total), &message);
+                }
+                _ => {}
+            })
+            .await?;
+            Ok(format!(
+                "Indexed {} of {} files ({} errors)",
+                result.indexed, result.total_files, result.errors
+            ))
+        }
+        JobType::GenerateEmbeddings => {
+            let result =
+                super::generate_kb_embeddings_impl(app.state(), app.clone(), |done, total| {
+                    let message = format!("Embedded {} of {} chunks", done, total);
+                    report_job_progress(
+                        app,
+                        ctx,
+                        "embedding",
+                        done as u64,
+                        Some(total as u64),
+                        &message,
+                    );
+                })
+                .await?;
+            Ok(format!(
+                "Created {} vectors for {} chunks",
+                result.vectors_created, result.chunks_processed
+            ))
+        }
+        JobType::MarkStaleSources => {
+            let days = params["days_threshold"]
+                .as_u64()
+                .map(|d| d as u32)
+                .unwrap_or(DEFAULT_STALE_DAYS);
+            report_job_progress(app, ctx, "marking", 0, Some(1), "Checking sources");
+            let count = super::mark_stale_sources(app.state(), Some(days))?;
+            report_job_progress(app, ctx, "marking", 1, Some(1), "Checked sources");
+            Ok(format!("Marked {} sources stale", count))
+        }
+        JobType::RefreshSources => {
+            let include_active = params["include_active"].as_bool().unwrap_or(false);
+            let sources = {
+                let state = app.state::<AppState>();
+                let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+                let db = db_lock.as_ref().ok_or("Database not initialized")?;
+                db.list_ingest_sources(None).map_err(|e| e.to_string())?
+            };
+
+            let sources: Vec<_> = sources
+                .into_iter()
+                .filter(|s| s.status == "stale" || (include_active && s.status == "active"))
+                .collect();
+            let total = sources.len() as u64;
+            let (mut refreshed, mut failed) = (0, 0);
+            for (i, source) in sources.into_iter().enumerate() {
+                if ctx.is_cancelled() {
+                    break;
+                }
+                report_job_progress(
+                    app,
+                    ctx,
+                    "refreshing",
+                    i as u64,
+                    Some(total),
+                    &source.source_uri,
+                );
+                match super::retry_source(app.state(), source.id.clone()) {
+                    Ok(_) => refreshed += 1,
+                    Err(e) => {
+                        failed += 1;
+                        tracing::warn!("Failed to refresh source {}: {}", source.source_uri, e);
+                    }
+                }
+            }
+            report_job_progress(
+                app,
+                ctx,
+                "refreshing",
+                refreshed + failed,
+                Some(total),
+                "Refresh finished",
+            );
+            Ok(format!(
+                "Refreshed {} sources ({} failed)",
+                refreshed, failed
+            ))
+        }
+        JobType::Backup => {
+            let folder = params["folder"]
+                .as_str()
+                .ok_or("Backup schedule has no destination folder")?;
+            let folder = validate_within_home(Path::new(folder)).map_err(|e| e.to_string())?;
+            std::fs::create_dir_all(&folder).map_err(|e| e.to_string())?;
+            let path = folder.join(format!(
+                "{}{}.zip",
+                SCHEDULED_BACKUP_PREFIX,
+                Local::now().format("%Y%m%d-%H%M%S")
+            ));
+
+            let path_display = path.display().to_string();
+            report_job_progress(app, ctx, "exporting", 0, Some(1), &path_display);
+            let summary = {
+                let state = app.state::<AppState>();
+                let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+                let db = db_lock.as_ref().ok_or("Database not initialized")?;
+                let summary = crate::backup::export_backup(db, &path, None, None)
+                    .map_err(|e| e.to_string())?;
+                if let Err(e) = crate::backup::record_backup_success(db, Utc::now()) {
+                    tracing::warn!("Failed to record backup time: {}", e);
+                }
+                summary
+            };
+            report_job_progress(app, ctx, "exporting", 1, Some(1), &path_display);
+
+            let mut message = format!("Backup written to {}", summary.path);
+            if let Some(retention) = params["retention"].as_u64() {
+                match crate::backup::prune_backups(&folder, retention.max(1) as usize) {
+                    Ok(pruned) if !pruned.is_empty() => {
+                        message.push_str(&format!(" ({} old backups removed)", pruned.len()));
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::warn!("Failed to prune old backups in {}: {}", folder.display(), e)
+                    }
+                }
+            }
+            Ok(message)
+        }
+        JobType::ExportDrafts => super::batch_export::run_export_drafts_job(app, ctx, &params),
+        JobType::IngestWeb => super::crawl::run_crawl_job(app, ctx, &params).await,
+        JobType::PollFeeds => {
+            let source_ids = super::feeds::feeds_to_poll(app.state(), &params)?;
+            let total = source_ids.len() as u64;
+            let (mut ingested, mut failed) = (0, 0);
+            let mut last_error = None;
+            for (i, source_id) in source_ids.into_iter().enumerate() {
+                if ctx.is_cancelled() {
+                    break;
+                }
+                report_job_progress(app, ctx, "polling", i as u64, Some(total), &source_id);
+                match super::feeds::poll_feed(app.state(), source_id.clone()) {
+                    Ok(result) => ingested += result.ingested,
+                    Err(e) => {
+                        failed += 1;
+                        tracing::warn!("Failed to poll feed {}: {}", source_id, e);
+                        last_error = Some(e);
+                    }
+                }
+            }
+            report_job_progress(app, ctx, "polling", total, Some(total), "Polling finished");
+            // Fail (and possibly retry) the run only when no feed could be polled
+            if let Some(e) = last_error.filter(|_| failed == total) {
+                return Err(e);
+            }
+            Ok(format!(
+                "Ingested {} new entries from {} feeds ({} failed)",
+                ingested, total, failed
+            ))
+        }
+        _ => Err(format!("No runner for job type: {}", job.job_type)),
+    }
+}
@@ -1,20 +1,20 @@
 use super::*;
+use crate::audit::AuditExportFormat;
+use crate::security::BiometricAuth;
 
 pub(crate) fn has_hf_token_impl() -> Result<bool, String> {
-    FileKeyStore::get_token(TOKEN_HUGGINGFACE)
+    Vault::get(TOKEN_HUGGINGFACE)
         .map(|t| t.is_some())
         .map_err(|e| e.to_string())
 }
 
 pub(crate) fn set_hf_token_impl(token: String) -> Result<(), String> {
-    FileKeyStore::store_token(TOKEN_HUGGINGFACE, &token).map_err(|e| e.to_string())?;
-    audit::audit_token_set("huggingface");
+    Vault::put(TOKEN_HUGGINGFACE, &token).map_err(|e| e.to_string())?;
     Ok(())
 }
 
 pub(crate) fn clear_hf_token_impl() -> Result<(), String> {
-    FileKeyStore::delete_token(TOKEN_HUGGINGFACE).map_err(|e| e.to_string())?;
-    audit::audit_token_cleared("huggingface");
+    Vault::delete(TOKEN_HUGGINGFACE).map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -25,25 +25,21 @@ pub(crate) fn set_github_token_impl(host: String, token: String) -> Result<(), S
         return Err("Token cannot be empty".to_string());
     }
     let key = format!("{}{}", GITHUB_TOKEN_PREFIX, host);
-    FileKeyStore::store_token(&key, token).map_err(|e| e.to_string())?;
-    audit::audit_token_set(&format!("github:{}", host));
+    Vault::put(&key, token).map_err(|e| e.to_string())?;
     Ok(())
 }
 
 pub(crate) fn clear_github_token_impl(host: String) -> Result<(), String> {
     let host = normalize_github_host(&host)?;
     let key = format!("{}{}", GITHUB_TOKEN_PREFIX, host);
-    FileKeyStore::delete_token(&key).map_err(|e| e.to_string())?;
-    audit::audit_token_cleared(&format!("github:{}", host));
+    Vault::delete(&key).map_err(|e| e.to_string())?;
     Ok(())
 }
 
 pub(crate) fn has_github_token_impl(host: String) -> Result<bool, String> {
     let host = normalize_github_host(&host)?;
     let key = format!("{}{}", GITHUB_TOKEN_PREFIX, host);
-    Ok(FileKeyStore::get_token(&key)
-        .map_err(|e| e.to_string())?
-        .is_some())
+    Ok(Vault::get(&key).map_err(|e| e.to_string())?.is_some())
 }
 
 pub(crate) fn get_audit_entries_impl(
@@ -52,10 +48,52 @@ pub(crate) fn get_audit_entries_impl(
     crate::audit::read_audit_entries(limit).map_err(|e| e.to_string())
 }
 
-pub(crate) fn export_audit_log_impl(export_path: String) -> Result<String, String> {
+pub(crate) fn verify_audit_log_impl() -> Result<crate::audit::AuditVerification, String> {
+    crate::audit::verify_audit_log().map_err(|e| e.to_string())
+}
+
+pub(crate) fn export_audit_log_impl(
+    export_path: String,
+    format: AuditExportFormat,
+    anonymize: bool,
+) -> Result<String, String> {
+    let entries = crate::audit::read_audit_entries(None).map_err(|e| e.to_string())?;
+    write_audit_export(&export_path, &entries, format, anonymize)
+}
+
+pub(crate) fn get_audit_retention_impl() -> Result<crate::audit::AuditRetention, String> {
+    crate::audit::get_audit_retention().map_err(|e| e.to_string())
+}
+
+pub(crate) fn set_audit_retention_impl(
+    retention: crate::audit::AuditRetention,
+) -> Result<(), String> {
+    crate::audit::set_audit_retention(retention).map_err(|e| e.to_string())
+}
+
+pub(crate) fn list_audit_archives_impl() -> Result<Vec<crate::audit::AuditArchive>, String> {
+    crate::audit::list_audit_archives().map_err(|e| e.to_string())
+}
+
+pub(crate) fn export_audit_archive_impl(
+    name: String,
+    export_path: String,
+    format: AuditExportFormat,
+    anonymize: bool,
+) -> Result<String, String> {
+    let entries = crate::audit::read_archive_entries(&name).map_err(|e| e.to_string())?;
+    write_audit_export(&export_path, &entries, format, anonymize)
+}
+
+fn write_audit_export(
+    export_path: &str,
+    entries: &[crate::audit::AuditEntry],
+    format: AuditExportFormat,
+    anonymize: bool,
+) -> Result<String, String> {
     use std::path::Path;
 
-    let path = Path::new(&export_path);
+    let path = Path::new(export_path);
     let validated = validate_within_home(path).map_err(|e| match e {
         ValidationError::PathTraversal => {
             "Export path must be within your home directory".to_string()
@@ -63,9 +101,18 @@ pub(crate) fn export_audit_log_impl(export_path: String) -> Result<String, Strin
         _ => format!("Invalid export path: {}", e),
     })?;
 
-    let entries = crate::audit::read_audit_entries(None).map_err(|e| e.to_string())?;
-    let json = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
-    std::fs::write(&validated, json).map_err(|e| e.to_string())?;
+    let anonymized;
+    let entries = if anonymize {
+        anonymized =
+            crate::anonymize::with_mapping(|p| crate::audit::anonymize_entries(entries, p))
+                .map_err(|e| e.to_string())?;
+        &anonymized[..]
+    } else {
+        entries
+    };
+
+    let content = crate::audit::format_audit_entries(entries, format).map_err(|e| e.to_string())?;
+    std::fs::write(&validated, content).map_err(|e| e.to_string())?;
     let _ = crate::security::set_secure_permissions(&validated, crate::security::FILE_PERMISSIONS);
 
     Ok(validated.to_string_lossy().to_string())
@@ -133,6 +180,120 @@ pub(crate) fn lock_app_impl(state: State<'_, AppState>) -> Result<(), String> {
             rusqlite::params![get_device_identifier()],
         )
         .map_err(|e| e.to_string())?;
+    crate::access::lock();
+    Ok(())
+}
+
+pub(crate) fn set_namespace_restricted_impl(
+    state: State<'_, AppState>,
+    namespace_id: String,
+    restricted: bool,
+) -> Result<(), String> {
+    let namespace_id =
+        normalize_and_validate_namespace_id(&namespace_id).map_err(|e| e.to_string())?;
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+    if !restricted {
+        crate::access::check_namespace(db, &namespace_id, "unrestrict_namespace")
+            .map_err(|e| e.to_string())?;
+    }
+    if !db
+        .set_namespace_restricted(&namespace_id, restricted)
+        .map_err(|e| e.to_string())?
+    {
+        return Err(format!("Namespace not found: {}", namespace_id));
+    }
+    Ok(())
+}
+
+pub(crate) fn unlock_restricted_namespaces_impl(
+    passphrase: Option<String>,
+) -> Result<RestrictedAccessStatus, String> {
+    let mode = FileKeyStore::get_storage_mode().map_err(|e| e.to_string())?;
+    let method = match (mode, passphrase) {
+        (KeyStorageMode::Passphrase | KeyStorageMode::Biometric, Some(passphrase)) => {
+            FileKeyStore::get_master_key_with_passphrase(&passphrase).map_err(|e| {
+                audit::audit_security_failure(
+                    audit::AuditEventType::AuthenticationFailed,
+                    "Restricted namespace unlock: passphrase rejected",
+                );
+                e.to_string()
+            })?;
+            "passphrase"
+        }
+        (KeyStorageMode::Passphrase, None) => return Err("Passphrase required".to_string()),
+        (KeyStorageMode::Biometric, None) => {
+            BiometricAuth::authenticate("unlock restricted namespaces").map_err(|e| {
+                audit::audit_security_failure(
+                    audit::AuditEventType::AuthenticationFailed,
+                    "Restricted namespace unlock: biometric check failed",
+                );
+                e.to_string()
+            })?;
+            "biometric"
+        }
+        // Keychain mode has no passphrase; use biometrics where the OS offers
+        // them, otherwise the signed-in OS account is the trust boundary
+        (KeyStorageMode::Keychain, _) if BiometricAuth::is_available() => {
+            BiometricAuth::authenticate("unlock restricted namespaces")
+                .map_err(|e| e.to_string())?;
+            "biometric"
+        }
+        (KeyStorageMode::Keychain, _) => "os_session",
+    };
+
+    crate::access::unlock();
+    audit::audit_restricted_access_unlocked(method);
+    Ok(restricted_access_status())
+}
+
+pub(crate) fn restricted_access_status() -> RestrictedAccessStatus {
+    let unlocked_until = crate::access::unlocked_until();
+    RestrictedAccessStatus {
+        unlocked: unlocked_until.is_some(),
+        unlocked_until: unlocked_until.map(|until| until.to_rfc3339()),
+    }
+}
+
+pub(crate) fn rotate_passphrase_impl(
+    state: State<'_, AppState>,
+    old_passphrase: String,
+    new_passphrase: String,
+) -> Result<(), String> {
+    let db_lock = state.db.lock().map_err(|e| e.to_string())?;
+    let db = db_lock.as_ref().ok_or("Database not initialized")?;
+
+    crate::security::KeyRotation::rotate_passphrase(&old_passphrase, &new_passphrase, |key| {
+        db.rekey(key)
+            .map_err(|e| crate::security::SecurityError::KeyRotationFailed(e.to_string()))
+    })
+    .map_err(|e| e.to_string())?;
+
+    // Sessions were unlocked with the old passphrase; force re-verification
+    db.conn()
+        .execute("DELETE FROM session_tokens", [])
+        .map_err(|e| e.to_string())?;
+    audit::audit_key_rotated();
+    Ok(())
+}
+
+pub(crate) fn get_biometric_status_impl() -> Result<BiometricStatus, String> {
+    let mode = FileKeyStore::get_storage_mode().map_err(|e| e.to_string())?;
+    Ok(BiometricStatus {
+        available: crate::security::BiometricAuth::is_available(),
+        enabled: mode == KeyStorageMode::Biometric,
+    })
+}
+
+pub(crate) fn enable_biometric_unlock_impl(passphrase: String) -> Result<(), String> {
+    FileKeyStore::enable_biometric_unlock(&passphrase).map_err(|e| e.to_string())?;
+    audit::audit_storage_mode_changed(&KeyStorageMode::Biometric.to_string());
+    Ok(())
+}
+
+pub(crate) fn disable_biometric_unlock_impl() -> Result<(), String> {
+    FileKeyStore::disable_biometric_unlock().map_err(|e| e.to_string())?;
+    audit::audit_storage_mode_changed(&KeyStorageMode::Passphrase.to_string());
     Ok(())
 }
 
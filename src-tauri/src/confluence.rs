@@ -0,0 +1,597 @@
+//! Confluence Cloud integration
+//!
+//! Publishes finalized drafts as Confluence pages. Drafts are converted to
+//! Confluence storage format (XHTML): the response markdown is rendered with
+//! raw HTML disabled, followed by a numbered list of cited KB sources.
+//!
+//! The client also lists and reads space pages for KB ingestion
+//! (see `sources::confluence`).
+
+use crate::db::Database;
+use crate::exports::ExportedSource;
+use crate::security::{vault::Vault, SecureString, TOKEN_CONFLUENCE};
+use base64::{engine::general_purpose, Engine as _};
+use pulldown_cmark::{html, Event, Options, Parser};
+use reqwest::{header, Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use thiserror::Error;
+use zeroize::Zeroize;
+
+/// Integration type key in `integration_configs`
+pub const CONFLUENCE_INTEGRATION: &str = "confluence";
+
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+const MAX_TITLE_CHARS: usize = 255;
+
+/// Pages requested per call when listing a space
+const SPACE_PAGE_BATCH: usize = 50;
+
+/// Listing stops after this many pages of one space
+pub const MAX_SPACE_PAGES: usize = 5000;
+
+#[derive(Debug, Error)]
+pub enum ConfluenceError {
+    #[error("Request error: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("API error: {0}")]
+    Api(String),
+    #[error("Not configured")]
+    NotConfigured,
+    #[error("Authentication failed - check your API token")]
+    AuthFailed,
+    #[error("Parent page not found: {0}")]
+    ParentNotFound(String),
+    #[error("A page titled \"{0}\" already exists in this space")]
+    DuplicateTitle(String),
+    #[error("Invalid input: {0}")]
+    Invalid(String),
+}
+
+/// Confluence configuration (stored in `integration_configs`, token in the token store)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfluenceConfig {
+    /// Site URL including the `/wiki` context path, e.g. `https://acme.atlassian.net/wiki`
+    pub base_url: String,
+    pub email: String,
+}
+
+impl ConfluenceConfig {
+    pub fn validate(&self) -> Result<(), ConfluenceError> {
+        let parsed = url::Url::parse(&self.base_url)
+            .map_err(|e| ConfluenceError::Invalid(format!("Invalid base URL: {}", e)))?;
+        if parsed.scheme() != "https" {
+            return Err(ConfluenceError::Invalid(
+                "HTTPS is required for Confluence connections".into(),
+            ));
+        }
+        if !self.email.contains('@') {
+            return Err(ConfluenceError::Invalid("Invalid email address".into()));
+        }
+        Ok(())
+    }
+}
+
+/// Validate a Confluence space key (letters and digits; personal spaces start with `~`)
+pub fn validate_space_key(space: &str) -> Result<(), ConfluenceError> {
+    let key = space.strip_prefix('~').unwrap_or(space);
+    if key.is_empty() || key.len() > 255 || !key.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(ConfluenceError::Invalid(format!(
+            "Invalid space key: {}",
+            space
+        )));
+    }
+    Ok(())
+}
+
+/// Draft content to publish
+#[derive(Debug, Clone, Default)]
+pub struct PagePublication {
+    pub title: String,
+    pub summary: Option<String>,
+    pub response: String,
+    pub sources: Vec<ExportedSource>,
+    pub ticket_id: Option<String>,
+}
+
+/// A current page of a space, without its body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpacePage {
+    pub id: String,
+    pub title: String,
+    /// Version number, incremented on every edit
+    pub version: u64,
+    /// Web UI link, when the API returned one
+    pub url: Option<String>,
+}
+
+/// Pages in a `/rest/api/content` listing; `_links.base` is the site URL
+fn parse_space_pages(json: &serde_json::Value) -> Vec<SpacePage> {
+    let base = json["_links"]["base"].as_str();
+    json["results"]
+        .as_array()
+        .map(|results| {
+            results
+                .iter()
+                .filter_map(|page| {
+                    Some(SpacePage {
+                        id: page["id"].as_str()?.to_string(),
+                        title: page["title"].as_str().unwrap_or_default().to_string(),
+                        version: page["version"]["number"].as_u64().unwrap_or(0),
+                        url: base
+                            .zip(page["_links"]["webui"].as_str())
+                            .map(|(base, webui)| format!("{}{}", base, webui)),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Result of publishing a page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublishedPage {
+    pub page_id: String,
+    pub title: String,
+    pub url: Option<String>,
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Render markdown to XHTML with raw HTML shown as text
+fn markdown_to_storage(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    let parser = Parser::new_ext(markdown, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        other => other,
+    });
+    let mut out = String::new();
+    html::push_html(&mut out, parser);
+    out
+}
+
+/// Convert a draft to Confluence storage format
+pub fn to_storage_format(page: &PagePublication) -> String {
+    let mut out = String::new();
+
+    if let Some(summary) = page.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        out.push_str("<h2>Summary</h2>\n");
+        out.push_str(&markdown_to_storage(summary));
+    }
+
+    out.push_str("<h2>Resolution</h2>\n");
+    out.push_str(&markdown_to_storage(&page.response));
+
+    if !page.sources.is_empty() {
+        out.push_str("<h2>Sources</h2>\n<ol>\n");
+        for source in &page.sources {
+            out.push_str("<li>");
+            match &source.url {
+                Some(url) => out.push_str(&format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape_xml(url),
+                    escape_xml(&source.title)
+                )),
+                None => out.push_str(&escape_xml(&source.title)),
+            }
+            let details: Vec<String> = source
+                .citation_details()
+                .iter()
+                .map(|(label, value)| format!("{}: {}", label, escape_xml(value)))
+                .collect();
+            if !details.is_empty() {
+                out.push_str(&format!("<br/><small>{}</small>", details.join(" · ")));
+            }
+            out.push_str("</li>\n");
+        }
+        out.push_str("</ol>\n");
+    }
+
+    if let Some(ticket) = page.ticket_id.as_deref().filter(|t| !t.is_empty()) {
+        out.push_str(&format!(
+            "<p><em>Resolution from ticket {}.</em></p>\n",
+            escape_xml(ticket)
+        ));
+    }
+
+    out
+}
+
+/// Page title: explicit title, else first line of the summary, else a default
+pub fn page_title(explicit: Option<&str>, summary: Option<&str>) -> String {
+    explicit
+        .into_iter()
+        .chain(summary.and_then(|s| s.lines().next()))
+        .map(str::trim)
+        .find(|t| !t.is_empty())
+        .unwrap_or("Support resolution")
+        .chars()
+        .take(MAX_TITLE_CHARS)
+        .collect()
+}
+
+/// Confluence REST API client
+pub struct ConfluenceClient {
+    client: Client,
+    base_url: String,
+    auth_header: SecureString,
+}
+
+impl ConfluenceClient {
+    /// Create a new client; the API token is encoded immediately and the
+    /// intermediate credentials string cleared
+    pub fn new(base_url: &str, email: &str, api_token: &str) -> Self {
+        let mut auth = format!("{}:{}", email, api_token);
+        let auth_header =
+            SecureString::new(format!("Basic {}", general_purpose::STANDARD.encode(&auth)));
+        auth.zeroize();
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            auth_header,
+        }
+    }
+
+    /// Build a client from the stored configuration, or `None` when
+    /// Confluence publishing is disabled
+    pub fn from_db(db: &Database) -> Result<Option<Self>, ConfluenceError> {
+        let record = db
+            .get_integration_config(CONFLUENCE_INTEGRATION)
+            .map_err(|e| ConfluenceError::Api(e.to_string()))?;
+        let Some(record) = record.filter(|r| r.enabled) else {
+            return Ok(None);
+        };
+
+        let config: ConfluenceConfig = record
+            .config_json
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| ConfluenceError::Invalid(e.to_string()))?
+            .ok_or(ConfluenceError::NotConfigured)?;
+        let token = Vault::get(TOKEN_CONFLUENCE)
+            .map_err(|e| ConfluenceError::Api(e.to_string()))?
+            .ok_or(ConfluenceError::NotConfigured)?;
+
+        Ok(Some(Self::new(&config.base_url, &config.email, &token)))
+    }
+
+    fn check_status(status: StatusCode) -> Result<(), ConfluenceError> {
+        if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+            return Err(ConfluenceError::AuthFailed);
+        }
+        Ok(())
+    }
+
+    /// Test the connection by fetching the current user
+    pub async fn test_connection(&self) -> Result<bool, ConfluenceError> {
+        let resp = self
+            .client
+            .get(format!("{}/rest/api/user/current", self.base_url))
+            .header(header::AUTHORIZATION, self.auth_header.as_str())
+            .header(header::ACCEPT, "application/json")
+            .send()
+            .await?;
+        Ok(resp.status().is_success())
+    }
+
+    /// Find a page ID by exact title within a space
+    pub async fn find_page_id(
+        &self,
+        space: &str,
+        title: &str,
+    ) -> Result<Option<String>, ConfluenceError> {
+        let resp = self
+            .client
+            .get(format!("{}/rest/api/content", self.base_url))
+            .query(&[("spaceKey", space), ("title", title), ("type", "page")])
+            .header(header::AUTHORIZATION, self.auth_header.as_str())
+            .header(header::ACCEPT, "application/json")
+            .send()
+            .await?;
+        Self::check_status(resp.status())?;
+        if !resp.status().is_success() {
+            return Err(ConfluenceError::Api(format!(
+                "HTTP {} searching for page",
+                resp.status()
+            )));
+        }
+        let json: serde_json::Value = resp.json().await?;
+        Ok(json["results"]
+            .as_array()
+            .and_then(|results| results.first())
+            .and_then(|page| page["id"].as_str())
+            .map(str::to_string))
+    }
+
+    /// Resolve a parent page given as a numeric page ID or a page title
+    pub async fn resolve_parent(
+        &self,
+        space: &str,
+        parent: &str,
+    ) -> Result<String, ConfluenceError> {
+        let parent = parent.trim();
+        if !parent.is_empty() && parent.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(parent.to_string());
+        }
+        self.find_page_id(space, parent)
+            .await?
+            .ok_or_else(|| ConfluenceError::ParentNotFound(parent.to_string()))
+    }
+
+    /// Stable URI of a page, independent of its title
+    pub fn page_uri(&self, page_id: &str) -> String {
+        format!("{}/pages/viewpage.action?pageId={}", self.base_url, page_id)
+    }
+
+    /// List the current pages of a space with their versions, up to
+    /// `MAX_SPACE_PAGES`
+    pub async fn list_space_pages(&self, space: &str) -> Result<Vec<SpacePage>, ConfluenceError> {
+        validate_space_key(space)?;
+
+        let limit = SPACE_PAGE_BATCH.to_string();
+        let mut pages = Vec::new();
+        let mut start = 0usize;
+        loop {
+            let start_param = start.to_string();
+            let resp = self
+                .client
+                .get(format!("{}/rest/api/content", self.base_url))
+                .query(&[
+                    ("spaceKey", space),
+                    ("type", "page"),
+                    ("status", "current"),
+                    ("expand", "version"),
+                    ("limit", limit.as_str()),
+                    ("start", start_param.as_str()),
+                ])
+                .header(header::AUTHORIZATION, self.auth_header.as_str())
+                .header(header::ACCEPT, "application/json")
+                .send()
+                .await?;
+            let status = resp.status();
+            Self::check_status(status)?;
+            if status == StatusCode::NOT_FOUND {
+                return Err(ConfluenceError::Invalid(format!(
+                    "Space not found: {}",
+                    space
+                )));
+            }
+            if !status.is_success() {
+                return Err(ConfluenceError::Api(format!(
+                    "HTTP {} listing pages",
+                    status
+                )));
+            }
+
+            let json: serde_json::Value = resp.json().await?;
+            let batch_len = json["results"].as_array().map_or(0, Vec::len);
+            pages.extend(parse_space_pages(&json));
+            start += batch_len;
+
+            if batch_len == 0 || json["_links"]["next"].is_null() || pages.len() >= MAX_SPACE_PAGES
+            {
+                break;
+            }
+        }
+
+        pages.truncate(MAX_SPACE_PAGES);
+        Ok(pages)
+    }
+
+    /// Fetch a page body in storage format, with the version it belongs to
+    pub async fn page_storage(&self, page_id: &str) -> Result<(u64, String), ConfluenceError> {
+        if page_id.is_empty() || !page_id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ConfluenceError::Invalid(format!(
+                "Invalid page ID: {}",
+                page_id
+            )));
+        }
+
+        let resp = self
+            .client
+            .get(format!("{}/rest/api/content/{}", self.base_url, page_id))
+            .query(&[("expand", "body.storage,version")])
+            .header(header::AUTHORIZATION, self.auth_header.as_str())
+            .header(header::ACCEPT, "application/json")
+            .send()
+            .await?;
+        let status = resp.status();
+        Self::check_status(status)?;
+        if !status.is_success() {
+            return Err(ConfluenceError::Api(format!(
+                "HTTP {} fetching page {}",
+                status, page_id
+            )));
+        }
+
+        let json: serde_json::Value = resp.json().await?;
+        let storage = json["body"]["storage"]["value"]
+            .as_str()
+            .ok_or_else(|| ConfluenceError::Api("Missing page body in response".into()))?
+            .to_string();
+        Ok((json["version"]["number"].as_u64().unwrap_or(0), storage))
+    }
+
+    /// Create a page in `space` (optionally under `parent_id`)
+    pub async fn create_page(
+        &self,
+        space: &str,
+        parent_id: Option<&str>,
+        page: &PagePublication,
+    ) -> Result<PublishedPage, ConfluenceError> {
+        validate_space_key(space)?;
+
+        let mut body = serde_json::json!({
+            "type": "page",
+            "title": page.title,
+            "space": { "key": space },
+            "body": {
+                "storage": {
+                    "value": to_storage_format(page),
+                    "representation": "storage",
+                }
+            }
+        });
+        if let Some(parent_id) = parent_id {
+            body["ancestors"] = serde_json::json!([{ "id": parent_id }]);
+        }
+
+        let resp = self
+            .client
+            .post(format!("{}/rest/api/content", self.base_url))
+            .header(header::AUTHORIZATION, self.auth_header.as_str())
+            .header(header::ACCEPT, "application/json")
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        Self::check_status(status)?;
+        if !status.is_success() {
+            let text = resp.text().await.unwrap_or_default();
+            if status == StatusCode::BAD_REQUEST && text.contains("already exists") {
+                return Err(ConfluenceError::DuplicateTitle(page.title.clone()));
+            }
+            return Err(ConfluenceError::Api(format!(
+                "HTTP {} creating page",
+                status
+            )));
+        }
+
+        let json: serde_json::Value = resp.json().await?;
+        let page_id = json["id"]
+            .as_str()
+            .ok_or_else(|| ConfluenceError::Api("Missing page ID in response".into()))?
+            .to_string();
+        let url = match (
+            json["_links"]["base"].as_str(),
+            json["_links"]["webui"].as_str(),
+        ) {
+            (Some(base), Some(webui)) => Some(format!("{}{}", base, webui)),
+            _ => None,
+        };
+
+        Ok(PublishedPage {
+            page_id,
+            title: page.title.clone(),
+            url,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn publication() -> PagePublication {
+        PagePublication {
+            title: "VPN error 809".to_string(),
+            summary: Some("User cannot connect to VPN".to_string()),
+            response: "Try these steps:\n\n1. Restart the **VPN client**\n2. Reboot <script>x</script>\n\nSee [1].".to_string(),
+            sources: vec![
+                ExportedSource {
+                    title: "VPN & Remote Access".to_string(),
+                    path: None,
+                    url: Some("https://kb.example.com/vpn?a=1&b=2".to_string()),
+                    ..Default::default()
+                },
+                ExportedSource {
+                    title: "Local notes".to_string(),
+                    path: Some("/kb/notes.md".to_string()),
+                    url: None,
+                    ..Default::default()
+                },
+            ],
+            ticket_id: Some("HELP-42".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_storage_format_structure() {
+        let storage = to_storage_format(&publication());
+        assert!(storage.starts_with("<h2>Summary</h2>\n<p>User cannot connect to VPN</p>"));
+        assert!(storage.contains("<h2>Resolution</h2>"));
+        assert!(storage.contains("<ol>\n<li>Restart the <strong>VPN client</strong></li>"));
+        assert!(storage.contains(
+            "<li><a href=\"https://kb.example.com/vpn?a=1&amp;b=2\">VPN &amp; Remote Access</a><br/><small>Source: https://kb.example.com/vpn?a=1&amp;b=2</small></li>"
+        ));
+        assert!(storage.contains("<li>Local notes<br/><small>Source: /kb/notes.md</small></li>"));
+        assert!(storage.contains("Resolution from ticket HELP-42."));
+    }
+
+    #[test]
+    fn test_raw_html_is_escaped() {
+        let storage = to_storage_format(&publication());
+        assert!(!storage.contains("<script>"));
+        assert!(storage.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn test_page_title() {
+        assert_eq!(page_title(Some(" Custom "), Some("Summary")), "Custom");
+        assert_eq!(page_title(None, Some("First line\nSecond")), "First line");
+        assert_eq!(page_title(Some(""), None), "Support resolution");
+        assert_eq!(
+            page_title(Some(&"x".repeat(300)), None).len(),
+            MAX_TITLE_CHARS
+        );
+    }
+
+    #[test]
+    fn test_parse_space_pages() {
+        let json = serde_json::json!({
+            "results": [
+                {
+                    "id": "101",
+                    "title": "VPN setup",
+                    "version": { "number": 7 },
+                    "_links": { "webui": "/spaces/IT/pages/101/VPN+setup" }
+                },
+                { "title": "No ID" },
+                { "id": "102", "title": "Printers", "version": { "number": 1 } }
+            ],
+            "_links": { "base": "https://acme.atlassian.net/wiki" }
+        });
+        let pages = parse_space_pages(&json);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].version, 7);
+        assert_eq!(
+            pages[0].url.as_deref(),
+            Some("https://acme.atlassian.net/wiki/spaces/IT/pages/101/VPN+setup")
+        );
+        assert_eq!(pages[1].url, None);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(validate_space_key("IT").is_ok());
+        assert!(validate_space_key("~jdoe").is_ok());
+        assert!(validate_space_key("IT SUPPORT").is_err());
+        assert!(validate_space_key("").is_err());
+
+        let config = ConfluenceConfig {
+            base_url: "http://acme.atlassian.net/wiki".to_string(),
+            email: "a@example.com".to_string(),
+        };
+        assert!(config.validate().is_err());
+        let config = ConfluenceConfig {
+            base_url: "https://acme.atlassian.net/wiki".to_string(),
+            ..config
+        };
+        assert!(config.validate().is_ok());
+    }
+}
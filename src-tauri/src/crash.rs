@@ -0,0 +1,324 @@
+//! Crash and panic reports
+//!
+//! A panic hook and a runtime error capture write structured reports (panic
+//! message, location, backtrace, recently invoked commands and the loaded
+//! model) to the crashes directory. Reports are listed in diagnostics on the
+//! next launch; the user reviews each one and opts in to including it in a
+//! support bundle.
+
+use crate::AppState;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::ipc::Invoke;
+use tauri::{Manager, Runtime};
+use thiserror::Error;
+
+/// Commands kept for the "last commands" section of a report
+const MAX_RECENT_COMMANDS: usize = 20;
+
+/// Reports kept on disk; older ones are deleted when a new one is written
+pub const MAX_CRASH_REPORTS: usize = 20;
+
+static RECENT_COMMANDS: Mutex<VecDeque<RecentCommand>> = Mutex::new(VecDeque::new());
+static APP_HANDLE: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+#[derive(Debug, Error)]
+pub enum CrashReportError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid crash report: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Crash report not found: {0}")]
+    NotFound(String),
+}
+
+/// What produced the report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CrashKind {
+    /// A Rust panic
+    Panic,
+    /// An error returned by the Tauri runtime
+    Error,
+}
+
+/// A command invocation recorded before the crash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentCommand {
+    pub command: String,
+    pub invoked_at: DateTime<Utc>,
+}
+
+/// Structured crash report, stored as `<id>.json` in the crashes directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub id: String,
+    pub kind: CrashKind,
+    pub created_at: DateTime<Utc>,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub thread: Option<String>,
+    pub message: String,
+    /// Source location of the panic (file:line:column)
+    pub location: Option<String>,
+    pub backtrace: Option<String>,
+    /// Most recent command invocations, oldest first
+    pub recent_commands: Vec<RecentCommand>,
+    /// Name of the LLM model loaded at the time, if any
+    pub loaded_model: Option<String>,
+    /// Set once the user has seen the report in diagnostics
+    #[serde(default)]
+    pub reviewed: bool,
+    /// User opted in to including the report in support bundles
+    #[serde(default)]
+    pub include_in_bundle: bool,
+}
+
+impl CrashReport {
+    /// Build a report for the current thread, with the recent commands and
+    /// loaded model captured without blocking
+    pub fn new(kind: CrashKind, message: String) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            created_at: Utc::now(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            thread: std::thread::current().name().map(|n| n.to_string()),
+            message,
+            location: None,
+            backtrace: None,
+            recent_commands: recent_commands(),
+            loaded_model: loaded_model(),
+            reviewed: false,
+            include_in_bundle: false,
+        }
+    }
+}
+
+/// Remember a command invocation for crash reports
+pub fn record_command(command: &str) {
+    if let Ok(mut commands) = RECENT_COMMANDS.lock() {
+        if commands.len() == MAX_RECENT_COMMANDS {
+            commands.pop_front();
+        }
+        commands.push_back(RecentCommand {
+            command: command.to_string(),
+            invoked_at: Utc::now(),
+        });
+    }
+}
+
+/// Recently invoked commands, oldest first. Uses `try_lock` so a panic
+/// while recording a command can't deadlock the hook.
+pub fn recent_commands() -> Vec<RecentCommand> {
+    RECENT_COMMANDS
+        .try_lock()
+        .map(|commands| commands.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Wrap an invoke handler so every command invocation is recorded
+pub fn record_commands<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        record_command(invoke.message.command());
+        handler(invoke)
+    }
+}
+
+/// Give the panic hook access to app state (for the loaded model)
+pub fn set_app_handle(handle: tauri::AppHandle) {
+    let _ = APP_HANDLE.set(handle);
+}
+
+fn loaded_model() -> Option<String> {
+    let state = APP_HANDLE.get()?.try_state::<AppState>()?;
+    let llm = state.llm.try_read()?;
+    llm.as_ref()?.try_model_name()
+}
+
+/// Message of a panic payload (`panic!` produces `&str` or `String`)
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Unknown panic payload".to_string())
+}
+
+/// Install a panic hook that writes a crash report, then runs the previous
+/// hook (which prints the panic to stderr)
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let mut report = CrashReport::new(CrashKind::Panic, panic_message(info.payload()));
+        report.location = info.location().map(|l| l.to_string());
+        report.backtrace = Some(Backtrace::force_capture().to_string());
+        if let Err(e) = write_report(&crate::db::get_crashes_dir(), &report) {
+            eprintln!("Failed to write crash report: {}", e);
+        }
+        previous(info);
+    }));
+}
+
+/// Write a crash report for an error returned by the Tauri runtime
+pub fn capture_error(source: &str, error: &dyn std::fmt::Display) {
+    let report = CrashReport::new(CrashKind::Error, format!("{}: {}", source, error));
+    if let Err(e) = write_report(&crate::db::get_crashes_dir(), &report) {
+        eprintln!("Failed to write crash report: {}", e);
+    }
+}
+
+fn report_path(dir: &Path, id: &str) -> Result<PathBuf, CrashReportError> {
+    // IDs are UUIDs; anything else can't name a report file
+    uuid::Uuid::parse_str(id).map_err(|_| CrashReportError::NotFound(id.to_string()))?;
+    Ok(dir.join(format!("{}.json", id)))
+}
+
+/// Write a report, deleting the oldest ones beyond `MAX_CRASH_REPORTS`
+pub fn write_report(dir: &Path, report: &CrashReport) -> Result<(), CrashReportError> {
+    std::fs::create_dir_all(dir)?;
+    let json = serde_json::to_string_pretty(report)?;
+    std::fs::write(report_path(dir, &report.id)?, json)?;
+
+    for old in list_reports(dir)?.iter().skip(MAX_CRASH_REPORTS) {
+        let _ = std::fs::remove_file(report_path(dir, &old.id)?);
+    }
+    Ok(())
+}
+
+/// Stored reports, newest first. Unreadable files are skipped.
+pub fn list_reports(dir: &Path) -> Result<Vec<CrashReport>, CrashReportError> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut reports: Vec<CrashReport> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .filter_map(|json| serde_json::from_str(&json).ok())
+        .collect();
+    reports.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+    Ok(reports)
+}
+
+/// Mark a report reviewed and record whether it goes into support bundles
+pub fn review_report(
+    dir: &Path,
+    id: &str,
+    include_in_bundle: bool,
+) -> Result<CrashReport, CrashReportError> {
+    let path = report_path(dir, id)?;
+    let json = std::fs::read_to_string(&path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => CrashReportError::NotFound(id.to_string()),
+        _ => e.into(),
+    })?;
+    let mut report: CrashReport = serde_json::from_str(&json)?;
+    report.reviewed = true;
+    report.include_in_bundle = include_in_bundle;
+    std::fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+    Ok(report)
+}
+
+pub fn delete_report(dir: &Path, id: &str) -> Result<(), CrashReportError> {
+    std::fs::remove_file(report_path(dir, id)?).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => CrashReportError::NotFound(id.to_string()),
+        _ => e.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("assistsupport-crashes-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let payload: Box<dyn Any + Send> = Box::new("index out of bounds");
+        assert_eq!(panic_message(payload.as_ref()), "index out of bounds");
+        let payload: Box<dyn Any + Send> = Box::new(format!("bad value {}", 3));
+        assert_eq!(panic_message(payload.as_ref()), "bad value 3");
+        let payload: Box<dyn Any + Send> = Box::new(42);
+        assert_eq!(panic_message(payload.as_ref()), "Unknown panic payload");
+    }
+
+    #[test]
+    fn test_recent_commands() {
+        for i in 0..MAX_RECENT_COMMANDS + 5 {
+            record_command(&format!("command_{}", i));
+        }
+        let commands = recent_commands();
+        assert_eq!(commands.len(), MAX_RECENT_COMMANDS);
+        assert_eq!(
+            commands.last().unwrap().command,
+            format!("command_{}", MAX_RECENT_COMMANDS + 4)
+        );
+    }
+
+    #[test]
+    fn test_report_lifecycle() {
+        let dir = temp_dir();
+        assert!(list_reports(&dir).unwrap().is_empty());
+
+        let mut first = CrashReport::new(CrashKind::Panic, "first".to_string());
+        first.created_at = Utc::now() - chrono::Duration::minutes(5);
+        write_report(&dir, &first).unwrap();
+        let second = CrashReport::new(CrashKind::Error, "second".to_string());
+        write_report(&dir, &second).unwrap();
+
+        let reports = list_reports(&dir).unwrap();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].message, "second");
+        assert!(!reports[0].reviewed);
+
+        let reviewed = review_report(&dir, &first.id, true).unwrap();
+        assert!(reviewed.reviewed && reviewed.include_in_bundle);
+        assert!(list_reports(&dir).unwrap()[1].include_in_bundle);
+
+        delete_report(&dir, &second.id).unwrap();
+        assert_eq!(list_reports(&dir).unwrap().len(), 1);
+        assert!(matches!(
+            delete_report(&dir, &second.id),
+            Err(CrashReportError::NotFound(_))
+        ));
+        assert!(matches!(
+            review_report(&dir, "../settings", false),
+            Err(CrashReportError::NotFound(_))
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_reports_are_pruned() {
+        let dir = temp_dir();
+        for i in 0..MAX_CRASH_REPORTS + 3 {
+            let mut report = CrashReport::new(CrashKind::Panic, format!("panic {}", i));
+            report.created_at = Utc::now() + chrono::Duration::seconds(i as i64);
+            write_report(&dir, &report).unwrap();
+        }
+        let reports = list_reports(&dir).unwrap();
+        assert_eq!(reports.len(), MAX_CRASH_REPORTS);
+        assert_eq!(
+            reports[0].message,
+            format!("panic {}", MAX_CRASH_REPORTS + 2)
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -5,16 +5,23 @@ pub mod executor;
 
 pub use executor::{DbExecutor, DbExecutorError};
 
-use crate::jobs::{Job, JobLog, JobStatus, JobType, LogLevel};
+use crate::diagnostics::PerformanceProfile;
+use crate::jobs::schedule::JobSchedule;
+use crate::jobs::{Job, JobLog, JobPriority, JobStatus, JobType, LogLevel};
+use crate::metrics::{MetricsSample, METRICS_RETENTION_DAYS};
+use crate::model_integrity::IntegrityRecord;
 use crate::security::{MasterKey, SecurityError};
 use crate::validation::{normalize_and_validate_namespace_id, ValidationError};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, Result as SqliteResult};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use zeroize::Zeroize;
 
-const CURRENT_SCHEMA_VERSION: i32 = 12;
+const CURRENT_SCHEMA_VERSION: i32 = 28;
+
+/// Performance profile runs kept for trend comparison
+const MAX_PERFORMANCE_PROFILES: usize = 100;
 
 #[derive(Debug, Error)]
 pub enum DbError {
@@ -102,6 +109,21 @@ impl Database {
         Ok(db)
     }
 
+    /// Re-encrypt the database with a new master key
+    pub fn rekey(&self, master_key: &MasterKey) -> Result<(), DbError> {
+        let mut hex_key = master_key.to_hex();
+        let mut rekey_pragma = format!("PRAGMA rekey = \"x'{}'\"", hex_key);
+        hex_key.zeroize();
+        let pragma_result = self.conn.execute_batch(&rekey_pragma);
+        rekey_pragma.zeroize();
+        pragma_result?;
+
+        // Verify the database is readable under the new key
+        self.conn
+            .execute_batch("SELECT count(*) FROM sqlite_master;")?;
+        Ok(())
+    }
+
     /// Initialize database schema
     pub fn initialize(&self) -> Result<(), DbError> {
         // Run integrity check
@@ -249,6 +271,70 @@ impl Database {
             self.migrate_v12()?;
         }
 
+        if from_version < 13 {
+            self.migrate_v13()?;
+        }
+
+        if from_version < 14 {
+            self.migrate_v14()?;
+        }
+
+        if from_version < 15 {
+            self.migrate_v15()?;
+        }
+
+        if from_version < 16 {
+            self.migrate_v16()?;
+        }
+
+        if from_version < 17 {
+            self.migrate_v17()?;
+        }
+
+        if from_version < 18 {
+            self.migrate_v18()?;
+        }
+
+        if from_version < 19 {
+            self.migrate_v19()?;
+        }
+
+        if from_version < 20 {
+            self.migrate_v20()?;
+        }
+
+        if from_version < 21 {
+            self.migrate_v21()?;
+        }
+
+        if from_version < 22 {
+            self.migrate_v22()?;
+        }
+
+        if from_version < 23 {
+            self.migrate_v23()?;
+        }
+
+        if from_version < 24 {
+            self.migrate_v24()?;
+        }
+
+        if from_version < 25 {
+            self.migrate_v25()?;
+        }
+
+        if from_version < 26 {
+            self.migrate_v26()?;
+        }
+
+        if from_version < 27 {
+            self.migrate_v27()?;
+        }
+
+        if from_version < 28 {
+            self.migrate_v28()?;
+        }
+
         tx.commit()?;
         self.set_schema_version(CURRENT_SCHEMA_VERSION)?;
 
@@ -1075,6 +1161,329 @@ impl Database {
         Ok(())
     }
 
+    /// Migration to v13: Follow-up reminders
+    fn migrate_v13(&self) -> Result<(), DbError> {
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS follow_ups (
+                id TEXT PRIMARY KEY,
+                draft_id TEXT,
+                ticket_id TEXT,
+                note TEXT,
+                due_at TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending' CHECK(status IN ('pending', 'completed')),
+                notified_at TEXT,
+                created_at TEXT NOT NULL,
+                completed_at TEXT,
+                FOREIGN KEY (draft_id) REFERENCES drafts(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_follow_ups_due ON follow_ups(status, due_at);
+            CREATE INDEX IF NOT EXISTS idx_follow_ups_draft ON follow_ups(draft_id);
+            "#,
+        )?;
+        Ok(())
+    }
+
+    /// Migration to v14: Add job_schedules table for recurring jobs
+    fn migrate_v14(&self) -> Result<(), DbError> {
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS job_schedules (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                cron TEXT NOT NULL,
+                job_type TEXT NOT NULL,
+                params_json TEXT,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                next_run_at TEXT,
+                last_run_at TEXT,
+                last_job_id TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_job_schedules_next_run ON job_schedules(enabled, next_run_at);
+            "#,
+        )?;
+        Ok(())
+    }
+
+    /// Migration to v15: Add job priorities
+    fn migrate_v15(&self) -> Result<(), DbError> {
+        self.conn.execute_batch(
+            r#"
+            ALTER TABLE jobs ADD COLUMN priority TEXT NOT NULL DEFAULT 'normal'
+                CHECK(priority IN ('low', 'normal', 'high'));
+            "#,
+        )?;
+        Ok(())
+    }
+
+    /// Migration to v16: Add job dependencies
+    fn migrate_v16(&self) -> Result<(), DbError> {
+        self.conn.execute_batch(
+            r#"
+            ALTER TABLE jobs ADD COLUMN depends_on_json TEXT;
+            "#,
+        )?;
+        Ok(())
+    }
+
+    /// Migration to v17: Add performance_profiles table
+    fn migrate_v17(&self) -> Result<(), DbError> {
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS performance_profiles (
+                id TEXT PRIMARY KEY,
+                created_at TEXT NOT NULL,
+                app_version TEXT NOT NULL,
+                total_ms REAL NOT NULL,
+                steps_json TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_performance_profiles_created ON performance_profiles(created_at);
+            "#,
+        )?;
+        Ok(())
+    }
+
+    /// Migration to v18: Periodic metrics samples for the diagnostics dashboard
+    fn migrate_v18(&self) -> Result<(), DbError> {
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS metrics_samples (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                sampled_at TEXT NOT NULL,
+                memory_bytes INTEGER NOT NULL,
+                generation_latency_ms REAL,
+                search_latency_ms REAL,
+                job_queue_depth INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_metrics_samples_sampled ON metrics_samples(sampled_at);
+            "#,
+        )?;
+        Ok(())
+    }
+
+    /// Migration to v19: Per-phase startup timings
+    fn migrate_v19(&self) -> Result<(), DbError> {
+        let has_phases: bool = self
+            .conn
+            .prepare("SELECT phases_json FROM startup_metrics LIMIT 0")
+            .is_ok();
+        if !has_phases {
+            self.conn.execute(
+                "ALTER TABLE startup_metrics ADD COLUMN phases_json TEXT",
+                [],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// v20: Restricted namespaces
+    fn migrate_v20(&self) -> Result<(), DbError> {
+        let has_restricted: bool = self
+            .conn
+            .prepare("SELECT restricted FROM namespaces LIMIT 0")
+            .is_ok();
+        if !has_restricted {
+            self.conn.execute(
+                "ALTER TABLE namespaces ADD COLUMN restricted INTEGER NOT NULL DEFAULT 0",
+                [],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// v21: User-defined export templates
+    fn migrate_v21(&self) -> Result<(), DbError> {
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS export_templates (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                description TEXT,
+                content TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            "#,
+        )?;
+        Ok(())
+    }
+
+    /// v22: Verified model digests
+    fn migrate_v22(&self) -> Result<(), DbError> {
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS model_integrity (
+                filename TEXT PRIMARY KEY,
+                sha256 TEXT NOT NULL,
+                checksum_source TEXT NOT NULL,
+                size_bytes INTEGER NOT NULL,
+                modified_ms INTEGER NOT NULL,
+                verified_at TEXT NOT NULL
+            );
+            "#,
+        )?;
+        Ok(())
+    }
+
+    /// v23: Consent to record finalized drafts in MemoryKernel
+    fn migrate_v23(&self) -> Result<(), DbError> {
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS memory_kernel_consent (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                record_decisions INTEGER NOT NULL DEFAULT 0,
+                consented_at TEXT
+            );
+            INSERT OR IGNORE INTO memory_kernel_consent (id, record_decisions) VALUES (1, 0);
+            "#,
+        )?;
+        Ok(())
+    }
+
+    /// v24: Source page numbers on KB chunks
+    fn migrate_v24(&self) -> Result<(), DbError> {
+        let has_page_number: bool = self
+            .conn
+            .prepare("SELECT page_number FROM kb_chunks LIMIT 0")
+            .is_ok();
+        if !has_page_number {
+            self.conn
+                .execute("ALTER TABLE kb_chunks ADD COLUMN page_number INTEGER", [])?;
+        }
+        Ok(())
+    }
+
+    /// v25: Original URL of saved web pages (HTML/MHTML) on KB documents
+    fn migrate_v25(&self) -> Result<(), DbError> {
+        let has_source_url: bool = self
+            .conn
+            .prepare("SELECT source_url FROM kb_documents LIMIT 0")
+            .is_ok();
+        if !has_source_url {
+            self.conn
+                .execute("ALTER TABLE kb_documents ADD COLUMN source_url TEXT", [])?;
+        }
+        Ok(())
+    }
+
+    /// v26: Allow `confluence` ingest sources
+    fn migrate_v26(&self) -> Result<(), DbError> {
+        self.rebuild_ingest_sources(&["web", "youtube", "github", "file", "confluence"])
+    }
+
+    /// v27: Allow `feed` (RSS/Atom) ingest sources and record the entries
+    /// seen per feed, keyed by GUID
+    fn migrate_v27(&self) -> Result<(), DbError> {
+        self.rebuild_ingest_sources(&["web", "youtube", "github", "file", "confluence", "feed"])?;
+        self.conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS feed_entries (
+                source_id TEXT NOT NULL,
+                guid TEXT NOT NULL,
+                document_id TEXT,
+                title TEXT,
+                link TEXT,
+                published_at TEXT,
+                ingested_at TEXT NOT NULL,
+                PRIMARY KEY (source_id, guid),
+                FOREIGN KEY (source_id) REFERENCES ingest_sources(id) ON DELETE CASCADE
+            );
+            "#,
+        )?;
+        Ok(())
+    }
+
+    /// v28: MemoryKernel policy citations (context package ids and cited
+    /// `memory_version_id`s) saved with each draft
+    fn migrate_v28(&self) -> Result<(), DbError> {
+        let has_policy_citations: bool = self
+            .conn
+            .prepare("SELECT policy_citations_json FROM drafts LIMIT 0")
+            .is_ok();
+        if !has_policy_citations {
+            self.conn.execute(
+                "ALTER TABLE drafts ADD COLUMN policy_citations_json TEXT",
+                [],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild ingest_sources so its `source_type` CHECK allows `source_types`.
+    /// SQLite cannot alter a CHECK constraint; dropping the table cascades to
+    /// ingest_runs, which are copied aside and restored. No-op if every type
+    /// is already allowed.
+    fn rebuild_ingest_sources(&self, source_types: &[&str]) -> Result<(), DbError> {
+        let table_sql: String = self.conn.query_row(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'ingest_sources'",
+            [],
+            |row| row.get(0),
+        )?;
+        if source_types
+            .iter()
+            .all(|t| table_sql.contains(&format!("'{}'", t)))
+        {
+            return Ok(());
+        }
+
+        let allowed = source_types
+            .iter()
+            .map(|t| format!("'{}'", t))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.conn.execute_batch(&format!(
+            r#"
+            CREATE TABLE ingest_sources_rebuild (
+                id TEXT PRIMARY KEY,
+                source_type TEXT NOT NULL CHECK(source_type IN ({allowed})),
+                source_uri TEXT NOT NULL,
+                namespace_id TEXT NOT NULL DEFAULT 'default',
+                title TEXT,
+                etag TEXT,
+                last_modified TEXT,
+                content_hash TEXT,
+                last_ingested_at TEXT,
+                status TEXT NOT NULL DEFAULT 'pending' CHECK(status IN ('pending', 'active', 'stale', 'error', 'removed')),
+                error_message TEXT,
+                metadata_json TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                trust_score REAL DEFAULT 0.5,
+                is_pinned INTEGER DEFAULT 0,
+                owner TEXT,
+                review_status TEXT DEFAULT 'pending'
+                    CHECK(review_status IN ('pending', 'approved', 'rejected', 'needs_review')),
+                tags_json TEXT,
+                stale_at TEXT,
+                FOREIGN KEY (namespace_id) REFERENCES namespaces(id) ON DELETE CASCADE,
+                UNIQUE(source_type, source_uri, namespace_id)
+            );
+            INSERT INTO ingest_sources_rebuild (id, source_type, source_uri, namespace_id, title, etag,
+                    last_modified, content_hash, last_ingested_at, status, error_message,
+                    metadata_json, created_at, updated_at, trust_score, is_pinned, owner,
+                    review_status, tags_json, stale_at)
+                SELECT id, source_type, source_uri, namespace_id, title, etag,
+                    last_modified, content_hash, last_ingested_at, status, error_message,
+                    metadata_json, created_at, updated_at, trust_score, is_pinned, owner,
+                    review_status, tags_json, stale_at
+                FROM ingest_sources;
+
+            CREATE TEMP TABLE ingest_runs_rebuild AS SELECT * FROM ingest_runs;
+            DROP TABLE ingest_sources;
+            ALTER TABLE ingest_sources_rebuild RENAME TO ingest_sources;
+            INSERT INTO ingest_runs SELECT * FROM ingest_runs_rebuild;
+            DROP TABLE ingest_runs_rebuild;
+
+            CREATE INDEX IF NOT EXISTS idx_ingest_sources_namespace ON ingest_sources(namespace_id);
+            CREATE INDEX IF NOT EXISTS idx_ingest_sources_type ON ingest_sources(source_type);
+            CREATE INDEX IF NOT EXISTS idx_ingest_sources_status ON ingest_sources(status);
+            "#
+        ))?;
+        Ok(())
+    }
+
     // -- Model state helpers --
 
     /// Record that a model was loaded (for auto-load on next startup)
@@ -1102,6 +1511,80 @@ impl Database {
         Ok(())
     }
 
+    /// Record a model file whose digest matched its published checksum
+    pub fn save_model_integrity(&self, record: &IntegrityRecord) -> Result<(), DbError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO model_integrity
+             (filename, sha256, checksum_source, size_bytes, modified_ms, verified_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                record.filename,
+                record.sha256,
+                record.checksum_source,
+                record.size_bytes as i64,
+                record.modified_ms,
+                record.verified_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get the verification record for a model file
+    pub fn get_model_integrity(&self, filename: &str) -> Result<Option<IntegrityRecord>, DbError> {
+        let result = self.conn.query_row(
+            "SELECT filename, sha256, checksum_source, size_bytes, modified_ms, verified_at
+             FROM model_integrity WHERE filename = ?1",
+            params![filename],
+            |row| {
+                Ok(IntegrityRecord {
+                    filename: row.get(0)?,
+                    sha256: row.get(1)?,
+                    checksum_source: row.get(2)?,
+                    size_bytes: row.get::<_, i64>(3)? as u64,
+                    modified_ms: row.get(4)?,
+                    verified_at: row.get(5)?,
+                })
+            },
+        );
+        match result {
+            Ok(record) => Ok(Some(record)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(DbError::Sqlite(e)),
+        }
+    }
+
+    /// List verification records for all model files
+    pub fn list_model_integrity(&self) -> Result<Vec<IntegrityRecord>, DbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT filename, sha256, checksum_source, size_bytes, modified_ms, verified_at
+             FROM model_integrity ORDER BY filename",
+        )?;
+
+        let records = stmt
+            .query_map([], |row| {
+                Ok(IntegrityRecord {
+                    filename: row.get(0)?,
+                    sha256: row.get(1)?,
+                    checksum_source: row.get(2)?,
+                    size_bytes: row.get::<_, i64>(3)? as u64,
+                    modified_ms: row.get(4)?,
+                    verified_at: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(records)
+    }
+
+    /// Forget the verification record for a model file
+    pub fn delete_model_integrity(&self, filename: &str) -> Result<(), DbError> {
+        self.conn.execute(
+            "DELETE FROM model_integrity WHERE filename = ?1",
+            params![filename],
+        )?;
+        Ok(())
+    }
+
     /// Get last loaded model info for a given type
     pub fn get_model_state(
         &self,
@@ -1121,7 +1604,7 @@ impl Database {
 
     // -- Startup metrics helpers --
 
-    /// Record a startup metric
+    /// Record a startup metric with its phase timings. Returns the row ID.
     pub fn record_startup_metric(
         &self,
         started_at: &str,
@@ -1129,18 +1612,78 @@ impl Database {
         total_ms: Option<i64>,
         init_app_ms: Option<i64>,
         models_cached: bool,
-    ) -> Result<(), DbError> {
+        phases: &[StartupPhase],
+    ) -> Result<i64, DbError> {
+        let phases_json = serde_json::to_string(phases)
+            .map_err(|e| DbError::Sqlite(rusqlite::Error::InvalidParameterName(e.to_string())))?;
         self.conn.execute(
-            "INSERT INTO startup_metrics (started_at, ui_ready_at, total_ms, init_app_ms, models_cached)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![started_at, ui_ready_at, total_ms, init_app_ms, models_cached as i32],
+            "INSERT INTO startup_metrics (started_at, ui_ready_at, total_ms, init_app_ms, models_cached, phases_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![started_at, ui_ready_at, total_ms, init_app_ms, models_cached as i32, phases_json],
         )?;
+        let id = self.conn.last_insert_rowid();
         // Keep only last 50 metrics
         self.conn.execute(
             "DELETE FROM startup_metrics WHERE id NOT IN (SELECT id FROM startup_metrics ORDER BY id DESC LIMIT 50)",
             [],
         )?;
-        Ok(())
+        Ok(id)
+    }
+
+    /// Add a phase to a recorded startup, unless one with the same name is
+    /// already there. Returns whether it was added.
+    pub fn add_startup_phase(
+        &self,
+        startup_id: i64,
+        phase: &StartupPhase,
+    ) -> Result<bool, DbError> {
+        let phases_json: Option<String> = match self.conn.query_row(
+            "SELECT phases_json FROM startup_metrics WHERE id = ?",
+            [startup_id],
+            |row| row.get(0),
+        ) {
+            Ok(json) => json,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+        let mut phases: Vec<StartupPhase> = phases_json
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        if phases.iter().any(|p| p.name == phase.name) {
+            return Ok(false);
+        }
+        phases.push(phase.clone());
+
+        let phases_json = serde_json::to_string(&phases)
+            .map_err(|e| DbError::Sqlite(rusqlite::Error::InvalidParameterName(e.to_string())))?;
+        self.conn.execute(
+            "UPDATE startup_metrics SET phases_json = ? WHERE id = ?",
+            params![phases_json, startup_id],
+        )?;
+        Ok(true)
+    }
+
+    /// Recorded startups with their phase timings, newest first
+    pub fn list_startup_timings(&self, limit: usize) -> Result<Vec<StartupTimings>, DbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, started_at, init_app_ms, models_cached, phases_json
+             FROM startup_metrics ORDER BY id DESC LIMIT ?",
+        )?;
+        let timings = stmt
+            .query_map([limit as i64], |row| {
+                let phases_json: Option<String> = row.get(4)?;
+                Ok(StartupTimings {
+                    id: row.get(0)?,
+                    started_at: row.get(1)?,
+                    init_app_ms: row.get(2)?,
+                    models_cached: row.get::<_, Option<i32>>(3)?.unwrap_or(0) != 0,
+                    phases: phases_json
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(timings)
     }
 
     /// Get last startup metric
@@ -1335,6 +1878,31 @@ impl Database {
         Ok(())
     }
 
+    /// Get MemoryKernel decision-recording consent
+    pub fn get_memory_kernel_consent(&self) -> Result<MemoryKernelConsent, DbError> {
+        let row = self.conn.query_row(
+            "SELECT record_decisions, consented_at FROM memory_kernel_consent WHERE id = 1",
+            [],
+            |row| {
+                Ok(MemoryKernelConsent {
+                    record_decisions: row.get::<_, i32>(0)? != 0,
+                    consented_at: row.get(1)?,
+                })
+            },
+        )?;
+        Ok(row)
+    }
+
+    /// Set MemoryKernel decision-recording consent
+    pub fn set_memory_kernel_consent(&self, record_decisions: bool) -> Result<(), DbError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        self.conn.execute(
+            "UPDATE memory_kernel_consent SET record_decisions = ?, consented_at = ? WHERE id = 1",
+            params![record_decisions as i32, now],
+        )?;
+        Ok(())
+    }
+
     /// List all decision trees
     pub fn list_decision_trees(&self) -> Result<Vec<DecisionTree>, DbError> {
         let mut stmt = self.conn.prepare(
@@ -1425,7 +1993,8 @@ impl Database {
         let mut stmt = self.conn.prepare(
             "SELECT id, input_text, summary_text, diagnosis_json, response_text,
                     ticket_id, kb_sources_json, created_at, updated_at, is_autosave, model_name,
-                    case_intake_json, status, handoff_summary, finalized_at, finalized_by
+                    case_intake_json, status, handoff_summary, finalized_at, finalized_by,
+                    policy_citations_json
              FROM drafts
              ORDER BY updated_at DESC
              LIMIT ?",
@@ -1453,6 +2022,48 @@ impl Database {
                     handoff_summary: row.get(13)?,
                     finalized_at: row.get(14)?,
                     finalized_by: row.get(15)?,
+                    policy_citations_json: row.get(16)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(drafts)
+    }
+
+    /// List every draft, oldest first (for batch export)
+    pub fn list_all_drafts(&self) -> Result<Vec<SavedDraft>, DbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, input_text, summary_text, diagnosis_json, response_text,
+                    ticket_id, kb_sources_json, created_at, updated_at, is_autosave, model_name,
+                    case_intake_json, status, handoff_summary, finalized_at, finalized_by,
+                    policy_citations_json
+             FROM drafts
+             ORDER BY created_at",
+        )?;
+
+        let drafts = stmt
+            .query_map([], |row| {
+                Ok(SavedDraft {
+                    id: row.get(0)?,
+                    input_text: row.get(1)?,
+                    summary_text: row.get(2)?,
+                    diagnosis_json: row.get(3)?,
+                    response_text: row.get(4)?,
+                    ticket_id: row.get(5)?,
+                    kb_sources_json: row.get(6)?,
+                    created_at: row.get(7)?,
+                    updated_at: row.get(8)?,
+                    is_autosave: row.get::<_, i32>(9)? != 0,
+                    model_name: row.get(10)?,
+                    case_intake_json: row.get(11)?,
+                    status: row
+                        .get::<_, Option<String>>(12)?
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or_default(),
+                    handoff_summary: row.get(13)?,
+                    finalized_at: row.get(14)?,
+                    finalized_by: row.get(15)?,
+                    policy_citations_json: row.get(16)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -1466,7 +2077,8 @@ impl Database {
         let mut stmt = self.conn.prepare(
             "SELECT id, input_text, summary_text, diagnosis_json, response_text,
                     ticket_id, kb_sources_json, created_at, updated_at, is_autosave, model_name,
-                    case_intake_json, status, handoff_summary, finalized_at, finalized_by
+                    case_intake_json, status, handoff_summary, finalized_at, finalized_by,
+                    policy_citations_json
              FROM drafts
              WHERE is_autosave = 0
                AND (input_text LIKE ?1 OR response_text LIKE ?1 OR ticket_id LIKE ?1)
@@ -1496,6 +2108,7 @@ impl Database {
                     handoff_summary: row.get(13)?,
                     finalized_at: row.get(14)?,
                     finalized_by: row.get(15)?,
+                    policy_citations_json: row.get(16)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -1508,7 +2121,8 @@ impl Database {
         let draft = self.conn.query_row(
             "SELECT id, input_text, summary_text, diagnosis_json, response_text,
                     ticket_id, kb_sources_json, created_at, updated_at, is_autosave, model_name,
-                    case_intake_json, status, handoff_summary, finalized_at, finalized_by
+                    case_intake_json, status, handoff_summary, finalized_at, finalized_by,
+                    policy_citations_json
              FROM drafts WHERE id = ?",
             [draft_id],
             |row| {
@@ -1532,6 +2146,7 @@ impl Database {
                     handoff_summary: row.get(13)?,
                     finalized_at: row.get(14)?,
                     finalized_by: row.get(15)?,
+                    policy_citations_json: row.get(16)?,
                 })
             },
         )?;
@@ -1544,8 +2159,9 @@ impl Database {
             "INSERT OR REPLACE INTO drafts
              (id, input_text, summary_text, diagnosis_json, response_text,
               ticket_id, kb_sources_json, created_at, updated_at, is_autosave, model_name,
-              case_intake_json, status, handoff_summary, finalized_at, finalized_by)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+              case_intake_json, status, handoff_summary, finalized_at, finalized_by,
+              policy_citations_json)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
             params![
                 &draft.id,
                 &draft.input_text,
@@ -1563,6 +2179,7 @@ impl Database {
                 &draft.handoff_summary,
                 &draft.finalized_at,
                 &draft.finalized_by,
+                &draft.policy_citations_json,
             ],
         )?;
         Ok(draft.id.clone())
@@ -1588,12 +2205,45 @@ impl Database {
         Ok(deleted)
     }
 
+    /// Autosave storage, and how much `cleanup_autosaves(keep_count)` would free
+    pub fn get_autosave_usage(&self, keep_count: usize) -> Result<AutosaveUsage, DbError> {
+        const SIZE_EXPR: &str = "COALESCE(SUM(LENGTH(input_text) + IFNULL(LENGTH(summary_text), 0)
+             + IFNULL(LENGTH(diagnosis_json), 0) + IFNULL(LENGTH(response_text), 0)
+             + IFNULL(LENGTH(kb_sources_json), 0)), 0)";
+        let (count, bytes) = self.conn.query_row(
+            &format!(
+                "SELECT COUNT(*), {} FROM drafts WHERE is_autosave = 1",
+                SIZE_EXPR
+            ),
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let (reclaimable_count, reclaimable_bytes) = self.conn.query_row(
+            &format!(
+                "SELECT COUNT(*), {} FROM drafts WHERE is_autosave = 1 AND id NOT IN (
+                    SELECT id FROM drafts WHERE is_autosave = 1
+                    ORDER BY created_at DESC LIMIT ?
+                )",
+                SIZE_EXPR
+            ),
+            [keep_count],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(AutosaveUsage {
+            count,
+            bytes,
+            reclaimable_count,
+            reclaimable_bytes,
+        })
+    }
+
     /// List autosave drafts (most recent first)
     pub fn list_autosaves(&self, limit: usize) -> Result<Vec<SavedDraft>, DbError> {
         let mut stmt = self.conn.prepare(
             "SELECT id, input_text, summary_text, diagnosis_json, response_text,
                     ticket_id, kb_sources_json, created_at, updated_at, is_autosave, model_name,
-                    case_intake_json, status, handoff_summary, finalized_at, finalized_by
+                    case_intake_json, status, handoff_summary, finalized_at, finalized_by,
+                    policy_citations_json
              FROM drafts
              WHERE is_autosave = 1
              ORDER BY created_at DESC
@@ -1622,6 +2272,7 @@ impl Database {
                     handoff_summary: row.get(13)?,
                     finalized_at: row.get(14)?,
                     finalized_by: row.get(15)?,
+                    policy_citations_json: row.get(16)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -2022,6 +2673,83 @@ impl Database {
         Ok(())
     }
 
+    // ============================================================================
+    // Export Template Methods
+    // ============================================================================
+
+    /// List all export templates
+    pub fn list_export_templates(&self) -> Result<Vec<ExportTemplate>, DbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, description, content, created_at, updated_at
+             FROM export_templates
+             ORDER BY name",
+        )?;
+
+        let templates = stmt
+            .query_map([], |row| {
+                Ok(ExportTemplate {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    description: row.get(2)?,
+                    content: row.get(3)?,
+                    created_at: row.get(4)?,
+                    updated_at: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(templates)
+    }
+
+    /// Get a single export template by ID
+    pub fn get_export_template(&self, template_id: &str) -> Result<ExportTemplate, DbError> {
+        let template = self.conn.query_row(
+            "SELECT id, name, description, content, created_at, updated_at
+             FROM export_templates WHERE id = ?",
+            [template_id],
+            |row| {
+                Ok(ExportTemplate {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    description: row.get(2)?,
+                    content: row.get(3)?,
+                    created_at: row.get(4)?,
+                    updated_at: row.get(5)?,
+                })
+            },
+        )?;
+        Ok(template)
+    }
+
+    /// Save an export template (insert or update)
+    pub fn save_export_template(&self, template: &ExportTemplate) -> Result<(), DbError> {
+        self.conn.execute(
+            "INSERT INTO export_templates (id, name, description, content, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                description = excluded.description,
+                content = excluded.content,
+                updated_at = excluded.updated_at",
+            params![
+                &template.id,
+                &template.name,
+                &template.description,
+                &template.content,
+                &template.created_at,
+                &template.updated_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Delete an export template
+    pub fn delete_export_template(&self, template_id: &str) -> Result<(), DbError> {
+        self.conn
+            .execute("DELETE FROM export_templates WHERE id = ?", [template_id])?;
+        Ok(())
+    }
+
     // ============================================================================
     // Custom Variable Methods
     // ============================================================================
@@ -2171,7 +2899,7 @@ impl Database {
     /// List all namespaces
     pub fn list_namespaces(&self) -> Result<Vec<Namespace>, DbError> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, description, color, created_at, updated_at
+            "SELECT id, name, description, color, created_at, updated_at, restricted
              FROM namespaces ORDER BY name",
         )?;
 
@@ -2184,6 +2912,7 @@ impl Database {
                     color: row.get(3)?,
                     created_at: row.get(4)?,
                     updated_at: row.get(5)?,
+                    restricted: row.get(6)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -2195,7 +2924,7 @@ impl Database {
     pub fn list_namespaces_with_counts(&self) -> Result<Vec<NamespaceWithCounts>, DbError> {
         let mut stmt = self.conn.prepare(
             "SELECT
-                n.id, n.name, n.description, n.color, n.created_at, n.updated_at,
+                n.id, n.name, n.description, n.color, n.created_at, n.updated_at, n.restricted,
                 COALESCE(d.doc_count, 0) as document_count,
                 COALESCE(s.source_count, 0) as source_count
              FROM namespaces n
@@ -2221,8 +2950,9 @@ impl Database {
                     color: row.get(3)?,
                     created_at: row.get(4)?,
                     updated_at: row.get(5)?,
-                    document_count: row.get(6)?,
-                    source_count: row.get(7)?,
+                    restricted: row.get(6)?,
+                    document_count: row.get(7)?,
+                    source_count: row.get(8)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -2234,7 +2964,7 @@ impl Database {
     pub fn get_namespace(&self, namespace_id: &str) -> Result<Namespace, DbError> {
         self.conn
             .query_row(
-                "SELECT id, name, description, color, created_at, updated_at
+                "SELECT id, name, description, color, created_at, updated_at, restricted
              FROM namespaces WHERE id = ?",
                 [namespace_id],
                 |row| {
@@ -2245,6 +2975,7 @@ impl Database {
                         color: row.get(3)?,
                         created_at: row.get(4)?,
                         updated_at: row.get(5)?,
+                        restricted: row.get(6)?,
                     })
                 },
             )
@@ -2295,6 +3026,44 @@ impl Database {
         Ok(())
     }
 
+    /// Mark a namespace restricted (or unrestricted). Returns false if the
+    /// namespace doesn't exist.
+    pub fn set_namespace_restricted(
+        &self,
+        namespace_id: &str,
+        restricted: bool,
+    ) -> Result<bool, DbError> {
+        let updated = self.conn.execute(
+            "UPDATE namespaces SET restricted = ?, updated_at = ? WHERE id = ?",
+            params![restricted, chrono::Utc::now().to_rfc3339(), namespace_id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// IDs of restricted namespaces
+    pub fn restricted_namespace_ids(&self) -> Result<std::collections::HashSet<String>, DbError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id FROM namespaces WHERE restricted = 1")?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<std::collections::HashSet<String>, _>>()?;
+        Ok(ids)
+    }
+
+    /// Namespace of a KB document, if the document exists
+    pub fn document_namespace(&self, document_id: &str) -> Result<Option<String>, DbError> {
+        match self.conn.query_row(
+            "SELECT namespace_id FROM kb_documents WHERE id = ?",
+            [document_id],
+            |row| row.get(0),
+        ) {
+            Ok(namespace_id) => Ok(Some(namespace_id)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(DbError::Sqlite(e)),
+        }
+    }
+
     /// Create a new namespace with name, description, and color
     ///
     /// The namespace ID is normalized using the centralized validation rules:
@@ -2320,6 +3089,7 @@ impl Database {
             color: color.map(|s| s.to_string()),
             created_at: now.clone(),
             updated_at: now,
+            restricted: false,
         };
 
         self.save_namespace(&namespace)?;
@@ -2714,6 +3484,39 @@ impl Database {
         Ok(runs)
     }
 
+    // ============================================================================
+    // Feed Entry Methods
+    // ============================================================================
+
+    /// Whether an entry with this GUID was already ingested from a feed
+    pub fn has_feed_entry(&self, source_id: &str, guid: &str) -> Result<bool, DbError> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM feed_entries WHERE source_id = ? AND guid = ?",
+            params![source_id, guid],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Record an ingested feed entry; an entry already recorded is kept
+    pub fn record_feed_entry(&self, entry: &FeedEntryRecord) -> Result<(), DbError> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO feed_entries (source_id, guid, document_id, title, link,
+                    published_at, ingested_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            params![
+                entry.source_id,
+                entry.guid,
+                entry.document_id,
+                entry.title,
+                entry.link,
+                entry.published_at,
+                entry.ingested_at,
+            ],
+        )?;
+        Ok(())
+    }
+
     // ============================================================================
     // FTS Search with Namespace Support
     // ============================================================================
@@ -2845,10 +3648,12 @@ impl Database {
     /// Create a new job
     pub fn create_job(&self, job: &Job) -> Result<(), DbError> {
         let metadata_json = job.metadata.as_ref().map(|m| m.to_string());
+        let depends_on_json =
+            (!job.depends_on.is_empty()).then(|| serde_json::json!(job.depends_on).to_string());
         self.conn.execute(
             "INSERT INTO jobs (id, job_type, status, created_at, updated_at, started_at, completed_at,
-                    progress, progress_message, error, metadata_json)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                    progress, progress_message, error, metadata_json, priority, depends_on_json)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
             params![
                 job.id,
                 job.job_type.to_string(),
@@ -2861,6 +3666,8 @@ impl Database {
                 job.progress_message,
                 job.error,
                 metadata_json,
+                job.priority.to_string(),
+                depends_on_json,
             ],
         )?;
         Ok(())
@@ -2869,40 +3676,9 @@ impl Database {
     /// Get a job by ID
     pub fn get_job(&self, job_id: &str) -> Result<Option<Job>, DbError> {
         match self.conn.query_row(
-            "SELECT id, job_type, status, created_at, updated_at, started_at, completed_at,
-                    progress, progress_message, error, metadata_json
-             FROM jobs WHERE id = ?",
+            &format!("SELECT {} FROM jobs WHERE id = ?", JOB_COLUMNS),
             [job_id],
-            |row| {
-                let status_str: String = row.get(2)?;
-                let metadata_json: Option<String> = row.get(10)?;
-                Ok(Job {
-                    id: row.get(0)?,
-                    job_type: row
-                        .get::<_, String>(1)?
-                        .parse::<JobType>()
-                        .unwrap_or(JobType::Custom("unknown".into())),
-                    status: status_str.parse::<JobStatus>().unwrap_or(JobStatus::Queued),
-                    created_at: chrono::DateTime::parse_from_rfc3339(&row.get::<_, String>(3)?)
-                        .map(|t| t.with_timezone(&Utc))
-                        .unwrap_or_else(|_| Utc::now()),
-                    updated_at: chrono::DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
-                        .map(|t| t.with_timezone(&Utc))
-                        .unwrap_or_else(|_| Utc::now()),
-                    started_at: row
-                        .get::<_, Option<String>>(5)?
-                        .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|t| t.with_timezone(&Utc)),
-                    completed_at: row
-                        .get::<_, Option<String>>(6)?
-                        .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|t| t.with_timezone(&Utc)),
-                    progress: row.get(7)?,
-                    progress_message: row.get(8)?,
-                    error: row.get(9)?,
-                    metadata: metadata_json.and_then(|s| serde_json::from_str(&s).ok()),
-                })
-            },
+            job_from_row,
         ) {
             Ok(job) => Ok(Some(job)),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
@@ -2912,57 +3688,24 @@ impl Database {
 
     /// List jobs, optionally filtered by status
     pub fn list_jobs(&self, status: Option<JobStatus>, limit: usize) -> Result<Vec<Job>, DbError> {
-        let map_row = |row: &rusqlite::Row| -> rusqlite::Result<Job> {
-            let status_str: String = row.get(2)?;
-            let metadata_json: Option<String> = row.get(10)?;
-            Ok(Job {
-                id: row.get(0)?,
-                job_type: row
-                    .get::<_, String>(1)?
-                    .parse::<JobType>()
-                    .unwrap_or(JobType::Custom("unknown".into())),
-                status: status_str.parse::<JobStatus>().unwrap_or(JobStatus::Queued),
-                created_at: chrono::DateTime::parse_from_rfc3339(&row.get::<_, String>(3)?)
-                    .map(|t| t.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                updated_at: chrono::DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
-                    .map(|t| t.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-                started_at: row
-                    .get::<_, Option<String>>(5)?
-                    .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
-                    .map(|t| t.with_timezone(&Utc)),
-                completed_at: row
-                    .get::<_, Option<String>>(6)?
-                    .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
-                    .map(|t| t.with_timezone(&Utc)),
-                progress: row.get(7)?,
-                progress_message: row.get(8)?,
-                error: row.get(9)?,
-                metadata: metadata_json.and_then(|s| serde_json::from_str(&s).ok()),
-            })
-        };
-
         let jobs: Vec<Job> = match status {
             Some(s) => {
-                let mut stmt = self.conn.prepare(
-                    "SELECT id, job_type, status, created_at, updated_at, started_at, completed_at,
-                            progress, progress_message, error, metadata_json
-                     FROM jobs WHERE status = ? ORDER BY created_at DESC LIMIT ?",
-                )?;
+                let mut stmt = self.conn.prepare(&format!(
+                    "SELECT {} FROM jobs WHERE status = ? ORDER BY created_at DESC LIMIT ?",
+                    JOB_COLUMNS
+                ))?;
                 let result: Vec<Job> = stmt
-                    .query_map(params![s.to_string(), limit as i64], map_row)?
+                    .query_map(params![s.to_string(), limit as i64], job_from_row)?
                     .collect::<Result<Vec<_>, _>>()?;
                 result
             }
             None => {
-                let mut stmt = self.conn.prepare(
-                    "SELECT id, job_type, status, created_at, updated_at, started_at, completed_at,
-                            progress, progress_message, error, metadata_json
-                     FROM jobs ORDER BY created_at DESC LIMIT ?",
-                )?;
+                let mut stmt = self.conn.prepare(&format!(
+                    "SELECT {} FROM jobs ORDER BY created_at DESC LIMIT ?",
+                    JOB_COLUMNS
+                ))?;
                 let result: Vec<Job> = stmt
-                    .query_map(params![limit as i64], map_row)?
+                    .query_map(params![limit as i64], job_from_row)?
                     .collect::<Result<Vec<_>, _>>()?;
                 result
             }
@@ -3021,6 +3764,69 @@ impl Database {
         Ok(())
     }
 
+    /// Replace a job's metadata
+    pub fn update_job_metadata(
+        &self,
+        job_id: &str,
+        metadata: &serde_json::Value,
+    ) -> Result<(), DbError> {
+        self.conn.execute(
+            "UPDATE jobs SET metadata_json = ? WHERE id = ?",
+            params![metadata.to_string(), job_id],
+        )?;
+        Ok(())
+    }
+
+    /// List queued or running jobs created before `before`, oldest first.
+    /// Called at startup to find work interrupted by the previous shutdown.
+    pub fn list_interrupted_jobs(&self, before: DateTime<Utc>) -> Result<Vec<Job>, DbError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM jobs WHERE status IN ('queued', 'running') AND created_at < ?
+             ORDER BY created_at ASC",
+            JOB_COLUMNS
+        ))?;
+        let jobs = stmt
+            .query_map(params![before.to_rfc3339()], job_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(jobs)
+    }
+
+    /// List queued jobs that are waiting on other jobs, oldest first
+    pub fn list_jobs_with_pending_dependencies(&self) -> Result<Vec<Job>, DbError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM jobs WHERE status = 'queued' AND depends_on_json IS NOT NULL
+             ORDER BY created_at ASC",
+            JOB_COLUMNS
+        ))?;
+        let jobs = stmt
+            .query_map([], job_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(jobs)
+    }
+
+    /// Get the status of each of the given jobs (missing jobs are omitted)
+    pub fn get_job_statuses(
+        &self,
+        job_ids: &[String],
+    ) -> Result<std::collections::HashMap<String, JobStatus>, DbError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, status FROM jobs WHERE id IN (SELECT value FROM json_each(?))")?;
+        let statuses = stmt
+            .query_map([serde_json::json!(job_ids).to_string()], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .filter_map(|row| match row {
+                Ok((id, status)) => status
+                    .parse::<JobStatus>()
+                    .ok()
+                    .map(|status| Ok((id, status))),
+                Err(e) => Some(Err(e)),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(statuses)
+    }
+
     /// Add a log entry for a job
     pub fn add_job_log(
         &self,
@@ -3094,35 +3900,137 @@ impl Database {
         Ok(counts)
     }
 
-    // ============================================================================
-    // Document Versioning Methods (Phase 14)
-    // ============================================================================
-
-    /// Create a version snapshot of a document before updating it
-    pub fn create_document_version(
-        &self,
-        document_id: &str,
-        change_reason: Option<&str>,
-    ) -> Result<String, DbError> {
-        // Get current document state
-        let (file_hash,): (String,) = self.conn.query_row(
-            "SELECT file_hash FROM kb_documents WHERE id = ?",
-            [document_id],
-            |row| Ok((row.get(0)?,)),
-        )?;
+    // -- Job schedules --
 
-        // Get current chunks as JSON
-        let mut stmt = self.conn.prepare(
-            "SELECT id, chunk_index, heading_path, content, word_count
-             FROM kb_chunks WHERE document_id = ? ORDER BY chunk_index",
+    /// Save a job schedule (insert or replace)
+    pub fn save_job_schedule(&self, schedule: &JobSchedule) -> Result<(), DbError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO job_schedules (id, name, cron, job_type, params_json, enabled,
+                    next_run_at, last_run_at, last_job_id, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                schedule.id,
+                schedule.name,
+                schedule.cron,
+                schedule.job_type.to_string(),
+                schedule.params.as_ref().map(|p| p.to_string()),
+                schedule.enabled,
+                schedule.next_run_at.map(|t| t.to_rfc3339()),
+                schedule.last_run_at.map(|t| t.to_rfc3339()),
+                schedule.last_job_id,
+                schedule.created_at.to_rfc3339(),
+                schedule.updated_at.to_rfc3339(),
+            ],
         )?;
+        Ok(())
+    }
 
-        let chunks: Vec<serde_json::Value> = stmt
-            .query_map([document_id], |row| {
-                Ok(serde_json::json!({
-                    "id": row.get::<_, String>(0)?,
-                    "chunk_index": row.get::<_, i32>(1)?,
-                    "heading_path": row.get::<_, Option<String>>(2)?,
+    /// Get a job schedule by ID
+    pub fn get_job_schedule(&self, id: &str) -> Result<Option<JobSchedule>, DbError> {
+        match self.conn.query_row(
+            &format!(
+                "SELECT {} FROM job_schedules s LEFT JOIN jobs j ON j.id = s.last_job_id
+                 WHERE s.id = ?",
+                JOB_SCHEDULE_COLUMNS
+            ),
+            [id],
+            job_schedule_from_row,
+        ) {
+            Ok(schedule) => Ok(Some(schedule)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(DbError::Sqlite(e)),
+        }
+    }
+
+    /// List all job schedules by name
+    pub fn list_job_schedules(&self) -> Result<Vec<JobSchedule>, DbError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM job_schedules s LEFT JOIN jobs j ON j.id = s.last_job_id
+             ORDER BY s.name",
+            JOB_SCHEDULE_COLUMNS
+        ))?;
+        let schedules = stmt
+            .query_map([], job_schedule_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(schedules)
+    }
+
+    /// List enabled schedules whose next run is at or before `now`
+    pub fn list_due_job_schedules(&self, now: DateTime<Utc>) -> Result<Vec<JobSchedule>, DbError> {
+        Ok(self
+            .list_job_schedules()?
+            .into_iter()
+            .filter(|s| s.is_due(now))
+            .collect())
+    }
+
+    /// Record a schedule run (or a skipped run when `job_id` is None) and
+    /// advance its next run time
+    pub fn record_job_schedule_run(
+        &self,
+        id: &str,
+        job_id: Option<&str>,
+        ran_at: DateTime<Utc>,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<(), DbError> {
+        let now = Utc::now().to_rfc3339();
+        match job_id {
+            Some(job_id) => self.conn.execute(
+                "UPDATE job_schedules SET last_run_at = ?, last_job_id = ?, next_run_at = ?,
+                        updated_at = ?
+                 WHERE id = ?",
+                params![
+                    ran_at.to_rfc3339(),
+                    job_id,
+                    next_run_at.map(|t| t.to_rfc3339()),
+                    now,
+                    id
+                ],
+            )?,
+            None => self.conn.execute(
+                "UPDATE job_schedules SET next_run_at = ?, updated_at = ? WHERE id = ?",
+                params![next_run_at.map(|t| t.to_rfc3339()), now, id],
+            )?,
+        };
+        Ok(())
+    }
+
+    /// Delete a job schedule (its past jobs are kept)
+    pub fn delete_job_schedule(&self, id: &str) -> Result<(), DbError> {
+        self.conn
+            .execute("DELETE FROM job_schedules WHERE id = ?", [id])?;
+        Ok(())
+    }
+
+    // ============================================================================
+    // Document Versioning Methods (Phase 14)
+    // ============================================================================
+
+    /// Create a version snapshot of a document before updating it
+    pub fn create_document_version(
+        &self,
+        document_id: &str,
+        change_reason: Option<&str>,
+    ) -> Result<String, DbError> {
+        // Get current document state
+        let (file_hash,): (String,) = self.conn.query_row(
+            "SELECT file_hash FROM kb_documents WHERE id = ?",
+            [document_id],
+            |row| Ok((row.get(0)?,)),
+        )?;
+
+        // Get current chunks as JSON
+        let mut stmt = self.conn.prepare(
+            "SELECT id, chunk_index, heading_path, content, word_count
+             FROM kb_chunks WHERE document_id = ? ORDER BY chunk_index",
+        )?;
+
+        let chunks: Vec<serde_json::Value> = stmt
+            .query_map([document_id], |row| {
+                Ok(serde_json::json!({
+                    "id": row.get::<_, String>(0)?,
+                    "chunk_index": row.get::<_, i32>(1)?,
+                    "heading_path": row.get::<_, Option<String>>(2)?,
                     "content": row.get::<_, String>(3)?,
                     "word_count": row.get::<_, Option<i32>>(4)?,
                 }))
@@ -4375,6 +5283,33 @@ impl Database {
         Ok(())
     }
 
+    /// Get the configuration record for a single integration type.
+    pub fn get_integration_config(
+        &self,
+        integration_type: &str,
+    ) -> Result<Option<IntegrationConfigRecord>, DbError> {
+        let result = self.conn.query_row(
+            "SELECT id, integration_type, enabled, config_json, updated_at
+             FROM integration_configs
+             WHERE integration_type = ?",
+            [integration_type],
+            |row| {
+                Ok(IntegrationConfigRecord {
+                    id: row.get(0)?,
+                    integration_type: row.get(1)?,
+                    enabled: row.get::<_, i32>(2)? == 1,
+                    config_json: row.get(3)?,
+                    updated_at: row.get(4)?,
+                })
+            },
+        );
+        match result {
+            Ok(record) => Ok(Some(record)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(DbError::Sqlite(e)),
+        }
+    }
+
     /// List integration configuration records.
     pub fn list_integration_configs(&self) -> Result<Vec<IntegrationConfigRecord>, DbError> {
         let mut stmt = self.conn.prepare(
@@ -4510,6 +5445,35 @@ impl Database {
         })
     }
 
+    /// KB storage per namespace, largest first
+    pub fn get_namespace_storage(&self) -> Result<Vec<NamespaceStorage>, DbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT n.id, n.name,
+                    COUNT(DISTINCT d.id) as doc_count,
+                    COUNT(c.id) as chunk_count,
+                    COALESCE(SUM(LENGTH(c.content)), 0) as content_bytes
+             FROM namespaces n
+             LEFT JOIN kb_documents d ON d.namespace_id = n.id
+             LEFT JOIN kb_chunks c ON c.document_id = d.id
+             GROUP BY n.id
+             ORDER BY content_bytes DESC, n.name",
+        )?;
+
+        let namespaces = stmt
+            .query_map([], |row| {
+                Ok(NamespaceStorage {
+                    namespace_id: row.get(0)?,
+                    namespace_name: row.get(1)?,
+                    document_count: row.get(2)?,
+                    chunk_count: row.get(3)?,
+                    content_bytes: row.get(4)?,
+                    estimated_vector_bytes: 0,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(namespaces)
+    }
+
     // ========================================================================
     // Phase 6: Draft Version Restore
     // ========================================================================
@@ -4867,6 +5831,120 @@ impl Database {
     // Phase 2 v0.4.0: Jira Status Transitions
     // ========================================================================
 
+    // -- Follow-up reminders --
+
+    /// Create a follow-up reminder for a draft and/or ticket.
+    /// `due_at` must be an RFC 3339 UTC timestamp (see `follow_ups::normalize_due_at`).
+    pub fn create_follow_up(
+        &self,
+        draft_id: Option<&str>,
+        ticket_id: Option<&str>,
+        due_at: &str,
+        note: Option<&str>,
+    ) -> Result<FollowUp, DbError> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "INSERT INTO follow_ups (id, draft_id, ticket_id, note, due_at, status, created_at)
+             VALUES (?, ?, ?, ?, ?, 'pending', ?)",
+            params![&id, draft_id, ticket_id, note, due_at, &now],
+        )?;
+        self.get_follow_up(&id)
+    }
+
+    /// Get a follow-up by ID
+    pub fn get_follow_up(&self, id: &str) -> Result<FollowUp, DbError> {
+        let follow_up = self.conn.query_row(
+            &format!("SELECT {} FROM follow_ups WHERE id = ?", FOLLOW_UP_COLUMNS),
+            [id],
+            FollowUp::from_row,
+        )?;
+        Ok(follow_up)
+    }
+
+    /// List follow-ups ordered by due date (pending only unless `include_completed`)
+    pub fn list_follow_ups(
+        &self,
+        include_completed: bool,
+        limit: usize,
+    ) -> Result<Vec<FollowUp>, DbError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM follow_ups
+             WHERE status = 'pending' OR ?
+             ORDER BY due_at ASC
+             LIMIT ?",
+            FOLLOW_UP_COLUMNS
+        ))?;
+        let rows = stmt
+            .query_map(params![include_completed, limit as i64], FollowUp::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// List pending follow-ups due at or before `now` (RFC 3339 UTC)
+    pub fn list_due_follow_ups(&self, now: &str) -> Result<Vec<FollowUp>, DbError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM follow_ups
+             WHERE status = 'pending' AND due_at <= ?
+             ORDER BY due_at ASC",
+            FOLLOW_UP_COLUMNS
+        ))?;
+        let rows = stmt
+            .query_map([now], FollowUp::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// List pending follow-ups for a draft
+    pub fn list_follow_ups_for_draft(&self, draft_id: &str) -> Result<Vec<FollowUp>, DbError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM follow_ups
+             WHERE draft_id = ? AND status = 'pending'
+             ORDER BY due_at ASC",
+            FOLLOW_UP_COLUMNS
+        ))?;
+        let rows = stmt
+            .query_map([draft_id], FollowUp::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Record that a local notification was shown for a follow-up
+    pub fn mark_follow_up_notified(&self, id: &str) -> Result<(), DbError> {
+        self.conn.execute(
+            "UPDATE follow_ups SET notified_at = ? WHERE id = ?",
+            params![Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Move a follow-up to a new due date (re-arms its notification)
+    pub fn reschedule_follow_up(&self, id: &str, due_at: &str) -> Result<(), DbError> {
+        self.conn.execute(
+            "UPDATE follow_ups
+             SET due_at = ?, notified_at = NULL, status = 'pending', completed_at = NULL
+             WHERE id = ?",
+            params![due_at, id],
+        )?;
+        Ok(())
+    }
+
+    /// Mark a follow-up as done
+    pub fn complete_follow_up(&self, id: &str) -> Result<(), DbError> {
+        self.conn.execute(
+            "UPDATE follow_ups SET status = 'completed', completed_at = ? WHERE id = ?",
+            params![Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Delete a follow-up
+    pub fn delete_follow_up(&self, id: &str) -> Result<(), DbError> {
+        self.conn
+            .execute("DELETE FROM follow_ups WHERE id = ?", [id])?;
+        Ok(())
+    }
+
     /// Save a Jira status transition
     pub fn save_jira_transition(
         &self,
@@ -4888,6 +5966,111 @@ impl Database {
         )?;
         Ok(transition.id.clone())
     }
+
+    // ========================================================================
+    // Performance Profiles
+    // ========================================================================
+
+    /// Store a performance profile run, keeping the most recent runs only
+    pub fn save_performance_profile(&self, profile: &PerformanceProfile) -> Result<(), DbError> {
+        let steps_json = serde_json::to_string(&profile.steps)
+            .map_err(|e| DbError::Sqlite(rusqlite::Error::InvalidParameterName(e.to_string())))?;
+        self.conn.execute(
+            "INSERT INTO performance_profiles (id, created_at, app_version, total_ms, steps_json)
+             VALUES (?, ?, ?, ?, ?)",
+            params![
+                profile.id,
+                profile.created_at,
+                profile.app_version,
+                profile.total_ms,
+                steps_json
+            ],
+        )?;
+        self.conn.execute(
+            "DELETE FROM performance_profiles WHERE id NOT IN
+             (SELECT id FROM performance_profiles ORDER BY created_at DESC LIMIT ?)",
+            [MAX_PERFORMANCE_PROFILES as i64],
+        )?;
+        Ok(())
+    }
+
+    /// List performance profile runs, newest first
+    pub fn list_performance_profiles(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<PerformanceProfile>, DbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, created_at, app_version, total_ms, steps_json
+             FROM performance_profiles ORDER BY created_at DESC LIMIT ?",
+        )?;
+        let profiles = stmt
+            .query_map([limit as i64], |row| {
+                let steps_json: String = row.get(4)?;
+                Ok(PerformanceProfile {
+                    id: row.get(0)?,
+                    created_at: row.get(1)?,
+                    app_version: row.get(2)?,
+                    total_ms: row.get(3)?,
+                    steps: serde_json::from_str(&steps_json).unwrap_or_default(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(profiles)
+    }
+
+    // ========================================================================
+    // Metrics Samples
+    // ========================================================================
+
+    /// Store a metrics sample and delete samples past the retention period
+    pub fn record_metrics_sample(&self, sample: &MetricsSample) -> Result<(), DbError> {
+        self.conn.execute(
+            "INSERT INTO metrics_samples
+             (sampled_at, memory_bytes, generation_latency_ms, search_latency_ms, job_queue_depth)
+             VALUES (?, ?, ?, ?, ?)",
+            params![
+                metrics_timestamp(sample.sampled_at),
+                sample.memory_bytes as i64,
+                sample.generation_latency_ms,
+                sample.search_latency_ms,
+                sample.job_queue_depth,
+            ],
+        )?;
+        let cutoff = sample.sampled_at - chrono::Duration::days(METRICS_RETENTION_DAYS);
+        self.conn.execute(
+            "DELETE FROM metrics_samples WHERE sampled_at < ?",
+            [metrics_timestamp(cutoff)],
+        )?;
+        Ok(())
+    }
+
+    /// Metrics samples taken at or after `since`, oldest first
+    pub fn get_metrics_samples(&self, since: DateTime<Utc>) -> Result<Vec<MetricsSample>, DbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT sampled_at, memory_bytes, generation_latency_ms, search_latency_ms, job_queue_depth
+             FROM metrics_samples WHERE sampled_at >= ? ORDER BY sampled_at",
+        )?;
+        let samples = stmt
+            .query_map([metrics_timestamp(since)], |row| {
+                let sampled_at: String = row.get(0)?;
+                Ok(MetricsSample {
+                    sampled_at: DateTime::parse_from_rfc3339(&sampled_at)
+                        .map(|t| t.with_timezone(&Utc))
+                        .unwrap_or_default(),
+                    memory_bytes: row.get::<_, i64>(1)?.max(0) as u64,
+                    generation_latency_ms: row.get(2)?,
+                    search_latency_ms: row.get(3)?,
+                    job_queue_depth: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(samples)
+    }
+}
+
+/// Fixed-width timestamp so metrics samples compare correctly as text
+fn metrics_timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
 }
 
 /// FTS5 search result
@@ -4908,6 +6091,13 @@ pub struct VectorConsent {
     pub encryption_supported: Option<bool>,
 }
 
+/// Consent to record finalized drafts as MemoryKernel decisions
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MemoryKernelConsent {
+    pub record_decisions: bool,
+    pub consented_at: Option<String>,
+}
+
 /// Decision tree from database
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DecisionTree {
@@ -5005,6 +6195,10 @@ pub struct SavedDraft {
     /// Who finalized the draft
     #[serde(default)]
     pub finalized_by: Option<String>,
+    /// MemoryKernel policy gate citations (JSON) for the generated response:
+    /// context package ids and the `memory_version_id` of each cited record
+    #[serde(default)]
+    pub policy_citations_json: Option<String>,
 }
 
 /// Draft version for history/diff view
@@ -5065,6 +6259,17 @@ pub struct ResponseTemplate {
     pub updated_at: String,
 }
 
+/// User-defined draft export layout (see `export_templates`)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ExportTemplate {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub content: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
 /// Custom template variable
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CustomVariable {
@@ -5083,6 +6288,9 @@ pub struct Namespace {
     pub color: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// Search, context and chunk reads require restricted access to be unlocked
+    #[serde(default)]
+    pub restricted: bool,
 }
 
 /// Namespace with document and source counts (optimized query result)
@@ -5094,6 +6302,7 @@ pub struct NamespaceWithCounts {
     pub color: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    pub restricted: bool,
     pub document_count: i64,
     pub source_count: i64,
 }
@@ -5117,6 +6326,19 @@ pub struct IngestSource {
     pub updated_at: String,
 }
 
+/// An entry ingested from a feed source, keyed by its GUID
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FeedEntryRecord {
+    pub source_id: String,
+    pub guid: String,
+    /// None if the entry had no indexable text
+    pub document_id: Option<String>,
+    pub title: Option<String>,
+    pub link: Option<String>,
+    pub published_at: Option<String>,
+    pub ingested_at: String,
+}
+
 /// Document version for rollback support (Phase 14)
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DocumentVersion {
@@ -5387,6 +6609,31 @@ pub struct KbHealthStats {
     pub namespace_distribution: Vec<NamespaceDistribution>,
 }
 
+impl KbHealthStats {
+    /// Share of stale documents above which a health warning is raised
+    const STALE_WARNING_RATIO: f64 = 0.25;
+
+    /// Human-readable warnings derived from the stats (empty when healthy)
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.total_documents == 0 {
+            warnings.push("Knowledge base is empty".to_string());
+            return warnings;
+        }
+        if self.total_chunks == 0 {
+            warnings.push("Knowledge base has documents but no indexed chunks".to_string());
+        }
+        let stale_ratio = self.stale_documents as f64 / self.total_documents as f64;
+        if stale_ratio > Self::STALE_WARNING_RATIO {
+            warnings.push(format!(
+                "{} of {} documents have not been re-indexed in 30 days",
+                self.stale_documents, self.total_documents
+            ));
+        }
+        warnings
+    }
+}
+
 /// Namespace distribution in KB health
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct NamespaceDistribution {
@@ -5396,6 +6643,51 @@ pub struct NamespaceDistribution {
     pub chunk_count: i64,
 }
 
+/// Time spent in one startup phase
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StartupPhase {
+    /// Phase name (data_migration, keystore, db_open, db_migrations,
+    /// vector_init, model_autoload:llm, model_autoload:embeddings)
+    pub name: String,
+    pub duration_ms: i64,
+}
+
+/// One recorded startup and its phases
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StartupTimings {
+    pub id: i64,
+    pub started_at: String,
+    /// Total time spent in `initialize_app`
+    pub init_app_ms: Option<i64>,
+    pub models_cached: bool,
+    /// Phases in the order they ran
+    pub phases: Vec<StartupPhase>,
+}
+
+/// KB storage attributed to one namespace
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct NamespaceStorage {
+    pub namespace_id: String,
+    pub namespace_name: String,
+    pub document_count: i64,
+    pub chunk_count: i64,
+    /// Size of the chunk text (approximate, in characters)
+    pub content_bytes: i64,
+    /// Estimated vector store size (chunks x embedding dimension x 4 bytes)
+    pub estimated_vector_bytes: u64,
+}
+
+/// Autosave drafts stored in the database
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AutosaveUsage {
+    pub count: i64,
+    /// Size of the draft text (approximate, in characters)
+    pub bytes: i64,
+    /// Autosaves beyond the kept count
+    pub reclaimable_count: i64,
+    pub reclaimable_bytes: i64,
+}
+
 /// Document review info for KB staleness tracking
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DocumentReviewInfo {
@@ -5472,6 +6764,116 @@ pub struct JiraStatusTransition {
     pub transitioned_at: String,
 }
 
+/// Follow-up reminder on a draft and/or ticket
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FollowUp {
+    pub id: String,
+    pub draft_id: Option<String>,
+    pub ticket_id: Option<String>,
+    pub note: Option<String>,
+    /// Due time (RFC 3339, UTC)
+    pub due_at: String,
+    /// "pending" or "completed"
+    pub status: String,
+    /// When the local notification was shown (None until due)
+    pub notified_at: Option<String>,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+}
+
+const FOLLOW_UP_COLUMNS: &str =
+    "id, draft_id, ticket_id, note, due_at, status, notified_at, created_at, completed_at";
+
+impl FollowUp {
+    fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            draft_id: row.get(1)?,
+            ticket_id: row.get(2)?,
+            note: row.get(3)?,
+            due_at: row.get(4)?,
+            status: row.get(5)?,
+            notified_at: row.get(6)?,
+            created_at: row.get(7)?,
+            completed_at: row.get(8)?,
+        })
+    }
+}
+
+const JOB_COLUMNS: &str = "id, job_type, status, created_at, updated_at, started_at, completed_at,
+     progress, progress_message, error, metadata_json, priority, depends_on_json";
+
+fn job_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Job> {
+    let status_str: String = row.get(2)?;
+    let metadata_json: Option<String> = row.get(10)?;
+    Ok(Job {
+        id: row.get(0)?,
+        job_type: row
+            .get::<_, String>(1)?
+            .parse::<JobType>()
+            .unwrap_or(JobType::Custom("unknown".into())),
+        status: status_str.parse::<JobStatus>().unwrap_or(JobStatus::Queued),
+        priority: row
+            .get::<_, String>(11)?
+            .parse::<JobPriority>()
+            .unwrap_or_default(),
+        created_at: chrono::DateTime::parse_from_rfc3339(&row.get::<_, String>(3)?)
+            .map(|t| t.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+        updated_at: chrono::DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+            .map(|t| t.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+        started_at: row
+            .get::<_, Option<String>>(5)?
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+            .map(|t| t.with_timezone(&Utc)),
+        completed_at: row
+            .get::<_, Option<String>>(6)?
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+            .map(|t| t.with_timezone(&Utc)),
+        progress: row.get(7)?,
+        progress_message: row.get(8)?,
+        error: row.get(9)?,
+        metadata: metadata_json.and_then(|s| serde_json::from_str(&s).ok()),
+        depends_on: row
+            .get::<_, Option<String>>(12)?
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+    })
+}
+
+const JOB_SCHEDULE_COLUMNS: &str = "s.id, s.name, s.cron, s.job_type, s.params_json, s.enabled,
+     s.next_run_at, s.last_run_at, s.last_job_id, s.created_at, s.updated_at, j.status";
+
+fn job_schedule_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<JobSchedule> {
+    let parse_time = |value: Option<String>| {
+        value
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+            .map(|t| t.with_timezone(&Utc))
+    };
+    Ok(JobSchedule {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        cron: row.get(2)?,
+        job_type: row
+            .get::<_, String>(3)?
+            .parse::<JobType>()
+            .unwrap_or(JobType::Custom("unknown".into())),
+        params: row
+            .get::<_, Option<String>>(4)?
+            .and_then(|s| serde_json::from_str(&s).ok()),
+        enabled: row.get(5)?,
+        next_run_at: parse_time(row.get(6)?),
+        last_run_at: parse_time(row.get(7)?),
+        last_job_id: row.get(8)?,
+        last_status: row
+            .get::<_, Option<String>>(11)?
+            .and_then(|s| s.parse::<JobStatus>().ok()),
+        created_at: parse_time(row.get(9)?).unwrap_or_else(Utc::now),
+        updated_at: parse_time(row.get(10)?).unwrap_or_else(Utc::now),
+    })
+}
+
 /// Built-in decision trees: (id, name, category, tree_json)
 const BUILTIN_TREES: &[(&str, &str, &str, &str)] = &[
     (
@@ -5540,6 +6942,11 @@ pub fn get_logs_dir() -> PathBuf {
         .join("AssistSupport")
 }
 
+/// Get crash reports directory
+pub fn get_crashes_dir() -> PathBuf {
+    get_app_data_dir().join("crashes")
+}
+
 /// Get cache directory
 pub fn get_cache_dir() -> PathBuf {
     dirs::cache_dir()
@@ -5561,6 +6968,23 @@ mod tests {
         (db, dir)
     }
 
+    #[test]
+    fn test_rekey() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let old_key = MasterKey::generate();
+        let new_key = MasterKey::generate();
+        {
+            let db = Database::open(&db_path, &old_key).unwrap();
+            db.initialize().unwrap();
+            db.rekey(&new_key).unwrap();
+        }
+
+        assert!(Database::open(&db_path, &old_key).is_err());
+        let db = Database::open(&db_path, &new_key).unwrap();
+        assert!(db.check_integrity().is_ok());
+    }
+
     #[test]
     fn test_database_creation() {
         let (db, _dir) = create_test_db();
@@ -5723,6 +7147,77 @@ mod tests {
         assert_eq!(consent.encryption_supported, Some(true));
     }
 
+    #[test]
+    fn test_memory_kernel_consent() {
+        let (db, _dir) = create_test_db();
+
+        // Recording finalized drafts is opt-in
+        let consent = db.get_memory_kernel_consent().unwrap();
+        assert!(!consent.record_decisions);
+        assert!(consent.consented_at.is_none());
+
+        db.set_memory_kernel_consent(true).unwrap();
+        let consent = db.get_memory_kernel_consent().unwrap();
+        assert!(consent.record_decisions);
+        assert!(consent.consented_at.is_some());
+    }
+
+    #[test]
+    fn test_draft_policy_citations() {
+        let (db, _dir) = create_test_db();
+
+        let citations = serde_json::json!({
+            "decision": "allow",
+            "ask_context_package_id": "ctx-ask",
+            "recall_context_package_id": "ctx-recall",
+            "constraints": [{
+                "memory_version_id": "01JCONSTRAINT",
+                "record_type": "constraint",
+                "rank": 1,
+                "reason": null,
+            }],
+            "related": [{
+                "memory_version_id": "01JDECISION",
+                "record_type": "decision",
+                "rank": 1,
+                "reason": null,
+            }],
+        });
+        let draft = SavedDraft {
+            id: "draft-1".to_string(),
+            input_text: "Can contractors use USB drives?".to_string(),
+            summary_text: None,
+            diagnosis_json: None,
+            response_text: Some("Contractors may use encrypted drives.".to_string()),
+            ticket_id: None,
+            kb_sources_json: None,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+            is_autosave: false,
+            model_name: None,
+            case_intake_json: None,
+            status: DraftStatus::default(),
+            handoff_summary: None,
+            finalized_at: None,
+            finalized_by: None,
+            policy_citations_json: Some(citations.to_string()),
+        };
+        db.save_draft(&draft).unwrap();
+
+        let saved = db.get_draft("draft-1").unwrap();
+        let saved: serde_json::Value =
+            serde_json::from_str(saved.policy_citations_json.as_deref().unwrap()).unwrap();
+        assert_eq!(saved["ask_context_package_id"], "ctx-ask");
+        assert_eq!(
+            saved["constraints"][0]["memory_version_id"],
+            "01JCONSTRAINT"
+        );
+        assert_eq!(saved["related"][0]["memory_version_id"], "01JDECISION");
+
+        let listed = db.list_drafts(10).unwrap();
+        assert_eq!(listed[0].policy_citations_json, draft.policy_citations_json);
+    }
+
     #[test]
     fn test_job_crud() {
         let (db, _dir) = create_test_db();
@@ -5789,6 +7284,77 @@ mod tests {
         assert_eq!(logs[3].message, "Starting ingestion");
     }
 
+    #[test]
+    fn test_list_interrupted_jobs() {
+        let (db, _dir) = create_test_db();
+
+        let mut running = Job::new(JobType::IndexKb);
+        running.created_at = Utc::now() - chrono::Duration::minutes(10);
+        db.create_job(&running).unwrap();
+        db.update_job_status(&running.id, JobStatus::Running, None)
+            .unwrap();
+
+        let mut queued = Job::new(JobType::Backup).with_priority(JobPriority::High);
+        queued.created_at = Utc::now() - chrono::Duration::minutes(5);
+        db.create_job(&queued).unwrap();
+
+        let mut done = Job::new(JobType::IndexKb);
+        done.created_at = Utc::now() - chrono::Duration::minutes(5);
+        db.create_job(&done).unwrap();
+        db.update_job_status(&done.id, JobStatus::Succeeded, None)
+            .unwrap();
+
+        let cutoff = Utc::now();
+        // Jobs created after startup belong to this session
+        db.create_job(&Job::new(JobType::IndexKb)).unwrap();
+
+        let interrupted = db.list_interrupted_jobs(cutoff).unwrap();
+        let ids: Vec<&str> = interrupted.iter().map(|j| j.id.as_str()).collect();
+        assert_eq!(ids, vec![running.id.as_str(), queued.id.as_str()]);
+        assert_eq!(interrupted[0].status, JobStatus::Running);
+        assert_eq!(interrupted[1].priority, JobPriority::High);
+
+        let metadata = serde_json::json!({ "completed": 3 });
+        db.update_job_metadata(&queued.id, &metadata).unwrap();
+        let job = db.get_job(&queued.id).unwrap().unwrap();
+        assert_eq!(job.metadata, Some(metadata));
+    }
+
+    #[test]
+    fn test_job_dependencies() {
+        let (db, _dir) = create_test_db();
+
+        let index = Job::new(JobType::IndexKb);
+        db.create_job(&index).unwrap();
+        let embed = Job::new(JobType::GenerateEmbeddings).with_dependencies(vec![index.id.clone()]);
+        db.create_job(&embed).unwrap();
+
+        let stored = db.get_job(&embed.id).unwrap().unwrap();
+        assert_eq!(stored.depends_on, vec![index.id.clone()]);
+        assert!(db
+            .get_job(&index.id)
+            .unwrap()
+            .unwrap()
+            .depends_on
+            .is_empty());
+
+        let waiting = db.list_jobs_with_pending_dependencies().unwrap();
+        assert_eq!(waiting.len(), 1);
+        assert_eq!(waiting[0].id, embed.id);
+
+        db.update_job_status(&index.id, JobStatus::Succeeded, None)
+            .unwrap();
+        let statuses = db
+            .get_job_statuses(&[index.id.clone(), "missing".to_string()])
+            .unwrap();
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[&index.id], JobStatus::Succeeded);
+
+        db.update_job_status(&embed.id, JobStatus::Running, None)
+            .unwrap();
+        assert!(db.list_jobs_with_pending_dependencies().unwrap().is_empty());
+    }
+
     #[test]
     fn test_list_jobs_by_status() {
         let (db, _dir) = create_test_db();
@@ -5851,4 +7417,452 @@ mod tests {
         let failed_job = db.get_job(&job2.id).unwrap().unwrap();
         assert_eq!(failed_job.error, Some("Test error".to_string()));
     }
+
+    #[test]
+    fn test_migrate_v26_keeps_sources_and_runs() {
+        let (db, _dir) = create_test_db();
+
+        // Recreate the pre-v26 table, whose CHECK rejects 'confluence'
+        let old_sql: String = db
+            .conn
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'ingest_sources'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        db.conn
+            .execute_batch(&format!(
+                "DROP TABLE ingest_sources; {};",
+                old_sql.replace(", 'confluence'", "")
+            ))
+            .unwrap();
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let source = IngestSource {
+            id: "src-1".to_string(),
+            source_type: "web".to_string(),
+            source_uri: "https://example.com/vpn".to_string(),
+            namespace_id: "default".to_string(),
+            title: Some("VPN".to_string()),
+            etag: None,
+            last_modified: None,
+            content_hash: None,
+            last_ingested_at: None,
+            status: "active".to_string(),
+            error_message: None,
+            metadata_json: None,
+            created_at: now.clone(),
+            updated_at: now,
+        };
+        db.save_ingest_source(&source).unwrap();
+        let run_id = db.create_ingest_run("src-1").unwrap();
+        assert!(db
+            .save_ingest_source(&IngestSource {
+                id: "src-2".to_string(),
+                source_type: "confluence".to_string(),
+                ..source.clone()
+            })
+            .is_err());
+
+        db.migrate_v26().unwrap();
+
+        assert_eq!(
+            db.get_ingest_source("src-1").unwrap().title.as_deref(),
+            Some("VPN")
+        );
+        let runs: i64 = db
+            .conn
+            .query_row(
+                "SELECT COUNT(*) FROM ingest_runs WHERE id = ?",
+                [&run_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(runs, 1);
+        db.save_ingest_source(&IngestSource {
+            id: "src-2".to_string(),
+            source_type: "confluence".to_string(),
+            ..source
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_feed_entries_dedupe_by_guid() {
+        let (db, _dir) = create_test_db();
+        let now = chrono::Utc::now().to_rfc3339();
+        db.save_ingest_source(&IngestSource {
+            id: "feed-1".to_string(),
+            source_type: "feed".to_string(),
+            source_uri: "https://status.example.com/history.rss".to_string(),
+            namespace_id: "default".to_string(),
+            title: Some("Example Status".to_string()),
+            etag: None,
+            last_modified: None,
+            content_hash: None,
+            last_ingested_at: None,
+            status: "active".to_string(),
+            error_message: None,
+            metadata_json: None,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+        })
+        .unwrap();
+
+        let entry = FeedEntryRecord {
+            source_id: "feed-1".to_string(),
+            guid: "incident-42".to_string(),
+            document_id: None,
+            title: Some("Degraded SSO".to_string()),
+            link: None,
+            published_at: None,
+            ingested_at: now,
+        };
+        assert!(!db.has_feed_entry("feed-1", "incident-42").unwrap());
+        db.record_feed_entry(&entry).unwrap();
+        db.record_feed_entry(&entry).unwrap();
+        assert!(db.has_feed_entry("feed-1", "incident-42").unwrap());
+
+        // Entries go with their feed
+        db.delete_ingest_source("feed-1").unwrap();
+        assert!(!db.has_feed_entry("feed-1", "incident-42").unwrap());
+    }
+
+    #[test]
+    fn test_get_integration_config() {
+        let (db, _dir) = create_test_db();
+        assert!(db.get_integration_config("slack").unwrap().is_none());
+
+        db.set_integration_config("slack", true, Some("{\"channel\":\"#ops\"}"))
+            .unwrap();
+        let record = db.get_integration_config("slack").unwrap().unwrap();
+        assert!(record.enabled);
+        assert_eq!(
+            record.config_json.as_deref(),
+            Some("{\"channel\":\"#ops\"}")
+        );
+    }
+
+    #[test]
+    fn test_follow_up_lifecycle() {
+        let (db, _dir) = create_test_db();
+        let later = db
+            .create_follow_up(None, Some("HELP-2"), "2026-03-05T09:00:00Z", None)
+            .unwrap();
+        let soon = db
+            .create_follow_up(
+                None,
+                Some("HELP-1"),
+                "2026-03-01T09:00:00Z",
+                Some("Check VPN fix"),
+            )
+            .unwrap();
+        assert_eq!(soon.status, "pending");
+
+        let pending = db.list_follow_ups(false, 10).unwrap();
+        assert_eq!(
+            pending.iter().map(|f| f.id.as_str()).collect::<Vec<_>>(),
+            vec![soon.id.as_str(), later.id.as_str()]
+        );
+
+        let due = db.list_due_follow_ups("2026-03-02T00:00:00Z").unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].note.as_deref(), Some("Check VPN fix"));
+
+        db.mark_follow_up_notified(&soon.id).unwrap();
+        assert!(db.get_follow_up(&soon.id).unwrap().notified_at.is_some());
+        db.reschedule_follow_up(&soon.id, "2026-03-03T09:00:00Z")
+            .unwrap();
+        let rescheduled = db.get_follow_up(&soon.id).unwrap();
+        assert!(rescheduled.notified_at.is_none());
+        assert!(db
+            .list_due_follow_ups("2026-03-02T00:00:00Z")
+            .unwrap()
+            .is_empty());
+
+        db.complete_follow_up(&later.id).unwrap();
+        assert_eq!(db.list_follow_ups(false, 10).unwrap().len(), 1);
+        assert_eq!(db.list_follow_ups(true, 10).unwrap().len(), 2);
+
+        db.delete_follow_up(&soon.id).unwrap();
+        assert!(db.get_follow_up(&soon.id).is_err());
+    }
+
+    #[test]
+    fn test_job_schedule_lifecycle() {
+        let (db, _dir) = create_test_db();
+        let mut schedule = JobSchedule::new(
+            "Weekly backup",
+            "0 3 * * sun",
+            JobType::Backup,
+            Some(serde_json::json!({ "folder": "/tmp/backups" })),
+        )
+        .unwrap();
+        db.save_job_schedule(&schedule).unwrap();
+
+        let loaded = db.get_job_schedule(&schedule.id).unwrap().unwrap();
+        assert_eq!(loaded.job_type, JobType::Backup);
+        assert_eq!(loaded.params.unwrap()["folder"], "/tmp/backups");
+        assert_eq!(loaded.next_run_at, schedule.next_run_at);
+        assert!(db.list_due_job_schedules(Utc::now()).unwrap().is_empty());
+
+        // Overdue schedules are due; disabled ones are not
+        let past = Utc::now() - chrono::Duration::hours(1);
+        schedule.next_run_at = Some(past);
+        db.save_job_schedule(&schedule).unwrap();
+        assert_eq!(db.list_due_job_schedules(Utc::now()).unwrap().len(), 1);
+
+        let job = schedule.create_job();
+        db.create_job(&job).unwrap();
+        db.update_job_status(&job.id, JobStatus::Succeeded, None)
+            .unwrap();
+        let next = Utc::now() + chrono::Duration::days(7);
+        db.record_job_schedule_run(&schedule.id, Some(&job.id), past, Some(next))
+            .unwrap();
+        let loaded = db.get_job_schedule(&schedule.id).unwrap().unwrap();
+        assert_eq!(loaded.last_job_id.as_deref(), Some(job.id.as_str()));
+        assert_eq!(loaded.last_status, Some(JobStatus::Succeeded));
+        assert!(db.list_due_job_schedules(Utc::now()).unwrap().is_empty());
+
+        db.delete_job_schedule(&schedule.id).unwrap();
+        assert!(db.get_job_schedule(&schedule.id).unwrap().is_none());
+        assert!(db.get_job(&job.id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_kb_health_warnings() {
+        let healthy = KbHealthStats {
+            total_documents: 10,
+            total_chunks: 50,
+            stale_documents: 1,
+            namespace_distribution: vec![],
+        };
+        assert!(healthy.warnings().is_empty());
+
+        let stale = KbHealthStats {
+            stale_documents: 6,
+            ..healthy.clone()
+        };
+        assert_eq!(stale.warnings().len(), 1);
+
+        let empty = KbHealthStats {
+            total_documents: 0,
+            total_chunks: 0,
+            stale_documents: 0,
+            namespace_distribution: vec![],
+        };
+        assert_eq!(
+            empty.warnings(),
+            vec!["Knowledge base is empty".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_performance_profiles() {
+        use crate::diagnostics::{ProfileStep, ProfileStepStatus};
+
+        let (db, _dir) = create_test_db();
+        let mut first = PerformanceProfile::new(vec![ProfileStep::ok(
+            "fts_search",
+            std::time::Duration::from_millis(8),
+            "2 results".into(),
+        )]);
+        first.created_at = "2026-03-01T10:00:00+00:00".into();
+        let mut second =
+            PerformanceProfile::new(vec![ProfileStep::skipped("generate", "No model loaded")]);
+        second.created_at = "2026-03-02T10:00:00+00:00".into();
+        db.save_performance_profile(&first).unwrap();
+        db.save_performance_profile(&second).unwrap();
+
+        let profiles = db.list_performance_profiles(10).unwrap();
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].id, second.id);
+        assert_eq!(profiles[0].steps[0].status, ProfileStepStatus::Skipped);
+        assert_eq!(profiles[1].steps[0].detail.as_deref(), Some("2 results"));
+
+        // Only the most recent runs are kept
+        for i in 0..MAX_PERFORMANCE_PROFILES {
+            let mut profile = PerformanceProfile::new(Vec::new());
+            profile.created_at = format!("2026-04-01T00:{:02}:{:02}+00:00", i / 60, i % 60);
+            db.save_performance_profile(&profile).unwrap();
+        }
+        let profiles = db
+            .list_performance_profiles(MAX_PERFORMANCE_PROFILES + 10)
+            .unwrap();
+        assert_eq!(profiles.len(), MAX_PERFORMANCE_PROFILES);
+        assert!(profiles.iter().all(|p| p.id != first.id));
+    }
+
+    #[test]
+    fn test_restricted_namespaces() {
+        let (db, _dir) = create_test_db();
+        db.create_namespace("HR Policies", None, None).unwrap();
+        db.conn()
+            .execute(
+                "INSERT INTO kb_documents (id, file_path, file_hash, indexed_at, namespace_id) VALUES (?, ?, ?, ?, ?)",
+                params!["doc1", "/test/leave.md", "abc123", "2024-01-01", "hr-policies"],
+            )
+            .unwrap();
+
+        assert!(db.restricted_namespace_ids().unwrap().is_empty());
+        assert!(db.set_namespace_restricted("hr-policies", true).unwrap());
+        assert!(!db.set_namespace_restricted("missing", true).unwrap());
+
+        let restricted = db.restricted_namespace_ids().unwrap();
+        assert_eq!(restricted.len(), 1);
+        assert!(restricted.contains("hr-policies"));
+        assert!(db.get_namespace("hr-policies").unwrap().restricted);
+        assert!(
+            db.list_namespaces_with_counts()
+                .unwrap()
+                .iter()
+                .find(|n| n.id == "hr-policies")
+                .unwrap()
+                .restricted
+        );
+
+        assert_eq!(
+            db.document_namespace("doc1").unwrap().as_deref(),
+            Some("hr-policies")
+        );
+        assert_eq!(db.document_namespace("missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_startup_timings() {
+        let (db, _dir) = create_test_db();
+        let phase = |name: &str, duration_ms| StartupPhase {
+            name: name.to_string(),
+            duration_ms,
+        };
+        let first = db
+            .record_startup_metric(
+                "2026-03-01T09:00:00Z",
+                None,
+                Some(900),
+                Some(900),
+                false,
+                &[phase("db_open", 120)],
+            )
+            .unwrap();
+        let second = db
+            .record_startup_metric(
+                "2026-03-02T09:00:00Z",
+                None,
+                Some(400),
+                Some(400),
+                false,
+                &[phase("keystore", 15), phase("db_open", 80)],
+            )
+            .unwrap();
+
+        assert!(db
+            .add_startup_phase(second, &phase("model_autoload:llm", 2500))
+            .unwrap());
+        // Only the first load after startup counts as the autoload
+        assert!(!db
+            .add_startup_phase(second, &phase("model_autoload:llm", 1800))
+            .unwrap());
+        assert!(!db
+            .add_startup_phase(9999, &phase("vector_init", 5))
+            .unwrap());
+
+        let timings = db.list_startup_timings(10).unwrap();
+        assert_eq!(timings.len(), 2);
+        assert_eq!(timings[0].id, second);
+        assert_eq!(
+            timings[0].phases,
+            vec![
+                phase("keystore", 15),
+                phase("db_open", 80),
+                phase("model_autoload:llm", 2500)
+            ]
+        );
+        assert_eq!(timings[1].id, first);
+        assert_eq!(timings[1].init_app_ms, Some(900));
+        assert_eq!(
+            db.get_last_startup_metric().unwrap(),
+            Some((400, 400, false))
+        );
+    }
+
+    #[test]
+    fn test_metrics_samples() {
+        let (db, _dir) = create_test_db();
+        let now = Utc::now();
+        let sample = |minutes_ago: i64, depth: u32| MetricsSample {
+            sampled_at: now - chrono::Duration::minutes(minutes_ago),
+            memory_bytes: 1024,
+            generation_latency_ms: Some(850.5),
+            search_latency_ms: None,
+            job_queue_depth: depth,
+        };
+
+        db.record_metrics_sample(&sample(METRICS_RETENTION_DAYS * 24 * 60 + 1, 9))
+            .unwrap();
+        db.record_metrics_sample(&sample(90, 1)).unwrap();
+        db.record_metrics_sample(&sample(30, 2)).unwrap();
+        db.record_metrics_sample(&sample(0, 3)).unwrap();
+
+        // The sample past the retention period was pruned
+        let all = db
+            .get_metrics_samples(now - chrono::Duration::days(METRICS_RETENTION_DAYS + 1))
+            .unwrap();
+        assert_eq!(all.len(), 3);
+
+        let recent = db
+            .get_metrics_samples(now - chrono::Duration::hours(1))
+            .unwrap();
+        assert_eq!(
+            recent.iter().map(|s| s.job_queue_depth).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(recent[0].generation_latency_ms, Some(850.5));
+        assert_eq!(recent[0].search_latency_ms, None);
+    }
+
+    #[test]
+    fn test_storage_usage() {
+        let (db, _dir) = create_test_db();
+        db.conn()
+            .execute(
+                "INSERT INTO kb_documents (id, file_path, file_hash, indexed_at) VALUES (?, ?, ?, ?)",
+                params!["doc1", "/test/doc.md", "abc123", "2024-01-01"],
+            )
+            .unwrap();
+        db.conn()
+            .execute(
+                "INSERT INTO kb_chunks (id, document_id, chunk_index, heading_path, content, word_count) VALUES (?, ?, ?, ?, ?, ?)",
+                params!["chunk1", "doc1", 0, "Heading", "0123456789", 1],
+            )
+            .unwrap();
+
+        let namespaces = db.get_namespace_storage().unwrap();
+        let default = namespaces
+            .iter()
+            .find(|n| n.namespace_id == "default")
+            .unwrap();
+        assert_eq!(default.document_count, 1);
+        assert_eq!(default.chunk_count, 1);
+        assert_eq!(default.content_bytes, 10);
+
+        for (i, created_at) in ["2026-01-01", "2026-01-02", "2026-01-03"]
+            .iter()
+            .enumerate()
+        {
+            db.conn()
+                .execute(
+                    "INSERT INTO drafts (id, input_text, response_text, created_at, updated_at, is_autosave)
+                     VALUES (?, 'input', 'reply', ?, ?, 1)",
+                    params![format!("autosave-{}", i), created_at, created_at],
+                )
+                .unwrap();
+        }
+        let usage = db.get_autosave_usage(1).unwrap();
+        assert_eq!(usage.count, 3);
+        assert_eq!(usage.bytes, 30);
+        assert_eq!(usage.reclaimable_count, 2);
+        assert_eq!(usage.reclaimable_bytes, 20);
+    }
 }
@@ -14,6 +14,10 @@ pub struct SystemHealth {
     pub embedding_model: ComponentHealth,
     /// File system health (data directories)
     pub file_system: ComponentHealth,
+    /// Automatic backup status
+    pub backups: ComponentHealth,
+    /// Crash reports from previous sessions
+    pub crash_reports: ComponentHealth,
     /// Overall status (worst of all components)
     pub overall_status: HealthStatus,
     /// Timestamp of this health check
@@ -297,6 +301,81 @@ pub fn check_filesystem_health() -> ComponentHealth {
     }
 }
 
+/// Check automatic backups: warns when the last successful backup is overdue
+pub fn check_backup_health(db: &crate::db::Database) -> ComponentHealth {
+    match crate::backup::backup_schedule_status(db) {
+        Ok(status) => backup_health(status.as_ref()),
+        Err(e) => ComponentHealth::error(
+            "Backups",
+            "Failed to load backup schedule",
+            Some(&e.to_string()),
+            false,
+        ),
+    }
+}
+
+/// Backup health for a loaded schedule status
+pub fn backup_health(status: Option<&crate::backup::BackupScheduleStatus>) -> ComponentHealth {
+    let Some(status) = status else {
+        return ComponentHealth::healthy("Backups", "Automatic backups not configured");
+    };
+    if !status.enabled {
+        return ComponentHealth::healthy("Backups", "Automatic backups disabled");
+    }
+
+    let last = status.last_success_at.map(|t| t.to_rfc3339());
+    if status.overdue {
+        let details = match &last {
+            Some(last) => format!(
+                "Last successful backup: {}. Check the backup folder ({}) and job history.",
+                last, status.folder
+            ),
+            None => format!(
+                "No successful backup yet. Check the backup folder ({}) and job history.",
+                status.folder
+            ),
+        };
+        return ComponentHealth::warning("Backups", "Backup overdue", Some(&details));
+    }
+    match last {
+        Some(last) => ComponentHealth::healthy("Backups", &format!("OK - last backup {}", last)),
+        None => ComponentHealth::healthy("Backups", "Scheduled - no backup yet"),
+    }
+}
+
+/// Check for crash reports the user hasn't reviewed yet
+pub fn check_crash_health() -> ComponentHealth {
+    match crate::crash::list_reports(&crate::db::get_crashes_dir()) {
+        Ok(reports) => crash_health(&reports),
+        Err(e) => ComponentHealth::error(
+            "Crash reports",
+            "Failed to read crash reports",
+            Some(&e.to_string()),
+            false,
+        ),
+    }
+}
+
+/// Crash report health: warns while unreviewed reports exist
+pub fn crash_health(reports: &[crate::crash::CrashReport]) -> ComponentHealth {
+    let unreviewed: Vec<_> = reports.iter().filter(|r| !r.reviewed).collect();
+    match unreviewed.first() {
+        None => ComponentHealth::healthy("Crash reports", "No new crash reports"),
+        Some(latest) => ComponentHealth::warning(
+            "Crash reports",
+            &format!(
+                "{} new crash report(s) from previous sessions",
+                unreviewed.len()
+            ),
+            Some(&format!(
+                "Most recent ({}): {}. Review it to choose whether to include it in support bundles.",
+                latest.created_at.to_rfc3339(),
+                latest.message
+            )),
+        ),
+    }
+}
+
 /// Attempt to repair the database
 pub fn repair_database(db: &crate::db::Database) -> RepairResult {
     // Try VACUUM to repair
@@ -443,6 +522,34 @@ pub fn get_failure_modes() -> Vec<FailureMode> {
             ],
             auto_repair_available: false,
         },
+        FailureMode {
+            id: "backup_overdue".to_string(),
+            problem: "Automatic backups are overdue".to_string(),
+            symptoms: vec![
+                "Diagnostics shows 'Backup overdue'".to_string(),
+                "No new files in the backup folder".to_string(),
+            ],
+            resolution_steps: vec![
+                "Check the backup job's history for errors".to_string(),
+                "Verify the backup folder exists and is writable".to_string(),
+                "Run the backup schedule manually".to_string(),
+            ],
+            auto_repair_available: false,
+        },
+        FailureMode {
+            id: "app_crashed".to_string(),
+            problem: "The app crashed or quit unexpectedly".to_string(),
+            symptoms: vec![
+                "Diagnostics shows new crash reports".to_string(),
+                "The app closed without warning during an operation".to_string(),
+            ],
+            resolution_steps: vec![
+                "Review the crash report in diagnostics".to_string(),
+                "Include it in a diagnostics bundle for support".to_string(),
+                "Check whether the crash repeats with the same model or command".to_string(),
+            ],
+            auto_repair_available: false,
+        },
     ]
 }
 
@@ -728,6 +835,215 @@ pub async fn get_vector_maintenance_info(
     })
 }
 
+/// Log size above which deleting old logs is suggested
+const LOG_CLEANUP_THRESHOLD_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Disk usage of one storage area
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StorageItem {
+    /// Storage area (database, vectors, models, downloads, backups, logs, autosaves)
+    pub name: String,
+    /// Location on disk (None for data stored inside the database)
+    pub path: Option<String>,
+    pub size_bytes: u64,
+    /// Number of files (drafts for autosaves)
+    pub item_count: u64,
+}
+
+/// A cleanup action that would free disk space
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CleanupSuggestion {
+    pub description: String,
+    pub reclaimable_bytes: u64,
+    /// Command that performs the cleanup, if there is one
+    pub command: Option<String>,
+}
+
+/// Disk usage breakdown with per-namespace attribution
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StorageBreakdown {
+    pub items: Vec<StorageItem>,
+    pub namespaces: Vec<crate::db::NamespaceStorage>,
+    pub total_bytes: u64,
+    /// Suggested cleanups, largest first
+    pub suggestions: Vec<CleanupSuggestion>,
+    pub checked_at: String,
+}
+
+/// Space that cleanup could free, gathered while measuring storage
+#[derive(Debug, Clone, Default)]
+pub struct ReclaimableSpace {
+    pub database_bytes: u64,
+    /// Free pages in the database file
+    pub database_free_bytes: u64,
+    pub old_autosaves: u64,
+    pub old_autosave_bytes: u64,
+    pub partial_downloads: u64,
+    pub partial_download_bytes: u64,
+    /// (file name, size) of downloaded models that aren't the selected model
+    pub unused_models: Vec<(String, u64)>,
+    pub log_bytes: u64,
+}
+
+/// Total size and file count of a directory tree (0 if it doesn't exist).
+/// Symlinks are not followed.
+pub fn directory_size(path: &std::path::Path) -> (u64, u64) {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return (0, 0);
+    };
+    let mut size = 0;
+    let mut files = 0;
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.path().symlink_metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            let (dir_size, dir_files) = directory_size(&entry.path());
+            size += dir_size;
+            files += dir_files;
+        } else if metadata.is_file() {
+            size += metadata.len();
+            files += 1;
+        }
+    }
+    (size, files)
+}
+
+fn format_megabytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// Suggest cleanups for the reclaimable space, largest first
+pub fn cleanup_suggestions(space: &ReclaimableSpace) -> Vec<CleanupSuggestion> {
+    let mut suggestions = Vec::new();
+
+    // Same threshold as run_database_maintenance
+    if space.database_bytes > 0 && space.database_free_bytes * 10 >= space.database_bytes {
+        suggestions.push(CleanupSuggestion {
+            description: format!(
+                "Run database maintenance to reclaim {} of free space",
+                format_megabytes(space.database_free_bytes)
+            ),
+            reclaimable_bytes: space.database_free_bytes,
+            command: Some("run_database_maintenance_cmd".to_string()),
+        });
+    }
+    if space.old_autosaves > 0 {
+        suggestions.push(CleanupSuggestion {
+            description: format!("Delete {} old autosaved drafts", space.old_autosaves),
+            reclaimable_bytes: space.old_autosave_bytes,
+            command: Some("cleanup_autosaves".to_string()),
+        });
+    }
+    if space.partial_downloads > 0 {
+        suggestions.push(CleanupSuggestion {
+            description: format!(
+                "Delete {} incomplete model downloads",
+                space.partial_downloads
+            ),
+            reclaimable_bytes: space.partial_download_bytes,
+            command: None,
+        });
+    }
+    for (name, size) in &space.unused_models {
+        suggestions.push(CleanupSuggestion {
+            description: format!("Delete unused model {}", name),
+            reclaimable_bytes: *size,
+            command: Some("delete_downloaded_model".to_string()),
+        });
+    }
+    if space.log_bytes > LOG_CLEANUP_THRESHOLD_BYTES {
+        suggestions.push(CleanupSuggestion {
+            description: format!(
+                "Delete old log files ({} in total)",
+                format_megabytes(space.log_bytes)
+            ),
+            reclaimable_bytes: space.log_bytes,
+            command: None,
+        });
+    }
+
+    suggestions.sort_by_key(|s| std::cmp::Reverse(s.reclaimable_bytes));
+    suggestions
+}
+
+/// Outcome of one step of the performance profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileStepStatus {
+    Ok,
+    /// The component needed for the step is not loaded or enabled
+    Skipped,
+    Failed,
+}
+
+/// Timing for one step of the standard profiling workload
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ProfileStep {
+    /// Step name (fts_search, embed, vector_search, generate, db_write)
+    pub name: String,
+    pub status: ProfileStepStatus,
+    /// Wall-clock time in milliseconds (None when skipped)
+    pub duration_ms: Option<f64>,
+    /// Result size, skip reason or error message
+    pub detail: Option<String>,
+}
+
+impl ProfileStep {
+    pub fn ok(name: &str, elapsed: std::time::Duration, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status: ProfileStepStatus::Ok,
+            duration_ms: Some(elapsed.as_secs_f64() * 1000.0),
+            detail: Some(detail),
+        }
+    }
+
+    pub fn skipped(name: &str, reason: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            status: ProfileStepStatus::Skipped,
+            duration_ms: None,
+            detail: Some(reason.to_string()),
+        }
+    }
+
+    pub fn failed(name: &str, elapsed: std::time::Duration, error: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            status: ProfileStepStatus::Failed,
+            duration_ms: Some(elapsed.as_secs_f64() * 1000.0),
+            detail: Some(error.to_string()),
+        }
+    }
+}
+
+/// Results of one performance profile run
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PerformanceProfile {
+    pub id: String,
+    /// Timestamp of the run (RFC 3339)
+    pub created_at: String,
+    /// App version that ran the profile, to spot regressions after updates
+    pub app_version: String,
+    pub steps: Vec<ProfileStep>,
+    /// Total time of the steps that ran
+    pub total_ms: f64,
+}
+
+impl PerformanceProfile {
+    pub fn new(steps: Vec<ProfileStep>) -> Self {
+        let total_ms = steps.iter().filter_map(|s| s.duration_ms).sum();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            steps,
+            total_ms,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -783,4 +1099,109 @@ mod tests {
         // Should at least not panic
         assert!(!health.name.is_empty());
     }
+
+    #[test]
+    fn test_backup_health() {
+        use crate::backup::{BackupFrequency, BackupScheduleStatus};
+
+        assert_eq!(backup_health(None).status, HealthStatus::Healthy);
+
+        let mut status = BackupScheduleStatus {
+            schedule_id: "schedule".to_string(),
+            enabled: true,
+            frequency: BackupFrequency::Daily,
+            folder: "/Users/me/Backups".to_string(),
+            retention: Some(7),
+            next_run_at: None,
+            last_success_at: Some(chrono::Utc::now()),
+            overdue: false,
+        };
+        let health = backup_health(Some(&status));
+        assert_eq!(health.status, HealthStatus::Healthy);
+        assert!(health.message.starts_with("OK - last backup"));
+
+        status.overdue = true;
+        let health = backup_health(Some(&status));
+        assert_eq!(health.status, HealthStatus::Warning);
+        assert!(health.details.unwrap().contains("/Users/me/Backups"));
+
+        status.enabled = false;
+        assert_eq!(backup_health(Some(&status)).status, HealthStatus::Healthy);
+    }
+
+    #[test]
+    fn test_crash_health() {
+        use crate::crash::{CrashKind, CrashReport};
+
+        assert_eq!(crash_health(&[]).status, HealthStatus::Healthy);
+
+        let mut report = CrashReport::new(CrashKind::Panic, "index out of bounds".to_string());
+        let health = crash_health(std::slice::from_ref(&report));
+        assert_eq!(health.status, HealthStatus::Warning);
+        assert!(health.details.unwrap().contains("index out of bounds"));
+
+        report.reviewed = true;
+        assert_eq!(crash_health(&[report]).status, HealthStatus::Healthy);
+    }
+
+    #[test]
+    fn test_performance_profile_total() {
+        let profile = PerformanceProfile::new(vec![
+            ProfileStep::ok(
+                "fts_search",
+                std::time::Duration::from_millis(12),
+                "3 results".into(),
+            ),
+            ProfileStep::skipped("generate", "No model loaded"),
+            ProfileStep::failed("db_write", std::time::Duration::from_millis(3), "locked"),
+        ]);
+        assert!((profile.total_ms - 15.0).abs() < 1e-9);
+        assert_eq!(profile.steps[1].status, ProfileStepStatus::Skipped);
+        assert_eq!(profile.steps[1].duration_ms, None);
+    }
+
+    #[test]
+    fn test_cleanup_suggestions() {
+        assert!(cleanup_suggestions(&ReclaimableSpace::default()).is_empty());
+
+        let space = ReclaimableSpace {
+            database_bytes: 100 * 1024 * 1024,
+            database_free_bytes: 30 * 1024 * 1024,
+            old_autosaves: 12,
+            old_autosave_bytes: 40_000,
+            unused_models: vec![("old-model.gguf".to_string(), 4 * 1024 * 1024 * 1024)],
+            log_bytes: 1024,
+            ..Default::default()
+        };
+        let suggestions = cleanup_suggestions(&space);
+        assert_eq!(suggestions.len(), 3);
+        assert_eq!(
+            suggestions[0].command.as_deref(),
+            Some("delete_downloaded_model")
+        );
+        assert_eq!(
+            suggestions[1].command.as_deref(),
+            Some("run_database_maintenance_cmd")
+        );
+        assert_eq!(suggestions[2].reclaimable_bytes, 40_000);
+
+        // A lightly fragmented database needs no maintenance
+        let space = ReclaimableSpace {
+            database_bytes: 100,
+            database_free_bytes: 5,
+            ..Default::default()
+        };
+        assert!(cleanup_suggestions(&space).is_empty());
+    }
+
+    #[test]
+    fn test_directory_size() {
+        let dir = std::env::temp_dir().join(format!("assistsupport-du-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::write(dir.join("a.bin"), [0u8; 10]).unwrap();
+        std::fs::write(dir.join("nested").join("b.bin"), [0u8; 5]).unwrap();
+        assert_eq!(directory_size(&dir), (15, 2));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(directory_size(&dir), (0, 0));
+    }
 }
@@ -1,15 +1,35 @@
 //! Model download manager for AssistSupport
 //! Supports HuggingFace downloads with resume, progress, and checksum verification
+//!
+//! Large files are fetched over several connections at once: the file is split
+//! into fixed-size chunks, each written to its own part file and hashed as it
+//! arrives. Completed chunks are recorded with their SHA-256 in a manifest so an
+//! interrupted download resumes without refetching verified chunks, and the
+//! parts are assembled in order before the whole-file checksum is checked.
+//!
+//! Network settings are stored as JSON in `integration_configs` under
+//! `downloads`: an optional HTTP(S)/SOCKS proxy used for all download traffic
+//! (its password lives in the encrypted token store as
+//! `download_proxy_password`) and alternate mirrors per model, tried when
+//! huggingface.co fails. The HuggingFace token is never sent to mirrors.
+//! The same settings can cap download bandwidth and hold downloads while a
+//! response is being generated, so a model download doesn't saturate the
+//! link agents are working over.
 
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::sync::mpsc;
 
-use crate::security::{FileKeyStore, TOKEN_HUGGINGFACE};
+use crate::jobs::retry::{retry_async, RetryPolicy};
+use crate::security::{vault::Vault, TOKEN_DOWNLOAD_PROXY_PASSWORD, TOKEN_HUGGINGFACE};
 
 #[derive(Debug, Error)]
 pub enum DownloadError {
@@ -27,6 +47,8 @@ pub enum DownloadError {
     HuggingFaceApi(String),
     #[error("File not found: {0}")]
     FileNotFound(String),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
 }
 
 /// Download progress event
@@ -54,6 +76,202 @@ pub enum DownloadProgress {
 const CONNECT_TIMEOUT_SECS: u64 = 20;
 const DOWNLOAD_TIMEOUT_SECS: u64 = 4 * 60 * 60;
 
+/// Default number of parallel connections
+pub const DEFAULT_CONNECTIONS: usize = 4;
+/// Upper bound on parallel connections
+pub const MAX_CONNECTIONS: usize = 16;
+/// Size of each chunk in a parallel download
+const CHUNK_SIZE_BYTES: u64 = 32 * 1024 * 1024;
+/// Records completed chunks and their checksums inside the parts folder
+const CHUNK_MANIFEST_FILE: &str = "manifest.json";
+
+/// Integration type key in `integration_configs`
+pub const DOWNLOADS_INTEGRATION: &str = "downloads";
+
+/// Lowest accepted bandwidth limit
+pub const MIN_BYTES_PER_SEC: u64 = 16 * 1024;
+/// How often a paused download checks whether generation has finished
+const PAUSE_POLL_MS: u64 = 250;
+
+/// Proxy for download traffic
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxyConfig {
+    /// `http://`, `https://`, `socks5://` or `socks5h://` proxy URL
+    pub url: String,
+    /// Login name; the password is kept in the token store
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Comma-separated hosts that bypass the proxy
+    #[serde(default)]
+    pub no_proxy: Option<String>,
+}
+
+impl ProxyConfig {
+    fn to_reqwest(&self) -> Result<reqwest::Proxy, DownloadError> {
+        let mut proxy = reqwest::Proxy::all(self.url.trim())
+            .map_err(|e| DownloadError::InvalidUrl(format!("Proxy: {}", e)))?;
+        if let Some(username) = &self.username {
+            let password = Vault::get(TOKEN_DOWNLOAD_PROXY_PASSWORD)
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            proxy = proxy.basic_auth(username, &password);
+        }
+        if let Some(no_proxy) = &self.no_proxy {
+            proxy = proxy.no_proxy(reqwest::NoProxy::from_string(no_proxy));
+        }
+        Ok(proxy)
+    }
+}
+
+/// Download network settings (stored as JSON in `integration_configs.config_json`, no secrets)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadNetworkConfig {
+    #[serde(default)]
+    pub proxy: Option<ProxyConfig>,
+    /// Alternate sources per model ID. Each entry is either a base URL laid out
+    /// like huggingface.co (`https://hf-mirror.com`) or a URL containing
+    /// `{repo}` and/or `{filename}` placeholders.
+    #[serde(default)]
+    pub mirrors: BTreeMap<String, Vec<String>>,
+    /// Try mirrors before huggingface.co (for networks that block it outright)
+    #[serde(default)]
+    pub prefer_mirrors: bool,
+    /// Bandwidth limit across all connections, in bytes per second
+    #[serde(default)]
+    pub max_bytes_per_sec: Option<u64>,
+    /// Hold downloads while a response is being generated
+    #[serde(default)]
+    pub pause_during_generation: bool,
+}
+
+impl DownloadNetworkConfig {
+    pub fn validate(&self) -> Result<(), DownloadError> {
+        if self
+            .max_bytes_per_sec
+            .is_some_and(|rate| rate < MIN_BYTES_PER_SEC)
+        {
+            return Err(DownloadError::InvalidConfig(format!(
+                "Bandwidth limit must be at least {} KB/s",
+                MIN_BYTES_PER_SEC / 1024
+            )));
+        }
+        if let Some(proxy) = &self.proxy {
+            let url = reqwest::Url::parse(proxy.url.trim())
+                .map_err(|e| DownloadError::InvalidUrl(format!("Proxy: {}", e)))?;
+            if !matches!(url.scheme(), "http" | "https" | "socks5" | "socks5h") {
+                return Err(DownloadError::InvalidUrl(format!(
+                    "Unsupported proxy scheme: {}",
+                    url.scheme()
+                )));
+            }
+            if url.host_str().is_none() {
+                return Err(DownloadError::InvalidUrl("Proxy URL has no host".into()));
+            }
+            if proxy
+                .username
+                .as_deref()
+                .is_some_and(|u| u.trim().is_empty())
+            {
+                return Err(DownloadError::InvalidUrl("Proxy username is empty".into()));
+            }
+        }
+        for mirror in self.mirrors.values().flatten() {
+            let url = reqwest::Url::parse(&mirror_url(mirror, "repo", "file"))
+                .map_err(|e| DownloadError::InvalidUrl(format!("Mirror {}: {}", mirror, e)))?;
+            if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+                return Err(DownloadError::InvalidUrl(format!(
+                    "Mirror must be an http(s) URL: {}",
+                    mirror
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Mirrors configured for a model
+    pub fn mirrors_for(&self, model_id: &str) -> Vec<String> {
+        self.mirrors.get(model_id).cloned().unwrap_or_default()
+    }
+}
+
+/// Paces download traffic: a byte-rate limit shared by all connections of a
+/// download, and optionally waiting while a response is being generated
+pub struct Throttle {
+    bytes_per_sec: Option<u64>,
+    pause_during_generation: bool,
+    /// Available bytes (negative when in debt) and when it was last refilled
+    bucket: std::sync::Mutex<(f64, Instant)>,
+}
+
+impl Throttle {
+    pub fn new(bytes_per_sec: Option<u64>, pause_during_generation: bool) -> Self {
+        let bytes_per_sec = bytes_per_sec.filter(|rate| *rate > 0);
+        Self {
+            bytes_per_sec,
+            pause_during_generation,
+            bucket: std::sync::Mutex::new((bytes_per_sec.unwrap_or(0) as f64, Instant::now())),
+        }
+    }
+
+    /// No limit and no pausing
+    pub fn unlimited() -> Self {
+        Self::new(None, false)
+    }
+
+    /// Take `bytes` from the bucket at `now` and return how long to wait
+    /// before using them. The bucket holds up to one second of traffic and
+    /// goes into debt so concurrent connections queue behind each other.
+    fn reserve_at(&self, bytes: u64, now: Instant) -> Duration {
+        let Some(rate) = self.bytes_per_sec else {
+            return Duration::ZERO;
+        };
+        let rate = rate as f64;
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let (available, refilled_at) = &mut *bucket;
+        let elapsed = now.saturating_duration_since(*refilled_at).as_secs_f64();
+        *available = (*available + elapsed * rate).min(rate) - bytes as f64;
+        *refilled_at = now;
+        if *available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-*available / rate)
+        }
+    }
+
+    /// Wait until `bytes` just received may be followed by more: first while
+    /// generation is running (if enabled), then for the rate limit
+    pub async fn consume(&self, bytes: u64, cancel_flag: &AtomicBool) {
+        while self.pause_during_generation
+            && crate::llm::generation_in_progress()
+            && !cancel_flag.load(Ordering::Relaxed)
+        {
+            tokio::time::sleep(Duration::from_millis(PAUSE_POLL_MS)).await;
+        }
+        let wait = self.reserve_at(bytes, Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Resolve a mirror entry to the download URL of `repo`/`filename`
+pub fn mirror_url(mirror: &str, repo: &str, filename: &str) -> String {
+    let mirror = mirror.trim();
+    if mirror.contains("{repo}") || mirror.contains("{filename}") {
+        mirror
+            .replace("{repo}", repo)
+            .replace("{filename}", filename)
+    } else {
+        format!(
+            "{}/{}/resolve/main/{}",
+            mirror.trim_end_matches('/'),
+            repo,
+            filename
+        )
+    }
+}
+
 /// Model source information
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ModelSource {
@@ -62,6 +280,9 @@ pub struct ModelSource {
     pub filename: String,
     pub size_bytes: Option<u64>,
     pub sha256: Option<String>,
+    /// Alternate sources (see [`DownloadNetworkConfig::mirrors`])
+    #[serde(default)]
+    pub mirrors: Vec<String>,
 }
 
 impl ModelSource {
@@ -73,6 +294,7 @@ impl ModelSource {
             filename: filename.to_string(),
             size_bytes: None,
             sha256: None,
+            mirrors: Vec::new(),
         }
     }
 
@@ -83,12 +305,101 @@ impl ModelSource {
             self.repo, self.filename
         )
     }
+
+    /// URLs to try in order: huggingface.co then mirrors, or mirrors first
+    pub fn download_urls(&self, prefer_mirrors: bool) -> Vec<String> {
+        let mirrors = self
+            .mirrors
+            .iter()
+            .map(|mirror| mirror_url(mirror, &self.repo, &self.filename));
+        let primary = std::iter::once(self.download_url());
+        let mut urls: Vec<String> = if prefer_mirrors {
+            mirrors.chain(primary).collect()
+        } else {
+            primary.chain(mirrors).collect()
+        };
+        let mut seen = std::collections::HashSet::new();
+        urls.retain(|url| seen.insert(url.clone()));
+        urls
+    }
+}
+
+/// Byte range of one chunk in a parallel download (`end` is inclusive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkRange {
+    pub index: usize,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ChunkRange {
+    /// Number of bytes in the chunk
+    pub fn size(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+/// Split `total` bytes into consecutive chunks of at most `chunk_size` bytes
+pub fn plan_chunks(total: u64, chunk_size: u64) -> Vec<ChunkRange> {
+    let chunk_size = chunk_size.max(1);
+    (0..total.div_ceil(chunk_size))
+        .map(|i| {
+            let start = i * chunk_size;
+            ChunkRange {
+                index: i as usize,
+                start,
+                end: (start + chunk_size).min(total) - 1,
+            }
+        })
+        .collect()
+}
+
+/// Completed chunks of an in-progress parallel download
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ChunkManifest {
+    /// Expected SHA-256 of the whole file, or the URL when unknown, so chunks
+    /// fetched from one mirror are reused when resuming from another
+    file_key: String,
+    total_bytes: u64,
+    chunk_size: u64,
+    /// Chunk index -> SHA-256 of the chunk's bytes
+    chunks: BTreeMap<usize, String>,
+}
+
+impl ChunkManifest {
+    fn new(file_key: &str, total_bytes: u64, chunk_size: u64) -> Self {
+        Self {
+            file_key: file_key.to_string(),
+            total_bytes,
+            chunk_size,
+            chunks: BTreeMap::new(),
+        }
+    }
+
+    /// Load a manifest for the same download, if one exists
+    fn load(path: &Path, file_key: &str, total_bytes: u64, chunk_size: u64) -> Option<Self> {
+        let json = std::fs::read_to_string(path).ok()?;
+        let manifest: Self = serde_json::from_str(&json).ok()?;
+        (manifest.file_key == file_key
+            && manifest.total_bytes == total_bytes
+            && manifest.chunk_size == chunk_size)
+            .then_some(manifest)
+    }
+
+    fn save(&self, path: &Path) -> Result<(), DownloadError> {
+        let json = serde_json::to_string(self).map_err(|e| DownloadError::Io(e.into()))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
 }
 
 /// Download manager
 pub struct DownloadManager {
     models_dir: PathBuf,
     downloads_dir: PathBuf,
+    connections: usize,
+    network: DownloadNetworkConfig,
+    throttle: Throttle,
 }
 
 impl DownloadManager {
@@ -97,9 +408,25 @@ impl DownloadManager {
         Self {
             models_dir: app_data_dir.join("models"),
             downloads_dir: app_data_dir.join("downloads"),
+            connections: DEFAULT_CONNECTIONS,
+            network: DownloadNetworkConfig::default(),
+            throttle: Throttle::unlimited(),
         }
     }
 
+    /// Use the configured proxy, mirror preference and bandwidth settings
+    pub fn with_network(mut self, network: DownloadNetworkConfig) -> Self {
+        self.throttle = Throttle::new(network.max_bytes_per_sec, network.pause_during_generation);
+        self.network = network;
+        self
+    }
+
+    /// Set the number of parallel connections (1 disables chunked downloads)
+    pub fn with_connections(mut self, connections: usize) -> Self {
+        self.connections = connections.clamp(1, MAX_CONNECTIONS);
+        self
+    }
+
     /// Ensure directories exist
     pub fn init(&self) -> Result<(), DownloadError> {
         std::fs::create_dir_all(&self.models_dir)?;
@@ -127,42 +454,99 @@ impl DownloadManager {
         Ok(models)
     }
 
-    /// Download a model with progress reporting
+    /// Download a model with progress reporting.
+    ///
+    /// Tries huggingface.co and the source's mirrors in turn until one
+    /// succeeds; only the last failure is reported as an error event.
     pub async fn download(
         &self,
         source: &ModelSource,
         progress_tx: mpsc::Sender<DownloadProgress>,
-        cancel_flag: std::sync::Arc<std::sync::atomic::AtomicBool>,
+        cancel_flag: std::sync::Arc<AtomicBool>,
     ) -> Result<PathBuf, DownloadError> {
-        use std::sync::atomic::Ordering;
-
         self.init()?;
 
-        let url = source.download_url();
-        let dest_path = self.models_dir.join(&source.filename);
-        let partial_path = self
-            .downloads_dir
-            .join(format!("{}.partial", source.filename));
+        let proxy = self.network.proxy.as_ref();
+        let hf_client = build_client(proxy, true)?;
+        let mirror_client = build_client(proxy, false)?;
+        let primary = source.download_url();
+        let client_for = |url: &str| {
+            if url == primary {
+                &hf_client
+            } else {
+                &mirror_client
+            }
+        };
 
-        // Get HuggingFace token from file-based storage (optional)
-        let hf_token = FileKeyStore::get_token(TOKEN_HUGGINGFACE).ok().flatten();
+        let urls = source.download_urls(self.network.prefer_mirrors);
+        let Some((last, earlier)) = urls.split_last() else {
+            return Err(DownloadError::InvalidUrl("No download source".into()));
+        };
 
-        // Build HTTP client
-        let mut headers = reqwest::header::HeaderMap::new();
-        if let Some(token) = &hf_token {
-            headers.insert(
-                reqwest::header::AUTHORIZATION,
-                reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
-                    .map_err(|e| DownloadError::Network(e.to_string()))?,
+        for url in earlier {
+            // Hold back error events so the UI doesn't treat a fallback as failure
+            let (attempt_tx, mut attempt_rx) = mpsc::channel(100);
+            let forward = async {
+                while let Some(event) = attempt_rx.recv().await {
+                    if !matches!(event, DownloadProgress::Error { .. }) {
+                        let _ = progress_tx.send(event).await;
+                    }
+                }
+            };
+            let (result, ()) = tokio::join!(
+                self.download_from(source, client_for(url), url, attempt_tx, &cancel_flag),
+                forward
             );
+            match result {
+                Err(e) if !matches!(e, DownloadError::Cancelled | DownloadError::Io(_)) => {
+                    tracing::warn!("Download from {} failed, trying next source: {}", url, e);
+                }
+                result => return result,
+            }
         }
 
-        let client = reqwest::Client::builder()
-            .default_headers(headers)
-            .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
-            .timeout(Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
-            .build()
-            .map_err(|e| DownloadError::Network(e.to_string()))?;
+        self.download_from(source, client_for(last), last, progress_tx, &cancel_flag)
+            .await
+    }
+
+    /// Download from one URL. Uses parallel chunked downloads when more than
+    /// one connection is configured and the server supports range requests,
+    /// otherwise a single resumable stream.
+    async fn download_from(
+        &self,
+        source: &ModelSource,
+        client: &reqwest::Client,
+        url: &str,
+        progress_tx: mpsc::Sender<DownloadProgress>,
+        cancel_flag: &AtomicBool,
+    ) -> Result<PathBuf, DownloadError> {
+        if self.connections > 1 {
+            if let Some(total) = probe_range_support(client, url, source.size_bytes).await {
+                if total > CHUNK_SIZE_BYTES {
+                    return self
+                        .download_parallel(source, client, url, total, progress_tx, cancel_flag)
+                        .await;
+                }
+            }
+        }
+
+        self.download_single(source, client, url, progress_tx, cancel_flag)
+            .await
+    }
+
+    /// Download over a single connection, resuming a previous `.partial` file
+    async fn download_single(
+        &self,
+        source: &ModelSource,
+        client: &reqwest::Client,
+        url: &str,
+        progress_tx: mpsc::Sender<DownloadProgress>,
+        cancel_flag: &AtomicBool,
+    ) -> Result<PathBuf, DownloadError> {
+        let dest_path = self.models_dir.join(&source.filename);
+        let partial_path = self
+            .downloads_dir
+            .join(format!("{}.partial", source.filename));
 
         // Check for existing partial download
         let mut resume_from = if partial_path.exists() {
@@ -172,7 +556,7 @@ impl DownloadManager {
         };
 
         // Start download with range request for resume
-        let mut request = client.get(&url);
+        let mut request = client.get(url);
         if resume_from > 0 {
             request = request.header("Range", format!("bytes={}-", resume_from));
         }
@@ -228,7 +612,7 @@ impl DownloadManager {
 
         let _ = progress_tx
             .send(DownloadProgress::Started {
-                url: url.clone(),
+                url: url.to_string(),
                 total_bytes,
             })
             .await;
@@ -247,7 +631,6 @@ impl DownloadManager {
         let mut last_downloaded = downloaded;
 
         let mut stream = response.bytes_stream();
-        use futures::StreamExt;
 
         while let Some(chunk) = stream.next().await {
             // Check cancellation
@@ -281,6 +664,7 @@ impl DownloadManager {
                 return Err(DownloadError::Io(e));
             }
             downloaded += chunk.len() as u64;
+            self.throttle.consume(chunk.len() as u64, cancel_flag).await;
 
             // Report progress every 100ms
             let now = std::time::Instant::now();
@@ -365,6 +749,176 @@ impl DownloadManager {
         Ok(dest_path)
     }
 
+    /// Download `total` bytes as chunks over up to `self.connections`
+    /// connections. Chunks already recorded in the manifest are kept if their
+    /// part file still matches its checksum. Parts are kept after a failure so
+    /// the next attempt resumes, and removed on cancel or checksum mismatch.
+    async fn download_parallel(
+        &self,
+        source: &ModelSource,
+        client: &reqwest::Client,
+        url: &str,
+        total: u64,
+        progress_tx: mpsc::Sender<DownloadProgress>,
+        cancel_flag: &AtomicBool,
+    ) -> Result<PathBuf, DownloadError> {
+        let dest_path = self.models_dir.join(&source.filename);
+        let partial_path = self
+            .downloads_dir
+            .join(format!("{}.partial", source.filename));
+        let parts_dir = self
+            .downloads_dir
+            .join(format!("{}.parts", source.filename));
+        std::fs::create_dir_all(&parts_dir)?;
+
+        let chunks = plan_chunks(total, CHUNK_SIZE_BYTES);
+        let manifest_path = parts_dir.join(CHUNK_MANIFEST_FILE);
+        let file_key = source.sha256.as_deref().unwrap_or(url).to_lowercase();
+        let mut manifest = ChunkManifest::load(&manifest_path, &file_key, total, CHUNK_SIZE_BYTES)
+            .unwrap_or_else(|| ChunkManifest::new(&file_key, total, CHUNK_SIZE_BYTES));
+        manifest.chunks.retain(|index, sha256| {
+            chunks.get(*index).is_some_and(|chunk| {
+                verify_chunk(&chunk_path(&parts_dir, *index), chunk.size(), sha256)
+            })
+        });
+
+        let pending: Vec<ChunkRange> = chunks
+            .iter()
+            .filter(|chunk| !manifest.chunks.contains_key(&chunk.index))
+            .copied()
+            .collect();
+        let downloaded = AtomicU64::new(total - pending.iter().map(ChunkRange::size).sum::<u64>());
+
+        let _ = progress_tx
+            .send(DownloadProgress::Started {
+                url: url.to_string(),
+                total_bytes: Some(total),
+            })
+            .await;
+
+        let connections = self.connections.min(pending.len()).max(1);
+        let (parts, counter, throttle) = (&parts_dir, &downloaded, &self.throttle);
+        let mut fetches = futures::stream::iter(pending.into_iter().map(move |chunk| async move {
+            let sha256 = fetch_chunk(
+                client,
+                url,
+                &chunk_path(parts, chunk.index),
+                chunk,
+                counter,
+                throttle,
+                cancel_flag,
+            )
+            .await?;
+            Ok::<_, DownloadError>((chunk.index, sha256))
+        }))
+        .buffer_unordered(connections);
+
+        let mut ticker = tokio::time::interval(Duration::from_millis(100));
+        let mut last_progress_time = std::time::Instant::now();
+        let mut last_downloaded = downloaded.load(Ordering::Relaxed);
+        let outcome = loop {
+            tokio::select! {
+                next = fetches.next() => match next {
+                    Some(Ok((index, sha256))) => {
+                        manifest.chunks.insert(index, sha256);
+                        if let Err(e) = manifest.save(&manifest_path) {
+                            break Err(e);
+                        }
+                    }
+                    Some(Err(e)) => break Err(e),
+                    None => break Ok(()),
+                },
+                _ = ticker.tick() => {
+                    let now = std::time::Instant::now();
+                    let current = downloaded.load(Ordering::Relaxed);
+                    let elapsed_secs = now.duration_since(last_progress_time).as_secs_f64();
+                    let speed_bps = if elapsed_secs > 0.0 {
+                        (current.saturating_sub(last_downloaded) as f64 / elapsed_secs) as u64
+                    } else {
+                        0
+                    };
+                    let _ = progress_tx
+                        .send(DownloadProgress::Progress {
+                            downloaded: current,
+                            total: Some(total),
+                            speed_bps,
+                        })
+                        .await;
+                    last_progress_time = now;
+                    last_downloaded = current;
+                }
+            }
+        };
+        drop(fetches);
+
+        match outcome {
+            Ok(()) => {}
+            Err(DownloadError::Cancelled) => {
+                let _ = progress_tx.send(DownloadProgress::Cancelled).await;
+                let _ = std::fs::remove_dir_all(&parts_dir);
+                return Err(DownloadError::Cancelled);
+            }
+            Err(e) => {
+                let _ = progress_tx
+                    .send(DownloadProgress::Error {
+                        message: e.to_string(),
+                    })
+                    .await;
+                return Err(e);
+            }
+        }
+
+        // Join the parts in order, hashing the whole file as it is written
+        let sha256 = match assemble_chunks(&parts_dir, &chunks, &partial_path) {
+            Ok(hash) => hash,
+            Err(e) => {
+                let _ = progress_tx
+                    .send(DownloadProgress::Error {
+                        message: "Failed to assemble download".to_string(),
+                    })
+                    .await;
+                cleanup_partial_file(&partial_path);
+                return Err(e);
+            }
+        };
+
+        if let Some(expected) = &source.sha256 {
+            if sha256.to_lowercase() != expected.to_lowercase() {
+                let _ = progress_tx
+                    .send(DownloadProgress::Error {
+                        message: "Checksum mismatch".to_string(),
+                    })
+                    .await;
+                cleanup_partial_file(&partial_path);
+                let _ = std::fs::remove_dir_all(&parts_dir);
+                return Err(DownloadError::ChecksumMismatch {
+                    expected: expected.clone(),
+                    actual: sha256,
+                });
+            }
+        }
+
+        if let Err(e) = std::fs::rename(&partial_path, &dest_path) {
+            let _ = progress_tx
+                .send(DownloadProgress::Error {
+                    message: "Failed to finalize download".to_string(),
+                })
+                .await;
+            cleanup_partial_file(&partial_path);
+            return Err(DownloadError::Io(e));
+        }
+        let _ = std::fs::remove_dir_all(&parts_dir);
+
+        let _ = progress_tx
+            .send(DownloadProgress::Completed {
+                path: dest_path.clone(),
+                sha256: sha256.clone(),
+            })
+            .await;
+
+        Ok(dest_path)
+    }
+
     /// Calculate SHA256 checksum of a file
     pub fn calculate_sha256(&self, path: &Path) -> Result<String, DownloadError> {
         let mut file = File::open(path)?;
@@ -391,14 +945,16 @@ impl DownloadManager {
         Ok(())
     }
 
-    /// Clean up partial downloads
+    /// Clean up partial downloads and chunk folders
     pub fn cleanup_partial(&self) -> Result<(), DownloadError> {
         if self.downloads_dir.exists() {
             for entry in std::fs::read_dir(&self.downloads_dir)? {
                 let entry = entry?;
                 let path = entry.path();
-                if path.extension().and_then(|e| e.to_str()) == Some("partial") {
-                    std::fs::remove_file(&path)?;
+                match path.extension().and_then(|e| e.to_str()) {
+                    Some("partial") => std::fs::remove_file(&path)?,
+                    Some("parts") if path.is_dir() => std::fs::remove_dir_all(&path)?,
+                    _ => {}
                 }
             }
         }
@@ -412,18 +968,255 @@ fn cleanup_partial_file(path: &Path) {
     }
 }
 
+/// HTTP client for model downloads through the configured proxy. With
+/// `hf_auth`, requests carry the HuggingFace token if one is set.
+fn build_client(
+    proxy: Option<&ProxyConfig>,
+    hf_auth: bool,
+) -> Result<reqwest::Client, DownloadError> {
+    // Get HuggingFace token from file-based storage (optional)
+    let hf_token = if hf_auth {
+        Vault::get(TOKEN_HUGGINGFACE).ok().flatten()
+    } else {
+        None
+    };
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Some(token) = &hf_token {
+        headers.insert(
+            reqwest::header::AUTHORIZATION,
+            reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+                .map_err(|e| DownloadError::Network(e.to_string()))?,
+        );
+    }
+
+    let mut builder = reqwest::Client::builder()
+        .default_headers(headers)
+        .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+        .timeout(Duration::from_secs(DOWNLOAD_TIMEOUT_SECS));
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(proxy.to_reqwest()?);
+    }
+    builder
+        .build()
+        .map_err(|e| DownloadError::Network(e.to_string()))
+}
+
+/// Total size of `url` if the server honours range requests. Returns `None`
+/// (fall back to a single stream) on any error or if the size disagrees with
+/// the expected size.
+async fn probe_range_support(
+    client: &reqwest::Client,
+    url: &str,
+    expected_size: Option<u64>,
+) -> Option<u64> {
+    let response = client
+        .get(url)
+        .header(reqwest::header::RANGE, "bytes=0-0")
+        .send()
+        .await
+        .ok()?;
+    if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+        return None;
+    }
+    let total = response
+        .headers()
+        .get(reqwest::header::CONTENT_RANGE)?
+        .to_str()
+        .ok()
+        .and_then(parse_content_range_total)?;
+    match expected_size {
+        Some(expected) if expected != total => None,
+        _ => Some(total),
+    }
+}
+
+/// Total length from a `Content-Range: bytes 0-0/1234` header
+fn parse_content_range_total(value: &str) -> Option<u64> {
+    value
+        .strip_prefix("bytes ")?
+        .rsplit_once('/')?
+        .1
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn chunk_path(parts_dir: &Path, index: usize) -> PathBuf {
+    parts_dir.join(format!("{:05}.part", index))
+}
+
+/// Whether a part file has the expected size and checksum
+fn verify_chunk(path: &Path, size: u64, sha256: &str) -> bool {
+    let Ok(mut file) = File::open(path) else {
+        return false;
+    };
+    if file.metadata().map(|m| m.len()).ok() != Some(size) {
+        return false;
+    }
+    let mut hasher = Sha256::new();
+    if std::io::copy(&mut file, &mut hasher).is_err() {
+        return false;
+    }
+    format!("{:x}", hasher.finalize()) == sha256
+}
+
+/// Whether a chunk failure is worth retrying (network errors other than 4xx)
+fn is_retryable_chunk_error(error: &DownloadError) -> bool {
+    match error {
+        DownloadError::Network(message) => {
+            !message.starts_with("HTTP 4") || message.starts_with("HTTP 429")
+        }
+        _ => false,
+    }
+}
+
+/// Fetch one chunk into its part file, retrying transient failures with
+/// backoff. Returns the SHA-256 of the chunk's bytes.
+async fn fetch_chunk(
+    client: &reqwest::Client,
+    url: &str,
+    path: &Path,
+    chunk: ChunkRange,
+    downloaded: &AtomicU64,
+    throttle: &Throttle,
+    cancel_flag: &AtomicBool,
+) -> Result<String, DownloadError> {
+    retry_async(
+        &RetryPolicy::network(),
+        is_retryable_chunk_error,
+        |_, _, _| !cancel_flag.load(Ordering::Relaxed),
+        || fetch_chunk_once(client, url, path, chunk, downloaded, throttle, cancel_flag),
+    )
+    .await
+}
+
+/// Single attempt at a chunk. Bytes counted towards `downloaded` are taken
+/// back out if the attempt fails, so retries don't inflate progress.
+async fn fetch_chunk_once(
+    client: &reqwest::Client,
+    url: &str,
+    path: &Path,
+    chunk: ChunkRange,
+    downloaded: &AtomicU64,
+    throttle: &Throttle,
+    cancel_flag: &AtomicBool,
+) -> Result<String, DownloadError> {
+    let mut written = 0u64;
+    let result = async {
+        let response = client
+            .get(url)
+            .header(
+                reqwest::header::RANGE,
+                format!("bytes={}-{}", chunk.start, chunk.end),
+            )
+            .send()
+            .await
+            .map_err(|e| DownloadError::Network(format!("Network error: {}", e)))?;
+        if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            return Err(DownloadError::Network(format!(
+                "HTTP {} for chunk {}",
+                response.status(),
+                chunk.index
+            )));
+        }
+
+        let mut file = File::create(path)?;
+        let mut hasher = Sha256::new();
+        let mut stream = response.bytes_stream();
+        while let Some(data) = stream.next().await {
+            if cancel_flag.load(Ordering::Relaxed) {
+                return Err(DownloadError::Cancelled);
+            }
+            let data = data.map_err(|e| DownloadError::Network(format!("Network error: {}", e)))?;
+            if written + data.len() as u64 > chunk.size() {
+                return Err(DownloadError::Network(format!(
+                    "Chunk {} overran its range",
+                    chunk.index
+                )));
+            }
+            file.write_all(&data)?;
+            hasher.update(&data);
+            written += data.len() as u64;
+            downloaded.fetch_add(data.len() as u64, Ordering::Relaxed);
+            throttle.consume(data.len() as u64, cancel_flag).await;
+        }
+        if written != chunk.size() {
+            return Err(DownloadError::Network(format!(
+                "Chunk {} ended early ({} of {} bytes)",
+                chunk.index,
+                written,
+                chunk.size()
+            )));
+        }
+        file.sync_all()?;
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+    .await;
+
+    if result.is_err() {
+        downloaded.fetch_sub(written, Ordering::Relaxed);
+    }
+    result
+}
+
+/// Concatenate the part files in chunk order into `out_path`, returning the
+/// SHA-256 of the assembled file
+fn assemble_chunks(
+    parts_dir: &Path,
+    chunks: &[ChunkRange],
+    out_path: &Path,
+) -> Result<String, DownloadError> {
+    let mut out = File::create(out_path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+
+    for chunk in chunks {
+        let mut part = File::open(chunk_path(parts_dir, chunk.index))?;
+        let mut copied = 0u64;
+        loop {
+            let n = part.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            out.write_all(&buffer[..n])?;
+            hasher.update(&buffer[..n]);
+            copied += n as u64;
+        }
+        if copied != chunk.size() {
+            return Err(DownloadError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!(
+                    "Chunk {} is incomplete ({} of {} bytes)",
+                    chunk.index,
+                    copied,
+                    chunk.size()
+                ),
+            )));
+        }
+    }
+    out.sync_all()?;
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
 /// Fetch file info from HuggingFace API to get SHA256 checksum
 /// Returns (size_bytes, sha256) if successful
 pub async fn fetch_hf_file_info(
     repo: &str,
     filename: &str,
+    proxy: Option<&ProxyConfig>,
 ) -> Result<(u64, String), DownloadError> {
     // HuggingFace API endpoint for file metadata
     let url = format!("https://huggingface.co/api/models/{}/tree/main", repo);
 
-    let client = reqwest::Client::builder()
+    let mut builder = reqwest::Client::builder()
         .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
-        .timeout(Duration::from_secs(30))
+        .timeout(Duration::from_secs(30));
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(proxy.to_reqwest()?);
+    }
+    let client = builder
         .build()
         .map_err(|e| DownloadError::Network(e.to_string()))?;
     let response = client
@@ -490,6 +1283,7 @@ pub fn recommended_models() -> Vec<ModelSource> {
             sha256: Some(
                 "6f85a640a97cf2bf5b8e764087b1e83da0fdb51d7c9fab7d0fece9385611df83".to_string(),
             ),
+            mirrors: Vec::new(),
         },
         ModelSource {
             name: "Llama-3.2-3B-Instruct (Balanced)".to_string(),
@@ -499,6 +1293,7 @@ pub fn recommended_models() -> Vec<ModelSource> {
             sha256: Some(
                 "6c1a2b41161032677be168d354123594c0e6e67d2b9227c84f296ad037c728ff".to_string(),
             ),
+            mirrors: Vec::new(),
         },
         ModelSource {
             name: "Phi-3.1-mini-4k-instruct (Reasoning)".to_string(),
@@ -508,6 +1303,7 @@ pub fn recommended_models() -> Vec<ModelSource> {
             sha256: Some(
                 "d6d25bf078321bea4a079c727b273cb0b5a2e0b4cf3add0f7a2c8e43075c414f".to_string(),
             ),
+            mirrors: Vec::new(),
         },
         ModelSource {
             name: "nomic-embed-text (Embeddings)".to_string(),
@@ -517,6 +1313,7 @@ pub fn recommended_models() -> Vec<ModelSource> {
             sha256: Some(
                 "0c7930f6c4f6f29b7da5046e3a2c0832aa3f602db3de5760a95f0582dbd3d6e6".to_string(),
             ),
+            mirrors: Vec::new(),
         },
     ]
 }
@@ -534,6 +1331,134 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_mirror_urls() {
+        let mut source = ModelSource::huggingface("org/Model-GGUF", "model.gguf");
+        source.mirrors = vec![
+            "https://hf-mirror.com/".into(),
+            "http://artifacts.corp/models/{filename}".into(),
+            "https://huggingface.co".into(),
+        ];
+        assert_eq!(
+            source.download_urls(false),
+            vec![
+                "https://huggingface.co/org/Model-GGUF/resolve/main/model.gguf",
+                "https://hf-mirror.com/org/Model-GGUF/resolve/main/model.gguf",
+                "http://artifacts.corp/models/model.gguf",
+            ]
+        );
+        assert_eq!(
+            source.download_urls(true)[0],
+            "https://hf-mirror.com/org/Model-GGUF/resolve/main/model.gguf"
+        );
+
+        let mut config = DownloadNetworkConfig {
+            proxy: Some(ProxyConfig {
+                url: "socks5h://proxy.corp:1080".into(),
+                username: None,
+                no_proxy: Some("localhost,.corp".into()),
+            }),
+            mirrors: [("model".to_string(), source.mirrors.clone())].into(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        config.proxy.as_mut().unwrap().url = "ftp://proxy.corp".into();
+        assert!(config.validate().is_err());
+        config.proxy = None;
+        config
+            .mirrors
+            .insert("other".into(), vec!["file:///tmp/{filename}".into()]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_throttle_paces_shared_budget() {
+        let throttle = Throttle::new(Some(100), false);
+        let start = Instant::now();
+        // A full second of budget is available up front
+        assert_eq!(throttle.reserve_at(100, start), Duration::ZERO);
+        // Then each reservation waits for the debt to be repaid
+        assert_eq!(throttle.reserve_at(50, start), Duration::from_millis(500));
+        assert_eq!(throttle.reserve_at(50, start), Duration::from_secs(1));
+        // Two seconds later the debt is cleared and the bucket refilled
+        let later = start + Duration::from_secs(3);
+        assert_eq!(throttle.reserve_at(100, later), Duration::ZERO);
+
+        let unlimited = Throttle::unlimited();
+        assert_eq!(unlimited.reserve_at(u64::MAX, start), Duration::ZERO);
+
+        let config = DownloadNetworkConfig {
+            max_bytes_per_sec: Some(1024),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_plan_chunks() {
+        let chunks = plan_chunks(10, 4);
+        assert_eq!(
+            chunks,
+            vec![
+                ChunkRange {
+                    index: 0,
+                    start: 0,
+                    end: 3
+                },
+                ChunkRange {
+                    index: 1,
+                    start: 4,
+                    end: 7
+                },
+                ChunkRange {
+                    index: 2,
+                    start: 8,
+                    end: 9
+                },
+            ]
+        );
+        assert_eq!(chunks.iter().map(ChunkRange::size).sum::<u64>(), 10);
+        assert_eq!(plan_chunks(8, 4).len(), 2);
+        assert!(plan_chunks(0, 4).is_empty());
+
+        assert_eq!(
+            parse_content_range_total("bytes 0-0/807694464"),
+            Some(807_694_464)
+        );
+        assert_eq!(parse_content_range_total("bytes 0-0/*"), None);
+    }
+
+    #[test]
+    fn test_assemble_chunks_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = b"hello parallel world";
+        let chunks = plan_chunks(data.len() as u64, 6);
+        // Write parts in reverse to make sure assembly follows chunk order
+        for chunk in chunks.iter().rev() {
+            let bytes = &data[chunk.start as usize..=chunk.end as usize];
+            std::fs::write(chunk_path(dir.path(), chunk.index), bytes).unwrap();
+            let sha256 = format!("{:x}", Sha256::digest(bytes));
+            assert!(verify_chunk(
+                &chunk_path(dir.path(), chunk.index),
+                chunk.size(),
+                &sha256
+            ));
+        }
+
+        let out = dir.path().join("model.partial");
+        let sha256 = assemble_chunks(dir.path(), &chunks, &out).unwrap();
+        assert_eq!(std::fs::read(&out).unwrap(), data);
+        assert_eq!(sha256, format!("{:x}", Sha256::digest(data)));
+
+        std::fs::write(chunk_path(dir.path(), 1), b"short").unwrap();
+        assert!(!verify_chunk(
+            &chunk_path(dir.path(), 1),
+            chunks[1].size(),
+            "x"
+        ));
+        assert!(assemble_chunks(dir.path(), &chunks, &out).is_err());
+    }
+
     #[test]
     fn test_recommended_models() {
         let models = recommended_models();
@@ -0,0 +1,445 @@
+//! SMTP email delivery for drafts
+//!
+//! Server settings are stored as JSON in `integration_configs` under `smtp`;
+//! the SMTP password lives in the encrypted token store as `smtp_password`.
+//! Subject and body are rendered from `{{var}}` templates (see
+//! [`crate::notifications::render_template`]) and the exported draft is sent
+//! as an attachment.
+
+use crate::db::Database;
+use crate::notifications::render_template;
+use crate::security::{vault::Vault, SecureString, TOKEN_SMTP_PASSWORD};
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Address, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Integration type key in `integration_configs`
+pub const SMTP_INTEGRATION: &str = "smtp";
+
+/// Maximum recipients (to + cc) per message
+pub const MAX_RECIPIENTS: usize = 20;
+
+const SMTP_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_SUBJECT_TEMPLATE: &str = "Re: [{{ticket_id}}] {{title}}";
+const DEFAULT_BODY_TEMPLATE: &str = "{{response}}";
+
+#[derive(Debug, Error)]
+pub enum EmailError {
+    #[error("Invalid email address: {0}")]
+    InvalidAddress(String),
+    #[error("Failed to build message: {0}")]
+    Message(#[from] lettre::error::Error),
+    #[error("SMTP error: {0}")]
+    Smtp(#[from] lettre::transport::smtp::Error),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Not configured")]
+    NotConfigured,
+}
+
+/// Connection security for the SMTP server
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpSecurity {
+    /// Plain connection upgraded with STARTTLS (usually port 587)
+    #[default]
+    StartTls,
+    /// Implicit TLS (usually port 465)
+    Tls,
+    /// Unencrypted; only allowed for localhost relays
+    None,
+}
+
+impl SmtpSecurity {
+    pub fn default_port(&self) -> u16 {
+        match self {
+            Self::StartTls => 587,
+            Self::Tls => 465,
+            Self::None => 25,
+        }
+    }
+}
+
+/// SMTP settings (stored as JSON in `integration_configs.config_json`, no secrets)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    /// Login name; when unset the server is used without authentication
+    #[serde(default)]
+    pub username: Option<String>,
+    pub from_address: String,
+    #[serde(default)]
+    pub from_name: Option<String>,
+    #[serde(default)]
+    pub reply_to: Option<String>,
+    #[serde(default = "default_subject_template")]
+    pub subject_template: String,
+    #[serde(default = "default_body_template")]
+    pub body_template: String,
+}
+
+fn default_subject_template() -> String {
+    DEFAULT_SUBJECT_TEMPLATE.to_string()
+}
+
+fn default_body_template() -> String {
+    DEFAULT_BODY_TEMPLATE.to_string()
+}
+
+impl SmtpConfig {
+    pub fn port(&self) -> u16 {
+        self.port.unwrap_or_else(|| self.security.default_port())
+    }
+
+    pub fn validate(&self) -> Result<(), EmailError> {
+        let host = self.host.trim();
+        if host.is_empty() || host.contains(|c: char| c.is_whitespace() || c == '/') {
+            return Err(EmailError::InvalidConfig("Invalid SMTP host".into()));
+        }
+        if self.port == Some(0) {
+            return Err(EmailError::InvalidConfig("Invalid SMTP port".into()));
+        }
+        if self.security == SmtpSecurity::None && !is_loopback_host(host) {
+            return Err(EmailError::InvalidConfig(
+                "Unencrypted SMTP is only allowed for localhost".into(),
+            ));
+        }
+        if self
+            .username
+            .as_deref()
+            .is_some_and(|u| u.trim().is_empty())
+        {
+            return Err(EmailError::InvalidConfig("Username cannot be empty".into()));
+        }
+        parse_address(&self.from_address)?;
+        if let Some(reply_to) = &self.reply_to {
+            parse_address(reply_to)?;
+        }
+        if self.subject_template.trim().is_empty() {
+            return Err(EmailError::InvalidConfig(
+                "Subject template cannot be empty".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn sender(&self) -> Result<Mailbox, EmailError> {
+        let address = parse_address(&self.from_address)?;
+        let name = self
+            .from_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(str::to_string);
+        Ok(Mailbox::new(name, address))
+    }
+}
+
+fn is_loopback_host(host: &str) -> bool {
+    matches!(host, "localhost" | "127.0.0.1" | "::1" | "[::1]")
+}
+
+/// Parse a bare email address (`user@example.com`)
+pub fn parse_address(address: &str) -> Result<Address, EmailError> {
+    address
+        .trim()
+        .parse::<Address>()
+        .map_err(|_| EmailError::InvalidAddress(address.to_string()))
+}
+
+/// File attached to an outgoing email
+#[derive(Debug, Clone)]
+pub struct EmailAttachment {
+    pub filename: String,
+    pub content_type: &'static str,
+    pub data: Vec<u8>,
+}
+
+/// Rendered email ready to send
+#[derive(Debug, Clone)]
+pub struct OutgoingEmail {
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub subject: String,
+    pub body: String,
+    pub attachment: Option<EmailAttachment>,
+}
+
+impl OutgoingEmail {
+    /// Render subject and body from the configured templates.
+    /// Line breaks are stripped from the subject to keep headers well-formed.
+    pub fn from_templates(
+        config: &SmtpConfig,
+        vars: &BTreeMap<String, String>,
+        to: Vec<String>,
+        cc: Vec<String>,
+    ) -> Self {
+        let subject = render_template(&config.subject_template, vars)
+            .split(['\r', '\n'])
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let body = render_template(&config.body_template, vars);
+        Self {
+            to,
+            cc,
+            subject,
+            body,
+            attachment: None,
+        }
+    }
+
+    pub fn with_attachment(mut self, attachment: EmailAttachment) -> Self {
+        self.attachment = Some(attachment);
+        self
+    }
+
+    /// Build the MIME message (plain-text body plus optional attachment)
+    pub fn build_message(&self, config: &SmtpConfig) -> Result<Message, EmailError> {
+        if self.to.is_empty() {
+            return Err(EmailError::InvalidAddress(
+                "at least one recipient is required".into(),
+            ));
+        }
+        if self.to.len() + self.cc.len() > MAX_RECIPIENTS {
+            return Err(EmailError::InvalidAddress(format!(
+                "too many recipients (max {})",
+                MAX_RECIPIENTS
+            )));
+        }
+
+        let mut builder = Message::builder()
+            .from(config.sender()?)
+            .subject(self.subject.as_str());
+        if let Some(reply_to) = &config.reply_to {
+            builder = builder.reply_to(Mailbox::new(None, parse_address(reply_to)?));
+        }
+        for to in &self.to {
+            builder = builder.to(Mailbox::new(None, parse_address(to)?));
+        }
+        for cc in &self.cc {
+            builder = builder.cc(Mailbox::new(None, parse_address(cc)?));
+        }
+
+        let message = match &self.attachment {
+            Some(attachment) => {
+                let content_type = ContentType::parse(attachment.content_type)
+                    .map_err(|e| EmailError::InvalidConfig(e.to_string()))?;
+                builder.multipart(
+                    MultiPart::mixed()
+                        .singlepart(SinglePart::plain(self.body.clone()))
+                        .singlepart(
+                            Attachment::new(attachment.filename.clone())
+                                .body(attachment.data.clone(), content_type),
+                        ),
+                )?
+            }
+            None => builder.singlepart(SinglePart::plain(self.body.clone()))?,
+        };
+        Ok(message)
+    }
+}
+
+/// Configured SMTP sender
+pub struct SmtpMailer {
+    config: SmtpConfig,
+    password: Option<SecureString>,
+}
+
+impl SmtpMailer {
+    /// Load the mailer from settings. Returns `None` when SMTP is not
+    /// configured or disabled.
+    pub fn from_db(db: &Database) -> Result<Option<Self>, EmailError> {
+        let record = db
+            .get_integration_config(SMTP_INTEGRATION)
+            .map_err(|e| EmailError::InvalidConfig(e.to_string()))?;
+        let Some(record) = record.filter(|r| r.enabled) else {
+            return Ok(None);
+        };
+
+        let config: SmtpConfig = record
+            .config_json
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| EmailError::InvalidConfig(e.to_string()))?
+            .ok_or(EmailError::NotConfigured)?;
+
+        let password = if config.username.is_some() {
+            let password = Vault::get(TOKEN_SMTP_PASSWORD)
+                .map_err(|e| EmailError::InvalidConfig(e.to_string()))?
+                .ok_or(EmailError::NotConfigured)?;
+            Some(SecureString::new(password))
+        } else {
+            None
+        };
+
+        Ok(Some(Self { config, password }))
+    }
+
+    pub fn config(&self) -> &SmtpConfig {
+        &self.config
+    }
+
+    fn transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>, EmailError> {
+        let host = self.config.host.trim();
+        let builder = match self.config.security {
+            SmtpSecurity::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?,
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host)?,
+            SmtpSecurity::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+        };
+        let mut builder = builder
+            .port(self.config.port())
+            .timeout(Some(Duration::from_secs(SMTP_TIMEOUT_SECS)));
+        if let (Some(username), Some(password)) = (&self.config.username, &self.password) {
+            builder = builder.credentials(Credentials::new(
+                username.trim().to_string(),
+                password.as_str().to_string(),
+            ));
+        }
+        Ok(builder.build())
+    }
+
+    /// Send an email through the configured server
+    pub async fn send(&self, email: &OutgoingEmail) -> Result<(), EmailError> {
+        let message = email.build_message(&self.config)?;
+        self.transport()?.send(message).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SmtpConfig {
+        SmtpConfig {
+            host: "smtp.example.com".into(),
+            port: None,
+            security: SmtpSecurity::StartTls,
+            username: Some("support@example.com".into()),
+            from_address: "support@example.com".into(),
+            from_name: Some("IT Support".into()),
+            reply_to: None,
+            subject_template: default_subject_template(),
+            body_template: "Hi,\n\n{{response}}\n\n-- {{agent}}".into(),
+        }
+    }
+
+    fn vars() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("ticket_id".to_string(), "HELP-42".to_string()),
+            (
+                "title".to_string(),
+                "VPN drops\r\nBcc: evil@example.com".to_string(),
+            ),
+            (
+                "response".to_string(),
+                "Please reinstall the client.".to_string(),
+            ),
+            ("agent".to_string(), "Sam".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config: SmtpConfig = serde_json::from_str(
+            r#"{"host":"smtp.example.com","from_address":"support@example.com"}"#,
+        )
+        .unwrap();
+        assert_eq!(config.security, SmtpSecurity::StartTls);
+        assert_eq!(config.port(), 587);
+        assert_eq!(config.subject_template, DEFAULT_SUBJECT_TEMPLATE);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_settings() {
+        let mut bad_host = config();
+        bad_host.host = "smtp.example.com/path".into();
+        assert!(bad_host.validate().is_err());
+
+        let mut plaintext = config();
+        plaintext.security = SmtpSecurity::None;
+        assert!(plaintext.validate().is_err());
+        plaintext.host = "localhost".into();
+        assert!(plaintext.validate().is_ok());
+
+        let mut bad_from = config();
+        bad_from.from_address = "not-an-address".into();
+        assert!(bad_from.validate().is_err());
+    }
+
+    #[test]
+    fn test_templates_render_single_line_subject() {
+        let email = OutgoingEmail::from_templates(
+            &config(),
+            &vars(),
+            vec!["user@example.com".into()],
+            vec![],
+        );
+        assert_eq!(
+            email.subject,
+            "Re: [HELP-42] VPN drops Bcc: evil@example.com"
+        );
+        assert_eq!(email.body, "Hi,\n\nPlease reinstall the client.\n\n-- Sam");
+    }
+
+    #[test]
+    fn test_build_message_with_attachment() {
+        let email = OutgoingEmail::from_templates(
+            &config(),
+            &vars(),
+            vec!["user@example.com".into()],
+            vec!["lead@example.com".into()],
+        )
+        .with_attachment(EmailAttachment {
+            filename: "draft-HELP-42.txt".into(),
+            content_type: "text/plain; charset=utf-8",
+            data: b"Please reinstall the client.".to_vec(),
+        });
+
+        let formatted =
+            String::from_utf8(email.build_message(&config()).unwrap().formatted()).unwrap();
+        assert!(formatted.contains("To: user@example.com"));
+        assert!(formatted.contains("Cc: lead@example.com"));
+        assert!(formatted.contains("\"IT Support\" <support@example.com>"));
+        assert!(formatted.contains("multipart/mixed"));
+        assert!(formatted.contains("filename=\"draft-HELP-42.txt\""));
+        assert!(!formatted.contains("\r\nBcc:"));
+    }
+
+    #[test]
+    fn test_build_message_rejects_bad_recipients() {
+        let no_recipients = OutgoingEmail::from_templates(&config(), &vars(), vec![], vec![]);
+        assert!(no_recipients.build_message(&config()).is_err());
+
+        let invalid = OutgoingEmail::from_templates(
+            &config(),
+            &vars(),
+            vec!["user@example.com\r\nBcc: evil@example.com".into()],
+            vec![],
+        );
+        assert!(invalid.build_message(&config()).is_err());
+
+        let too_many = OutgoingEmail::from_templates(
+            &config(),
+            &vars(),
+            (0..=MAX_RECIPIENTS)
+                .map(|i| format!("user{}@example.com", i))
+                .collect(),
+            vec![],
+        );
+        assert!(too_many.build_message(&config()).is_err());
+    }
+}
@@ -0,0 +1,554 @@
+//! User-defined export templates
+//!
+//! Teams define their own draft layouts (ticket header, greeting, steps,
+//! sign-off) with a small Handlebars-style syntax:
+//!
+//! - `{{name}}` inserts a value; dotted paths (`{{source.title}}`) walk objects
+//! - `{{#if name}}...{{else}}...{{/if}}` and `{{#unless name}}...{{/unless}}`
+//! - `{{#each list}}...{{else}}...{{/each}}`, where `{{this}}` is the current
+//!   item, item fields resolve directly, and `{{@index}}`, `{{@number}}`,
+//!   `{{@first}}` and `{{@last}}` describe its position
+//! - `{{! comment }}`
+//!
+//! Block tags on a line of their own don't leave blank lines behind. Output is
+//! plain text (nothing is HTML-escaped). As with notification templates,
+//! unknown placeholders are left in the output so typos are visible.
+
+use crate::db::{CustomVariable, SavedDraft};
+use crate::exports::{apply_safe_export, redact_sources, ExportedSource, SafeExportOptions};
+use once_cell::sync::Lazy;
+use regex_lite::Regex;
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+/// Maximum template size accepted on save
+pub const MAX_TEMPLATE_LEN: usize = 64 * 1024;
+
+/// Numbered or bulleted list items in a response, e.g. `1. Restart` or `- Restart`
+static STEP_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:\d+[.)]|[-*+])\s+(.+?)\s*$").unwrap());
+
+static NAME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:this|@[a-z]+|[A-Za-z_][A-Za-z0-9_]*(?:\.[A-Za-z0-9_]+)*)$").unwrap()
+});
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TemplateError {
+    #[error("Template is too large (max {} bytes)", MAX_TEMPLATE_LEN)]
+    TooLarge,
+    #[error("Unclosed tag on line {0}")]
+    UnclosedTag(usize),
+    #[error("Invalid tag {{{{{tag}}}}} on line {line}")]
+    InvalidTag { tag: String, line: usize },
+    #[error("Unexpected {{{{{tag}}}}} on line {line}")]
+    UnexpectedTag { tag: String, line: usize },
+    #[error("{{{{#{block}}}}} opened on line {line} is never closed")]
+    UnclosedBlock { block: String, line: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    If,
+    Unless,
+    Each,
+}
+
+impl BlockKind {
+    fn parse(keyword: &str) -> Option<Self> {
+        match keyword {
+            "if" => Some(Self::If),
+            "unless" => Some(Self::Unless),
+            "each" => Some(Self::Each),
+            _ => None,
+        }
+    }
+
+    fn keyword(&self) -> &'static str {
+        match self {
+            Self::If => "if",
+            Self::Unless => "unless",
+            Self::Each => "each",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    /// Value lookup; the original tag text is kept for unknown placeholders
+    Var {
+        name: String,
+        raw: String,
+    },
+    Block {
+        kind: BlockKind,
+        name: String,
+        body: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+#[derive(Debug)]
+enum Tag {
+    Var(String),
+    Open(BlockKind, String),
+    Else,
+    Close(BlockKind),
+    Comment,
+}
+
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Tag { tag: Tag, raw: String, line: usize },
+}
+
+struct Frame {
+    kind: BlockKind,
+    name: String,
+    line: usize,
+    body: Vec<Node>,
+    otherwise: Option<Vec<Node>>,
+}
+
+impl Frame {
+    fn target(&mut self) -> &mut Vec<Node> {
+        self.otherwise.as_mut().unwrap_or(&mut self.body)
+    }
+}
+
+/// A parsed export template
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    /// Parse a template, reporting the first syntax error
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        if source.len() > MAX_TEMPLATE_LEN {
+            return Err(TemplateError::TooLarge);
+        }
+
+        let mut root = Vec::new();
+        let mut stack: Vec<Frame> = Vec::new();
+
+        for token in tokenize(source)? {
+            let (tag, raw, line) = match token {
+                Token::Text(text) => {
+                    target(&mut root, &mut stack).push(Node::Text(text));
+                    continue;
+                }
+                Token::Tag { tag, raw, line } => (tag, raw, line),
+            };
+
+            match tag {
+                Tag::Comment => {}
+                Tag::Var(name) => target(&mut root, &mut stack).push(Node::Var { name, raw }),
+                Tag::Open(kind, name) => stack.push(Frame {
+                    kind,
+                    name,
+                    line,
+                    body: Vec::new(),
+                    otherwise: None,
+                }),
+                Tag::Else => match stack.last_mut() {
+                    Some(frame) if frame.otherwise.is_none() => frame.otherwise = Some(Vec::new()),
+                    _ => return Err(TemplateError::UnexpectedTag { tag: raw, line }),
+                },
+                Tag::Close(kind) => match stack.pop() {
+                    Some(frame) if frame.kind == kind => {
+                        target(&mut root, &mut stack).push(Node::Block {
+                            kind,
+                            name: frame.name,
+                            body: frame.body,
+                            otherwise: frame.otherwise.unwrap_or_default(),
+                        })
+                    }
+                    _ => return Err(TemplateError::UnexpectedTag { tag: raw, line }),
+                },
+            }
+        }
+
+        if let Some(frame) = stack.pop() {
+            return Err(TemplateError::UnclosedBlock {
+                block: frame.kind.keyword().to_string(),
+                line: frame.line,
+            });
+        }
+        Ok(Self { nodes: root })
+    }
+
+    /// Render against a JSON object context
+    pub fn render(&self, context: &Value) -> String {
+        let mut out = String::new();
+        let mut scopes = vec![Scope {
+            value: context,
+            position: None,
+        }];
+        render_nodes(&self.nodes, &mut scopes, &mut out);
+        out
+    }
+}
+
+fn target<'a>(root: &'a mut Vec<Node>, stack: &'a mut [Frame]) -> &'a mut Vec<Node> {
+    match stack.last_mut() {
+        Some(frame) => frame.target(),
+        None => root,
+    }
+}
+
+fn line_of(source: &str, offset: usize) -> usize {
+    source[..offset].matches('\n').count() + 1
+}
+
+fn parse_tag(inner: &str, raw: &str, line: usize) -> Result<Tag, TemplateError> {
+    let invalid = || TemplateError::InvalidTag {
+        tag: raw.to_string(),
+        line,
+    };
+    let inner = inner.trim();
+
+    if inner.starts_with('!') {
+        return Ok(Tag::Comment);
+    }
+    if inner == "else" {
+        return Ok(Tag::Else);
+    }
+    if let Some(rest) = inner.strip_prefix('#') {
+        let (keyword, name) = rest.split_once(char::is_whitespace).ok_or_else(invalid)?;
+        let kind = BlockKind::parse(keyword).ok_or_else(invalid)?;
+        let name = name.trim();
+        if !NAME_RE.is_match(name) {
+            return Err(invalid());
+        }
+        return Ok(Tag::Open(kind, name.to_string()));
+    }
+    if let Some(keyword) = inner.strip_prefix('/') {
+        return BlockKind::parse(keyword.trim())
+            .map(Tag::Close)
+            .ok_or_else(invalid);
+    }
+    if NAME_RE.is_match(inner) {
+        Ok(Tag::Var(inner.to_string()))
+    } else {
+        Err(invalid())
+    }
+}
+
+/// Split a template into text and tags. Block, `else` and comment tags that
+/// sit alone on a line take the whole line with them.
+fn tokenize(source: &str) -> Result<Vec<Token>, TemplateError> {
+    let mut tokens = Vec::new();
+    let mut cursor = 0;
+
+    while let Some(found) = source[cursor..].find("{{") {
+        let start = cursor + found;
+        let line = line_of(source, start);
+        let close = source[start + 2..]
+            .find("}}")
+            .ok_or(TemplateError::UnclosedTag(line))?;
+        let end = start + 2 + close + 2;
+        let raw = &source[start..end];
+        let tag = parse_tag(&source[start + 2..end - 2], raw, line)?;
+
+        let mut text_end = start;
+        let mut next = end;
+        if !matches!(tag, Tag::Var(_)) {
+            let line_start = source[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let line_end = source[end..].find('\n').map(|i| end + i);
+            let rest_of_line = &source[end..line_end.unwrap_or(source.len())];
+            if line_start >= cursor
+                && source[line_start..start].trim().is_empty()
+                && rest_of_line.trim().is_empty()
+            {
+                text_end = line_start;
+                next = line_end.map(|i| i + 1).unwrap_or(source.len());
+            }
+        }
+
+        if text_end > cursor {
+            tokens.push(Token::Text(source[cursor..text_end].to_string()));
+        }
+        tokens.push(Token::Tag {
+            tag,
+            raw: raw.to_string(),
+            line,
+        });
+        cursor = next;
+    }
+
+    if cursor < source.len() {
+        tokens.push(Token::Text(source[cursor..].to_string()));
+    }
+    Ok(tokens)
+}
+
+/// Position of the current item inside `{{#each}}`
+struct Position {
+    index: usize,
+    len: usize,
+}
+
+struct Scope<'a> {
+    value: &'a Value,
+    position: Option<Position>,
+}
+
+fn resolve<'a>(scopes: &[Scope<'a>], name: &str) -> Option<&'a Value> {
+    let mut parts = name.split('.');
+    let first = parts.next()?;
+    let mut value = if first == "this" {
+        scopes.last()?.value
+    } else {
+        scopes.iter().rev().find_map(|s| s.value.get(first))?
+    };
+    for part in parts {
+        value = value.get(part)?;
+    }
+    Some(value)
+}
+
+fn lookup(scopes: &[Scope], name: &str) -> Option<Value> {
+    let Some(local) = name.strip_prefix('@') else {
+        return resolve(scopes, name).cloned();
+    };
+    let position = scopes.iter().rev().find_map(|s| s.position.as_ref())?;
+    match local {
+        "index" => Some(json!(position.index)),
+        "number" => Some(json!(position.index + 1)),
+        "first" => Some(json!(position.index == 0)),
+        "last" => Some(json!(position.index + 1 == position.len)),
+        _ => None,
+    }
+}
+
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(b)) => *b,
+        Some(Value::String(s)) => !s.is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(Value::Number(n)) => n.as_f64() != Some(0.0),
+        Some(Value::Object(_)) => true,
+    }
+}
+
+fn render_nodes<'a>(nodes: &[Node], scopes: &mut Vec<Scope<'a>>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var { name, raw } => match lookup(scopes, name) {
+                Some(Value::String(s)) => out.push_str(&s),
+                Some(Value::Null) => {}
+                Some(v @ (Value::Number(_) | Value::Bool(_))) => out.push_str(&v.to_string()),
+                // Missing or not printable: keep the placeholder visible
+                _ => out.push_str(raw),
+            },
+            Node::Block {
+                kind: BlockKind::Each,
+                name,
+                body,
+                otherwise,
+            } => match resolve(scopes, name) {
+                Some(Value::Array(items)) if !items.is_empty() => {
+                    for (index, item) in items.iter().enumerate() {
+                        scopes.push(Scope {
+                            value: item,
+                            position: Some(Position {
+                                index,
+                                len: items.len(),
+                            }),
+                        });
+                        render_nodes(body, scopes, out);
+                        scopes.pop();
+                    }
+                }
+                _ => render_nodes(otherwise, scopes, out),
+            },
+            Node::Block {
+                kind,
+                name,
+                body,
+                otherwise,
+            } => {
+                let truthy = is_truthy(lookup(scopes, name).as_ref());
+                let show_body = truthy == (*kind == BlockKind::If);
+                render_nodes(if show_body { body } else { otherwise }, scopes, out);
+            }
+        }
+    }
+}
+
+/// Extract numbered or bulleted steps from a response
+pub fn extract_steps(response: &str) -> Vec<String> {
+    response
+        .lines()
+        .filter_map(|line| STEP_RE.captures(line))
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
+/// Build the render context for a draft.
+///
+/// Custom variables are available by name unless they collide with a
+/// built-in field. Each source carries its citation details (`uri`,
+/// `heading`, `chunk_id`, `score`). Safe export options are applied to free
+/// text and sources, and `strip_internal_ids` also blanks the draft and
+/// ticket IDs.
+pub fn draft_context(
+    draft: &SavedDraft,
+    sources: &[ExportedSource],
+    variables: &[CustomVariable],
+    safe_options: Option<&SafeExportOptions>,
+) -> Value {
+    let clean = |text: &str| match safe_options {
+        Some(opts) => apply_safe_export(text, opts),
+        None => text.to_string(),
+    };
+    let strip_ids = safe_options.is_some_and(|opts| opts.strip_internal_ids);
+    let sources = match safe_options {
+        Some(opts) => redact_sources(sources.to_vec(), opts),
+        None => sources.to_vec(),
+    };
+
+    let response = clean(draft.response_text.as_deref().unwrap_or_default());
+    let summary = draft.summary_text.as_deref().map(clean);
+    let title = summary
+        .iter()
+        .flat_map(|s| s.lines())
+        .chain(draft.input_text.lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("Support request")
+        .chars()
+        .take(80)
+        .collect::<String>();
+
+    let mut context = Map::new();
+    for variable in variables {
+        context.insert(variable.name.clone(), json!(variable.value));
+    }
+    let built_in = json!({
+        "draft_id": (!strip_ids).then(|| draft.id.clone()),
+        "ticket_id": draft.ticket_id.clone().filter(|id| !strip_ids && !id.is_empty()),
+        "title": clean(&title),
+        "summary": summary,
+        "response": response,
+        "steps": extract_steps(&response),
+        "sources": sources
+            .iter()
+            .map(|s| json!({
+                "title": s.title,
+                "path": s.path,
+                "url": s.url,
+                "uri": s.uri(),
+                "heading": s.heading,
+                "chunk_id": s.chunk_id,
+                "score": s.score.map(|score| format!("{:.3}", score)),
+            }))
+            .collect::<Vec<_>>(),
+        "handoff_summary": draft.handoff_summary.as_deref().map(clean),
+        "agent": draft.finalized_by,
+        "model": draft.model_name,
+        "date": chrono::Local::now().format("%Y-%m-%d").to_string(),
+    });
+    if let Value::Object(fields) = built_in {
+        context.extend(fields);
+    }
+    Value::Object(context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(source: &str, context: Value) -> String {
+        Template::parse(source).unwrap().render(&context)
+    }
+
+    #[test]
+    fn test_render_blocks_and_standalone_lines() {
+        let template = "\
+[{{ticket_id}}] {{title}}
+Hi {{customer}},
+
+{{#if steps}}
+Steps:
+{{#each steps}}
+  {{@number}}. {{this}}
+{{/each}}
+{{else}}
+{{response}}
+{{/if}}
+{{#each sources}}
+- {{title}}{{#if url}} ({{url}}){{/if}}
+{{/each}}
+{{! sign-off }}
+Thanks, {{agent}}
+";
+        let context = json!({
+            "ticket_id": "IT-42",
+            "title": "VPN drops",
+            "response": "unused",
+            "steps": ["Restart the client", "Reconnect"],
+            "sources": [
+                { "title": "VPN guide", "url": "https://kb/vpn" },
+                { "title": "FAQ", "url": null },
+            ],
+            "agent": null,
+        });
+        assert_eq!(
+            render(template, context),
+            "\
+[IT-42] VPN drops
+Hi {{customer}},
+
+Steps:
+  1. Restart the client
+  2. Reconnect
+- VPN guide (https://kb/vpn)
+- FAQ
+Thanks, \n"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Template::parse("a\n{{#if x}}\nb").unwrap_err(),
+            TemplateError::UnclosedBlock {
+                block: "if".into(),
+                line: 2
+            }
+        );
+        assert_eq!(
+            Template::parse("{{#each x}}{{/if}}").unwrap_err(),
+            TemplateError::UnexpectedTag {
+                tag: "{{/if}}".into(),
+                line: 1
+            }
+        );
+        assert!(matches!(
+            Template::parse("{{#with x}}{{/with}}"),
+            Err(TemplateError::InvalidTag { .. })
+        ));
+        assert!(matches!(
+            Template::parse("{{#if x}}{{else}}{{else}}{{/if}}"),
+            Err(TemplateError::UnexpectedTag { .. })
+        ));
+        assert_eq!(
+            Template::parse("ok\n{{oops").unwrap_err(),
+            TemplateError::UnclosedTag(2)
+        );
+    }
+
+    #[test]
+    fn test_extract_steps() {
+        let response = "Try this:\n1. Restart\n2) Sign in again\n- Clear cache\nDone.";
+        assert_eq!(
+            extract_steps(response),
+            vec!["Restart", "Sign in again", "Clear cache"]
+        );
+    }
+}
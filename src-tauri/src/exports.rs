@@ -1,6 +1,9 @@
 //! Export utilities for drafts and responses (Phase 18)
 //! Provides HTML, plaintext, and clipboard-ready formats
 
+use once_cell::sync::Lazy;
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd};
+use regex_lite::Regex;
 use serde::{Deserialize, Serialize};
 
 /// Export format options
@@ -15,8 +18,163 @@ pub enum ExportFormat {
     TicketHtml,
     /// JSON structured format
     Json,
+    /// Themed HTML fragment with inline styles and citation links, for
+    /// pasting into rich-text portals
+    StyledHtml(HtmlTheme),
 }
 
+impl ExportFormat {
+    /// File extension for saved exports
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Plaintext => "txt",
+            Self::Html | Self::TicketHtml | Self::StyledHtml(_) => "html",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Themes for styled HTML export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HtmlTheme {
+    #[default]
+    Standard,
+    Compact,
+    Dark,
+    HighContrast,
+}
+
+/// A styled HTML theme as offered in the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct HtmlThemeInfo {
+    pub id: HtmlTheme,
+    pub label: &'static str,
+}
+
+/// Inline styles for each element of a styled export. Styles are inline
+/// because rich-text portals drop `<style>` blocks on paste.
+struct ThemeStyles {
+    container: &'static str,
+    heading: &'static str,
+    summary: &'static str,
+    paragraph: &'static str,
+    list: &'static str,
+    quote: &'static str,
+    code: &'static str,
+    link: &'static str,
+    sources: &'static str,
+    /// Citation details under each source
+    detail: &'static str,
+}
+
+const STANDARD_STYLES: ThemeStyles = ThemeStyles {
+    container: "font-family: -apple-system, 'Segoe UI', Roboto, Arial, sans-serif; font-size: 14px; line-height: 1.6; color: #1f2933;",
+    heading: "margin: 16px 0 8px; color: #102a43;",
+    summary: "background: #f0f4f8; border-left: 4px solid #486581; padding: 10px 14px; margin: 0 0 16px;",
+    paragraph: "margin: 0 0 12px;",
+    list: "margin: 0 0 12px; padding-left: 24px;",
+    quote: "margin: 0 0 12px; padding-left: 12px; border-left: 3px solid #bcccdc; color: #486581;",
+    code: "font-family: Menlo, Consolas, monospace; font-size: 13px; background: #f0f4f8; padding: 1px 4px; border-radius: 3px;",
+    link: "color: #2f6fb0; text-decoration: none;",
+    sources: "margin-top: 16px; padding-top: 8px; border-top: 1px solid #d9e2ec; font-size: 13px;",
+    detail: "font-size: 12px; color: #627d98;",
+};
+
+const COMPACT_STYLES: ThemeStyles = ThemeStyles {
+    container: "font-family: Arial, Helvetica, sans-serif; font-size: 13px; line-height: 1.4; color: #222222;",
+    heading: "margin: 10px 0 4px; font-size: 14px;",
+    summary: "margin: 0 0 8px; font-style: italic;",
+    paragraph: "margin: 0 0 6px;",
+    list: "margin: 0 0 6px; padding-left: 18px;",
+    quote: "margin: 0 0 6px; padding-left: 8px; border-left: 2px solid #cccccc;",
+    code: "font-family: Consolas, monospace; font-size: 12px;",
+    link: "color: #0b57d0;",
+    sources: "margin-top: 8px; font-size: 12px; color: #555555;",
+    detail: "font-size: 11px; color: #777777;",
+};
+
+const DARK_STYLES: ThemeStyles = ThemeStyles {
+    container: "font-family: -apple-system, 'Segoe UI', Roboto, Arial, sans-serif; font-size: 14px; line-height: 1.6; color: #e6edf3; background: #0d1117; padding: 16px; border-radius: 6px;",
+    heading: "margin: 16px 0 8px; color: #f0f6fc;",
+    summary: "background: #161b22; border-left: 4px solid #58a6ff; padding: 10px 14px; margin: 0 0 16px;",
+    paragraph: "margin: 0 0 12px;",
+    list: "margin: 0 0 12px; padding-left: 24px;",
+    quote: "margin: 0 0 12px; padding-left: 12px; border-left: 3px solid #30363d; color: #8b949e;",
+    code: "font-family: Menlo, Consolas, monospace; font-size: 13px; background: #161b22; padding: 1px 4px; border-radius: 3px;",
+    link: "color: #58a6ff; text-decoration: none;",
+    sources: "margin-top: 16px; padding-top: 8px; border-top: 1px solid #30363d; font-size: 13px;",
+    detail: "font-size: 12px; color: #8b949e;",
+};
+
+const HIGH_CONTRAST_STYLES: ThemeStyles = ThemeStyles {
+    container: "font-family: Verdana, Arial, sans-serif; font-size: 16px; line-height: 1.7; color: #000000; background: #ffffff;",
+    heading: "margin: 18px 0 8px; color: #000000; text-decoration: underline;",
+    summary: "border: 2px solid #000000; padding: 10px 14px; margin: 0 0 16px;",
+    paragraph: "margin: 0 0 14px;",
+    list: "margin: 0 0 14px; padding-left: 28px;",
+    quote: "margin: 0 0 14px; padding-left: 12px; border-left: 4px solid #000000;",
+    code: "font-family: Consolas, monospace; font-size: 15px; background: #ffff00; color: #000000;",
+    link: "color: #0000ee; text-decoration: underline; font-weight: bold;",
+    sources: "margin-top: 18px; padding-top: 8px; border-top: 2px solid #000000;",
+    detail: "font-size: 13px; color: #000000;",
+};
+
+impl HtmlTheme {
+    pub const ALL: [HtmlTheme; 4] = [
+        HtmlTheme::Standard,
+        HtmlTheme::Compact,
+        HtmlTheme::Dark,
+        HtmlTheme::HighContrast,
+    ];
+
+    /// Parse a theme ID (as serialized, e.g. `high_contrast`)
+    pub fn parse(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|theme| theme.id() == id)
+    }
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Compact => "compact",
+            Self::Dark => "dark",
+            Self::HighContrast => "high_contrast",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Standard => "Standard",
+            Self::Compact => "Compact",
+            Self::Dark => "Dark",
+            Self::HighContrast => "High contrast",
+        }
+    }
+
+    fn styles(&self) -> &'static ThemeStyles {
+        match self {
+            Self::Standard => &STANDARD_STYLES,
+            Self::Compact => &COMPACT_STYLES,
+            Self::Dark => &DARK_STYLES,
+            Self::HighContrast => &HIGH_CONTRAST_STYLES,
+        }
+    }
+}
+
+/// All styled HTML themes
+pub fn html_themes() -> Vec<HtmlThemeInfo> {
+    HtmlTheme::ALL
+        .into_iter()
+        .map(|id| HtmlThemeInfo {
+            id,
+            label: id.label(),
+        })
+        .collect()
+}
+
+/// Inline citations as instructed by the prompts, e.g. `[Source 2]`
+static CITATION_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[Source (\d+)\]").unwrap());
+
 /// Safe export options - strips sensitive data
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SafeExportOptions {
@@ -30,6 +188,10 @@ pub struct SafeExportOptions {
     pub strip_emails: bool,
     /// Custom patterns to strip (regex)
     pub custom_patterns: Vec<String>,
+    /// Replace user names, emails, hostnames and ticket IDs with consistent
+    /// pseudonyms (see `anonymize`)
+    #[serde(default)]
+    pub pseudonymize: bool,
 }
 
 /// Exported draft content
@@ -49,12 +211,84 @@ pub struct ExportedDraft {
     pub metadata: ExportMetadata,
 }
 
-/// KB source in export
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// KB source in export, cited as `[Source N]` in the response
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ExportedSource {
     pub title: String,
     pub path: Option<String>,
     pub url: Option<String>,
+    /// KB document the cited chunk belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_id: Option<String>,
+    /// Heading path of the chunk within its document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    /// Retrieval score from the search that built the generation context
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+}
+
+impl ExportedSource {
+    /// Build from a KB search result as stored with a draft
+    pub fn from_search_result(result: &serde_json::Value) -> Self {
+        let text = |key: &str| {
+            result[key]
+                .as_str()
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        Self {
+            title: text("title").unwrap_or_else(|| "Unknown".to_string()),
+            path: text("file_path"),
+            url: text("url"),
+            document_id: text("document_id"),
+            chunk_id: text("chunk_id"),
+            heading: text("heading_path"),
+            score: result["score"].as_f64(),
+        }
+    }
+
+    /// Where the source lives: its URL, else its path (web, YouTube and
+    /// GitHub sources store their URL as the path)
+    pub fn uri(&self) -> Option<&str> {
+        self.url
+            .as_deref()
+            .or(self.path.as_deref())
+            .filter(|u| !u.is_empty())
+    }
+
+    /// Labelled citation details (section, chunk, source URI, score) for
+    /// recipients to verify where a step came from
+    pub fn citation_details(&self) -> Vec<(&'static str, String)> {
+        let mut details = Vec::new();
+        if let Some(heading) = self.heading.as_deref() {
+            details.push(("Section", heading.to_string()));
+        }
+        if let Some(chunk_id) = self.chunk_id.as_deref() {
+            details.push(("Chunk", chunk_id.to_string()));
+        }
+        if let Some(uri) = self.uri() {
+            details.push(("Source", uri.to_string()));
+        }
+        if let Some(score) = self.score {
+            details.push(("Score", format!("{:.3}", score)));
+        }
+        details
+    }
+}
+
+/// Parse the KB sources stored with a draft (`kb_sources_json`)
+pub fn sources_from_json(json: &str) -> Vec<ExportedSource> {
+    serde_json::from_str::<Vec<serde_json::Value>>(json)
+        .map(|results| {
+            results
+                .iter()
+                .map(ExportedSource::from_search_result)
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 /// Export metadata
@@ -76,17 +310,23 @@ pub fn format_draft(
 ) -> String {
     let mut response = response_text.to_string();
     let mut summary_text = summary.map(|s| s.to_string());
+    let mut sources = sources.to_vec();
 
     // Apply safe export transformations if requested
     if let Some(opts) = safe_options {
         response = apply_safe_export(&response, opts);
         summary_text = summary_text.map(|s| apply_safe_export(&s, opts));
+        sources = redact_sources(sources, opts);
     }
+    let sources = sources.as_slice();
 
     match format {
         ExportFormat::Plaintext => format_plaintext(&response, summary_text.as_deref(), sources),
         ExportFormat::Html => format_html(&response, summary_text.as_deref(), sources, false),
         ExportFormat::TicketHtml => format_html(&response, summary_text.as_deref(), sources, true),
+        ExportFormat::StyledHtml(theme) => {
+            format_styled_html(&response, summary_text.as_deref(), sources, theme)
+        }
         ExportFormat::Json => {
             let export = serde_json::json!({
                 "response": response,
@@ -111,13 +351,12 @@ fn format_plaintext(response: &str, summary: Option<&str>, sources: &[ExportedSo
     output.push_str(response);
 
     if !sources.is_empty() {
-        output.push_str("\n\n---\nSources:\n");
+        output.push_str("\n\n---\nCitations:\n");
         for (i, source) in sources.iter().enumerate() {
-            output.push_str(&format!("[{}] {}", i + 1, source.title));
-            if let Some(url) = &source.url {
-                output.push_str(&format!(" - {}", url));
+            output.push_str(&format!("[{}] {}\n", i + 1, source.title));
+            for (label, value) in source.citation_details() {
+                output.push_str(&format!("    {}: {}\n", label, value));
             }
-            output.push('\n');
         }
     }
 
@@ -174,21 +413,24 @@ fn format_html(
 
     if !sources.is_empty() {
         if simplified {
-            html.push_str("<hr>\n<p><strong>Sources:</strong></p>\n<ul>\n");
+            html.push_str("<hr>\n<p><strong>Citations:</strong></p>\n<ul>\n");
         } else {
-            html.push_str("<div class=\"sources\">\n<h4>Sources</h4>\n<ul>\n");
+            html.push_str("<div class=\"sources\">\n<h4>Citations</h4>\n<ul>\n");
         }
         for (i, source) in sources.iter().enumerate() {
-            html.push_str("<li>");
-            if let Some(url) = &source.url {
-                html.push_str(&format!(
+            html.push_str(&format!("<li id=\"source-{}\">", i + 1));
+            match source_href(source) {
+                Some(href) => html.push_str(&format!(
                     "[{}] <a href=\"{}\">{}</a>",
                     i + 1,
-                    escape_html(url),
+                    escape_html(&href),
                     escape_html(&source.title)
-                ));
-            } else {
-                html.push_str(&format!("[{}] {}", i + 1, escape_html(&source.title)));
+                )),
+                None => html.push_str(&format!("[{}] {}", i + 1, escape_html(&source.title))),
+            }
+            let details = citation_details_html(source);
+            if !details.is_empty() {
+                html.push_str(&format!("<br><small>{}</small>", details));
             }
             html.push_str("</li>\n");
         }
@@ -206,8 +448,247 @@ fn format_html(
     html
 }
 
+/// Format as a themed HTML fragment: inline styles, markdown rendered, and
+/// `[Source N]` citations linked to the KB document (or its entry in the
+/// source list)
+fn format_styled_html(
+    response: &str,
+    summary: Option<&str>,
+    sources: &[ExportedSource],
+    theme: HtmlTheme,
+) -> String {
+    let styles = theme.styles();
+    let mut html = format!("<div style=\"{}\">\n", styles.container);
+
+    if let Some(sum) = summary.filter(|s| !s.trim().is_empty()) {
+        html.push_str(&format!(
+            "<div style=\"{}\">\n<strong>Summary</strong>\n{}</div>\n",
+            styles.summary,
+            styled_markdown(sum, sources, styles)
+        ));
+    }
+
+    html.push_str(&styled_markdown(response, sources, styles));
+
+    if !sources.is_empty() {
+        html.push_str(&format!(
+            "<div style=\"{}\">\n<strong>Citations</strong>\n<ol style=\"{}\">\n",
+            styles.sources, styles.list
+        ));
+        for (i, source) in sources.iter().enumerate() {
+            let title = escape_html(&source.title);
+            let entry = match source_href(source) {
+                Some(href) => format!(
+                    "<a href=\"{}\" style=\"{}\">{}</a>",
+                    escape_html(&href),
+                    styles.link,
+                    title
+                ),
+                None => title,
+            };
+            let details = citation_details_html(source);
+            let details = if details.is_empty() {
+                details
+            } else {
+                format!("<br><span style=\"{}\">{}</span>", styles.detail, details)
+            };
+            html.push_str(&format!(
+                "<li id=\"source-{}\">{}{}</li>\n",
+                i + 1,
+                entry,
+                details
+            ));
+        }
+        html.push_str("</ol>\n</div>\n");
+    }
+
+    html.push_str("</div>\n");
+    html
+}
+
+/// Render markdown with inline styles. Raw HTML in the text is shown as text.
+fn styled_markdown(markdown: &str, sources: &[ExportedSource], styles: &ThemeStyles) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let open = |tag: &str, style: &str| -> Event {
+        Event::InlineHtml(format!("<{} style=\"{}\">", tag, style).into())
+    };
+    let mut in_code_block = false;
+    let mut events = Vec::new();
+    // The parser splits `[Source N]` into several text events; merge runs of
+    // text so citations can be matched
+    let mut text = String::new();
+    for event in Parser::new_ext(markdown, options) {
+        if let Event::Text(t) = &event {
+            if !in_code_block {
+                text.push_str(t);
+                continue;
+            }
+        }
+        if !text.is_empty() {
+            link_citations(&std::mem::take(&mut text), sources, styles, &mut events);
+        }
+        match event {
+            Event::Html(raw) | Event::InlineHtml(raw) => events.push(Event::Text(raw)),
+            Event::Start(Tag::Paragraph) => events.push(open("p", styles.paragraph)),
+            Event::Start(Tag::Heading { level, .. }) => {
+                events.push(open(&level.to_string(), styles.heading))
+            }
+            Event::Start(Tag::List(None)) => events.push(open("ul", styles.list)),
+            Event::Start(Tag::List(Some(start))) => events.push(Event::InlineHtml(
+                format!("<ol start=\"{}\" style=\"{}\">", start, styles.list).into(),
+            )),
+            Event::Start(Tag::BlockQuote(_)) => events.push(open("blockquote", styles.quote)),
+            Event::Start(Tag::CodeBlock(_)) => {
+                in_code_block = true;
+                events.push(Event::InlineHtml(
+                    format!("<pre style=\"{}\"><code>", styles.code).into(),
+                ));
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                events.push(Event::End(TagEnd::CodeBlock));
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                let href = if is_safe_href(&dest_url) {
+                    dest_url.as_ref()
+                } else {
+                    "#"
+                };
+                events.push(Event::InlineHtml(
+                    format!(
+                        "<a href=\"{}\" style=\"{}\">",
+                        escape_html(href),
+                        styles.link
+                    )
+                    .into(),
+                ));
+            }
+            Event::Code(code) => events.push(Event::InlineHtml(
+                format!(
+                    "<code style=\"{}\">{}</code>",
+                    styles.code,
+                    escape_html(&code)
+                )
+                .into(),
+            )),
+            other => events.push(other),
+        }
+    }
+
+    if !text.is_empty() {
+        link_citations(&text, sources, styles, &mut events);
+    }
+
+    let mut out = String::new();
+    html::push_html(&mut out, events.into_iter());
+    out
+}
+
+/// Split text around `[Source N]` citations, linking each valid one
+fn link_citations<'a>(
+    text: &str,
+    sources: &[ExportedSource],
+    styles: &ThemeStyles,
+    events: &mut Vec<Event<'a>>,
+) {
+    let mut cursor = 0;
+    for caps in CITATION_RE.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        let Some(index) = caps[1]
+            .parse::<usize>()
+            .ok()
+            .filter(|n| (1..=sources.len()).contains(n))
+        else {
+            continue;
+        };
+        let href = source_href(&sources[index - 1]).unwrap_or_else(|| format!("#source-{}", index));
+        events.push(Event::Text(CowStr::from(
+            text[cursor..whole.start()].to_string(),
+        )));
+        events.push(Event::InlineHtml(
+            format!(
+                "<sup><a href=\"{}\" style=\"{}\" title=\"{}\">[{}]</a></sup>",
+                escape_html(&href),
+                styles.link,
+                escape_html(&sources[index - 1].title),
+                index
+            )
+            .into(),
+        ));
+        cursor = whole.end();
+    }
+    events.push(Event::Text(CowStr::from(text[cursor..].to_string())));
+}
+
+/// Citation details as escaped HTML, separated by middots
+fn citation_details_html(source: &ExportedSource) -> String {
+    source
+        .citation_details()
+        .iter()
+        .map(|(label, value)| format!("{}: {}", label, escape_html(value)))
+        .collect::<Vec<_>>()
+        .join(" &middot; ")
+}
+
+/// Drop internal IDs and local paths from sources when the safe export
+/// options ask for it
+pub(crate) fn redact_sources(
+    sources: Vec<ExportedSource>,
+    opts: &SafeExportOptions,
+) -> Vec<ExportedSource> {
+    sources
+        .into_iter()
+        .map(|mut source| {
+            source.title = apply_safe_export(&source.title, opts);
+            source.heading = source.heading.map(|h| apply_safe_export(&h, opts));
+            if opts.strip_internal_ids {
+                source.document_id = None;
+                source.chunk_id = None;
+            }
+            if opts.strip_file_paths && source.path.as_deref().is_some_and(|p| !is_web_url(p)) {
+                source.path = None;
+            }
+            source
+        })
+        .collect()
+}
+
+fn is_web_url(uri: &str) -> bool {
+    let lower = uri.trim().to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+/// Link target for a KB source: its URL, else a file URL for its path
+fn source_href(source: &ExportedSource) -> Option<String> {
+    if let Some(url) = source.url.as_deref().filter(|u| is_safe_href(u)) {
+        return Some(url.to_string());
+    }
+    let path = source.path.as_deref().filter(|p| !p.is_empty())?;
+    if is_web_url(path) {
+        return Some(path.to_string());
+    }
+    let path = path.replace('\\', "/");
+    let path = if path.starts_with('/') {
+        path
+    } else {
+        format!("/{}", path)
+    };
+    Some(format!("file://{}", path.replace(' ', "%20")))
+}
+
+/// Allow only web, mail, file and in-page links
+fn is_safe_href(href: &str) -> bool {
+    let lower = href.trim().to_ascii_lowercase();
+    ["http://", "https://", "mailto:", "file://", "#"]
+        .iter()
+        .any(|scheme| lower.starts_with(scheme))
+}
+
 /// Apply safe export transformations
-fn apply_safe_export(text: &str, opts: &SafeExportOptions) -> String {
+pub(crate) fn apply_safe_export(text: &str, opts: &SafeExportOptions) -> String {
     let mut result = text.to_string();
 
     if opts.strip_emails {
@@ -272,7 +753,16 @@ pub fn format_for_clipboard(
     if include_sources && !sources.is_empty() {
         output.push_str("\n\n---\nReferences:\n");
         for (i, source) in sources.iter().enumerate() {
-            output.push_str(&format!("• [{}] {}\n", i + 1, source.title));
+            output.push_str(&format!("• [{}] {}", i + 1, source.title));
+            let details: Vec<String> = source
+                .citation_details()
+                .into_iter()
+                .map(|(label, value)| format!("{}: {}", label, value))
+                .collect();
+            if !details.is_empty() {
+                output.push_str(&format!(" ({})", details.join("; ")));
+            }
+            output.push('\n');
         }
     }
 
@@ -289,6 +779,7 @@ mod tests {
             title: "VPN Guide".to_string(),
             path: Some("/docs/vpn.md".to_string()),
             url: None,
+            ..Default::default()
         }];
 
         let output = format_plaintext("Test response", Some("Test summary"), &sources);
@@ -345,12 +836,108 @@ mod tests {
         assert!(!result.contains("550e8400"));
     }
 
+    #[test]
+    fn test_styled_html_links_citations() {
+        let sources = vec![
+            ExportedSource {
+                title: "VPN Guide".to_string(),
+                path: Some("/kb/VPN Guide.md".to_string()),
+                url: None,
+                ..Default::default()
+            },
+            ExportedSource {
+                title: "Wiki".to_string(),
+                path: None,
+                url: Some("https://wiki.example.com/vpn".to_string()),
+                ..Default::default()
+            },
+        ];
+        let output = format_draft(
+            "Restart the client [Source 1].\n\n- Check **MFA** [Source 2]\n- Ignore [Source 9]",
+            Some("VPN fix"),
+            &sources,
+            ExportFormat::StyledHtml(HtmlTheme::Dark),
+            None,
+        );
+
+        assert!(output.starts_with("<div style=\"font-family"));
+        assert!(output.contains("background: #0d1117"));
+        assert!(output.contains("<a href=\"file:///kb/VPN%20Guide.md\""));
+        assert!(output.contains("<a href=\"https://wiki.example.com/vpn\""));
+        assert!(output.contains(">[2]</a></sup>"));
+        assert!(output.contains("[Source 9]"));
+        assert!(output.contains("<strong>MFA</strong>"));
+        assert!(output.contains("<li id=\"source-2\">"));
+        assert!(!output.contains("<html>"));
+    }
+
+    #[test]
+    fn test_styled_html_escapes_raw_html() {
+        let output = format_draft(
+            "<script>alert('xss')</script>\n\nSee [link](javascript:alert(1))",
+            None,
+            &[],
+            ExportFormat::StyledHtml(HtmlTheme::Standard),
+            None,
+        );
+        assert!(!output.contains("<script>"));
+        assert!(output.contains("&lt;script&gt;"));
+        assert!(!output.contains("javascript:"));
+    }
+
+    #[test]
+    fn test_citations_from_search_results() {
+        let sources = sources_from_json(
+            r#"[{"chunk_id":"c-1","document_id":"d-1","file_path":"https://kb.example.com/vpn",
+                 "title":"VPN Guide","heading_path":"Setup > Errors","score":0.8213},
+                {"chunk_id":"c-2","document_id":"d-2","file_path":"/Users/sam/kb/mfa.md",
+                 "title":null,"heading_path":null,"score":0.5}]"#,
+        );
+        assert_eq!(sources[1].title, "Unknown");
+
+        let text = format_draft("Steps", None, &sources, ExportFormat::Plaintext, None);
+        assert!(text.contains(
+            "[1] VPN Guide\n    Section: Setup > Errors\n    Chunk: c-1\n    Source: https://kb.example.com/vpn\n    Score: 0.821\n"
+        ));
+
+        let html = format_draft("Steps", None, &sources, ExportFormat::Html, None);
+        assert!(html.contains("<a href=\"https://kb.example.com/vpn\">VPN Guide</a>"));
+        assert!(html.contains("Section: Setup &gt; Errors &middot; Chunk: c-1"));
+
+        let opts = SafeExportOptions {
+            strip_internal_ids: true,
+            strip_file_paths: true,
+            ..Default::default()
+        };
+        let json = format_draft("Steps", None, &sources, ExportFormat::Json, Some(&opts));
+        assert!(!json.contains("c-1") && !json.contains("/Users/sam"));
+        assert!(json.contains("https://kb.example.com/vpn"));
+        assert!(json.contains("\"score\": 0.5"));
+
+        let clipboard = format_for_clipboard("Steps", &sources, true);
+        assert!(clipboard
+            .contains("• [2] Unknown (Chunk: c-2; Source: /Users/sam/kb/mfa.md; Score: 0.500)"));
+    }
+
+    #[test]
+    fn test_html_theme_ids() {
+        for theme in HtmlTheme::ALL {
+            assert_eq!(HtmlTheme::parse(theme.id()), Some(theme));
+            assert_eq!(
+                serde_json::to_value(theme).unwrap(),
+                serde_json::Value::String(theme.id().to_string())
+            );
+        }
+        assert_eq!(HtmlTheme::parse("neon"), None);
+    }
+
     #[test]
     fn test_format_for_clipboard() {
         let sources = vec![ExportedSource {
             title: "Doc 1".to_string(),
             path: None,
             url: Some("https://example.com".to_string()),
+            ..Default::default()
         }];
 
         let output = format_for_clipboard("Response text", &sources, true);
@@ -0,0 +1,194 @@
+//! Follow-up reminders
+//!
+//! Helpers for follow-up due dates and iCalendar (ICS) export. Follow-ups
+//! are stored in the `follow_ups` table; due times are kept as RFC 3339 UTC
+//! strings with second precision so they sort and compare as text.
+
+use crate::db::FollowUp;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+
+/// Calendar event length for exported follow-ups
+const EVENT_MINUTES: i64 = 15;
+const ICS_PRODID: &str = "-//AssistSupport//Follow-ups//EN";
+
+/// Parse an RFC 3339 timestamp (any offset) into the stored UTC form
+pub fn normalize_due_at(due_at: &str) -> Result<String, String> {
+    let parsed = DateTime::parse_from_rfc3339(due_at.trim())
+        .map_err(|e| format!("Invalid due date '{}': {}", due_at, e))?;
+    Ok(format_timestamp(parsed.with_timezone(&Utc)))
+}
+
+/// Due time `days` from `now`, in the stored UTC form
+pub fn due_in_days(now: DateTime<Utc>, days: u32) -> String {
+    format_timestamp(now + Duration::days(i64::from(days)))
+}
+
+/// Format a timestamp the way `due_at` is stored
+pub fn format_timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Title shown in notifications and calendar entries
+pub fn follow_up_title(follow_up: &FollowUp) -> String {
+    match follow_up.ticket_id.as_deref().filter(|t| !t.is_empty()) {
+        Some(ticket) => format!("Follow up on {}", ticket),
+        None => "Follow up on draft".to_string(),
+    }
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11)
+fn escape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Append a content line, folded at 75 octets (RFC 5545 §3.1)
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out.push_str("\r\n");
+}
+
+fn ics_timestamp(value: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ").to_string())
+}
+
+/// Build an iCalendar file with one event (and display alarm) per follow-up
+pub fn to_ics(follow_ups: &[FollowUp], now: DateTime<Utc>) -> String {
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, &format!("PRODID:{}", ICS_PRODID));
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, "METHOD:PUBLISH");
+
+    for follow_up in follow_ups {
+        let Some(start) = ics_timestamp(&follow_up.due_at) else {
+            continue;
+        };
+        let title = escape_text(&follow_up_title(follow_up));
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(
+            &mut out,
+            &format!("UID:follow-up-{}@assistsupport", follow_up.id),
+        );
+        push_line(&mut out, &format!("DTSTAMP:{}", stamp));
+        push_line(&mut out, &format!("DTSTART:{}", start));
+        push_line(&mut out, &format!("DURATION:PT{}M", EVENT_MINUTES));
+        push_line(&mut out, &format!("SUMMARY:{}", title));
+        if let Some(note) = follow_up.note.as_deref().filter(|n| !n.is_empty()) {
+            push_line(&mut out, &format!("DESCRIPTION:{}", escape_text(note)));
+        }
+        if follow_up.status == "completed" {
+            push_line(&mut out, "STATUS:CANCELLED");
+        }
+        push_line(&mut out, "BEGIN:VALARM");
+        push_line(&mut out, "ACTION:DISPLAY");
+        push_line(&mut out, &format!("DESCRIPTION:{}", title));
+        push_line(&mut out, "TRIGGER:PT0M");
+        push_line(&mut out, "END:VALARM");
+        push_line(&mut out, "END:VEVENT");
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn follow_up(id: &str, ticket_id: Option<&str>, note: Option<&str>) -> FollowUp {
+        FollowUp {
+            id: id.to_string(),
+            draft_id: None,
+            ticket_id: ticket_id.map(str::to_string),
+            note: note.map(str::to_string),
+            due_at: "2026-03-04T14:30:00Z".to_string(),
+            status: "pending".to_string(),
+            notified_at: None,
+            created_at: "2026-03-01T09:00:00Z".to_string(),
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_due_at() {
+        assert_eq!(
+            normalize_due_at("2026-03-04T09:30:00-05:00").unwrap(),
+            "2026-03-04T14:30:00Z"
+        );
+        assert_eq!(
+            normalize_due_at("2026-03-04T14:30:00.250Z").unwrap(),
+            "2026-03-04T14:30:00Z"
+        );
+        assert!(normalize_due_at("next tuesday").is_err());
+    }
+
+    #[test]
+    fn test_due_in_days() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+        assert_eq!(due_in_days(now, 3), "2026-03-04T09:00:00Z");
+    }
+
+    #[test]
+    fn test_to_ics() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+        let ics = to_ics(
+            &[
+                follow_up(
+                    "a",
+                    Some("HELP-1"),
+                    Some("Confirm VPN works; ask about MFA, too"),
+                ),
+                follow_up("b", None, None),
+            ],
+            now,
+        );
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("UID:follow-up-a@assistsupport\r\n"));
+        assert!(ics.contains("DTSTAMP:20260301T090000Z\r\n"));
+        assert!(ics.contains("DTSTART:20260304T143000Z\r\n"));
+        assert!(ics.contains("SUMMARY:Follow up on HELP-1\r\n"));
+        assert!(ics.contains("DESCRIPTION:Confirm VPN works\\; ask about MFA\\, too\r\n"));
+        assert!(ics.contains("SUMMARY:Follow up on draft\r\n"));
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let note = "x".repeat(200);
+        let ics = to_ics(
+            &[follow_up("a", None, Some(&note))],
+            Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap(),
+        );
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+        assert!(ics
+            .replace("\r\n ", "")
+            .contains(&format!("DESCRIPTION:{}", note)));
+    }
+}
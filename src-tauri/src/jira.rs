@@ -4,6 +4,7 @@
 use base64::{engine::general_purpose, Engine as _};
 use reqwest::{header, Client, StatusCode};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
 use thiserror::Error;
 use zeroize::Zeroize;
@@ -70,6 +71,136 @@ pub struct JiraConfig {
     pub email: String,
 }
 
+/// Draft metadata that can be pushed into Jira custom fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DraftMetadataField {
+    /// Resolution category (case intake custom field `resolution_category`)
+    ResolutionCategory,
+    /// Root cause (case intake custom field `root_cause`, falls back to diagnostic notes)
+    RootCause,
+    /// Link to the first cited KB article that has a URL
+    KbArticleLink,
+}
+
+/// How a mapped value is encoded in the Jira issue update payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JiraFieldKind {
+    /// Plain text / URL field: `"customfield_10001": "value"`
+    #[default]
+    Text,
+    /// Single-select field: `"customfield_10001": {"value": "value"}`
+    Select,
+    /// Labels field: `"labels": ["value"]`
+    Labels,
+}
+
+/// Target Jira field for a single piece of draft metadata
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JiraFieldTarget {
+    /// Jira field ID (e.g., "customfield_10042")
+    pub field_id: String,
+    #[serde(default)]
+    pub kind: JiraFieldKind,
+}
+
+/// Custom field mapping for one Jira project (stored in DB settings)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JiraFieldMapping {
+    /// Project key this mapping applies to (e.g., "HELP")
+    pub project_key: String,
+    #[serde(default)]
+    pub fields: HashMap<DraftMetadataField, JiraFieldTarget>,
+}
+
+/// Draft metadata values resolved for a push
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DraftFieldValues {
+    pub resolution_category: Option<String>,
+    pub root_cause: Option<String>,
+    pub kb_article_link: Option<String>,
+}
+
+impl DraftFieldValues {
+    fn get(&self, field: DraftMetadataField) -> Option<&str> {
+        let value = match field {
+            DraftMetadataField::ResolutionCategory => self.resolution_category.as_deref(),
+            DraftMetadataField::RootCause => self.root_cause.as_deref(),
+            DraftMetadataField::KbArticleLink => self.kb_article_link.as_deref(),
+        };
+        value.map(str::trim).filter(|v| !v.is_empty())
+    }
+}
+
+impl JiraFieldMapping {
+    /// Validate project key and field IDs before the mapping is saved
+    pub fn validate(&self) -> Result<(), JiraError> {
+        let key = self.project_key.trim();
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(JiraError::Api(format!(
+                "Invalid project key: {}",
+                self.project_key
+            )));
+        }
+
+        for target in self.fields.values() {
+            let id = target.field_id.trim();
+            let valid = id == "labels"
+                || id
+                    .strip_prefix("customfield_")
+                    .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+            if !valid {
+                return Err(JiraError::Api(format!(
+                    "Invalid Jira field ID: {}",
+                    target.field_id
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build the `fields` object for an issue update. Unset values are skipped.
+    pub fn build_fields(
+        &self,
+        values: &DraftFieldValues,
+    ) -> serde_json::Map<String, serde_json::Value> {
+        let mut fields = serde_json::Map::new();
+
+        // Sort for a deterministic payload (HashMap iteration order is random)
+        let mut entries: Vec<_> = self.fields.iter().collect();
+        entries.sort_by(|a, b| a.1.field_id.cmp(&b.1.field_id));
+
+        for (metadata_field, target) in entries {
+            let Some(value) = values.get(*metadata_field) else {
+                continue;
+            };
+            let encoded = match target.kind {
+                JiraFieldKind::Text => serde_json::json!(value),
+                JiraFieldKind::Select => serde_json::json!({ "value": value }),
+                // Jira labels cannot contain spaces
+                JiraFieldKind::Labels => serde_json::json!([value.replace(' ', "-")]),
+            };
+            fields.insert(target.field_id.clone(), encoded);
+        }
+
+        fields
+    }
+}
+
+/// Extract the project key from a ticket key ("HELP-123" -> "HELP")
+pub fn project_key_for_ticket(ticket_key: &str) -> Option<&str> {
+    ticket_key
+        .rsplit_once('-')
+        .map(|(project, _)| project)
+        .filter(|p| !p.is_empty())
+}
+
 /// Jira ticket/issue
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JiraTicket {
@@ -356,6 +487,37 @@ impl JiraClient {
             .await
     }
 
+    /// Update fields on a ticket (used for custom field mapping)
+    pub async fn update_fields(
+        &self,
+        ticket_key: &str,
+        fields: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), JiraError> {
+        validate_ticket_id(ticket_key)
+            .map_err(|e| JiraError::Api(format!("Invalid ticket key: {}", e)))?;
+
+        if fields.is_empty() {
+            return Ok(());
+        }
+
+        let url = format!("{}/rest/api/3/issue/{}", self.base_url, ticket_key);
+        let body = serde_json::json!({ "fields": fields });
+
+        self.execute_with_retry(|| async {
+            self.client
+                .put(&url)
+                .header(header::AUTHORIZATION, self.auth_header.as_str())
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::ACCEPT, "application/json")
+                .json(&body)
+                .send()
+                .await
+        })
+        .await?;
+
+        Ok(())
+    }
+
     /// Execute a request with retry logic for transient errors
     /// Does NOT retry on auth errors (401/403)
     async fn execute_with_retry<F, Fut>(
@@ -527,6 +689,97 @@ pub struct KbCitation {
 mod tests {
     use super::*;
 
+    fn sample_mapping() -> JiraFieldMapping {
+        let mut fields = HashMap::new();
+        fields.insert(
+            DraftMetadataField::ResolutionCategory,
+            JiraFieldTarget {
+                field_id: "customfield_10010".to_string(),
+                kind: JiraFieldKind::Select,
+            },
+        );
+        fields.insert(
+            DraftMetadataField::RootCause,
+            JiraFieldTarget {
+                field_id: "customfield_10020".to_string(),
+                kind: JiraFieldKind::Text,
+            },
+        );
+        fields.insert(
+            DraftMetadataField::KbArticleLink,
+            JiraFieldTarget {
+                field_id: "customfield_10030".to_string(),
+                kind: JiraFieldKind::Text,
+            },
+        );
+        JiraFieldMapping {
+            project_key: "HELP".to_string(),
+            fields,
+        }
+    }
+
+    #[test]
+    fn test_build_fields_encodes_by_kind() {
+        let mapping = sample_mapping();
+        let values = DraftFieldValues {
+            resolution_category: Some("Hardware".to_string()),
+            root_cause: Some("Failed SSD".to_string()),
+            kb_article_link: Some("https://kb.example.com/ssd".to_string()),
+        };
+
+        let fields = mapping.build_fields(&values);
+        assert_eq!(
+            fields["customfield_10010"],
+            serde_json::json!({ "value": "Hardware" })
+        );
+        assert_eq!(fields["customfield_10020"], serde_json::json!("Failed SSD"));
+        assert_eq!(
+            fields["customfield_10030"],
+            serde_json::json!("https://kb.example.com/ssd")
+        );
+    }
+
+    #[test]
+    fn test_build_fields_skips_missing_values() {
+        let mapping = sample_mapping();
+        let values = DraftFieldValues {
+            resolution_category: None,
+            root_cause: Some("   ".to_string()),
+            kb_article_link: Some("https://kb.example.com/a".to_string()),
+        };
+
+        let fields = mapping.build_fields(&values);
+        assert_eq!(fields.len(), 1);
+        assert!(fields.contains_key("customfield_10030"));
+    }
+
+    #[test]
+    fn test_mapping_validation() {
+        assert!(sample_mapping().validate().is_ok());
+
+        let mut bad_project = sample_mapping();
+        bad_project.project_key = "help; drop".to_string();
+        assert!(bad_project.validate().is_err());
+
+        let mut bad_field = sample_mapping();
+        bad_field.fields.insert(
+            DraftMetadataField::RootCause,
+            JiraFieldTarget {
+                field_id: "summary".to_string(),
+                kind: JiraFieldKind::Text,
+            },
+        );
+        assert!(bad_field.validate().is_err());
+    }
+
+    #[test]
+    fn test_project_key_for_ticket() {
+        assert_eq!(project_key_for_ticket("HELP-123"), Some("HELP"));
+        assert_eq!(project_key_for_ticket("IT_OPS-7"), Some("IT_OPS"));
+        assert_eq!(project_key_for_ticket("-7"), None);
+        assert_eq!(project_key_for_ticket("HELP"), None);
+    }
+
     #[test]
     fn test_parse_description_null() {
         let fields = serde_json::json!({
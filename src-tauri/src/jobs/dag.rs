@@ -0,0 +1,175 @@
+//! Job dependency chains
+//!
+//! A job may depend on other jobs; it stays queued until every dependency
+//! has succeeded, and fails without running if any dependency failed or was
+//! cancelled. Failures propagate down the chain the same way.
+
+use super::JobStatus;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DependencyError {
+    #[error("Duplicate step key: {0}")]
+    DuplicateKey(String),
+    #[error("Step '{step}' depends on unknown step '{dependency}'")]
+    UnknownDependency { step: String, dependency: String },
+    #[error("Step '{0}' depends on itself")]
+    SelfDependency(String),
+    #[error("Dependency cycle between steps: {}", .0.join(", "))]
+    Cycle(Vec<String>),
+}
+
+/// Order chain steps so every step comes after its dependencies.
+/// `steps` are (key, dependency keys) pairs; returns indices into `steps`.
+pub fn topological_order(steps: &[(&str, Vec<&str>)]) -> Result<Vec<usize>, DependencyError> {
+    let mut index = HashMap::new();
+    for (i, (key, _)) in steps.iter().enumerate() {
+        if index.insert(*key, i).is_some() {
+            return Err(DependencyError::DuplicateKey(key.to_string()));
+        }
+    }
+    for (key, deps) in steps {
+        for dep in deps {
+            if dep == key {
+                return Err(DependencyError::SelfDependency(key.to_string()));
+            }
+            if !index.contains_key(dep) {
+                return Err(DependencyError::UnknownDependency {
+                    step: key.to_string(),
+                    dependency: dep.to_string(),
+                });
+            }
+        }
+    }
+
+    // Kahn's algorithm, keeping the original order among ready steps
+    let mut remaining: Vec<HashSet<usize>> = steps
+        .iter()
+        .map(|(_, deps)| deps.iter().map(|d| index[d]).collect())
+        .collect();
+    let mut order = Vec::with_capacity(steps.len());
+    let mut placed = vec![false; steps.len()];
+    while order.len() < steps.len() {
+        let ready: Vec<usize> = (0..steps.len())
+            .filter(|&i| !placed[i] && remaining[i].is_empty())
+            .collect();
+        if ready.is_empty() {
+            let cycle = (0..steps.len())
+                .filter(|&i| !placed[i])
+                .map(|i| steps[i].0.to_string())
+                .collect();
+            return Err(DependencyError::Cycle(cycle));
+        }
+        for i in ready {
+            placed[i] = true;
+            order.push(i);
+            for deps in remaining.iter_mut() {
+                deps.remove(&i);
+            }
+        }
+    }
+    Ok(order)
+}
+
+/// Whether a queued job can run, given the status of each dependency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyState {
+    /// All dependencies succeeded
+    Ready,
+    /// At least one dependency is still queued or running
+    Waiting,
+    /// A dependency failed, was cancelled or no longer exists
+    Failed(String),
+}
+
+/// Resolve a job's dependencies. `statuses` maps job ID to status; missing
+/// IDs are dependencies that have been deleted.
+pub fn dependency_state(
+    depends_on: &[String],
+    statuses: &HashMap<String, JobStatus>,
+) -> DependencyState {
+    let mut waiting = false;
+    for dep in depends_on {
+        match statuses.get(dep) {
+            Some(JobStatus::Succeeded) => {}
+            Some(JobStatus::Queued | JobStatus::Running) => waiting = true,
+            Some(status) => {
+                return DependencyState::Failed(format!("Dependency {} {}", dep, status))
+            }
+            None => return DependencyState::Failed(format!("Dependency {} no longer exists", dep)),
+        }
+    }
+    if waiting {
+        DependencyState::Waiting
+    } else {
+        DependencyState::Ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topological_order() {
+        let steps = vec![
+            ("embed", vec!["index"]),
+            ("index", vec![]),
+            ("backup", vec!["embed", "index"]),
+        ];
+        assert_eq!(topological_order(&steps), Ok(vec![1, 0, 2]));
+
+        let independent = vec![("a", vec![]), ("b", vec![])];
+        assert_eq!(topological_order(&independent), Ok(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_invalid_chains() {
+        assert_eq!(
+            topological_order(&[("a", vec![]), ("a", vec![])]),
+            Err(DependencyError::DuplicateKey("a".into()))
+        );
+        assert_eq!(
+            topological_order(&[("a", vec!["a"])]),
+            Err(DependencyError::SelfDependency("a".into()))
+        );
+        assert_eq!(
+            topological_order(&[("a", vec!["missing"])]),
+            Err(DependencyError::UnknownDependency {
+                step: "a".into(),
+                dependency: "missing".into()
+            })
+        );
+        assert_eq!(
+            topological_order(&[("a", vec!["c"]), ("b", vec![]), ("c", vec!["a"])]),
+            Err(DependencyError::Cycle(vec!["a".into(), "c".into()]))
+        );
+    }
+
+    #[test]
+    fn test_dependency_state() {
+        let deps = vec!["index".to_string(), "refresh".to_string()];
+        let mut statuses = HashMap::from([
+            ("index".to_string(), JobStatus::Succeeded),
+            ("refresh".to_string(), JobStatus::Running),
+        ]);
+        assert_eq!(dependency_state(&deps, &statuses), DependencyState::Waiting);
+
+        statuses.insert("refresh".into(), JobStatus::Succeeded);
+        assert_eq!(dependency_state(&deps, &statuses), DependencyState::Ready);
+
+        statuses.insert("index".into(), JobStatus::Failed);
+        assert_eq!(
+            dependency_state(&deps, &statuses),
+            DependencyState::Failed("Dependency index failed".into())
+        );
+
+        statuses.remove("index");
+        assert!(matches!(
+            dependency_state(&deps, &statuses),
+            DependencyState::Failed(_)
+        ));
+        assert_eq!(dependency_state(&[], &statuses), DependencyState::Ready);
+    }
+}
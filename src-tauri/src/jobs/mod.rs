@@ -0,0 +1,701 @@
+//! Job system for background task management
+//!
+//! Provides a reliable, observable, and cancelable job queue for long-running
+//! operations like content ingestion.
+
+pub mod dag;
+pub mod progress;
+pub mod queue;
+pub mod retry;
+pub mod schedule;
+
+use chrono::{DateTime, Utc};
+use progress::ProgressTracker;
+use queue::{JobPermit, JobQueue};
+use retry::RetryPolicy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Job status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl std::fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Queued => write!(f, "queued"),
+            Self::Running => write!(f, "running"),
+            Self::Succeeded => write!(f, "succeeded"),
+            Self::Failed => write!(f, "failed"),
+            Self::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl std::str::FromStr for JobStatus {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "queued" => Ok(Self::Queued),
+            "running" => Ok(Self::Running),
+            "succeeded" => Ok(Self::Succeeded),
+            "failed" => Ok(Self::Failed),
+            "cancelled" => Ok(Self::Cancelled),
+            _ => Err(()),
+        }
+    }
+}
+
+impl JobStatus {
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// Job priority. When slots free up, higher-priority jobs start first.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum JobPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl std::fmt::Display for JobPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::Normal => write!(f, "normal"),
+            Self::High => write!(f, "high"),
+        }
+    }
+}
+
+impl std::str::FromStr for JobPriority {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(Self::Low),
+            "normal" => Ok(Self::Normal),
+            "high" => Ok(Self::High),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Custom job type used by batch generation
+pub const BATCH_GENERATE_JOB: &str = "batch_generate";
+
+/// Job type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobType {
+    IngestWeb,
+    IngestYoutube,
+    IngestGithub,
+    IngestBatch,
+    IndexKb,
+    GenerateEmbeddings,
+    RefreshSources,
+    MarkStaleSources,
+    Backup,
+    ExportDrafts,
+    PollFeeds,
+    Custom(String),
+}
+
+impl std::fmt::Display for JobType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IngestWeb => write!(f, "ingest_web"),
+            Self::IngestYoutube => write!(f, "ingest_youtube"),
+            Self::IngestGithub => write!(f, "ingest_github"),
+            Self::IngestBatch => write!(f, "ingest_batch"),
+            Self::IndexKb => write!(f, "index_kb"),
+            Self::GenerateEmbeddings => write!(f, "generate_embeddings"),
+            Self::RefreshSources => write!(f, "refresh_sources"),
+            Self::MarkStaleSources => write!(f, "mark_stale_sources"),
+            Self::Backup => write!(f, "backup"),
+            Self::ExportDrafts => write!(f, "export_drafts"),
+            Self::PollFeeds => write!(f, "poll_feeds"),
+            Self::Custom(s) => write!(f, "custom:{}", s),
+        }
+    }
+}
+
+impl std::str::FromStr for JobType {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "ingest_web" => Self::IngestWeb,
+            "ingest_youtube" => Self::IngestYoutube,
+            "ingest_github" => Self::IngestGithub,
+            "ingest_batch" => Self::IngestBatch,
+            "index_kb" => Self::IndexKb,
+            "generate_embeddings" => Self::GenerateEmbeddings,
+            "refresh_sources" => Self::RefreshSources,
+            "mark_stale_sources" => Self::MarkStaleSources,
+            "backup" => Self::Backup,
+            "export_drafts" => Self::ExportDrafts,
+            "poll_feeds" => Self::PollFeeds,
+            other => {
+                if let Some(custom) = other.strip_prefix("custom:") {
+                    Self::Custom(custom.to_string())
+                } else {
+                    Self::Custom(other.to_string())
+                }
+            }
+        })
+    }
+}
+
+impl JobType {
+    /// Whether this job type can run from a recurring schedule
+    pub fn is_schedulable(&self) -> bool {
+        matches!(
+            self,
+            Self::IndexKb
+                | Self::GenerateEmbeddings
+                | Self::RefreshSources
+                | Self::MarkStaleSources
+                | Self::Backup
+                | Self::PollFeeds
+        )
+    }
+
+    /// Default priority: background maintenance yields to user-facing work
+    pub fn default_priority(&self) -> JobPriority {
+        match self {
+            Self::GenerateEmbeddings
+            | Self::RefreshSources
+            | Self::MarkStaleSources
+            | Self::Backup
+            | Self::PollFeeds => JobPriority::Low,
+            _ => JobPriority::Normal,
+        }
+    }
+
+    /// Concurrency group; jobs in a group share its concurrency limit
+    pub fn concurrency_group(&self) -> &str {
+        match self {
+            Self::IngestWeb
+            | Self::IngestYoutube
+            | Self::IngestGithub
+            | Self::IngestBatch
+            | Self::RefreshSources
+            | Self::PollFeeds => "ingest",
+            Self::IndexKb => "index",
+            Self::GenerateEmbeddings => "embeddings",
+            Self::MarkStaleSources => "maintenance",
+            Self::Backup => "backup",
+            Self::ExportDrafts => "export",
+            Self::Custom(name) => name,
+        }
+    }
+
+    /// Whether an interrupted job of this type can be resumed after a restart
+    pub fn is_resumable(&self) -> bool {
+        self.is_schedulable() || matches!(self, Self::Custom(name) if name == BATCH_GENERATE_JOB)
+    }
+}
+
+/// Job record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub job_type: JobType,
+    pub status: JobStatus,
+    #[serde(default)]
+    pub priority: JobPriority,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub progress: f32,
+    pub progress_message: Option<String>,
+    pub error: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+    /// IDs of jobs that must succeed before this one runs
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+impl Job {
+    pub fn new(job_type: JobType) -> Self {
+        let now = Utc::now();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            priority: job_type.default_priority(),
+            job_type,
+            status: JobStatus::Queued,
+            created_at: now,
+            updated_at: now,
+            started_at: None,
+            completed_at: None,
+            progress: 0.0,
+            progress_message: None,
+            error: None,
+            metadata: None,
+            depends_on: Vec::new(),
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: serde_json::Value) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    pub fn with_priority(mut self, priority: JobPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_dependencies(mut self, depends_on: Vec<String>) -> Self {
+        self.depends_on = depends_on;
+        self
+    }
+
+    /// ID of the schedule that created this job, if any
+    pub fn schedule_id(&self) -> Option<&str> {
+        self.metadata.as_ref()?.get("schedule_id")?.as_str()
+    }
+}
+
+/// Job log entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobLog {
+    pub id: i64,
+    pub job_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Debug => write!(f, "debug"),
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Job progress event (for Tauri events), emitted the same way by every job type
+#[derive(Debug, Clone, Serialize)]
+pub struct JobProgressEvent {
+    pub job_id: String,
+    pub phase: String,
+    /// Items done in this phase
+    pub current: u64,
+    /// Items in this phase, when known
+    pub total: Option<u64>,
+    pub percent: f32,
+    /// Estimated seconds left in this phase
+    pub eta_seconds: Option<u64>,
+    pub message: String,
+    /// Most recent log line for the job
+    pub last_log: Option<String>,
+}
+
+/// Job log event (for Tauri events)
+#[derive(Debug, Clone, Serialize)]
+pub struct JobLogEvent {
+    pub job_id: String,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+/// Job done event (for Tauri events)
+#[derive(Debug, Clone, Serialize)]
+pub struct JobDoneEvent {
+    pub job_id: String,
+    pub status: JobStatus,
+    pub error: Option<String>,
+}
+
+/// Cancellation token for jobs
+#[derive(Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Job manager for tracking active jobs
+pub struct JobManager {
+    /// Active cancellation tokens by job ID
+    cancellation_tokens: std::sync::Mutex<HashMap<String, CancellationToken>>,
+    /// Schedules with a run in progress (a schedule never overlaps itself)
+    running_schedules: std::sync::Mutex<HashSet<String>>,
+    scheduler_started: AtomicBool,
+    /// Retry policy overrides by job type (see `RetryPolicy::for_job_type`)
+    retry_policies: std::sync::Mutex<HashMap<String, RetryPolicy>>,
+    /// When this process started; older queued/running jobs were interrupted
+    started_at: DateTime<Utc>,
+    /// Concurrency slots shared by running jobs
+    queue: JobQueue,
+}
+
+impl JobManager {
+    pub fn new() -> Self {
+        Self {
+            cancellation_tokens: std::sync::Mutex::new(HashMap::new()),
+            running_schedules: std::sync::Mutex::new(HashSet::new()),
+            scheduler_started: AtomicBool::new(false),
+            retry_policies: std::sync::Mutex::new(HashMap::new()),
+            started_at: Utc::now(),
+            queue: JobQueue::new(),
+        }
+    }
+
+    /// When the job manager was created (app start)
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    /// Retry policy for a job type
+    pub fn retry_policy(&self, job_type: &JobType) -> RetryPolicy {
+        let policies = self.retry_policies.lock().unwrap();
+        policies
+            .get(&job_type.to_string())
+            .copied()
+            .unwrap_or_else(|| RetryPolicy::for_job_type(job_type))
+    }
+
+    /// Override the retry policy for a job type
+    pub fn set_retry_policy(&self, job_type: &JobType, policy: RetryPolicy) {
+        let mut policies = self.retry_policies.lock().unwrap();
+        policies.insert(job_type.to_string(), policy);
+    }
+
+    /// Mark the scheduler loop as started; returns false if it already was
+    pub fn start_scheduler(&self) -> bool {
+        !self.scheduler_started.swap(true, Ordering::SeqCst)
+    }
+
+    /// Claim a schedule for a run; returns false while a previous run is active
+    pub fn begin_scheduled_run(&self, schedule_id: &str) -> bool {
+        let mut running = self.running_schedules.lock().unwrap();
+        running.insert(schedule_id.to_string())
+    }
+
+    /// Release a schedule after its run completes
+    pub fn end_scheduled_run(&self, schedule_id: &str) {
+        let mut running = self.running_schedules.lock().unwrap();
+        running.remove(schedule_id);
+    }
+
+    /// Wait for a concurrency slot for a job. Returns None if the job is
+    /// cancelled while waiting; the slot is released when the permit drops.
+    pub async fn acquire_slot(
+        &self,
+        job_type: &JobType,
+        priority: JobPriority,
+        cancel_token: &CancellationToken,
+    ) -> Option<JobPermit<'_>> {
+        self.queue.acquire(job_type, priority, cancel_token).await
+    }
+
+    /// Override the concurrency limit for a group (see `JobType::concurrency_group`)
+    pub fn set_concurrency_limit(&self, group: &str, limit: usize) {
+        self.queue.set_limit(group, limit);
+    }
+
+    /// Register a job and get its cancellation token (the existing token if
+    /// the job is already registered)
+    pub fn register_job(&self, job_id: &str) -> CancellationToken {
+        let mut tokens = self.cancellation_tokens.lock().unwrap();
+        tokens.entry(job_id.to_string()).or_default().clone()
+    }
+
+    /// Cancel a job
+    pub fn cancel_job(&self, job_id: &str) -> bool {
+        let tokens = self.cancellation_tokens.lock().unwrap();
+        if let Some(token) = tokens.get(job_id) {
+            token.cancel();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Unregister a completed job
+    pub fn unregister_job(&self, job_id: &str) {
+        let mut tokens = self.cancellation_tokens.lock().unwrap();
+        tokens.remove(job_id);
+    }
+
+    /// Check if a job is registered
+    pub fn is_job_active(&self, job_id: &str) -> bool {
+        let tokens = self.cancellation_tokens.lock().unwrap();
+        tokens.contains_key(job_id)
+    }
+}
+
+impl Default for JobManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Job runner context for executing jobs with event emission
+pub struct JobContext {
+    pub job_id: String,
+    pub cancel_token: CancellationToken,
+    progress: std::sync::Mutex<ProgressTracker>,
+}
+
+impl JobContext {
+    pub fn new(job_id: String, cancel_token: CancellationToken) -> Self {
+        Self {
+            job_id,
+            cancel_token,
+            progress: std::sync::Mutex::new(ProgressTracker::new()),
+        }
+    }
+
+    /// Check if the job has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel_token.is_cancelled()
+    }
+
+    /// Create a progress event for `current` of `total` items in `phase`
+    pub fn progress_event(
+        &self,
+        phase: &str,
+        current: u64,
+        total: Option<u64>,
+        message: &str,
+    ) -> JobProgressEvent {
+        let mut tracker = self.progress.lock().unwrap();
+        let eta = tracker.update(phase, current, total);
+        JobProgressEvent {
+            job_id: self.job_id.clone(),
+            phase: phase.to_string(),
+            current,
+            total,
+            percent: progress::percent(current, total),
+            eta_seconds: eta.map(|d| d.as_secs()),
+            message: message.to_string(),
+            last_log: tracker.last_log().map(str::to_string),
+        }
+    }
+
+    /// Whether a progress event should also be written to the job record
+    pub fn should_persist_progress(&self, event: &JobProgressEvent) -> bool {
+        let finished = event.total.is_some_and(|total| event.current >= total);
+        self.progress.lock().unwrap().should_persist(finished)
+    }
+
+    /// Create a log event (and remember it as the job's last log line)
+    pub fn log_event(&self, level: LogLevel, message: &str) -> JobLogEvent {
+        self.progress.lock().unwrap().set_last_log(message);
+        JobLogEvent {
+            job_id: self.job_id.clone(),
+            level,
+            message: message.to_string(),
+        }
+    }
+
+    /// Create a done event
+    pub fn done_event(&self, status: JobStatus, error: Option<String>) -> JobDoneEvent {
+        JobDoneEvent {
+            job_id: self.job_id.clone(),
+            status,
+            error,
+        }
+    }
+}
+
+/// Event names for Tauri
+pub mod events {
+    pub const JOB_PROGRESS: &str = "job:progress";
+    pub const JOB_LOG: &str = "job:log";
+    pub const JOB_DONE: &str = "job:done";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_status_display() {
+        assert_eq!(JobStatus::Queued.to_string(), "queued");
+        assert_eq!(JobStatus::Running.to_string(), "running");
+        assert_eq!(JobStatus::Succeeded.to_string(), "succeeded");
+        assert_eq!(JobStatus::Failed.to_string(), "failed");
+        assert_eq!(JobStatus::Cancelled.to_string(), "cancelled");
+    }
+
+    #[test]
+    fn test_job_status_terminal() {
+        assert!(!JobStatus::Queued.is_terminal());
+        assert!(!JobStatus::Running.is_terminal());
+        assert!(JobStatus::Succeeded.is_terminal());
+        assert!(JobStatus::Failed.is_terminal());
+        assert!(JobStatus::Cancelled.is_terminal());
+    }
+
+    #[test]
+    fn test_job_type_display() {
+        assert_eq!(JobType::IngestWeb.to_string(), "ingest_web");
+        assert_eq!("poll_feeds".parse::<JobType>().unwrap(), JobType::PollFeeds);
+        assert!(JobType::PollFeeds.is_schedulable());
+        assert_eq!(
+            JobType::Custom("test".to_string()).to_string(),
+            "custom:test"
+        );
+    }
+
+    #[test]
+    fn test_cancellation_token() {
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+        token.cancel();
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn test_job_manager() {
+        let manager = JobManager::new();
+        let token = manager.register_job("job-1");
+
+        assert!(manager.is_job_active("job-1"));
+        assert!(!token.is_cancelled());
+
+        manager.cancel_job("job-1");
+        assert!(token.is_cancelled());
+
+        manager.unregister_job("job-1");
+        assert!(!manager.is_job_active("job-1"));
+    }
+
+    #[test]
+    fn test_scheduled_runs_do_not_overlap() {
+        let manager = JobManager::new();
+        assert!(manager.start_scheduler());
+        assert!(!manager.start_scheduler());
+
+        assert!(manager.begin_scheduled_run("nightly"));
+        assert!(!manager.begin_scheduled_run("nightly"));
+        manager.end_scheduled_run("nightly");
+        assert!(manager.begin_scheduled_run("nightly"));
+    }
+
+    #[test]
+    fn test_retry_policy_overrides() {
+        let manager = JobManager::new();
+        assert_eq!(
+            manager.retry_policy(&JobType::IngestWeb),
+            RetryPolicy::network()
+        );
+
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            ..RetryPolicy::network()
+        };
+        manager.set_retry_policy(&JobType::IngestWeb, policy);
+        assert_eq!(manager.retry_policy(&JobType::IngestWeb), policy);
+        assert_eq!(
+            manager.retry_policy(&JobType::IngestGithub),
+            RetryPolicy::network()
+        );
+    }
+
+    #[test]
+    fn test_resumable_jobs() {
+        assert!(JobType::IndexKb.is_resumable());
+        assert!(JobType::Custom(BATCH_GENERATE_JOB.into()).is_resumable());
+        assert!(!JobType::IngestWeb.is_resumable());
+        assert!(!JobType::Custom("unknown".into()).is_resumable());
+
+        let job = Job::new(JobType::Backup).with_metadata(serde_json::json!({
+            "schedule_id": "sched-1",
+        }));
+        assert_eq!(job.schedule_id(), Some("sched-1"));
+        assert_eq!(Job::new(JobType::IndexKb).schedule_id(), None);
+    }
+
+    #[test]
+    fn test_job_priority() {
+        assert!(JobPriority::High > JobPriority::Normal);
+        assert!(JobPriority::Normal > JobPriority::Low);
+        assert_eq!("high".parse::<JobPriority>(), Ok(JobPriority::High));
+        assert_eq!(JobPriority::Low.to_string(), "low");
+        assert!("urgent".parse::<JobPriority>().is_err());
+
+        assert_eq!(
+            Job::new(JobType::GenerateEmbeddings).priority,
+            JobPriority::Low
+        );
+        assert_eq!(Job::new(JobType::IngestWeb).priority, JobPriority::Normal);
+        assert_eq!(JobType::IngestGithub.concurrency_group(), "ingest");
+        assert_eq!(
+            JobType::Custom(BATCH_GENERATE_JOB.into()).concurrency_group(),
+            BATCH_GENERATE_JOB
+        );
+    }
+
+    #[test]
+    fn test_progress_events() {
+        let ctx = JobContext::new("job-1".into(), CancellationToken::new());
+        ctx.log_event(LogLevel::Info, "Run started");
+
+        let event = ctx.progress_event("indexing", 5, Some(20), "notes.md");
+        assert_eq!(event.percent, 25.0);
+        assert_eq!(event.total, Some(20));
+        assert_eq!(event.last_log.as_deref(), Some("Run started"));
+        assert!(ctx.should_persist_progress(&event));
+        assert!(!ctx.should_persist_progress(&event));
+
+        let done = ctx.progress_event("indexing", 20, Some(20), "Done");
+        assert!(ctx.should_persist_progress(&done));
+        assert_eq!(done.eta_seconds, None);
+    }
+}
@@ -0,0 +1,122 @@
+//! Job progress tracking
+//!
+//! Every job type reports progress with the same payload: the current phase,
+//! items done out of a total (when known), an ETA extrapolated from the time
+//! spent in the phase so far, and the job's most recent log line.
+
+use std::time::{Duration, Instant};
+
+/// Minimum time between progress writes to the database
+const PERSIST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Don't estimate until the phase has run this long (early rates are noisy)
+const MIN_ETA_ELAPSED: Duration = Duration::from_secs(2);
+
+/// Estimate the time left in a phase by extrapolating its average rate
+pub fn estimate_eta(elapsed: Duration, current: u64, total: u64) -> Option<Duration> {
+    if current == 0 || current >= total || elapsed < MIN_ETA_ELAPSED {
+        return None;
+    }
+    let per_item = elapsed.as_secs_f64() / current as f64;
+    Some(Duration::from_secs_f64(per_item * (total - current) as f64))
+}
+
+/// Percent complete, or 0 when the total is unknown
+pub fn percent(current: u64, total: Option<u64>) -> f32 {
+    match total {
+        Some(0) => 100.0,
+        Some(total) => (current.min(total) as f32 / total as f32) * 100.0,
+        None => 0.0,
+    }
+}
+
+/// Per-job progress state: phase timing, last log line and DB write throttling
+pub struct ProgressTracker {
+    phase: String,
+    phase_started: Instant,
+    last_log: Option<String>,
+    last_persisted: Option<Instant>,
+}
+
+impl ProgressTracker {
+    pub fn new() -> Self {
+        Self {
+            phase: String::new(),
+            phase_started: Instant::now(),
+            last_log: None,
+            last_persisted: None,
+        }
+    }
+
+    /// Record progress in `phase`, restarting the phase clock when it changes.
+    /// Returns the ETA for the phase, if one can be estimated.
+    pub fn update(&mut self, phase: &str, current: u64, total: Option<u64>) -> Option<Duration> {
+        if self.phase != phase {
+            self.phase = phase.to_string();
+            self.phase_started = Instant::now();
+        }
+        total.and_then(|total| estimate_eta(self.phase_started.elapsed(), current, total))
+    }
+
+    pub fn set_last_log(&mut self, message: &str) {
+        self.last_log = Some(message.to_string());
+    }
+
+    pub fn last_log(&self) -> Option<&str> {
+        self.last_log.as_deref()
+    }
+
+    /// Whether progress should be written to the database now (throttled,
+    /// except for the final update of a phase)
+    pub fn should_persist(&mut self, finished: bool) -> bool {
+        let due = finished
+            || self
+                .last_persisted
+                .is_none_or(|at| at.elapsed() >= PERSIST_INTERVAL);
+        if due {
+            self.last_persisted = Some(Instant::now());
+        }
+        due
+    }
+}
+
+impl Default for ProgressTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_eta() {
+        let eta = estimate_eta(Duration::from_secs(10), 25, 100);
+        assert_eq!(eta, Some(Duration::from_secs(30)));
+        assert_eq!(estimate_eta(Duration::from_secs(10), 0, 100), None);
+        assert_eq!(estimate_eta(Duration::from_secs(10), 100, 100), None);
+        assert_eq!(estimate_eta(Duration::from_millis(500), 50, 100), None);
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(percent(1, Some(4)), 25.0);
+        assert_eq!(percent(5, Some(4)), 100.0);
+        assert_eq!(percent(0, Some(0)), 100.0);
+        assert_eq!(percent(3, None), 0.0);
+    }
+
+    #[test]
+    fn test_tracker() {
+        let mut tracker = ProgressTracker::new();
+        assert_eq!(tracker.update("indexing", 0, Some(10)), None);
+        assert!(tracker.should_persist(false));
+        assert!(!tracker.should_persist(false));
+        assert!(tracker.should_persist(true));
+
+        assert_eq!(tracker.last_log(), None);
+        tracker.set_last_log("Indexed notes.md");
+        assert_eq!(tracker.last_log(), Some("Indexed notes.md"));
+    }
+}
@@ -0,0 +1,329 @@
+//! Job priorities and concurrency limits
+//!
+//! Running jobs hold a slot in their concurrency group (one embedding job,
+//! up to three ingestion jobs, ...) and in a global pool. When slots free
+//! up, the highest-priority waiting job that fits starts first, so a long
+//! re-embed can't starve user-facing work.
+
+use super::{CancellationToken, JobPriority, JobType, BATCH_GENERATE_JOB};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Maximum number of jobs running at once across all groups
+pub const MAX_CONCURRENT_JOBS: usize = 4;
+
+/// How often a waiting job re-checks its cancellation token
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Default concurrency limit for a group
+pub fn default_limit(group: &str) -> usize {
+    match group {
+        "ingest" => 3,
+        "index" | "embeddings" | "backup" | BATCH_GENERATE_JOB => 1,
+        _ => 2,
+    }
+}
+
+struct Waiter {
+    seq: u64,
+    group: String,
+    priority: JobPriority,
+}
+
+#[derive(Default)]
+struct QueueState {
+    running: HashMap<String, usize>,
+    total_running: usize,
+    waiting: Vec<Waiter>,
+    next_seq: u64,
+    limits: HashMap<String, usize>,
+}
+
+impl QueueState {
+    fn limit(&self, group: &str) -> usize {
+        self.limits
+            .get(group)
+            .copied()
+            .unwrap_or_else(|| default_limit(group))
+    }
+
+    fn has_capacity(&self, group: &str) -> bool {
+        self.total_running < MAX_CONCURRENT_JOBS
+            && self.running.get(group).copied().unwrap_or(0) < self.limit(group)
+    }
+
+    /// Start waiter `seq` if its group has capacity and no waiter ahead of
+    /// it (higher priority, or same priority and queued earlier) could start
+    fn try_start(&mut self, seq: u64) -> bool {
+        let Some(index) = self.waiting.iter().position(|w| w.seq == seq) else {
+            return false;
+        };
+        let waiter = &self.waiting[index];
+        if !self.has_capacity(&waiter.group) {
+            return false;
+        }
+        let blocked = self.waiting.iter().any(|other| {
+            (other.priority, std::cmp::Reverse(other.seq))
+                > (waiter.priority, std::cmp::Reverse(waiter.seq))
+                && self.has_capacity(&other.group)
+        });
+        if blocked {
+            return false;
+        }
+
+        let waiter = self.waiting.remove(index);
+        *self.running.entry(waiter.group).or_default() += 1;
+        self.total_running += 1;
+        true
+    }
+}
+
+/// Concurrency slots for running jobs
+#[derive(Default)]
+pub struct JobQueue {
+    state: Mutex<QueueState>,
+    notify: Notify,
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the concurrency limit for a group
+    pub fn set_limit(&self, group: &str, limit: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.limits.insert(group.to_string(), limit.max(1));
+        drop(state);
+        self.notify.notify_waiters();
+    }
+
+    /// Number of running jobs in a group
+    pub fn running(&self, group: &str) -> usize {
+        let state = self.state.lock().unwrap();
+        state.running.get(group).copied().unwrap_or(0)
+    }
+
+    /// Number of jobs waiting for a slot
+    pub fn waiting(&self) -> usize {
+        self.state.lock().unwrap().waiting.len()
+    }
+
+    /// Wait for a slot. Returns None if the job is cancelled while waiting.
+    pub async fn acquire(
+        &self,
+        job_type: &JobType,
+        priority: JobPriority,
+        cancel_token: &CancellationToken,
+    ) -> Option<JobPermit<'_>> {
+        let group = job_type.concurrency_group().to_string();
+        let seq = {
+            let mut state = self.state.lock().unwrap();
+            let seq = state.next_seq;
+            state.next_seq += 1;
+            state.waiting.push(Waiter {
+                seq,
+                group: group.clone(),
+                priority,
+            });
+            seq
+        };
+        let mut waiting = WaitingGuard {
+            queue: self,
+            seq,
+            active: true,
+        };
+
+        loop {
+            // Register for wakeups before checking so a release isn't missed
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if self.state.lock().unwrap().try_start(seq) {
+                waiting.active = false;
+                return Some(JobPermit { queue: self, group });
+            }
+            if cancel_token.is_cancelled() {
+                return None;
+            }
+            let _ = tokio::time::timeout(CANCEL_POLL_INTERVAL, notified).await;
+        }
+    }
+
+    fn release(&self, group: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(count) = state.running.get_mut(group) {
+            *count = count.saturating_sub(1);
+        }
+        state.total_running = state.total_running.saturating_sub(1);
+        drop(state);
+        self.notify.notify_waiters();
+    }
+}
+
+/// Removes a waiter that gave up (cancelled or dropped) from the queue
+struct WaitingGuard<'a> {
+    queue: &'a JobQueue,
+    seq: u64,
+    active: bool,
+}
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        if self.active {
+            let mut state = self.queue.state.lock().unwrap();
+            state.waiting.retain(|w| w.seq != self.seq);
+            drop(state);
+            self.queue.notify.notify_waiters();
+        }
+    }
+}
+
+/// A held concurrency slot, released on drop
+pub struct JobPermit<'a> {
+    queue: &'a JobQueue,
+    group: String,
+}
+
+impl JobPermit<'_> {
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+}
+
+impl Drop for JobPermit<'_> {
+    fn drop(&mut self) {
+        self.queue.release(&self.group);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn token() -> CancellationToken {
+        CancellationToken::new()
+    }
+
+    #[tokio::test]
+    async fn test_group_limit() {
+        let queue = JobQueue::new();
+        let first = queue
+            .acquire(&JobType::GenerateEmbeddings, JobPriority::Normal, &token())
+            .await
+            .unwrap();
+        assert_eq!(first.group(), "embeddings");
+
+        // A second embedding job waits until the first finishes
+        let second = tokio::time::timeout(
+            Duration::from_millis(50),
+            queue.acquire(&JobType::GenerateEmbeddings, JobPriority::High, &token()),
+        )
+        .await;
+        assert!(second.is_err());
+        assert_eq!(queue.waiting(), 0);
+
+        // Other groups are not blocked
+        let ingest = queue
+            .acquire(&JobType::IngestWeb, JobPriority::Normal, &token())
+            .await
+            .unwrap();
+        assert_eq!(queue.running("ingest"), 1);
+
+        drop(first);
+        drop(ingest);
+        let _again = queue
+            .acquire(&JobType::GenerateEmbeddings, JobPriority::Low, &token())
+            .await
+            .unwrap();
+        assert_eq!(queue.running("embeddings"), 1);
+        assert_eq!(queue.running("ingest"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_priority_order() {
+        let queue = Arc::new(JobQueue::new());
+        queue.set_limit("index", 1);
+        let held = queue
+            .acquire(&JobType::IndexKb, JobPriority::Normal, &token())
+            .await
+            .unwrap();
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut handles = Vec::new();
+        for priority in [JobPriority::Low, JobPriority::High, JobPriority::Normal] {
+            let task_queue = queue.clone();
+            let order = order.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = task_queue
+                    .acquire(&JobType::IndexKb, priority, &token())
+                    .await
+                    .unwrap();
+                order.lock().unwrap().push(priority);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }));
+            // Make sure each task is queued before the next one
+            while queue.waiting() < handles.len() {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        drop(held);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec![JobPriority::High, JobPriority::Normal, JobPriority::Low]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_while_waiting() {
+        let queue = JobQueue::new();
+        let _held = queue
+            .acquire(&JobType::Backup, JobPriority::Normal, &token())
+            .await
+            .unwrap();
+
+        let cancel = token();
+        cancel.cancel();
+        let permit = queue
+            .acquire(&JobType::Backup, JobPriority::High, &cancel)
+            .await;
+        assert!(permit.is_none());
+        assert_eq!(queue.waiting(), 0);
+        assert_eq!(queue.running("backup"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_global_limit() {
+        let queue = JobQueue::new();
+        let mut permits = Vec::new();
+        for i in 0..MAX_CONCURRENT_JOBS {
+            let job_type = JobType::Custom(format!("custom_{}", i));
+            permits.push(
+                queue
+                    .acquire(&job_type, JobPriority::Normal, &token())
+                    .await
+                    .unwrap(),
+            );
+        }
+        let blocked = tokio::time::timeout(
+            Duration::from_millis(50),
+            queue.acquire(&JobType::IndexKb, JobPriority::High, &token()),
+        )
+        .await;
+        assert!(blocked.is_err());
+
+        permits.pop();
+        assert!(queue
+            .acquire(&JobType::IndexKb, JobPriority::High, &token())
+            .await
+            .is_some());
+    }
+}
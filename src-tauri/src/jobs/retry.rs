@@ -0,0 +1,332 @@
+//! Retry policies for jobs
+//!
+//! Network-bound job types retry transient failures (timeouts, dropped
+//! connections, rate limits, 5xx responses) with exponential backoff.
+//! Permanent failures such as invalid URLs, blocked hosts or missing
+//! credentials fail immediately.
+
+use super::JobType;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+
+/// Error fragments that indicate a permanent failure
+const PERMANENT_MARKERS: &[&str] = &[
+    "ssrf blocked",
+    "invalid url",
+    "invalid source",
+    "offline mode",
+    "not found",
+    "authentication",
+    "unauthorized",
+    "forbidden",
+    "cancelled",
+    "too large",
+];
+
+/// Error fragments that indicate a transient failure
+const TRANSIENT_MARKERS: &[&str] = &[
+    "timeout",
+    "timed out",
+    "rate limited",
+    "too many requests",
+    "connection reset",
+    "connection refused",
+    "connection closed",
+    "broken pipe",
+    "temporarily unavailable",
+    "dns resolution failed",
+    "error sending request",
+    "could not resolve host",
+    "failed to connect",
+    "remote end hung up",
+    "early eof",
+    "rpc failed",
+    "http 429",
+    "http 502",
+    "http 503",
+    "http 504",
+    "returned error: 502",
+    "returned error: 503",
+    "returned error: 504",
+];
+
+/// Retry policy for a job type
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total attempts including the first (1 = no retries)
+    pub max_attempts: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    pub multiplier: f64,
+}
+
+impl RetryPolicy {
+    /// Run once, never retry
+    pub const fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff_ms: 0,
+            max_backoff_ms: 0,
+            multiplier: 1.0,
+        }
+    }
+
+    /// Default for network-bound work: 4 attempts, 2s → 4s → 8s
+    pub const fn network() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff_ms: 2_000,
+            max_backoff_ms: 60_000,
+            multiplier: 2.0,
+        }
+    }
+
+    /// Default policy for a job type
+    pub fn for_job_type(job_type: &JobType) -> Self {
+        match job_type {
+            JobType::IngestWeb
+            | JobType::IngestYoutube
+            | JobType::IngestGithub
+            | JobType::IngestBatch => Self::network(),
+            _ => Self::none(),
+        }
+    }
+
+    /// Delay before retry number `retry` (1 = first retry)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.powi(retry.saturating_sub(1).min(32) as i32);
+        let delay = (self.initial_backoff_ms as f64 * factor).min(self.max_backoff_ms as f64);
+        Duration::from_millis(delay as u64)
+    }
+
+    /// Whether a failure on `attempt` (1-based) should be retried
+    pub fn should_retry(&self, attempt: u32, retryable: bool) -> bool {
+        retryable && attempt < self.max_attempts
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Classify an error message as transient (worth retrying)
+pub fn is_retryable_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    if PERMANENT_MARKERS.iter().any(|m| message.contains(m)) {
+        return false;
+    }
+    TRANSIENT_MARKERS.iter().any(|m| message.contains(m))
+}
+
+/// Run `op` until it succeeds, fails permanently or runs out of attempts.
+/// `on_retry(attempt, error, delay)` is called before each backoff sleep and
+/// can return false to stop (e.g. when the job was cancelled).
+pub async fn retry_async<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    is_retryable: impl Fn(&E) -> bool,
+    mut on_retry: impl FnMut(u32, &E, Duration) -> bool,
+    mut op: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                if !policy.should_retry(attempt, is_retryable(&e)) {
+                    return Err(e);
+                }
+                let delay = policy.backoff(attempt);
+                if !on_retry(attempt, &e, delay) {
+                    return Err(e);
+                }
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Blocking variant of [`retry_async`] for synchronous operations
+pub fn retry_blocking<T, E>(
+    policy: &RetryPolicy,
+    is_retryable: impl Fn(&E) -> bool,
+    mut on_retry: impl FnMut(u32, &E, Duration) -> bool,
+    mut op: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                if !policy.should_retry(attempt, is_retryable(&e)) {
+                    return Err(e);
+                }
+                let delay = policy.backoff(attempt);
+                if !on_retry(attempt, &e, delay) {
+                    return Err(e);
+                }
+                std::thread::sleep(delay);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 4,
+            multiplier: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy::network();
+        assert_eq!(policy.backoff(1), Duration::from_secs(2));
+        assert_eq!(policy.backoff(2), Duration::from_secs(4));
+        assert_eq!(policy.backoff(3), Duration::from_secs(8));
+        assert_eq!(policy.backoff(10), Duration::from_secs(60));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_policy_by_job_type() {
+        assert_eq!(
+            RetryPolicy::for_job_type(&JobType::IngestWeb),
+            RetryPolicy::network()
+        );
+        assert_eq!(
+            RetryPolicy::for_job_type(&JobType::IndexKb),
+            RetryPolicy::none()
+        );
+        assert!(!RetryPolicy::none().should_retry(1, true));
+    }
+
+    #[test]
+    fn test_error_classification() {
+        assert!(is_retryable_error("Network error: Request timeout"));
+        assert!(is_retryable_error(
+            "Network error: Network request failed: connection reset by peer"
+        ));
+        assert!(!is_retryable_error(
+            "Network error: Network request failed: HTTP 400 Bad Request"
+        ));
+        assert!(is_retryable_error(
+            "API error: HTTP 503 Service Unavailable"
+        ));
+        assert!(is_retryable_error("Rate limited: retry later"));
+        assert!(is_retryable_error(
+            "fatal: unable to access 'https://github.com/a/b/': Could not resolve host: github.com"
+        ));
+        assert!(!is_retryable_error("Network error: SSRF blocked: 10.0.0.1"));
+        assert!(!is_retryable_error("Content not found: 404"));
+        assert!(!is_retryable_error(
+            "Authentication required: token expired"
+        ));
+        assert!(!is_retryable_error(
+            "Parsing error: unexpected end of input"
+        ));
+    }
+
+    #[test]
+    fn test_retry_blocking() {
+        let calls = Cell::new(0);
+        let result: Result<u32, &str> = retry_blocking(
+            &fast_policy(3),
+            |_| true,
+            |_, _, _| true,
+            || {
+                calls.set(calls.get() + 1);
+                if calls.get() < 3 {
+                    Err("timeout")
+                } else {
+                    Ok(calls.get())
+                }
+            },
+        );
+        assert_eq!(result, Ok(3));
+
+        // Permanent errors are not retried
+        calls.set(0);
+        let result: Result<(), &str> = retry_blocking(
+            &fast_policy(3),
+            |e: &&str| is_retryable_error(e),
+            |_, _, _| true,
+            || {
+                calls.set(calls.get() + 1);
+                Err("invalid url")
+            },
+        );
+        assert_eq!(result, Err("invalid url"));
+        assert_eq!(calls.get(), 1);
+
+        // Attempts are bounded, and on_retry can stop early
+        calls.set(0);
+        let _: Result<(), &str> = retry_blocking(
+            &fast_policy(3),
+            |_| true,
+            |_, _, _| true,
+            || {
+                calls.set(calls.get() + 1);
+                Err("timeout")
+            },
+        );
+        assert_eq!(calls.get(), 3);
+        calls.set(0);
+        let _: Result<(), &str> = retry_blocking(
+            &fast_policy(3),
+            |_| true,
+            |_, _, _| false,
+            || {
+                calls.set(calls.get() + 1);
+                Err("timeout")
+            },
+        );
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_async() {
+        let calls = Cell::new(0);
+        let mut retries = Vec::new();
+        let result: Result<&str, String> = retry_async(
+            &fast_policy(4),
+            |e: &String| is_retryable_error(e),
+            |attempt, _, delay| {
+                retries.push((attempt, delay));
+                true
+            },
+            || {
+                calls.set(calls.get() + 1);
+                let n = calls.get();
+                async move {
+                    if n < 3 {
+                        Err("HTTP 502 Bad Gateway".to_string())
+                    } else {
+                        Ok("done")
+                    }
+                }
+            },
+        )
+        .await;
+        assert_eq!(result, Ok("done"));
+        assert_eq!(
+            retries,
+            vec![(1, Duration::from_millis(1)), (2, Duration::from_millis(2))]
+        );
+    }
+}
@@ -0,0 +1,409 @@
+//! Recurring job schedules
+//!
+//! Schedules use standard five-field cron expressions
+//! (`minute hour day-of-month month day-of-week`), evaluated in local time so
+//! "0 2 * * *" means 2am on the user's clock. Schedules are stored in the
+//! `job_schedules` table; each run creates a regular job record.
+
+use super::{Job, JobStatus, JobType};
+use chrono::{
+    DateTime, Datelike, Duration, Local, LocalResult, NaiveDate, TimeZone, Timelike, Utc,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// How far ahead to search for the next matching time
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CronError {
+    #[error("Expected 5 fields (minute hour day month weekday), found {0}")]
+    FieldCount(usize),
+    #[error("Invalid {field} value: {value}")]
+    InvalidValue { field: &'static str, value: String },
+    #[error("Expression never matches a date")]
+    NeverMatches,
+}
+
+struct Field {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    names: &'static [&'static str],
+}
+
+const MINUTE: Field = Field {
+    name: "minute",
+    min: 0,
+    max: 59,
+    names: &[],
+};
+const HOUR: Field = Field {
+    name: "hour",
+    min: 0,
+    max: 23,
+    names: &[],
+};
+const DAY_OF_MONTH: Field = Field {
+    name: "day-of-month",
+    min: 1,
+    max: 31,
+    names: &[],
+};
+const MONTH: Field = Field {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: &[
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ],
+};
+const DAY_OF_WEEK: Field = Field {
+    name: "day-of-week",
+    min: 0,
+    max: 7,
+    names: &["sun", "mon", "tue", "wed", "thu", "fri", "sat"],
+};
+
+impl Field {
+    fn value(&self, token: &str) -> Option<u32> {
+        let lower = token.to_ascii_lowercase();
+        if let Some(index) = self.names.iter().position(|n| *n == lower) {
+            return Some(index as u32 + if self.names.len() == 12 { 1 } else { 0 });
+        }
+        token
+            .parse::<u32>()
+            .ok()
+            .filter(|v| (self.min..=self.max).contains(v))
+    }
+
+    /// Parse one field into a bitmask (bit N set = value N matches).
+    /// Returns the mask and whether the field was restricted (not `*`).
+    fn parse(&self, spec: &str) -> Result<(u64, bool), CronError> {
+        let invalid = || CronError::InvalidValue {
+            field: self.name,
+            value: spec.to_string(),
+        };
+        let mut mask = 0u64;
+        for item in spec.split(',') {
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => {
+                    let step = step.parse::<u32>().ok().filter(|s| *s > 0);
+                    (range, Some(step.ok_or_else(invalid)?))
+                }
+                None => (item, None),
+            };
+            let (start, end) = if range == "*" {
+                (self.min, self.max)
+            } else if let Some((a, b)) = range.split_once('-') {
+                let (a, b) = (
+                    self.value(a).ok_or_else(invalid)?,
+                    self.value(b).ok_or_else(invalid)?,
+                );
+                if a > b {
+                    return Err(invalid());
+                }
+                (a, b)
+            } else {
+                let a = self.value(range).ok_or_else(invalid)?;
+                // "5/15" means every 15 starting at 5
+                (a, if step.is_some() { self.max } else { a })
+            };
+            let step = step.unwrap_or(1);
+            let mut v = start;
+            while v <= end {
+                mask |= 1 << v;
+                v += step;
+            }
+        }
+        Ok((mask, spec != "*"))
+    }
+}
+
+/// A parsed cron expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl std::str::FromStr for CronSchedule {
+    type Err = CronError;
+
+    fn from_str(expr: &str) -> Result<Self, Self::Err> {
+        let expr = match expr.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(CronError::FieldCount(fields.len()));
+        }
+
+        let (minutes, _) = MINUTE.parse(fields[0])?;
+        let (hours, _) = HOUR.parse(fields[1])?;
+        let (days_of_month, day_of_month_restricted) = DAY_OF_MONTH.parse(fields[2])?;
+        let (months, _) = MONTH.parse(fields[3])?;
+        let (mut days_of_week, day_of_week_restricted) = DAY_OF_WEEK.parse(fields[4])?;
+        // 7 is an alias for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        let schedule = Self {
+            minutes,
+            hours,
+            days_of_month,
+            months,
+            days_of_week,
+            day_of_month_restricted,
+            day_of_week_restricted,
+        };
+        schedule
+            .next_after(&Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap())
+            .ok_or(CronError::NeverMatches)?;
+        Ok(schedule)
+    }
+}
+
+impl CronSchedule {
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let dom = self.days_of_month & (1 << date.day()) != 0;
+        let dow = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+        // Standard cron: when both day fields are restricted, either may match
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            dom || dow
+        } else {
+            dom && dow
+        }
+    }
+
+    /// The first matching time strictly after `after`, in the same time zone
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let mut t = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = t + Duration::days(MAX_LOOKAHEAD_DAYS);
+
+        while t <= limit {
+            if self.months & (1 << t.month()) == 0 {
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.day_matches(t.date()) {
+                t = t.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if self.hours & (1 << t.hour()) == 0 {
+                t = t.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if self.minutes & (1 << t.minute()) == 0 {
+                t += Duration::minutes(1);
+                continue;
+            }
+            match tz.from_local_datetime(&t) {
+                LocalResult::Single(dt) if dt > *after => return Some(dt),
+                LocalResult::Ambiguous(first, second) => {
+                    if first > *after {
+                        return Some(first);
+                    }
+                    if second > *after {
+                        return Some(second);
+                    }
+                }
+                // Skipped by a DST transition
+                _ => {}
+            }
+            t += Duration::minutes(1);
+        }
+        None
+    }
+
+    /// The next `count` matching times after `after`
+    pub fn upcoming<Tz: TimeZone>(&self, after: &DateTime<Tz>, count: usize) -> Vec<DateTime<Tz>> {
+        let mut times = Vec::with_capacity(count);
+        let mut cursor = after.clone();
+        while times.len() < count {
+            match self.next_after(&cursor) {
+                Some(next) => {
+                    cursor = next.clone();
+                    times.push(next);
+                }
+                None => break,
+            }
+        }
+        times
+    }
+}
+
+/// Next run time for a cron expression, evaluated in local time
+pub fn next_run_after(
+    cron: &str,
+    after: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, CronError> {
+    let schedule: CronSchedule = cron.parse()?;
+    Ok(schedule
+        .next_after(&after.with_timezone(&Local))
+        .map(|t| t.with_timezone(&Utc)))
+}
+
+/// A persistent recurring job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobSchedule {
+    pub id: String,
+    pub name: String,
+    /// Five-field cron expression (local time)
+    pub cron: String,
+    pub job_type: JobType,
+    /// Job-type specific parameters, copied into each job's metadata
+    pub params: Option<serde_json::Value>,
+    pub enabled: bool,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_job_id: Option<String>,
+    /// Status of the most recent run's job
+    pub last_status: Option<JobStatus>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl JobSchedule {
+    pub fn new(
+        name: &str,
+        cron: &str,
+        job_type: JobType,
+        params: Option<serde_json::Value>,
+    ) -> Result<Self, CronError> {
+        let now = Utc::now();
+        Ok(Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            cron: cron.trim().to_string(),
+            job_type,
+            params,
+            enabled: true,
+            next_run_at: next_run_after(cron, now)?,
+            last_run_at: None,
+            last_job_id: None,
+            last_status: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Whether the schedule should run at `now`
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.enabled && self.next_run_at.is_some_and(|t| t <= now)
+    }
+
+    /// Build the job for one run of this schedule
+    pub fn create_job(&self) -> Job {
+        Job::new(self.job_type.clone()).with_metadata(serde_json::json!({
+            "schedule_id": self.id,
+            "schedule_name": self.name,
+            "params": self.params,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    fn next(expr: &str, after: DateTime<Utc>) -> DateTime<Utc> {
+        expr.parse::<CronSchedule>()
+            .unwrap()
+            .next_after(&after)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_next_after() {
+        // 2026-03-04 is a Wednesday
+        let now = at(2026, 3, 4, 10, 17);
+        assert_eq!(next("* * * * *", now), at(2026, 3, 4, 10, 18));
+        assert_eq!(next("*/15 * * * *", now), at(2026, 3, 4, 10, 30));
+        assert_eq!(next("0 2 * * *", now), at(2026, 3, 5, 2, 0));
+        assert_eq!(next("30 9 * * mon-fri", now), at(2026, 3, 5, 9, 30));
+        assert_eq!(next("0 0 * * 0", now), at(2026, 3, 8, 0, 0));
+        assert_eq!(next("0 0 * * 7", now), at(2026, 3, 8, 0, 0));
+        assert_eq!(next("@monthly", now), at(2026, 4, 1, 0, 0));
+        assert_eq!(next("0 6 1,15 * *", now), at(2026, 3, 15, 6, 0));
+        assert_eq!(next("0 0 29 2 *", now), at(2028, 2, 29, 0, 0));
+        // Both day fields restricted: either matches (the 1st or any Friday)
+        assert_eq!(next("0 0 1 * fri", now), at(2026, 3, 6, 0, 0));
+        // Strictly after, even when `now` matches
+        assert_eq!(next("17 10 * * *", now), at(2026, 3, 5, 10, 17));
+    }
+
+    #[test]
+    fn test_upcoming() {
+        let schedule: CronSchedule = "0 */6 * * *".parse().unwrap();
+        let runs = schedule.upcoming(&at(2026, 3, 4, 10, 17), 3);
+        assert_eq!(
+            runs,
+            vec![
+                at(2026, 3, 4, 12, 0),
+                at(2026, 3, 4, 18, 0),
+                at(2026, 3, 5, 0, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "* * * *".parse::<CronSchedule>(),
+            Err(CronError::FieldCount(4))
+        );
+        assert!(matches!(
+            "60 * * * *".parse::<CronSchedule>(),
+            Err(CronError::InvalidValue {
+                field: "minute",
+                ..
+            })
+        ));
+        assert!("* * * * 8".parse::<CronSchedule>().is_err());
+        assert!("*/0 * * * *".parse::<CronSchedule>().is_err());
+        assert!("5-1 * * * *".parse::<CronSchedule>().is_err());
+        assert_eq!(
+            "0 0 31 2 *".parse::<CronSchedule>(),
+            Err(CronError::NeverMatches)
+        );
+    }
+
+    #[test]
+    fn test_schedule_due() {
+        let mut schedule =
+            JobSchedule::new("Nightly re-index", "0 2 * * *", JobType::IndexKb, None).unwrap();
+        let next_run = schedule.next_run_at.unwrap();
+        assert!(next_run > Utc::now());
+        assert!(!schedule.is_due(Utc::now()));
+        assert!(schedule.is_due(next_run));
+
+        schedule.enabled = false;
+        assert!(!schedule.is_due(next_run));
+
+        let job = schedule.create_job();
+        assert_eq!(job.job_type, JobType::IndexKb);
+        assert_eq!(job.metadata.unwrap()["schedule_id"], schedule.id.as_str());
+    }
+}
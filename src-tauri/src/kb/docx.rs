@@ -3,6 +3,9 @@
 use std::path::Path;
 use thiserror::Error;
 
+use super::indexer::{Section, SectionBuilder};
+use super::office::EmbeddedImage;
+
 #[derive(Debug, Error)]
 pub enum DocxError {
     #[error("IO error: {0}")]
@@ -11,34 +14,62 @@ pub enum DocxError {
     Parse(String),
 }
 
-/// Extract text content from a DOCX file
-pub fn extract_text(path: &Path) -> Result<String, DocxError> {
+/// Extract a DOCX file into sections, starting a new section at each
+/// heading-styled paragraph
+pub fn extract_sections(path: &Path) -> Result<Vec<Section>, DocxError> {
     let file_bytes = std::fs::read(path)?;
 
     let doc = docx_rs::read_docx(&file_bytes).map_err(|e| DocxError::Parse(e.to_string()))?;
 
-    let mut text = String::new();
+    let mut builder = SectionBuilder::default();
 
     // Iterate through the document body children
     for child in &doc.document.children {
-        extract_from_document_child(child, &mut text);
+        match child {
+            docx_rs::DocumentChild::Paragraph(p) => {
+                let mut text = String::new();
+                extract_from_paragraph(p, &mut text);
+                let level = p
+                    .property
+                    .style
+                    .as_ref()
+                    .and_then(|style| heading_level(&style.val));
+                match level {
+                    Some(level) if !text.trim().is_empty() => builder.heading(&text, level),
+                    _ => {
+                        builder.push(&text);
+                        builder.push("\n");
+                    }
+                }
+            }
+            docx_rs::DocumentChild::Table(table) => {
+                let mut text = String::new();
+                extract_from_table(table, &mut text);
+                builder.push(&text);
+                builder.push("\n");
+            }
+            _ => {}
+        }
     }
 
-    Ok(text)
+    Ok(builder.finish())
 }
 
-fn extract_from_document_child(child: &docx_rs::DocumentChild, text: &mut String) {
-    match child {
-        docx_rs::DocumentChild::Paragraph(p) => {
-            extract_from_paragraph(p, text);
-            text.push('\n');
-        }
-        docx_rs::DocumentChild::Table(table) => {
-            extract_from_table(table, text);
-            text.push('\n');
-        }
-        _ => {}
+/// Extract images embedded in a DOCX file (`word/media/`)
+pub fn extract_images(path: &Path) -> Result<Vec<EmbeddedImage>, DocxError> {
+    Ok(super::office::extract_images(path, "word/media/")?)
+}
+
+/// Heading level for a paragraph style id like `Heading2` or `Title`
+fn heading_level(style_id: &str) -> Option<u8> {
+    let id = style_id.to_lowercase().replace([' ', '_', '-'], "");
+    if id == "title" {
+        return Some(1);
     }
+    id.strip_prefix("heading")?
+        .parse::<u8>()
+        .ok()
+        .filter(|level| (1..=9).contains(level))
 }
 
 fn extract_from_paragraph(p: &docx_rs::Paragraph, text: &mut String) {
@@ -103,7 +134,52 @@ mod tests {
 
     #[test]
     fn test_extract_nonexistent() {
-        let result = extract_text(Path::new("/nonexistent.docx"));
+        let result = extract_sections(Path::new("/nonexistent.docx"));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_heading_level() {
+        assert_eq!(heading_level("Heading1"), Some(1));
+        assert_eq!(heading_level("heading 3"), Some(3));
+        assert_eq!(heading_level("Title"), Some(1));
+        assert_eq!(heading_level("Normal"), None);
+        assert_eq!(heading_level("Heading10"), None);
+    }
+
+    #[test]
+    fn test_extract_sections_splits_at_headings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("runbook.docx");
+        let file = std::fs::File::create(&path).unwrap();
+        docx_rs::Docx::new()
+            .add_paragraph(
+                docx_rs::Paragraph::new()
+                    .add_run(docx_rs::Run::new().add_text("VPN Runbook"))
+                    .style("Title"),
+            )
+            .add_paragraph(
+                docx_rs::Paragraph::new().add_run(docx_rs::Run::new().add_text("Overview text.")),
+            )
+            .add_paragraph(
+                docx_rs::Paragraph::new()
+                    .add_run(docx_rs::Run::new().add_text("Reset"))
+                    .style("Heading2"),
+            )
+            .add_paragraph(
+                docx_rs::Paragraph::new()
+                    .add_run(docx_rs::Run::new().add_text("Restart the client.")),
+            )
+            .build()
+            .pack(file)
+            .unwrap();
+
+        let sections = extract_sections(&path).unwrap();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].heading.as_deref(), Some("VPN Runbook"));
+        assert!(sections[0].content.contains("Overview text."));
+        assert_eq!(sections[1].heading.as_deref(), Some("Reset"));
+        assert_eq!(sections[1].level, 2);
+        assert!(extract_images(&path).unwrap().is_empty());
+    }
 }
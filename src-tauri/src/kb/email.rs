@@ -0,0 +1,444 @@
+//! Email (EML/MSG) extraction for KB indexing
+//!
+//! Exported support emails are indexed by their own text: the preferred
+//! plain-text body (or HTML rendered to text), with quoted reply chains
+//! removed so each message in a thread contributes only what it added.
+//! From/To/Date/Subject travel on every chunk through the section heading.
+
+use std::io::Read;
+use std::path::Path;
+
+use regex_lite::Regex;
+use thiserror::Error;
+
+use super::indexer::Section;
+use super::mime;
+
+#[derive(Debug, Error)]
+pub enum EmailError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Email parse error: {0}")]
+    Parse(String),
+}
+
+/// Nested multipart levels followed before giving up
+const MAX_MULTIPART_DEPTH: usize = 8;
+
+/// Largest MSG property stream read (bodies of huge threads are truncated)
+const MAX_MSG_STREAM_BYTES: u64 = 10 * 1024 * 1024;
+
+/// A parsed email message
+#[derive(Debug, Default, Clone)]
+pub struct EmailMessage {
+    pub subject: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub date: Option<String>,
+    /// Address of the mailbox the message was delivered to
+    pub mailbox: Option<String>,
+    /// Body text with quoted replies removed
+    pub body: String,
+}
+
+impl EmailMessage {
+    /// One section headed by subject and sender metadata, so every chunk
+    /// carries From/To/Date/Subject in its heading path
+    pub fn into_sections(self) -> Vec<Section> {
+        if self.body.trim().is_empty() {
+            return Vec::new();
+        }
+        let subject = self
+            .subject
+            .clone()
+            .unwrap_or_else(|| "(no subject)".to_string());
+        let metadata: Vec<String> = [("From", &self.from), ("To", &self.to), ("Date", &self.date)]
+            .into_iter()
+            .filter_map(|(label, value)| value.as_ref().map(|v| format!("{}: {}", label, v)))
+            .collect();
+        let heading = if metadata.is_empty() {
+            subject
+        } else {
+            format!("{} ({})", subject, metadata.join("; "))
+        };
+
+        vec![Section {
+            heading: Some(heading),
+            level: 1,
+            content: self.body,
+            page: None,
+        }]
+    }
+}
+
+/// Parse an RFC 822 (`.eml`) message
+pub fn parse_eml(bytes: &[u8]) -> Result<EmailMessage, EmailError> {
+    let raw = String::from_utf8_lossy(bytes);
+    let (headers, body) = mime::split_headers(&raw);
+    if headers.is_empty() {
+        return Err(EmailError::Parse("no message headers".to_string()));
+    }
+
+    let header = |name: &str| {
+        mime::header_value(&headers, name)
+            .map(|v| mime::decode_encoded_words(&v))
+            .filter(|v| !v.trim().is_empty())
+    };
+    let text = find_body_text(&headers, body, 0)?.unwrap_or_default();
+
+    Ok(EmailMessage {
+        subject: header("subject"),
+        from: header("from"),
+        to: header("to"),
+        date: header("date").map(|d| normalize_date(&d)),
+        mailbox: header("delivered-to")
+            .or_else(|| header("x-original-to"))
+            .or_else(|| header("to"))
+            .and_then(|v| first_address(&v)),
+        body: strip_quoted_reply(&text),
+    })
+}
+
+/// Parse an Outlook (`.msg`) message stored as an OLE compound file
+pub fn parse_msg(path: &Path) -> Result<EmailMessage, EmailError> {
+    let mut file = cfb::open(path)?;
+    let mut string_prop = |id: u16| -> Option<String> {
+        read_msg_string(&mut file, id)
+            .ok()
+            .flatten()
+            .filter(|v| !v.trim().is_empty())
+    };
+
+    let subject = string_prop(0x0037);
+    let sender_name = string_prop(0x0C1A);
+    let sender_email = string_prop(0x5D01).or_else(|| string_prop(0x0C1F));
+    let display_to = string_prop(0x0E04);
+    let received_by = string_prop(0x0076);
+    let transport_headers = string_prop(0x007D).unwrap_or_default();
+    let plain_body = string_prop(0x1000);
+    let html_body = if plain_body.is_none() {
+        read_msg_stream(&mut file, "__substg1.0_10130102")
+            .ok()
+            .flatten()
+            .map(|bytes| mime::decode_charset(&bytes, None))
+    } else {
+        None
+    };
+
+    let (headers, _) = mime::split_headers(&transport_headers);
+    let from = match (sender_name, sender_email) {
+        (Some(name), Some(email)) if name != email => Some(format!("{} <{}>", name, email)),
+        (name, email) => email.or(name),
+    };
+    let text = plain_body
+        .or_else(|| html_body.map(|html| html_to_text(&html)))
+        .unwrap_or_default();
+
+    Ok(EmailMessage {
+        subject,
+        from,
+        to: display_to.or_else(|| mime::header_value(&headers, "to")),
+        date: read_msg_submit_time(&mut file)
+            .or_else(|| mime::header_value(&headers, "date").map(|d| normalize_date(&d))),
+        mailbox: mime::header_value(&headers, "delivered-to")
+            .or(received_by)
+            .or_else(|| mime::header_value(&headers, "to"))
+            .and_then(|v| first_address(&v)),
+        body: strip_quoted_reply(&text),
+    })
+}
+
+/// Mailbox address of an email file, used to pick a per-mailbox namespace
+pub fn mailbox_for_file(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    let message = match ext.as_str() {
+        "eml" => parse_eml(&std::fs::read(path).ok()?).ok()?,
+        "msg" => parse_msg(path).ok()?,
+        _ => return None,
+    };
+    message.mailbox
+}
+
+/// Walk the MIME tree for the best body: the first `text/plain` part, else
+/// the first `text/html` part rendered to text. Attachments are skipped.
+fn find_body_text(
+    headers: &[(String, String)],
+    body: &str,
+    depth: usize,
+) -> Result<Option<String>, EmailError> {
+    let content_type = mime::content_type(headers);
+    if content_type.starts_with("multipart/") {
+        if depth >= MAX_MULTIPART_DEPTH {
+            return Ok(None);
+        }
+        let boundary = mime::header_value(headers, "content-type")
+            .and_then(|ct| mime::header_param(&ct, "boundary"))
+            .ok_or_else(|| EmailError::Parse("multipart without boundary".to_string()))?;
+
+        let mut html = None;
+        for (part_headers, part_body) in mime::parts(body, &boundary) {
+            let is_attachment = mime::header_value(&part_headers, "content-disposition")
+                .map(|d| d.to_ascii_lowercase().starts_with("attachment"))
+                .unwrap_or(false);
+            if is_attachment {
+                continue;
+            }
+            let part_type = mime::content_type(&part_headers);
+            if part_type == "text/html" {
+                html.get_or_insert((part_headers, part_body));
+                continue;
+            }
+            if let Some(text) = find_body_text(&part_headers, part_body, depth + 1)? {
+                return Ok(Some(text));
+            }
+        }
+        return match html {
+            Some((part_headers, part_body)) => Ok(Some(html_to_text(
+                &mime::decode_text(&part_headers, part_body).map_err(EmailError::Parse)?,
+            ))),
+            None => Ok(None),
+        };
+    }
+
+    let is_html = match content_type.as_str() {
+        "" | "text/plain" => false,
+        "text/html" => true,
+        _ => return Ok(None),
+    };
+    let decoded = mime::decode_text(headers, body).map_err(EmailError::Parse)?;
+    Ok(Some(if is_html {
+        html_to_text(&decoded)
+    } else {
+        decoded
+    }))
+}
+
+fn html_to_text(html: &str) -> String {
+    super::html::extract_html(html)
+        .sections
+        .into_iter()
+        .map(|section| match section.heading {
+            Some(heading) => format!("{}\n{}", heading, section.content),
+            None => section.content,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Drop quoted reply chains: `>`-prefixed lines, and everything after an
+/// "On ... wrote:" attribution or an Outlook "Original Message"/`From:`
+/// reply header
+pub fn strip_quoted_reply(body: &str) -> String {
+    let lines: Vec<&str> = body.lines().collect();
+    let mut kept: Vec<&str> = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if is_reply_boundary(trimmed, &lines[i + 1..]) && kept.iter().any(|l| !l.trim().is_empty())
+        {
+            break;
+        }
+        if trimmed.starts_with('>') {
+            continue;
+        }
+        kept.push(line.trim_end());
+    }
+
+    let text = kept.join("\n").trim().to_string();
+    if text.is_empty() {
+        body.trim().to_string()
+    } else {
+        text
+    }
+}
+
+fn is_reply_boundary(line: &str, rest: &[&str]) -> bool {
+    let lower = line.to_ascii_lowercase();
+    let next = rest.first().map(|l| l.trim()).unwrap_or_default();
+
+    // Gmail/Apple Mail: "On Tue, Jan 6, 2026 at 9:14 AM Jane <jane@x.com> wrote:",
+    // sometimes wrapped so "wrote:" lands on the next line
+    if lower.starts_with("on ") && (lower.ends_with("wrote:") || next.ends_with("wrote:")) {
+        return true;
+    }
+    if lower.contains("original message") && lower.starts_with("---") {
+        return true;
+    }
+    // Outlook: a rule line or a From:/Sent: header block
+    let starts_header_block = |lines: &[&str]| {
+        lines.first().map(|l| l.trim().starts_with("From:")) == Some(true)
+            && lines
+                .iter()
+                .skip(1)
+                .take(3)
+                .any(|l| l.trim().starts_with("Sent:") || l.trim().starts_with("Date:"))
+    };
+    if line.len() >= 10 && line.chars().all(|c| c == '_') {
+        return starts_header_block(rest);
+    }
+    line.starts_with("From:") && starts_header_block([&[line][..], rest].concat().as_slice())
+}
+
+/// RFC 2822 dates become RFC 3339 when they parse; others are kept as-is
+fn normalize_date(value: &str) -> String {
+    chrono::DateTime::parse_from_rfc2822(value.trim())
+        .map(|d| d.to_rfc3339())
+        .unwrap_or_else(|_| value.trim().to_string())
+}
+
+fn first_address(value: &str) -> Option<String> {
+    let re = Regex::new(r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}").unwrap();
+    re.find(value).map(|m| m.as_str().to_ascii_lowercase())
+}
+
+fn read_msg_stream<F: Read + std::io::Seek>(
+    file: &mut cfb::CompoundFile<F>,
+    name: &str,
+) -> std::io::Result<Option<Vec<u8>>> {
+    let path = format!("/{}", name);
+    if !file.is_stream(&path) {
+        return Ok(None);
+    }
+    let mut bytes = Vec::new();
+    file.open_stream(&path)?
+        .take(MAX_MSG_STREAM_BYTES)
+        .read_to_end(&mut bytes)?;
+    Ok(Some(bytes))
+}
+
+/// String property by id: UTF-16LE (`001F`) or 8-bit (`001E`) stream
+fn read_msg_string<F: Read + std::io::Seek>(
+    file: &mut cfb::CompoundFile<F>,
+    id: u16,
+) -> std::io::Result<Option<String>> {
+    if let Some(bytes) = read_msg_stream(file, &format!("__substg1.0_{:04X}001F", id))? {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        return Ok(Some(
+            String::from_utf16_lossy(&units)
+                .trim_end_matches('\0')
+                .to_string(),
+        ));
+    }
+    Ok(
+        read_msg_stream(file, &format!("__substg1.0_{:04X}001E", id))?.map(|bytes| {
+            mime::decode_charset(&bytes, Some("windows-1252"))
+                .trim_end_matches('\0')
+                .to_string()
+        }),
+    )
+}
+
+/// PidTagClientSubmitTime (0x0039, FILETIME) from the fixed-size property stream
+fn read_msg_submit_time<F: Read + std::io::Seek>(
+    file: &mut cfb::CompoundFile<F>,
+) -> Option<String> {
+    const SUBMIT_TIME_TAG: u32 = 0x0039_0040;
+    // Top-level message property stream: 32-byte header, then 16-byte entries
+    let bytes = read_msg_stream(file, "__properties_version1.0").ok()??;
+    let entry = bytes.get(32..)?.chunks_exact(16).find(|entry| {
+        u32::from_le_bytes([entry[0], entry[1], entry[2], entry[3]]) == SUBMIT_TIME_TAG
+    })?;
+    let filetime = u64::from_le_bytes(entry[8..16].try_into().ok()?);
+    // FILETIME counts 100ns intervals since 1601-01-01
+    let unix_secs = (filetime / 10_000_000).checked_sub(11_644_473_600)?;
+    chrono::DateTime::from_timestamp(unix_secs as i64, 0).map(|d| d.to_rfc3339())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THREAD_REPLY: &str = "Delivered-To: support@acme.com\r\n\
+From: =?UTF-8?Q?Jos=C3=A9_Ortiz?= <jose@customer.com>\r\n\
+To: Acme Support <support@acme.com>\r\n\
+Date: Tue, 6 Jan 2026 09:14:00 -0500\r\n\
+Subject: Re: VPN drops every hour\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/mixed; boundary=\"outer\"\r\n\
+\r\n\
+--outer\r\n\
+Content-Type: multipart/alternative; boundary=\"inner\"\r\n\
+\r\n\
+--inner\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+Disabling the idle timeout in the client fixed it =E2=80=94 thanks!\r\n\
+\r\n\
+On Mon, Jan 5, 2026 at 4:02 PM Acme Support <support@acme.com> wrote:\r\n\
+> Please try disabling the idle timeout.\r\n\
+> Older context here.\r\n\
+--inner\r\n\
+Content-Type: text/html; charset=utf-8\r\n\
+\r\n\
+<p>HTML version</p>\r\n\
+--inner--\r\n\
+--outer\r\n\
+Content-Type: text/plain\r\n\
+Content-Disposition: attachment; filename=\"log.txt\"\r\n\
+\r\n\
+attachment text\r\n\
+--outer--\r\n";
+
+    #[test]
+    fn test_parse_eml_headers_and_plain_body() {
+        let message = parse_eml(THREAD_REPLY.as_bytes()).unwrap();
+        assert_eq!(message.subject.as_deref(), Some("Re: VPN drops every hour"));
+        assert_eq!(
+            message.from.as_deref(),
+            Some("José Ortiz <jose@customer.com>")
+        );
+        assert_eq!(message.date.as_deref(), Some("2026-01-06T09:14:00-05:00"));
+        assert_eq!(message.mailbox.as_deref(), Some("support@acme.com"));
+        assert_eq!(
+            message.body,
+            "Disabling the idle timeout in the client fixed it — thanks!"
+        );
+    }
+
+    #[test]
+    fn test_into_sections_carries_metadata_in_heading() {
+        let sections = parse_eml(THREAD_REPLY.as_bytes()).unwrap().into_sections();
+        assert_eq!(sections.len(), 1);
+        let heading = sections[0].heading.as_deref().unwrap();
+        assert!(heading.starts_with("Re: VPN drops every hour (From: José Ortiz"));
+        assert!(heading.contains("To: Acme Support <support@acme.com>"));
+        assert!(heading.contains("Date: 2026-01-06T09:14:00-05:00"));
+    }
+
+    #[test]
+    fn test_parse_eml_html_only_base64() {
+        let eml = "From: a@b.com\r\nSubject: Printer\r\nContent-Type: text/html; charset=utf-8\r\nContent-Transfer-Encoding: base64\r\n\r\nPGgxPlByaW50ZXI8L2gxPjxwPlJlc3RhcnQgdGhlIHNwb29sZXIuPC9wPg==\r\n";
+        let message = parse_eml(eml.as_bytes()).unwrap();
+        assert_eq!(message.body, "Printer\nRestart the spooler.");
+    }
+
+    #[test]
+    fn test_parse_eml_rejects_headerless_input() {
+        assert!(parse_eml(b"just some text").is_err());
+    }
+
+    #[test]
+    fn test_strip_quoted_reply_outlook_header_block() {
+        let body = "Confirmed working now.\n\n________________________________\nFrom: Support\nSent: Monday, January 5, 2026 4:02 PM\nTo: Jose\nSubject: RE: VPN\n\nPlease reboot.";
+        assert_eq!(strip_quoted_reply(body), "Confirmed working now.");
+
+        let body = "Still failing.\n\n-----Original Message-----\nFrom: Support";
+        assert_eq!(strip_quoted_reply(body), "Still failing.");
+    }
+
+    #[test]
+    fn test_strip_quoted_reply_keeps_unquoted_message() {
+        let body = "From: the help desk portal\nPlease reset my password.";
+        assert_eq!(strip_quoted_reply(body), body);
+        assert_eq!(strip_quoted_reply("> only quoted"), "> only quoted");
+    }
+
+    #[test]
+    fn test_parse_msg_nonexistent() {
+        assert!(parse_msg(Path::new("/nonexistent.msg")).is_err());
+    }
+}
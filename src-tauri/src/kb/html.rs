@@ -0,0 +1,472 @@
+//! HTML and MHTML extraction for KB indexing
+//!
+//! Saved vendor articles carry site chrome (navigation, headers, cookie
+//! banners). Extraction prefers the `<article>`/`<main>` body, drops
+//! boilerplate elements, keeps `<pre>` blocks and tables intact, and splits
+//! sections at `<h1>`-`<h6>`.
+
+use regex_lite::Regex;
+use thiserror::Error;
+
+use super::indexer::{Section, SectionBuilder};
+use super::mime;
+
+#[derive(Debug, Error)]
+pub enum HtmlError {
+    #[error("MHTML parse error: {0}")]
+    Mhtml(String),
+}
+
+/// Text extracted from an HTML page
+#[derive(Debug)]
+pub struct HtmlDocument {
+    pub title: Option<String>,
+    /// URL the page was saved from, when the file records it
+    pub source_url: Option<String>,
+    pub sections: Vec<Section>,
+}
+
+/// Elements removed with their content before extraction
+const BOILERPLATE_ELEMENTS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "iframe", "form", "nav", "aside", "button",
+];
+
+/// Page chrome removed when there is no `<article>`/`<main>` to select;
+/// inside an article these usually hold the title and byline
+const PAGE_CHROME_ELEMENTS: &[&str] = &["header", "footer"];
+
+/// An `<article>`/`<main>` with fewer words is treated as a teaser, not the body
+const MIN_MAIN_CONTENT_WORDS: usize = 20;
+
+/// Placeholder delimiters for preserved blocks (Unicode private use area)
+const BLOCK_START: char = '\u{E000}';
+const BLOCK_END: char = '\u{E001}';
+
+/// Extract an HTML file's bytes, honoring a `<meta charset>` declaration
+pub fn extract_html_bytes(bytes: &[u8]) -> HtmlDocument {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(2048)]);
+    let charset = Regex::new(r#"(?i)<meta[^>]+charset\s*=\s*["']?([A-Za-z0-9_\-]+)"#)
+        .ok()
+        .and_then(|re| re.captures(&head)?.get(1).map(|m| m.as_str().to_string()));
+    extract_html(&mime::decode_charset(bytes, charset.as_deref()))
+}
+
+/// Extract title, source URL, and heading-delimited sections from HTML
+pub fn extract_html(html: &str) -> HtmlDocument {
+    let title = find_title(html);
+    let source_url = find_source_url(html);
+
+    let mut html = remove_pattern(html, r"(?s)<!--.*?-->");
+    for tag in BOILERPLATE_ELEMENTS {
+        html = remove_element(&html, tag);
+    }
+    let body = match main_content(&html) {
+        Some(main) => main,
+        None => PAGE_CHROME_ELEMENTS
+            .iter()
+            .fold(body_content(&html), |body, tag| remove_element(&body, tag)),
+    };
+
+    // Pull out code blocks and tables before whitespace is normalized
+    let mut blocks = Vec::new();
+    let body = replace_blocks(
+        &body,
+        r"(?is)<pre\b[^>]*>(.*?)</pre\s*>",
+        &mut blocks,
+        |inner| {
+            let code = decode_entities(&strip_tags(&br_to_newline(inner)));
+            format!("```\n{}\n```", code.trim_matches('\n'))
+        },
+    );
+    let body = replace_blocks(
+        &body,
+        r"(?is)<table\b[^>]*>(.*?)</table\s*>",
+        &mut blocks,
+        table_to_text,
+    );
+
+    let heading_re = Regex::new(r"(?is)<h([1-6])\b[^>]*>(.*?)</h[1-6]\s*>").unwrap();
+    let mut builder = SectionBuilder::default();
+    let mut last = 0;
+    for cap in heading_re.captures_iter(&body) {
+        let whole = cap.get(0).unwrap();
+        builder.push(&restore_blocks(
+            &html_to_text(&body[last..whole.start()]),
+            &blocks,
+        ));
+        let heading = inline_text(&cap[2]);
+        if !heading.is_empty() {
+            builder.heading(&heading, cap[1].parse().unwrap_or(1));
+        }
+        last = whole.end();
+    }
+    builder.push(&restore_blocks(&html_to_text(&body[last..]), &blocks));
+
+    HtmlDocument {
+        title,
+        source_url,
+        sections: builder.finish(),
+    }
+}
+
+/// Extract the HTML part of an MHTML (`multipart/related`) archive
+pub fn extract_mhtml(bytes: &[u8]) -> Result<HtmlDocument, HtmlError> {
+    let raw = String::from_utf8_lossy(bytes);
+    let (headers, body) = mime::split_headers(&raw);
+    let snapshot_url = mime::header_value(&headers, "snapshot-content-location")
+        .or_else(|| mime::header_value(&headers, "content-location"));
+
+    let content_type = mime::header_value(&headers, "content-type").unwrap_or_default();
+    let (part_headers, part_body) = match mime::header_param(&content_type, "boundary") {
+        Some(boundary) => mime::parts(body, &boundary)
+            .into_iter()
+            .find(|(part_headers, _)| mime::content_type(part_headers) == "text/html")
+            .ok_or_else(|| HtmlError::Mhtml("no text/html part".to_string()))?,
+        None => (headers, body),
+    };
+    let html = mime::decode_text(&part_headers, part_body).map_err(HtmlError::Mhtml)?;
+
+    let mut doc = extract_html(&html);
+    if doc.source_url.is_none() {
+        doc.source_url = snapshot_url
+            .or_else(|| mime::header_value(&part_headers, "content-location"))
+            .filter(|url| is_web_url(url));
+    }
+    Ok(doc)
+}
+
+fn remove_pattern(html: &str, pattern: &str) -> String {
+    Regex::new(pattern)
+        .map(|re| re.replace_all(html, " ").into_owned())
+        .unwrap_or_else(|_| html.to_string())
+}
+
+fn remove_element(html: &str, tag: &str) -> String {
+    remove_pattern(html, &format!(r"(?is)<{tag}\b[^>]*>.*?</{tag}\s*>"))
+}
+
+fn find_title(html: &str) -> Option<String> {
+    let re = Regex::new(r"(?is)<title[^>]*>(.*?)</title\s*>").unwrap();
+    re.captures(html)
+        .map(|cap| inline_text(&cap[1]))
+        .filter(|t| !t.is_empty())
+}
+
+fn is_web_url(url: &str) -> bool {
+    url::Url::parse(url)
+        .map(|u| matches!(u.scheme(), "http" | "https"))
+        .unwrap_or(false)
+}
+
+/// Original URL from a canonical link, `og:url`, or a browser's
+/// `<!-- saved from url=(NNNN)... -->` marker
+fn find_source_url(html: &str) -> Option<String> {
+    let patterns = [
+        r#"(?is)<link\b[^>]*rel\s*=\s*["']canonical["'][^>]*href\s*=\s*["']([^"']+)["']"#,
+        r#"(?is)<link\b[^>]*href\s*=\s*["']([^"']+)["'][^>]*rel\s*=\s*["']canonical["']"#,
+        r#"(?is)<meta\b[^>]*property\s*=\s*["']og:url["'][^>]*content\s*=\s*["']([^"']+)["']"#,
+        r"(?i)<!--\s*saved from url=\(\d+\)(\S+?)\s*-->",
+    ];
+    patterns.iter().find_map(|pattern| {
+        let re = Regex::new(pattern).ok()?;
+        let url = decode_entities(re.captures(html)?.get(1)?.as_str().trim());
+        is_web_url(&url).then_some(url)
+    })
+}
+
+/// Readability-style body selection: the largest `<article>` or `<main>`
+/// (or `role="main"` element) with enough text to be the page's content
+fn main_content(html: &str) -> Option<String> {
+    let candidates = [
+        r"(?is)<article\b[^>]*>(.*?)</article\s*>",
+        r"(?is)<main\b[^>]*>(.*)</main\s*>",
+        r#"(?is)<div\b[^>]*role\s*=\s*["']main["'][^>]*>(.*)</div\s*>"#,
+    ];
+    for pattern in candidates {
+        let re = Regex::new(pattern).unwrap();
+        let best = re
+            .captures_iter(html)
+            .filter_map(|cap| cap.get(1).map(|m| m.as_str()))
+            .max_by_key(|inner| strip_tags(inner).split_whitespace().count());
+        if let Some(inner) = best {
+            if strip_tags(inner).split_whitespace().count() >= MIN_MAIN_CONTENT_WORDS {
+                return Some(inner.to_string());
+            }
+        }
+    }
+    None
+}
+
+fn body_content(html: &str) -> String {
+    let body_re = Regex::new(r"(?is)<body\b[^>]*>(.*)</body\s*>").unwrap();
+    body_re
+        .captures(html)
+        .and_then(|cap| cap.get(1))
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_else(|| html.to_string())
+}
+
+fn replace_blocks(
+    html: &str,
+    pattern: &str,
+    blocks: &mut Vec<String>,
+    render: impl Fn(&str) -> String,
+) -> String {
+    let re = Regex::new(pattern).unwrap();
+    re.replace_all(html, |cap: &regex_lite::Captures| {
+        blocks.push(render(&cap[1]));
+        format!("\n{}{}{}\n", BLOCK_START, blocks.len() - 1, BLOCK_END)
+    })
+    .into_owned()
+}
+
+fn restore_blocks(text: &str, blocks: &[String]) -> String {
+    let re = Regex::new(&format!("{}(\\d+){}", BLOCK_START, BLOCK_END)).unwrap();
+    re.replace_all(text, |cap: &regex_lite::Captures| {
+        cap[1]
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| blocks.get(i))
+            .cloned()
+            .unwrap_or_default()
+    })
+    .into_owned()
+}
+
+/// Render table rows as `cell | cell` lines, like DOCX tables
+fn table_to_text(inner: &str) -> String {
+    let row_re = Regex::new(r"(?is)<tr\b[^>]*>(.*?)</tr\s*>").unwrap();
+    let cell_re = Regex::new(r"(?is)<t[hd]\b[^>]*>(.*?)</t[hd]\s*>").unwrap();
+    row_re
+        .captures_iter(inner)
+        .map(|row| {
+            cell_re
+                .captures_iter(&row[1])
+                .map(|cell| inline_text(&cell[1]))
+                .collect::<Vec<_>>()
+        })
+        .filter(|cells| cells.iter().any(|c| !c.is_empty()))
+        .map(|cells| cells.join(" | "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn br_to_newline(html: &str) -> String {
+    Regex::new(r"(?i)<br\s*/?>")
+        .unwrap()
+        .replace_all(html, "\n")
+        .into_owned()
+}
+
+fn strip_tags(html: &str) -> String {
+    Regex::new(r"<[^>]*>")
+        .unwrap()
+        .replace_all(html, "")
+        .into_owned()
+}
+
+/// Single-line text for headings and table cells
+fn inline_text(html: &str) -> String {
+    decode_entities(&strip_tags(html))
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Block-aware plain text: block elements end lines, runs of spaces collapse
+fn html_to_text(html: &str) -> String {
+    let blocks_re =
+        Regex::new(r"(?i)</?(?:p|div|br|li|ul|ol|tr|section|blockquote|dd|dt)\b[^>]*>").unwrap();
+    let text = blocks_re.replace_all(html, "\n");
+    let text = decode_entities(&strip_tags(&text));
+
+    let mut out = String::new();
+    let mut blank = false;
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            blank = !out.is_empty();
+            continue;
+        }
+        if blank {
+            out.push('\n');
+            blank = false;
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Decode named entities common in saved pages and numeric references
+fn decode_entities(s: &str) -> String {
+    let re = Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").unwrap();
+    re.replace_all(s, |cap: &regex_lite::Captures| {
+        let entity = &cap[1];
+        let decoded = if let Some(hex) = entity
+            .strip_prefix("#x")
+            .or_else(|| entity.strip_prefix("#X"))
+        {
+            u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+        } else if let Some(dec) = entity.strip_prefix('#') {
+            dec.parse::<u32>().ok().and_then(char::from_u32)
+        } else {
+            match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "copy" => Some('©'),
+                "reg" => Some('®'),
+                "trade" => Some('™'),
+                "mdash" => Some('—'),
+                "ndash" => Some('–'),
+                "hellip" => Some('…'),
+                "rsquo" | "lsquo" => Some('\''),
+                "rdquo" | "ldquo" => Some('"'),
+                _ => None,
+            }
+        };
+        decoded
+            .map(String::from)
+            .unwrap_or_else(|| cap[0].to_string())
+    })
+    .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTICLE: &str = r#"<!DOCTYPE html>
+<!-- saved from url=(0041)https://support.vendor.com/kb/vpn-reset -->
+<html><head>
+<meta charset="utf-8">
+<title>Reset VPN &amp; MFA | Vendor Support</title>
+<script>var tracking = "do not index";</script>
+</head>
+<body>
+<nav><a href="/">Home</a> <a href="/kb">Knowledge Base</a></nav>
+<header>Sign in to Vendor Support</header>
+<main>
+  <h1>Reset VPN &amp; MFA</h1>
+  <p>Use these steps when the VPN client rejects a user's second factor after a phone change.</p>
+  <h2>Commands</h2>
+  <pre><code>vpnctl reset --user alice
+vpnctl  status</code></pre>
+  <table>
+    <tr><th>Platform</th><th>Client</th></tr>
+    <tr><td>macOS</td><td>4.2&nbsp;or later</td></tr>
+  </table>
+</main>
+<footer>&copy; 2026 Vendor. Cookie preferences.</footer>
+</body></html>"#;
+
+    #[test]
+    fn test_extract_html_strips_boilerplate_and_splits_sections() {
+        let doc = extract_html(ARTICLE);
+        assert_eq!(
+            doc.title.as_deref(),
+            Some("Reset VPN & MFA | Vendor Support")
+        );
+        assert_eq!(
+            doc.source_url.as_deref(),
+            Some("https://support.vendor.com/kb/vpn-reset")
+        );
+
+        let all: String = doc.sections.iter().map(|s| s.content.as_str()).collect();
+        assert!(!all.contains("Knowledge Base"));
+        assert!(!all.contains("Sign in"));
+        assert!(!all.contains("Cookie"));
+        assert!(!all.contains("tracking"));
+
+        assert_eq!(doc.sections.len(), 2);
+        assert_eq!(doc.sections[0].heading.as_deref(), Some("Reset VPN & MFA"));
+        assert_eq!(doc.sections[1].heading.as_deref(), Some("Commands"));
+        assert_eq!(doc.sections[1].level, 2);
+    }
+
+    #[test]
+    fn test_extract_html_preserves_code_and_tables() {
+        let doc = extract_html(ARTICLE);
+        let commands = &doc.sections[1].content;
+        assert!(commands.contains("```\nvpnctl reset --user alice\nvpnctl  status\n```"));
+        assert!(commands.contains("Platform | Client"));
+        assert!(commands.contains("macOS | 4.2 or later"));
+    }
+
+    #[test]
+    fn test_find_source_url_prefers_canonical() {
+        let html = r#"<link rel="canonical" href="https://kb.example.com/a?x=1&amp;y=2"><meta property="og:url" content="https://other.example.com">"#;
+        assert_eq!(
+            find_source_url(html).as_deref(),
+            Some("https://kb.example.com/a?x=1&y=2")
+        );
+        assert_eq!(
+            find_source_url(r#"<link rel="canonical" href="/relative">"#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extract_html_keeps_article_header() {
+        let html = r#"<body><header>Site banner</header><article><header><h1>Printer Setup</h1></header>
+<p>Install the driver from the vendor portal, then add the printer by IP address and print a test page to confirm.</p>
+</article><footer>Contact us</footer></body>"#;
+        let doc = extract_html(html);
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].heading.as_deref(), Some("Printer Setup"));
+        assert!(!doc.sections[0].content.contains("Site banner"));
+        assert!(!doc.sections[0].content.contains("Contact us"));
+    }
+
+    #[test]
+    fn test_extract_mhtml_quoted_printable() {
+        let mhtml = "From: <Saved by Blink>\r\n\
+Snapshot-Content-Location: https://support.vendor.com/kb/printers\r\n\
+Subject: Printers\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/related;\r\n\
+\ttype=\"text/html\";\r\n\
+\tboundary=\"----MultipartBoundary--abc\"\r\n\
+\r\n\
+------MultipartBoundary--abc\r\n\
+Content-Type: text/html\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+Content-Location: https://support.vendor.com/kb/printers\r\n\
+\r\n\
+<html><body><h1 class=3D\"title\">Printer setup</h1><p>Install the caf=C3=A9 =\r\n\
+driver.</p></body></html>\r\n\
+------MultipartBoundary--abc\r\n\
+Content-Type: image/png\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+iVBORw0KGgo=\r\n\
+------MultipartBoundary--abc--\r\n";
+
+        let doc = extract_mhtml(mhtml.as_bytes()).unwrap();
+        assert_eq!(
+            doc.source_url.as_deref(),
+            Some("https://support.vendor.com/kb/printers")
+        );
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].heading.as_deref(), Some("Printer setup"));
+        assert_eq!(doc.sections[0].content.trim(), "Install the café driver.");
+    }
+
+    #[test]
+    fn test_extract_mhtml_without_html_part() {
+        let mhtml = "Content-Type: multipart/related; boundary=\"b\"\r\n\r\n--b\r\nContent-Type: image/png\r\n\r\nxx\r\n--b--\r\n";
+        assert!(extract_mhtml(mhtml.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("a &lt;b&gt; &#169; &#x2014; &bogus;"),
+            "a <b> © — &bogus;"
+        );
+    }
+}
@@ -9,6 +9,7 @@ use uuid::Uuid;
 
 use super::ocr::OcrManager;
 use super::pdf::PdfExtractor;
+use super::tabular::{read_delimited, table_sections, Table, TabularOptions};
 use crate::db::{Database, DbError};
 
 #[derive(Debug, Error)]
@@ -23,8 +24,16 @@ pub enum IndexerError {
     Ocr(#[from] super::ocr::OcrError),
     #[error("DOCX error: {0}")]
     Docx(#[from] super::docx::DocxError),
+    #[error("ODT error: {0}")]
+    Odt(#[from] super::odt::OdtError),
+    #[error("HTML error: {0}")]
+    Html(#[from] super::html::HtmlError),
+    #[error("Email error: {0}")]
+    Email(#[from] super::email::EmailError),
     #[error("XLSX error: {0}")]
     Xlsx(#[from] super::xlsx::XlsxError),
+    #[error("Table error: {0}")]
+    Tabular(#[from] super::tabular::TabularError),
     #[error("Parse error: {0}")]
     Parse(String),
     #[error("Unsupported file type: {0}")]
@@ -43,7 +52,13 @@ pub enum DocumentType {
     PlainText,
     Image,
     Docx,
+    Odt,
     Xlsx,
+    Csv,
+    Html,
+    Mhtml,
+    Eml,
+    Msg,
     Code(CodeLanguage),
 }
 
@@ -113,7 +128,13 @@ impl DocumentType {
             "txt" | "text" => Some(Self::PlainText),
             "png" | "jpg" | "jpeg" | "gif" | "tiff" | "tif" => Some(Self::Image),
             "docx" => Some(Self::Docx),
+            "odt" => Some(Self::Odt),
             "xlsx" | "xls" => Some(Self::Xlsx),
+            "csv" | "tsv" => Some(Self::Csv),
+            "html" | "htm" => Some(Self::Html),
+            "mhtml" | "mht" => Some(Self::Mhtml),
+            "eml" => Some(Self::Eml),
+            "msg" => Some(Self::Msg),
             // Code files
             "py" => Some(Self::Code(CodeLanguage::Python)),
             "js" | "jsx" | "mjs" => Some(Self::Code(CodeLanguage::JavaScript)),
@@ -137,6 +158,8 @@ pub struct Chunk {
     pub heading_path: Option<String>,
     pub content: String,
     pub word_count: usize,
+    /// 1-based source page, for paginated documents like PDF
+    pub page_number: Option<u32>,
 }
 
 /// Parsed document ready for chunking
@@ -144,6 +167,8 @@ pub struct Chunk {
 pub struct ParsedDocument {
     pub title: Option<String>,
     pub sections: Vec<Section>,
+    /// URL a saved web page (HTML/MHTML) was downloaded from
+    pub source_url: Option<String>,
 }
 
 /// A document section with heading
@@ -152,6 +177,47 @@ pub struct Section {
     pub heading: Option<String>,
     pub level: u8,
     pub content: String,
+    /// 1-based source page, for paginated documents like PDF
+    pub page: Option<u32>,
+}
+
+/// Collects text into sections, starting a new section at each heading
+#[derive(Default)]
+pub struct SectionBuilder {
+    sections: Vec<Section>,
+    heading: Option<String>,
+    level: u8,
+    content: String,
+}
+
+impl SectionBuilder {
+    /// Close the current section and start one under `heading`
+    pub fn heading(&mut self, heading: &str, level: u8) {
+        self.flush();
+        self.heading = Some(heading.trim().to_string());
+        self.level = level;
+    }
+
+    pub fn push(&mut self, text: &str) {
+        self.content.push_str(text);
+    }
+
+    fn flush(&mut self) {
+        let content = std::mem::take(&mut self.content);
+        if !content.trim().is_empty() {
+            self.sections.push(Section {
+                heading: self.heading.take(),
+                level: self.level,
+                content,
+                page: None,
+            });
+        }
+    }
+
+    pub fn finish(mut self) -> Vec<Section> {
+        self.flush();
+        self.sections
+    }
 }
 
 /// Indexing progress event
@@ -165,6 +231,14 @@ pub enum IndexProgress {
         total: usize,
         file_name: String,
     },
+    /// A page of the current file was extracted (PDF)
+    PageProcessed {
+        current: usize,
+        total: usize,
+        file_name: String,
+        page: usize,
+        total_pages: usize,
+    },
     Completed {
         indexed: usize,
         skipped: usize,
@@ -191,6 +265,10 @@ pub struct KbIndexer {
     ocr_manager: OcrManager,
     target_chunk_words: usize,
     max_chunk_words: usize,
+    /// OCR images embedded in DOCX/ODT files
+    office_image_ocr: bool,
+    /// Sheet/column selection and row grouping for spreadsheets
+    tabular: TabularOptions,
 }
 
 impl KbIndexer {
@@ -201,9 +279,23 @@ impl KbIndexer {
             ocr_manager: OcrManager::new(),
             target_chunk_words: 350, // Target 200-500 words
             max_chunk_words: 500,    // Hard cap
+            office_image_ocr: false,
+            tabular: TabularOptions::default(),
         }
     }
 
+    /// Enable or disable OCR of images embedded in DOCX/ODT files
+    pub fn with_office_image_ocr(mut self, enabled: bool) -> Self {
+        self.office_image_ocr = enabled;
+        self
+    }
+
+    /// Set which sheets/columns of spreadsheets are indexed and how many rows go in a chunk
+    pub fn with_tabular_options(mut self, options: TabularOptions) -> Self {
+        self.tabular = options;
+        self
+    }
+
     /// Calculate SHA256 hash of file contents
     ///
     /// Uses streaming to handle large files efficiently.
@@ -247,7 +339,13 @@ impl KbIndexer {
     /// Get max file size for a document type
     fn max_file_size(doc_type: &DocumentType) -> u64 {
         match doc_type {
-            DocumentType::Pdf | DocumentType::Docx | DocumentType::Xlsx => MAX_BINARY_FILE_SIZE,
+            DocumentType::Pdf
+            | DocumentType::Docx
+            | DocumentType::Odt
+            | DocumentType::Xlsx
+            | DocumentType::Mhtml
+            | DocumentType::Eml
+            | DocumentType::Msg => MAX_BINARY_FILE_SIZE,
             DocumentType::Image => MAX_IMAGE_FILE_SIZE,
             _ => MAX_TEXT_FILE_SIZE,
         }
@@ -306,6 +404,16 @@ impl KbIndexer {
 
     /// Parse a document into sections
     pub fn parse_document(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
+        self.parse_document_with_progress(path, |_, _| {})
+    }
+
+    /// Parse a document into sections, calling `on_page(page, total)` as pages
+    /// of a paginated document are extracted
+    pub fn parse_document_with_progress(
+        &self,
+        path: &Path,
+        on_page: impl FnMut(usize, usize),
+    ) -> Result<ParsedDocument, IndexerError> {
         let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
         let doc_type = DocumentType::from_extension(ext)
@@ -323,11 +431,17 @@ impl KbIndexer {
 
         match doc_type {
             DocumentType::Markdown => self.parse_markdown(path),
-            DocumentType::Pdf => self.parse_pdf(path),
+            DocumentType::Pdf => self.parse_pdf(path, on_page),
             DocumentType::PlainText => self.parse_plaintext(path),
             DocumentType::Image => self.parse_image(path),
             DocumentType::Docx => self.parse_docx(path),
+            DocumentType::Odt => self.parse_odt(path),
             DocumentType::Xlsx => self.parse_xlsx(path),
+            DocumentType::Csv => self.parse_csv(path),
+            DocumentType::Html => self.parse_html(path),
+            DocumentType::Mhtml => self.parse_mhtml(path),
+            DocumentType::Eml => self.parse_eml(path),
+            DocumentType::Msg => self.parse_msg(path),
             DocumentType::Code(lang) => self.parse_code(path, lang),
         }
     }
@@ -335,8 +449,13 @@ impl KbIndexer {
     /// Parse a Markdown file
     fn parse_markdown(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
         let raw = std::fs::read(path)?;
-        let content = String::from_utf8_lossy(&raw).into_owned();
-        let parser = pulldown_cmark::Parser::new(&content);
+        let content = String::from_utf8_lossy(&raw);
+        Ok(Self::parse_markdown_text(&content))
+    }
+
+    /// Parse Markdown text into sections split at headings; the first H1 is the title
+    pub fn parse_markdown_text(content: &str) -> ParsedDocument {
+        let parser = pulldown_cmark::Parser::new(content);
 
         let mut title: Option<String> = None;
         let mut sections = Vec::new();
@@ -344,6 +463,7 @@ impl KbIndexer {
             heading: None,
             level: 0,
             content: String::new(),
+            page: None,
         };
 
         use pulldown_cmark::{Event, Tag, TagEnd};
@@ -377,6 +497,7 @@ impl KbIndexer {
                         heading: Some(heading),
                         level: heading_level,
                         content: String::new(),
+                        page: None,
                     };
                 }
                 Event::Text(text) | Event::Code(text) => {
@@ -420,46 +541,65 @@ impl KbIndexer {
             sections.push(current_section);
         }
 
-        Ok(ParsedDocument { title, sections })
+        ParsedDocument {
+            title,
+            sections,
+            source_url: None,
+        }
     }
 
-    /// Parse a PDF file with automatic OCR fallback for scanned documents
-    fn parse_pdf(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
-        // Use OCR fallback for scanned PDFs (< 100 chars per page average)
+    /// Parse a PDF file into one section per page, OCR'ing scanned pages
+    fn parse_pdf(
+        &self,
+        path: &Path,
+        on_page: impl FnMut(usize, usize),
+    ) -> Result<ParsedDocument, IndexerError> {
         let ocr_manager = &self.ocr_manager;
-        let pages = self
-            .pdf_extractor
-            .extract_text_with_ocr_fallback(path, |img_path| {
+        let pages = self.pdf_extractor.extract_text_with_ocr_fallback(
+            path,
+            |img_path| {
                 ocr_manager
                     .recognize(img_path)
                     .map(|r| r.text)
                     .map_err(|e| e.to_string())
-            })?;
-
-        // Combine all pages
-        let mut all_text = String::new();
-        for page_text in pages.iter() {
-            all_text.push_str(page_text);
-            all_text.push_str("\n\n");
-        }
+            },
+            on_page,
+        )?;
 
         // Try to extract title from first line
-        let title = all_text
-            .lines()
-            .next()
-            .filter(|l| l.len() < 100 && !l.is_empty())
+        let title = pages
+            .iter()
+            .flat_map(|page| page.lines())
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .filter(|l| l.len() < 100)
             .map(|s| s.to_string());
 
         Ok(ParsedDocument {
             title,
-            sections: vec![Section {
-                heading: None,
-                level: 0,
-                content: all_text,
-            }],
+            sections: Self::pdf_page_sections(pages),
+            source_url: None,
         })
     }
 
+    /// One section per non-empty page, headed and tagged with its page number
+    fn pdf_page_sections(pages: Vec<String>) -> Vec<Section> {
+        pages
+            .into_iter()
+            .enumerate()
+            .filter(|(_, text)| !text.trim().is_empty())
+            .map(|(idx, content)| {
+                let page = idx as u32 + 1;
+                Section {
+                    heading: Some(format!("Page {}", page)),
+                    level: 1,
+                    content,
+                    page: Some(page),
+                }
+            })
+            .collect()
+    }
+
     /// Parse a plain text file
     fn parse_plaintext(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
         let raw = std::fs::read(path)?;
@@ -475,7 +615,9 @@ impl KbIndexer {
                 heading: None,
                 level: 0,
                 content,
+                page: None,
             }],
+            source_url: None,
         })
     }
 
@@ -493,13 +635,19 @@ impl KbIndexer {
                 heading: None,
                 level: 0,
                 content: result.text,
+                page: None,
             }],
+            source_url: None,
         })
     }
 
-    /// Parse a DOCX file
+    /// Parse a DOCX file, keeping heading-styled paragraphs as section boundaries
     fn parse_docx(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
-        let content = super::docx::extract_text(path)?;
+        let mut sections = super::docx::extract_sections(path)?;
+        if self.office_image_ocr {
+            let images = super::docx::extract_images(path)?;
+            sections.extend(self.embedded_image_section(path, &images));
+        }
         let title = path
             .file_stem()
             .and_then(|s| s.to_str())
@@ -507,17 +655,18 @@ impl KbIndexer {
 
         Ok(ParsedDocument {
             title,
-            sections: vec![Section {
-                heading: None,
-                level: 0,
-                content,
-            }],
+            sections,
+            source_url: None,
         })
     }
 
-    /// Parse an Excel file (XLSX/XLS)
-    fn parse_xlsx(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
-        let content = super::xlsx::extract_text(path)?;
+    /// Parse an ODT file, keeping `text:h` headings as section boundaries
+    fn parse_odt(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
+        let mut sections = super::odt::extract_sections(path)?;
+        if self.office_image_ocr {
+            let images = super::odt::extract_images(path)?;
+            sections.extend(self.embedded_image_section(path, &images));
+        }
         let title = path
             .file_stem()
             .and_then(|s| s.to_str())
@@ -525,14 +674,132 @@ impl KbIndexer {
 
         Ok(ParsedDocument {
             title,
-            sections: vec![Section {
-                heading: None,
-                level: 0,
-                content,
-            }],
+            sections,
+            source_url: None,
         })
     }
 
+    /// OCR embedded images into one trailing section. Images that fail OCR
+    /// are logged and skipped so the document text is still indexed.
+    fn embedded_image_section(
+        &self,
+        path: &Path,
+        images: &[super::office::EmbeddedImage],
+    ) -> Option<Section> {
+        let mut content = String::new();
+        for image in images {
+            let text = tempfile::Builder::new()
+                .suffix(&format!(".{}", image.extension()))
+                .tempfile()
+                .and_then(|mut file| {
+                    use std::io::Write;
+                    file.write_all(&image.bytes)?;
+                    Ok(file)
+                })
+                .map_err(IndexerError::from)
+                .and_then(|file| Ok(self.ocr_manager.recognize(file.path())?.text));
+            match text {
+                Ok(text) if !text.trim().is_empty() => {
+                    content.push_str(text.trim());
+                    content.push_str("\n\n");
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!("OCR failed for {} in {:?}: {}", image.name, path, e);
+                }
+            }
+        }
+
+        if content.trim().is_empty() {
+            return None;
+        }
+        Some(Section {
+            heading: Some("Embedded images".to_string()),
+            level: 1,
+            content,
+            page: None,
+        })
+    }
+
+    /// Parse an Excel file (XLSX/XLS) into header-plus-rows sections per sheet
+    fn parse_xlsx(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
+        let tables = super::xlsx::extract_tables(path)?;
+        Ok(self.tabular_document(path, &tables))
+    }
+
+    /// Parse a CSV or TSV file into header-plus-rows sections
+    fn parse_csv(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
+        let is_tsv = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.eq_ignore_ascii_case("tsv"))
+            .unwrap_or(false);
+        let delimiter = if is_tsv { '\t' } else { ',' };
+        let tables: Vec<Table> = read_delimited(path, delimiter)?.into_iter().collect();
+        Ok(self.tabular_document(path, &tables))
+    }
+
+    fn tabular_document(&self, path: &Path, tables: &[Table]) -> ParsedDocument {
+        let title = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_string());
+
+        ParsedDocument {
+            title,
+            sections: table_sections(tables, &self.tabular, self.max_chunk_words),
+            source_url: None,
+        }
+    }
+
+    /// Parse a saved HTML page, dropping navigation and other boilerplate
+    fn parse_html(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
+        let raw = std::fs::read(path)?;
+        Ok(Self::html_document(
+            path,
+            super::html::extract_html_bytes(&raw),
+        ))
+    }
+
+    /// Parse a saved MHTML web archive
+    fn parse_mhtml(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
+        let raw = std::fs::read(path)?;
+        Ok(Self::html_document(path, super::html::extract_mhtml(&raw)?))
+    }
+
+    fn html_document(path: &Path, doc: super::html::HtmlDocument) -> ParsedDocument {
+        let title = doc.title.or_else(|| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .map(|s| s.to_string())
+        });
+        ParsedDocument {
+            title,
+            sections: doc.sections,
+            source_url: doc.source_url,
+        }
+    }
+
+    /// Parse an exported email (`.eml`)
+    fn parse_eml(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
+        let raw = std::fs::read(path)?;
+        Ok(Self::email_document(super::email::parse_eml(&raw)?))
+    }
+
+    /// Parse an Outlook message (`.msg`)
+    fn parse_msg(&self, path: &Path) -> Result<ParsedDocument, IndexerError> {
+        Ok(Self::email_document(super::email::parse_msg(path)?))
+    }
+
+    /// Email documents are titled by subject, with quoted replies already stripped
+    fn email_document(message: super::email::EmailMessage) -> ParsedDocument {
+        ParsedDocument {
+            title: message.subject.clone(),
+            sections: message.into_sections(),
+            source_url: None,
+        }
+    }
+
     /// Parse a code file into logical chunks (functions, classes, etc.)
     fn parse_code(&self, path: &Path, lang: CodeLanguage) -> Result<ParsedDocument, IndexerError> {
         let raw = std::fs::read(path)?;
@@ -547,6 +814,7 @@ impl KbIndexer {
             return Ok(ParsedDocument {
                 title,
                 sections: vec![],
+                source_url: None,
             });
         }
 
@@ -570,6 +838,7 @@ impl KbIndexer {
                                 heading: current_heading.clone(),
                                 level: 1,
                                 content: section_content,
+                                page: None,
                             });
                         }
                     }
@@ -590,6 +859,7 @@ impl KbIndexer {
                     heading: current_heading,
                     level: 1,
                     content: section_content,
+                    page: None,
                 });
             }
         }
@@ -599,7 +869,11 @@ impl KbIndexer {
             sections = self.chunk_code_by_lines(&content, 50);
         }
 
-        Ok(ParsedDocument { title, sections })
+        Ok(ParsedDocument {
+            title,
+            sections,
+            source_url: None,
+        })
     }
 
     /// Extract function/method name from a line
@@ -634,6 +908,7 @@ impl KbIndexer {
                 )),
                 level: 1,
                 content: chunk.join("\n"),
+                page: None,
             });
         }
 
@@ -663,7 +938,10 @@ impl KbIndexer {
                 Some(current_heading_path.join(" > "))
             };
 
-            let section_chunks = self.chunk_text(&section.content, heading_path);
+            let mut section_chunks = self.chunk_text(&section.content, heading_path);
+            for chunk in &mut section_chunks {
+                chunk.page_number = section.page;
+            }
             chunks.extend(section_chunks);
         }
 
@@ -684,6 +962,7 @@ impl KbIndexer {
                 heading_path,
                 content: text.trim().to_string(),
                 word_count: words.len(),
+                page_number: None,
             }];
         }
 
@@ -702,6 +981,7 @@ impl KbIndexer {
                         heading_path: heading_path.clone(),
                         content: current_words.join(" "),
                         word_count: current_words.len(),
+                        page_number: None,
                     });
                     current_words.clear();
                 }
@@ -723,6 +1003,7 @@ impl KbIndexer {
                     heading_path: heading_path.clone(),
                     content: current_words.join(" "),
                     word_count: current_words.len(),
+                    page_number: None,
                 });
                 current_words.clear();
             }
@@ -734,6 +1015,7 @@ impl KbIndexer {
                 heading_path,
                 content: current_words.join(" "),
                 word_count: current_words.len(),
+                page_number: None,
             });
         }
 
@@ -762,6 +1044,7 @@ impl KbIndexer {
                         heading_path: heading_path.clone(),
                         content: current_words.join(" "),
                         word_count: current_words.len(),
+                        page_number: None,
                     });
                     current_words.clear();
                 }
@@ -774,6 +1057,7 @@ impl KbIndexer {
                             heading_path: heading_path.clone(),
                             content: current_words.join(" "),
                             word_count: current_words.len(),
+                            page_number: None,
                         });
                         current_words.clear();
                     }
@@ -787,6 +1071,7 @@ impl KbIndexer {
                     heading_path: heading_path.clone(),
                     content: current_words.join(" "),
                     word_count: current_words.len(),
+                    page_number: None,
                 });
                 current_words.clear();
             }
@@ -798,6 +1083,7 @@ impl KbIndexer {
                     heading_path: heading_path.clone(),
                     content: current_words.join(" "),
                     word_count: current_words.len(),
+                    page_number: None,
                 });
                 current_words.clear();
             }
@@ -808,6 +1094,7 @@ impl KbIndexer {
                 heading_path,
                 content: current_words.join(" "),
                 word_count: current_words.len(),
+                page_number: None,
             });
         }
 
@@ -816,6 +1103,16 @@ impl KbIndexer {
 
     /// Index a single document into the database
     pub fn index_document(&self, db: &Database, path: &Path) -> Result<usize, IndexerError> {
+        self.index_document_with_progress(db, path, |_, _| {})
+    }
+
+    /// Index a single document, calling `on_page(page, total)` as pages are extracted
+    pub fn index_document_with_progress(
+        &self,
+        db: &Database,
+        path: &Path,
+        on_page: impl FnMut(usize, usize),
+    ) -> Result<usize, IndexerError> {
         let file_hash = Self::file_hash(path)?;
         let file_path = path.to_string_lossy().to_string();
 
@@ -835,7 +1132,7 @@ impl KbIndexer {
         }
 
         // Parse the document
-        let parsed = self.parse_document(path)?;
+        let parsed = self.parse_document_with_progress(path, on_page)?;
 
         // Chunk the document
         let chunks = self.chunk_document(&parsed);
@@ -876,9 +1173,17 @@ impl KbIndexer {
 
         db.conn()
             .execute(
-                "INSERT OR REPLACE INTO kb_documents (id, file_path, file_hash, title, indexed_at, chunk_count)
-                 VALUES (?, ?, ?, ?, ?, ?)",
-                params![&doc_id, &file_path, &file_hash, &title, &now, chunks.len() as i64],
+                "INSERT OR REPLACE INTO kb_documents (id, file_path, file_hash, title, indexed_at, chunk_count, source_url)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    &doc_id,
+                    &file_path,
+                    &file_hash,
+                    &title,
+                    &now,
+                    chunks.len() as i64,
+                    &parsed.source_url,
+                ],
             )
             .map_err(|e| IndexerError::Database(DbError::Sqlite(e)))?;
 
@@ -887,8 +1192,8 @@ impl KbIndexer {
             let chunk_id = Uuid::new_v4().to_string();
             db.conn()
                 .execute(
-                    "INSERT INTO kb_chunks (id, document_id, chunk_index, heading_path, content, word_count)
-                     VALUES (?, ?, ?, ?, ?, ?)",
+                    "INSERT INTO kb_chunks (id, document_id, chunk_index, heading_path, content, word_count, page_number)
+                     VALUES (?, ?, ?, ?, ?, ?, ?)",
                     params![
                         &chunk_id,
                         &doc_id,
@@ -896,6 +1201,7 @@ impl KbIndexer {
                         &chunk.heading_path,
                         &chunk.content,
                         chunk.word_count as i64,
+                        chunk.page_number,
                     ],
                 )
                 .map_err(|e| IndexerError::Database(DbError::Sqlite(e)))?;
@@ -936,7 +1242,16 @@ impl KbIndexer {
                 file_name: file_name.clone(),
             });
 
-            match self.index_document(db, file) {
+            let on_page = |page, total_pages| {
+                progress_callback(IndexProgress::PageProcessed {
+                    current: i + 1,
+                    total,
+                    file_name: file_name.clone(),
+                    page,
+                    total_pages,
+                })
+            };
+            match self.index_document_with_progress(db, file, on_page) {
                 Ok(0) => skipped += 1,
                 Ok(_) => indexed += 1,
                 Err(e) => {
@@ -1062,10 +1377,23 @@ mod tests {
             DocumentType::from_extension("docx"),
             Some(DocumentType::Docx)
         );
+        assert_eq!(DocumentType::from_extension("odt"), Some(DocumentType::Odt));
         assert_eq!(
             DocumentType::from_extension("xlsx"),
             Some(DocumentType::Xlsx)
         );
+        assert_eq!(DocumentType::from_extension("csv"), Some(DocumentType::Csv));
+        assert_eq!(DocumentType::from_extension("TSV"), Some(DocumentType::Csv));
+        assert_eq!(
+            DocumentType::from_extension("htm"),
+            Some(DocumentType::Html)
+        );
+        assert_eq!(
+            DocumentType::from_extension("mhtml"),
+            Some(DocumentType::Mhtml)
+        );
+        assert_eq!(DocumentType::from_extension("eml"), Some(DocumentType::Eml));
+        assert_eq!(DocumentType::from_extension("msg"), Some(DocumentType::Msg));
         assert_eq!(DocumentType::from_extension("unknown"), None);
     }
 
@@ -1113,13 +1441,16 @@ Nested content.
                     heading: Some("Intro".to_string()),
                     level: 1,
                     content: "This is a short introduction.".to_string(),
+                    page: None,
                 },
                 Section {
                     heading: Some("Body".to_string()),
                     level: 2,
                     content: "A ".repeat(600), // ~600 words
+                    page: None,
                 },
             ],
+            source_url: None,
         };
 
         let chunks = indexer.chunk_document(&doc);
@@ -1133,6 +1464,102 @@ Nested content.
         }
     }
 
+    #[test]
+    fn test_pdf_pages_chunk_with_page_numbers() {
+        let indexer = KbIndexer::new();
+
+        let doc = ParsedDocument {
+            title: None,
+            sections: KbIndexer::pdf_page_sections(vec![
+                "Reset the VPN client.".to_string(),
+                "   ".to_string(),
+                "B ".repeat(600),
+            ]),
+            source_url: None,
+        };
+        assert_eq!(doc.sections.len(), 2);
+
+        let chunks = indexer.chunk_document(&doc);
+        assert_eq!(chunks[0].page_number, Some(1));
+        assert_eq!(chunks[0].heading_path.as_deref(), Some("Page 1"));
+
+        // Page 2 is blank; page 3 splits into several chunks, all on page 3
+        let page_three: Vec<_> = chunks.iter().skip(1).collect();
+        assert!(page_three.len() > 1);
+        for chunk in page_three {
+            assert_eq!(chunk.page_number, Some(3));
+            assert_eq!(chunk.heading_path.as_deref(), Some("Page 3"));
+        }
+    }
+
+    #[test]
+    fn test_csv_chunks_carry_headers_and_rows() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("assets.csv");
+        std::fs::write(
+            &path,
+            "Asset Tag,Owner,Model\nASSET-1233,Priya Shah,ThinkPad X1\nASSET-1234,Jordan Lee,\"MacBook Pro, 14\"\"\"\n",
+        )
+        .unwrap();
+
+        let indexer = KbIndexer::new().with_tabular_options(TabularOptions {
+            rows_per_chunk: 1,
+            ..Default::default()
+        });
+        let doc = indexer.parse_document(&path).unwrap();
+        let chunks = indexer.chunk_document(&doc);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].heading_path.as_deref(), Some("assets > Row 3"));
+        assert_eq!(
+            chunks[1].content,
+            "Asset Tag | Owner | Model\nASSET-1234 | Jordan Lee | MacBook Pro, 14\""
+        );
+    }
+
+    #[test]
+    fn test_index_html_records_source_url() {
+        use crate::db::Database;
+        use crate::security::MasterKey;
+
+        let db_dir = tempdir().unwrap();
+        let db = Database::open(&db_dir.path().join("test.db"), &MasterKey::generate()).unwrap();
+        db.initialize().unwrap();
+
+        let kb_dir = tempdir().unwrap();
+        let path = kb_dir.path().join("sso.html");
+        std::fs::write(
+            &path,
+            r#"<html><head><title>SSO Login Loop</title>
+<link rel="canonical" href="https://support.vendor.com/kb/sso-loop"></head>
+<body><nav>Products Pricing Support</nav>
+<h1>SSO Login Loop</h1><p>Clear the IdP session cookie and sign in again.</p></body></html>"#,
+        )
+        .unwrap();
+
+        let indexer = KbIndexer::new();
+        assert_eq!(indexer.index_document(&db, &path).unwrap(), 1);
+
+        let (title, source_url): (String, Option<String>) = db
+            .conn()
+            .query_row("SELECT title, source_url FROM kb_documents", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!(title, "SSO Login Loop");
+        assert_eq!(
+            source_url.as_deref(),
+            Some("https://support.vendor.com/kb/sso-loop")
+        );
+
+        let content: String = db
+            .conn()
+            .query_row("SELECT content FROM kb_chunks", [], |row| row.get(0))
+            .unwrap();
+        assert!(content.contains("Clear the IdP session cookie"));
+        assert!(!content.contains("Pricing"));
+    }
+
     #[test]
     fn test_file_hash() {
         let dir = tempdir().unwrap();
@@ -1305,6 +1732,22 @@ If self-service is not available, contact IT support for an admin reset.
         assert!(files[0].to_string_lossy().contains("real_subdir"));
     }
 
+    #[test]
+    fn test_section_builder_splits_at_headings() {
+        let mut builder = SectionBuilder::default();
+        builder.push("Preamble\n");
+        builder.heading("Install", 1);
+        builder.push("Run the installer.\n");
+        builder.heading("Empty", 2);
+        builder.heading("Verify", 2);
+        builder.push("Check the version.\n");
+
+        let sections = builder.finish();
+        let headings: Vec<_> = sections.iter().map(|s| s.heading.as_deref()).collect();
+        assert_eq!(headings, vec![None, Some("Install"), Some("Verify")]);
+        assert_eq!(sections[2].level, 2);
+    }
+
     #[test]
     fn test_file_size_limit_constants() {
         // Verify size limits are reasonable
@@ -1331,6 +1774,10 @@ If self-service is not available, contact IT support for an admin reset.
             KbIndexer::max_file_size(&DocumentType::Docx),
             MAX_BINARY_FILE_SIZE
         );
+        assert_eq!(
+            KbIndexer::max_file_size(&DocumentType::Odt),
+            MAX_BINARY_FILE_SIZE
+        );
         assert_eq!(
             KbIndexer::max_file_size(&DocumentType::Xlsx),
             MAX_BINARY_FILE_SIZE
@@ -0,0 +1,607 @@
+//! Recursive website crawling for AssistSupport
+//! Follows links breadth-first from a start page up to a depth limit,
+//! honoring robots.txt, URL include/exclude patterns and a politeness delay.
+//! Pages are fetched through `WebIngester`, so every hop keeps its SSRF checks.
+
+use super::web::{FetchedPage, WebIngestConfig, WebIngester};
+use super::{CancellationToken, IngestError, IngestResult, IngestedDocument};
+use crate::kb::network::{canonicalize_url, extract_links};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// Default number of link hops followed from the start page
+pub const DEFAULT_MAX_DEPTH: usize = 2;
+
+/// Upper bound for `max_depth`
+pub const MAX_DEPTH: usize = 5;
+
+/// Default number of pages fetched in one crawl
+pub const DEFAULT_MAX_PAGES: usize = 50;
+
+/// Upper bound for `max_pages`
+pub const MAX_PAGES: usize = 500;
+
+/// Default wait between requests
+pub const DEFAULT_DELAY_MS: u64 = 1000;
+
+/// Upper bound for the wait between requests, including robots.txt Crawl-delay
+pub const MAX_DELAY_MS: u64 = 30_000;
+
+/// Product token matched against robots.txt User-agent lines
+const ROBOTS_USER_AGENT: &str = "AssistSupport";
+
+/// Content types accepted for robots.txt
+const ROBOTS_CONTENT_TYPES: &[&str] = &["text/"];
+
+/// How far and where a crawl may go
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrawlOptions {
+    /// Link hops followed from the start page (0 fetches only the start page)
+    pub max_depth: usize,
+    /// Page requests (successful or not) before the crawl stops
+    pub max_pages: usize,
+    /// Only follow links on the start page's origin
+    pub same_origin: bool,
+    /// Patterns a followed link must match one of; empty follows every link.
+    /// `*` matches any run of characters. Patterns containing `://` match
+    /// the whole URL, others match the path and query (e.g. `/docs/*`).
+    pub include_patterns: Vec<String>,
+    /// Patterns whose matching links are never followed
+    pub exclude_patterns: Vec<String>,
+    /// Minimum wait between requests in milliseconds; a longer robots.txt
+    /// Crawl-delay takes precedence
+    pub delay_ms: u64,
+    /// Skip pages robots.txt disallows
+    pub respect_robots: bool,
+}
+
+impl Default for CrawlOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_pages: DEFAULT_MAX_PAGES,
+            same_origin: true,
+            include_patterns: Vec::new(),
+            exclude_patterns: Vec::new(),
+            delay_ms: DEFAULT_DELAY_MS,
+            respect_robots: true,
+        }
+    }
+}
+
+impl CrawlOptions {
+    /// Check limits and compile the URL patterns
+    pub fn validate(&self) -> IngestResult<()> {
+        if self.max_depth > MAX_DEPTH {
+            return Err(IngestError::InvalidSource(format!(
+                "Crawl depth must be at most {}",
+                MAX_DEPTH
+            )));
+        }
+        if self.max_pages == 0 || self.max_pages > MAX_PAGES {
+            return Err(IngestError::InvalidSource(format!(
+                "Crawl page limit must be between 1 and {}",
+                MAX_PAGES
+            )));
+        }
+        if self.delay_ms > MAX_DELAY_MS {
+            return Err(IngestError::InvalidSource(format!(
+                "Crawl delay must be at most {} ms",
+                MAX_DELAY_MS
+            )));
+        }
+        UrlFilter::new(self).map(|_| ())
+    }
+}
+
+/// Include/exclude patterns compiled to anchored regexes
+#[derive(Debug)]
+pub struct UrlFilter {
+    include: Vec<UrlPattern>,
+    exclude: Vec<UrlPattern>,
+}
+
+#[derive(Debug)]
+struct UrlPattern {
+    regex: regex_lite::Regex,
+    full_url: bool,
+}
+
+impl UrlPattern {
+    fn new(pattern: &str) -> IngestResult<Self> {
+        let regex = format!(
+            "^{}$",
+            pattern
+                .split('*')
+                .map(regex_lite::escape)
+                .collect::<Vec<_>>()
+                .join(".*")
+        );
+        let regex = regex_lite::Regex::new(&regex).map_err(|e| {
+            IngestError::InvalidSource(format!("Invalid URL pattern '{}': {}", pattern, e))
+        })?;
+        Ok(Self {
+            regex,
+            full_url: pattern.contains("://"),
+        })
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        if self.full_url {
+            self.regex.is_match(url.as_str())
+        } else {
+            self.regex.is_match(&path_and_query(url))
+        }
+    }
+}
+
+impl UrlFilter {
+    pub fn new(options: &CrawlOptions) -> IngestResult<Self> {
+        let compile = |patterns: &[String]| -> IngestResult<Vec<UrlPattern>> {
+            patterns
+                .iter()
+                .map(|p| p.trim())
+                .filter(|p| !p.is_empty())
+                .map(UrlPattern::new)
+                .collect()
+        };
+        Ok(Self {
+            include: compile(&options.include_patterns)?,
+            exclude: compile(&options.exclude_patterns)?,
+        })
+    }
+
+    /// Whether a link passes the include and exclude patterns
+    pub fn allows(&self, url: &Url) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| p.matches(url)))
+            && !self.exclude.iter().any(|p| p.matches(url))
+    }
+}
+
+/// Allow/Disallow rules from the robots.txt group that applies to us
+#[derive(Debug, Clone, Default)]
+pub struct RobotsRules {
+    rules: Vec<RobotsRule>,
+    crawl_delay: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+struct RobotsRule {
+    allow: bool,
+    pattern: String,
+}
+
+#[derive(Debug, Default)]
+struct RobotsGroup {
+    agents: Vec<String>,
+    rules: Vec<RobotsRule>,
+    crawl_delay: Option<f64>,
+}
+
+impl RobotsRules {
+    /// Rules that allow every path (no robots.txt)
+    pub fn allow_all() -> Self {
+        Self::default()
+    }
+
+    /// Rules that disallow every path (robots.txt unreachable)
+    pub fn disallow_all() -> Self {
+        Self {
+            rules: vec![RobotsRule {
+                allow: false,
+                pattern: "/".to_string(),
+            }],
+            crawl_delay: None,
+        }
+    }
+
+    /// Parse robots.txt, keeping the groups naming `user_agent` or, when
+    /// none does, the `*` groups
+    pub fn parse(content: &str, user_agent: &str) -> Self {
+        let mut groups: Vec<RobotsGroup> = Vec::new();
+        let mut reading_agents = false;
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim();
+
+            if key == "user-agent" {
+                if !reading_agents {
+                    groups.push(RobotsGroup::default());
+                }
+                reading_agents = true;
+                if let Some(group) = groups.last_mut() {
+                    group.agents.push(value.to_ascii_lowercase());
+                }
+                continue;
+            }
+
+            reading_agents = false;
+            let Some(group) = groups.last_mut() else {
+                continue;
+            };
+            match key.as_str() {
+                // An empty Disallow allows everything, so it adds no rule
+                "allow" | "disallow" if !value.is_empty() => group.rules.push(RobotsRule {
+                    allow: key == "allow",
+                    pattern: value.to_string(),
+                }),
+                "crawl-delay" => {
+                    group.crawl_delay = value.parse::<f64>().ok().filter(|d| d.is_finite());
+                }
+                _ => {}
+            }
+        }
+
+        let user_agent = user_agent.to_ascii_lowercase();
+        let naming = |agent: &str| -> Vec<&RobotsGroup> {
+            groups
+                .iter()
+                .filter(|g| g.agents.iter().any(|a| a == agent))
+                .collect()
+        };
+        let mut selected = naming(&user_agent);
+        if selected.is_empty() {
+            selected = naming("*");
+        }
+
+        Self {
+            rules: selected
+                .iter()
+                .flat_map(|g| g.rules.iter().cloned())
+                .collect(),
+            crawl_delay: selected
+                .iter()
+                .filter_map(|g| g.crawl_delay)
+                .filter(|d| *d > 0.0)
+                .reduce(f64::max)
+                .map(|secs| Duration::from_secs_f64(secs.min(MAX_DELAY_MS as f64 / 1000.0))),
+        }
+    }
+
+    /// Whether `path` (path plus query) may be fetched. The longest
+    /// matching rule wins; Allow wins a tie.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        let mut best: Option<(usize, bool)> = None;
+        for rule in &self.rules {
+            if !robots_pattern_matches(&rule.pattern, path) {
+                continue;
+            }
+            let len = rule.pattern.len();
+            best = match best {
+                Some((best_len, allow)) if best_len > len || (best_len == len && allow) => {
+                    Some((best_len, allow))
+                }
+                _ => Some((len, rule.allow)),
+            };
+        }
+        !matches!(best, Some((_, false)))
+    }
+
+    /// Crawl-delay requested for us, if any
+    pub fn crawl_delay(&self) -> Option<Duration> {
+        self.crawl_delay
+    }
+}
+
+/// Match a robots.txt path pattern: a prefix match where `*` matches any
+/// run of characters and a trailing `$` anchors the end
+fn robots_pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    if !path.starts_with(first) {
+        return false;
+    }
+    let mut pos = first.len();
+    let rest: Vec<&str> = parts.collect();
+    for (i, part) in rest.iter().enumerate() {
+        if anchored && i == rest.len() - 1 {
+            return path.len() - pos >= part.len() && path.ends_with(part);
+        }
+        match path[pos..].find(part) {
+            Some(offset) => pos += offset + part.len(),
+            None => return false,
+        }
+    }
+    !anchored || pos == path.len()
+}
+
+fn path_and_query(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
+
+/// Counts from a crawl
+#[derive(Debug, Default)]
+pub struct CrawlReport {
+    /// Pages fetched successfully
+    pub pages_fetched: usize,
+    /// Fetched pages indexed (including unchanged pages left as they were)
+    pub pages_indexed: usize,
+    /// Pages skipped because robots.txt disallows them
+    pub blocked_by_robots: usize,
+    /// Links not followed because of the origin restriction or URL patterns
+    pub links_filtered: usize,
+    /// Pages that failed to fetch or index
+    pub errors: usize,
+    pub documents: Vec<IngestedDocument>,
+}
+
+/// Breadth-first crawler over a `WebIngester`
+pub struct WebCrawler {
+    ingester: WebIngester,
+    options: CrawlOptions,
+    filter: UrlFilter,
+    /// robots.txt rules by origin
+    robots: HashMap<String, RobotsRules>,
+    last_request: Option<Instant>,
+}
+
+impl WebCrawler {
+    pub async fn new(options: CrawlOptions) -> IngestResult<Self> {
+        options.validate()?;
+        let filter = UrlFilter::new(&options)?;
+        let ingester = WebIngester::new(WebIngestConfig::default()).await?;
+        Ok(Self {
+            ingester,
+            options,
+            filter,
+            robots: HashMap::new(),
+            last_request: None,
+        })
+    }
+
+    /// Crawl from `start_url`, passing each fetched page to `index_page`.
+    /// `on_progress` is called with the running counts and the next URL
+    /// before each page is fetched. Follows links only from pages fetched within `max_depth`
+    /// hops; the start page itself is never filtered by the URL patterns.
+    pub async fn crawl(
+        &mut self,
+        start_url: &str,
+        cancel_token: &CancellationToken,
+        mut index_page: impl FnMut(&FetchedPage) -> Result<IngestedDocument, String>,
+        on_progress: impl Fn(&CrawlReport, &str),
+    ) -> IngestResult<CrawlReport> {
+        let start = Url::parse(&canonicalize_url(start_url)?)
+            .map_err(|e| IngestError::InvalidSource(e.to_string()))?;
+        let mut origin = start.origin();
+        let mut report = CrawlReport::default();
+        let mut seen = HashSet::from([start.to_string()]);
+        let mut queue = VecDeque::from([(start, 0)]);
+        let mut requests = 0;
+
+        while let Some((url, depth)) = queue.pop_front() {
+            if requests >= self.options.max_pages {
+                break;
+            }
+            on_progress(&report, url.as_str());
+            if cancel_token.is_cancelled() {
+                return Err(IngestError::Cancelled);
+            }
+
+            if self.options.respect_robots {
+                let rules = self.robots_rules(&url).await;
+                if !rules.is_allowed(&path_and_query(&url)) {
+                    tracing::info!("robots.txt disallows {}", url);
+                    report.blocked_by_robots += 1;
+                    continue;
+                }
+            }
+
+            self.wait_turn(&url).await;
+            requests += 1;
+            let page = match self.ingester.fetch_page(url.as_str()).await {
+                Ok(page) => page,
+                Err(e) => {
+                    tracing::warn!("Failed to fetch {}: {}", url, e);
+                    report.errors += 1;
+                    continue;
+                }
+            };
+            report.pages_fetched += 1;
+
+            // A redirect can leave the site; judge links by where we landed
+            let page_url = Url::parse(&page.url).unwrap_or(url);
+            if depth == 0 {
+                origin = page_url.origin();
+            } else if self.options.same_origin && page_url.origin() != origin {
+                report.links_filtered += 1;
+                continue;
+            }
+
+            if cancel_token.is_cancelled() {
+                return Err(IngestError::Cancelled);
+            }
+            match index_page(&page) {
+                Ok(document) => {
+                    report.pages_indexed += 1;
+                    report.documents.push(document);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to index {}: {}", page.url, e);
+                    report.errors += 1;
+                }
+            }
+
+            if depth >= self.options.max_depth {
+                continue;
+            }
+            for link in extract_links(&page_url, &page.content) {
+                let Some(link) = canonicalize_url(&link)
+                    .ok()
+                    .and_then(|l| Url::parse(&l).ok())
+                else {
+                    continue;
+                };
+                if !seen.insert(link.to_string()) {
+                    continue;
+                }
+                if (self.options.same_origin && link.origin() != origin)
+                    || !self.filter.allows(&link)
+                {
+                    report.links_filtered += 1;
+                    continue;
+                }
+                queue.push_back((link, depth + 1));
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// robots.txt rules for the URL's origin, fetched once per origin.
+    /// A missing robots.txt allows everything; one that can't be read
+    /// (server error, timeout, rate limit) disallows everything.
+    async fn robots_rules(&mut self, url: &Url) -> RobotsRules {
+        let key = url.origin().ascii_serialization();
+        if let Some(rules) = self.robots.get(&key) {
+            return rules.clone();
+        }
+
+        let rules = match url.join("/robots.txt") {
+            Ok(robots_url) => {
+                self.wait_turn(url).await;
+                match self
+                    .ingester
+                    .fetch_document(robots_url.as_str(), ROBOTS_CONTENT_TYPES)
+                    .await
+                {
+                    Ok(document) => RobotsRules::parse(&document.content, ROBOTS_USER_AGENT),
+                    Err(
+                        IngestError::NotFound(_)
+                        | IngestError::AuthRequired(_)
+                        | IngestError::InvalidSource(_),
+                    ) => RobotsRules::allow_all(),
+                    Err(e) => {
+                        tracing::warn!("Failed to read {}: {}", robots_url, e);
+                        RobotsRules::disallow_all()
+                    }
+                }
+            }
+            Err(_) => RobotsRules::allow_all(),
+        };
+        self.robots.insert(key, rules.clone());
+        rules
+    }
+
+    /// Sleep until the politeness delay since the previous request has
+    /// passed
+    async fn wait_turn(&mut self, url: &Url) {
+        let mut delay = Duration::from_millis(self.options.delay_ms);
+        if let Some(crawl_delay) = self
+            .robots
+            .get(&url.origin().ascii_serialization())
+            .and_then(RobotsRules::crawl_delay)
+        {
+            delay = delay.max(crawl_delay);
+        }
+        if let Some(elapsed) = self.last_request.map(|t| t.elapsed()) {
+            if elapsed < delay {
+                tokio::time::sleep(delay - elapsed).await;
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTS: &str = "\
+# Example robots.txt
+User-agent: *
+Disallow: /private/
+Crawl-delay: 2
+
+User-agent: Googlebot
+User-agent: AssistSupport
+Disallow: /internal/
+Allow: /internal/public/
+Disallow: /*.pdf$
+Disallow: /search?
+Crawl-delay: 5
+";
+
+    #[test]
+    fn test_robots_uses_named_group() {
+        let rules = RobotsRules::parse(ROBOTS, ROBOTS_USER_AGENT);
+        assert!(rules.is_allowed("/private/notes"));
+        assert!(!rules.is_allowed("/internal/runbook"));
+        assert!(rules.is_allowed("/internal/public/faq"));
+        assert_eq!(rules.crawl_delay(), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_robots_falls_back_to_wildcard_group() {
+        let rules = RobotsRules::parse(ROBOTS, "OtherBot");
+        assert!(!rules.is_allowed("/private/notes"));
+        assert!(rules.is_allowed("/internal/runbook"));
+        assert_eq!(rules.crawl_delay(), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_robots_wildcards_and_anchors() {
+        let rules = RobotsRules::parse(ROBOTS, ROBOTS_USER_AGENT);
+        assert!(!rules.is_allowed("/docs/vpn-guide.pdf"));
+        assert!(rules.is_allowed("/docs/vpn-guide.pdf.html"));
+        assert!(!rules.is_allowed("/search?q=vpn"));
+        assert!(rules.is_allowed("/search"));
+    }
+
+    #[test]
+    fn test_robots_empty_disallow_and_tie() {
+        let rules = RobotsRules::parse(
+            "User-agent: *\nDisallow:\n\nUser-agent: assistsupport\nDisallow: /kb\nAllow: /kb\n",
+            ROBOTS_USER_AGENT,
+        );
+        assert!(rules.is_allowed("/kb/article"));
+        assert!(RobotsRules::parse("User-agent: *\nDisallow:\n", "x").is_allowed("/"));
+        assert!(!RobotsRules::disallow_all().is_allowed("/anything"));
+    }
+
+    #[test]
+    fn test_url_filter_patterns() {
+        let options = CrawlOptions {
+            include_patterns: vec!["/docs/*".into(), "https://help.example.com/*".into()],
+            exclude_patterns: vec!["*/archive/*".into(), "*?print=*".into()],
+            ..Default::default()
+        };
+        let filter = UrlFilter::new(&options).unwrap();
+        let allows = |url: &str| filter.allows(&Url::parse(url).unwrap());
+
+        assert!(allows("https://example.com/docs/vpn"));
+        assert!(allows("https://help.example.com/articles/1"));
+        assert!(!allows("https://example.com/blog/post"));
+        assert!(!allows("https://example.com/docs/archive/2019"));
+        assert!(!allows("https://example.com/docs/vpn?print=1"));
+    }
+
+    #[test]
+    fn test_validate_limits() {
+        assert!(CrawlOptions::default().validate().is_ok());
+        let too_deep = CrawlOptions {
+            max_depth: MAX_DEPTH + 1,
+            ..Default::default()
+        };
+        assert!(too_deep.validate().is_err());
+        let no_pages = CrawlOptions {
+            max_pages: 0,
+            ..Default::default()
+        };
+        assert!(no_pages.validate().is_err());
+    }
+}
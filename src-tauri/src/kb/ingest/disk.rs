@@ -5,6 +5,7 @@
 use super::{IngestError, IngestResult, IngestedDocument};
 use crate::db::{Database, IngestRunCompletion, IngestSource};
 use crate::kb::indexer::KbIndexer;
+use crate::validation::normalize_and_validate_namespace_id;
 use std::path::Path;
 
 /// Result of a disk folder ingestion
@@ -20,15 +21,24 @@ pub struct DiskIngestResult {
 /// Disk folder ingester with ingest source/run tracking
 pub struct DiskIngester {
     indexer: KbIndexer,
+    mailbox_namespaces: bool,
 }
 
 impl DiskIngester {
     pub fn new() -> Self {
         Self {
             indexer: KbIndexer::new(),
+            mailbox_namespaces: false,
         }
     }
 
+    /// File emails (EML/MSG) under a `mailbox-<address>` namespace per
+    /// delivery mailbox instead of the folder's namespace
+    pub fn with_mailbox_namespaces(mut self, enabled: bool) -> Self {
+        self.mailbox_namespaces = enabled;
+        self
+    }
+
     /// Ingest a folder into the knowledge base with source tracking.
     ///
     /// Creates `IngestSource` (source_type="file") and `IngestRun` entries
@@ -52,7 +62,8 @@ impl DiskIngester {
         let mut documents = Vec::new();
 
         for file_path in &files {
-            match self.ingest_file(db, file_path, namespace_id) {
+            let namespace_id = self.namespace_for(db, file_path, namespace_id);
+            match self.ingest_file(db, file_path, &namespace_id) {
                 Ok(Some(doc)) => {
                     ingested += 1;
                     documents.push(doc);
@@ -76,6 +87,29 @@ impl DiskIngester {
         })
     }
 
+    /// Namespace for a file: its mailbox namespace for emails when enabled
+    /// and the mailbox is known, otherwise the folder's namespace
+    fn namespace_for(&self, db: &Database, file_path: &Path, default: &str) -> String {
+        if !self.mailbox_namespaces {
+            return default.to_string();
+        }
+        let Some(mailbox) = crate::kb::email::mailbox_for_file(file_path) else {
+            return default.to_string();
+        };
+        let namespace_id =
+            match normalize_and_validate_namespace_id(&format!("mailbox-{}", mailbox)) {
+                Ok(id) => id,
+                Err(_) => return default.to_string(),
+            };
+        match db.ensure_namespace_exists(&namespace_id) {
+            Ok(()) => namespace_id,
+            Err(e) => {
+                tracing::warn!("Failed to create namespace {}: {}", namespace_id, e);
+                default.to_string()
+            }
+        }
+    }
+
     /// Ingest a single file with source/run tracking.
     /// Returns `Ok(None)` if the file content is unchanged (skipped).
     fn ingest_file(
@@ -192,8 +226,8 @@ impl DiskIngester {
         db.conn()
             .execute(
                 "INSERT INTO kb_documents (id, file_path, file_hash, title, indexed_at, chunk_count,
-                        namespace_id, source_type, source_id)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                        namespace_id, source_type, source_id, source_url)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
                 rusqlite::params![
                     doc_id,
                     file_path_str,
@@ -204,6 +238,7 @@ impl DiskIngester {
                     namespace_id,
                     "file",
                     source.id,
+                    parsed.source_url,
                 ],
             )
             .map_err(IngestError::Sqlite)?;
@@ -213,8 +248,8 @@ impl DiskIngester {
             let chunk_id = uuid::Uuid::new_v4().to_string();
             db.conn()
                 .execute(
-                    "INSERT INTO kb_chunks (id, document_id, chunk_index, heading_path, content, word_count, namespace_id)
-                     VALUES (?, ?, ?, ?, ?, ?, ?)",
+                    "INSERT INTO kb_chunks (id, document_id, chunk_index, heading_path, content, word_count, namespace_id, page_number)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
                     rusqlite::params![
                         chunk_id,
                         doc_id,
@@ -223,6 +258,7 @@ impl DiskIngester {
                         chunk.content,
                         chunk.word_count as i32,
                         namespace_id,
+                        chunk.page_number,
                     ],
                 )
                 .map_err(IngestError::Sqlite)?;
@@ -432,4 +468,41 @@ mod tests {
         assert_eq!(ns, "test-ns");
         assert_eq!(st, "file");
     }
+
+    #[test]
+    fn test_disk_ingest_mailbox_namespaces() {
+        let (_db_dir, db) = setup_test_db();
+        let kb_dir = TempDir::new().unwrap();
+
+        std::fs::write(
+            kb_dir.path().join("ticket.eml"),
+            "Delivered-To: Support@Acme.com\r\nFrom: user@customer.com\r\nSubject: Printer offline\r\n\r\nThe office printer shows offline after the update.\r\n",
+        )
+        .unwrap();
+        std::fs::write(
+            kb_dir.path().join("guide.md"),
+            "# Printers\n\nRestart the spooler service.",
+        )
+        .unwrap();
+
+        db.ensure_namespace_exists("default").unwrap();
+
+        let ingester = DiskIngester::new().with_mailbox_namespaces(true);
+        let result = ingester
+            .ingest_folder(&db, kb_dir.path(), "default")
+            .unwrap();
+        assert_eq!(result.ingested, 2);
+
+        let namespace_for = |suffix: &str| -> String {
+            db.conn()
+                .query_row(
+                    "SELECT namespace_id FROM kb_documents WHERE file_path LIKE ?",
+                    [format!("%{}", suffix)],
+                    |row| row.get(0),
+                )
+                .unwrap()
+        };
+        assert_eq!(namespace_for("ticket.eml"), "mailbox-support-acme-com");
+        assert_eq!(namespace_for("guide.md"), "default");
+    }
 }
@@ -173,6 +173,13 @@ impl GitHubRemoteRepo {
     }
 }
 
+/// Remote repository checked out into the local clone cache
+#[derive(Debug, Clone)]
+pub struct ClonedRepo {
+    pub remote: GitHubRemoteRepo,
+    pub path: PathBuf,
+}
+
 fn is_valid_slug(value: &str) -> bool {
     value
         .chars()
@@ -562,6 +569,14 @@ impl GitHubIngester {
             ));
         }
 
+        // Network hiccups (DNS, dropped connections, 5xx) are reported as
+        // network errors so callers can retry them
+        if crate::jobs::retry::is_retryable_error(&stderr) {
+            return Err(IngestError::Network(
+                crate::kb::network::NetworkError::RequestFailed(stderr),
+            ));
+        }
+
         Err(IngestError::InvalidSource(if stderr.is_empty() {
             "Git command failed".to_string()
         } else {
@@ -753,6 +768,7 @@ impl GitHubIngester {
             let parsed = ParsedDocument {
                 title: Some(title.clone()),
                 sections,
+                source_url: None,
             };
 
             // Chunk the document
@@ -903,6 +919,19 @@ impl GitHubIngester {
         cancel_token: &CancellationToken,
         progress: Option<&ProgressCallback>,
     ) -> IngestResult<Vec<IngestedDocument>> {
+        let cloned = self.clone_remote_repo(repo_url, token, cancel_token, progress)?;
+        self.index_remote_repo(db, &cloned, namespace_id, cancel_token, progress)
+    }
+
+    /// Clone or update a remote repository in the local cache without
+    /// touching the database
+    pub fn clone_remote_repo(
+        &self,
+        repo_url: &str,
+        token: Option<&str>,
+        cancel_token: &CancellationToken,
+        progress: Option<&ProgressCallback>,
+    ) -> IngestResult<ClonedRepo> {
         let remote = parse_https_repo_url(repo_url)?;
 
         if let Some(progress) = progress {
@@ -914,7 +943,23 @@ impl GitHubIngester {
             });
         }
 
-        let repo_dir = self.clone_or_update_repo(&remote, token, cancel_token)?;
+        let path = self.clone_or_update_repo(&remote, token, cancel_token)?;
+        Ok(ClonedRepo { remote, path })
+    }
+
+    /// Index a repository checkout produced by `clone_remote_repo`
+    pub fn index_remote_repo(
+        &self,
+        db: &Database,
+        cloned: &ClonedRepo,
+        namespace_id: &str,
+        cancel_token: &CancellationToken,
+        progress: Option<&ProgressCallback>,
+    ) -> IngestResult<Vec<IngestedDocument>> {
+        let ClonedRepo {
+            remote,
+            path: repo_dir,
+        } = cloned;
         let source_uri = format!(
             "github://{}/{}/{}",
             remote.host_port, remote.owner, remote.repo
@@ -929,7 +974,7 @@ impl GitHubIngester {
 
         self.ingest_repo_internal(
             db,
-            &repo_dir,
+            repo_dir,
             namespace_id,
             cancel_token,
             progress,
@@ -948,6 +993,7 @@ fn build_sections_from_code(content: &str, headings: &[(usize, String)]) -> Vec<
             heading: headings.first().map(|(_, h)| h.clone()),
             level: 1,
             content: content.to_string(),
+            page: None,
         }];
     }
 
@@ -957,6 +1003,7 @@ fn build_sections_from_code(content: &str, headings: &[(usize, String)]) -> Vec<
         heading: headings.first().map(|(_, h)| h.clone()),
         level: 1,
         content: content.to_string(),
+        page: None,
     }]
 }
 
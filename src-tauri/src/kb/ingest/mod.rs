@@ -1,7 +1,8 @@
 //! Content ingestion modules for AssistSupport
-//! Handles web pages, YouTube videos, GitHub repos, and batch processing
+//! Handles web pages and site crawls, YouTube videos, GitHub repos, and batch processing
 
 pub mod batch;
+pub mod crawl;
 pub mod disk;
 pub mod github;
 pub mod web;
@@ -38,6 +39,21 @@ pub enum IngestError {
     Io(#[from] std::io::Error),
 }
 
+impl IngestError {
+    /// Whether the failure is transient, so retrying the ingestion may succeed
+    pub fn is_transient(&self) -> bool {
+        use crate::kb::network::NetworkError;
+        match self {
+            Self::Timeout(_) | Self::RateLimited(_) => true,
+            Self::Network(NetworkError::Timeout | NetworkError::DnsResolutionFailed(_)) => true,
+            Self::Network(NetworkError::RequestFailed(message)) => {
+                crate::jobs::retry::is_retryable_error(message)
+            }
+            _ => false,
+        }
+    }
+}
+
 /// Result type for ingestion operations
 pub type IngestResult<T> = Result<T, IngestError>;
 
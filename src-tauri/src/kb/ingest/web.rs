@@ -53,6 +53,9 @@ impl Default for WebIngestConfig {
     }
 }
 
+/// Content types `fetch_page` accepts
+const HTML_CONTENT_TYPES: &[&str] = &["text/html", "application/xhtml"];
+
 /// Fetched web page content
 #[derive(Debug)]
 pub struct FetchedPage {
@@ -127,11 +130,36 @@ impl WebIngester {
     /// Security: DNS is resolved once and pinned IPs are used for the connection,
     /// preventing DNS rebinding attacks.
     pub async fn fetch_page(&self, url: &str) -> IngestResult<FetchedPage> {
+        let mut page = self.fetch_document(url, HTML_CONTENT_TYPES).await?;
+
+        // Check for login page
+        let page_url =
+            Url::parse(&page.url).map_err(|e| IngestError::InvalidSource(e.to_string()))?;
+        if is_login_page(&page_url, Some(&page.content)) {
+            return Err(IngestError::AuthRequired(format!(
+                "URL {} appears to be a login page. Please download the content manually after authenticating.",
+                page.url
+            )));
+        }
+
+        // Extract title from HTML
+        page.title = extract_html_title(&page.content);
+        Ok(page)
+    }
+
+    /// Fetch a URL whose content type contains one of `accepted` (e.g. a
+    /// feed), with the same DNS pinning, redirect and size checks as
+    /// `fetch_page`. A response without a content type is accepted.
+    pub async fn fetch_document(
+        &self,
+        url: &str,
+        accepted: &'static [&'static str],
+    ) -> IngestResult<FetchedPage> {
         // Validate URL and get pinned IPs
         let validated = validate_url_for_ssrf_with_pinning(url, &self.resolver).await?;
 
         // Fetch with redirect handling
-        self.fetch_with_redirects(validated, 0).await
+        self.fetch_with_redirects(validated, 0, accepted).await
     }
 
     /// Fetch a page following redirects with DNS pinning on each hop
@@ -141,6 +169,7 @@ impl WebIngester {
         &'a self,
         validated: ValidatedUrl,
         redirect_count: usize,
+        accepted: &'static [&'static str],
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = IngestResult<FetchedPage>> + Send + 'a>>
     {
         Box::pin(async move {
@@ -200,7 +229,7 @@ impl WebIngester {
 
                     // Follow redirect recursively
                     return self
-                        .fetch_with_redirects(redirect_validated, redirect_count + 1)
+                        .fetch_with_redirects(redirect_validated, redirect_count + 1, accepted)
                         .await;
                 }
             }
@@ -246,12 +275,14 @@ impl WebIngester {
                 .and_then(|v| v.to_str().ok())
                 .map(|s| s.to_string());
 
-            // Check content type is HTML
+            // Check content type is one we can parse
             if let Some(ref ct) = content_type {
-                if !ct.contains("text/html") && !ct.contains("application/xhtml") {
+                if !accepted.iter().any(|a| ct.contains(a)) {
                     return Err(IngestError::InvalidSource(format!(
-                        "URL {} is not HTML (content-type: {})",
-                        validated.url, ct
+                        "URL {} is not {} (content-type: {})",
+                        validated.url,
+                        accepted.join(" or "),
+                        ct
                     )));
                 }
             }
@@ -276,17 +307,6 @@ impl WebIngester {
             // Use the original URL for canonical (not the IP-based request URL)
             let canonical = canonicalize_url(validated.url.as_str())?;
 
-            // Check for login page
-            if is_login_page(&validated.url, Some(&content)) {
-                return Err(IngestError::AuthRequired(format!(
-                "URL {} appears to be a login page. Please download the content manually after authenticating.",
-                validated.url
-            )));
-            }
-
-            // Extract title from HTML
-            let title = extract_html_title(&content);
-
             Ok(FetchedPage {
                 url: validated.url.to_string(),
                 canonical_url: canonical,
@@ -294,7 +314,7 @@ impl WebIngester {
                 content_type,
                 etag,
                 last_modified,
-                title,
+                title: None,
             })
         }) // Close Box::pin(async move {
     }
@@ -427,6 +447,18 @@ impl WebIngester {
         // Fetch the page
         let page = self.fetch_page(url).await?;
 
+        Self::index_page(db, &page, namespace_id, cancel_token, progress)
+    }
+
+    /// Index an already fetched page into the knowledge base, skipping the
+    /// re-index when its text is unchanged since the last ingestion
+    pub fn index_page(
+        db: &Database,
+        page: &FetchedPage,
+        namespace_id: &str,
+        cancel_token: &CancellationToken,
+        progress: Option<&ProgressCallback>,
+    ) -> IngestResult<IngestedDocument> {
         if cancel_token.is_cancelled() {
             return Err(IngestError::Cancelled);
         }
@@ -541,6 +573,7 @@ impl WebIngester {
         let parsed = ParsedDocument {
             title: Some(title.clone()),
             sections,
+            source_url: None,
         };
 
         if cancel_token.is_cancelled() {
@@ -791,6 +824,7 @@ fn build_sections_from_headings(content: &str, headings: &[(usize, String)]) ->
             heading: None,
             level: 0,
             content: content.to_string(),
+            page: None,
         }];
     }
 
@@ -800,6 +834,7 @@ fn build_sections_from_headings(content: &str, headings: &[(usize, String)]) ->
         heading: None,
         level: 0,
         content: content.to_string(),
+        page: None,
     }];
 
     // Note: A more sophisticated approach would split the content by heading positions
@@ -54,6 +54,14 @@ pub struct TranscriptEntry {
     pub text: String,
 }
 
+/// Metadata and transcript fetched for a video, ready to index
+#[derive(Debug, Clone)]
+pub struct FetchedVideo {
+    pub source_uri: String,
+    pub metadata: VideoMetadata,
+    pub transcript: String,
+}
+
 /// YouTube video ingester
 pub struct YouTubeIngester {
     config: YouTubeIngestConfig,
@@ -314,6 +322,17 @@ impl YouTubeIngester {
         cancel_token: &CancellationToken,
         progress: Option<&ProgressCallback>,
     ) -> IngestResult<IngestedDocument> {
+        let video = self.fetch_video(url, cancel_token, progress).await?;
+        Self::index_video(db, &video, namespace_id, cancel_token, progress)
+    }
+
+    /// Fetch a video's metadata and transcript without touching the database
+    pub async fn fetch_video(
+        &self,
+        url: &str,
+        cancel_token: &CancellationToken,
+        progress: Option<&ProgressCallback>,
+    ) -> IngestResult<FetchedVideo> {
         if cancel_token.is_cancelled() {
             return Err(IngestError::Cancelled);
         }
@@ -387,6 +406,31 @@ impl YouTubeIngester {
             });
         }
 
+        Ok(FetchedVideo {
+            source_uri,
+            metadata,
+            transcript: full_transcript,
+        })
+    }
+
+    /// Index a fetched video transcript into the knowledge base
+    pub fn index_video(
+        db: &Database,
+        video: &FetchedVideo,
+        namespace_id: &str,
+        cancel_token: &CancellationToken,
+        progress: Option<&ProgressCallback>,
+    ) -> IngestResult<IngestedDocument> {
+        if cancel_token.is_cancelled() {
+            return Err(IngestError::Cancelled);
+        }
+        let FetchedVideo {
+            source_uri,
+            metadata,
+            transcript: full_transcript,
+        } = video;
+        let source_uri = source_uri.clone();
+
         let now = chrono::Utc::now().to_rfc3339();
 
         // Create or update source
@@ -506,7 +550,9 @@ impl YouTubeIngester {
                 heading: Some(title.clone()),
                 level: 1,
                 content: full_transcript.clone(),
+                page: None,
             }],
+            source_url: None,
         };
 
         // Chunk the document
@@ -0,0 +1,200 @@
+//! Minimal MIME parsing shared by MHTML and email (EML) extraction
+
+use base64::Engine;
+use regex_lite::Regex;
+
+/// Unfolded headers with lowercased names, in file order
+pub type Headers = Vec<(String, String)>;
+
+/// Split a MIME entity into unfolded headers and body
+pub fn split_headers(raw: &str) -> (Headers, &str) {
+    let (head, body) = match (raw.find("\r\n\r\n"), raw.find("\n\n")) {
+        (Some(crlf), Some(lf)) if lf < crlf => (&raw[..lf], &raw[lf + 2..]),
+        (Some(crlf), _) => (&raw[..crlf], &raw[crlf + 4..]),
+        (None, Some(lf)) => (&raw[..lf], &raw[lf + 2..]),
+        (None, None) => (raw, ""),
+    };
+
+    let mut headers: Headers = Vec::new();
+    for line in head.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    (headers, body)
+}
+
+/// First value of a header; `name` must be lowercase
+pub fn header_value(headers: &[(String, String)], name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.clone())
+}
+
+/// A `key=value` parameter from a header like `Content-Type`
+pub fn header_param(value: &str, name: &str) -> Option<String> {
+    value.split(';').skip(1).find_map(|param| {
+        let (key, val) = param.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| val.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Lowercased media type of an entity, e.g. `text/html`
+pub fn content_type(headers: &[(String, String)]) -> String {
+    header_value(headers, "content-type")
+        .map(|ct| {
+            ct.split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase()
+        })
+        .unwrap_or_default()
+}
+
+/// Body parts of a multipart entity, skipping the preamble and epilogue
+pub fn parts<'a>(body: &'a str, boundary: &str) -> Vec<(Headers, &'a str)> {
+    let delimiter = format!("--{}", boundary);
+    body.split(delimiter.as_str())
+        .skip(1)
+        .take_while(|part| !part.starts_with("--"))
+        .map(|part| split_headers(part.trim_start_matches(['\r', '\n'])))
+        .collect()
+}
+
+/// Undo `Content-Transfer-Encoding` and decode the `charset` to text
+pub fn decode_text(headers: &[(String, String)], body: &str) -> Result<String, String> {
+    let encoding = header_value(headers, "content-transfer-encoding")
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let bytes = match encoding.as_str() {
+        "quoted-printable" => decode_quoted_printable(body),
+        "base64" => {
+            let compact: String = body.chars().filter(|c| !c.is_whitespace()).collect();
+            base64::engine::general_purpose::STANDARD
+                .decode(compact)
+                .map_err(|e| e.to_string())?
+        }
+        _ => body.as_bytes().to_vec(),
+    };
+    let charset = header_value(headers, "content-type").and_then(|ct| header_param(&ct, "charset"));
+    Ok(decode_charset(&bytes, charset.as_deref()))
+}
+
+/// Decode bytes in the named charset, falling back to UTF-8
+pub fn decode_charset(bytes: &[u8], label: Option<&str>) -> String {
+    let encoding = label
+        .and_then(|l| encoding_rs::Encoding::for_label(l.trim().as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8);
+    encoding.decode(bytes).0.into_owned()
+}
+
+pub fn decode_quoted_printable(input: &str) -> Vec<u8> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'=' {
+            out.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        // Soft line break
+        if bytes[i + 1..].starts_with(b"\r\n") {
+            i += 3;
+        } else if bytes[i + 1..].starts_with(b"\n") {
+            i += 2;
+        } else if let Some(byte) = input
+            .get(i + 1..i + 3)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(b'=');
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Decode RFC 2047 encoded words in a header, e.g. `=?UTF-8?B?...?=`
+pub fn decode_encoded_words(value: &str) -> String {
+    let re = Regex::new(r"=\?([^?]+)\?([bBqQ])\?([^?]*)\?=").unwrap();
+    // Whitespace between adjacent encoded words is not part of the text
+    let joined = Regex::new(r"\?=\s+=\?").unwrap().replace_all(value, "?==?");
+    re.replace_all(&joined, |cap: &regex_lite::Captures| {
+        let bytes = if cap[2].eq_ignore_ascii_case("b") {
+            base64::engine::general_purpose::STANDARD
+                .decode(&cap[3])
+                .ok()
+        } else {
+            Some(decode_quoted_printable(&cap[3].replace('_', " ")))
+        };
+        bytes
+            .map(|b| decode_charset(&b, Some(&cap[1])))
+            .unwrap_or_else(|| cap[0].to_string())
+    })
+    .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_headers_unfolds_continuations() {
+        let (headers, body) =
+            split_headers("Subject: Printer\r\n  offline\r\nContent-Type: text/plain\r\n\r\nHi");
+        assert_eq!(
+            header_value(&headers, "subject").as_deref(),
+            Some("Printer offline")
+        );
+        assert_eq!(content_type(&headers), "text/plain");
+        assert_eq!(body, "Hi");
+    }
+
+    #[test]
+    fn test_parts_skips_preamble_and_epilogue() {
+        let body = "preamble\r\n--b\r\nContent-Type: text/plain\r\n\r\none\r\n--b\r\nContent-Type: text/html\r\n\r\ntwo\r\n--b--\r\nepilogue";
+        let parts = parts(body, "b");
+        assert_eq!(parts.len(), 2);
+        assert_eq!(content_type(&parts[1].0), "text/html");
+        assert_eq!(parts[0].1.trim(), "one");
+    }
+
+    #[test]
+    fn test_decode_text_quoted_printable_latin1() {
+        let headers = vec![
+            (
+                "content-type".to_string(),
+                "text/plain; charset=\"iso-8859-1\"".to_string(),
+            ),
+            (
+                "content-transfer-encoding".to_string(),
+                "quoted-printable".to_string(),
+            ),
+        ];
+        assert_eq!(
+            decode_text(&headers, "caf=E9 =\r\nopen").unwrap(),
+            "café open"
+        );
+    }
+
+    #[test]
+    fn test_decode_encoded_words() {
+        assert_eq!(
+            decode_encoded_words("=?UTF-8?B?Q2Fmw6k=?= =?utf-8?Q?_login?= issue"),
+            "Café login issue"
+        );
+        assert_eq!(decode_encoded_words("Plain subject"), "Plain subject");
+    }
+}
@@ -3,13 +3,19 @@
 
 pub mod dns;
 pub mod docx;
+pub mod email;
 pub mod embeddings;
+pub mod html;
 pub mod indexer;
 pub mod ingest;
+pub mod mime;
 pub mod network;
 pub mod ocr;
+pub mod odt;
+pub mod office;
 pub mod pdf;
 pub mod search;
+pub mod tabular;
 pub mod vectors;
 pub mod watcher;
 pub mod xlsx;
@@ -491,8 +491,21 @@ pub fn is_login_page(url: &Url, content: Option<&str>) -> bool {
 
 /// Extract same-origin links from HTML content
 pub fn extract_same_origin_links(base_url: &Url, html: &str) -> Vec<String> {
-    let mut links = Vec::new();
     let base_host = base_url.host_str().unwrap_or("");
+    extract_links(base_url, html)
+        .into_iter()
+        .filter(|link| {
+            Url::parse(link)
+                .map(|u| u.host_str() == Some(base_host))
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+/// Extract http(s) links from HTML content, resolved against `base_url`
+/// with fragments removed
+pub fn extract_links(base_url: &Url, html: &str) -> Vec<String> {
+    let mut links = Vec::new();
 
     // Simple regex-like extraction (avoid full HTML parser for performance)
     // This is intentionally conservative to avoid XSS vectors
@@ -501,7 +514,7 @@ pub fn extract_same_origin_links(base_url: &Url, html: &str) -> Vec<String> {
         .captures_iter(html)
     {
         if let Some(href) = cap.get(1) {
-            let href_str = href.as_str();
+            let href_str = href.as_str().trim();
 
             // Skip javascript:, mailto:, tel:, etc.
             if href_str.starts_with("javascript:")
@@ -513,9 +526,9 @@ pub fn extract_same_origin_links(base_url: &Url, html: &str) -> Vec<String> {
             }
 
             // Resolve relative URLs
-            if let Ok(resolved) = base_url.join(href_str) {
-                // Only include same-origin links
-                if resolved.host_str() == Some(base_host) {
+            if let Ok(mut resolved) = base_url.join(&html_attr_decode(href_str)) {
+                if matches!(resolved.scheme(), "http" | "https") {
+                    resolved.set_fragment(None);
                     links.push(resolved.to_string());
                 }
             }
@@ -529,6 +542,11 @@ pub fn extract_same_origin_links(base_url: &Url, html: &str) -> Vec<String> {
     links
 }
 
+/// Decode the `&amp;` escapes common in href query strings
+fn html_attr_decode(value: &str) -> String {
+    value.replace("&amp;", "&").replace("&#38;", "&")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -721,4 +739,27 @@ mod tests {
         assert!(!links.iter().any(|l| l.contains("evil.com")));
         assert!(!links.iter().any(|l| l.contains("javascript:")));
     }
+
+    #[test]
+    fn test_extract_links() {
+        let base = Url::parse("https://example.com/docs/").unwrap();
+        let html = r#"
+            <a href="vpn.html#setup">VPN</a>
+            <a href="vpn.html">VPN again</a>
+            <a href="https://other.example.org/">Other</a>
+            <a href="search?q=a&amp;page=2">Search</a>
+            <a href="ftp://example.com/file">FTP</a>
+            <a href="mailto:help@example.com">Mail</a>
+        "#;
+
+        let links = extract_links(&base, html);
+        assert_eq!(
+            links,
+            vec![
+                "https://example.com/docs/search?q=a&page=2".to_string(),
+                "https://example.com/docs/vpn.html".to_string(),
+                "https://other.example.org/".to_string(),
+            ]
+        );
+    }
 }
@@ -0,0 +1,195 @@
+//! ODT (OpenDocument Text) extraction for KB indexing
+
+use std::path::Path;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use thiserror::Error;
+
+use super::indexer::{Section, SectionBuilder};
+use super::office::EmbeddedImage;
+
+#[derive(Debug, Error)]
+pub enum OdtError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("ODT parse error: {0}")]
+    Parse(String),
+}
+
+/// Extract an ODT file into sections, starting a new section at each heading
+pub fn extract_sections(path: &Path) -> Result<Vec<Section>, OdtError> {
+    let content = super::office::read_part(path, "content.xml")?;
+    parse_content(&content)
+}
+
+/// Extract images embedded in an ODT file (`Pictures/`)
+pub fn extract_images(path: &Path) -> Result<Vec<EmbeddedImage>, OdtError> {
+    Ok(super::office::extract_images(path, "Pictures/")?)
+}
+
+/// Heading level from a `text:h` element's `text:outline-level`, defaulting to 1
+fn outline_level(element: &BytesStart) -> u8 {
+    element
+        .try_get_attribute("text:outline-level")
+        .ok()
+        .flatten()
+        .and_then(|attr| attr.unescape_value().ok()?.parse::<u8>().ok())
+        .filter(|level| (1..=9).contains(level))
+        .unwrap_or(1)
+}
+
+/// Walk `content.xml`: headings start sections, paragraphs end lines, and
+/// table rows are joined with ` | ` like DOCX tables
+fn parse_content(xml: &str) -> Result<Vec<Section>, OdtError> {
+    let mut reader = Reader::from_str(xml);
+    let mut builder = SectionBuilder::default();
+
+    let mut in_body = false;
+    // Open text:p/text:h elements; text outside them is indentation
+    let mut text_depth = 0usize;
+    let mut heading: Option<(u8, String)> = None;
+    let mut cells: Option<Vec<String>> = None;
+    let mut cell: Option<String> = None;
+
+    // Append to the innermost open container: heading, table cell, or section
+    fn emit(
+        text: &str,
+        heading: &mut Option<(u8, String)>,
+        cell: &mut Option<String>,
+        builder: &mut SectionBuilder,
+    ) {
+        if let Some((_, heading_text)) = heading {
+            heading_text.push_str(text);
+        } else if let Some(cell_text) = cell {
+            cell_text.push_str(text);
+        } else {
+            builder.push(text);
+        }
+    }
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| OdtError::Parse(e.to_string()))?;
+        match event {
+            Event::Start(e) => match e.name().as_ref() {
+                b"office:text" => in_body = true,
+                b"text:h" if in_body => {
+                    text_depth += 1;
+                    heading = Some((outline_level(&e), String::new()));
+                }
+                b"text:p" if in_body => text_depth += 1,
+                b"table:table-row" if in_body => cells = Some(Vec::new()),
+                b"table:table-cell" if in_body => cell = Some(String::new()),
+                _ => {}
+            },
+            Event::Empty(e) if in_body => match e.name().as_ref() {
+                b"text:s" => emit(" ", &mut heading, &mut cell, &mut builder),
+                b"text:tab" => emit("\t", &mut heading, &mut cell, &mut builder),
+                b"text:line-break" => emit("\n", &mut heading, &mut cell, &mut builder),
+                _ => {}
+            },
+            Event::Text(e) if in_body && text_depth > 0 => {
+                let text = e.unescape().map_err(|e| OdtError::Parse(e.to_string()))?;
+                emit(&text, &mut heading, &mut cell, &mut builder);
+            }
+            Event::End(e) => match e.name().as_ref() {
+                b"office:text" => in_body = false,
+                b"text:h" => {
+                    text_depth = text_depth.saturating_sub(1);
+                    if let Some((level, text)) = heading.take() {
+                        if text.trim().is_empty() {
+                            continue;
+                        }
+                        if cell.is_some() {
+                            emit(&text, &mut heading, &mut cell, &mut builder);
+                        } else {
+                            builder.heading(&text, level);
+                        }
+                    }
+                }
+                b"text:p" if in_body => {
+                    text_depth = text_depth.saturating_sub(1);
+                    let separator = if cell.is_some() { " " } else { "\n" };
+                    emit(separator, &mut heading, &mut cell, &mut builder);
+                }
+                b"table:table-cell" => {
+                    if let (Some(text), Some(row)) = (cell.take(), cells.as_mut()) {
+                        row.push(text.trim().to_string());
+                    }
+                }
+                b"table:table-row" => {
+                    if let Some(row) = cells.take() {
+                        if row.iter().any(|c| !c.is_empty()) {
+                            builder.push(&row.join(" | "));
+                            builder.push("\n");
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0">
+  <office:automatic-styles><text:p>not body</text:p></office:automatic-styles>
+  <office:body>
+    <office:text>
+      <text:p>Intro &amp; scope</text:p>
+      <text:h text:outline-level="1">Printer Setup</text:h>
+      <text:p>Open<text:s/>the <text:span>print</text:span> queue.</text:p>
+      <text:h text:outline-level="2">Drivers</text:h>
+      <table:table>
+        <table:table-row>
+          <table:table-cell><text:p>Model</text:p></table:table-cell>
+          <table:table-cell><text:p>Driver</text:p></table:table-cell>
+        </table:table-row>
+        <table:table-row>
+          <table:table-cell><text:p>HP 400</text:p></table:table-cell>
+          <table:table-cell><text:p>PCL6</text:p></table:table-cell>
+        </table:table-row>
+      </table:table>
+    </office:text>
+  </office:body>
+</office:document-content>"#;
+
+    #[test]
+    fn test_parse_content_splits_at_headings() {
+        let sections = parse_content(CONTENT).unwrap();
+        assert_eq!(sections.len(), 3);
+
+        assert_eq!(sections[0].heading, None);
+        assert_eq!(sections[0].content.trim(), "Intro & scope");
+
+        assert_eq!(sections[1].heading.as_deref(), Some("Printer Setup"));
+        assert_eq!(sections[1].level, 1);
+        assert_eq!(sections[1].content.trim(), "Open the print queue.");
+
+        assert_eq!(sections[2].heading.as_deref(), Some("Drivers"));
+        assert_eq!(sections[2].level, 2);
+        assert!(sections[2].content.contains("Model | Driver"));
+        assert!(sections[2].content.contains("HP 400 | PCL6"));
+        assert!(!sections[2].content.contains("not body"));
+    }
+
+    #[test]
+    fn test_extract_nonexistent() {
+        assert!(extract_sections(Path::new("/nonexistent.odt")).is_err());
+    }
+
+    #[test]
+    fn test_parse_content_rejects_malformed_xml() {
+        assert!(parse_content("<office:text><text:p>open</office:text>").is_err());
+    }
+}
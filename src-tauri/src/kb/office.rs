@@ -0,0 +1,129 @@
+//! Shared helpers for zip-based Office documents (DOCX, ODT)
+
+use std::io::Read;
+use std::path::Path;
+
+/// Maximum uncompressed size of a document part like `content.xml` (50MB)
+const MAX_PART_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Embedded images larger than this are skipped (20MB, same as standalone images)
+const MAX_EMBEDDED_IMAGE_BYTES: u64 = 20 * 1024 * 1024;
+
+/// At most this many embedded images are extracted per document
+const MAX_EMBEDDED_IMAGES: usize = 50;
+
+/// Image formats the OCR engine accepts
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "tif", "tiff", "bmp"];
+
+/// An image embedded in an Office document
+#[derive(Debug, Clone)]
+pub struct EmbeddedImage {
+    /// Archive path, e.g. `word/media/image1.png`
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+impl EmbeddedImage {
+    /// File extension, lowercased
+    pub fn extension(&self) -> String {
+        Path::new(&self.name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("png")
+            .to_lowercase()
+    }
+}
+
+fn open_archive(path: &Path) -> std::io::Result<zip::ZipArchive<std::fs::File>> {
+    let file = std::fs::File::open(path)?;
+    zip::ZipArchive::new(file).map_err(std::io::Error::other)
+}
+
+/// Read one XML part of the package as UTF-8
+pub fn read_part(path: &Path, name: &str) -> std::io::Result<String> {
+    let mut archive = open_archive(path)?;
+    let entry = archive.by_name(name).map_err(std::io::Error::other)?;
+    let mut bytes = Vec::new();
+    entry.take(MAX_PART_BYTES + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > MAX_PART_BYTES {
+        return Err(std::io::Error::other(format!(
+            "{} exceeds {} bytes",
+            name, MAX_PART_BYTES
+        )));
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Extract images stored under `media_prefix` (e.g. `word/media/` or `Pictures/`),
+/// skipping oversized entries and non-image formats
+pub fn extract_images(path: &Path, media_prefix: &str) -> std::io::Result<Vec<EmbeddedImage>> {
+    let mut archive = open_archive(path)?;
+    let mut images = Vec::new();
+
+    for i in 0..archive.len() {
+        if images.len() >= MAX_EMBEDDED_IMAGES {
+            tracing::warn!(
+                "Stopped after {} embedded images in {:?}",
+                MAX_EMBEDDED_IMAGES,
+                path
+            );
+            break;
+        }
+        let entry = archive.by_index(i).map_err(std::io::Error::other)?;
+        let name = entry.name().to_string();
+        let is_image = name.starts_with(media_prefix)
+            && Path::new(&name)
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| IMAGE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+                .unwrap_or(false);
+        if !is_image || entry.size() > MAX_EMBEDDED_IMAGE_BYTES {
+            continue;
+        }
+
+        let mut bytes = Vec::new();
+        entry
+            .take(MAX_EMBEDDED_IMAGE_BYTES + 1)
+            .read_to_end(&mut bytes)?;
+        if bytes.len() as u64 > MAX_EMBEDDED_IMAGE_BYTES {
+            continue;
+        }
+        images.push(EmbeddedImage { name, bytes });
+    }
+
+    Ok(images)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_images_filters_by_prefix_and_type() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("doc.docx");
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        for (name, bytes) in [
+            ("word/document.xml", b"<w:document/>".as_slice()),
+            ("word/media/image1.png", b"png".as_slice()),
+            ("word/media/chart.emf", b"emf".as_slice()),
+            ("docProps/thumbnail.jpeg", b"jpg".as_slice()),
+        ] {
+            writer.start_file(name, options).unwrap();
+            writer.write_all(bytes).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let images = extract_images(&path, "word/media/").unwrap();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].name, "word/media/image1.png");
+        assert_eq!(images[0].extension(), "png");
+        assert_eq!(
+            read_part(&path, "word/document.xml").unwrap(),
+            "<w:document/>"
+        );
+    }
+}
@@ -5,6 +5,14 @@ use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 use thiserror::Error;
 
+/// Pages whose text layer has fewer characters than this are treated as scanned
+const MIN_PAGE_TEXT_CHARS: usize = 100;
+
+/// Whether a page's text layer is too sparse to index without OCR
+pub fn page_needs_ocr(text: &str) -> bool {
+    text.trim().chars().count() < MIN_PAGE_TEXT_CHARS
+}
+
 #[derive(Debug, Error)]
 pub enum PdfError {
     #[error("PDFium library not found")]
@@ -187,65 +195,77 @@ impl PdfExtractor {
         Ok(())
     }
 
-    /// Extract text with OCR fallback for scanned PDFs
-    /// Uses OCR if average text per page is below threshold
+    /// Extract text per page, falling back to OCR for scanned pages
+    ///
+    /// Pages whose text layer is shorter than `MIN_PAGE_TEXT_CHARS` are rendered
+    /// and OCR'd; the longer of the two texts is kept. `on_page(page, total)` is
+    /// called after each page with its 1-based number.
     pub fn extract_text_with_ocr_fallback(
         &self,
         pdf_path: &Path,
         ocr_fn: impl Fn(&Path) -> Result<String, String>,
+        mut on_page: impl FnMut(usize, usize),
     ) -> Result<Vec<String>, PdfError> {
         let regular_pages = self.extract_text(pdf_path)?;
+        let total = regular_pages.len();
+        let scanned = regular_pages.iter().filter(|p| page_needs_ocr(p)).count();
+        if scanned > 0 {
+            tracing::info!(
+                "PDF has {} of {} pages without a usable text layer, using OCR",
+                scanned,
+                total
+            );
+        }
 
-        // Check if we need OCR (less than 100 chars average per page)
-        let total_chars: usize = regular_pages.iter().map(|p| p.len()).sum();
-        let avg_chars = if regular_pages.is_empty() {
-            0
+        let temp_dir = if scanned > 0 {
+            Some(TempDir::new()?)
         } else {
-            total_chars / regular_pages.len()
+            None
         };
-
-        if avg_chars >= 100 {
-            // Regular PDF with good text - return as is
-            return Ok(regular_pages);
+        let mut pages = Vec::with_capacity(total);
+
+        for (page_idx, text) in regular_pages.into_iter().enumerate() {
+            let page_text = match &temp_dir {
+                Some(temp_dir) if page_needs_ocr(&text) => {
+                    let img_path = temp_dir
+                        .path()
+                        .join(format!("pdf_ocr_page_{}.png", page_idx));
+                    let ocr_text = self.ocr_page(pdf_path, page_idx, &img_path, &ocr_fn);
+                    // Clean up temp image
+                    let _ = std::fs::remove_file(&img_path);
+                    match ocr_text {
+                        Some(ocr_text) if ocr_text.trim().len() > text.trim().len() => ocr_text,
+                        _ => text,
+                    }
+                }
+                _ => text,
+            };
+            pages.push(page_text);
+            on_page(page_idx + 1, total);
         }
 
-        // Scanned PDF - need OCR for each page
-        tracing::info!(
-            "PDF appears to be scanned (avg {} chars/page), using OCR",
-            avg_chars
-        );
-
-        let page_count = self.page_count(pdf_path)?;
-        let mut ocr_pages = Vec::with_capacity(page_count);
-        let temp_dir = TempDir::new()?;
-
-        for page_idx in 0..page_count {
-            let img_path = temp_dir
-                .path()
-                .join(format!("pdf_ocr_page_{}.png", page_idx));
-
-            // Render page to image
-            if let Err(e) = self.render_page_to_image(pdf_path, page_idx, &img_path) {
-                tracing::warn!("Failed to render page {}: {}", page_idx, e);
-                // Fall back to whatever text we got
-                ocr_pages.push(regular_pages.get(page_idx).cloned().unwrap_or_default());
-                continue;
-            }
+        Ok(pages)
+    }
 
-            // Run OCR on the rendered image
-            match ocr_fn(&img_path) {
-                Ok(text) => ocr_pages.push(text),
-                Err(e) => {
-                    tracing::warn!("OCR failed for page {}: {}", page_idx, e);
-                    ocr_pages.push(regular_pages.get(page_idx).cloned().unwrap_or_default());
-                }
+    /// Render one page and OCR it, or `None` if either step fails
+    fn ocr_page(
+        &self,
+        pdf_path: &Path,
+        page_idx: usize,
+        img_path: &Path,
+        ocr_fn: &impl Fn(&Path) -> Result<String, String>,
+    ) -> Option<String> {
+        if let Err(e) = self.render_page_to_image(pdf_path, page_idx, img_path) {
+            tracing::warn!("Failed to render page {}: {}", page_idx, e);
+            return None;
+        }
+        match ocr_fn(img_path) {
+            Ok(text) => Some(text),
+            Err(e) => {
+                tracing::warn!("OCR failed for page {}: {}", page_idx, e);
+                None
             }
-
-            // Clean up temp image
-            let _ = std::fs::remove_file(&img_path);
         }
-
-        Ok(ocr_pages)
     }
 
     /// Get page count for a PDF
@@ -327,6 +347,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_page_needs_ocr() {
+        assert!(page_needs_ocr(""));
+        assert!(page_needs_ocr("   \n  Page 3  \n"));
+        assert!(!page_needs_ocr(&"Reset the VPN client. ".repeat(10)));
+    }
+
     #[test]
     fn test_pdf_page_count() {
         let extractor = PdfExtractor::new();
@@ -0,0 +1,335 @@
+//! Row-aware chunking for spreadsheets and delimited files (XLSX/XLS, CSV, TSV)
+//!
+//! Each chunk repeats the table's column headers followed by a group of
+//! rows, so a lookup like "who owns ASSET-1234" lands on a chunk that has
+//! both the row and the column names that explain it.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::indexer::Section;
+
+#[derive(Debug, Error)]
+pub enum TabularError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("CSV parse error: {0}")]
+    Csv(#[from] crate::batch::BatchCsvError),
+}
+
+/// Default number of rows per chunk
+pub const DEFAULT_ROWS_PER_CHUNK: usize = 20;
+
+/// Upper bound for `rows_per_chunk`
+pub const MAX_ROWS_PER_CHUNK: usize = 200;
+
+/// Which parts of spreadsheets to index and how many rows go in a chunk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TabularOptions {
+    pub rows_per_chunk: usize,
+    /// Sheet names to index (case-insensitive). Empty, or no sheet of a
+    /// workbook matching, indexes every sheet.
+    pub sheets: Vec<String>,
+    /// Column headers to index (case-insensitive). Empty, or no column of a
+    /// table matching, indexes every column.
+    pub columns: Vec<String>,
+}
+
+impl Default for TabularOptions {
+    fn default() -> Self {
+        Self {
+            rows_per_chunk: DEFAULT_ROWS_PER_CHUNK,
+            sheets: Vec::new(),
+            columns: Vec::new(),
+        }
+    }
+}
+
+/// A sheet or delimited file: a header row plus data rows
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub name: String,
+    pub headers: Vec<String>,
+    /// Data rows with their 1-based row number in the source
+    pub rows: Vec<(usize, Vec<String>)>,
+}
+
+impl Table {
+    /// Build a table from records whose first non-empty record is the header
+    pub fn from_records(name: &str, records: Vec<(usize, Vec<String>)>) -> Option<Self> {
+        let mut records = records
+            .into_iter()
+            .filter(|(_, cells)| cells.iter().any(|c| !c.trim().is_empty()));
+        let (_, header_cells) = records.next()?;
+        let headers = header_cells
+            .iter()
+            .enumerate()
+            .map(|(i, h)| {
+                let h = h.trim();
+                if h.is_empty() {
+                    format!("Column {}", i + 1)
+                } else {
+                    h.to_string()
+                }
+            })
+            .collect();
+        Some(Self {
+            name: name.to_string(),
+            headers,
+            rows: records.collect(),
+        })
+    }
+}
+
+/// Read a CSV (or TSV when `delimiter` is a tab) file as a single table
+pub fn read_delimited(path: &Path, delimiter: char) -> Result<Option<Table>, TabularError> {
+    let raw = std::fs::read(path)?;
+    let content = String::from_utf8_lossy(&raw);
+    let records = crate::batch::parse_delimited(&content, delimiter)?;
+    let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("Table");
+    Ok(Table::from_records(name, records))
+}
+
+/// Sections of headers plus up to `rows_per_chunk` rows, each kept under
+/// `max_words` so the chunker never splits a group away from its headers.
+/// Sections are headed `<table> > Rows <first>-<last>`.
+pub fn table_sections(
+    tables: &[Table],
+    options: &TabularOptions,
+    max_words: usize,
+) -> Vec<Section> {
+    let wanted_sheets: Vec<&Table> = tables
+        .iter()
+        .filter(|t| matches_any(&t.name, &options.sheets))
+        .collect();
+    let tables: Vec<&Table> = if wanted_sheets.is_empty() {
+        tables.iter().collect()
+    } else {
+        wanted_sheets
+    };
+
+    let rows_per_chunk = options.rows_per_chunk.clamp(1, MAX_ROWS_PER_CHUNK);
+    let mut sections = Vec::new();
+
+    for table in tables {
+        let mut columns: Vec<usize> = (0..table.headers.len())
+            .filter(|&i| matches_any(&table.headers[i], &options.columns))
+            .collect();
+        if columns.is_empty() {
+            columns = (0..table.headers.len()).collect();
+        }
+
+        let select = |cells: &[String]| -> Vec<String> {
+            columns
+                .iter()
+                .map(|&i| {
+                    cells
+                        .get(i)
+                        .map(|c| c.trim())
+                        .unwrap_or_default()
+                        .to_string()
+                })
+                .collect()
+        };
+        let header_line = select(&table.headers).join(" | ");
+        let header_words = header_line.split_whitespace().count();
+
+        sections.push(Section {
+            heading: Some(table.name.clone()),
+            level: 1,
+            content: String::new(),
+            page: None,
+        });
+
+        let mut group: Vec<(usize, String)> = Vec::new();
+        let mut group_words = header_words;
+        for (row_number, cells) in &table.rows {
+            let cells = select(cells);
+            if cells.iter().all(|c| c.is_empty()) {
+                continue;
+            }
+            let line = cells.join(" | ");
+            let words = line.split_whitespace().count();
+            if !group.is_empty()
+                && (group.len() >= rows_per_chunk || group_words + words > max_words)
+            {
+                sections.push(row_group_section(&header_line, &group));
+                group.clear();
+                group_words = header_words;
+            }
+            group_words += words;
+            group.push((*row_number, line));
+        }
+        if !group.is_empty() {
+            sections.push(row_group_section(&header_line, &group));
+        }
+    }
+
+    sections
+}
+
+fn row_group_section(header_line: &str, group: &[(usize, String)]) -> Section {
+    let first = group.first().map(|(n, _)| *n).unwrap_or_default();
+    let last = group.last().map(|(n, _)| *n).unwrap_or_default();
+    let mut content = format!("{}\n", header_line);
+    for (_, line) in group {
+        content.push_str(line);
+        content.push('\n');
+    }
+    Section {
+        heading: Some(if first == last {
+            format!("Row {}", first)
+        } else {
+            format!("Rows {}-{}", first, last)
+        }),
+        level: 2,
+        content,
+        page: None,
+    }
+}
+
+fn matches_any(name: &str, filters: &[String]) -> bool {
+    filters
+        .iter()
+        .any(|f| f.trim().eq_ignore_ascii_case(name.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inventory() -> Table {
+        let records = vec![
+            (
+                1,
+                vec![
+                    "Asset Tag".into(),
+                    "Owner".into(),
+                    "Model".into(),
+                    "".into(),
+                ],
+            ),
+            (
+                2,
+                vec![
+                    "ASSET-1233".into(),
+                    "Priya Shah".into(),
+                    "ThinkPad X1".into(),
+                    "n/a".into(),
+                ],
+            ),
+            (3, vec!["".into(), "".into(), "".into(), "".into()]),
+            (
+                4,
+                vec![
+                    "ASSET-1234".into(),
+                    "Jordan Lee".into(),
+                    "MacBook Pro".into(),
+                    "".into(),
+                ],
+            ),
+            (
+                5,
+                vec![
+                    "ASSET-1235".into(),
+                    "Sam Ortiz".into(),
+                    "Dell XPS".into(),
+                    "".into(),
+                ],
+            ),
+        ];
+        Table::from_records("Laptops", records).unwrap()
+    }
+
+    #[test]
+    fn test_from_records_names_blank_headers() {
+        let table = inventory();
+        assert_eq!(table.headers[3], "Column 4");
+        assert_eq!(table.rows.len(), 3);
+        assert_eq!(table.rows[1].0, 4);
+    }
+
+    #[test]
+    fn test_every_chunk_repeats_headers() {
+        let options = TabularOptions {
+            rows_per_chunk: 2,
+            ..Default::default()
+        };
+        let sections = table_sections(&[inventory()], &options, 500);
+
+        assert_eq!(sections[0].heading.as_deref(), Some("Laptops"));
+        assert_eq!(sections[1].heading.as_deref(), Some("Rows 2-4"));
+        assert_eq!(sections[2].heading.as_deref(), Some("Row 5"));
+        for section in &sections[1..] {
+            assert!(section
+                .content
+                .starts_with("Asset Tag | Owner | Model | Column 4\n"));
+        }
+        assert!(sections[1]
+            .content
+            .contains("ASSET-1234 | Jordan Lee | MacBook Pro | \n"));
+    }
+
+    #[test]
+    fn test_column_filter() {
+        let options = TabularOptions {
+            columns: vec!["asset tag".into(), "OWNER".into(), "Serial".into()],
+            ..Default::default()
+        };
+        let sections = table_sections(&[inventory()], &options, 500);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(
+            sections[1].content,
+            "Asset Tag | Owner\nASSET-1233 | Priya Shah\nASSET-1234 | Jordan Lee\nASSET-1235 | Sam Ortiz\n"
+        );
+    }
+
+    #[test]
+    fn test_sheet_filter_falls_back_to_all_sheets() {
+        let mut licenses = inventory();
+        licenses.name = "Licenses".into();
+        let tables = [inventory(), licenses];
+
+        let only_licenses = TabularOptions {
+            sheets: vec!["licenses".into()],
+            ..Default::default()
+        };
+        let sections = table_sections(&tables, &only_licenses, 500);
+        assert_eq!(sections[0].heading.as_deref(), Some("Licenses"));
+        assert_eq!(sections.len(), 2);
+
+        let no_match = TabularOptions {
+            sheets: vec!["Monitors".into()],
+            ..Default::default()
+        };
+        assert_eq!(table_sections(&tables, &no_match, 500).len(), 4);
+    }
+
+    #[test]
+    fn test_groups_respect_word_budget() {
+        let options = TabularOptions::default();
+        // Header is 9 words (counting separators); rows are 9, 8 and 8
+        let sections = table_sections(&[inventory()], &options, 30);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[1].heading.as_deref(), Some("Rows 2-4"));
+        assert_eq!(sections[2].heading.as_deref(), Some("Row 5"));
+    }
+
+    #[test]
+    fn test_read_delimited_tsv() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("licenses.tsv");
+        std::fs::write(&path, "Product\tSeats\nZoom\t250\n").unwrap();
+
+        let table = read_delimited(&path, '\t').unwrap().unwrap();
+        assert_eq!(table.name, "licenses");
+        assert_eq!(table.headers, vec!["Product", "Seats"]);
+        assert_eq!(
+            table.rows,
+            vec![(2, vec!["Zoom".to_string(), "250".to_string()])]
+        );
+    }
+}
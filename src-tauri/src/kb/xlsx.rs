@@ -1,9 +1,11 @@
-//! XLSX/XLS text extraction for KB indexing
+//! XLSX/XLS table extraction for KB indexing
 
 use calamine::{open_workbook_auto, Data, Reader};
 use std::path::Path;
 use thiserror::Error;
 
+use super::tabular::Table;
+
 #[derive(Debug, Error)]
 pub enum XlsxError {
     #[error("IO error: {0}")]
@@ -14,35 +16,31 @@ pub enum XlsxError {
     Calamine(#[from] calamine::Error),
 }
 
-/// Extract text content from an Excel file (XLSX or XLS)
-pub fn extract_text(path: &Path) -> Result<String, XlsxError> {
+/// Extract every sheet of an Excel file (XLSX or XLS) as a table whose
+/// first non-empty row is the header
+pub fn extract_tables(path: &Path) -> Result<Vec<Table>, XlsxError> {
     let mut workbook = open_workbook_auto(path)?;
 
-    let mut text = String::new();
+    let mut tables = Vec::new();
     let sheet_names: Vec<String> = workbook.sheet_names().to_vec();
 
     for sheet_name in sheet_names {
-        // Add sheet name as a header
-        text.push_str(&format!("## {}\n\n", sheet_name));
-
-        if let Ok(range) = workbook.worksheet_range(&sheet_name) {
-            for row in range.rows() {
-                let row_text: Vec<String> = row.iter().map(cell_to_string).collect();
-
-                // Skip completely empty rows
-                if row_text.iter().all(|s| s.is_empty()) {
-                    continue;
-                }
-
-                text.push_str(&row_text.join(" | "));
-                text.push('\n');
-            }
+        let Ok(range) = workbook.worksheet_range(&sheet_name) else {
+            continue;
+        };
+        // Rows are numbered as the user sees them in Excel
+        let first_row = range.start().map(|(row, _)| row as usize).unwrap_or(0);
+        let records = range
+            .rows()
+            .enumerate()
+            .map(|(i, row)| (first_row + i + 1, row.iter().map(cell_to_string).collect()))
+            .collect();
+        if let Some(table) = Table::from_records(&sheet_name, records) {
+            tables.push(table);
         }
-
-        text.push('\n');
     }
 
-    Ok(text)
+    Ok(tables)
 }
 
 fn cell_to_string(cell: &Data) -> String {
@@ -78,7 +76,7 @@ mod tests {
 
     #[test]
     fn test_extract_nonexistent() {
-        let result = extract_text(Path::new("/nonexistent.xlsx"));
+        let result = extract_tables(Path::new("/nonexistent.xlsx"));
         assert!(result.is_err());
     }
 }
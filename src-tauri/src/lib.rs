@@ -1,23 +1,35 @@
 //! AssistSupport - Self-contained local KB + LLM app for IT support
 
+pub mod access;
+pub mod anonymize;
 pub mod audit;
 pub mod backup;
+pub mod batch;
+pub mod batch_export;
 pub mod commands;
+pub mod confluence;
+pub mod crash;
 pub mod db;
 pub mod diagnostics;
 pub mod downloads;
+pub mod email;
 pub mod error;
+pub mod export_templates;
 pub mod exports;
 pub mod feedback;
+pub mod follow_ups;
 pub mod jira;
 pub mod jobs;
 pub mod kb;
 pub mod llm;
+pub mod metrics;
 pub mod migration;
 pub mod model_integrity;
+pub mod notifications;
 pub mod prompts;
 pub mod security;
 pub mod sources;
+pub mod support_bundle;
 pub mod validation;
 
 use crate::db::Database;
@@ -57,11 +69,22 @@ impl Default for AppState {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    crash::install_panic_hook();
+
+    let result = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .manage(AppState::default())
-        .invoke_handler(tauri::generate_handler![
+        .setup(|app| {
+            crash::set_app_handle(app.handle().clone());
+            tauri::async_runtime::spawn(commands::schedules::run_scheduler(app.handle().clone()));
+            tauri::async_runtime::spawn(commands::diagnostics::run_metrics_sampler(
+                app.handle().clone(),
+            ));
+            Ok(())
+        })
+        .invoke_handler(crash::record_commands(tauri::generate_handler![
             commands::greet,
             commands::initialize_app,
             commands::check_fts5_enabled,
@@ -77,6 +100,7 @@ pub fn run() {
             commands::load_model,
             commands::load_custom_model,
             commands::validate_gguf_file,
+            commands::list_model_integrity,
             commands::unload_model,
             commands::get_model_info,
             commands::is_model_loaded,
@@ -100,9 +124,16 @@ pub fn run() {
             commands::clear_hf_token,
             commands::download_model,
             commands::cancel_download,
+            commands::downloads::configure_download_network,
+            commands::downloads::get_download_network_config,
+            commands::downloads::clear_download_network_config,
             // KB Indexer commands
             commands::set_kb_folder,
             commands::get_kb_folder,
+            commands::get_kb_office_image_ocr,
+            commands::set_kb_office_image_ocr,
+            commands::get_kb_tabular_options,
+            commands::set_kb_tabular_options,
             commands::index_kb,
             commands::get_kb_stats,
             commands::list_kb_documents,
@@ -137,12 +168,23 @@ pub fn run() {
             commands::get_jira_config,
             commands::configure_jira,
             commands::clear_jira_config,
+            commands::get_jira_field_mappings,
+            commands::save_jira_field_mapping,
+            commands::delete_jira_field_mapping,
             commands::get_jira_ticket,
             commands::add_jira_comment,
             commands::push_draft_to_jira,
             // Export commands (Phase 18)
             commands::export_draft_formatted,
+            commands::list_html_export_themes,
             commands::format_draft_for_clipboard,
+            commands::export_templates::list_export_templates,
+            commands::export_templates::get_export_template,
+            commands::export_templates::save_export_template,
+            commands::export_templates::delete_export_template,
+            commands::export_templates::export_draft_with_template,
+            commands::export_templates::preview_export_template,
+            commands::batch_export::export_drafts_batch,
             // Draft & Template commands
             commands::list_drafts,
             commands::search_drafts,
@@ -156,6 +198,8 @@ pub fn run() {
             commands::create_draft_version,
             commands::list_draft_versions,
             commands::finalize_draft,
+            commands::get_memory_kernel_consent,
+            commands::set_memory_kernel_consent,
             commands::archive_draft,
             commands::update_draft_handoff,
             // Playbook commands (Phase 17)
@@ -184,6 +228,8 @@ pub fn run() {
             commands::backup::export_backup,
             commands::backup::preview_backup_import,
             commands::backup::import_backup,
+            commands::backup::get_backup_schedule,
+            commands::backup::set_backup_schedule,
             // Ingestion commands
             commands::ingest_kb_from_disk,
             commands::ingest_url,
@@ -194,14 +240,27 @@ pub fn run() {
             commands::set_github_token,
             commands::clear_github_token,
             commands::has_github_token,
+            commands::list_secrets,
+            commands::create_secret,
+            commands::rotate_secret,
+            commands::delete_secret,
             commands::get_audit_entries,
             commands::export_audit_log,
+            commands::verify_audit_log,
+            commands::get_audit_retention,
+            commands::set_audit_retention,
+            commands::list_audit_archives,
+            commands::export_audit_archive,
             // Namespace commands
             commands::list_namespaces,
             commands::list_namespaces_with_counts,
             commands::create_namespace,
             commands::rename_namespace,
             commands::delete_namespace,
+            commands::set_namespace_restricted,
+            commands::unlock_restricted_namespaces,
+            commands::lock_restricted_namespaces,
+            commands::get_restricted_access_status,
             // Ingest source management commands
             commands::list_ingest_sources,
             commands::delete_ingest_source,
@@ -220,6 +279,14 @@ pub fn run() {
             commands::get_job_logs,
             commands::get_job_counts,
             commands::cleanup_old_jobs,
+            // Job schedule commands
+            commands::schedules::list_job_schedules,
+            commands::schedules::create_job_schedule,
+            commands::schedules::update_job_schedule,
+            commands::schedules::delete_job_schedule,
+            commands::schedules::preview_cron_schedule,
+            commands::schedules::run_job_schedule_now,
+            commands::schedules::create_job_chain,
             // Document versioning commands (Phase 14)
             commands::list_document_versions,
             commands::rollback_document,
@@ -244,6 +311,14 @@ pub fn run() {
             commands::diagnostics::get_llm_resource_limits,
             commands::diagnostics::set_llm_resource_limits,
             commands::diagnostics::get_vector_maintenance_info_cmd,
+            commands::diagnostics::run_performance_profile,
+            commands::diagnostics::list_performance_profiles,
+            commands::diagnostics::get_storage_breakdown,
+            commands::diagnostics::export_diagnostics_bundle,
+            commands::diagnostics::list_crash_reports,
+            commands::diagnostics::review_crash_report,
+            commands::diagnostics::delete_crash_report,
+            commands::diagnostics::get_metrics_timeseries,
             // Phase 4: Response Rating commands
             commands::rate_response,
             commands::get_draft_rating,
@@ -279,6 +354,8 @@ pub fn run() {
             commands::restore_draft_version,
             // Phase 9: Batch Processing commands
             commands::batch_generate,
+            commands::batch_generate_from_csv,
+            commands::preview_batch_csv,
             commands::get_batch_status,
             commands::export_batch_results,
             // Phase 2 v0.4.0: KB Staleness
@@ -302,11 +379,16 @@ pub fn run() {
             // v0.4.1: Startup & Model State
             commands::get_model_state,
             commands::get_startup_metrics,
+            commands::get_startup_timings,
             // v0.4.1: Session Tokens
             commands::create_session_token,
             commands::validate_session_token,
             commands::clear_session_token,
             commands::lock_app,
+            commands::rotate_passphrase,
+            commands::get_biometric_status,
+            commands::enable_biometric_unlock,
+            commands::disable_biometric_unlock,
             // v0.6.0: Pilot Feedback
             commands::log_pilot_query,
             commands::submit_pilot_feedback,
@@ -323,9 +405,55 @@ pub fn run() {
             commands::memory_kernel::get_memory_kernel_integration_pin,
             commands::memory_kernel::get_memory_kernel_preflight_status,
             commands::memory_kernel::memory_kernel_query_ask,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+            commands::memory_kernel::memory_kernel_policy_gate,
+            // Notification integrations
+            commands::notifications::configure_slack,
+            commands::notifications::get_slack_config,
+            commands::notifications::clear_slack_config,
+            commands::notifications::configure_teams,
+            commands::notifications::get_teams_config,
+            commands::notifications::clear_teams_config,
+            commands::notifications::get_notification_settings,
+            commands::notifications::update_notification_preferences,
+            commands::notifications::send_test_notification,
+            commands::notifications::list_webhooks,
+            commands::notifications::save_webhook,
+            commands::notifications::delete_webhook,
+            commands::notifications::rotate_webhook_secret,
+            commands::notifications::send_test_webhook,
+            commands::confluence::configure_confluence,
+            commands::confluence::get_confluence_config,
+            commands::confluence::clear_confluence_config,
+            commands::confluence::publish_draft_to_confluence,
+            commands::confluence::ingest_confluence,
+            commands::crawl::crawl_url,
+            commands::feeds::add_feed,
+            commands::feeds::list_feeds,
+            commands::feeds::poll_feed,
+            commands::feeds::remove_feed,
+            commands::email::configure_smtp,
+            commands::email::get_smtp_config,
+            commands::email::clear_smtp_config,
+            commands::email::send_draft_email,
+            commands::follow_ups::create_follow_up,
+            commands::follow_ups::list_follow_ups,
+            commands::follow_ups::list_due_follow_ups,
+            commands::follow_ups::get_draft_follow_ups,
+            commands::follow_ups::reschedule_follow_up,
+            commands::follow_ups::complete_follow_up,
+            commands::follow_ups::delete_follow_up,
+            commands::follow_ups::notify_due_follow_ups,
+            commands::follow_ups::export_follow_ups_ics,
+        ]))
+        .run(tauri::generate_context!());
+
+    // Record the error as a crash report rather than panicking, which would
+    // write a second report through the panic hook
+    if let Err(e) = result {
+        crash::capture_error("Tauri runtime", &e);
+        eprintln!("error while running tauri application: {}", e);
+        std::process::exit(1);
+    }
 }
 
 #[cfg(test)]
@@ -4,6 +4,7 @@
 use parking_lot::RwLock;
 use std::num::NonZeroU32;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::mpsc;
@@ -97,6 +98,31 @@ pub enum GenerationEvent {
     Error(String),
 }
 
+/// Generations currently running, across all engines
+static ACTIVE_GENERATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether any response is being generated right now (used to pause
+/// background downloads)
+pub fn generation_in_progress() -> bool {
+    ACTIVE_GENERATIONS.load(Ordering::Relaxed) > 0
+}
+
+/// Counts a generation as active until dropped
+struct ActiveGeneration;
+
+impl ActiveGeneration {
+    fn start() -> Self {
+        ACTIVE_GENERATIONS.fetch_add(1, Ordering::Relaxed);
+        Self
+    }
+}
+
+impl Drop for ActiveGeneration {
+    fn drop(&mut self) {
+        ACTIVE_GENERATIONS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 /// LLM Engine state
 pub struct LlmState {
     backend: Arc<LlamaBackend>,
@@ -140,6 +166,17 @@ impl LlmEngine {
             .and_then(|s| s.model_info.clone())
     }
 
+    /// Name of the loaded model, or None if there isn't one or the state is
+    /// locked (used from the panic hook, which must not block)
+    pub fn try_model_name(&self) -> Option<String> {
+        let state = self.state.try_read()?;
+        state
+            .as_ref()?
+            .model_info
+            .as_ref()
+            .map(|info| info.name.clone())
+    }
+
     /// Load a model from file
     pub fn load_model(
         &self,
@@ -220,6 +257,7 @@ impl LlmEngine {
         cancel_flag: Arc<std::sync::atomic::AtomicBool>,
     ) -> Result<(), LlmError> {
         let start_time = std::time::Instant::now();
+        let _active = ActiveGeneration::start();
 
         // Clone what we need for the blocking operation
         let prompt = prompt.to_string();
@@ -245,8 +283,6 @@ impl LlmEngine {
         cancel_flag: Arc<std::sync::atomic::AtomicBool>,
         start_time: std::time::Instant,
     ) -> Result<(), LlmError> {
-        use std::sync::atomic::Ordering;
-
         let state_guard = state.read();
         let state = state_guard.as_ref().ok_or(LlmError::NoModel)?;
         let model = state.model.as_ref().ok_or(LlmError::NoModel)?;
@@ -450,7 +486,9 @@ impl LlmEngine {
         }
 
         // Send completion event
-        let duration_ms = start_time.elapsed().as_millis() as u64;
+        let elapsed = start_time.elapsed();
+        crate::metrics::record_generation_latency(elapsed);
+        let duration_ms = elapsed.as_millis() as u64;
         let _ = tx.blocking_send(GenerationEvent::Done {
             tokens_generated,
             duration_ms,
@@ -521,6 +559,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_active_generation_guard() {
+        let before = ACTIVE_GENERATIONS.load(Ordering::Relaxed);
+        {
+            let _active = ActiveGeneration::start();
+            assert!(generation_in_progress());
+        }
+        assert_eq!(ACTIVE_GENERATIONS.load(Ordering::Relaxed), before);
+    }
+
     #[test]
     fn test_generation_params_default() {
         let params = GenerationParams::default();
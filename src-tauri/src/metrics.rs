@@ -0,0 +1,221 @@
+//! Local metrics timeseries
+//!
+//! Generation and search latencies are recorded as they happen; a sampler
+//! periodically averages them together with memory usage and job queue depth
+//! into a `metrics_samples` row. The diagnostics UI charts the samples,
+//! averaged into buckets sized for the requested range.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Seconds between samples
+pub const METRICS_SAMPLE_INTERVAL_SECS: u64 = 60;
+
+/// Samples older than this are deleted
+pub const METRICS_RETENTION_DAYS: i64 = 30;
+
+static LATENCIES: Mutex<LatencyWindow> = Mutex::new(LatencyWindow::new());
+
+/// Latencies recorded since the last sample
+#[derive(Debug, Default)]
+struct LatencyWindow {
+    generation_ms: Vec<f64>,
+    search_ms: Vec<f64>,
+}
+
+impl LatencyWindow {
+    const fn new() -> Self {
+        Self {
+            generation_ms: Vec::new(),
+            search_ms: Vec::new(),
+        }
+    }
+}
+
+/// Record how long a generation took
+pub fn record_generation_latency(elapsed: std::time::Duration) {
+    if let Ok(mut window) = LATENCIES.lock() {
+        window.generation_ms.push(elapsed.as_secs_f64() * 1000.0);
+    }
+}
+
+/// Record how long a KB search took
+pub fn record_search_latency(elapsed: std::time::Duration) {
+    if let Ok(mut window) = LATENCIES.lock() {
+        window.search_ms.push(elapsed.as_secs_f64() * 1000.0);
+    }
+}
+
+/// Average generation and search latency since the last call, resetting the
+/// window. None when nothing was recorded.
+pub fn take_latencies() -> (Option<f64>, Option<f64>) {
+    let Ok(mut window) = LATENCIES.lock() else {
+        return (None, None);
+    };
+    let window = std::mem::take(&mut *window);
+    (mean(&window.generation_ms), mean(&window.search_ms))
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+/// One periodic sample
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsSample {
+    pub sampled_at: DateTime<Utc>,
+    /// Process resident memory (0 where unsupported)
+    pub memory_bytes: u64,
+    /// Average generation latency since the previous sample
+    pub generation_latency_ms: Option<f64>,
+    /// Average search latency since the previous sample
+    pub search_latency_ms: Option<f64>,
+    /// Jobs waiting to run
+    pub job_queue_depth: u32,
+}
+
+/// Time range for a timeseries query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsRange {
+    Hour,
+    Day,
+    Week,
+    Month,
+}
+
+impl MetricsRange {
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Hour => Duration::hours(1),
+            Self::Day => Duration::days(1),
+            Self::Week => Duration::weeks(1),
+            Self::Month => Duration::days(METRICS_RETENTION_DAYS),
+        }
+    }
+
+    /// Bucket size, chosen to give a chart roughly 60-170 points
+    pub fn bucket(&self) -> Duration {
+        match self {
+            Self::Hour => Duration::minutes(1),
+            Self::Day => Duration::minutes(15),
+            Self::Week => Duration::hours(1),
+            Self::Month => Duration::hours(6),
+        }
+    }
+}
+
+/// Samples averaged over one bucket
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricsPoint {
+    /// Start of the bucket
+    pub timestamp: DateTime<Utc>,
+    pub memory_bytes: u64,
+    pub generation_latency_ms: Option<f64>,
+    pub search_latency_ms: Option<f64>,
+    /// Highest queue depth seen in the bucket
+    pub job_queue_depth: u32,
+    pub sample_count: usize,
+}
+
+/// Timeseries returned to the diagnostics UI
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsTimeseries {
+    pub range: MetricsRange,
+    pub bucket_secs: i64,
+    pub points: Vec<MetricsPoint>,
+}
+
+/// Average samples (sorted oldest first) into buckets of `bucket`, skipping
+/// empty buckets
+pub fn downsample(samples: &[MetricsSample], bucket: Duration) -> Vec<MetricsPoint> {
+    let bucket_secs = bucket.num_seconds().max(1);
+    let mut points = Vec::new();
+    let mut group: Vec<&MetricsSample> = Vec::new();
+    let mut group_start = 0;
+
+    for sample in samples {
+        let start = sample.sampled_at.timestamp().div_euclid(bucket_secs) * bucket_secs;
+        if !group.is_empty() && start != group_start {
+            points.push(bucket_point(group_start, &group));
+            group.clear();
+        }
+        group_start = start;
+        group.push(sample);
+    }
+    if !group.is_empty() {
+        points.push(bucket_point(group_start, &group));
+    }
+    points
+}
+
+fn bucket_point(start: i64, group: &[&MetricsSample]) -> MetricsPoint {
+    let generation: Vec<f64> = group
+        .iter()
+        .filter_map(|s| s.generation_latency_ms)
+        .collect();
+    let search: Vec<f64> = group.iter().filter_map(|s| s.search_latency_ms).collect();
+    MetricsPoint {
+        timestamp: DateTime::from_timestamp(start, 0).unwrap_or_default(),
+        memory_bytes: group.iter().map(|s| s.memory_bytes).sum::<u64>() / group.len() as u64,
+        generation_latency_ms: mean(&generation),
+        search_latency_ms: mean(&search),
+        job_queue_depth: group.iter().map(|s| s.job_queue_depth).max().unwrap_or(0),
+        sample_count: group.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(minute: i64, memory_bytes: u64, generation: Option<f64>) -> MetricsSample {
+        MetricsSample {
+            sampled_at: DateTime::from_timestamp(1_700_000_000 / 3600 * 3600 + minute * 60, 0)
+                .unwrap(),
+            memory_bytes,
+            generation_latency_ms: generation,
+            search_latency_ms: None,
+            job_queue_depth: minute as u32,
+        }
+    }
+
+    #[test]
+    fn test_take_latencies() {
+        record_generation_latency(std::time::Duration::from_millis(100));
+        record_generation_latency(std::time::Duration::from_millis(300));
+        record_search_latency(std::time::Duration::from_millis(20));
+        let (generation, search) = take_latencies();
+        assert_eq!(generation, Some(200.0));
+        assert_eq!(search, Some(20.0));
+        assert_eq!(take_latencies(), (None, None));
+    }
+
+    #[test]
+    fn test_downsample() {
+        let samples = vec![
+            sample(0, 100, Some(10.0)),
+            sample(5, 300, None),
+            sample(14, 200, Some(30.0)),
+            sample(40, 400, None),
+        ];
+        let points = downsample(&samples, Duration::minutes(15));
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].sample_count, 3);
+        assert_eq!(points[0].memory_bytes, 200);
+        assert_eq!(points[0].generation_latency_ms, Some(20.0));
+        assert_eq!(points[0].job_queue_depth, 14);
+        assert_eq!(
+            points[1].timestamp,
+            samples[0].sampled_at + Duration::minutes(30)
+        );
+        assert_eq!(points[1].generation_latency_ms, None);
+
+        assert!(downsample(&[], Duration::minutes(1)).is_empty());
+    }
+}
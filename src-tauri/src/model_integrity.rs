@@ -2,14 +2,25 @@
 //!
 //! This module provides SHA256 verification for downloaded models
 //! with an allowlist of known-good model hashes.
+//!
+//! Verified digests are stored in the `model_integrity` table together with
+//! the file's size and modification time, so unchanged files can be loaded
+//! without re-hashing them every time.
 
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
+use std::time::UNIX_EPOCH;
 use thiserror::Error;
 
+/// Expected checksum fetched from the HuggingFace API
+pub const CHECKSUM_SOURCE_HUGGINGFACE: &str = "huggingface";
+/// Expected checksum taken from the built-in allowlist
+pub const CHECKSUM_SOURCE_ALLOWLIST: &str = "allowlist";
+
 #[derive(Debug, Error)]
 pub enum IntegrityError {
     #[error("IO error: {0}")]
@@ -187,6 +198,78 @@ impl VerificationResult {
     }
 }
 
+/// A model file whose digest matched its published checksum
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityRecord {
+    pub filename: String,
+    pub sha256: String,
+    /// Where the expected checksum came from (`huggingface` or `allowlist`)
+    pub checksum_source: String,
+    pub size_bytes: u64,
+    /// File modification time when verified (ms since the Unix epoch)
+    pub modified_ms: i64,
+    pub verified_at: String,
+}
+
+impl IntegrityRecord {
+    /// Whether the file still has the size and modification time it had when verified
+    pub fn matches_file(&self, path: &Path) -> bool {
+        file_fingerprint(path).is_ok_and(|fp| fp == (self.size_bytes, self.modified_ms))
+    }
+}
+
+/// Size and modification time (ms) of a file
+fn file_fingerprint(path: &Path) -> Result<(u64, i64), IntegrityError> {
+    let metadata = std::fs::metadata(path)?;
+    let modified_ms = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+    Ok((metadata.len(), modified_ms))
+}
+
+/// Check a model file against `expected_sha256` before it is registered.
+///
+/// `recorded` is reused without hashing when it is for the same checksum and
+/// the file is unchanged since it was verified. Returns the record to store.
+pub fn verify_against(
+    path: &Path,
+    expected_sha256: &str,
+    checksum_source: &str,
+    recorded: Option<&IntegrityRecord>,
+) -> Result<IntegrityRecord, IntegrityError> {
+    if let Some(record) = recorded {
+        if record.sha256.eq_ignore_ascii_case(expected_sha256) && record.matches_file(path) {
+            return Ok(record.clone());
+        }
+    }
+
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_string();
+    let (size_bytes, modified_ms) = file_fingerprint(path)?;
+    let sha256 = calculate_sha256(path)?;
+    if !sha256.eq_ignore_ascii_case(expected_sha256) {
+        return Err(IntegrityError::HashMismatch {
+            model: filename,
+            expected: expected_sha256.to_lowercase(),
+            actual: sha256,
+        });
+    }
+
+    Ok(IntegrityRecord {
+        filename,
+        sha256,
+        checksum_source: checksum_source.to_string(),
+        size_bytes,
+        modified_ms,
+        verified_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
 /// Calculate SHA256 hash of a file
 pub fn calculate_sha256(path: &Path) -> Result<String, IntegrityError> {
     let mut file = File::open(path)?;
@@ -265,6 +348,32 @@ mod tests {
         assert!(!result.is_verified());
     }
 
+    #[test]
+    fn test_verify_against_reuses_unchanged_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model.gguf");
+        std::fs::write(&path, b"test content").unwrap();
+        let expected = "6AE8A75555209FD6C44157C0AED8016E763FF435A19CF186F76863140143FF72";
+
+        let record = verify_against(&path, expected, CHECKSUM_SOURCE_ALLOWLIST, None).unwrap();
+        assert_eq!(record.filename, "model.gguf");
+        assert_eq!(record.sha256, expected.to_lowercase());
+        assert_eq!(record.size_bytes, 12);
+        assert!(record.matches_file(&path));
+        assert_eq!(
+            verify_against(&path, expected, CHECKSUM_SOURCE_ALLOWLIST, Some(&record)).unwrap(),
+            record
+        );
+
+        // A changed file is hashed again and rejected
+        std::fs::write(&path, b"tampered content").unwrap();
+        assert!(!record.matches_file(&path));
+        assert!(matches!(
+            verify_against(&path, expected, CHECKSUM_SOURCE_ALLOWLIST, Some(&record)),
+            Err(IntegrityError::HashMismatch { .. })
+        ));
+    }
+
     #[test]
     fn test_strict_mode_rejects_unverified() {
         let mut file = NamedTempFile::with_suffix(".gguf").unwrap();
@@ -0,0 +1,395 @@
+//! Outbound notifications for AssistSupport
+//!
+//! Notifications are built from app events (draft ready, batch complete,
+//! source ingestion results, KB health warnings, stale sources, ratings),
+//! rendered through per-integration message templates, and delivered in the
+//! background so commands never block on network calls. Integration settings
+//! live in `integration_configs`; webhook URLs, bot tokens and signing secrets
+//! live in the encrypted token store.
+
+pub mod slack;
+pub mod teams;
+pub mod webhooks;
+
+use crate::db::Database;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum NotificationError {
+    #[error("Request error: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Delivery failed: {0}")]
+    Delivery(String),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Not configured")]
+    NotConfigured,
+}
+
+/// App events that can trigger a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// A draft was finalized and is ready to send
+    DraftReady,
+    /// A batch generation job finished
+    BatchComplete,
+    /// One or more sources failed to ingest
+    SourceIngestionFailed,
+    /// KB health check found new problems (stale or empty knowledge base)
+    KbHealthWarning,
+    /// Sources were marked stale because they have not been refreshed
+    SourcesStale,
+    /// A source ingestion run finished
+    IngestionCompleted,
+    /// A response rating was submitted
+    RatingSubmitted,
+}
+
+impl NotificationEvent {
+    pub const ALL: [NotificationEvent; 7] = [
+        Self::DraftReady,
+        Self::BatchComplete,
+        Self::SourceIngestionFailed,
+        Self::KbHealthWarning,
+        Self::SourcesStale,
+        Self::IngestionCompleted,
+        Self::RatingSubmitted,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DraftReady => "draft_ready",
+            Self::BatchComplete => "batch_complete",
+            Self::SourceIngestionFailed => "source_ingestion_failed",
+            Self::KbHealthWarning => "kb_health_warning",
+            Self::SourcesStale => "sources_stale",
+            Self::IngestionCompleted => "ingestion_completed",
+            Self::RatingSubmitted => "rating_submitted",
+        }
+    }
+
+    /// Whether chat integrations send this event when no toggle is set.
+    /// High-volume events are opt-in.
+    pub fn enabled_by_default(&self) -> bool {
+        !matches!(self, Self::IngestionCompleted | Self::RatingSubmitted)
+    }
+
+    /// Short human-readable title (used as card headings)
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::DraftReady => "Draft ready",
+            Self::BatchComplete => "Batch complete",
+            Self::SourceIngestionFailed => "Source ingestion failed",
+            Self::KbHealthWarning => "Knowledge base health warning",
+            Self::SourcesStale => "Stale sources",
+            Self::IngestionCompleted => "Ingestion completed",
+            Self::RatingSubmitted => "Response rated",
+        }
+    }
+
+    /// Built-in message template used when no custom template is configured
+    pub fn default_template(&self) -> &'static str {
+        match self {
+            Self::DraftReady => "Draft ready: {{title}} (ticket {{ticket_id}})",
+            Self::BatchComplete => {
+                "Batch {{job_id}} complete: {{completed}}/{{total}} responses generated"
+            }
+            Self::SourceIngestionFailed => {
+                "Source ingestion failed for {{failed_count}} source(s) in {{namespace}}: {{errors}}"
+            }
+            Self::KbHealthWarning => "Knowledge base health warning: {{warnings}}",
+            Self::SourcesStale => {
+                "{{stale_count}} source(s) marked stale (not refreshed in {{days}} days)"
+            }
+            Self::IngestionCompleted => {
+                "Ingested {{document_count}} document(s) from {{source}} into {{namespace}} ({{failed_count}} failed)"
+            }
+            Self::RatingSubmitted => "Draft {{draft_id}} rated {{rating}}/5",
+        }
+    }
+}
+
+impl std::fmt::Display for NotificationEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for NotificationEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|e| e.as_str() == s)
+            .ok_or_else(|| format!("Unknown notification event: {}", s))
+    }
+}
+
+/// A notification with the variables available to its message template
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Notification {
+    pub event: NotificationEvent,
+    pub vars: BTreeMap<String, String>,
+}
+
+impl Notification {
+    pub fn new(event: NotificationEvent) -> Self {
+        Self {
+            event,
+            vars: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_var(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.vars.insert(key.to_string(), value.into());
+        self
+    }
+
+    pub fn draft_ready(draft_id: &str, ticket_id: Option<&str>, title: &str) -> Self {
+        Self::new(NotificationEvent::DraftReady)
+            .with_var("draft_id", draft_id)
+            .with_var("ticket_id", ticket_id.unwrap_or("none"))
+            .with_var("title", title)
+    }
+
+    pub fn batch_complete(job_id: &str, completed: usize, total: usize) -> Self {
+        Self::new(NotificationEvent::BatchComplete)
+            .with_var("job_id", job_id)
+            .with_var("completed", completed.to_string())
+            .with_var("total", total.to_string())
+    }
+
+    /// `failures` is a list of (source, error) pairs; only the first few are included
+    pub fn source_ingestion_failed(namespace: &str, failures: &[(String, String)]) -> Self {
+        const MAX_LISTED: usize = 3;
+        let mut errors: Vec<String> = failures
+            .iter()
+            .take(MAX_LISTED)
+            .map(|(source, error)| format!("{} ({})", source, error))
+            .collect();
+        if failures.len() > MAX_LISTED {
+            errors.push(format!("and {} more", failures.len() - MAX_LISTED));
+        }
+
+        Self::new(NotificationEvent::SourceIngestionFailed)
+            .with_var("namespace", namespace)
+            .with_var("failed_count", failures.len().to_string())
+            .with_var("errors", errors.join("; "))
+    }
+
+    pub fn kb_health_warning(warnings: &[String]) -> Self {
+        Self::new(NotificationEvent::KbHealthWarning)
+            .with_var("warnings", warnings.join("; "))
+            .with_var("warning_count", warnings.len().to_string())
+    }
+
+    pub fn sources_stale(stale_count: usize, days: i64) -> Self {
+        Self::new(NotificationEvent::SourcesStale)
+            .with_var("stale_count", stale_count.to_string())
+            .with_var("days", days.to_string())
+    }
+
+    pub fn ingestion_completed(
+        namespace: &str,
+        source: &str,
+        document_count: usize,
+        failed_count: usize,
+    ) -> Self {
+        Self::new(NotificationEvent::IngestionCompleted)
+            .with_var("namespace", namespace)
+            .with_var("source", source)
+            .with_var("document_count", document_count.to_string())
+            .with_var("failed_count", failed_count.to_string())
+    }
+
+    pub fn rating_submitted(draft_id: &str, rating: i32, category: Option<&str>) -> Self {
+        Self::new(NotificationEvent::RatingSubmitted)
+            .with_var("draft_id", draft_id)
+            .with_var("rating", rating.to_string())
+            .with_var("category", category.unwrap_or("none"))
+    }
+
+    /// Render the given template (or the event default) with this notification's variables
+    pub fn render(&self, template: Option<&str>) -> String {
+        render_template(
+            template.unwrap_or_else(|| self.event.default_template()),
+            &self.vars,
+        )
+    }
+}
+
+/// Per-integration event toggles and message templates
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventPreferences {
+    /// Per-event toggles; events not listed use `NotificationEvent::enabled_by_default`
+    #[serde(default)]
+    pub events: BTreeMap<NotificationEvent, bool>,
+    /// Per-event message templates (`{{var}}` placeholders); missing events use defaults
+    #[serde(default)]
+    pub templates: BTreeMap<NotificationEvent, String>,
+}
+
+impl EventPreferences {
+    pub fn is_event_enabled(&self, event: NotificationEvent) -> bool {
+        self.events
+            .get(&event)
+            .copied()
+            .unwrap_or_else(|| event.enabled_by_default())
+    }
+
+    /// Render the message for a notification, or `None` if the event is disabled
+    pub fn message_for(&self, notification: &Notification) -> Option<String> {
+        if !self.is_event_enabled(notification.event) {
+            return None;
+        }
+        let template = self.templates.get(&notification.event);
+        Some(notification.render(template.map(String::as_str)))
+    }
+}
+
+/// Replace `{{name}}` placeholders with values from `vars`.
+/// Unknown placeholders are left untouched so template typos are visible.
+pub fn render_template(template: &str, vars: &BTreeMap<String, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let key = after[..end].trim();
+                match vars.get(key) {
+                    Some(value) => out.push_str(value),
+                    None => out.push_str(&rest[start..start + 2 + end + 2]),
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Deliver a notification to every enabled integration.
+///
+/// Configuration is read synchronously (the caller already holds the DB);
+/// delivery happens on the async runtime and failures are only logged.
+pub fn dispatch(db: &Database, notification: &Notification) {
+    match slack::SlackNotifier::from_db(db) {
+        Ok(Some(notifier)) => {
+            if let Some(message) = notifier.message_for(notification) {
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = notifier.send(&message).await {
+                        tracing::warn!("Slack notification failed: {}", e);
+                    }
+                });
+            }
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Slack notifications unavailable: {}", e),
+    }
+
+    match teams::TeamsNotifier::from_db(db) {
+        Ok(Some(notifier)) => {
+            if let Some(card) = notifier.card_for(notification) {
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = notifier.send(&card).await {
+                        tracing::warn!("Teams notification failed: {}", e);
+                    }
+                });
+            }
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Teams notifications unavailable: {}", e),
+    }
+
+    match webhooks::WebhookDispatcher::from_db(db) {
+        Ok(dispatcher) => {
+            let deliveries = dispatcher.deliveries_for(notification);
+            if !deliveries.is_empty() {
+                tauri::async_runtime::spawn(async move {
+                    for delivery in deliveries {
+                        if let Err(e) = dispatcher.send(&delivery).await {
+                            tracing::warn!(
+                                "Webhook delivery to {} failed: {}",
+                                delivery.endpoint_name,
+                                e
+                            );
+                        }
+                    }
+                });
+            }
+        }
+        Err(e) => tracing::warn!("Webhooks unavailable: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template_substitutes_vars() {
+        let n = Notification::batch_complete("job-1", 4, 5);
+        assert_eq!(
+            n.render(None),
+            "Batch job-1 complete: 4/5 responses generated"
+        );
+        assert_eq!(n.render(Some("{{ job_id }} done")), "job-1 done");
+    }
+
+    #[test]
+    fn test_render_template_keeps_unknown_placeholders() {
+        let vars = BTreeMap::new();
+        assert_eq!(render_template("Hi {{who}}", &vars), "Hi {{who}}");
+        assert_eq!(render_template("Broken {{who", &vars), "Broken {{who");
+    }
+
+    #[test]
+    fn test_source_failure_summary_truncates() {
+        let failures: Vec<(String, String)> = (0..5)
+            .map(|i| (format!("https://example.com/{}", i), "timeout".to_string()))
+            .collect();
+        let n = Notification::source_ingestion_failed("it-docs", &failures);
+        assert_eq!(n.vars["failed_count"], "5");
+        assert!(n.vars["errors"].ends_with("and 2 more"));
+    }
+
+    #[test]
+    fn test_high_volume_events_are_opt_in() {
+        let prefs = EventPreferences::default();
+        assert!(prefs.is_event_enabled(NotificationEvent::DraftReady));
+        assert!(!prefs.is_event_enabled(NotificationEvent::RatingSubmitted));
+        assert!(prefs
+            .message_for(&Notification::rating_submitted("d1", 2, None))
+            .is_none());
+
+        let mut prefs = prefs;
+        prefs
+            .events
+            .insert(NotificationEvent::RatingSubmitted, true);
+        assert_eq!(
+            prefs
+                .message_for(&Notification::rating_submitted("d1", 2, None))
+                .as_deref(),
+            Some("Draft d1 rated 2/5")
+        );
+    }
+
+    #[test]
+    fn test_event_round_trip() {
+        for event in NotificationEvent::ALL {
+            assert_eq!(event.as_str().parse::<NotificationEvent>(), Ok(event));
+        }
+        assert!("nope".parse::<NotificationEvent>().is_err());
+    }
+}
@@ -0,0 +1,251 @@
+//! Slack notification delivery
+//!
+//! Supports two delivery modes:
+//! - Incoming webhook (URL stored in the token store as `slack_webhook_url`)
+//! - Bot token + `chat.postMessage` (token stored as `slack_bot_token`, requires a channel)
+
+use super::{EventPreferences, Notification, NotificationError};
+use crate::db::Database;
+use crate::security::{vault::Vault, SecureString, TOKEN_SLACK_BOT, TOKEN_SLACK_WEBHOOK};
+use reqwest::{header, Client};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Integration type key in `integration_configs`
+pub const SLACK_INTEGRATION: &str = "slack";
+
+const SLACK_POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+const SLACK_WEBHOOK_HOST: &str = "hooks.slack.com";
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+
+/// Slack settings (stored as JSON in `integration_configs.config_json`, no secrets)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlackConfig {
+    /// Target channel (required for bot delivery, optional override for webhooks)
+    #[serde(default)]
+    pub channel: Option<String>,
+    #[serde(flatten)]
+    pub preferences: EventPreferences,
+}
+
+impl SlackConfig {
+    pub fn validate(&self) -> Result<(), NotificationError> {
+        if let Some(channel) = &self.channel {
+            let channel = channel.trim();
+            if channel.is_empty() || channel.len() > 80 || channel.contains(char::is_whitespace) {
+                return Err(NotificationError::InvalidConfig(format!(
+                    "Invalid Slack channel: {}",
+                    channel
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Validate a Slack incoming webhook URL (HTTPS, hooks.slack.com only)
+pub fn validate_webhook_url(url: &str) -> Result<(), NotificationError> {
+    let parsed = url::Url::parse(url)
+        .map_err(|e| NotificationError::InvalidConfig(format!("Invalid webhook URL: {}", e)))?;
+    if parsed.scheme() != "https" || parsed.host_str() != Some(SLACK_WEBHOOK_HOST) {
+        return Err(NotificationError::InvalidConfig(format!(
+            "Slack webhook URL must be https://{}/...",
+            SLACK_WEBHOOK_HOST
+        )));
+    }
+    Ok(())
+}
+
+enum SlackDelivery {
+    Webhook(SecureString),
+    Bot(SecureString),
+}
+
+/// Configured Slack sender
+pub struct SlackNotifier {
+    client: Client,
+    delivery: SlackDelivery,
+    config: SlackConfig,
+}
+
+impl SlackNotifier {
+    /// Load the Slack notifier from settings. Returns `None` when Slack is
+    /// not configured or disabled.
+    pub fn from_db(db: &Database) -> Result<Option<Self>, NotificationError> {
+        let record = db
+            .get_integration_config(SLACK_INTEGRATION)
+            .map_err(|e| NotificationError::InvalidConfig(e.to_string()))?;
+        let Some(record) = record.filter(|r| r.enabled) else {
+            return Ok(None);
+        };
+
+        let config: SlackConfig = match record.config_json.as_deref() {
+            Some(json) => serde_json::from_str(json)
+                .map_err(|e| NotificationError::InvalidConfig(e.to_string()))?,
+            None => SlackConfig::default(),
+        };
+
+        let read_token = |name: &str| {
+            Vault::get(name).map_err(|e| NotificationError::InvalidConfig(e.to_string()))
+        };
+
+        let delivery = if let Some(token) = read_token(TOKEN_SLACK_BOT)? {
+            SlackDelivery::Bot(SecureString::new(token))
+        } else if let Some(url) = read_token(TOKEN_SLACK_WEBHOOK)? {
+            SlackDelivery::Webhook(SecureString::new(url))
+        } else {
+            return Err(NotificationError::NotConfigured);
+        };
+
+        Ok(Some(Self::new(delivery, config)))
+    }
+
+    fn new(delivery: SlackDelivery, config: SlackConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            delivery,
+            config,
+        }
+    }
+
+    /// Render the message for a notification, or `None` if the event is disabled
+    pub fn message_for(&self, notification: &Notification) -> Option<String> {
+        self.config.preferences.message_for(notification)
+    }
+
+    /// Post a message to Slack
+    pub async fn send(&self, text: &str) -> Result<(), NotificationError> {
+        let payload = build_payload(text, self.config.channel.as_deref());
+
+        match &self.delivery {
+            SlackDelivery::Webhook(url) => {
+                let resp = self.client.post(url.as_str()).json(&payload).send().await?;
+                if !resp.status().is_success() {
+                    return Err(NotificationError::Delivery(format!(
+                        "Slack webhook returned HTTP {}",
+                        resp.status()
+                    )));
+                }
+            }
+            SlackDelivery::Bot(token) => {
+                if self.config.channel.is_none() {
+                    return Err(NotificationError::InvalidConfig(
+                        "A channel is required when using a Slack bot token".into(),
+                    ));
+                }
+                let resp = self
+                    .client
+                    .post(SLACK_POST_MESSAGE_URL)
+                    .header(header::AUTHORIZATION, format!("Bearer {}", token.as_str()))
+                    .json(&payload)
+                    .send()
+                    .await?;
+                // chat.postMessage returns HTTP 200 with {"ok": false} on errors
+                let body: serde_json::Value = resp.json().await?;
+                if body["ok"].as_bool() != Some(true) {
+                    return Err(NotificationError::Delivery(format!(
+                        "Slack API error: {}",
+                        body["error"].as_str().unwrap_or("unknown")
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Build the JSON body shared by webhook and `chat.postMessage` delivery
+fn build_payload(text: &str, channel: Option<&str>) -> serde_json::Value {
+    let mut payload = serde_json::json!({
+        "text": text,
+        "username": "AssistSupport",
+    });
+    if let Some(channel) = channel {
+        payload["channel"] = serde_json::json!(channel.trim());
+    }
+    payload
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifications::NotificationEvent;
+
+    #[test]
+    fn test_event_toggles_default_enabled() {
+        let mut config = SlackConfig::default();
+        assert!(config
+            .preferences
+            .is_event_enabled(NotificationEvent::DraftReady));
+
+        config
+            .preferences
+            .events
+            .insert(NotificationEvent::DraftReady, false);
+        assert!(!config
+            .preferences
+            .is_event_enabled(NotificationEvent::DraftReady));
+        assert!(config
+            .preferences
+            .is_event_enabled(NotificationEvent::BatchComplete));
+    }
+
+    #[test]
+    fn test_custom_template_and_disabled_event() {
+        let mut config = SlackConfig::default();
+        config.preferences.templates.insert(
+            NotificationEvent::BatchComplete,
+            ":white_check_mark: {{job_id}}".to_string(),
+        );
+        config
+            .preferences
+            .events
+            .insert(NotificationEvent::SourceIngestionFailed, false);
+        let notifier = SlackNotifier::new(
+            SlackDelivery::Webhook(SecureString::new(String::new())),
+            config,
+        );
+
+        assert_eq!(
+            notifier.message_for(&Notification::batch_complete("job-9", 1, 1)),
+            Some(":white_check_mark: job-9".to_string())
+        );
+        assert_eq!(
+            notifier.message_for(&Notification::source_ingestion_failed("default", &[])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_config_json_round_trip() {
+        let json = r##"{"channel":"#it-support","events":{"draft_ready":false},"templates":{"batch_complete":"done"}}"##;
+        let config: SlackConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.channel.as_deref(), Some("#it-support"));
+        assert!(!config
+            .preferences
+            .is_event_enabled(NotificationEvent::DraftReady));
+        assert!(config.validate().is_ok());
+
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["templates"]["batch_complete"], "done");
+    }
+
+    #[test]
+    fn test_validate_webhook_url() {
+        assert!(validate_webhook_url("https://hooks.slack.com/services/T000/B000/XXX").is_ok());
+        assert!(validate_webhook_url("http://hooks.slack.com/services/T000").is_err());
+        assert!(validate_webhook_url("https://evil.example.com/services/T000").is_err());
+    }
+
+    #[test]
+    fn test_build_payload_channel() {
+        let payload = build_payload("hello", Some(" #ops "));
+        assert_eq!(payload["channel"], "#ops");
+        assert!(build_payload("hello", None).get("channel").is_none());
+    }
+}
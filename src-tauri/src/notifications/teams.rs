@@ -0,0 +1,204 @@
+//! Microsoft Teams notification delivery
+//!
+//! Posts Adaptive Cards to a Teams incoming webhook (classic connector URL on
+//! `*.webhook.office.com` or a Workflows URL on `*.logic.azure.com`). The
+//! webhook URL is stored in the token store as `teams_webhook_url`.
+
+use super::{EventPreferences, Notification, NotificationError};
+use crate::db::Database;
+use crate::security::{vault::Vault, SecureString, TOKEN_TEAMS_WEBHOOK};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Integration type key in `integration_configs`
+pub const TEAMS_INTEGRATION: &str = "teams";
+
+const TEAMS_WEBHOOK_HOST_SUFFIXES: &[&str] = &[".webhook.office.com", ".logic.azure.com"];
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+
+/// Teams settings (stored as JSON in `integration_configs.config_json`, no secrets)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamsConfig {
+    #[serde(flatten)]
+    pub preferences: EventPreferences,
+}
+
+/// Validate a Teams incoming webhook URL (HTTPS, Microsoft webhook hosts only)
+pub fn validate_webhook_url(url: &str) -> Result<(), NotificationError> {
+    let parsed = url::Url::parse(url)
+        .map_err(|e| NotificationError::InvalidConfig(format!("Invalid webhook URL: {}", e)))?;
+    let host_ok = parsed.host_str().is_some_and(|host| {
+        TEAMS_WEBHOOK_HOST_SUFFIXES
+            .iter()
+            .any(|suffix| host.ends_with(suffix))
+    });
+    if parsed.scheme() != "https" || !host_ok {
+        return Err(NotificationError::InvalidConfig(
+            "Teams webhook URL must be an https://*.webhook.office.com or https://*.logic.azure.com URL"
+                .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Configured Teams sender
+pub struct TeamsNotifier {
+    client: Client,
+    webhook_url: SecureString,
+    config: TeamsConfig,
+}
+
+impl TeamsNotifier {
+    /// Load the Teams notifier from settings. Returns `None` when Teams is
+    /// not configured or disabled.
+    pub fn from_db(db: &Database) -> Result<Option<Self>, NotificationError> {
+        let record = db
+            .get_integration_config(TEAMS_INTEGRATION)
+            .map_err(|e| NotificationError::InvalidConfig(e.to_string()))?;
+        let Some(record) = record.filter(|r| r.enabled) else {
+            return Ok(None);
+        };
+
+        let config: TeamsConfig = match record.config_json.as_deref() {
+            Some(json) => serde_json::from_str(json)
+                .map_err(|e| NotificationError::InvalidConfig(e.to_string()))?,
+            None => TeamsConfig::default(),
+        };
+
+        let webhook_url = Vault::get(TOKEN_TEAMS_WEBHOOK)
+            .map_err(|e| NotificationError::InvalidConfig(e.to_string()))?
+            .ok_or(NotificationError::NotConfigured)?;
+
+        Ok(Some(Self::new(SecureString::new(webhook_url), config)))
+    }
+
+    fn new(webhook_url: SecureString, config: TeamsConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            webhook_url,
+            config,
+        }
+    }
+
+    /// Build the card for a notification, or `None` if the event is disabled
+    pub fn card_for(&self, notification: &Notification) -> Option<serde_json::Value> {
+        let message = self.config.preferences.message_for(notification)?;
+        Some(build_card(
+            notification.event.title(),
+            &message,
+            &notification.vars,
+        ))
+    }
+
+    /// Post a card to the webhook
+    pub async fn send(&self, card: &serde_json::Value) -> Result<(), NotificationError> {
+        let resp = self
+            .client
+            .post(self.webhook_url.as_str())
+            .json(card)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(NotificationError::Delivery(format!(
+                "Teams webhook returned HTTP {}",
+                resp.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Build an Adaptive Card message: title, rendered message, and a fact set
+/// with the notification variables.
+pub fn build_card(
+    title: &str,
+    message: &str,
+    vars: &BTreeMap<String, String>,
+) -> serde_json::Value {
+    let facts: Vec<serde_json::Value> = vars
+        .iter()
+        .map(|(key, value)| serde_json::json!({ "title": key, "value": value }))
+        .collect();
+
+    serde_json::json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "contentUrl": null,
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": [
+                    {
+                        "type": "TextBlock",
+                        "text": title,
+                        "weight": "Bolder",
+                        "size": "Medium"
+                    },
+                    {
+                        "type": "TextBlock",
+                        "text": message,
+                        "wrap": true
+                    },
+                    {
+                        "type": "FactSet",
+                        "facts": facts
+                    }
+                ]
+            }
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifications::NotificationEvent;
+
+    #[test]
+    fn test_validate_webhook_url() {
+        assert!(validate_webhook_url("https://contoso.webhook.office.com/webhookb2/abc").is_ok());
+        assert!(
+            validate_webhook_url("https://prod-01.westus.logic.azure.com/workflows/abc").is_ok()
+        );
+        assert!(validate_webhook_url("http://contoso.webhook.office.com/webhookb2/abc").is_err());
+        assert!(validate_webhook_url("https://webhook.office.com.evil.com/abc").is_err());
+    }
+
+    #[test]
+    fn test_card_contains_title_message_and_facts() {
+        let notification = Notification::sources_stale(3, 7);
+        let card = build_card(notification.event.title(), "3 stale", &notification.vars);
+        let body = &card["attachments"][0]["content"]["body"];
+
+        assert_eq!(body[0]["text"], "Stale sources");
+        assert_eq!(body[1]["text"], "3 stale");
+        let facts = body[2]["facts"].as_array().unwrap();
+        assert_eq!(facts.len(), 2);
+        assert_eq!(facts[0]["title"], "days");
+    }
+
+    #[test]
+    fn test_disabled_event_produces_no_card() {
+        let mut config = TeamsConfig::default();
+        config
+            .preferences
+            .events
+            .insert(NotificationEvent::KbHealthWarning, false);
+        let notifier = TeamsNotifier::new(SecureString::new(String::new()), config);
+
+        assert!(notifier
+            .card_for(&Notification::kb_health_warning(&["empty".to_string()]))
+            .is_none());
+        assert!(notifier
+            .card_for(&Notification::sources_stale(1, 7))
+            .is_some());
+    }
+}
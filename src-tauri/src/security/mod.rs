@@ -4,9 +4,14 @@
 //! Key storage modes:
 //! - Keychain: Master key stored in macOS Keychain (default, most secure)
 //! - Passphrase: Master key wrapped with user passphrase (portable, offline backup)
+//! - Biometric: Passphrase mode, plus a Keychain copy of the key released after
+//!   Touch ID / Windows Hello so the passphrase isn't typed every launch
 //!
 //! Credentials are stored under ~/Library/Application Support/AssistSupport/
-//! with restrictive permissions. Tokens are encrypted at rest with the master key.
+//! with restrictive permissions. Tokens are encrypted at rest with the master key
+//! and managed as named secrets through [`vault::Vault`].
+
+pub mod vault;
 
 use aes_gcm::{
     aead::{Aead, KeyInit, OsRng},
@@ -29,6 +34,7 @@ const SERVICE_NAME: &str = "AssistSupport";
 const MASTER_KEY_ENTRY: &str = "master-key";
 const HF_TOKEN_ENTRY: &str = "huggingface-token";
 const JIRA_TOKEN_ENTRY: &str = "jira-api-token";
+const BIOMETRIC_KEY_ENTRY: &str = "master-key-biometric";
 
 const ARGON2_MEMORY_COST: u32 = 65536; // 64 MiB
 const ARGON2_TIME_COST: u32 = 3;
@@ -80,6 +86,8 @@ pub enum KeyStorageMode {
     Keychain,
     /// Store master key wrapped with user passphrase
     Passphrase,
+    /// Passphrase mode, with the key also released by biometric unlock
+    Biometric,
 }
 
 impl std::fmt::Display for KeyStorageMode {
@@ -87,6 +95,7 @@ impl std::fmt::Display for KeyStorageMode {
         match self {
             Self::Keychain => write!(f, "keychain"),
             Self::Passphrase => write!(f, "passphrase"),
+            Self::Biometric => write!(f, "biometric"),
         }
     }
 }
@@ -98,6 +107,7 @@ impl std::str::FromStr for KeyStorageMode {
         match s.to_lowercase().as_str() {
             "keychain" => Ok(Self::Keychain),
             "passphrase" => Ok(Self::Passphrase),
+            "biometric" => Ok(Self::Biometric),
             _ => Err(SecurityError::InvalidKeyFormat),
         }
     }
@@ -127,6 +137,14 @@ pub enum SecurityError {
     PassphraseRequired,
     #[error("Key rotation failed: {0}")]
     KeyRotationFailed(String),
+    #[error("Biometric unlock unavailable: {0}")]
+    BiometricUnavailable(String),
+    #[error("Biometric authentication failed: {0}")]
+    BiometricFailed(String),
+    #[error("Invalid secret: {0}")]
+    InvalidSecret(String),
+    #[error("Secret already exists: {0}")]
+    SecretExists(String),
 }
 
 /// Securely zeroed master key wrapper
@@ -239,7 +257,11 @@ impl KeychainManager {
 
     /// Retrieve master key from Keychain
     pub fn get_master_key() -> Result<MasterKey, SecurityError> {
-        let entry = keyring::Entry::new(SERVICE_NAME, MASTER_KEY_ENTRY)
+        Self::read_key_entry(MASTER_KEY_ENTRY)
+    }
+
+    fn read_key_entry(name: &str) -> Result<MasterKey, SecurityError> {
+        let entry = keyring::Entry::new(SERVICE_NAME, name)
             .map_err(|e| SecurityError::Keychain(e.to_string()))?;
 
         let mut secret = entry.get_secret().map_err(|e| match e {
@@ -268,6 +290,34 @@ impl KeychainManager {
             .map_err(|e| SecurityError::Keychain(e.to_string()))
     }
 
+    /// Store the copy of the master key released by biometric unlock
+    pub fn store_biometric_key(key: &MasterKey) -> Result<(), SecurityError> {
+        let entry = keyring::Entry::new(SERVICE_NAME, BIOMETRIC_KEY_ENTRY)
+            .map_err(|e| SecurityError::Keychain(e.to_string()))?;
+
+        entry
+            .set_secret(key.as_bytes())
+            .map_err(|e| SecurityError::Keychain(e.to_string()))
+    }
+
+    /// Retrieve the biometric copy of the master key. Callers must run
+    /// `BiometricAuth::authenticate` first.
+    pub fn get_biometric_key() -> Result<MasterKey, SecurityError> {
+        Self::read_key_entry(BIOMETRIC_KEY_ENTRY)
+    }
+
+    /// Delete the biometric copy of the master key
+    pub fn delete_biometric_key() -> Result<(), SecurityError> {
+        let entry = keyring::Entry::new(SERVICE_NAME, BIOMETRIC_KEY_ENTRY)
+            .map_err(|e| SecurityError::Keychain(e.to_string()))?;
+
+        match entry.delete_credential() {
+            Ok(()) => Ok(()),
+            Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(SecurityError::Keychain(e.to_string())),
+        }
+    }
+
     /// Check if Keychain is available
     pub fn is_available() -> bool {
         keyring::Entry::new(SERVICE_NAME, "test")
@@ -344,9 +394,15 @@ impl KeychainManager {
     }
 }
 
-/// Token names for file storage
+/// Vault secret names for built-in integrations
 pub const TOKEN_HUGGINGFACE: &str = "huggingface_token";
 pub const TOKEN_JIRA: &str = "jira_api_token";
+pub const TOKEN_SLACK_WEBHOOK: &str = "slack_webhook_url";
+pub const TOKEN_SLACK_BOT: &str = "slack_bot_token";
+pub const TOKEN_TEAMS_WEBHOOK: &str = "teams_webhook_url";
+pub const TOKEN_SMTP_PASSWORD: &str = "smtp_password";
+pub const TOKEN_CONFLUENCE: &str = "confluence_api_token";
+pub const TOKEN_DOWNLOAD_PROXY_PASSWORD: &str = "download_proxy_password";
 
 /// Wrapped key file format (JSON)
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -372,6 +428,145 @@ struct KeyStorageConfig {
     mode: KeyStorageMode,
 }
 
+/// Prompt shown when unlocking at launch
+const BIOMETRIC_UNLOCK_REASON: &str = "unlock AssistSupport";
+
+/// Prompt shown when turning biometric unlock on
+const BIOMETRIC_ENABLE_REASON: &str = "enable biometric unlock for AssistSupport";
+
+/// Loads the WinRT UserConsentVerifier and an `Await` helper for its async calls
+#[cfg(target_os = "windows")]
+const WINDOWS_HELLO_PRELUDE: &str = r#"
+Add-Type -AssemblyName System.Runtime.WindowsRuntime
+$asTask = [System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object {
+    $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and
+    $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation`1'
+} | Select-Object -First 1
+$null = [Windows.Security.Credentials.UI.UserConsentVerifier, Windows.Security.Credentials.UI, ContentType = WindowsRuntime]
+function Await($op, $type) {
+    $task = $asTask.MakeGenericMethod($type).Invoke($null, @($op))
+    $task.Wait() | Out-Null
+    $task.Result
+}
+"#;
+
+/// Output of the biometric helper
+#[derive(Debug, serde::Deserialize)]
+struct BiometricResponse {
+    success: bool,
+    error: Option<String>,
+}
+
+/// OS biometric prompt: Touch ID via the `biometric_auth` helper on macOS,
+/// Windows Hello via PowerShell/WinRT on Windows
+pub struct BiometricAuth;
+
+impl BiometricAuth {
+    /// Check whether biometric unlock can be used on this machine
+    pub fn is_available() -> bool {
+        Self::run("check", None).is_ok()
+    }
+
+    /// Show the OS biometric prompt; succeeds only if the user verified
+    pub fn authenticate(reason: &str) -> Result<(), SecurityError> {
+        Self::run("authenticate", Some(reason))
+    }
+
+    #[cfg(target_os = "macos")]
+    fn run(action: &str, reason: Option<&str>) -> Result<(), SecurityError> {
+        let helper = Self::find_helper().ok_or_else(|| {
+            SecurityError::BiometricUnavailable("biometric_auth helper not found".into())
+        })?;
+        let mut command = std::process::Command::new(helper);
+        command.arg(action);
+        if let Some(reason) = reason {
+            command.arg(reason);
+        }
+        let output = command
+            .output()
+            .map_err(|e| SecurityError::BiometricUnavailable(e.to_string()))?;
+
+        let response: BiometricResponse = serde_json::from_slice(&output.stdout)
+            .map_err(|e| SecurityError::BiometricFailed(format!("Invalid helper output: {}", e)))?;
+        Self::check_response(action, response)
+    }
+
+    #[cfg(target_os = "windows")]
+    fn run(action: &str, reason: Option<&str>) -> Result<(), SecurityError> {
+        let (body, expected) = if action == "check" {
+            (
+                "Await ([Windows.Security.Credentials.UI.UserConsentVerifier]::CheckAvailabilityAsync()) ([Windows.Security.Credentials.UI.UserConsentVerifierAvailability])",
+                "Available",
+            )
+        } else {
+            (
+                "Await ([Windows.Security.Credentials.UI.UserConsentVerifier]::RequestVerificationAsync($env:ASSISTSUPPORT_BIOMETRIC_REASON)) ([Windows.Security.Credentials.UI.UserConsentVerificationResult])",
+                "Verified",
+            )
+        };
+        let script = format!("{}{}", WINDOWS_HELLO_PRELUDE, body);
+        let output = std::process::Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+            .env("ASSISTSUPPORT_BIOMETRIC_REASON", reason.unwrap_or_default())
+            .output()
+            .map_err(|e| SecurityError::BiometricUnavailable(e.to_string()))?;
+
+        let result = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Self::check_response(
+            action,
+            BiometricResponse {
+                success: result == expected,
+                error: Some(result),
+            },
+        )
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn run(action: &str, _reason: Option<&str>) -> Result<(), SecurityError> {
+        Self::check_response(
+            action,
+            BiometricResponse {
+                success: false,
+                error: Some("Not supported on this platform".into()),
+            },
+        )
+    }
+
+    fn check_response(action: &str, response: BiometricResponse) -> Result<(), SecurityError> {
+        if response.success {
+            return Ok(());
+        }
+        let error = response.error.unwrap_or_else(|| "Unknown error".into());
+        if action == "check" {
+            Err(SecurityError::BiometricUnavailable(error))
+        } else {
+            Err(SecurityError::BiometricFailed(error))
+        }
+    }
+
+    /// Locate the compiled helper (dev tree, then app bundle resources)
+    #[cfg(target_os = "macos")]
+    fn find_helper() -> Option<PathBuf> {
+        let dev_paths = [
+            PathBuf::from("helpers/biometric_auth"),
+            PathBuf::from("./helpers/biometric_auth"),
+            PathBuf::from("../src-tauri/helpers/biometric_auth"),
+        ];
+        if let Some(path) = dev_paths.iter().find(|p| p.exists()) {
+            return Some(path.clone());
+        }
+
+        let exe_path = std::env::current_exe().ok()?;
+        let resource_path = exe_path
+            .parent()?
+            .parent()?
+            .join("Resources")
+            .join("helpers")
+            .join("biometric_auth");
+        resource_path.exists().then_some(resource_path)
+    }
+}
+
 /// Secure key storage with support for Keychain and passphrase modes
 ///
 /// Storage location: ~/Library/Application Support/AssistSupport/
@@ -381,6 +576,7 @@ struct KeyStorageConfig {
 ///
 /// Keychain mode: Master key stored in macOS Keychain (most secure)
 /// Passphrase mode: Master key wrapped with user passphrase (portable)
+/// Biometric mode: Passphrase mode plus a Keychain copy released by biometrics
 pub struct FileKeyStore;
 
 impl FileKeyStore {
@@ -606,6 +802,14 @@ impl FileKeyStore {
             return Err(SecurityError::PassphraseRequired);
         }
 
+        // Biometric mode falls back to the passphrase if the prompt fails or
+        // is cancelled
+        if mode == KeyStorageMode::Biometric {
+            return BiometricAuth::authenticate(BIOMETRIC_UNLOCK_REASON)
+                .and_then(|_| KeychainManager::get_biometric_key())
+                .map_err(|_| SecurityError::PassphraseRequired);
+        }
+
         // 1. Try Keychain first
         if let Ok(key) = KeychainManager::get_master_key() {
             // Clean up any legacy files
@@ -712,7 +916,10 @@ impl FileKeyStore {
 
         let wrapped_path = Self::wrapped_key_path()?;
         Self::write_private_file(&wrapped_path, content.as_bytes())?;
-        Self::set_storage_mode(KeyStorageMode::Passphrase)?;
+        // Biometric mode keeps its Keychain copy alongside the wrapped key
+        if Self::get_storage_mode().ok() != Some(KeyStorageMode::Biometric) {
+            Self::set_storage_mode(KeyStorageMode::Passphrase)?;
+        }
 
         Ok(())
     }
@@ -724,10 +931,35 @@ impl FileKeyStore {
         Ok(())
     }
 
+    /// Enable biometric unlock (passphrase mode only). Verifies the
+    /// passphrase and the user's biometrics before storing the Keychain copy.
+    pub fn enable_biometric_unlock(passphrase: &str) -> Result<(), SecurityError> {
+        if Self::get_storage_mode()? != KeyStorageMode::Passphrase {
+            return Err(SecurityError::BiometricUnavailable(
+                "Passphrase mode is not enabled".into(),
+            ));
+        }
+
+        let key = Self::get_master_key_with_passphrase(passphrase)?;
+        BiometricAuth::authenticate(BIOMETRIC_ENABLE_REASON)?;
+        KeychainManager::store_biometric_key(&key)?;
+        Self::set_storage_mode(KeyStorageMode::Biometric)
+    }
+
+    /// Disable biometric unlock, returning to passphrase mode
+    pub fn disable_biometric_unlock() -> Result<(), SecurityError> {
+        if Self::get_storage_mode()? != KeyStorageMode::Biometric {
+            return Ok(());
+        }
+        KeychainManager::delete_biometric_key()?;
+        Self::set_storage_mode(KeyStorageMode::Passphrase)
+    }
+
     /// Delete all key storage
     pub fn delete_all_key_storage() -> Result<(), SecurityError> {
         // Delete from Keychain
         let _ = KeychainManager::delete_master_key();
+        let _ = KeychainManager::delete_biometric_key();
 
         // Delete wrapped key file
         let wrapped_path = Self::wrapped_key_path()?;
@@ -779,38 +1011,6 @@ impl FileKeyStore {
         Ok(())
     }
 
-    /// Get a token by name from tokens.json
-    pub fn get_token(name: &str) -> Result<Option<String>, SecurityError> {
-        let master_key = Self::get_master_key()?;
-        let tokens = Self::read_tokens_map_with_key(&master_key)?;
-        Ok(tokens.get(name).cloned())
-    }
-
-    /// Store a token by name to tokens.json
-    pub fn store_token(name: &str, value: &str) -> Result<(), SecurityError> {
-        let master_key = Self::get_master_key()?;
-        Self::store_token_with_key(&master_key, name, value)
-    }
-
-    /// Delete a token by name from tokens.json
-    pub fn delete_token(name: &str) -> Result<(), SecurityError> {
-        let master_key = Self::get_master_key()?;
-        let tokens_path = Self::tokens_path()?;
-        if !tokens_path.exists() {
-            return Ok(());
-        }
-
-        let mut tokens = Self::read_tokens_map_with_key(&master_key)?;
-        tokens.remove(name);
-
-        if tokens.is_empty() {
-            secure_delete_file(&tokens_path).map_err(|e| SecurityError::FileIO(e.to_string()))?;
-            return Ok(());
-        }
-
-        Self::store_tokens_map_with_key(&master_key, &tokens)
-    }
-
     /// Migrate tokens from Keychain to file storage
     fn migrate_tokens_from_keychain(master_key: &MasterKey) -> Result<(), SecurityError> {
         // Migrate HuggingFace token
@@ -833,10 +1033,11 @@ impl FileKeyStore {
         Self::has_any_key_storage()
     }
 
-    /// Check if passphrase mode is active
+    /// Check if the master key is passphrase-wrapped (passphrase or
+    /// biometric mode)
     pub fn is_passphrase_mode() -> bool {
         Self::get_storage_mode()
-            .map(|m| m == KeyStorageMode::Passphrase)
+            .map(|m| matches!(m, KeyStorageMode::Passphrase | KeyStorageMode::Biometric))
             .unwrap_or(false)
     }
 }
@@ -890,6 +1091,87 @@ impl KeyRotation {
         Ok((old_key, new_key))
     }
 
+    /// Rotate a (possibly compromised) passphrase: generates a new master key,
+    /// re-encrypts the database via `rekey_database` and the stored tokens,
+    /// wraps the new key with the new passphrase, then re-verifies the
+    /// wrapping. On failure the old key, tokens and wrapping are restored.
+    pub fn rotate_passphrase(
+        old_passphrase: &str,
+        new_passphrase: &str,
+        rekey_database: impl Fn(&MasterKey) -> Result<(), SecurityError>,
+    ) -> Result<(), SecurityError> {
+        if !FileKeyStore::is_passphrase_mode() {
+            return Err(SecurityError::KeyRotationFailed(
+                "Passphrase mode is not enabled".into(),
+            ));
+        }
+        if new_passphrase.is_empty() {
+            return Err(SecurityError::KeyRotationFailed(
+                "New passphrase cannot be empty".into(),
+            ));
+        }
+        if new_passphrase == old_passphrase {
+            return Err(SecurityError::KeyRotationFailed(
+                "New passphrase must differ from the current one".into(),
+            ));
+        }
+
+        // Fails with a decryption error if the old passphrase is wrong
+        let old_key = FileKeyStore::get_master_key_with_passphrase(old_passphrase)?;
+        let new_key = MasterKey::generate();
+        let biometric = FileKeyStore::get_storage_mode()? == KeyStorageMode::Biometric;
+        let tokens_path = FileKeyStore::tokens_path()?;
+        let tokens = if tokens_path.exists() {
+            Some(FileKeyStore::read_tokens_map_with_key(&old_key)?)
+        } else {
+            None
+        };
+
+        rekey_database(&new_key)?;
+
+        let result = Self::store_rotated_key(&new_key, tokens.as_ref(), new_passphrase, biometric);
+
+        if let Err(e) = result {
+            // Best-effort rollback to the old key
+            let _ = rekey_database(&old_key);
+            if let Some(tokens) = &tokens {
+                let _ = FileKeyStore::store_tokens_map_with_key(&old_key, tokens);
+            }
+            let _ = FileKeyStore::store_master_key_with_passphrase(&old_key, old_passphrase);
+            if biometric {
+                let _ = KeychainManager::store_biometric_key(&old_key);
+            }
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Store tokens and the wrapped key under a rotated key, then check the
+    /// new passphrase unwraps it
+    fn store_rotated_key(
+        new_key: &MasterKey,
+        tokens: Option<&HashMap<String, String>>,
+        new_passphrase: &str,
+        biometric: bool,
+    ) -> Result<(), SecurityError> {
+        if let Some(tokens) = tokens {
+            FileKeyStore::store_tokens_map_with_key(new_key, tokens)?;
+        }
+        FileKeyStore::store_master_key_with_passphrase(new_key, new_passphrase)?;
+        if biometric {
+            KeychainManager::store_biometric_key(new_key)?;
+        }
+
+        let verified = FileKeyStore::get_master_key_with_passphrase(new_passphrase)?;
+        if verified.as_bytes() != new_key.as_bytes() {
+            return Err(SecurityError::KeyRotationFailed(
+                "New passphrase did not unwrap the new key".into(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Change passphrase without rotating the key
     pub fn change_passphrase(
         old_passphrase: &str,
@@ -943,6 +1225,7 @@ impl KeyRotation {
         // Store in Keychain
         KeychainManager::store_master_key(&key)?;
         FileKeyStore::set_storage_mode(KeyStorageMode::Keychain)?;
+        let _ = KeychainManager::delete_biometric_key();
 
         // Delete wrapped key file
         let wrapped_path = FileKeyStore::wrapped_key_path()?;
@@ -1188,6 +1471,42 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_storage_mode_roundtrip() {
+        for mode in [
+            KeyStorageMode::Keychain,
+            KeyStorageMode::Passphrase,
+            KeyStorageMode::Biometric,
+        ] {
+            assert_eq!(mode.to_string().parse::<KeyStorageMode>().unwrap(), mode);
+        }
+        assert!("touchid".parse::<KeyStorageMode>().is_err());
+    }
+
+    #[test]
+    fn test_biometric_response() {
+        let failed = |error: Option<&str>| BiometricResponse {
+            success: false,
+            error: error.map(|e| e.to_string()),
+        };
+        assert!(BiometricAuth::check_response(
+            "authenticate",
+            BiometricResponse {
+                success: true,
+                error: None
+            }
+        )
+        .is_ok());
+        assert!(matches!(
+            BiometricAuth::check_response("check", failed(Some("No biometrics enrolled"))),
+            Err(SecurityError::BiometricUnavailable(e)) if e == "No biometrics enrolled"
+        ));
+        assert!(matches!(
+            BiometricAuth::check_response("authenticate", failed(None)),
+            Err(SecurityError::BiometricFailed(_))
+        ));
+    }
+
     #[test]
     fn test_export_crypto() {
         let data = b"Export test data";
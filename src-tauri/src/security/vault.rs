@@ -0,0 +1,315 @@
+//! Named secrets vault
+//!
+//! Every integration credential (HuggingFace, Jira, GitHub, Slack, Teams,
+//! SMTP, Confluence, webhook signing secrets) is a named secret in the vault.
+//! Secrets live in the encrypted tokens.json alongside a metadata entry that
+//! records when each one was created and last rotated. Every create, rotate
+//! and delete is audited under the secret's name; values are never logged.
+
+use super::{secure_delete_file, FileKeyStore, MasterKey, SecurityError};
+use crate::audit;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Reserved tokens.json entry holding secret metadata (JSON encoded)
+const METADATA_ENTRY: &str = "__vault_metadata__";
+
+const MAX_SECRET_NAME_LEN: usize = 128;
+
+/// Serializes read-modify-write cycles on tokens.json
+static VAULT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Metadata kept for each secret
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct SecretMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rotated_at: Option<String>,
+    #[serde(default)]
+    rotation_count: u32,
+}
+
+/// A stored secret as listed to callers (the value is never included)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SecretInfo {
+    pub name: String,
+    /// None for secrets stored before the vault tracked metadata
+    pub created_at: Option<String>,
+    pub rotated_at: Option<String>,
+    pub rotation_count: u32,
+}
+
+/// What `Vault::put` did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretChange {
+    Created,
+    Rotated,
+}
+
+/// Named secret storage backed by the encrypted tokens file
+pub struct Vault;
+
+impl Vault {
+    /// Get a secret's value
+    pub fn get(name: &str) -> Result<Option<String>, SecurityError> {
+        validate_name(name)?;
+        let master_key = FileKeyStore::get_master_key()?;
+        let secrets = FileKeyStore::read_tokens_map_with_key(&master_key)?;
+        Ok(secrets.get(name).cloned())
+    }
+
+    /// Whether a secret exists (false if the vault can't be read)
+    pub fn contains(name: &str) -> bool {
+        Self::get(name).map(|v| v.is_some()).unwrap_or(false)
+    }
+
+    /// List stored secrets, sorted by name
+    pub fn list() -> Result<Vec<SecretInfo>, SecurityError> {
+        let master_key = FileKeyStore::get_master_key()?;
+        let secrets = FileKeyStore::read_tokens_map_with_key(&master_key)?;
+        Ok(list_secrets(&secrets))
+    }
+
+    /// Create a new secret; fails if one with this name already exists
+    pub fn create(name: &str, value: &str) -> Result<(), SecurityError> {
+        Self::write(name, value, Some(SecretChange::Created)).map(|_| ())
+    }
+
+    /// Replace the value of an existing secret
+    pub fn rotate(name: &str, value: &str) -> Result<(), SecurityError> {
+        Self::write(name, value, Some(SecretChange::Rotated)).map(|_| ())
+    }
+
+    /// Create the secret, or rotate it if it already exists
+    pub fn put(name: &str, value: &str) -> Result<SecretChange, SecurityError> {
+        Self::write(name, value, None)
+    }
+
+    /// Delete a secret, returning whether it existed
+    pub fn delete(name: &str) -> Result<bool, SecurityError> {
+        validate_name(name)?;
+        let _guard = VAULT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let master_key = FileKeyStore::get_master_key()?;
+        let tokens_path = FileKeyStore::tokens_path()?;
+        if !tokens_path.exists() {
+            return Ok(false);
+        }
+
+        let mut secrets = FileKeyStore::read_tokens_map_with_key(&master_key)?;
+        if !remove_secret(&mut secrets, name)? {
+            return Ok(false);
+        }
+
+        if secrets.is_empty() {
+            secure_delete_file(&tokens_path).map_err(|e| SecurityError::FileIO(e.to_string()))?;
+        } else {
+            FileKeyStore::store_tokens_map_with_key(&master_key, &secrets)?;
+        }
+        audit::audit_secret_deleted(name);
+        Ok(true)
+    }
+
+    fn write(
+        name: &str,
+        value: &str,
+        expected: Option<SecretChange>,
+    ) -> Result<SecretChange, SecurityError> {
+        validate_name(name)?;
+        if value.is_empty() {
+            return Err(SecurityError::InvalidSecret(format!(
+                "Secret '{}' cannot be empty",
+                name
+            )));
+        }
+
+        let _guard = VAULT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let master_key = FileKeyStore::get_master_key()?;
+        let change = Self::write_with_key(&master_key, name, value, expected)?;
+        match change {
+            SecretChange::Created => audit::audit_secret_created(name),
+            SecretChange::Rotated => audit::audit_secret_rotated(name),
+        }
+        Ok(change)
+    }
+
+    fn write_with_key(
+        master_key: &MasterKey,
+        name: &str,
+        value: &str,
+        expected: Option<SecretChange>,
+    ) -> Result<SecretChange, SecurityError> {
+        let mut secrets = FileKeyStore::read_tokens_map_with_key(master_key)?;
+        let change = match (secrets.contains_key(name), expected) {
+            (true, Some(SecretChange::Created)) => {
+                return Err(SecurityError::SecretExists(name.to_string()))
+            }
+            (false, Some(SecretChange::Rotated)) => {
+                return Err(SecurityError::TokenNotFound(name.to_string()))
+            }
+            _ => upsert_secret(&mut secrets, name, value, &Utc::now().to_rfc3339())?,
+        };
+        FileKeyStore::store_tokens_map_with_key(master_key, &secrets)?;
+        Ok(change)
+    }
+}
+
+/// Secret names: 1-128 chars of ASCII letters, digits, `_`, `-`, `.`, `:`
+fn validate_name(name: &str) -> Result<(), SecurityError> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_SECRET_NAME_LEN
+        && name != METADATA_ENTRY
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'));
+    if valid {
+        Ok(())
+    } else {
+        Err(SecurityError::InvalidSecret(format!(
+            "Invalid secret name: {:?}",
+            name
+        )))
+    }
+}
+
+fn read_metadata(
+    secrets: &HashMap<String, String>,
+) -> Result<HashMap<String, SecretMetadata>, SecurityError> {
+    match secrets.get(METADATA_ENTRY) {
+        Some(json) => serde_json::from_str(json)
+            .map_err(|e| SecurityError::FileIO(format!("Invalid vault metadata: {}", e))),
+        None => Ok(HashMap::new()),
+    }
+}
+
+fn write_metadata(
+    secrets: &mut HashMap<String, String>,
+    metadata: &HashMap<String, SecretMetadata>,
+) -> Result<(), SecurityError> {
+    if metadata.is_empty() {
+        secrets.remove(METADATA_ENTRY);
+        return Ok(());
+    }
+    let json = serde_json::to_string(metadata).map_err(|e| SecurityError::FileIO(e.to_string()))?;
+    secrets.insert(METADATA_ENTRY.to_string(), json);
+    Ok(())
+}
+
+fn upsert_secret(
+    secrets: &mut HashMap<String, String>,
+    name: &str,
+    value: &str,
+    now: &str,
+) -> Result<SecretChange, SecurityError> {
+    let mut metadata = read_metadata(secrets)?;
+    let existed = secrets
+        .insert(name.to_string(), value.to_string())
+        .is_some();
+
+    let entry = metadata.entry(name.to_string()).or_default();
+    let change = if existed {
+        entry.rotated_at = Some(now.to_string());
+        entry.rotation_count += 1;
+        SecretChange::Rotated
+    } else {
+        *entry = SecretMetadata {
+            created_at: Some(now.to_string()),
+            ..Default::default()
+        };
+        SecretChange::Created
+    };
+
+    write_metadata(secrets, &metadata)?;
+    Ok(change)
+}
+
+fn remove_secret(secrets: &mut HashMap<String, String>, name: &str) -> Result<bool, SecurityError> {
+    let mut metadata = read_metadata(secrets)?;
+    let existed = secrets.remove(name).is_some();
+    if metadata.remove(name).is_some() || existed {
+        write_metadata(secrets, &metadata)?;
+    }
+    Ok(existed)
+}
+
+fn list_secrets(secrets: &HashMap<String, String>) -> Vec<SecretInfo> {
+    // Unreadable metadata shouldn't hide the secrets themselves
+    let metadata = read_metadata(secrets).unwrap_or_default();
+    let mut infos: Vec<SecretInfo> = secrets
+        .keys()
+        .filter(|name| name.as_str() != METADATA_ENTRY)
+        .map(|name| {
+            let meta = metadata.get(name);
+            SecretInfo {
+                name: name.clone(),
+                created_at: meta.and_then(|m| m.created_at.clone()),
+                rotated_at: meta.and_then(|m| m.rotated_at.clone()),
+                rotation_count: meta.map(|m| m.rotation_count).unwrap_or(0),
+            }
+        })
+        .collect();
+    infos.sort_by(|a, b| a.name.cmp(&b.name));
+    infos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_lifecycle_metadata() {
+        let mut secrets = HashMap::new();
+        // Stored before the vault existed
+        secrets.insert("jira_api_token".to_string(), "legacy".to_string());
+
+        assert_eq!(
+            upsert_secret(&mut secrets, "github_token:github.com", "a", "t1").unwrap(),
+            SecretChange::Created
+        );
+        assert_eq!(
+            upsert_secret(&mut secrets, "github_token:github.com", "b", "t2").unwrap(),
+            SecretChange::Rotated
+        );
+        assert_eq!(
+            upsert_secret(&mut secrets, "jira_api_token", "new", "t3").unwrap(),
+            SecretChange::Rotated
+        );
+        assert_eq!(secrets["github_token:github.com"], "b");
+
+        let infos = list_secrets(&secrets);
+        assert_eq!(
+            infos,
+            vec![
+                SecretInfo {
+                    name: "github_token:github.com".to_string(),
+                    created_at: Some("t1".to_string()),
+                    rotated_at: Some("t2".to_string()),
+                    rotation_count: 1,
+                },
+                SecretInfo {
+                    name: "jira_api_token".to_string(),
+                    created_at: None,
+                    rotated_at: Some("t3".to_string()),
+                    rotation_count: 1,
+                },
+            ]
+        );
+
+        assert!(remove_secret(&mut secrets, "jira_api_token").unwrap());
+        assert!(!remove_secret(&mut secrets, "jira_api_token").unwrap());
+        assert!(remove_secret(&mut secrets, "github_token:github.com").unwrap());
+        assert!(secrets.is_empty());
+    }
+
+    #[test]
+    fn test_secret_name_validation() {
+        assert!(validate_name("huggingface_token").is_ok());
+        assert!(validate_name("webhook_secret:3f2a-b1").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name(METADATA_ENTRY).is_err());
+        assert!(validate_name("has space").is_err());
+        assert!(validate_name(&"x".repeat(MAX_SECRET_NAME_LEN + 1)).is_err());
+    }
+}
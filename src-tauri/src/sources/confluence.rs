@@ -0,0 +1,831 @@
+//! Confluence Cloud connector
+//!
+//! Pulls the pages of a space through the REST API, converts their storage
+//! format (XHTML with `ac:`/`ri:` macros) to markdown and indexes them with
+//! ingest source/run tracking. Each page is one ingest source
+//! (`source_type = "confluence"`) whose `etag` holds the page version, so
+//! re-ingesting a space only re-indexes edited pages and removes deleted ones.
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use sha2::{Digest, Sha256};
+
+use crate::confluence::{ConfluenceClient, ConfluenceError, SpacePage, MAX_SPACE_PAGES};
+use crate::db::{Database, IngestRunCompletion, IngestSource};
+use crate::kb::indexer::KbIndexer;
+use crate::kb::ingest::{IngestError, IngestResult, IngestedDocument};
+use crate::kb::network::NetworkError;
+use crate::validation::{normalize_and_validate_namespace_id, ValidationError};
+
+/// Ingest source type for Confluence pages
+pub const CONFLUENCE_SOURCE_TYPE: &str = "confluence";
+
+/// Result of ingesting a Confluence space
+#[derive(Debug, Clone)]
+pub struct ConfluenceIngestResult {
+    pub namespace_id: String,
+    pub total_pages: usize,
+    pub ingested: usize,
+    /// Pages whose version had not changed since the last ingest
+    pub skipped: usize,
+    /// Previously ingested pages no longer in the space
+    pub removed: usize,
+    pub errors: usize,
+    pub documents: Vec<IngestedDocument>,
+}
+
+/// Namespace a space maps to by default, e.g. `IT` -> `confluence-it`
+pub fn space_namespace(space_key: &str) -> Result<String, ValidationError> {
+    normalize_and_validate_namespace_id(&format!(
+        "confluence-{}",
+        space_key.trim().trim_start_matches('~')
+    ))
+}
+
+fn ingest_error(error: ConfluenceError) -> IngestError {
+    match error {
+        ConfluenceError::AuthFailed | ConfluenceError::NotConfigured => {
+            IngestError::AuthRequired(error.to_string())
+        }
+        ConfluenceError::Invalid(message) => IngestError::InvalidSource(message),
+        ConfluenceError::Request(e) if e.is_timeout() => IngestError::Timeout(e.to_string()),
+        other => IngestError::Network(NetworkError::RequestFailed(other.to_string())),
+    }
+}
+
+/// Ingests Confluence spaces into the knowledge base
+pub struct ConfluenceIngester {
+    client: ConfluenceClient,
+    indexer: KbIndexer,
+}
+
+impl ConfluenceIngester {
+    pub fn new(client: ConfluenceClient) -> Self {
+        Self {
+            client,
+            indexer: KbIndexer::new(),
+        }
+    }
+
+    /// Ingest every current page of `space_key` into `namespace_id`.
+    ///
+    /// Pages whose version matches the last ingest are skipped; pages
+    /// ingested earlier from this space that are gone are removed. A failing
+    /// page is counted in `errors` without stopping the rest of the space.
+    pub async fn ingest_space(
+        &self,
+        db: &Database,
+        space_key: &str,
+        namespace_id: &str,
+    ) -> IngestResult<ConfluenceIngestResult> {
+        let pages = self
+            .client
+            .list_space_pages(space_key)
+            .await
+            .map_err(ingest_error)?;
+        let total_pages = pages.len();
+
+        let mut ingested = 0;
+        let mut skipped = 0;
+        let mut errors = 0;
+        let mut documents = Vec::new();
+
+        for page in &pages {
+            match self.ingest_page(db, space_key, page, namespace_id).await {
+                Ok(Some(doc)) => {
+                    ingested += 1;
+                    documents.push(doc);
+                }
+                Ok(None) => skipped += 1,
+                Err(e) => {
+                    tracing::warn!("Failed to ingest Confluence page {}: {}", page.id, e);
+                    errors += 1;
+                }
+            }
+        }
+
+        // A truncated listing cannot tell deleted pages from unlisted ones
+        let removed = if total_pages < MAX_SPACE_PAGES {
+            self.remove_deleted_pages(db, space_key, &pages, namespace_id)?
+        } else {
+            0
+        };
+
+        Ok(ConfluenceIngestResult {
+            namespace_id: namespace_id.to_string(),
+            total_pages,
+            ingested,
+            skipped,
+            removed,
+            errors,
+            documents,
+        })
+    }
+
+    /// Ingest one page with source/run tracking.
+    /// Returns `Ok(None)` if the page version is unchanged (skipped).
+    async fn ingest_page(
+        &self,
+        db: &Database,
+        space_key: &str,
+        page: &SpacePage,
+        namespace_id: &str,
+    ) -> IngestResult<Option<IngestedDocument>> {
+        let source_uri = self.client.page_uri(&page.id);
+        let now = chrono::Utc::now().to_rfc3339();
+        let version = page.version.to_string();
+
+        let existing = db.find_ingest_source(CONFLUENCE_SOURCE_TYPE, &source_uri, namespace_id)?;
+        if let Some(existing) = &existing {
+            if existing.etag.as_deref() == Some(version.as_str()) {
+                // Version unchanged — record a no-op run and skip
+                let run_id = db.create_ingest_run(&existing.id)?;
+                db.complete_ingest_run(IngestRunCompletion {
+                    run_id: &run_id,
+                    status: "completed",
+                    docs_added: 0,
+                    docs_updated: 0,
+                    docs_removed: 0,
+                    chunks_added: 0,
+                    error_message: None,
+                })?;
+                return Ok(None);
+            }
+        }
+
+        let (fetched_version, storage) = self
+            .client
+            .page_storage(&page.id)
+            .await
+            .map_err(ingest_error)?;
+        let content_hash = format!("{:x}", Sha256::digest(storage.as_bytes()));
+
+        let is_new = existing.is_none();
+        let mut source = existing.unwrap_or_else(|| IngestSource {
+            id: uuid::Uuid::new_v4().to_string(),
+            source_type: CONFLUENCE_SOURCE_TYPE.to_string(),
+            source_uri: source_uri.clone(),
+            namespace_id: namespace_id.to_string(),
+            title: None,
+            etag: None,
+            last_modified: None,
+            content_hash: None,
+            last_ingested_at: None,
+            status: "active".to_string(),
+            error_message: None,
+            metadata_json: None,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+        });
+        source.title = Some(page.title.clone());
+        source.content_hash = Some(content_hash.clone());
+        source.last_ingested_at = Some(now.clone());
+        source.status = "active".to_string();
+        source.error_message = None;
+        source.metadata_json =
+            Some(serde_json::json!({ "space_key": space_key, "page_id": page.id }).to_string());
+        source.updated_at = now.clone();
+        db.save_ingest_source(&source)?;
+
+        let run_id = db.create_ingest_run(&source.id)?;
+
+        let mut parsed = KbIndexer::parse_markdown_text(&storage_to_markdown(&storage));
+        parsed.title = Some(page.title.clone());
+        parsed.source_url = page.url.clone().or_else(|| Some(source_uri.clone()));
+
+        let chunks = self.indexer.chunk_document(&parsed);
+        let chunk_count = chunks.len();
+        let word_count: usize = chunks.iter().map(|c| c.word_count).sum();
+
+        // Delete existing documents for this source (handles re-ingestion)
+        db.delete_documents_for_source(&source.id)?;
+
+        // The version is recorded only once the page is indexed, so a page
+        // that fails part-way is fetched again next time
+        source.etag = Some(fetched_version.max(page.version).to_string());
+
+        if chunks.is_empty() {
+            db.save_ingest_source(&source)?;
+            db.complete_ingest_run(IngestRunCompletion {
+                run_id: &run_id,
+                status: "completed",
+                docs_added: 0,
+                docs_updated: 0,
+                docs_removed: 0,
+                chunks_added: 0,
+                error_message: None,
+            })?;
+            return Ok(None);
+        }
+
+        let doc_id = uuid::Uuid::new_v4().to_string();
+        db.conn().execute(
+            "INSERT INTO kb_documents (id, file_path, file_hash, title, indexed_at, chunk_count,
+                    namespace_id, source_type, source_id, source_url)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            rusqlite::params![
+                doc_id,
+                source_uri,
+                content_hash,
+                page.title,
+                now,
+                chunk_count as i32,
+                namespace_id,
+                CONFLUENCE_SOURCE_TYPE,
+                source.id,
+                parsed.source_url,
+            ],
+        )?;
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            let chunk_id = uuid::Uuid::new_v4().to_string();
+            db.conn().execute(
+                "INSERT INTO kb_chunks (id, document_id, chunk_index, heading_path, content, word_count, namespace_id)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                rusqlite::params![
+                    chunk_id,
+                    doc_id,
+                    i as i32,
+                    chunk.heading_path,
+                    chunk.content,
+                    chunk.word_count as i32,
+                    namespace_id,
+                ],
+            )?;
+        }
+
+        db.save_ingest_source(&source)?;
+
+        let (docs_added, docs_updated) = if is_new { (1, 0) } else { (0, 1) };
+        db.complete_ingest_run(IngestRunCompletion {
+            run_id: &run_id,
+            status: "completed",
+            docs_added,
+            docs_updated,
+            docs_removed: 0,
+            chunks_added: chunk_count as i32,
+            error_message: None,
+        })?;
+
+        Ok(Some(IngestedDocument {
+            id: doc_id,
+            title: page.title.clone(),
+            source_uri,
+            chunk_count,
+            word_count,
+        }))
+    }
+
+    /// Remove pages ingested earlier from this space that are no longer listed
+    fn remove_deleted_pages(
+        &self,
+        db: &Database,
+        space_key: &str,
+        pages: &[SpacePage],
+        namespace_id: &str,
+    ) -> IngestResult<usize> {
+        let listed: std::collections::HashSet<String> =
+            pages.iter().map(|p| self.client.page_uri(&p.id)).collect();
+
+        let mut removed = 0;
+        for source in db.list_ingest_sources(Some(namespace_id))? {
+            if source.source_type != CONFLUENCE_SOURCE_TYPE || listed.contains(&source.source_uri) {
+                continue;
+            }
+            let in_space = source
+                .metadata_json
+                .as_deref()
+                .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+                .is_some_and(|m| m["space_key"].as_str() == Some(space_key));
+            if !in_space {
+                continue;
+            }
+            db.delete_documents_for_source(&source.id)?;
+            db.delete_ingest_source(&source.id)?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
+
+/// Decode HTML named entities, which storage format uses but XML does not define
+fn html_entity(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "amp" => "&",
+        "lt" => "<",
+        "gt" => ">",
+        "quot" => "\"",
+        "apos" => "'",
+        "nbsp" => " ",
+        "ndash" => "–",
+        "mdash" => "—",
+        "hellip" => "…",
+        "lsquo" => "‘",
+        "rsquo" => "’",
+        "ldquo" => "“",
+        "rdquo" => "”",
+        "bull" => "•",
+        "middot" => "·",
+        "rarr" => "→",
+        "larr" => "←",
+        "times" => "×",
+        "copy" => "©",
+        "reg" => "®",
+        "trade" => "™",
+        _ => return None,
+    })
+}
+
+fn attr(element: &BytesStart, name: &str) -> Option<String> {
+    let attr = element.try_get_attribute(name).ok().flatten()?;
+    Some(
+        attr.unescape_value()
+            .map(|v| v.into_owned())
+            .unwrap_or_else(|_| String::from_utf8_lossy(&attr.value).into_owned()),
+    )
+}
+
+/// Elements whose text is never shown: macro parameters, task metadata
+const HIDDEN_ELEMENTS: &[&[u8]] = &[
+    b"ac:parameter",
+    b"ac:task-id",
+    b"ac:task-status",
+    b"ac:placeholder",
+    b"style",
+    b"script",
+];
+
+/// Admonition macros and the label their body is introduced with
+const PANEL_MACROS: &[(&str, &str)] = &[
+    ("info", "Info"),
+    ("note", "Note"),
+    ("tip", "Tip"),
+    ("warning", "Warning"),
+];
+
+/// Streaming markdown writer for storage-format events
+#[derive(Default)]
+struct MarkdownWriter {
+    out: String,
+    /// Open lists: whether ordered, and the next item number
+    lists: Vec<(bool, usize)>,
+    /// Open links: where their text starts, and the target to show
+    links: Vec<(usize, Option<String>)>,
+    /// Open `ac:link`s: where their text starts, and the linked page title
+    page_links: Vec<(usize, Option<String>)>,
+    /// Open macros by name, with the code language when given
+    macros: Vec<(String, Option<String>)>,
+    /// Text of the code macro `language` parameter being read
+    language: Option<String>,
+    row: Option<Vec<String>>,
+    cell: Option<String>,
+    /// Rows written in the current table
+    table_rows: usize,
+    header_row: bool,
+    hidden_depth: usize,
+    pre_depth: usize,
+    in_heading: bool,
+    /// A list item marker was written and no text has followed yet
+    item_open: bool,
+}
+
+impl MarkdownWriter {
+    fn target(&mut self) -> &mut String {
+        match self.cell.as_mut() {
+            Some(cell) => cell,
+            None => &mut self.out,
+        }
+    }
+
+    /// End the current block with a blank line (a line break inside lists)
+    fn block_break(&mut self) {
+        if self.item_open {
+            return;
+        }
+        if !self.lists.is_empty() && self.cell.is_none() {
+            self.line_break();
+            return;
+        }
+        if let Some(cell) = self.cell.as_mut() {
+            if !cell.is_empty() && !cell.ends_with(' ') {
+                cell.push(' ');
+            }
+            return;
+        }
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+        if self.out.is_empty() {
+            return;
+        }
+        while !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn line_break(&mut self) {
+        if self.cell.is_some() || self.in_heading {
+            self.text(" ");
+        } else if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    /// Append text, collapsing whitespace outside preformatted blocks
+    fn text(&mut self, text: &str) {
+        if self.hidden_depth > 0 {
+            return;
+        }
+        if self.pre_depth > 0 {
+            self.target().push_str(text);
+            return;
+        }
+        let in_cell = self.cell.is_some();
+        let target = self.target();
+        let mut pending_space = text.starts_with(char::is_whitespace);
+        let mut wrote = false;
+        for word in text.split_whitespace() {
+            let at_line_start = target.is_empty() || target.ends_with(['\n', ' ']);
+            if pending_space && !at_line_start {
+                target.push(' ');
+            }
+            if in_cell {
+                target.push_str(&word.replace('|', "\\|"));
+            } else {
+                target.push_str(word);
+            }
+            pending_space = true;
+            wrote = true;
+        }
+        if wrote {
+            if text.ends_with(char::is_whitespace) {
+                target.push(' ');
+            }
+            self.item_open = false;
+        } else if !text.is_empty() && !target.is_empty() && !target.ends_with([' ', '\n']) {
+            target.push(' ');
+        }
+    }
+
+    /// Close an inline marker like `**`, keeping trailing spaces outside it
+    fn close_inline(&mut self, marker: &str) {
+        let target = self.target();
+        let trimmed = target.trim_end_matches(' ').len();
+        let had_space = trimmed < target.len();
+        target.truncate(trimmed);
+        target.push_str(marker);
+        if had_space {
+            target.push(' ');
+        }
+    }
+
+    fn start(&mut self, e: &BytesStart) {
+        let name = e.name();
+        let name = name.as_ref();
+        if HIDDEN_ELEMENTS.contains(&name) {
+            if name == b"ac:parameter"
+                && attr(e, "ac:name").as_deref() == Some("language")
+                && self.macros.last().is_some()
+            {
+                self.language = Some(String::new());
+            }
+            self.hidden_depth += 1;
+            return;
+        }
+        if self.hidden_depth > 0 {
+            return;
+        }
+
+        match name {
+            b"p" | b"div" | b"blockquote" => self.block_break(),
+            b"h1" | b"h2" | b"h3" | b"h4" | b"h5" | b"h6" => {
+                self.block_break();
+                let level = (name[1] - b'0') as usize;
+                self.out.push_str(&"#".repeat(level));
+                self.out.push(' ');
+                self.in_heading = true;
+            }
+            b"br" => self.line_break(),
+            b"hr" => {
+                self.block_break();
+                self.out.push_str("---");
+                self.block_break();
+            }
+            b"strong" | b"b" if !self.in_heading => self.target().push_str("**"),
+            b"em" | b"i" if !self.in_heading => self.target().push_str("_"),
+            b"code" if self.pre_depth == 0 => self.target().push('`'),
+            b"pre" => {
+                self.block_break();
+                self.out.push_str("```\n");
+                self.pre_depth += 1;
+            }
+            b"ul" | b"ol" | b"ac:task-list" => {
+                if self.lists.is_empty() {
+                    self.block_break();
+                }
+                self.lists.push((name == b"ol", 1));
+            }
+            b"li" | b"ac:task" => {
+                let depth = self.lists.len().max(1);
+                let marker = match self.lists.last_mut() {
+                    Some((true, number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.line_break();
+                self.out.push_str(&"  ".repeat(depth - 1));
+                self.out.push_str(&marker);
+                self.item_open = true;
+            }
+            b"a" => {
+                let start = self.target().len();
+                self.links
+                    .push((start, attr(e, "href").filter(|h| !h.is_empty())));
+            }
+            b"ac:link" => {
+                let start = self.target().len();
+                self.page_links.push((start, None));
+            }
+            b"ri:page" | b"ri:attachment" => {
+                let title = attr(e, "ri:content-title").or_else(|| attr(e, "ri:filename"));
+                if let Some(link) = self.page_links.last_mut() {
+                    link.1 = title;
+                }
+            }
+            b"time" => {
+                if let Some(datetime) = attr(e, "datetime") {
+                    self.text(&format!(" {} ", datetime));
+                }
+            }
+            b"table" => {
+                self.block_break();
+                self.table_rows = 0;
+            }
+            b"tr" => {
+                self.row = Some(Vec::new());
+                self.header_row = true;
+            }
+            b"th" | b"td" => {
+                if name == b"td" {
+                    self.header_row = false;
+                }
+                self.cell = Some(String::new());
+            }
+            b"ac:structured-macro" => {
+                let macro_name = attr(e, "ac:name").unwrap_or_default();
+                if let Some((_, label)) = PANEL_MACROS.iter().find(|(m, _)| *m == macro_name) {
+                    self.block_break();
+                    self.out.push_str(&format!("**{}:**", label));
+                    self.block_break();
+                }
+                self.macros.push((macro_name, None));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &[u8]) {
+        if HIDDEN_ELEMENTS.contains(&name) {
+            self.hidden_depth = self.hidden_depth.saturating_sub(1);
+            if let Some(language) = self.language.take() {
+                if let Some(open) = self.macros.last_mut() {
+                    open.1 = Some(language.trim().to_string()).filter(|l| !l.is_empty());
+                }
+            }
+            return;
+        }
+        if self.hidden_depth > 0 {
+            return;
+        }
+
+        match name {
+            b"p" | b"div" | b"blockquote" => self.block_break(),
+            b"h1" | b"h2" | b"h3" | b"h4" | b"h5" | b"h6" => {
+                self.in_heading = false;
+                self.block_break();
+            }
+            b"strong" | b"b" if !self.in_heading => self.close_inline("**"),
+            b"em" | b"i" if !self.in_heading => self.close_inline("_"),
+            b"code" if self.pre_depth == 0 => self.close_inline("`"),
+            b"pre" => {
+                self.pre_depth = self.pre_depth.saturating_sub(1);
+                if !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                self.out.push_str("```");
+                self.block_break();
+            }
+            b"ul" | b"ol" | b"ac:task-list" => {
+                self.lists.pop();
+                self.item_open = false;
+                if self.lists.is_empty() {
+                    self.block_break();
+                }
+            }
+            b"li" | b"ac:task" => self.item_open = false,
+            b"a" => {
+                if let Some((start, href)) = self.links.pop() {
+                    let target = self.target();
+                    let start = start.min(target.len());
+                    let has_text = !target[start..].trim().is_empty();
+                    match href {
+                        Some(href) if has_text => {
+                            target.insert(start, '[');
+                            self.close_inline(&format!("]({})", href));
+                        }
+                        Some(href) => {
+                            target.truncate(start);
+                            target.push_str(&href);
+                        }
+                        None => {}
+                    }
+                }
+            }
+            b"ac:link" => {
+                if let Some((start, title)) = self.page_links.pop() {
+                    let target = self.target();
+                    let start = start.min(target.len());
+                    if target[start..].trim().is_empty() {
+                        if let Some(title) = title {
+                            target.truncate(start);
+                            self.text(&title);
+                        }
+                    }
+                }
+            }
+            b"th" | b"td" => {
+                if let (Some(cell), Some(row)) = (self.cell.take(), self.row.as_mut()) {
+                    row.push(cell.trim().to_string());
+                }
+            }
+            b"tr" => {
+                if let Some(row) = self.row.take() {
+                    if row.is_empty() {
+                        return;
+                    }
+                    self.out.push_str(&format!("| {} |\n", row.join(" | ")));
+                    if self.table_rows == 0 && self.header_row {
+                        let separator = vec!["---"; row.len()].join(" | ");
+                        self.out.push_str(&format!("| {} |\n", separator));
+                    }
+                    self.table_rows += 1;
+                }
+            }
+            b"table" => self.block_break(),
+            b"ac:structured-macro" => {
+                self.macros.pop();
+            }
+            _ => {}
+        }
+    }
+
+    /// `ac:plain-text-body` content: a fenced block for code macros
+    fn cdata(&mut self, text: &str) {
+        if self.hidden_depth > 0 {
+            if let Some(language) = self.language.as_mut() {
+                language.push_str(text);
+            }
+            return;
+        }
+        match self.macros.last() {
+            Some((name, language)) if name == "code" || name == "noformat" => {
+                let language = language.clone().unwrap_or_default();
+                self.block_break();
+                self.out
+                    .push_str(&format!("```{}\n{}\n```", language, text.trim_end()));
+                self.block_break();
+            }
+            _ => self.text(text),
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut markdown = String::with_capacity(self.out.len());
+        let mut blank_lines = 0;
+        for line in self.out.lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                blank_lines += 1;
+                if blank_lines > 1 {
+                    continue;
+                }
+            } else {
+                blank_lines = 0;
+            }
+            markdown.push_str(line);
+            markdown.push('\n');
+        }
+        markdown.trim().to_string()
+    }
+}
+
+/// Convert Confluence storage format to markdown.
+/// Headings, lists, tables, links and code macros are kept; macro
+/// parameters and task metadata are dropped. Malformed markup ends the
+/// conversion at that point instead of failing the page.
+pub fn storage_to_markdown(storage: &str) -> String {
+    let mut reader = Reader::from_str(storage);
+    reader.config_mut().check_end_names = false;
+    let mut writer = MarkdownWriter::default();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => writer.start(&e),
+            Ok(Event::Empty(e)) => {
+                writer.start(&e);
+                writer.end(e.name().as_ref());
+            }
+            Ok(Event::End(e)) => writer.end(e.name().as_ref()),
+            Ok(Event::Text(e)) => {
+                let text = e
+                    .unescape_with(html_entity)
+                    .map(|t| t.into_owned())
+                    .unwrap_or_else(|_| String::from_utf8_lossy(&e).into_owned());
+                if let Some(language) = writer.language.as_mut() {
+                    language.push_str(&text);
+                } else {
+                    writer.text(&text);
+                }
+            }
+            Ok(Event::CData(e)) => writer.cdata(&String::from_utf8_lossy(&e)),
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                tracing::warn!("Stopped converting Confluence page: {}", e);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_to_markdown_structure() {
+        let storage = r#"<h1>VPN&nbsp;Setup</h1>
+<p>Install the <strong>GlobalProtect </strong>client &amp; sign in.</p>
+<ac:structured-macro ac:name="info" ac:schema-version="1"><ac:parameter ac:name="title">Heads up</ac:parameter><ac:rich-text-body><p>Requires MFA.</p></ac:rich-text-body></ac:structured-macro>
+<h2>Steps</h2>
+<ol><li>Open the app</li><li><p>Enter <code>vpn.acme.com</code></p><ul><li>Use SSO</li></ul></li></ol>
+<p>See <a href="https://kb.acme.com/mfa">MFA guide</a> and <ac:link><ri:page ri:content-title="Token Reset" /></ac:link>.</p>"#;
+
+        assert_eq!(
+            storage_to_markdown(storage),
+            "# VPN Setup\n\n\
+             Install the **GlobalProtect** client & sign in.\n\n\
+             **Info:**\n\n\
+             Requires MFA.\n\n\
+             ## Steps\n\n\
+             1. Open the app\n\
+             2. Enter `vpn.acme.com`\n\
+             \x20\x20- Use SSO\n\n\
+             See [MFA guide](https://kb.acme.com/mfa) and Token Reset."
+        );
+    }
+
+    #[test]
+    fn test_storage_to_markdown_code_macro_and_table() {
+        let storage = r#"<ac:structured-macro ac:name="code"><ac:parameter ac:name="language">bash</ac:parameter><ac:plain-text-body><![CDATA[sudo systemctl restart cups
+lpstat -p]]></ac:plain-text-body></ac:structured-macro>
+<table><tbody><tr><th>Printer</th><th>Floor</th></tr><tr><td>HP 400 | color</td><td>3</td></tr></tbody></table>
+<ac:task-list><ac:task><ac:task-id>1</ac:task-id><ac:task-status>incomplete</ac:task-status><ac:task-body>Label toner</ac:task-body></ac:task></ac:task-list>"#;
+
+        assert_eq!(
+            storage_to_markdown(storage),
+            "```bash\nsudo systemctl restart cups\nlpstat -p\n```\n\n\
+             | Printer | Floor |\n\
+             | --- | --- |\n\
+             | HP 400 \\| color | 3 |\n\n\
+             - Label toner"
+        );
+    }
+
+    #[test]
+    fn test_storage_to_markdown_sections_index_under_headings() {
+        let markdown = storage_to_markdown(
+            "<h1>Printers</h1><p>Shared printers by floor.</p><h2>Jams</h2><p>Open tray 2.</p>",
+        );
+        let parsed = KbIndexer::parse_markdown_text(&markdown);
+        assert_eq!(parsed.title.as_deref(), Some("Printers"));
+        let chunks = KbIndexer::new().chunk_document(&parsed);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].heading_path.as_deref(), Some("Printers > Jams"));
+        assert_eq!(chunks[1].content, "Open tray 2.");
+    }
+
+    #[test]
+    fn test_storage_to_markdown_stops_at_malformed_markup() {
+        assert_eq!(storage_to_markdown("<p>Reboot first</p><p"), "Reboot first");
+    }
+
+    #[test]
+    fn test_space_namespace() {
+        assert_eq!(space_namespace("IT").unwrap(), "confluence-it");
+        assert_eq!(space_namespace("~jdoe").unwrap(), "confluence-jdoe");
+    }
+}
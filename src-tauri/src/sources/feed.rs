@@ -0,0 +1,659 @@
+//! RSS/Atom feed connector
+//!
+//! A registered feed is an ingest source (`source_type = "feed"`) whose URI is
+//! the feed URL. Each poll fetches the feed and indexes entries whose GUID has
+//! not been seen from that feed before, one document per entry, so vendor
+//! status pages and security advisories accumulate without re-indexing.
+//! Polling runs from a `poll_feeds` job schedule.
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::db::{Database, FeedEntryRecord, IngestRunCompletion, IngestSource};
+use crate::kb::html::extract_html;
+use crate::kb::indexer::{KbIndexer, ParsedDocument};
+use crate::kb::ingest::web::{FetchedPage, WebIngestConfig, WebIngester};
+use crate::kb::ingest::{IngestError, IngestResult, IngestedDocument};
+
+/// Ingest source type for feeds
+pub const FEED_SOURCE_TYPE: &str = "feed";
+
+/// Content types accepted when fetching a feed (RSS, Atom, RDF and plain XML)
+const FEED_CONTENT_TYPES: &[&str] = &["xml"];
+
+/// Entries considered per poll; feeds list their newest entries first
+const MAX_ENTRIES_PER_POLL: usize = 200;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FeedError {
+    #[error("Invalid feed XML: {0}")]
+    Xml(String),
+    #[error("Not an RSS or Atom feed (root element <{0}>)")]
+    NotAFeed(String),
+}
+
+/// A parsed RSS 2.0, RSS 1.0 (RDF) or Atom feed
+#[derive(Debug, Clone, Default)]
+pub struct Feed {
+    pub title: Option<String>,
+    pub entries: Vec<FeedEntry>,
+}
+
+/// One feed item/entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedEntry {
+    /// RSS `guid`, Atom `id` or RDF `rdf:about`; falls back to the link, then
+    /// a hash of the title, date and content
+    pub guid: String,
+    pub title: Option<String>,
+    pub link: Option<String>,
+    /// RFC 3339 when the feed's date could be parsed, as given otherwise
+    pub published: Option<String>,
+    /// HTML or plain text body (full content preferred over the summary)
+    pub content: String,
+}
+
+/// Result of polling one feed
+#[derive(Debug, Clone)]
+pub struct FeedPollResult {
+    pub source_id: String,
+    pub title: Option<String>,
+    pub total_entries: usize,
+    pub ingested: usize,
+    /// Entries ingested by an earlier poll
+    pub skipped: usize,
+    pub errors: usize,
+    pub documents: Vec<IngestedDocument>,
+}
+
+/// A fetched and parsed feed, ready to index
+#[derive(Debug, Clone)]
+pub struct FetchedFeed {
+    pub page: FetchedPage,
+    pub feed: Feed,
+}
+
+/// Build a new feed source for `url` in `namespace_id`
+pub fn feed_source(url: &str, namespace_id: &str, metadata_json: Option<String>) -> IngestSource {
+    let now = chrono::Utc::now().to_rfc3339();
+    IngestSource {
+        id: uuid::Uuid::new_v4().to_string(),
+        source_type: FEED_SOURCE_TYPE.to_string(),
+        source_uri: url.to_string(),
+        namespace_id: namespace_id.to_string(),
+        title: None,
+        etag: None,
+        last_modified: None,
+        content_hash: None,
+        last_ingested_at: None,
+        status: "pending".to_string(),
+        error_message: None,
+        metadata_json,
+        created_at: now.clone(),
+        updated_at: now,
+    }
+}
+
+/// Polls feed sources and indexes their new entries
+pub struct FeedIngester {
+    web: WebIngester,
+    indexer: KbIndexer,
+}
+
+impl FeedIngester {
+    pub async fn new() -> IngestResult<Self> {
+        Ok(Self {
+            web: WebIngester::new(WebIngestConfig::default()).await?,
+            indexer: KbIndexer::new(),
+        })
+    }
+
+    /// Fetch and parse `source`'s feed without touching the database
+    pub async fn fetch(&self, source: &IngestSource) -> IngestResult<FetchedFeed> {
+        let page = self
+            .web
+            .fetch_document(&source.source_uri, FEED_CONTENT_TYPES)
+            .await?;
+        let feed = parse_feed(&page.content).map_err(|e| IngestError::Parse(e.to_string()))?;
+        Ok(FetchedFeed { page, feed })
+    }
+
+    /// Record a feed that could not be fetched or parsed: the source is
+    /// marked as errored and a failed ingest run is logged
+    pub fn record_fetch_failure(
+        db: &Database,
+        source: &IngestSource,
+        error: &IngestError,
+    ) -> IngestResult<()> {
+        let message = error.to_string();
+        let run_id = db.create_ingest_run(&source.id)?;
+        db.update_ingest_source_status(&source.id, "error", Some(&message))?;
+        db.complete_ingest_run(IngestRunCompletion {
+            run_id: &run_id,
+            status: "failed",
+            docs_added: 0,
+            docs_updated: 0,
+            docs_removed: 0,
+            chunks_added: 0,
+            error_message: Some(&message),
+        })?;
+        Ok(())
+    }
+
+    /// Ingest the entries of a fetched feed not seen before into the source's
+    /// namespace. A failing entry is counted in `errors` without stopping the
+    /// rest.
+    pub fn index(
+        &self,
+        db: &Database,
+        source: &IngestSource,
+        fetched: FetchedFeed,
+    ) -> IngestResult<FeedPollResult> {
+        let FetchedFeed { page, feed } = fetched;
+        let run_id = db.create_ingest_run(&source.id)?;
+
+        let entries: Vec<&FeedEntry> = feed.entries.iter().take(MAX_ENTRIES_PER_POLL).collect();
+        let mut ingested = 0;
+        let mut skipped = 0;
+        let mut errors = 0;
+        let mut documents = Vec::new();
+
+        // Oldest first, so documents are indexed in publication order
+        for entry in entries.iter().rev() {
+            match db.has_feed_entry(&source.id, &entry.guid) {
+                Ok(true) => {
+                    skipped += 1;
+                    continue;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::warn!("Failed to check feed entry {}: {}", entry.guid, e);
+                    errors += 1;
+                    continue;
+                }
+            }
+            match self.ingest_entry(db, source, entry) {
+                Ok(doc) => {
+                    ingested += 1;
+                    documents.extend(doc);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to ingest feed entry {}: {}", entry.guid, e);
+                    errors += 1;
+                }
+            }
+        }
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut updated = source.clone();
+        updated.title = feed.title.clone().or(updated.title);
+        updated.etag = page.etag;
+        updated.last_modified = page.last_modified;
+        updated.last_ingested_at = Some(now.clone());
+        updated.status = "active".to_string();
+        updated.error_message = None;
+        updated.updated_at = now;
+        db.save_ingest_source(&updated)?;
+
+        db.complete_ingest_run(IngestRunCompletion {
+            run_id: &run_id,
+            status: "completed",
+            docs_added: documents.len() as i32,
+            docs_updated: 0,
+            docs_removed: 0,
+            chunks_added: documents.iter().map(|d| d.chunk_count as i32).sum(),
+            error_message: None,
+        })?;
+
+        Ok(FeedPollResult {
+            source_id: source.id.clone(),
+            title: updated.title,
+            total_entries: entries.len(),
+            ingested,
+            skipped,
+            errors,
+            documents,
+        })
+    }
+
+    /// Index one entry as a document and record its GUID.
+    /// Returns `Ok(None)` for an entry without indexable text.
+    fn ingest_entry(
+        &self,
+        db: &Database,
+        source: &IngestSource,
+        entry: &FeedEntry,
+    ) -> IngestResult<Option<IngestedDocument>> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let title = entry
+            .title
+            .clone()
+            .or_else(|| entry.link.clone())
+            .unwrap_or_else(|| entry.guid.clone());
+
+        let parsed = ParsedDocument {
+            title: Some(title.clone()),
+            sections: extract_html(&entry.content).sections,
+            source_url: entry.link.clone(),
+        };
+        let chunks = self.indexer.chunk_document(&parsed);
+
+        let mut record = FeedEntryRecord {
+            source_id: source.id.clone(),
+            guid: entry.guid.clone(),
+            document_id: None,
+            title: entry.title.clone(),
+            link: entry.link.clone(),
+            published_at: entry.published.clone(),
+            ingested_at: now.clone(),
+        };
+        if chunks.is_empty() {
+            db.record_feed_entry(&record)?;
+            return Ok(None);
+        }
+
+        // GUIDs are unique per feed, not across feeds
+        let source_uri = format!("feed://{}/{}", source.id, entry.guid);
+        let content_hash = format!("{:x}", Sha256::digest(entry.content.as_bytes()));
+        let chunk_count = chunks.len();
+        let word_count: usize = chunks.iter().map(|c| c.word_count).sum();
+
+        let doc_id = uuid::Uuid::new_v4().to_string();
+        db.conn().execute(
+            "INSERT INTO kb_documents (id, file_path, file_hash, title, indexed_at, chunk_count,
+                    namespace_id, source_type, source_id, source_url)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            rusqlite::params![
+                doc_id,
+                source_uri,
+                content_hash,
+                title,
+                now,
+                chunk_count as i32,
+                source.namespace_id,
+                FEED_SOURCE_TYPE,
+                source.id,
+                entry.link,
+            ],
+        )?;
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            let chunk_id = uuid::Uuid::new_v4().to_string();
+            db.conn().execute(
+                "INSERT INTO kb_chunks (id, document_id, chunk_index, heading_path, content, word_count, namespace_id)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                rusqlite::params![
+                    chunk_id,
+                    doc_id,
+                    i as i32,
+                    chunk.heading_path,
+                    chunk.content,
+                    chunk.word_count as i32,
+                    source.namespace_id,
+                ],
+            )?;
+        }
+
+        record.document_id = Some(doc_id.clone());
+        db.record_feed_entry(&record)?;
+
+        Ok(Some(IngestedDocument {
+            id: doc_id,
+            title,
+            source_uri,
+            chunk_count,
+            word_count,
+        }))
+    }
+}
+
+/// Entry fields collected while parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    FeedTitle,
+    Title,
+    Link,
+    Id,
+    Published,
+    Updated,
+    Summary,
+    Content,
+}
+
+#[derive(Debug, Default)]
+struct RawEntry {
+    id: Option<String>,
+    title: Option<String>,
+    link: Option<String>,
+    published: Option<String>,
+    updated: Option<String>,
+    summary: Option<String>,
+    content: Option<String>,
+}
+
+impl RawEntry {
+    fn set(&mut self, field: Field, value: String) {
+        let slot = match field {
+            Field::FeedTitle => return,
+            Field::Title => &mut self.title,
+            Field::Link => &mut self.link,
+            Field::Id => &mut self.id,
+            Field::Published => &mut self.published,
+            Field::Updated => &mut self.updated,
+            Field::Summary => &mut self.summary,
+            Field::Content => &mut self.content,
+        };
+        if slot.is_none() && !value.is_empty() {
+            *slot = Some(value);
+        }
+    }
+
+    fn finish(self) -> Option<FeedEntry> {
+        let content = self.content.or(self.summary).unwrap_or_default();
+        let published = self.published.or(self.updated).map(|d| normalize_date(&d));
+        let guid = match self.id.clone().or_else(|| self.link.clone()) {
+            Some(guid) => guid,
+            None if self.title.is_none() && content.is_empty() => return None,
+            None => {
+                let mut hasher = Sha256::new();
+                for part in [
+                    self.title.as_deref().unwrap_or_default(),
+                    published.as_deref().unwrap_or_default(),
+                    &content,
+                ] {
+                    hasher.update(part.as_bytes());
+                    hasher.update([0]);
+                }
+                format!("sha256:{:x}", hasher.finalize())
+            }
+        };
+        Some(FeedEntry {
+            guid,
+            title: self.title,
+            link: self.link,
+            published,
+            content,
+        })
+    }
+}
+
+/// Which entry field an element inside an item/entry holds
+fn entry_field(name: &str) -> Option<Field> {
+    Some(match name {
+        "title" => Field::Title,
+        "link" => Field::Link,
+        "guid" | "id" => Field::Id,
+        "pubDate" | "published" | "date" | "issued" => Field::Published,
+        "updated" | "modified" => Field::Updated,
+        "description" | "summary" => Field::Summary,
+        // `content:encoded` in RSS, `content` in Atom
+        "encoded" | "content" => Field::Content,
+        _ => return None,
+    })
+}
+
+fn local_name(e: &BytesStart) -> String {
+    String::from_utf8_lossy(e.local_name().as_ref()).into_owned()
+}
+
+fn attribute(e: &BytesStart, name: &str) -> Option<String> {
+    let attr = e.try_get_attribute(name).ok()??;
+    attr.unescape_value().ok().map(|v| v.trim().to_string())
+}
+
+/// The `href` of an Atom `<link>` that points at the entry itself
+fn atom_link(e: &BytesStart) -> Option<String> {
+    let rel = attribute(e, "rel");
+    if rel.is_none() || rel.as_deref() == Some("alternate") {
+        attribute(e, "href").filter(|href| !href.is_empty())
+    } else {
+        None
+    }
+}
+
+/// Parse an RSS 2.0, RSS 1.0 (RDF) or Atom document
+pub fn parse_feed(xml: &str) -> Result<Feed, FeedError> {
+    let mut reader = Reader::from_str(xml);
+    let mut feed = Feed::default();
+    let mut stack: Vec<String> = Vec::new();
+    // Depth of the open item/entry element
+    let mut entry: Option<(usize, RawEntry)> = None;
+    // Field being collected and the depth of its element
+    let mut field: Option<(usize, Field)> = None;
+    let mut text = String::new();
+    let mut has_root = false;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| FeedError::Xml(e.to_string()))?;
+        match event {
+            Event::Start(e) => {
+                let name = local_name(&e);
+                if stack.is_empty() {
+                    if has_root || !matches!(name.as_str(), "rss" | "RDF" | "feed") {
+                        return Err(FeedError::NotAFeed(name));
+                    }
+                    has_root = true;
+                }
+                stack.push(name.clone());
+                let depth = stack.len();
+
+                if field.is_some() {
+                    // Markup inside an Atom `type="xhtml"` body is kept as HTML
+                    text.push_str(&format!("<{}>", name));
+                } else if let Some((entry_depth, raw)) = entry.as_mut() {
+                    if depth == *entry_depth + 1 {
+                        if let Some(f) = entry_field(&name) {
+                            if f == Field::Link {
+                                raw.link = raw.link.take().or_else(|| atom_link(&e));
+                            }
+                            field = Some((depth, f));
+                            text.clear();
+                        }
+                    }
+                } else if name == "item" || name == "entry" {
+                    let raw = RawEntry {
+                        id: attribute(&e, "rdf:about"),
+                        ..Default::default()
+                    };
+                    entry = Some((depth, raw));
+                } else if name == "title"
+                    && depth >= 2
+                    && matches!(stack[depth - 2].as_str(), "channel" | "feed")
+                {
+                    field = Some((depth, Field::FeedTitle));
+                    text.clear();
+                }
+            }
+            Event::Empty(e) => {
+                let name = local_name(&e);
+                if field.is_some() {
+                    text.push_str(&format!("<{} />", name));
+                } else if let Some((entry_depth, raw)) = entry.as_mut() {
+                    if name == "link" && stack.len() == *entry_depth && raw.link.is_none() {
+                        raw.link = atom_link(&e);
+                    }
+                }
+            }
+            Event::Text(t) => {
+                if let Some((field_depth, _)) = field {
+                    if stack.len() == field_depth {
+                        let unescaped = t
+                            .unescape()
+                            .map(|c| c.into_owned())
+                            .unwrap_or_else(|_| String::from_utf8_lossy(&t).into_owned());
+                        text.push_str(&unescaped);
+                    } else {
+                        // Still escaped, which is what the HTML body needs
+                        text.push_str(&String::from_utf8_lossy(&t));
+                    }
+                }
+            }
+            Event::CData(t) => {
+                if field.is_some() {
+                    text.push_str(&String::from_utf8_lossy(&t));
+                }
+            }
+            Event::End(_) => {
+                let depth = stack.len();
+                let name = stack.pop().unwrap_or_default();
+                match field {
+                    Some((field_depth, f)) if field_depth == depth => {
+                        let value = text.trim().to_string();
+                        text.clear();
+                        field = None;
+                        match (f, entry.as_mut()) {
+                            (Field::FeedTitle, _) => {
+                                if feed.title.is_none() && !value.is_empty() {
+                                    feed.title = Some(value);
+                                }
+                            }
+                            (f, Some((_, raw))) => raw.set(f, value),
+                            _ => {}
+                        }
+                    }
+                    Some(_) => text.push_str(&format!("</{}>", name)),
+                    None => {
+                        if entry.as_ref().is_some_and(|(d, _)| *d == depth) {
+                            if let Some(parsed) = entry.take().and_then(|(_, raw)| raw.finish()) {
+                                feed.entries.push(parsed);
+                            }
+                        }
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !has_root {
+        return Err(FeedError::Xml("No root element".to_string()));
+    }
+    Ok(feed)
+}
+
+/// Convert RFC 2822 (RSS) and RFC 3339 (Atom) dates to RFC 3339 UTC
+fn normalize_date(value: &str) -> String {
+    let value = value.trim();
+    chrono::DateTime::parse_from_rfc2822(value)
+        .or_else(|_| chrono::DateTime::parse_from_rfc3339(value))
+        .map(|d| d.with_timezone(&chrono::Utc).to_rfc3339())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rss() {
+        let xml = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+  <channel>
+    <title>Example Status</title>
+    <link>https://status.example.com</link>
+    <item>
+      <title>Degraded SSO &amp; MFA</title>
+      <link>https://status.example.com/incidents/42</link>
+      <guid isPermaLink="false">incident-42</guid>
+      <pubDate>Tue, 13 Oct 2026 14:05:00 GMT</pubDate>
+      <description>&lt;p&gt;Short summary&lt;/p&gt;</description>
+      <content:encoded><![CDATA[<p>Users may see <b>MFA</b> prompts twice.</p>]]></content:encoded>
+    </item>
+    <item>
+      <title>Scheduled maintenance</title>
+      <description>Database upgrade</description>
+    </item>
+  </channel>
+</rss>"#;
+        let feed = parse_feed(xml).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Example Status"));
+        assert_eq!(feed.entries.len(), 2);
+
+        let first = &feed.entries[0];
+        assert_eq!(first.guid, "incident-42");
+        assert_eq!(first.title.as_deref(), Some("Degraded SSO & MFA"));
+        assert_eq!(
+            first.link.as_deref(),
+            Some("https://status.example.com/incidents/42")
+        );
+        assert_eq!(
+            first.published.as_deref(),
+            Some("2026-10-13T14:05:00+00:00")
+        );
+        assert_eq!(
+            first.content,
+            "<p>Users may see <b>MFA</b> prompts twice.</p>"
+        );
+
+        // No guid or link: a stable hash stands in
+        let second = &feed.entries[1];
+        assert!(second.guid.starts_with("sha256:"));
+        assert_eq!(second.content, "Database upgrade");
+        assert_eq!(parse_feed(xml).unwrap().entries[1].guid, second.guid);
+    }
+
+    #[test]
+    fn test_parse_atom() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title type="text">Vendor Security Advisories</title>
+  <link href="https://vendor.example.com/advisories" />
+  <entry>
+    <title>VSA-2026-007: VPN client privilege escalation</title>
+    <link rel="replies" href="https://vendor.example.com/comments/7" />
+    <link href="https://vendor.example.com/advisories/7" />
+    <id>urn:uuid:7d0c7b1e-advisory-7</id>
+    <updated>2026-10-12T09:00:00+02:00</updated>
+    <summary>Upgrade to 5.2.1.</summary>
+    <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><p>Affects 5.0 &lt; 5.2.1</p><br/></div></content>
+  </entry>
+</feed>"#;
+        let feed = parse_feed(xml).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Vendor Security Advisories"));
+        let entry = &feed.entries[0];
+        assert_eq!(entry.guid, "urn:uuid:7d0c7b1e-advisory-7");
+        assert_eq!(
+            entry.link.as_deref(),
+            Some("https://vendor.example.com/advisories/7")
+        );
+        assert_eq!(
+            entry.published.as_deref(),
+            Some("2026-10-12T07:00:00+00:00")
+        );
+        assert_eq!(
+            entry.content,
+            "<div><p>Affects 5.0 &lt; 5.2.1</p><br /></div>"
+        );
+    }
+
+    #[test]
+    fn test_parse_rdf_uses_about_as_guid() {
+        let xml = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns="http://purl.org/rss/1.0/">
+  <channel rdf:about="https://example.com/"><title>Notices</title></channel>
+  <item rdf:about="https://example.com/notices/1">
+    <title>Notice 1</title>
+    <link>https://example.com/notices/1</link>
+  </item>
+</rdf:RDF>"#;
+        let feed = parse_feed(xml).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Notices"));
+        assert_eq!(feed.entries[0].guid, "https://example.com/notices/1");
+    }
+
+    #[test]
+    fn test_parse_rejects_non_feeds() {
+        assert_eq!(
+            parse_feed("<html><body>Hi</body></html>").unwrap_err(),
+            FeedError::NotAFeed("html".to_string())
+        );
+        assert!(matches!(
+            parse_feed("<rss><channel><item></channel></rss>"),
+            Err(FeedError::Xml(_))
+        ));
+    }
+}
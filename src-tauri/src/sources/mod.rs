@@ -1,6 +1,9 @@
 //! Source definition module for AssistSupport
-//! Handles YAML source file parsing for batch content ingestion
+//! Handles YAML source file parsing for batch content ingestion and
+//! connectors for hosted knowledge sources (Confluence, RSS/Atom feeds)
 
+pub mod confluence;
+pub mod feed;
 pub mod parser;
 
 pub use parser::{ParseError, SourceDefinition, SourceFile, SourceType};
@@ -0,0 +1,241 @@
+//! Support bundle export
+//!
+//! Collects recent logs, system health, model/config info and anonymized
+//! error summaries into a single ZIP for attaching to support requests.
+//! Every file goes through `scrub_pii` before it is written.
+
+use crate::exports::{apply_safe_export, SafeExportOptions};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+/// Most recent audit entries included in the bundle
+pub const MAX_AUDIT_ENTRIES: usize = 500;
+
+/// Most recently modified log files included in the bundle
+pub const MAX_LOG_FILES: usize = 5;
+
+/// Bytes kept from the end of each log file
+pub const MAX_LOG_TAIL_BYTES: u64 = 512 * 1024;
+
+/// Patterns scrubbed in addition to emails, usernames, paths and IDs
+const SECRET_PATTERNS: &[&str] = &[
+    // IPv4 addresses
+    r"\b\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}\b",
+    // Bearer tokens and well-known token prefixes
+    r"(?i)bearer\s+[A-Za-z0-9._~+/-]+=*",
+    r"\b(?:hf_|ghp_|gho_|github_pat_|xox[abprs]-)[A-Za-z0-9_-]{10,}",
+    // Credentials in query strings
+    r"(?i)[?&](?:token|key|api_key|password|secret)=[^&\s]+",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum SupportBundleError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("ZIP error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+}
+
+/// Summary of a written bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct SupportBundleSummary {
+    pub path: String,
+    pub files: Vec<String>,
+    pub size_bytes: u64,
+}
+
+/// Errors with the same source and (scrubbed) message, grouped
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ErrorSummary {
+    /// Where the error came from (e.g. "job:ingest_web", "audit:decryption_failed")
+    pub source: String,
+    pub message: String,
+    pub count: usize,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Replace personal data in free text: the OS username, email addresses,
+/// @handles, file paths, UUIDs, IP addresses and token-like strings
+pub fn scrub_pii(text: &str) -> String {
+    let mut text = text.to_string();
+    if let Some(user) = dirs::home_dir()
+        .and_then(|home| home.file_name().map(|n| n.to_string_lossy().to_string()))
+        .filter(|user| user.len() >= 3)
+    {
+        text = text.replace(&user, "[user]");
+    }
+
+    let options = SafeExportOptions {
+        strip_usernames: true,
+        strip_internal_ids: true,
+        strip_file_paths: true,
+        strip_emails: true,
+        custom_patterns: SECRET_PATTERNS.iter().map(|p| p.to_string()).collect(),
+        pseudonymize: false,
+    };
+    apply_safe_export(&text, &options)
+}
+
+/// Group errors by source and scrubbed message, most frequent first
+pub fn summarize_errors(
+    errors: impl IntoIterator<Item = (String, String, DateTime<Utc>)>,
+) -> Vec<ErrorSummary> {
+    let mut groups: HashMap<(String, String), ErrorSummary> = HashMap::new();
+    for (source, message, at) in errors {
+        let message = scrub_pii(&message);
+        groups
+            .entry((source.clone(), message.clone()))
+            .and_modify(|summary| {
+                summary.count += 1;
+                summary.last_seen = summary.last_seen.max(at);
+            })
+            .or_insert(ErrorSummary {
+                source,
+                message,
+                count: 1,
+                last_seen: at,
+            });
+    }
+
+    let mut summaries: Vec<ErrorSummary> = groups.into_values().collect();
+    summaries.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| b.last_seen.cmp(&a.last_seen))
+    });
+    summaries
+}
+
+/// Read up to `max_bytes` from the end of a file, starting at a line boundary
+pub fn tail_file(path: &Path, max_bytes: u64) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+
+    let text = String::from_utf8_lossy(&bytes);
+    if start == 0 {
+        return Ok(text.into_owned());
+    }
+    // Drop the partial first line
+    Ok(text
+        .split_once('\n')
+        .map(|(_, rest)| rest.to_string())
+        .unwrap_or_default())
+}
+
+/// Write `files` (name, content) to a ZIP at `path`, scrubbing each one
+pub fn write_bundle(
+    path: &Path,
+    files: &[(String, String)],
+) -> Result<SupportBundleSummary, SupportBundleError> {
+    let file = File::create(path)?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for (name, content) in files {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(scrub_pii(content).as_bytes())?;
+    }
+    zip.finish()?;
+
+    Ok(SupportBundleSummary {
+        path: path.display().to_string(),
+        files: files.iter().map(|(name, _)| name.clone()).collect(),
+        size_bytes: std::fs::metadata(path)?.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub_pii() {
+        let scrubbed = scrub_pii(
+            "Failed for jane.doe@example.com at /Users/someone/Documents/kb/vpn.md \
+             from 10.1.2.3 with Bearer abc.def-123 (job 123e4567-e89b-12d3-a456-426614174000) \
+             token hf_abcdefghijklmnop url https://host/api?token=s3cret&page=2",
+        );
+        assert!(!scrubbed.contains("jane.doe"));
+        assert!(!scrubbed.contains("/Users/someone"));
+        assert!(!scrubbed.contains("10.1.2.3"));
+        assert!(!scrubbed.contains("abc.def-123"));
+        assert!(!scrubbed.contains("123e4567"));
+        assert!(!scrubbed.contains("hf_abcdefghijklmnop"));
+        assert!(!scrubbed.contains("s3cret"));
+        assert!(scrubbed.contains("[email]"));
+        assert!(scrubbed.contains("page=2"));
+    }
+
+    #[test]
+    fn test_summarize_errors() {
+        let at = |h| {
+            DateTime::parse_from_rfc3339(&format!("2026-03-01T{:02}:00:00Z", h))
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let summaries = summarize_errors(vec![
+            (
+                "job:ingest_web".to_string(),
+                "Timeout fetching 10.0.0.1".to_string(),
+                at(1),
+            ),
+            (
+                "job:ingest_web".to_string(),
+                "Timeout fetching 10.0.0.2".to_string(),
+                at(3),
+            ),
+            ("job:backup".to_string(), "Disk full".to_string(), at(2)),
+        ]);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].source, "job:ingest_web");
+        assert_eq!(summaries[0].count, 2);
+        assert_eq!(summaries[0].last_seen, at(3));
+        assert_eq!(summaries[1].message, "Disk full");
+    }
+
+    #[test]
+    fn test_tail_file_and_bundle() {
+        let dir =
+            std::env::temp_dir().join(format!("assistsupport-bundle-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let log = dir.join("app.log");
+        std::fs::write(&log, "first line\nsecond line\nthird line\n").unwrap();
+        assert_eq!(
+            tail_file(&log, 1024).unwrap(),
+            "first line\nsecond line\nthird line\n"
+        );
+        assert_eq!(tail_file(&log, 15).unwrap(), "third line\n");
+
+        let path = dir.join("bundle.zip");
+        let summary = write_bundle(
+            &path,
+            &[(
+                "errors.json".to_string(),
+                "{\"email\": \"a.b@example.org\"}".to_string(),
+            )],
+        )
+        .unwrap();
+        assert_eq!(summary.files, vec!["errors.json"]);
+
+        let mut archive = zip::ZipArchive::new(File::open(&path).unwrap()).unwrap();
+        let mut content = String::new();
+        archive
+            .by_name("errors.json")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "{\"email\": \"[email]\"}");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -28,10 +28,23 @@ import type {
   ChecklistItem,
   ChecklistState,
   FirstResponseTone,
+  MemoryKernelPolicyGateResult,
   SearchResult,
 } from '../../types';
 import './DraftTab.css';
 
+/** MemoryKernel citations saved with a draft; null when the policy gate fell back */
+function policyCitationsJson(policy: MemoryKernelPolicyGateResult): string | null {
+  if (!policy.applied) return null;
+  return JSON.stringify({
+    decision: policy.decision,
+    ask_context_package_id: policy.ask_context_package_id,
+    recall_context_package_id: policy.recall_context_package_id,
+    constraints: policy.constraints,
+    related: policy.related,
+  });
+}
+
 export interface DraftTabHandle {
   generate: () => void;
   loadDraft: (draft: SavedDraft) => void;
@@ -82,6 +95,7 @@ export const DraftTab = forwardRef<DraftTabHandle, DraftTabProps>(function Draft
   const [firstResponse, setFirstResponse] = useState('');
   const [firstResponseTone, setFirstResponseTone] = useState<FirstResponseTone>('slack');
   const [firstResponseGenerating, setFirstResponseGenerating] = useState(false);
+  const [policyCitations, setPolicyCitations] = useState<string | null>(null);
   const [approvalQuery, setApprovalQuery] = useState('');
   const [approvalResults, setApprovalResults] = useState<SearchResult[]>([]);
   const [approvalSearching, setApprovalSearching] = useState(false);
@@ -198,7 +212,12 @@ export const DraftTab = forwardRef<DraftTabHandle, DraftTabProps>(function Draft
         ocr_text: ocrText ?? undefined,
         jira_ticket: currentTicket ?? undefined,
       });
+      if (result.refused) {
+        showError(result.policy.message);
+        return;
+      }
       setFirstResponse(result.text);
+      setPolicyCitations(policyCitationsJson(result.policy));
     } catch (e) {
       console.error('First response generation failed:', e);
       showError(`First response failed: ${e}`);
@@ -219,6 +238,7 @@ export const DraftTab = forwardRef<DraftTabHandle, DraftTabProps>(function Draft
 
   const handleClearFirstResponse = useCallback(() => {
     setFirstResponse('');
+    setPolicyCitations(null);
   }, []);
 
   const handleChecklistGenerate = useCallback(async () => {
@@ -504,6 +524,7 @@ export const DraftTab = forwardRef<DraftTabHandle, DraftTabProps>(function Draft
     setFirstResponse('');
     setFirstResponseTone('slack');
     setFirstResponseGenerating(false);
+    setPolicyCitations(null);
     setApprovalQuery('');
     setApprovalResults([]);
     setApprovalSummary('');
@@ -613,6 +634,7 @@ export const DraftTab = forwardRef<DraftTabHandle, DraftTabProps>(function Draft
     setOriginalResponse(loadedResponse);
     setIsResponseEdited(false);
     setSavedDraftId(draft.id);
+    setPolicyCitations(draft.policy_citations_json ?? null);
     if (draft.diagnosis_json) {
       try {
         const diagData = JSON.parse(draft.diagnosis_json);
@@ -771,13 +793,14 @@ export const DraftTab = forwardRef<DraftTabHandle, DraftTabProps>(function Draft
       kb_sources_json: sources.length > 0 ? JSON.stringify(sources) : null,
       is_autosave: false,
       model_name: loadedModelName,
+      policy_citations_json: policyCitations,
     });
 
     if (draftId) {
       setSavedDraftId(draftId);
       showSuccess('Draft saved');
     }
-  }, [input, buildDiagnosisJson, response, currentTicketId, sources, saveDraft, showError, showSuccess, loadedModelName]);
+  }, [input, buildDiagnosisJson, response, currentTicketId, sources, saveDraft, showError, showSuccess, loadedModelName, policyCitations]);
 
   // Load initial draft if provided
   useEffect(() => {
@@ -813,12 +836,13 @@ export const DraftTab = forwardRef<DraftTabHandle, DraftTabProps>(function Draft
         ticket_id: currentTicketId,
         kb_sources_json: sources.length > 0 ? JSON.stringify(sources) : null,
         model_name: loadedModelName,
+        policy_citations_json: policyCitations,
       });
     }
     return () => {
       cancelAutosave();
     };
-  }, [input, buildDiagnosisJson, response, currentTicketId, sources, loadedModelName, triggerAutosave, cancelAutosave]);
+  }, [input, buildDiagnosisJson, response, currentTicketId, sources, loadedModelName, policyCitations, triggerAutosave, cancelAutosave]);
 
   const handleCopyResponse = useCallback(async () => {
     if (!response) return;
@@ -102,6 +102,8 @@ function getSourceTypeLabel(sourceType: string | null): string {
     case 'url': return 'URL';
     case 'youtube': return 'YouTube';
     case 'github': return 'GitHub';
+    case 'confluence': return 'Confluence';
+    case 'feed': return 'Feed';
     default: return sourceType;
   }
 }
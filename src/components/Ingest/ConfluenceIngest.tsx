@@ -0,0 +1,86 @@
+import { useState } from 'react';
+import { useIngest } from '../../hooks/useIngest';
+import { Button } from '../shared/Button';
+
+interface ConfluenceIngestProps {
+  namespaceId: string;
+  onSuccess: (message: string) => void;
+  onError: (message: string) => void;
+}
+
+export function ConfluenceIngest({ namespaceId, onSuccess, onError }: ConfluenceIngestProps) {
+  const { ingestConfluence, ingesting } = useIngest();
+  const [spaceKey, setSpaceKey] = useState('');
+  const [useSpaceNamespace, setUseSpaceNamespace] = useState(true);
+
+  const handleIngest = async () => {
+    if (!spaceKey.trim()) return;
+
+    try {
+      const result = await ingestConfluence(
+        spaceKey.trim(),
+        useSpaceNamespace ? undefined : namespaceId
+      );
+      const parts = [`${result.ingested} updated`, `${result.skipped} unchanged`];
+      if (result.removed > 0) parts.push(`${result.removed} removed`);
+      if (result.errors > 0) parts.push(`${result.errors} failed`);
+      onSuccess(`Ingested ${result.total_pages} pages into ${result.namespace_id} (${parts.join(', ')})`);
+      setSpaceKey('');
+    } catch (e) {
+      onError(`Failed to ingest Confluence space: ${e}`);
+    }
+  };
+
+  return (
+    <div className="ingest-form">
+      <div className="ingest-form-header">
+        <h3>Confluence Space</h3>
+        <p>Ingest every current page of a Confluence space using the connection configured in Settings.</p>
+      </div>
+
+      <div className="ingest-form-field">
+        <label htmlFor="confluence-space">Space Key</label>
+        <input
+          id="confluence-space"
+          type="text"
+          placeholder="ITKB"
+          value={spaceKey}
+          onChange={(e) => setSpaceKey(e.target.value)}
+          onKeyDown={(e) => e.key === 'Enter' && handleIngest()}
+          disabled={ingesting}
+        />
+      </div>
+
+      <div className="ingest-form-field">
+        <label>
+          <input
+            type="checkbox"
+            checked={useSpaceNamespace}
+            onChange={(e) => setUseSpaceNamespace(e.target.checked)}
+            disabled={ingesting}
+          />
+          {' '}Use the space's own namespace (confluence-&lt;space&gt;) instead of the selected one
+        </label>
+      </div>
+
+      <div className="ingest-form-info">
+        <ul>
+          <li>Requires Confluence to be enabled with an API token in Settings</li>
+          <li>Re-ingesting only re-indexes pages whose version changed</li>
+          <li>Pages deleted from the space are removed from the knowledge base</li>
+          <li>Page bodies are converted to markdown and split by heading</li>
+        </ul>
+      </div>
+
+      <div className="ingest-form-actions">
+        <Button
+          variant="primary"
+          onClick={handleIngest}
+          disabled={!spaceKey.trim() || ingesting}
+        >
+          {ingesting ? 'Ingesting...' : 'Ingest Space'}
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,160 @@
+import { useState, useEffect, useCallback } from 'react';
+import { useIngest } from '../../hooks/useIngest';
+import { Button } from '../shared/Button';
+import type { FeedInfo } from '../../types';
+
+interface FeedIngestProps {
+  namespaceId: string;
+  onSuccess: (message: string) => void;
+  onError: (message: string) => void;
+}
+
+const POLL_INTERVALS = [
+  { label: 'Every 15 minutes', cron: '*/15 * * * *' },
+  { label: 'Hourly', cron: '0 * * * *' },
+  { label: 'Every 6 hours', cron: '0 */6 * * *' },
+  { label: 'Daily', cron: '0 6 * * *' },
+];
+
+export function FeedIngest({ namespaceId, onSuccess, onError }: FeedIngestProps) {
+  const { listFeeds, addFeed, pollFeed, removeFeed, ingesting } = useIngest();
+  const [feeds, setFeeds] = useState<FeedInfo[]>([]);
+  const [url, setUrl] = useState('');
+  const [cron, setCron] = useState('0 * * * *');
+
+  const loadFeeds = useCallback(async () => {
+    try {
+      setFeeds(await listFeeds(namespaceId));
+    } catch (e) {
+      onError(`Failed to load feeds: ${e}`);
+    }
+  }, [listFeeds, namespaceId, onError]);
+
+  useEffect(() => {
+    loadFeeds();
+  }, [loadFeeds]);
+
+  const handlePoll = async (sourceId: string) => {
+    try {
+      const result = await pollFeed(sourceId);
+      const name = result.title ?? 'feed';
+      const failed = result.errors > 0 ? `, ${result.errors} failed` : '';
+      onSuccess(`Ingested ${result.ingested} new entries from ${name} (${result.skipped} already ingested${failed})`);
+    } catch (e) {
+      onError(`Failed to poll feed: ${e}`);
+    }
+    await loadFeeds();
+  };
+
+  const handleAdd = async () => {
+    if (!url.trim()) return;
+
+    try {
+      const feed = await addFeed(url.trim(), namespaceId, cron);
+      setUrl('');
+      await handlePoll(feed.source.id);
+    } catch (e) {
+      onError(`Failed to add feed: ${e}`);
+    }
+  };
+
+  const handleRemove = async (sourceId: string) => {
+    try {
+      await removeFeed(sourceId);
+      onSuccess('Feed removed');
+      await loadFeeds();
+    } catch (e) {
+      onError(`Failed to remove feed: ${e}`);
+    }
+  };
+
+  return (
+    <div className="ingest-form">
+      <div className="ingest-form-header">
+        <h3>RSS/Atom Feeds</h3>
+        <p>Follow vendor status pages and security advisories. New entries are ingested into the selected namespace on a schedule.</p>
+      </div>
+
+      <div className="ingest-form-field">
+        <label htmlFor="feed-url">Feed URL</label>
+        <input
+          id="feed-url"
+          type="text"
+          placeholder="https://status.example.com/history.rss"
+          value={url}
+          onChange={(e) => setUrl(e.target.value)}
+          onKeyDown={(e) => e.key === 'Enter' && handleAdd()}
+          disabled={ingesting}
+        />
+      </div>
+
+      <div className="ingest-form-field">
+        <label htmlFor="feed-interval">Check for new entries</label>
+        <select
+          id="feed-interval"
+          value={cron}
+          onChange={(e) => setCron(e.target.value)}
+          disabled={ingesting}
+        >
+          {POLL_INTERVALS.map((interval) => (
+            <option key={interval.cron} value={interval.cron}>
+              {interval.label}
+            </option>
+          ))}
+        </select>
+      </div>
+
+      <div className="ingest-form-actions">
+        <Button
+          variant="primary"
+          onClick={handleAdd}
+          disabled={!url.trim() || ingesting}
+        >
+          {ingesting ? 'Ingesting...' : 'Add Feed'}
+        </Button>
+      </div>
+
+      {feeds.length > 0 && (
+        <div className="ingest-form-field">
+          <label>Feeds in this namespace</label>
+          {feeds.map((feed) => (
+            <div key={feed.source.id} className="token-actions">
+              <span>{feed.source.title ?? feed.source.source_uri}</span>
+              <span className="token-status">
+                {feed.source.status === 'error'
+                  ? `Error: ${feed.source.error_message ?? 'unknown'}`
+                  : feed.source.last_ingested_at
+                    ? `Checked ${new Date(feed.source.last_ingested_at).toLocaleString()}`
+                    : 'Not checked yet'}
+              </span>
+              <Button
+                variant="secondary"
+                size="small"
+                onClick={() => handlePoll(feed.source.id)}
+                disabled={ingesting}
+              >
+                Check Now
+              </Button>
+              <Button
+                variant="secondary"
+                size="small"
+                onClick={() => handleRemove(feed.source.id)}
+                disabled={ingesting}
+              >
+                Remove
+              </Button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      <div className="ingest-form-info">
+        <ul>
+          <li>Supports RSS 2.0, RSS 1.0 (RDF) and Atom feeds</li>
+          <li>Each entry becomes one document; entries are never ingested twice</li>
+          <li>Removing a feed also removes the documents ingested from it</li>
+        </ul>
+      </div>
+    </div>
+  );
+}
@@ -5,10 +5,12 @@ import { Button } from '../shared/Button';
 import { UrlIngest } from './UrlIngest';
 import { YouTubeIngest } from './YouTubeIngest';
 import { GitHubIngest } from './GitHubIngest';
+import { ConfluenceIngest } from './ConfluenceIngest';
+import { FeedIngest } from './FeedIngest';
 import { BatchIngest } from './BatchIngest';
 import './IngestTab.css';
 
-type IngestMode = 'url' | 'youtube' | 'github' | 'batch';
+type IngestMode = 'url' | 'youtube' | 'github' | 'confluence' | 'feed' | 'batch';
 
 export function IngestTab() {
   const {
@@ -142,6 +144,20 @@ export function IngestTab() {
             >
               GitHub
             </button>
+            <button
+              className={`mode-btn ${mode === 'confluence' ? 'active' : ''}`}
+              onClick={() => setMode('confluence')}
+              disabled={ingesting}
+            >
+              Confluence
+            </button>
+            <button
+              className={`mode-btn ${mode === 'feed' ? 'active' : ''}`}
+              onClick={() => setMode('feed')}
+              disabled={ingesting}
+            >
+              Feeds
+            </button>
             <button
               className={`mode-btn ${mode === 'batch' ? 'active' : ''}`}
               onClick={() => setMode('batch')}
@@ -176,6 +192,20 @@ export function IngestTab() {
             onError={handleIngestError}
           />
         )}
+        {mode === 'confluence' && (
+          <ConfluenceIngest
+            namespaceId={selectedNamespace}
+            onSuccess={handleIngestSuccess}
+            onError={handleIngestError}
+          />
+        )}
+        {mode === 'feed' && (
+          <FeedIngest
+            namespaceId={selectedNamespace}
+            onSuccess={handleIngestSuccess}
+            onError={handleIngestError}
+          />
+        )}
         {mode === 'batch' && (
           <BatchIngest
             onSuccess={handleIngestSuccess}
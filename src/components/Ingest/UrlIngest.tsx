@@ -8,9 +8,26 @@ interface UrlIngestProps {
   onError: (message: string) => void;
 }
 
+/** Split a textarea of patterns into one pattern per non-empty line */
+function parsePatterns(value: string): string[] {
+  return value
+    .split('\n')
+    .map((p) => p.trim())
+    .filter(Boolean);
+}
+
 export function UrlIngest({ namespaceId, onSuccess, onError }: UrlIngestProps) {
-  const { ingestUrl, ingesting } = useIngest();
+  const { ingestUrl, crawlUrl, ingesting } = useIngest();
   const [url, setUrl] = useState('');
+  const [crawl, setCrawl] = useState(false);
+  const [maxDepth, setMaxDepth] = useState(2);
+  const [maxPages, setMaxPages] = useState(50);
+  const [delayMs, setDelayMs] = useState(1000);
+  const [includePatterns, setIncludePatterns] = useState('');
+  const [excludePatterns, setExcludePatterns] = useState('');
+  const [starting, setStarting] = useState(false);
+
+  const busy = ingesting || starting;
 
   const handleIngest = async () => {
     if (!url.trim()) return;
@@ -21,6 +38,26 @@ export function UrlIngest({ namespaceId, onSuccess, onError }: UrlIngestProps) {
       return;
     }
 
+    if (crawl) {
+      setStarting(true);
+      try {
+        const jobId = await crawlUrl(url.trim(), namespaceId, {
+          max_depth: maxDepth,
+          max_pages: maxPages,
+          delay_ms: delayMs,
+          include_patterns: parsePatterns(includePatterns),
+          exclude_patterns: parsePatterns(excludePatterns),
+        });
+        onSuccess(`Started crawl job ${jobId} for ${url.trim()}. Pages appear in the knowledge base as they are indexed.`);
+        setUrl('');
+      } catch (e) {
+        onError(`Failed to start crawl: ${e}`);
+      } finally {
+        setStarting(false);
+      }
+      return;
+    }
+
     try {
       const result = await ingestUrl(url.trim(), namespaceId);
       onSuccess(`Ingested "${result.title}" (${result.chunk_count} chunks, ${result.word_count} words)`);
@@ -45,16 +82,102 @@ export function UrlIngest({ namespaceId, onSuccess, onError }: UrlIngestProps) {
           placeholder="https://example.com/docs/page"
           value={url}
           onChange={(e) => setUrl(e.target.value)}
-          onKeyDown={(e) => e.key === 'Enter' && !ingesting && handleIngest()}
-          disabled={ingesting}
+          onKeyDown={(e) => e.key === 'Enter' && !busy && handleIngest()}
+          disabled={busy}
         />
       </div>
 
+      <div className="ingest-form-field">
+        <label>
+          <input
+            type="checkbox"
+            checked={crawl}
+            onChange={(e) => setCrawl(e.target.checked)}
+            disabled={busy}
+          />
+          {' '}Crawl linked pages on the same site
+        </label>
+      </div>
+
+      {crawl && (
+        <>
+          <div className="ingest-form-field">
+            <label htmlFor="crawl-depth">Max Depth</label>
+            <input
+              id="crawl-depth"
+              type="number"
+              min={0}
+              max={5}
+              value={maxDepth}
+              onChange={(e) => setMaxDepth(Number(e.target.value))}
+              disabled={busy}
+            />
+          </div>
+
+          <div className="ingest-form-field">
+            <label htmlFor="crawl-pages">Max Pages</label>
+            <input
+              id="crawl-pages"
+              type="number"
+              min={1}
+              max={500}
+              value={maxPages}
+              onChange={(e) => setMaxPages(Number(e.target.value))}
+              disabled={busy}
+            />
+          </div>
+
+          <div className="ingest-form-field">
+            <label htmlFor="crawl-delay">Delay Between Requests (ms)</label>
+            <input
+              id="crawl-delay"
+              type="number"
+              min={0}
+              max={30000}
+              step={250}
+              value={delayMs}
+              onChange={(e) => setDelayMs(Number(e.target.value))}
+              disabled={busy}
+            />
+          </div>
+
+          <div className="ingest-form-field">
+            <label htmlFor="crawl-include">Include Patterns (one per line, optional)</label>
+            <textarea
+              id="crawl-include"
+              rows={2}
+              placeholder="/docs/*"
+              value={includePatterns}
+              onChange={(e) => setIncludePatterns(e.target.value)}
+              disabled={busy}
+            />
+          </div>
+
+          <div className="ingest-form-field">
+            <label htmlFor="crawl-exclude">Exclude Patterns (one per line, optional)</label>
+            <textarea
+              id="crawl-exclude"
+              rows={2}
+              placeholder="*/archive/*"
+              value={excludePatterns}
+              onChange={(e) => setExcludePatterns(e.target.value)}
+              disabled={busy}
+            />
+          </div>
+        </>
+      )}
+
       <div className="ingest-form-info">
         <ul>
           <li>Only public pages can be ingested</li>
           <li>Private/login-protected pages will return an error</li>
           <li>Large pages may take longer to process</li>
+          {crawl && (
+            <>
+              <li>Crawls follow links breadth-first and honor the site's robots.txt</li>
+              <li>Patterns use * as a wildcard and match the path, or the full URL when they include ://</li>
+            </>
+          )}
         </ul>
       </div>
 
@@ -62,9 +185,11 @@ export function UrlIngest({ namespaceId, onSuccess, onError }: UrlIngestProps) {
         <Button
           variant="primary"
           onClick={handleIngest}
-          disabled={!url.trim() || ingesting}
+          disabled={!url.trim() || busy}
         >
-          {ingesting ? 'Ingesting...' : 'Ingest Page'}
+          {crawl
+            ? (starting ? 'Starting...' : 'Start Crawl')
+            : (ingesting ? 'Ingesting...' : 'Ingest Page')}
         </Button>
       </div>
     </div>
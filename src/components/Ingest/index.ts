@@ -2,4 +2,6 @@ export { IngestTab } from './IngestTab';
 export { UrlIngest } from './UrlIngest';
 export { YouTubeIngest } from './YouTubeIngest';
 export { GitHubIngest } from './GitHubIngest';
+export { ConfluenceIngest } from './ConfluenceIngest';
+export { FeedIngest } from './FeedIngest';
 export { BatchIngest } from './BatchIngest';
@@ -92,6 +92,8 @@ export function KnowledgeBrowser() {
       case 'web': return '🌐';
       case 'youtube': return '🎬';
       case 'github': return '🐙';
+      case 'confluence': return '📘';
+      case 'feed': return '📰';
       default: return '📦';
     }
   };
@@ -15,8 +15,10 @@ import type {
   CustomVariable,
   DeploymentHealthSummary,
   IntegrationConfigRecord,
+  MemoryKernelConsent,
   ModelInfo,
   StartupMetricsResult,
+  TabularOptions,
 } from '../../types';
 import './SettingsTab.css';
 
@@ -112,6 +114,9 @@ export function SettingsTab() {
   const [kbFolder, setKbFolderState] = useState<string | null>(null);
   const [indexStats, setIndexStats] = useState<{ total_chunks: number; total_files: number } | null>(null);
   const [vectorEnabled, setVectorEnabled] = useState(false);
+  const [officeImageOcrEnabled, setOfficeImageOcrEnabled] = useState(false);
+  const [tabularForm, setTabularForm] = useState({ rowsPerChunk: '20', sheets: '', columns: '' });
+  const [memoryKernelDecisionsEnabled, setMemoryKernelDecisionsEnabled] = useState(false);
   const [jiraConfigured, setJiraConfigured] = useState(false);
   const [jiraForm, setJiraForm] = useState({ baseUrl: '', email: '', apiToken: '' });
   const [contextWindowSize, setContextWindowSize] = useState<number | null>(null);
@@ -157,6 +162,23 @@ export function SettingsTab() {
     invoke<StartupMetricsResult>('get_startup_metrics')
       .then(m => setStartupMetrics(m))
       .catch(() => {});
+    invoke<boolean>('get_kb_office_image_ocr')
+      .then(enabled => setOfficeImageOcrEnabled(enabled ?? false))
+      .catch(() => {});
+    invoke<TabularOptions>('get_kb_tabular_options')
+      .then(options => {
+        if (options) {
+          setTabularForm({
+            rowsPerChunk: String(options.rows_per_chunk),
+            sheets: options.sheets.join(', '),
+            columns: options.columns.join(', '),
+          });
+        }
+      })
+      .catch(() => {});
+    invoke<MemoryKernelConsent>('get_memory_kernel_consent')
+      .then(consent => setMemoryKernelDecisionsEnabled(consent?.record_decisions ?? false))
+      .catch(() => {});
   }, [loadVariables, loadAuditEntries]);
 
   async function loadInitialState() {
@@ -203,6 +225,42 @@ export function SettingsTab() {
     }
   }
 
+  async function handleOfficeImageOcrToggle() {
+    const newValue = !officeImageOcrEnabled;
+    try {
+      await invoke('set_kb_office_image_ocr', { enabled: newValue });
+      setOfficeImageOcrEnabled(newValue);
+    } catch (err) {
+      setError(`Failed to update embedded image OCR: ${err}`);
+    }
+  }
+
+  async function handleSaveTabularOptions(e: React.FormEvent) {
+    e.preventDefault();
+    const splitNames = (value: string) => value.split(',').map(s => s.trim()).filter(Boolean);
+    const options: TabularOptions = {
+      rows_per_chunk: Number(tabularForm.rowsPerChunk),
+      sheets: splitNames(tabularForm.sheets),
+      columns: splitNames(tabularForm.columns),
+    };
+    try {
+      await invoke('set_kb_tabular_options', { options });
+      showSuccess('Spreadsheet options saved. Rebuild the index to apply them.');
+    } catch (err) {
+      setError(`Failed to update spreadsheet options: ${err}`);
+    }
+  }
+
+  async function handleMemoryKernelDecisionsToggle() {
+    const newValue = !memoryKernelDecisionsEnabled;
+    try {
+      await invoke('set_memory_kernel_consent', { recordDecisions: newValue });
+      setMemoryKernelDecisionsEnabled(newValue);
+    } catch (err) {
+      setError(`Failed to update MemoryKernel consent: ${err}`);
+    }
+  }
+
   async function handleJiraConnect(e: React.FormEvent) {
     e.preventDefault();
     setError(null);
@@ -852,6 +910,59 @@ export function SettingsTab() {
               </Button>
             </div>
           )}
+
+          <label className="toggle-label">
+            <input
+              type="checkbox"
+              checked={officeImageOcrEnabled}
+              onChange={handleOfficeImageOcrToggle}
+            />
+            <span className="toggle-text">OCR images in Word and ODT documents</span>
+          </label>
+          <p className="setting-note">
+            Extracts text from screenshots embedded in DOCX and ODT files. Slows indexing.
+          </p>
+
+          <form className="tabular-form" onSubmit={handleSaveTabularOptions}>
+            <div className="form-field">
+              <label htmlFor="tabular-rows">Spreadsheet rows per chunk</label>
+              <input
+                id="tabular-rows"
+                type="number"
+                min={1}
+                max={200}
+                value={tabularForm.rowsPerChunk}
+                onChange={e => setTabularForm(f => ({ ...f, rowsPerChunk: e.target.value }))}
+                required
+              />
+            </div>
+            <div className="form-field">
+              <label htmlFor="tabular-sheets">Sheets to index</label>
+              <input
+                id="tabular-sheets"
+                type="text"
+                placeholder="All sheets"
+                value={tabularForm.sheets}
+                onChange={e => setTabularForm(f => ({ ...f, sheets: e.target.value }))}
+              />
+            </div>
+            <div className="form-field">
+              <label htmlFor="tabular-columns">Columns to index</label>
+              <input
+                id="tabular-columns"
+                type="text"
+                placeholder="All columns"
+                value={tabularForm.columns}
+                onChange={e => setTabularForm(f => ({ ...f, columns: e.target.value }))}
+              />
+              <p className="field-hint">
+                Comma-separated names for XLSX, CSV and TSV files. Every chunk repeats the column headers.
+              </p>
+            </div>
+            <Button type="submit" variant="secondary" size="small">
+              Save Spreadsheet Options
+            </Button>
+          </form>
         </div>
       </section>
 
@@ -876,6 +987,26 @@ export function SettingsTab() {
         </div>
       </section>
 
+      <section className="settings-section">
+        <h2>MemoryKernel</h2>
+        <p className="settings-description">
+          Record finalized drafts as decisions so organizational knowledge accumulates automatically.
+        </p>
+        <div className="vector-consent">
+          <label className="toggle-label">
+            <input
+              type="checkbox"
+              checked={memoryKernelDecisionsEnabled}
+              onChange={handleMemoryKernelDecisionsToggle}
+            />
+            <span className="toggle-text">Record finalized drafts in MemoryKernel</span>
+          </label>
+          <p className="setting-note">
+            Writes the draft summary, ticket ID, and finalizing agent to the local MemoryKernel service.
+          </p>
+        </div>
+      </section>
+
       <section className="settings-section">
         <h2>Template Variables</h2>
         <p className="settings-description">
@@ -5,6 +5,10 @@ import type {
   IngestSource,
   IngestResult,
   BatchIngestResult,
+  ConfluenceIngestResult,
+  CrawlOptions,
+  FeedInfo,
+  FeedPollResult,
   KbDocumentInfo,
   DocumentChunk,
   SourceHealthSummary,
@@ -211,6 +215,15 @@ export function useIngest() {
     }
   }, []);
 
+  // Crawl a site from a URL as a background job; resolves to the job ID
+  const crawlUrl = useCallback(async (
+    url: string,
+    namespaceId: string,
+    options?: Partial<CrawlOptions>
+  ): Promise<string> => {
+    return await invoke<string>('crawl_url', { url, namespaceId, options: options ?? null });
+  }, []);
+
   // Ingest a YouTube video
   const ingestYoutube = useCallback(async (
     url: string,
@@ -280,6 +293,54 @@ export function useIngest() {
     await invoke('clear_github_token', { host });
   }, []);
 
+  // Ingest a Confluence space; omitting namespaceId uses the space's own namespace
+  const ingestConfluence = useCallback(async (
+    spaceKey: string,
+    namespaceId?: string
+  ): Promise<ConfluenceIngestResult> => {
+    setState(prev => ({ ...prev, ingesting: true, error: null }));
+    try {
+      const result = await invoke<ConfluenceIngestResult>('ingest_confluence', {
+        spaceKey,
+        namespaceId: namespaceId ?? null,
+      });
+      setState(prev => ({ ...prev, ingesting: false }));
+      return result;
+    } catch (e) {
+      setState(prev => ({ ...prev, ingesting: false, error: String(e) }));
+      throw e;
+    }
+  }, []);
+
+  // RSS/Atom feeds polled on a schedule into a namespace
+  const listFeeds = useCallback(async (namespaceId?: string): Promise<FeedInfo[]> => {
+    return await invoke<FeedInfo[]>('list_feeds', { namespaceId: namespaceId ?? null });
+  }, []);
+
+  const addFeed = useCallback(async (
+    url: string,
+    namespaceId: string,
+    cron?: string
+  ): Promise<FeedInfo> => {
+    return await invoke<FeedInfo>('add_feed', { url, namespaceId, cron: cron ?? null });
+  }, []);
+
+  const pollFeed = useCallback(async (sourceId: string): Promise<FeedPollResult> => {
+    setState(prev => ({ ...prev, ingesting: true, error: null }));
+    try {
+      const result = await invoke<FeedPollResult>('poll_feed', { sourceId });
+      setState(prev => ({ ...prev, ingesting: false }));
+      return result;
+    } catch (e) {
+      setState(prev => ({ ...prev, ingesting: false, error: String(e) }));
+      throw e;
+    }
+  }, []);
+
+  const removeFeed = useCallback(async (sourceId: string): Promise<void> => {
+    await invoke('remove_feed', { sourceId });
+  }, []);
+
   // Process a YAML source file
   const processSourceFile = useCallback(async (
     filePath: string
@@ -348,12 +409,18 @@ export function useIngest() {
     clearKnowledgeData,
     checkYtdlp,
     ingestUrl,
+    crawlUrl,
     ingestYoutube,
     ingestGithub,
     ingestGithubRemote,
     hasGithubToken,
     setGithubToken,
     clearGithubToken,
+    ingestConfluence,
+    listFeeds,
+    addFeed,
+    pollFeed,
+    removeFeed,
     processSourceFile,
     getSourceHealth,
     retrySource,
@@ -36,13 +36,21 @@ interface IndexProgressStarted {
 interface IndexProgressProcessing {
   Processing: { current: number; total: number; file_name: string };
 }
+interface IndexProgressPageProcessed {
+  PageProcessed: { current: number; total: number; file_name: string; page: number; total_pages: number };
+}
 interface IndexProgressCompleted {
   Completed: { indexed: number; skipped: number; errors: number };
 }
 interface IndexProgressError {
   Error: { file_name: string; message: string };
 }
-type IndexProgressEvent = IndexProgressStarted | IndexProgressProcessing | IndexProgressCompleted | IndexProgressError;
+type IndexProgressEvent =
+  | IndexProgressStarted
+  | IndexProgressProcessing
+  | IndexProgressPageProcessed
+  | IndexProgressCompleted
+  | IndexProgressError;
 
 export function useKb() {
   const { logEvent } = useAnalytics();
@@ -85,6 +93,17 @@ export function useKb() {
             percentage: Math.round((current / total) * 100),
           },
         }));
+      } else if ('PageProcessed' in payload) {
+        const { current, total, file_name, page, total_pages } = payload.PageProcessed;
+        setState(prev => ({
+          ...prev,
+          indexingProgress: {
+            current,
+            total,
+            currentFile: `${file_name} (page ${page} of ${total_pages})`,
+            percentage: Math.round((current / total) * 100),
+          },
+        }));
       } else if ('Completed' in payload) {
         setState(prev => ({
           ...prev,
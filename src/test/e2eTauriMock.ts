@@ -238,6 +238,10 @@ export function setupE2eTauriMock(): void {
           return null;
         case 'get_vector_consent':
           return { enabled: false, consented_at: null, encryption_supported: true };
+        case 'get_kb_office_image_ocr':
+          return false;
+        case 'get_kb_tabular_options':
+          return { rows_per_chunk: 20, sheets: [], columns: [] };
         case 'get_kb_folder':
           return '/mock/kb';
         case 'get_kb_stats':
@@ -64,6 +64,45 @@ export interface MemoryKernelEnrichmentResult {
   preflight: MemoryKernelPreflightStatus;
 }
 
+export interface MemoryKernelConsent {
+  record_decisions: boolean;
+  consented_at: string | null;
+}
+
+export interface MemoryKernelDecisionRecordResult {
+  recorded: boolean;
+  status: 'recorded' | 'skipped' | 'fallback';
+  message: string;
+  fallback_reason: string | null;
+  machine_error_code: string | null;
+  memory_id: string | null;
+  memory_version_id: string | null;
+  preflight: MemoryKernelPreflightStatus | null;
+}
+
+export interface MemoryKernelPolicyCitation {
+  memory_version_id: string;
+  record_type: string;
+  rank: number;
+  reason: string | null;
+}
+
+export interface MemoryKernelPolicyGateResult {
+  applied: boolean;
+  status: 'allowed' | 'blocked' | 'fallback';
+  decision: string | null;
+  blocked: boolean;
+  message: string;
+  fallback_reason: string | null;
+  machine_error_code: string | null;
+  ask_context_package_id: string | null;
+  recall_context_package_id: string | null;
+  constraints: MemoryKernelPolicyCitation[];
+  related: MemoryKernelPolicyCitation[];
+  prompt_context: string | null;
+  preflight: MemoryKernelPreflightStatus;
+}
+
 // LLM types
 export interface ModelInfo {
   id: string;
@@ -197,6 +236,8 @@ export interface FirstResponseResult {
   text: string;
   tokens_generated: number;
   duration_ms: number;
+  refused: boolean;
+  policy: MemoryKernelPolicyGateResult;
 }
 
 export interface ChecklistItem {
@@ -267,6 +308,13 @@ export interface IndexStats {
   total_words: number;
 }
 
+/** Spreadsheet (XLSX/CSV/TSV) indexing options; empty lists mean all */
+export interface TabularOptions {
+  rows_per_chunk: number;
+  sheets: string[];
+  columns: string[];
+}
+
 export interface IndexResult {
   total_files: number;
   indexed: number;
@@ -484,6 +532,8 @@ export interface SavedDraft {
   is_autosave: boolean;
   /** Name of the model that generated this response (e.g., "Llama 3.2 3B Instruct") */
   model_name?: string | null;
+  /** MemoryKernel policy citations (JSON): context package ids and cited memory_version_ids */
+  policy_citations_json?: string | null;
 }
 
 export interface ResponseTemplate {
@@ -558,6 +608,56 @@ export interface IngestResult {
   word_count: number;
 }
 
+export interface ConfluenceIngestResult {
+  namespace_id: string;
+  total_pages: number;
+  ingested: number;
+  skipped: number;
+  removed: number;
+  errors: number;
+  documents: IngestResult[];
+}
+
+export interface JobSchedule {
+  id: string;
+  name: string;
+  cron: string;
+  job_type: string;
+  params: Record<string, unknown> | null;
+  enabled: boolean;
+  next_run_at: string | null;
+  last_run_at: string | null;
+  last_job_id: string | null;
+  last_status: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface FeedInfo {
+  source: IngestSource;
+  schedule: JobSchedule | null;
+}
+
+export interface CrawlOptions {
+  max_depth: number;
+  max_pages: number;
+  same_origin: boolean;
+  include_patterns: string[];
+  exclude_patterns: string[];
+  delay_ms: number;
+  respect_robots: boolean;
+}
+
+export interface FeedPollResult {
+  source_id: string;
+  title: string | null;
+  total_entries: number;
+  ingested: number;
+  skipped: number;
+  errors: number;
+  documents: IngestResult[];
+}
+
 export interface BatchIngestResult {
   successful: IngestResult[];
   failed: FailedSource[];